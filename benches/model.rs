@@ -4,11 +4,12 @@ use criterion::{black_box, criterion_group, criterion_main, Criterion};
 use crossbeam_channel::{Receiver, Sender};
 use nalgebra::{Matrix3, Matrix6, Translation3, UnitQuaternion, Vector3};
 use swerve_vehicle_descriptors::{
-    change_notification_processing::{ChangeID, HardwareChangeProcessor},
+    change_notification_processing::{ChangeID, ChangeNotifier, HardwareChangeProcessor},
     hardware::{
         actuator_interface::{ActuatorAvailableRatesOfChange, HardwareActuator},
         joint_state::{JointState, JointStateRange},
     },
+    kinematics::transform_cache::TransformCache,
     model_elements::{
         frame_elements::{Actuator, FrameDofType, FrameID, JointConstraint},
         model::{ChassisElementPhysicalProperties, MotionModel},
@@ -28,6 +29,8 @@ criterion_group! {
         motion_model_get_homogeneous_transform_to_ancestor,
         motion_model_get_homogeneous_transform_to_body,
         motion_model_get_homogeneous_transform_between_frames,
+        motion_model_transforms_to_body_all,
+        transform_cache_transforms_to_body,
 }
 
 criterion_main!(benches);
@@ -114,6 +117,23 @@ pub fn motion_model_is_ancestor(c: &mut Criterion) {
     });
 }
 
+pub fn motion_model_transforms_to_body_all(c: &mut Criterion) {
+    let model = create_model_and_fill();
+
+    c.bench_function("MotionModel::transforms_to_body_all", |b| {
+        b.iter(|| model.transforms_to_body_all());
+    });
+}
+
+pub fn transform_cache_transforms_to_body(c: &mut Criterion) {
+    let model = create_model_and_fill();
+    let mut cache = TransformCache::new();
+
+    c.bench_function("TransformCache::transforms_to_body", |b| {
+        b.iter(|| cache.transforms_to_body(black_box(&model)).map(|t| t.len()));
+    });
+}
+
 fn create_model_and_fill() -> MotionModel {
     let change_processor = Box::new(HardwareChangeProcessor::new(10));
 
@@ -218,7 +238,7 @@ struct MockHardwareActuator {
     receiver: Receiver<(JointState, ActuatorAvailableRatesOfChange)>,
     sender: Sender<(JointState, ActuatorAvailableRatesOfChange)>,
     command_sender: Sender<JointState>,
-    update_sender: Option<Sender<ChangeID>>,
+    update_sender: Option<ChangeNotifier>,
     id: Option<ChangeID>,
 }
 
@@ -237,7 +257,7 @@ impl HardwareActuator for MockHardwareActuator {
         Ok(self.command_sender.clone())
     }
 
-    fn on_change(&mut self, id: ChangeID, sender: Sender<ChangeID>) {
+    fn on_change(&mut self, id: ChangeID, sender: ChangeNotifier) {
         self.id = Some(id);
         self.update_sender = Some(sender);
     }
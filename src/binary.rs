@@ -0,0 +1,152 @@
+//! Provides a compact binary format for a [MotionModel], e.g. to store the description of the
+//! vehicle in the flash memory of the controller of the vehicle.
+//!
+//! The binary format contains the same data as the serde serialization of the model, including
+//! the calibrations and the transmissions of the joints, encoded with
+//! [postcard](https://docs.rs/postcard). The encoded model is preceded by a header that
+//! contains:
+//!
+//! * the magic bytes `SVD` followed by a zero byte,
+//! * the version of the format, as a little-endian 16-bit number,
+//! * the length of the encoded model in bytes, as a little-endian 32-bit number,
+//! * the CRC-32 checksum of the encoded model, as a little-endian 32-bit number.
+//!
+//! The version is incremented each time the layout of the encoded model changes. Data with a
+//! different version is rejected, so that a controller never loads a description that it
+//! interprets differently from the tool that wrote it.
+//!
+//! The binary format is only available when the `binary` feature is enabled.
+
+use crate::{model_elements::model::MotionModel, Error};
+
+#[cfg(test)]
+#[path = "binary_tests.rs"]
+mod binary_tests;
+
+/// The bytes at the start of the binary format.
+const MAGIC: [u8; 4] = *b"SVD\0";
+
+/// The version of the binary format that is written by [MotionModel::to_binary].
+pub const FORMAT_VERSION: u16 = 1;
+
+/// The length of the header, in bytes.
+const HEADER_LENGTH: usize = 14;
+
+/// The reversed polynomial of the CRC-32 checksum, as used by IEEE 802.3.
+const CRC32_POLYNOMIAL: u32 = 0xEDB8_8320;
+
+impl MotionModel {
+    /// Creates a [MotionModel] from the binary format that was created by
+    /// [MotionModel::to_binary].
+    ///
+    /// As with a model that is deserialized with serde, the actuators and the sensors need to be
+    /// attached to the model after it is loaded.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'bytes' - The binary format of the model.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::InvalidBinaryModel] - Returned when the data does not start with a valid
+    ///   header, has a different format version, is truncated, does not match its checksum or
+    ///   cannot be decoded.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use nalgebra::{Matrix3, Translation3, UnitQuaternion, Vector3};
+    /// use swerve_vehicle_descriptors::model_elements::model::{
+    ///     ChassisElementPhysicalProperties, MotionModel,
+    /// };
+    ///
+    /// let mut model = MotionModel::new();
+    /// model
+    ///     .add_body(
+    ///         "body".to_string(),
+    ///         Translation3::identity(),
+    ///         UnitQuaternion::identity(),
+    ///         ChassisElementPhysicalProperties::from_mass_properties(
+    ///             10.0,
+    ///             Vector3::zeros(),
+    ///             Matrix3::identity(),
+    ///         ),
+    ///     )
+    ///     .unwrap();
+    ///
+    /// let bytes = model.to_binary().unwrap();
+    /// let loaded = MotionModel::from_binary(&bytes).unwrap();
+    /// assert_eq!(model.body().unwrap(), loaded.body().unwrap());
+    /// ```
+    pub fn from_binary(bytes: &[u8]) -> Result<MotionModel, Error> {
+        if bytes.len() < HEADER_LENGTH || bytes[0..4] != MAGIC {
+            return Err(invalid("the data does not start with a model header"));
+        }
+
+        let version = u16::from_le_bytes([bytes[4], bytes[5]]);
+        if version != FORMAT_VERSION {
+            return Err(invalid(&format!(
+                "the format version {} is not supported, expected version {}",
+                version, FORMAT_VERSION
+            )));
+        }
+
+        let length = u32::from_le_bytes([bytes[6], bytes[7], bytes[8], bytes[9]]) as usize;
+        let checksum = u32::from_le_bytes([bytes[10], bytes[11], bytes[12], bytes[13]]);
+        let payload = &bytes[HEADER_LENGTH..];
+        if payload.len() != length {
+            return Err(invalid(&format!(
+                "expected {} bytes of model data, found {}",
+                length,
+                payload.len()
+            )));
+        }
+
+        if crc32(payload) != checksum {
+            return Err(invalid("the checksum of the model data does not match"));
+        }
+
+        postcard::from_bytes(payload).map_err(|e| invalid(&e.to_string()))
+    }
+
+    /// Returns the model in the binary format, see the [binary](crate::binary) module.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::InvalidBinaryModel] - Returned when the model cannot be encoded, e.g. because
+    ///   it is larger than 4 GiB.
+    pub fn to_binary(&self) -> Result<Vec<u8>, Error> {
+        let payload = postcard::to_allocvec(self).map_err(|e| invalid(&e.to_string()))?;
+        let length = u32::try_from(payload.len())
+            .map_err(|_| invalid("the model data is too large for the binary format"))?;
+
+        let mut bytes = Vec::with_capacity(HEADER_LENGTH + payload.len());
+        bytes.extend_from_slice(&MAGIC);
+        bytes.extend_from_slice(&FORMAT_VERSION.to_le_bytes());
+        bytes.extend_from_slice(&length.to_le_bytes());
+        bytes.extend_from_slice(&crc32(&payload).to_le_bytes());
+        bytes.extend_from_slice(&payload);
+        Ok(bytes)
+    }
+}
+
+/// Returns the CRC-32 checksum of the given data.
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = 0xFFFF_FFFFu32;
+    for byte in data {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (CRC32_POLYNOMIAL & mask);
+        }
+    }
+
+    !crc
+}
+
+/// Returns an [Error::InvalidBinaryModel] with the given reason.
+fn invalid(reason: &str) -> Error {
+    Error::InvalidBinaryModel {
+        reason: reason.to_string(),
+    }
+}
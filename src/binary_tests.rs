@@ -0,0 +1,101 @@
+use nalgebra::{Translation3, UnitQuaternion};
+
+use crate::{
+    model_elements::{
+        frame_elements::{FrameDofType, FrameID, JointCalibration, JointConstraint},
+        model::MotionModel,
+    },
+    test_fixtures::physical_properties,
+    Error,
+};
+
+use super::{crc32, FORMAT_VERSION, HEADER_LENGTH};
+
+// Builds a body with a calibrated suspension element.
+fn build_model() -> (MotionModel, FrameID) {
+    let mut model = MotionModel::new();
+    let body = model
+        .add_body(
+            "body".to_string(),
+            Translation3::identity(),
+            UnitQuaternion::identity(),
+            physical_properties(),
+        )
+        .unwrap();
+    let suspension = model
+        .add_suspension_element(
+            "suspension".to_string(),
+            FrameDofType::PrismaticZ,
+            body,
+            Translation3::new(1.0, 0.5, 0.0),
+            UnitQuaternion::identity(),
+            physical_properties(),
+            JointConstraint::new().with_position_limits(-0.1, 0.1),
+        )
+        .unwrap();
+    model
+        .set_calibration(&suspension, JointCalibration::new(0.01, -1.0).unwrap())
+        .unwrap();
+
+    (model, suspension)
+}
+
+fn reason(result: Result<MotionModel, Error>) -> String {
+    match result {
+        Err(Error::InvalidBinaryModel { reason }) => reason,
+        _ => panic!("Expected the binary model to be invalid"),
+    }
+}
+
+#[test]
+fn when_computing_a_checksum_it_should_match_the_standard_crc32() {
+    assert_eq!(0, crc32(&[]));
+    assert_eq!(0xCBF4_3926, crc32(b"123456789"));
+}
+
+#[test]
+fn when_writing_a_model_it_should_round_trip() {
+    let (model, suspension) = build_model();
+
+    let bytes = model.to_binary().unwrap();
+    assert_eq!(b"SVD\0", &bytes[0..4]);
+    assert_eq!(FORMAT_VERSION.to_le_bytes(), [bytes[4], bytes[5]]);
+    assert!(bytes.len() < serde_json::to_vec(&model).unwrap().len());
+
+    let loaded = MotionModel::from_binary(&bytes).unwrap();
+    assert_eq!(model.body().unwrap(), loaded.body().unwrap());
+    assert_eq!(
+        model.calibration(&suspension).unwrap(),
+        loaded.calibration(&suspension).unwrap()
+    );
+    assert_eq!(
+        model.joint_constraint(&suspension).unwrap(),
+        loaded.joint_constraint(&suspension).unwrap()
+    );
+    assert_eq!(
+        model.homogeneous_transform_to_body(&suspension).unwrap(),
+        loaded.homogeneous_transform_to_body(&suspension).unwrap()
+    );
+}
+
+#[test]
+fn when_reading_corrupted_data_it_should_error() {
+    let (model, _) = build_model();
+    let bytes = model.to_binary().unwrap();
+
+    assert!(reason(MotionModel::from_binary(&bytes[0..10])).contains("header"));
+
+    let mut wrong_magic = bytes.clone();
+    wrong_magic[0] = b'X';
+    assert!(reason(MotionModel::from_binary(&wrong_magic)).contains("header"));
+
+    let mut wrong_version = bytes.clone();
+    wrong_version[4] = wrong_version[4].wrapping_add(1);
+    assert!(reason(MotionModel::from_binary(&wrong_version)).contains("version"));
+
+    assert!(reason(MotionModel::from_binary(&bytes[0..bytes.len() - 1])).contains("expected"));
+
+    let mut flipped = bytes.clone();
+    flipped[HEADER_LENGTH + 3] ^= 0x01;
+    assert!(reason(MotionModel::from_binary(&flipped)).contains("checksum"));
+}
@@ -1,24 +1,40 @@
 //! Provides types for the (asynchronous) processing of messages from/to hardware
+//!
+//! The [HardwareChangeProcessor] processes the notifications of the hardware on a background
+//! thread. When the `async` feature is enabled the `AsyncHardwareChangeProcessor` processes the
+//! updates of hardware that uses the channels of tokio as tasks on a tokio runtime.
+//!
+//! The metrics of the [HardwareChangeProcessor], e.g. the number of waiting notifications and
+//! the time between the notification of an update and its processing, are reported through the
+//! [metrics] module.
 
 use std::{
-    collections::HashMap,
+    collections::{HashMap, VecDeque},
     fmt::Display,
     sync::{
-        atomic::{AtomicUsize, Ordering},
-        Arc, Mutex,
+        atomic::{AtomicU64, AtomicUsize, Ordering},
+        Arc, Mutex, MutexGuard,
     },
     thread::{self, JoinHandle},
-    time::Duration,
+    time::{Duration, Instant},
 };
 
-use crossbeam_channel::{Receiver, Sender};
+use crossbeam_channel::{Receiver, SendError, Sender, TrySendError};
 
-use crate::Error;
+use crate::{instrumentation::event, Error};
+
+use metrics::ProcessorMetrics;
+
+#[cfg(feature = "async")]
+use tokio::{runtime::Handle, sync::watch, task::JoinHandle as TaskHandle};
 
 #[cfg(test)]
+#[allow(clippy::needless_range_loop)]
 #[path = "change_notification_processing_tests.rs"]
 mod change_notification_processing_tests;
 
+pub mod metrics;
+
 /// The ChangeID counter value for the 'NONE' ID.
 static NONE_CHANGE_ID: usize = 0;
 
@@ -76,14 +92,122 @@ impl AsRef<ChangeID> for ChangeID {
     }
 }
 
+/// A notification of an update, together with the time at which the notification was sent.
+type Notification = (ChangeID, Instant);
+
+/// Notifies a [HardwareChangeProcessor] that the task with a given [ChangeID] has an update
+/// waiting.
+///
+/// The time at which a notification is sent is recorded with the [ChangeID], so that the
+/// processor can report the latency of the update, from the notification by the hardware to the
+/// moment the update is processed, to its [metrics].
+#[derive(Clone, Debug)]
+pub struct ChangeNotifier {
+    /// The channel on which the notifications are sent to the processor.
+    sender: Sender<Notification>,
+}
+
+impl ChangeNotifier {
+    /// Notifies the processor that the task with the given [ChangeID] has an update waiting.
+    /// Blocks if the processor uses [OverflowPolicy::Block] and its queue is full.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'id' - The [ChangeID] of the task.
+    ///
+    /// ## Errors
+    ///
+    /// * [SendError] - Returned, with the given ID, when the processor no longer exists.
+    pub fn send(&self, id: ChangeID) -> Result<(), SendError<ChangeID>> {
+        self.sender
+            .send((id, Instant::now()))
+            .map_err(|SendError((id, _))| SendError(id))
+    }
+
+    /// Notifies the processor that the task with the given [ChangeID] has an update waiting,
+    /// without blocking.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'id' - The [ChangeID] of the task.
+    ///
+    /// ## Errors
+    ///
+    /// * [TrySendError::Full] - Returned, with the given ID, when the processor uses
+    ///   [OverflowPolicy::Block] and its queue is full.
+    /// * [TrySendError::Disconnected] - Returned, with the given ID, when the processor no
+    ///   longer exists.
+    pub fn try_send(&self, id: ChangeID) -> Result<(), TrySendError<ChangeID>> {
+        self.sender
+            .try_send((id, Instant::now()))
+            .map_err(|err| match err {
+                TrySendError::Full((id, _)) => TrySendError::Full(id),
+                TrySendError::Disconnected((id, _)) => TrySendError::Disconnected(id),
+            })
+    }
+}
+
+/// Defines how a [HardwareChangeProcessor] handles notifications that arrive faster than they
+/// can be processed.
+///
+/// The tasks that are created by the [Actuator](crate::model_elements::frame_elements::Actuator)
+/// and the [JointSensor](crate::model_elements::frame_elements::JointSensor) process all the
+/// states that are waiting in the channel of the hardware each time they are executed, so
+/// coalescing or dropping the notifications for these tasks does not lose any states.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum OverflowPolicy {
+    /// Notifications are queued without a limit. This is the default policy.
+    Unbounded,
+
+    /// A notification is discarded when a notification with the same [ChangeID] is already
+    /// waiting to be processed, so that each task is executed at most once for a burst of
+    /// notifications.
+    Coalesce,
+
+    /// At most 'capacity' notifications are queued. When a new notification arrives while the
+    /// queue is full the oldest notification is discarded.
+    DropOldest {
+        /// The maximum number of queued notifications. A capacity of zero is treated as one.
+        capacity: usize,
+    },
+
+    /// At most 'capacity' notifications are queued. When the queue is full the hardware that
+    /// sends a notification is blocked until there is space in the queue.
+    Block {
+        /// The maximum number of queued notifications. A capacity of zero is treated as one.
+        capacity: usize,
+    },
+}
+
+/// Stores the number of notifications that were discarded by the [OverflowPolicy].
+#[derive(Debug, Default)]
+struct OverflowCounters {
+    /// The number of notifications that were discarded because a notification with the same
+    /// ID was already waiting.
+    coalesced: AtomicU64,
+
+    /// The number of notifications that were discarded because the queue was full.
+    dropped: AtomicU64,
+}
+
 /// An inner struct that stores the state of the task scheduler queue
 struct HardwareChangeProcessorState {
     /// The map of functions that the task scheduler will run when a notification
     /// of change comes through.
     ready_queue: HashMap<ChangeID, Box<dyn Fn() + Sync + Send>>,
 
+    /// The tasks that are executed once per processing period. A task is removed once it
+    /// returns false.
+    periodic_tasks: Vec<Box<dyn FnMut() -> bool + Send>>,
+
     /// A flag indicating whether or not the task scheduler jobs are being cancelled.
     cancelled: bool,
+
+    /// A flag indicating whether or not the processing of the notifications is paused.
+    paused: bool,
+
+    /// The metrics to which the processor reports, or [None] if no metrics are collected.
+    metrics: Option<Arc<dyn ProcessorMetrics>>,
 }
 
 impl HardwareChangeProcessorState {
@@ -91,28 +215,55 @@ impl HardwareChangeProcessorState {
     fn new() -> Self {
         Self {
             ready_queue: HashMap::new(),
+            periodic_tasks: Vec::new(),
             cancelled: false,
+            paused: false,
+            metrics: None,
         }
     }
 }
 
 /// Defines a scheduler that waits for updates to tasks and executes a closure when it
 /// gets a notification of an update.
+///
+/// The notifications are processed on a background thread that runs until the processor is
+/// stopped, either explicitly with [HardwareChangeProcessor::stop] or by dropping the processor.
+/// The background thread finishes the task that it is executing before it stops, so a task is
+/// never interrupted halfway through an update.
+///
+/// Next to the tasks that are executed when a notification is received, the processor executes
+/// periodic tasks once per processing period, e.g. to send the states of a
+/// [JointProfile](crate::hardware::joint_profile::JointProfile) to an actuator.
+///
+/// A processor created with [HardwareChangeProcessor::manual] has no background thread. The
+/// notifications are only processed when [HardwareChangeProcessor::process_pending] is called,
+/// which makes the processing deterministic, e.g. for tests and simulations.
 pub struct HardwareChangeProcessor {
     /// The template of the channel sender that is used to notify the scheduler when
     /// there is an update for one of the tasks
-    sender_template: Sender<ChangeID>,
+    sender_template: Sender<Notification>,
 
-    /// The thread handle for the background update thread
-    background_runner: JoinHandle<()>,
+    /// The thread handle for the background update thread. Set to [None] once the thread
+    /// has been joined.
+    background_runner: Option<JoinHandle<()>>,
 
     /// The queue containing the tasks that the background thread runs through
     queue: Arc<Mutex<HardwareChangeProcessorState>>,
+
+    /// Determines how notifications that arrive faster than they can be processed are handled.
+    overflow_policy: OverflowPolicy,
+
+    /// The number of notifications that were discarded by the overflow policy.
+    overflow_counters: Arc<OverflowCounters>,
+
+    /// The receiver of the notifications for a processor without a background thread, or
+    /// [None] if the notifications are processed by the background thread.
+    manual_receiver: Option<Receiver<Notification>>,
 }
 
 impl HardwareChangeProcessor {
-    /// Adds a new task to the scheduler and returns the [ChangeID] that is used to notify the
-    /// scheduler that the task has an update waiting.
+    /// Adds a new task to the scheduler and returns the [ChangeNotifier] and the [ChangeID] that
+    /// are used to notify the scheduler that the task has an update waiting.
     ///
     /// ## Parameters
     ///
@@ -120,7 +271,7 @@ impl HardwareChangeProcessor {
     pub fn add(
         &self,
         closure: Box<dyn Fn() + Sync + Send>,
-    ) -> Result<(Sender<ChangeID>, ChangeID), Error> {
+    ) -> Result<(ChangeNotifier, ChangeID), Error> {
         let result = ChangeID::new();
         {
             let guard = self.queue.lock();
@@ -129,7 +280,20 @@ impl HardwareChangeProcessor {
             map.ready_queue.insert(result, closure);
         }
 
-        Ok((self.sender_template.clone(), result))
+        let notifier = ChangeNotifier {
+            sender: self.sender_template.clone(),
+        };
+        Ok((notifier, result))
+    }
+
+    /// Adds a task that is executed once per processing period, for as long as the task
+    /// returns true. Periodic tasks are not executed while the processor is paused.
+    ///
+    /// ## Parameters
+    ///
+    /// `task` - The task that should be executed. Returns false when the task is done.
+    pub(crate) fn add_periodic(&self, task: Box<dyn FnMut() -> bool + Send>) {
+        self.lock_state().periodic_tasks.push(task);
     }
 
     /// Creates the background task update thread
@@ -137,6 +301,47 @@ impl HardwareChangeProcessor {
         thread::spawn(f)
     }
 
+    /// Creates a new [HardwareChangeProcessor] instance that does not have a background thread.
+    ///
+    /// The notifications and the periodic tasks are only processed when
+    /// [HardwareChangeProcessor::process_pending] is called, on the thread of the caller.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use std::sync::{
+    ///     atomic::{AtomicUsize, Ordering},
+    ///     Arc,
+    /// };
+    /// use swerve_vehicle_descriptors::change_notification_processing::HardwareChangeProcessor;
+    ///
+    /// let processor = HardwareChangeProcessor::manual();
+    /// let counter = Arc::new(AtomicUsize::new(0));
+    /// let task_counter = counter.clone();
+    /// let (sender, id) = processor
+    ///     .add(Box::new(move || {
+    ///         task_counter.fetch_add(1, Ordering::SeqCst);
+    ///     }))
+    ///     .unwrap();
+    ///
+    /// sender.send(id).unwrap();
+    /// assert_eq!(0, counter.load(Ordering::SeqCst));
+    ///
+    /// assert_eq!(1, processor.process_pending());
+    /// assert_eq!(1, counter.load(Ordering::SeqCst));
+    /// ```
+    pub fn manual() -> Self {
+        let (s, r) = crossbeam_channel::unbounded();
+        Self {
+            sender_template: s,
+            background_runner: None,
+            queue: Arc::new(Mutex::new(HardwareChangeProcessorState::new())),
+            overflow_policy: OverflowPolicy::Unbounded,
+            overflow_counters: Arc::new(OverflowCounters::default()),
+            manual_receiver: Some(r),
+        }
+    }
+
     /// Creates a new [HardwareChangeProcessor] instance
     ///
     /// This creates a new background thread that waits for [ChangeID]s to be received. Once a
@@ -146,48 +351,307 @@ impl HardwareChangeProcessor {
     ///
     /// * `processing_rate_in_hz` - The rate at which tasks should be processed.
     pub fn new(processing_rate_in_hz: i32) -> Self {
-        let (s, r) = crossbeam_channel::unbounded();
+        Self::with_overflow_policy(processing_rate_in_hz, OverflowPolicy::Unbounded)
+    }
+
+    /// Returns the number of notifications that were discarded because a notification with the
+    /// same [ChangeID] was already waiting. Only non-zero for [OverflowPolicy::Coalesce].
+    pub fn number_of_coalesced_notifications(&self) -> u64 {
+        self.overflow_counters.coalesced.load(Ordering::SeqCst)
+    }
+
+    /// Returns the number of notifications that were discarded because the queue was full.
+    /// Only non-zero for [OverflowPolicy::DropOldest].
+    pub fn number_of_dropped_notifications(&self) -> u64 {
+        self.overflow_counters.dropped.load(Ordering::SeqCst)
+    }
+
+    /// Returns the [OverflowPolicy] of the processor.
+    pub fn overflow_policy(&self) -> OverflowPolicy {
+        self.overflow_policy
+    }
+
+    /// Creates a new [HardwareChangeProcessor] instance that handles the notifications that
+    /// arrive faster than they can be processed with the given policy.
+    ///
+    /// ## Parameters
+    ///
+    /// * `processing_rate_in_hz` - The rate at which tasks should be processed.
+    /// * `overflow_policy` - Determines how notifications that arrive faster than they can be
+    ///   processed are handled.
+    pub fn with_overflow_policy(
+        processing_rate_in_hz: i32,
+        overflow_policy: OverflowPolicy,
+    ) -> Self {
+        let (s, r) = match overflow_policy {
+            OverflowPolicy::Block { capacity } => crossbeam_channel::bounded(capacity.max(1)),
+            _ => crossbeam_channel::unbounded(),
+        };
 
         let queue = Arc::new(Mutex::new(HardwareChangeProcessorState::new()));
         let queue_copy = queue.clone();
+        let overflow_counters = Arc::new(OverflowCounters::default());
+        let overflow_counters_copy = overflow_counters.clone();
 
         let background_runner = Self::create_thread(move || {
             let internal_queue = &queue_copy;
             let receiver = &r;
-            Self::run(internal_queue, receiver, processing_rate_in_hz);
+            Self::run(
+                internal_queue,
+                receiver,
+                processing_rate_in_hz,
+                overflow_policy,
+                &overflow_counters_copy,
+            );
         });
 
         Self {
             sender_template: s,
-            background_runner,
+            background_runner: Some(background_runner),
             queue,
+            overflow_policy,
+            overflow_counters,
+            manual_receiver: None,
         }
     }
 
+    /// Returns a value indicating whether the processor may discard notifications, in which
+    /// case a task can not assume that it is run once for each notification.
+    pub(crate) fn discards_notifications(&self) -> bool {
+        matches!(
+            self.overflow_policy,
+            OverflowPolicy::Coalesce | OverflowPolicy::DropOldest { .. }
+        )
+    }
+
+    /// Moves the notifications that are waiting in the channel to the pending queue, applying
+    /// the given policy.
+    fn collect_pending(
+        receiver: &Receiver<Notification>,
+        pending: &mut VecDeque<Notification>,
+        overflow_policy: OverflowPolicy,
+        overflow_counters: &OverflowCounters,
+        metrics: Option<&dyn ProcessorMetrics>,
+    ) {
+        for (id, sent_at) in receiver.try_iter() {
+            match overflow_policy {
+                // The waiting notification keeps the time at which it was sent, so the latency
+                // covers the whole burst
+                OverflowPolicy::Coalesce if pending.iter().any(|(waiting, _)| *waiting == id) => {
+                    overflow_counters.coalesced.fetch_add(1, Ordering::SeqCst);
+                    if let Some(metrics) = metrics {
+                        metrics.notification_discarded(id);
+                    }
+                    event!(TRACE, change = %id, "Coalesced a hardware notification");
+                }
+                OverflowPolicy::DropOldest { capacity } => {
+                    pending.push_back((id, sent_at));
+                    while pending.len() > capacity.max(1) {
+                        event!(WARN, change = ?pending.front().map(|(id, _)| id), "Dropped a hardware notification because the queue is full");
+                        if let (Some((dropped, _)), Some(metrics)) = (pending.pop_front(), metrics)
+                        {
+                            metrics.notification_discarded(dropped);
+                        }
+                        overflow_counters.dropped.fetch_add(1, Ordering::SeqCst);
+                    }
+                }
+                _ => pending.push_back((id, sent_at)),
+            }
+        }
+    }
+
+    /// Returns a value indicating whether the processing of the notifications is paused.
+    pub fn is_paused(&self) -> bool {
+        self.lock_state().paused
+    }
+
+    /// Returns a value indicating whether the background thread is running, i.e. the processor
+    /// has not been stopped and the thread has not exited. A paused processor is still running.
+    /// A processor without a background thread is running until it is stopped.
+    pub fn is_running(&self) -> bool {
+        let is_cancelled = self.lock_state().cancelled;
+        let is_finished = match &self.background_runner {
+            Some(handle) => handle.is_finished(),
+            None => self.manual_receiver.is_none(),
+        };
+
+        !is_cancelled && !is_finished
+    }
+
+    /// Stops the background thread and waits for it to exit. The task that is being executed
+    /// is finished first. Calling this function more than once has no effect.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::ChangeProcessorFailed] - Returned when the background thread panicked, e.g.
+    ///   because one of the tasks panicked.
+    pub fn join(&mut self) -> Result<(), Error> {
+        self.stop();
+        match self.background_runner.take() {
+            Some(handle) => handle.join().map_err(|_| {
+                event!(
+                    ERROR,
+                    "The background thread of the hardware change processor panicked"
+                );
+                Error::ChangeProcessorFailed
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Pauses the processing of the notifications. Notifications that are received while the
+    /// processor is paused are queued and processed once the processor is resumed.
+    pub fn pause(&self) {
+        self.lock_state().paused = true;
+    }
+
+    /// Executes the periodic tasks once and then the tasks for all the notifications that are
+    /// waiting, on the thread of the caller. Returns the number of notifications for which a
+    /// task was executed.
+    ///
+    /// Only processors created with [HardwareChangeProcessor::manual] are processed this way.
+    /// For other processors, and for processors that are paused or stopped, nothing is executed
+    /// and zero is returned.
+    pub fn process_pending(&self) -> usize {
+        let receiver = match &self.manual_receiver {
+            Some(r) => r,
+            None => return 0,
+        };
+
+        let mut state = self.lock_state();
+        if state.cancelled || state.paused {
+            return 0;
+        }
+
+        state.periodic_tasks.retain_mut(|task| task());
+
+        let metrics = state.metrics.clone();
+        let mut count = 0;
+        while let Some((id, sent_at)) = Self::next_notification(receiver, metrics.as_deref()) {
+            if let Some(f) = state.ready_queue.get(&id) {
+                Self::execute(id, sent_at, f, metrics.as_deref());
+                count += 1;
+            }
+        }
+
+        count
+    }
+
+    /// Resumes the processing of the notifications after a call to
+    /// [HardwareChangeProcessor::pause].
+    pub fn resume(&self) {
+        self.lock_state().paused = false;
+    }
+
+    /// Sets the metrics to which the processor reports, replacing the metrics that were set
+    /// before.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'metrics' - The metrics, or [None] to stop reporting metrics.
+    pub fn set_metrics(&self, metrics: Option<Arc<dyn ProcessorMetrics>>) {
+        self.lock_state().metrics = metrics;
+    }
+
+    /// Signals the background thread to stop without waiting for it to exit. The thread exits
+    /// after it finishes the task that it is executing, or at the end of its current wait.
+    /// Notifications that have not been processed are discarded. Use
+    /// [HardwareChangeProcessor::join] to wait for the thread to exit.
+    pub fn stop(&self) {
+        self.lock_state().cancelled = true;
+    }
+
+    /// Executes the task for the notification with the given ID and reports the time between
+    /// sending the notification and the end of the task to the metrics.
+    fn execute(
+        id: ChangeID,
+        sent_at: Instant,
+        task: &(dyn Fn() + Sync + Send),
+        metrics: Option<&dyn ProcessorMetrics>,
+    ) {
+        task();
+        if let Some(metrics) = metrics {
+            metrics.notification_processed(id, sent_at.elapsed());
+        }
+    }
+
+    /// Takes the next notification from the channel, after reporting the number of waiting
+    /// notifications to the metrics.
+    fn next_notification(
+        receiver: &Receiver<Notification>,
+        metrics: Option<&dyn ProcessorMetrics>,
+    ) -> Option<Notification> {
+        if let Some(metrics) = metrics {
+            metrics.queue_depth(receiver.len());
+        }
+
+        receiver.try_recv().ok()
+    }
+
+    /// Locks the state of the processor. A poisoned lock is recovered because the state
+    /// is only ever updated with single assignments.
+    fn lock_state(&self) -> MutexGuard<'_, HardwareChangeProcessorState> {
+        self.queue.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
     /// Runs the task processing.
     #[cfg_attr(test, mutants::skip)] // This cannot easily be unit tested in a way that mutations are easy to catch
     fn run(
         queue: &Arc<Mutex<HardwareChangeProcessorState>>,
-        receiver: &Receiver<ChangeID>,
+        receiver: &Receiver<Notification>,
         rate_in_hz: i32,
+        overflow_policy: OverflowPolicy,
+        overflow_counters: &OverflowCounters,
     ) {
         let sleep_time_in_millis = ((1.0 / (rate_in_hz as f64)) * 1000.0) as u64;
+        let period = Duration::from_millis(sleep_time_in_millis);
+        let mut next_tick = Instant::now();
+        let mut pending = VecDeque::new();
         loop {
-            let is_cancelled: bool;
-            {
+            let (is_cancelled, is_paused, metrics) = {
                 let arc_lock = queue.lock().unwrap_or_else(|err| err.into_inner());
-                is_cancelled = arc_lock.cancelled;
-            }
+                (
+                    arc_lock.cancelled,
+                    arc_lock.paused,
+                    arc_lock.metrics.clone(),
+                )
+            };
 
             if is_cancelled {
                 break;
             }
 
-            // check the receiver
-            let result = receiver.try_recv();
-            if result.is_ok() {
-                let id = result.unwrap();
+            if is_paused {
+                // Leave the notifications in the channel until the processing is resumed
+                thread::sleep(Duration::from_millis(sleep_time_in_millis));
+                continue;
+            }
 
+            if Instant::now() >= next_tick {
+                let mut state = queue.lock().unwrap_or_else(|err| err.into_inner());
+                state.periodic_tasks.retain_mut(|task| task());
+                next_tick = Instant::now() + period;
+            }
+
+            // check the receiver. The policies that discard notifications need to see all the
+            // waiting notifications, the other policies leave them in the channel.
+            let result = match overflow_policy {
+                OverflowPolicy::Coalesce | OverflowPolicy::DropOldest { .. } => {
+                    Self::collect_pending(
+                        receiver,
+                        &mut pending,
+                        overflow_policy,
+                        overflow_counters,
+                        metrics.as_deref(),
+                    );
+                    if let Some(metrics) = &metrics {
+                        metrics.queue_depth(pending.len());
+                    }
+                    pending.pop_front()
+                }
+                _ => Self::next_notification(receiver, metrics.as_deref()),
+            };
+            if let Some((id, sent_at)) = result {
                 // unwrap the hashmap and see if we have the ID
                 let func: Option<&Box<dyn Fn() + Sync + Send>>;
                 {
@@ -196,11 +660,12 @@ impl HardwareChangeProcessor {
 
                     match func {
                         Some(f) => {
-                            f();
+                            Self::execute(id, sent_at, f.as_ref(), metrics.as_deref());
                         }
                         None => {
                             // The ID didn't exist in our map, but we did have an ID, so we just continue
                             // and go around the loop again to see if there's another ID waiting
+                            event!(TRACE, change = %id, "Ignored a hardware notification without a task");
                         }
                     };
                 }
@@ -210,7 +675,11 @@ impl HardwareChangeProcessor {
                 //
                 // In order to do this right we should really count how many milliseconds have past since the
                 // last time we slept(??) and then set our duration - wake time (give or take)
-                thread::sleep(Duration::from_millis(sleep_time_in_millis));
+                thread::sleep(
+                    next_tick
+                        .saturating_duration_since(Instant::now())
+                        .min(period),
+                );
             }
         }
 
@@ -219,10 +688,86 @@ impl HardwareChangeProcessor {
 }
 
 impl Drop for HardwareChangeProcessor {
+    /// Signals the background thread to stop. The thread is not joined so that dropping the
+    /// processor does not block, the thread exits on its own once it sees the signal.
     fn drop(&mut self) {
-        {
-            let mut arc_lock = self.queue.lock().unwrap_or_else(|err| err.into_inner());
-            arc_lock.cancelled = true;
+        self.stop();
+    }
+}
+
+/// Defines a processor that runs a task on a tokio runtime for each of the asynchronous hardware
+/// elements. Each task waits for the hardware to publish a new state and stores the state in
+/// the actuator or sensor.
+///
+/// The tasks are aborted when the processor is dropped. This type is only available when the
+/// `async` feature is enabled.
+#[cfg(feature = "async")]
+pub struct AsyncHardwareChangeProcessor {
+    /// The handle of the runtime on which the tasks are spawned.
+    handle: Handle,
+
+    /// The handles of the tasks that process the state updates.
+    tasks: Mutex<Vec<TaskHandle<()>>>,
+}
+
+#[cfg(feature = "async")]
+impl AsyncHardwareChangeProcessor {
+    /// Creates a new [AsyncHardwareChangeProcessor] instance that spawns its tasks on the
+    /// runtime of the given handle.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'handle' - The handle of the tokio runtime, e.g. [Handle::current].
+    pub fn new(handle: Handle) -> Self {
+        Self {
+            handle,
+            tasks: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Returns the number of tasks that are still processing state updates. A task stops when
+    /// the hardware drops the sending side of its channel.
+    pub fn number_of_active_tasks(&self) -> usize {
+        let tasks = self.tasks.lock().unwrap_or_else(|err| err.into_inner());
+        tasks.iter().filter(|task| !task.is_finished()).count()
+    }
+
+    /// Spawns a task that passes the current value of the given channel, and every value that
+    /// is published after that, to the given closure.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'receiver' - The channel on which the hardware publishes its state.
+    /// * 'update' - The closure that stores the state.
+    pub(crate) fn watch<T, F>(&self, mut receiver: watch::Receiver<T>, update: F)
+    where
+        T: Clone + Send + Sync + 'static,
+        F: Fn(T) + Send + 'static,
+    {
+        let task = self.handle.spawn(async move {
+            loop {
+                let value = receiver.borrow_and_update().clone();
+                update(value);
+
+                if receiver.changed().await.is_err() {
+                    // The hardware is gone, so there will be no more updates
+                    break;
+                }
+            }
+        });
+
+        let mut tasks = self.tasks.lock().unwrap_or_else(|err| err.into_inner());
+        tasks.retain(|task| !task.is_finished());
+        tasks.push(task);
+    }
+}
+
+#[cfg(feature = "async")]
+impl Drop for AsyncHardwareChangeProcessor {
+    fn drop(&mut self) {
+        let tasks = self.tasks.lock().unwrap_or_else(|err| err.into_inner());
+        for task in tasks.iter() {
+            task.abort();
         }
     }
 }
@@ -0,0 +1,196 @@
+//! Provides the metrics of a [HardwareChangeProcessor](super::HardwareChangeProcessor), which
+//! show whether the processor keeps up with the rate at which the hardware publishes its updates.
+//!
+//! The processor reports to the [ProcessorMetrics] that is set with
+//! [HardwareChangeProcessor::set_metrics](super::HardwareChangeProcessor::set_metrics):
+//!
+//! * The number of notifications that are waiting to be processed, each time the processor
+//!   takes the next notification from its queue.
+//! * The latency of each update, i.e. the time between the moment the hardware sent the
+//!   notification and the moment the task for the notification finished, together with the
+//!   [ChangeID] of the notification. The [ChangeID] of the hardware of a joint is available from
+//!   [JointSensor::change_id](crate::model_elements::frame_elements::JointSensor::change_id) and
+//!   [Actuator::change_id](crate::model_elements::frame_elements::Actuator::change_id).
+//! * The notifications that are discarded by the
+//!   [OverflowPolicy](super::OverflowPolicy) of the processor.
+//!
+//! The [ProcessorStatistics] collects these values as counters and gauges. Applications that
+//! export their metrics to a monitoring system implement [ProcessorMetrics] themselves.
+//!
+//! # Examples
+//!
+//! ```
+//! use std::sync::Arc;
+//! use swerve_vehicle_descriptors::change_notification_processing::{
+//!     metrics::ProcessorStatistics, HardwareChangeProcessor,
+//! };
+//!
+//! let processor = HardwareChangeProcessor::manual();
+//! let statistics = Arc::new(ProcessorStatistics::new());
+//! processor.set_metrics(Some(statistics.clone()));
+//!
+//! let (sender, id) = processor.add(Box::new(|| {})).unwrap();
+//! sender.send(id).unwrap();
+//! sender.send(id).unwrap();
+//! processor.process_pending();
+//!
+//! assert_eq!(2, statistics.number_of_processed_notifications());
+//! assert_eq!(2, statistics.maximum_queue_depth());
+//! assert!(statistics.update_latency(&id).is_some());
+//! ```
+
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicU64, AtomicUsize, Ordering},
+        Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+use super::ChangeID;
+
+#[cfg(test)]
+#[path = "metrics_tests.rs"]
+mod metrics_tests;
+
+/// Defines the callbacks through which a
+/// [HardwareChangeProcessor](super::HardwareChangeProcessor) reports its metrics.
+///
+/// The callbacks are executed on the thread that processes the notifications, so they should
+/// return quickly. All callbacks do nothing by default.
+pub trait ProcessorMetrics: Send + Sync {
+    /// Called when a notification is discarded by the overflow policy of the processor.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'id' - The [ChangeID] of the notification.
+    fn notification_discarded(&self, _id: ChangeID) {}
+
+    /// Called after the task for a notification was executed.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'id' - The [ChangeID] of the notification.
+    /// * 'latency' - The time between sending the notification and the end of the task, which
+    ///   includes the time the notification waited in the queue of the processor.
+    fn notification_processed(&self, _id: ChangeID, _latency: Duration) {}
+
+    /// Called before the processor takes the next notification from its queue.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'depth' - The number of notifications that are waiting to be processed.
+    fn queue_depth(&self, _depth: usize) {}
+}
+
+/// Collects the metrics of a [HardwareChangeProcessor](super::HardwareChangeProcessor) as
+/// counters and gauges.
+#[derive(Debug)]
+pub struct ProcessorStatistics {
+    /// The time at which the statistics were created.
+    created_at: Instant,
+
+    /// The number of notifications that were discarded by the overflow policy.
+    discarded: AtomicU64,
+
+    /// The latency of the last processed notification for each [ChangeID].
+    latencies: Mutex<HashMap<ChangeID, Duration>>,
+
+    /// The largest number of notifications that were waiting to be processed.
+    maximum_queue_depth: AtomicUsize,
+
+    /// The number of notifications for which a task was executed.
+    processed: AtomicU64,
+
+    /// The number of notifications that were waiting to be processed the last time the
+    /// processor took a notification from its queue.
+    queue_depth: AtomicUsize,
+}
+
+impl ProcessorStatistics {
+    /// Returns the largest number of notifications that were waiting to be processed.
+    pub fn maximum_queue_depth(&self) -> usize {
+        self.maximum_queue_depth.load(Ordering::SeqCst)
+    }
+
+    /// Creates a new [ProcessorStatistics] instance.
+    pub fn new() -> Self {
+        Self {
+            created_at: Instant::now(),
+            discarded: AtomicU64::new(0),
+            latencies: Mutex::new(HashMap::new()),
+            maximum_queue_depth: AtomicUsize::new(0),
+            processed: AtomicU64::new(0),
+            queue_depth: AtomicUsize::new(0),
+        }
+    }
+
+    /// Returns the number of notifications that were discarded by the overflow policy of the
+    /// processor.
+    pub fn number_of_discarded_notifications(&self) -> u64 {
+        self.discarded.load(Ordering::SeqCst)
+    }
+
+    /// Returns the number of notifications for which a task was executed.
+    pub fn number_of_processed_notifications(&self) -> u64 {
+        self.processed.load(Ordering::SeqCst)
+    }
+
+    /// Returns the number of notifications that were waiting to be processed the last time the
+    /// processor took a notification from its queue.
+    pub fn queue_depth(&self) -> usize {
+        self.queue_depth.load(Ordering::SeqCst)
+    }
+
+    /// Returns the latency of the last processed notification with the given [ChangeID], i.e.
+    /// the time between the moment the hardware sent the notification and the moment the
+    /// notification was processed, or [None] if no notification with the ID has been processed.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'id' - The [ChangeID] of the notification, e.g. the ID of the hardware of a joint.
+    pub fn update_latency(&self, id: &ChangeID) -> Option<Duration> {
+        self.latencies
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .get(id)
+            .copied()
+    }
+
+    /// Returns the average number of notifications that were processed per second since the
+    /// statistics were created.
+    pub fn updates_per_second(&self) -> f64 {
+        let elapsed = self.created_at.elapsed().as_secs_f64();
+        if elapsed <= 0.0 {
+            return 0.0;
+        }
+
+        self.number_of_processed_notifications() as f64 / elapsed
+    }
+}
+
+impl Default for ProcessorStatistics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ProcessorMetrics for ProcessorStatistics {
+    fn notification_discarded(&self, _id: ChangeID) {
+        self.discarded.fetch_add(1, Ordering::SeqCst);
+    }
+
+    fn notification_processed(&self, id: ChangeID, latency: Duration) {
+        self.processed.fetch_add(1, Ordering::SeqCst);
+        self.latencies
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .insert(id, latency);
+    }
+
+    fn queue_depth(&self, depth: usize) {
+        self.queue_depth.store(depth, Ordering::SeqCst);
+        self.maximum_queue_depth.fetch_max(depth, Ordering::SeqCst);
+    }
+}
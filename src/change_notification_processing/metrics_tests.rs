@@ -0,0 +1,114 @@
+use std::{sync::Arc, time::Duration};
+
+use crate::{
+    change_notification_processing::{HardwareChangeProcessor, OverflowPolicy},
+    hardware::{
+        joint_state::{JointState, JointStateRange},
+        simulation::SimulatedSensor,
+    },
+    model_elements::frame_elements::JointSensor,
+    number_space::NumberSpaceType,
+};
+
+use super::{ProcessorMetrics, ProcessorStatistics};
+
+#[test]
+fn when_processing_notifications_it_should_report_the_queue_depth_and_the_latency() {
+    let processor = HardwareChangeProcessor::manual();
+    let statistics = Arc::new(ProcessorStatistics::new());
+    processor.set_metrics(Some(statistics.clone()));
+
+    let (sender, id) = processor
+        .add(Box::new(|| std::thread::sleep(Duration::from_millis(2))))
+        .unwrap();
+    for _ in 0..3 {
+        sender.send(id).unwrap();
+    }
+
+    assert_eq!(3, processor.process_pending());
+    assert_eq!(3, statistics.number_of_processed_notifications());
+    assert_eq!(3, statistics.maximum_queue_depth());
+    assert_eq!(0, statistics.queue_depth());
+    assert!(statistics.update_latency(&id).unwrap() >= Duration::from_millis(2));
+    assert!(statistics.updates_per_second() > 0.0);
+
+    // Without metrics nothing is reported
+    processor.set_metrics(None);
+    sender.send(id).unwrap();
+    assert_eq!(1, processor.process_pending());
+    assert_eq!(3, statistics.number_of_processed_notifications());
+}
+
+#[test]
+fn when_a_notification_waits_it_should_include_the_wait_in_the_latency() {
+    let processor = HardwareChangeProcessor::manual();
+    let statistics = Arc::new(ProcessorStatistics::new());
+    processor.set_metrics(Some(statistics.clone()));
+
+    let (sender, id) = processor.add(Box::new(|| {})).unwrap();
+    sender.send(id).unwrap();
+    std::thread::sleep(Duration::from_millis(20));
+
+    assert_eq!(1, processor.process_pending());
+    assert!(statistics.update_latency(&id).unwrap() >= Duration::from_millis(20));
+}
+
+#[test]
+fn when_a_joint_sensor_is_updated_it_should_report_the_latency_for_its_change_id() {
+    let processor = HardwareChangeProcessor::manual();
+    let statistics = Arc::new(ProcessorStatistics::new());
+    processor.set_metrics(Some(statistics.clone()));
+
+    let mut hardware = SimulatedSensor::new(
+        NumberSpaceType::LinearUnlimited,
+        JointStateRange::new(
+            JointState::new(-1.0, None, None, None),
+            JointState::new(1.0, None, None, None),
+        ),
+        JointState::new(0.5, None, None, None),
+    );
+    let sensor = JointSensor::new(&mut hardware, &processor).unwrap();
+    let id = sensor.change_id().unwrap();
+    assert_eq!(None, statistics.update_latency(&id));
+
+    hardware.step();
+    processor.process_pending();
+
+    assert_eq!(0.5, sensor.value().unwrap().position());
+    assert!(statistics.update_latency(&id).is_some());
+}
+
+#[test]
+fn when_notifications_are_dropped_it_should_report_the_discarded_notifications() {
+    let processor = HardwareChangeProcessor::with_overflow_policy(
+        100,
+        OverflowPolicy::DropOldest { capacity: 1 },
+    );
+    let statistics = Arc::new(ProcessorStatistics::new());
+    processor.set_metrics(Some(statistics.clone()));
+    let (sender, id) = processor.add(Box::new(|| {})).unwrap();
+
+    processor.pause();
+    for _ in 0..3 {
+        sender.send(id).unwrap();
+    }
+    processor.resume();
+    std::thread::sleep(Duration::from_millis(50));
+
+    assert_eq!(2, statistics.number_of_discarded_notifications());
+    assert_eq!(1, statistics.number_of_processed_notifications());
+    assert_eq!(1, statistics.maximum_queue_depth());
+}
+
+#[test]
+fn when_a_metrics_callback_is_not_implemented_it_should_do_nothing() {
+    struct NoMetrics;
+    impl ProcessorMetrics for NoMetrics {}
+
+    let processor = HardwareChangeProcessor::manual();
+    processor.set_metrics(Some(Arc::new(NoMetrics)));
+    let (sender, id) = processor.add(Box::new(|| {})).unwrap();
+    sender.send(id).unwrap();
+
+    assert_eq!(1, processor.process_pending());
+}
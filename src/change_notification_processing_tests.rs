@@ -118,3 +118,211 @@ fn test_task_execution_with_unregistered_task() {
     // Check if the task was executed
     assert!(!executed_flag.load(Ordering::SeqCst));
 }
+
+#[test]
+fn when_pausing_the_processor_it_should_hold_the_notifications_until_resumed() {
+    let scheduler = HardwareChangeProcessor::new(100);
+    assert!(scheduler.is_running());
+    assert!(!scheduler.is_paused());
+
+    let executed_flag = Arc::new(AtomicBool::new(false));
+    let executed_flag_clone = executed_flag.clone();
+    let task = move || {
+        executed_flag_clone.store(true, Ordering::SeqCst);
+    };
+    let (sender, task_id) = scheduler.add(Box::new(task)).unwrap();
+
+    scheduler.pause();
+    assert!(scheduler.is_paused());
+    assert!(scheduler.is_running());
+
+    sender.send(task_id).unwrap();
+    std::thread::sleep(Duration::from_millis(50));
+    assert!(!executed_flag.load(Ordering::SeqCst));
+
+    scheduler.resume();
+    assert!(!scheduler.is_paused());
+    std::thread::sleep(Duration::from_millis(50));
+    assert!(executed_flag.load(Ordering::SeqCst));
+}
+
+#[test]
+fn when_stopping_the_processor_it_should_stop_the_background_thread() {
+    let mut scheduler = HardwareChangeProcessor::new(100);
+
+    scheduler.stop();
+    assert!(!scheduler.is_running());
+
+    assert_eq!(Ok(()), scheduler.join());
+    assert_eq!(Ok(()), scheduler.join());
+    assert!(!scheduler.is_running());
+}
+
+#[test]
+fn when_adding_a_periodic_task_it_should_run_until_the_task_is_done() {
+    let scheduler = HardwareChangeProcessor::new(100);
+    let counter = Arc::new(AtomicUsize::new(0));
+    let task_counter = counter.clone();
+    scheduler.add_periodic(Box::new(move || {
+        task_counter.fetch_add(1, Ordering::SeqCst) < 4
+    }));
+
+    std::thread::sleep(Duration::from_millis(200));
+    assert_eq!(5, counter.load(Ordering::SeqCst));
+}
+
+#[test]
+fn when_a_task_panics_it_should_report_the_failure_on_join() {
+    let mut scheduler = HardwareChangeProcessor::new(100);
+    let (sender, task_id) = scheduler.add(Box::new(|| panic!("task failed"))).unwrap();
+
+    sender.send(task_id).unwrap();
+    let deadline = std::time::Instant::now() + Duration::from_secs(2);
+    while scheduler.is_running() && std::time::Instant::now() < deadline {
+        std::thread::sleep(Duration::from_millis(1));
+    }
+    assert!(!scheduler.is_running());
+
+    assert_eq!(Err(Error::ChangeProcessorFailed), scheduler.join());
+}
+
+// Adds a task that counts the number of times it is executed.
+fn add_counting_task(
+    scheduler: &HardwareChangeProcessor,
+) -> (ChangeNotifier, ChangeID, Arc<AtomicUsize>) {
+    let counter = Arc::new(AtomicUsize::new(0));
+    let counter_clone = counter.clone();
+    let (sender, id) = scheduler
+        .add(Box::new(move || {
+            counter_clone.fetch_add(1, Ordering::SeqCst);
+        }))
+        .unwrap();
+
+    (sender, id, counter)
+}
+
+#[test]
+fn when_coalescing_notifications_it_should_execute_each_task_once() {
+    let scheduler = HardwareChangeProcessor::with_overflow_policy(100, OverflowPolicy::Coalesce);
+    assert_eq!(OverflowPolicy::Coalesce, scheduler.overflow_policy());
+    let (sender, id, counter) = add_counting_task(&scheduler);
+
+    scheduler.pause();
+    for _ in 0..5 {
+        sender.send(id).unwrap();
+    }
+    scheduler.resume();
+    std::thread::sleep(Duration::from_millis(50));
+
+    assert_eq!(1, counter.load(Ordering::SeqCst));
+    assert_eq!(4, scheduler.number_of_coalesced_notifications());
+    assert_eq!(0, scheduler.number_of_dropped_notifications());
+}
+
+#[test]
+fn when_the_queue_overflows_with_drop_oldest_it_should_drop_the_oldest_notifications() {
+    let scheduler = HardwareChangeProcessor::with_overflow_policy(
+        100,
+        OverflowPolicy::DropOldest { capacity: 2 },
+    );
+    let tasks: Vec<_> = (0..3).map(|_| add_counting_task(&scheduler)).collect();
+
+    scheduler.pause();
+    for (sender, id, _) in tasks.iter() {
+        sender.send(*id).unwrap();
+    }
+    scheduler.resume();
+    std::thread::sleep(Duration::from_millis(50));
+
+    let counts: Vec<_> = tasks
+        .iter()
+        .map(|(_, _, counter)| counter.load(Ordering::SeqCst))
+        .collect();
+    assert_eq!(vec![0, 1, 1], counts);
+    assert_eq!(1, scheduler.number_of_dropped_notifications());
+    assert_eq!(0, scheduler.number_of_coalesced_notifications());
+}
+
+#[test]
+fn when_the_queue_is_full_with_block_it_should_not_accept_more_notifications() {
+    let scheduler =
+        HardwareChangeProcessor::with_overflow_policy(100, OverflowPolicy::Block { capacity: 1 });
+    let (sender, id, counter) = add_counting_task(&scheduler);
+
+    scheduler.pause();
+    sender.send(id).unwrap();
+    assert!(sender.try_send(id).is_err());
+
+    scheduler.resume();
+    std::thread::sleep(Duration::from_millis(50));
+    assert_eq!(1, counter.load(Ordering::SeqCst));
+    assert!(sender.try_send(id).is_ok());
+}
+
+#[test]
+fn when_creating_a_processor_it_should_not_limit_the_queue() {
+    let scheduler = HardwareChangeProcessor::new(100);
+    assert_eq!(OverflowPolicy::Unbounded, scheduler.overflow_policy());
+
+    let (sender, id, counter) = add_counting_task(&scheduler);
+    scheduler.pause();
+    for _ in 0..3 {
+        sender.send(id).unwrap();
+    }
+    scheduler.resume();
+    std::thread::sleep(Duration::from_millis(50));
+
+    assert_eq!(3, counter.load(Ordering::SeqCst));
+    assert_eq!(0, scheduler.number_of_coalesced_notifications());
+    assert_eq!(0, scheduler.number_of_dropped_notifications());
+}
+
+#[test]
+fn when_processing_a_manual_processor_it_should_only_execute_the_tasks_on_request() {
+    let scheduler = HardwareChangeProcessor::manual();
+    assert!(scheduler.is_running());
+
+    let (sender, id, counter) = add_counting_task(&scheduler);
+    let periodic_counter = Arc::new(AtomicUsize::new(0));
+    let task_counter = periodic_counter.clone();
+    scheduler.add_periodic(Box::new(move || {
+        task_counter.fetch_add(1, Ordering::SeqCst) < 1
+    }));
+
+    sender.send(id).unwrap();
+    sender.send(id).unwrap();
+    assert_eq!(0, counter.load(Ordering::SeqCst));
+
+    assert_eq!(2, scheduler.process_pending());
+    assert_eq!(2, counter.load(Ordering::SeqCst));
+    assert_eq!(1, periodic_counter.load(Ordering::SeqCst));
+
+    // Nothing is processed while the processor is paused
+    sender.send(id).unwrap();
+    scheduler.pause();
+    assert_eq!(0, scheduler.process_pending());
+    scheduler.resume();
+    assert_eq!(1, scheduler.process_pending());
+    assert_eq!(3, counter.load(Ordering::SeqCst));
+
+    // The periodic task is done after its second execution
+    assert_eq!(0, scheduler.process_pending());
+    assert_eq!(2, periodic_counter.load(Ordering::SeqCst));
+
+    scheduler.stop();
+    assert!(!scheduler.is_running());
+    sender.send(id).unwrap();
+    assert_eq!(0, scheduler.process_pending());
+    assert_eq!(3, counter.load(Ordering::SeqCst));
+}
+
+#[test]
+fn when_processing_a_threaded_processor_manually_it_should_do_nothing() {
+    let scheduler = HardwareChangeProcessor::new(100);
+    scheduler.pause();
+    let (sender, id, counter) = add_counting_task(&scheduler);
+    sender.send(id).unwrap();
+
+    assert_eq!(0, scheduler.process_pending());
+    assert_eq!(0, counter.load(Ordering::SeqCst));
+}
@@ -0,0 +1,142 @@
+//! Provides the functions used to export the kinematic tree of a [MotionModel] as a
+//! [Graphviz](https://graphviz.org) DOT description.
+//!
+//! Each frame element is exported as a node with a label that shows the name of the element,
+//! the degree of freedom of the element relative to its parent and whether the element is
+//! actuated or has a sensor. Elements that should be actuated but do not have an actuator are
+//! marked as such, which makes it easier to find out why a model is not valid. Each connection
+//! between an element and its parent is exported as an edge with a label that shows the static
+//! transform from the element to its parent.
+//!
+//! The DOT description can be rendered with the Graphviz tools, e.g. `dot -Tsvg model.dot`.
+
+use std::fmt::Write;
+
+use crate::{
+    model_elements::{frame_elements::FrameID, model::MotionModel},
+    urdf::number,
+    Error,
+};
+
+#[cfg(test)]
+#[path = "dot_tests.rs"]
+mod dot_tests;
+
+impl MotionModel {
+    /// Returns the Graphviz DOT description of the kinematic tree of the model.
+    ///
+    /// The nodes are identified by the [FrameID] of the element so that elements that share a
+    /// name are shown as separate nodes. The transforms are shown with the joints at their zero
+    /// position.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the model has no body.
+    pub fn to_dot(&self) -> Result<String, Error> {
+        let body = self.body()?;
+
+        let mut dot = String::new();
+        // Writing to a String never fails so the results of 'writeln!' can be ignored.
+        let _ = writeln!(
+            dot,
+            "digraph \"{}\" {{",
+            escape(self.reference_frame(body)?.name())
+        );
+        let _ = writeln!(dot, "  node [shape=box];");
+        self.write_node(&mut dot, body)?;
+
+        let mut stack = vec![*body];
+        while let Some(parent) = stack.pop() {
+            let children = self.children_of(&parent)?;
+
+            // Push in reverse order so that the children are written in order
+            for child in children.iter().rev() {
+                stack.push(**child);
+            }
+
+            for child in children {
+                self.write_node(&mut dot, child)?;
+                self.write_edge(&mut dot, &parent, child)?;
+            }
+        }
+
+        let _ = writeln!(dot, "}}");
+        Ok(dot)
+    }
+
+    /// Writes the DOT edge that connects the given element to its parent.
+    fn write_edge(&self, dot: &mut String, parent: &FrameID, child: &FrameID) -> Result<(), Error> {
+        let transform = match self.kinematics_table(child) {
+            Some(table) => table.transform_at(0.0),
+            None => *self.static_transform_to_parent(child)?,
+        };
+        let translation = transform.translation.vector;
+        let (roll, pitch, yaw) = transform.rotation.euler_angles();
+
+        let _ = writeln!(
+            dot,
+            "  \"{}\" -> \"{}\" [label=\"xyz: {} {} {}\\nrpy: {} {} {}\"];",
+            parent,
+            child,
+            number(translation.x),
+            number(translation.y),
+            number(translation.z),
+            number(roll),
+            number(pitch),
+            number(yaw)
+        );
+
+        Ok(())
+    }
+
+    /// Writes the DOT node for the given element.
+    fn write_node(&self, dot: &mut String, frame_id: &FrameID) -> Result<(), Error> {
+        let frame = self.reference_frame(frame_id)?;
+
+        let mut flags = vec![];
+        if frame.is_actuated() {
+            if self.is_actuated(frame_id) {
+                flags.push("actuated");
+            } else {
+                flags.push("actuated (no actuator)");
+            }
+        }
+
+        if self.has_sensor(frame_id) {
+            flags.push("sensor");
+        }
+
+        let mut label = format!(
+            "{}\\n{:?}",
+            escape(frame.name()),
+            frame.degree_of_freedom_kind()
+        );
+        if !flags.is_empty() {
+            let _ = write!(label, "\\n{}", flags.join(", "));
+        }
+
+        let _ = writeln!(dot, "  \"{}\" [label=\"{}\"];", frame_id, label);
+        Ok(())
+    }
+}
+
+/// Returns the given text with the characters that have a special meaning in a double quoted
+/// string escaped. The result can be used in both DOT and JSON strings.
+pub(crate) fn escape(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(result, "\\u{:04x}", c as u32);
+            }
+            _ => result.push(c),
+        }
+    }
+
+    result
+}
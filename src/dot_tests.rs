@@ -0,0 +1,79 @@
+use crate::{model_elements::model::MotionModel, test_fixtures::two_module_model};
+
+use super::escape;
+
+#[test]
+fn when_exporting_an_empty_model_it_should_error() {
+    let model = MotionModel::new();
+
+    assert!(model.to_dot().is_err());
+}
+
+#[test]
+fn when_exporting_a_model_it_should_write_a_node_for_each_element() {
+    let (model, frames) = two_module_model();
+    let dot = model.to_dot().unwrap();
+
+    assert!(dot.starts_with("digraph \"body\" {\n"));
+    assert!(dot.ends_with("}\n"));
+
+    let body = model.body().unwrap();
+    assert!(dot.contains(&format!("  \"{}\" [label=\"body\\nStatic\"];", body)));
+
+    let suspension = frames[0].suspension().unwrap();
+    assert!(dot.contains(&format!(
+        "  \"{}\" [label=\"left_suspension\\nPrismaticZ\"];",
+        suspension
+    )));
+
+    // The modules do not have actuators yet
+    assert!(dot.contains(&format!(
+        "  \"{}\" [label=\"right_steering\\nRevoluteZ\\nactuated (no actuator)\"];",
+        frames[1].steering()
+    )));
+    assert!(dot.contains(&format!(
+        "  \"{}\" [label=\"right_wheel\\nRevoluteY\\nactuated (no actuator)\"];",
+        frames[1].wheel()
+    )));
+
+    assert_eq!(3, dot.matches("[label=\"left_").count());
+    assert_eq!(2, dot.matches("[label=\"right_").count());
+}
+
+#[test]
+fn when_exporting_a_model_it_should_write_an_edge_for_each_connection() {
+    let (model, frames) = two_module_model();
+    let dot = model.to_dot().unwrap();
+
+    let body = model.body().unwrap();
+    assert_eq!(5, dot.matches(" -> ").count());
+
+    let suspension = frames[0].suspension().unwrap();
+    assert!(dot.contains(&format!(
+        "  \"{}\" -> \"{}\" [label=\"xyz: 1 0.5 0\\nrpy: 0 0 0\"];",
+        body, suspension
+    )));
+    assert!(dot.contains(&format!(
+        "  \"{}\" -> \"{}\" [label=\"xyz: 0 0 0\\nrpy: 0 0 0\"];",
+        suspension,
+        frames[0].steering()
+    )));
+    assert!(dot.contains(&format!(
+        "  \"{}\" -> \"{}\" [label=\"xyz: 1 -0.5 0\\nrpy: 0 0 0\"];",
+        body,
+        frames[1].steering()
+    )));
+    assert!(dot.contains(&format!(
+        "  \"{}\" -> \"{}\" [label=\"xyz: 0 0 -0.25\\nrpy: 0 0 0\"];",
+        frames[1].steering(),
+        frames[1].wheel()
+    )));
+}
+
+#[test]
+fn when_escaping_text_it_should_escape_quotes_and_backslashes() {
+    assert_eq!("plain", escape("plain"));
+    assert_eq!("a \\\"quoted\\\" name", escape("a \"quoted\" name"));
+    assert_eq!("back\\\\slash", escape("back\\slash"));
+    assert_eq!("two\\nlines", escape("two\nlines"));
+}
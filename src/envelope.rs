@@ -0,0 +1,468 @@
+//! Provides the functions used to export the operating envelope of a [MotionModel], e.g. for
+//! inclusion in the safety documentation of the vehicle.
+//!
+//! The [VehicleEnvelope] summarizes the values that are relevant for the safety case of the
+//! vehicle: the maximum speeds for each operating mode, the mass properties of the vehicle with
+//! and without the rated payload, the footprint formed by the wheel contact points and the
+//! static stability margins. Because the envelope is derived from the same model that is used
+//! to control the vehicle the safety documentation stays in sync with the software description
+//! of the vehicle. When the `serde` feature is enabled the envelope can be serialized to any
+//! format supported by serde, e.g. JSON.
+//!
+//! All values are computed with the joints in their zero position and are expressed in the
+//! body reference frame. Distances are in meters, masses in kilograms, angles in radians and
+//! moments of inertia in kilogram square meters.
+
+use nalgebra::{Matrix3, Vector2, Vector3};
+
+use crate::{
+    kinematics::mass_properties::{
+        combined_center_of_mass, element_masses_in_body_with, ElementMass,
+    },
+    model_elements::model::MotionModel,
+    Error,
+};
+
+#[cfg(test)]
+#[path = "envelope_tests.rs"]
+mod envelope_tests;
+
+/// The maximum speeds of the body for an operating mode.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DirectionalSpeedLimits {
+    /// The name of the operating mode.
+    operating_mode: String,
+
+    /// The maximum speed, in meters per second, along the positive x-axis of the body.
+    forward: f64,
+
+    /// The maximum speed, in meters per second, along the negative x-axis of the body.
+    backward: f64,
+
+    /// The maximum speed, in meters per second, along the positive y-axis of the body.
+    left: f64,
+
+    /// The maximum speed, in meters per second, along the negative y-axis of the body.
+    right: f64,
+
+    /// The maximum angular speed, in radians per second, around the z-axis of the body.
+    angular: f64,
+}
+
+impl DirectionalSpeedLimits {
+    /// Returns the maximum angular speed, in radians per second, around the z-axis of the body.
+    pub fn angular(&self) -> f64 {
+        self.angular
+    }
+
+    /// Returns the maximum speed, in meters per second, along the negative x-axis of the body.
+    pub fn backward(&self) -> f64 {
+        self.backward
+    }
+
+    /// Returns the maximum speed, in meters per second, along the positive x-axis of the body.
+    pub fn forward(&self) -> f64 {
+        self.forward
+    }
+
+    /// Returns the maximum speed, in meters per second, along the positive y-axis of the body.
+    pub fn left(&self) -> f64 {
+        self.left
+    }
+
+    /// Returns the name of the operating mode to which the limits apply.
+    pub fn operating_mode(&self) -> &str {
+        &self.operating_mode
+    }
+
+    /// Returns the maximum speed, in meters per second, along the negative y-axis of the body.
+    pub fn right(&self) -> f64 {
+        self.right
+    }
+}
+
+/// The mass properties of the vehicle.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MassSummary {
+    /// The total mass in kilograms.
+    mass: f64,
+
+    /// The position of the center of mass in the body reference frame.
+    center_of_mass: Vector3<f64>,
+
+    /// The moment of inertia around the center of mass, along the axes of the body reference
+    /// frame.
+    moment_of_inertia: Matrix3<f64>,
+}
+
+impl MassSummary {
+    /// Returns the position of the center of mass in the body reference frame.
+    pub fn center_of_mass(&self) -> &Vector3<f64> {
+        &self.center_of_mass
+    }
+
+    /// Returns the total mass in kilograms.
+    pub fn mass(&self) -> f64 {
+        self.mass
+    }
+
+    /// Returns the moment of inertia around the center of mass, along the axes of the body
+    /// reference frame.
+    pub fn moment_of_inertia(&self) -> &Matrix3<f64> {
+        &self.moment_of_inertia
+    }
+
+    /// Returns the moment of inertia around the vertical axis through the center of mass.
+    pub fn yaw_moment_of_inertia(&self) -> f64 {
+        self.moment_of_inertia[(2, 2)]
+    }
+}
+
+/// The static stability of the vehicle for a given mass distribution.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StabilityMargin {
+    /// The distance between the projection of the center of mass on the ground and the
+    /// closest edge of the support polygon. Negative when the projection is outside the
+    /// support polygon.
+    distance_to_edge: f64,
+
+    /// The angle over which the vehicle can be tilted towards the closest edge of the support
+    /// polygon before it tips over.
+    tip_over_angle: f64,
+}
+
+impl StabilityMargin {
+    /// Returns the distance between the projection of the center of mass on the ground and the
+    /// closest edge of the support polygon. The distance is negative when the projection is
+    /// outside the support polygon.
+    pub fn distance_to_edge(&self) -> f64 {
+        self.distance_to_edge
+    }
+
+    /// Returns the angle over which the vehicle can be tilted towards the closest edge of the
+    /// support polygon before it tips over.
+    pub fn tip_over_angle(&self) -> f64 {
+        self.tip_over_angle
+    }
+}
+
+/// Summarizes the operating envelope of a vehicle, as derived from its [MotionModel].
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VehicleEnvelope {
+    /// The maximum speeds for each of the operating modes, ordered by the name of the mode.
+    speed_limits: Vec<DirectionalSpeedLimits>,
+
+    /// The mass of the rated payload in kilograms.
+    rated_payload: f64,
+
+    /// The mass properties of the vehicle without payload.
+    unloaded: MassSummary,
+
+    /// The mass properties of the vehicle with the rated payload.
+    loaded: MassSummary,
+
+    /// The sum of the moments of inertia of the wheels around their axles.
+    wheel_rotational_inertia: f64,
+
+    /// The corners of the support polygon, in counter-clockwise order, in the xy-plane of the
+    /// body reference frame.
+    support_polygon: Vec<Vector2<f64>>,
+
+    /// The minimum x and y coordinates of the wheel contact points.
+    footprint_minimum: Vector2<f64>,
+
+    /// The maximum x and y coordinates of the wheel contact points.
+    footprint_maximum: Vector2<f64>,
+
+    /// The stability margin of the vehicle without payload.
+    unloaded_stability_margin: Option<StabilityMargin>,
+
+    /// The stability margin of the vehicle with the rated payload.
+    loaded_stability_margin: Option<StabilityMargin>,
+}
+
+impl VehicleEnvelope {
+    /// Returns the maximum x and y coordinates of the wheel contact points in the body
+    /// reference frame.
+    pub fn footprint_maximum(&self) -> &Vector2<f64> {
+        &self.footprint_maximum
+    }
+
+    /// Returns the minimum x and y coordinates of the wheel contact points in the body
+    /// reference frame.
+    pub fn footprint_minimum(&self) -> &Vector2<f64> {
+        &self.footprint_minimum
+    }
+
+    /// Returns the mass properties of the vehicle with the rated payload.
+    pub fn loaded(&self) -> &MassSummary {
+        &self.loaded
+    }
+
+    /// Returns the stability margin of the vehicle with the rated payload, or [None] if the
+    /// wheels do not form a support polygon, e.g. because there are fewer than three wheels.
+    pub fn loaded_stability_margin(&self) -> Option<&StabilityMargin> {
+        self.loaded_stability_margin.as_ref()
+    }
+
+    /// Returns the mass of the rated payload in kilograms.
+    pub fn rated_payload(&self) -> f64 {
+        self.rated_payload
+    }
+
+    /// Returns the maximum speeds for each of the operating modes of the vehicle, ordered by
+    /// the name of the mode.
+    pub fn speed_limits(&self) -> &[DirectionalSpeedLimits] {
+        &self.speed_limits
+    }
+
+    /// Returns the corners of the support polygon, i.e. the convex hull of the wheel contact
+    /// points, in counter-clockwise order in the xy-plane of the body reference frame.
+    pub fn support_polygon(&self) -> &[Vector2<f64>] {
+        &self.support_polygon
+    }
+
+    /// Returns the mass properties of the vehicle without payload.
+    pub fn unloaded(&self) -> &MassSummary {
+        &self.unloaded
+    }
+
+    /// Returns the stability margin of the vehicle without payload, or [None] if the wheels do
+    /// not form a support polygon, e.g. because there are fewer than three wheels.
+    pub fn unloaded_stability_margin(&self) -> Option<&StabilityMargin> {
+        self.unloaded_stability_margin.as_ref()
+    }
+
+    /// Returns the sum of the moments of inertia of the wheels around their axles. The wheels
+    /// need to be decelerated together with the vehicle when braking.
+    pub fn wheel_rotational_inertia(&self) -> f64 {
+        self.wheel_rotational_inertia
+    }
+}
+
+impl MotionModel {
+    /// Returns the operating envelope of the vehicle.
+    ///
+    /// The speed limits are taken from the operating modes of the model. The mass properties
+    /// assume that the moment of inertia of each chassis element is given around its center of
+    /// mass, along the axes of the reference frame of the element. The payload is treated as a
+    /// point mass. The ground is assumed to be the plane through the lowest wheel contact point.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'rated_payload' - The mass, in kilograms, of the rated payload of the vehicle.
+    /// * 'payload_center_of_mass' - The position of the center of mass of the payload in the
+    ///   body reference frame.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the model has no body.
+    /// * [Error::MissingWheelRadius] - Returned when the radius of one of the wheels is not known.
+    pub fn vehicle_envelope(
+        &self,
+        rated_payload: f64,
+        payload_center_of_mass: Vector3<f64>,
+    ) -> Result<VehicleEnvelope, Error> {
+        let speed_limits = self.directional_speed_limits();
+
+        let unloaded = self.mass_summary_at_zero_position(&[])?;
+        let loaded =
+            self.mass_summary_at_zero_position(&[(rated_payload, payload_center_of_mass)])?;
+
+        let body = *self.body()?;
+        let mut wheel_rotational_inertia = 0.0;
+        let mut contact_points = Vec::new();
+        for wheel in self.wheels()? {
+            let radius = self.wheel_radius(wheel)?;
+            let to_body = self.homogeneous_transform_to_ancestor_with(wheel, &body, |_| None)?;
+            contact_points.push(Vector3::new(
+                to_body[(0, 3)],
+                to_body[(1, 3)],
+                to_body[(2, 3)] - radius,
+            ));
+
+            // The wheels rotate around their y-axis
+            wheel_rotational_inertia += self.chassis_element(wheel)?.moment_of_inertia()[(1, 1)];
+        }
+
+        let ground_height = contact_points
+            .iter()
+            .map(|p| p.z)
+            .fold(f64::INFINITY, f64::min);
+        let points: Vec<Vector2<f64>> = contact_points.iter().map(|p| p.xy()).collect();
+        let support_polygon = convex_hull(&points);
+
+        let mut footprint_minimum = Vector2::zeros();
+        let mut footprint_maximum = Vector2::zeros();
+        if let Some(first) = points.first() {
+            footprint_minimum = *first;
+            footprint_maximum = *first;
+            for p in points.iter() {
+                footprint_minimum = footprint_minimum.inf(p);
+                footprint_maximum = footprint_maximum.sup(p);
+            }
+        }
+
+        let unloaded_stability_margin =
+            stability_margin(&support_polygon, unloaded.center_of_mass(), ground_height);
+        let loaded_stability_margin =
+            stability_margin(&support_polygon, loaded.center_of_mass(), ground_height);
+
+        Ok(VehicleEnvelope {
+            speed_limits,
+            rated_payload,
+            unloaded,
+            loaded,
+            wheel_rotational_inertia,
+            support_polygon,
+            footprint_minimum,
+            footprint_maximum,
+            unloaded_stability_margin,
+            loaded_stability_margin,
+        })
+    }
+
+    /// Returns the maximum speeds for each of the operating modes, ordered by the name of the
+    /// mode.
+    ///
+    /// The linear speed limit of an operating mode applies to the magnitude of the linear
+    /// velocity, so the limits are the same in all directions.
+    fn directional_speed_limits(&self) -> Vec<DirectionalSpeedLimits> {
+        let mut names: Vec<&str> = self.operating_modes().collect();
+        names.sort_unstable();
+
+        names
+            .into_iter()
+            .filter_map(|name| self.operating_mode_by_name(name))
+            .map(|mode| {
+                let linear = mode.effective_maximum_linear_speed();
+                DirectionalSpeedLimits {
+                    operating_mode: mode.name().to_string(),
+                    forward: linear,
+                    backward: linear,
+                    left: linear,
+                    right: linear,
+                    angular: mode.effective_maximum_angular_speed(),
+                }
+            })
+            .collect()
+    }
+
+    /// Returns the mass properties of the model, with all the joints in their zero position,
+    /// combined with the given additional point masses.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'point_masses' - The mass and the position in the body reference frame of the
+    ///   additional point masses.
+    fn mass_summary_at_zero_position(
+        &self,
+        point_masses: &[(f64, Vector3<f64>)],
+    ) -> Result<MassSummary, Error> {
+        let mut elements = element_masses_in_body_with(self, |_| None)?;
+        for (mass, position) in point_masses {
+            elements.push(ElementMass {
+                mass: *mass,
+                center_of_mass: *position,
+                moment_of_inertia: Matrix3::zeros(),
+            });
+        }
+
+        let (mass, center_of_mass) = combined_center_of_mass(&elements);
+
+        // Parallel axis theorem
+        let moment_of_inertia = elements.iter().fold(Matrix3::zeros(), |acc, e| {
+            let d = e.center_of_mass - center_of_mass;
+            acc + e.moment_of_inertia
+                + (Matrix3::identity() * d.norm_squared() - d * d.transpose()) * e.mass
+        });
+
+        Ok(MassSummary {
+            mass,
+            center_of_mass,
+            moment_of_inertia,
+        })
+    }
+}
+
+/// Returns the convex hull of the given points in counter-clockwise order. Points on the edges
+/// of the hull are not included.
+///
+/// ## Parameters
+///
+/// * 'points' - The points for which the hull should be computed.
+pub(crate) fn convex_hull(points: &[Vector2<f64>]) -> Vec<Vector2<f64>> {
+    let mut sorted = points.to_vec();
+    sorted.sort_by(|a, b| a.x.total_cmp(&b.x).then(a.y.total_cmp(&b.y)));
+    sorted.dedup();
+    if sorted.len() < 3 {
+        return sorted;
+    }
+
+    // Andrew's monotone chain algorithm
+    let cross = |o: &Vector2<f64>, a: &Vector2<f64>, b: &Vector2<f64>| (a - o).perp(&(b - o));
+
+    let mut hull: Vec<Vector2<f64>> = Vec::with_capacity(2 * sorted.len());
+    for pass in 0..2 {
+        let start = hull.len();
+        let iter: Box<dyn Iterator<Item = &Vector2<f64>>> = if pass == 0 {
+            Box::new(sorted.iter())
+        } else {
+            Box::new(sorted.iter().rev())
+        };
+
+        for p in iter {
+            while hull.len() >= start + 2
+                && cross(&hull[hull.len() - 2], &hull[hull.len() - 1], p) <= 0.0
+            {
+                hull.pop();
+            }
+
+            hull.push(*p);
+        }
+
+        // The last point of each half is the first point of the other half
+        hull.pop();
+    }
+
+    hull
+}
+
+/// Returns the static stability margin for the given center of mass, or [None] if the support
+/// polygon has fewer than three corners.
+///
+/// ## Parameters
+///
+/// * 'support_polygon' - The corners of the support polygon in counter-clockwise order.
+/// * 'center_of_mass' - The position of the center of mass in the body reference frame.
+/// * 'ground_height' - The z-coordinate of the ground in the body reference frame.
+fn stability_margin(
+    support_polygon: &[Vector2<f64>],
+    center_of_mass: &Vector3<f64>,
+    ground_height: f64,
+) -> Option<StabilityMargin> {
+    if support_polygon.len() < 3 {
+        return None;
+    }
+
+    let projection = center_of_mass.xy();
+    let distance_to_edge = support_polygon
+        .iter()
+        .zip(support_polygon.iter().cycle().skip(1))
+        .map(|(a, b)| {
+            let edge = b - a;
+            // Positive when the point is to the left of the edge, i.e. inside the polygon
+            edge.perp(&(projection - a)) / edge.norm()
+        })
+        .fold(f64::INFINITY, f64::min);
+
+    let height = center_of_mass.z - ground_height;
+    Some(StabilityMargin {
+        distance_to_edge,
+        tip_over_angle: distance_to_edge.atan2(height),
+    })
+}
@@ -0,0 +1,272 @@
+use std::f64::consts::PI;
+
+use nalgebra::{Translation3, UnitQuaternion, Vector2, Vector3};
+
+use crate::{
+    change_notification_processing::HardwareChangeProcessor,
+    model_elements::{
+        frame_elements::{Actuator, FrameID},
+        model::MotionModel,
+        operating_mode::OperatingMode,
+    },
+    number_space::NumberSpaceType,
+    test_fixtures::physical_properties_with_mass,
+    test_support::MockActuator,
+    Error,
+};
+
+use super::convex_hull;
+
+// A vehicle with a 10 kg body, with the center of mass 0.3 above the body origin, and a module
+// at each of the given positions. Each module consists of a 1 kg steering frame and a 1 kg wheel
+// frame with a radius of 0.1, 0.1 below the steering frame.
+fn create_vehicle(
+    positions: &[(f64, f64)],
+    change_processor: &HardwareChangeProcessor,
+) -> (MotionModel, Vec<FrameID>) {
+    let mut model = MotionModel::new();
+    let body = model
+        .add_body(
+            "body".to_string(),
+            Translation3::identity(),
+            UnitQuaternion::identity(),
+            physical_properties_with_mass(10.0, Vector3::new(0.0, 0.0, 0.3)),
+        )
+        .unwrap();
+
+    let mut wheels = vec![];
+    for (x, y) in positions {
+        let steering = model
+            .add_steering_element(
+                "steering".to_string(),
+                body,
+                Translation3::new(*x, *y, 0.0),
+                UnitQuaternion::from_euler_angles(0.0, 0.0, 0.25 * PI),
+                physical_properties_with_mass(1.0, Vector3::zeros()),
+                Actuator::new(
+                    &mut MockActuator::new(NumberSpaceType::LinearUnlimited),
+                    change_processor,
+                )
+                .unwrap(),
+            )
+            .unwrap();
+
+        let wheel = model
+            .add_wheel(
+                "wheel".to_string(),
+                steering,
+                Translation3::new(0.0, 0.0, -0.1),
+                UnitQuaternion::identity(),
+                physical_properties_with_mass(1.0, Vector3::zeros()),
+                Actuator::new(
+                    &mut MockActuator::new(NumberSpaceType::LinearUnlimited),
+                    change_processor,
+                )
+                .unwrap(),
+            )
+            .unwrap();
+        model.set_wheel_radius(&wheel, 0.1).unwrap();
+
+        wheels.push(wheel);
+    }
+
+    (model, wheels)
+}
+
+const FOUR_WHEELS: [(f64, f64); 4] = [(1.0, 0.5), (-1.0, 0.5), (-1.0, -0.5), (1.0, -0.5)];
+
+#[test]
+fn when_computing_the_convex_hull_it_should_skip_interior_and_collinear_points() {
+    let points = vec![
+        Vector2::new(0.0, 0.0),
+        Vector2::new(1.0, 1.0),
+        Vector2::new(2.0, 0.0),
+        Vector2::new(2.0, 2.0),
+        Vector2::new(0.0, 2.0),
+        Vector2::new(1.0, 0.0),
+        Vector2::new(2.0, 0.0),
+    ];
+
+    let hull = convex_hull(&points);
+    assert_eq!(
+        vec![
+            Vector2::new(0.0, 0.0),
+            Vector2::new(2.0, 0.0),
+            Vector2::new(2.0, 2.0),
+            Vector2::new(0.0, 2.0),
+        ],
+        hull
+    );
+}
+
+#[test]
+fn when_getting_the_envelope_it_should_summarize_the_mass_properties() {
+    let change_processor = HardwareChangeProcessor::new(10);
+    let (model, _) = create_vehicle(&FOUR_WHEELS, &change_processor);
+
+    let envelope = model
+        .vehicle_envelope(2.0, Vector3::new(0.0, 0.3, 0.5))
+        .unwrap();
+
+    assert_eq!(2.0, envelope.rated_payload());
+
+    let unloaded = envelope.unloaded();
+    assert!((unloaded.mass() - 18.0).abs() < 1e-12);
+    assert!((unloaded.center_of_mass() - Vector3::new(0.0, 0.0, 2.6 / 18.0)).norm() < 1e-12);
+
+    // Each element contributes its own inertia and the modules are 1.25 m^2 from the z-axis
+    assert!((unloaded.yaw_moment_of_inertia() - 19.0).abs() < 1e-12);
+    assert!(
+        (unloaded.moment_of_inertia() - unloaded.moment_of_inertia().transpose()).norm() < 1e-12
+    );
+
+    let loaded = envelope.loaded();
+    assert!((loaded.mass() - 20.0).abs() < 1e-12);
+    assert!((loaded.center_of_mass() - Vector3::new(0.0, 0.03, 0.18)).norm() < 1e-12);
+    assert!(loaded.yaw_moment_of_inertia() > unloaded.yaw_moment_of_inertia());
+
+    assert!((envelope.wheel_rotational_inertia() - 4.0).abs() < 1e-12);
+}
+
+#[test]
+fn when_getting_the_envelope_it_should_describe_the_footprint_and_stability() {
+    let change_processor = HardwareChangeProcessor::new(10);
+    let (model, _) = create_vehicle(&FOUR_WHEELS, &change_processor);
+
+    let envelope = model
+        .vehicle_envelope(2.0, Vector3::new(0.0, 0.3, 0.5))
+        .unwrap();
+
+    let polygon = envelope.support_polygon();
+    assert_eq!(4, polygon.len());
+    for (x, y) in FOUR_WHEELS {
+        assert!(polygon
+            .iter()
+            .any(|p| (p - Vector2::new(x, y)).norm() < 1e-12));
+    }
+
+    assert!((envelope.footprint_minimum() - Vector2::new(-1.0, -0.5)).norm() < 1e-12);
+    assert!((envelope.footprint_maximum() - Vector2::new(1.0, 0.5)).norm() < 1e-12);
+
+    // The ground is 0.2 below the body origin
+    let unloaded = envelope.unloaded_stability_margin().unwrap();
+    assert!((unloaded.distance_to_edge() - 0.5).abs() < 1e-12);
+    assert!((unloaded.tip_over_angle() - 0.5_f64.atan2(2.6 / 18.0 + 0.2)).abs() < 1e-12);
+
+    let loaded = envelope.loaded_stability_margin().unwrap();
+    assert!((loaded.distance_to_edge() - 0.47).abs() < 1e-12);
+    assert!((loaded.tip_over_angle() - 0.47_f64.atan2(0.38)).abs() < 1e-12);
+}
+
+#[test]
+fn when_getting_the_envelope_with_payload_outside_the_footprint_it_should_have_a_negative_margin() {
+    let change_processor = HardwareChangeProcessor::new(10);
+    let (model, _) = create_vehicle(&FOUR_WHEELS, &change_processor);
+
+    let envelope = model
+        .vehicle_envelope(100.0, Vector3::new(0.0, 2.0, 0.5))
+        .unwrap();
+
+    let margin = envelope.loaded_stability_margin().unwrap();
+    assert!(margin.distance_to_edge() < 0.0);
+    assert!(margin.tip_over_angle() < 0.0);
+}
+
+#[test]
+fn when_getting_the_envelope_with_two_wheels_it_should_have_no_stability_margin() {
+    let change_processor = HardwareChangeProcessor::new(10);
+    let (model, _) = create_vehicle(&[(0.0, 0.5), (0.0, -0.5)], &change_processor);
+
+    let envelope = model.vehicle_envelope(0.0, Vector3::zeros()).unwrap();
+
+    assert_eq!(2, envelope.support_polygon().len());
+    assert!(envelope.unloaded_stability_margin().is_none());
+    assert!(envelope.loaded_stability_margin().is_none());
+}
+
+#[test]
+fn when_getting_the_envelope_it_should_list_the_speed_limits_per_operating_mode() {
+    let change_processor = HardwareChangeProcessor::new(10);
+    let (mut model, _) = create_vehicle(&FOUR_WHEELS, &change_processor);
+    model
+        .add_operating_mode(OperatingMode::new("outdoor".to_string(), 2.0, 1.0, 1.0))
+        .unwrap();
+    model
+        .add_operating_mode(OperatingMode::new("indoor".to_string(), 1.0, 0.5, 0.5))
+        .unwrap();
+
+    let envelope = model.vehicle_envelope(0.0, Vector3::zeros()).unwrap();
+
+    let limits = envelope.speed_limits();
+    assert_eq!(2, limits.len());
+    assert_eq!("indoor", limits[0].operating_mode());
+    assert_eq!(0.5, limits[0].forward());
+    assert_eq!(0.5, limits[0].backward());
+    assert_eq!(0.5, limits[0].left());
+    assert_eq!(0.5, limits[0].right());
+    assert_eq!(0.25, limits[0].angular());
+    assert_eq!("outdoor", limits[1].operating_mode());
+    assert_eq!(2.0, limits[1].forward());
+    assert_eq!(1.0, limits[1].angular());
+}
+
+#[test]
+fn when_getting_the_envelope_without_wheel_radius_it_should_error() {
+    let change_processor = HardwareChangeProcessor::new(10);
+    let (model, wheels) = create_vehicle(&FOUR_WHEELS, &change_processor);
+    let mut model = model;
+    let steering = *model.steering_frame_for_wheel(&wheels[0]).unwrap();
+    model.remove_element(&wheels[0]).unwrap();
+    let wheel = model
+        .add_wheel(
+            "wheel".to_string(),
+            steering,
+            Translation3::new(0.0, 0.0, -0.1),
+            UnitQuaternion::identity(),
+            physical_properties_with_mass(1.0, Vector3::zeros()),
+            Actuator::new(
+                &mut MockActuator::new(NumberSpaceType::LinearUnlimited),
+                &change_processor,
+            )
+            .unwrap(),
+        )
+        .unwrap();
+
+    assert_eq!(
+        Err(Error::MissingWheelRadius { id: wheel }),
+        model.vehicle_envelope(0.0, Vector3::zeros())
+    );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn when_serializing_the_envelope_it_should_round_trip() {
+    let change_processor = HardwareChangeProcessor::new(10);
+    let (mut model, _) = create_vehicle(&FOUR_WHEELS, &change_processor);
+    model
+        .add_operating_mode(OperatingMode::new("indoor".to_string(), 1.0, 0.5, 0.5))
+        .unwrap();
+
+    let envelope = model
+        .vehicle_envelope(2.0, Vector3::new(0.0, 0.3, 0.5))
+        .unwrap();
+
+    let json = serde_json::to_string(&envelope).unwrap();
+    let loaded: super::VehicleEnvelope = serde_json::from_str(&json).unwrap();
+    assert_eq!(envelope.speed_limits(), loaded.speed_limits());
+    assert_eq!(envelope.support_polygon(), loaded.support_polygon());
+    assert!((envelope.loaded().mass() - loaded.loaded().mass()).abs() < 1e-12);
+    assert!(
+        (envelope.loaded().moment_of_inertia() - loaded.loaded().moment_of_inertia()).norm()
+            < 1e-12
+    );
+    assert!(
+        (envelope
+            .loaded_stability_margin()
+            .unwrap()
+            .distance_to_edge()
+            - loaded.loaded_stability_margin().unwrap().distance_to_edge())
+        .abs()
+            < 1e-12
+    );
+}
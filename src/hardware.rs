@@ -2,9 +2,25 @@
 //! like sensors and actuators. The [sensor_interface::HardwareSensor] trait provides the functions
 //! necessary to get information from a physical, or simulated, sensor. The
 //! [actuator_interface::HardwareActuator] trait provides functions necessary to get information
-//! to and from a physical, or simulated, actuator.
+//! to and from a physical, or simulated, actuator. The [command_middleware::CommandMiddleware]
+//! trait provides the extension point for transforming or vetoing the commands before they are
+//! sent to an actuator and the [state_middleware::StateMiddleware] trait provides the extension
+//! point for transforming or rejecting the joint states received from the hardware. The
+//! [state_fusion::StateFusion] trait defines how the states of an actuator and a sensor for the
+//! same joint are combined and a [joint_profile::JointProfile] describes how the state of a joint
+//! should change over time. The [health] module defines the health of the actuators. The [simulation] module provides simulated actuators and sensors
+//! that can be advanced step by step, e.g. for tests. When the `async` feature is enabled the `async_interface` module
+//! provides the same interfaces for hardware that uses the channels of tokio.
 //!
 
 pub mod actuator_interface;
+#[cfg(feature = "async")]
+pub mod async_interface;
+pub mod command_middleware;
+pub mod health;
+pub mod joint_profile;
 pub mod joint_state;
 pub mod sensor_interface;
+pub mod simulation;
+pub mod state_fusion;
+pub mod state_middleware;
@@ -2,7 +2,12 @@
 
 use crossbeam_channel::{Receiver, Sender};
 
-use crate::{change_notification_processing::ChangeID, number_space::NumberSpaceType, Error};
+use crate::{
+    change_notification_processing::{ChangeID, ChangeNotifier},
+    number_space::NumberSpaceType,
+    units::{MetersPerSecond, RadiansPerSecond},
+    Error,
+};
 
 use super::joint_state::{JointState, JointStateRange};
 
@@ -54,6 +59,39 @@ pub struct ActuatorAvailableRatesOfChange {
 }
 
 impl ActuatorAvailableRatesOfChange {
+    /// Returns the current minimum and maximum velocity of an actuator that drives a revolute
+    /// joint.
+    pub fn angular_velocity_limits(&self) -> (RadiansPerSecond, RadiansPerSecond) {
+        (
+            RadiansPerSecond::new(self.minimum_velocity),
+            RadiansPerSecond::new(self.maximum_velocity),
+        )
+    }
+
+    /// Returns a value indicating whether all the rates of change are finite, i.e. not NaN or
+    /// infinite.
+    pub fn is_finite(&self) -> bool {
+        [
+            self.minimum_velocity,
+            self.maximum_velocity,
+            self.minimum_acceleration,
+            self.maximum_acceleration,
+            self.minimum_jerk,
+            self.maximum_jerk,
+        ]
+        .iter()
+        .all(|v| v.is_finite())
+    }
+
+    /// Returns the current minimum and maximum velocity of an actuator that drives a prismatic
+    /// joint.
+    pub fn linear_velocity_limits(&self) -> (MetersPerSecond, MetersPerSecond) {
+        (
+            MetersPerSecond::new(self.minimum_velocity),
+            MetersPerSecond::new(self.maximum_velocity),
+        )
+    }
+
     /// Returns the current maximum acceleration.
     pub fn maximum_acceleration(&self) -> f64 {
         self.maximum_acceleration
@@ -152,5 +190,5 @@ pub trait HardwareActuator {
     /// Stores the notification function for updating the software actuator
     /// and the [ChangeID] that informs the software actuator which hardware
     /// actuator has been updated.
-    fn on_change(&mut self, id: ChangeID, notifier: Sender<ChangeID>);
+    fn on_change(&mut self, id: ChangeID, notifier: ChangeNotifier);
 }
@@ -23,3 +23,30 @@ fn test_getters() {
     assert_eq!(rates_of_change.minimum_jerk(), 5.0);
     assert_eq!(rates_of_change.maximum_jerk(), 6.0);
 }
+
+#[test]
+fn test_is_finite() {
+    let rates_of_change = ActuatorAvailableRatesOfChange::new(1.0, 2.0, 3.0, 4.0, 5.0, 6.0);
+    assert!(rates_of_change.is_finite());
+
+    let rates_of_change = ActuatorAvailableRatesOfChange::new(1.0, f64::NAN, 3.0, 4.0, 5.0, 6.0);
+    assert!(!rates_of_change.is_finite());
+
+    let rates_of_change =
+        ActuatorAvailableRatesOfChange::new(1.0, 2.0, 3.0, 4.0, 5.0, f64::INFINITY);
+    assert!(!rates_of_change.is_finite());
+}
+
+#[test]
+fn test_typed_velocity_limits() {
+    let rates_of_change = ActuatorAvailableRatesOfChange::new(-1.0, 2.0, 3.0, 4.0, 5.0, 6.0);
+
+    assert_eq!(
+        (RadiansPerSecond::new(-1.0), RadiansPerSecond::new(2.0)),
+        rates_of_change.angular_velocity_limits()
+    );
+    assert_eq!(
+        (MetersPerSecond::new(-1.0), MetersPerSecond::new(2.0)),
+        rates_of_change.linear_velocity_limits()
+    );
+}
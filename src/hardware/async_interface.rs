@@ -0,0 +1,64 @@
+//! Defines the asynchronous interfaces for actuators and sensors.
+//!
+//! The [HardwareActuator](super::actuator_interface::HardwareActuator) and
+//! [HardwareSensor](super::sensor_interface::HardwareSensor) traits are built around crossbeam
+//! channels that are processed on the background thread of the
+//! [HardwareChangeProcessor](crate::change_notification_processing::HardwareChangeProcessor).
+//! The traits in this module provide the same information through the channels of
+//! [tokio](https://tokio.rs), so that the hardware of a robot control stack that is built on
+//! tokio can be connected without bridging threads manually. The current state of the hardware
+//! is published through a [watch] channel and the commands are received through an [mpsc]
+//! channel. The states are processed by tasks on the runtime of an
+//! [AsyncHardwareChangeProcessor](crate::change_notification_processing::AsyncHardwareChangeProcessor).
+//!
+//! Use [Actuator::new_async](crate::model_elements::frame_elements::Actuator::new_async) and
+//! [JointSensor::new_async](crate::model_elements::frame_elements::JointSensor::new_async) to
+//! create the actuators and sensors for the model from the asynchronous hardware.
+//!
+//! This module is only available when the `async` feature is enabled.
+
+use tokio::sync::{mpsc, watch};
+
+use crate::{number_space::NumberSpaceType, Error};
+
+use super::{
+    actuator_interface::ActuatorAvailableRatesOfChange,
+    joint_state::{JointState, JointStateRange},
+};
+
+#[cfg(test)]
+#[path = "async_interface_tests.rs"]
+mod async_interface_tests;
+
+/// Defines the asynchronous interface for hardware that moves a robot joint element.
+pub trait AsyncHardwareActuator {
+    /// Returns the [NumberSpaceType] that is used to describe the motion of the actuator.
+    fn actuator_motion_type(&self) -> NumberSpaceType;
+
+    /// Returns the minimum and maximum states for the actuator.
+    fn actuator_range(&self) -> JointStateRange;
+
+    /// Returns the [mpsc::Sender] that is used to send the desired [JointState] to the
+    /// hardware. Commands are sent without waiting, a command that does not fit in the channel
+    /// is reported as an error.
+    fn command_sender(&self) -> Result<mpsc::Sender<JointState>, Error>;
+
+    /// Returns the [watch::Receiver] that is used to receive the current [JointState] and the
+    /// currently available minimum and maximum rate of change.
+    fn current_state_receiver(
+        &self,
+    ) -> Result<watch::Receiver<(JointState, ActuatorAvailableRatesOfChange)>, Error>;
+}
+
+/// Defines the asynchronous interface for hardware that senses the state of a robot joint
+/// element.
+pub trait AsyncHardwareSensor {
+    /// Returns the [watch::Receiver] that is used to receive the current [JointState].
+    fn current_state_receiver(&self) -> Result<watch::Receiver<JointState>, Error>;
+
+    /// Returns the [NumberSpaceType] that is used to describe the motion of the joint.
+    fn joint_motion_type(&self) -> NumberSpaceType;
+
+    /// Returns the minimum and maximum states for the sensor.
+    fn joint_range(&self) -> JointStateRange;
+}
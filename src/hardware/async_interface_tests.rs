@@ -0,0 +1,166 @@
+use tokio::{
+    runtime::{Builder, Runtime},
+    sync::{mpsc, watch},
+};
+
+use crate::{
+    change_notification_processing::AsyncHardwareChangeProcessor,
+    hardware::{
+        actuator_interface::ActuatorAvailableRatesOfChange,
+        joint_state::{JointState, JointStateRange},
+    },
+    model_elements::frame_elements::{Actuator, JointSensor},
+    number_space::NumberSpaceType,
+    Error,
+};
+
+use super::{AsyncHardwareActuator, AsyncHardwareSensor};
+
+struct MockAsyncActuator {
+    state_sender: watch::Sender<(JointState, ActuatorAvailableRatesOfChange)>,
+    command_sender: mpsc::Sender<JointState>,
+}
+
+impl AsyncHardwareActuator for MockAsyncActuator {
+    fn actuator_motion_type(&self) -> NumberSpaceType {
+        NumberSpaceType::LinearUnlimited
+    }
+
+    fn actuator_range(&self) -> JointStateRange {
+        JointStateRange::new(
+            JointState::new(-100.0, None, None, None),
+            JointState::new(100.0, None, None, None),
+        )
+    }
+
+    fn command_sender(&self) -> Result<mpsc::Sender<JointState>, Error> {
+        Ok(self.command_sender.clone())
+    }
+
+    fn current_state_receiver(
+        &self,
+    ) -> Result<watch::Receiver<(JointState, ActuatorAvailableRatesOfChange)>, Error> {
+        Ok(self.state_sender.subscribe())
+    }
+}
+
+struct MockAsyncSensor {
+    state_sender: watch::Sender<JointState>,
+}
+
+impl AsyncHardwareSensor for MockAsyncSensor {
+    fn current_state_receiver(&self) -> Result<watch::Receiver<JointState>, Error> {
+        Ok(self.state_sender.subscribe())
+    }
+
+    fn joint_motion_type(&self) -> NumberSpaceType {
+        NumberSpaceType::LinearUnlimited
+    }
+
+    fn joint_range(&self) -> JointStateRange {
+        JointStateRange::new(
+            JointState::new(-100.0, None, None, None),
+            JointState::new(100.0, None, None, None),
+        )
+    }
+}
+
+fn runtime() -> Runtime {
+    Builder::new_current_thread().build().unwrap()
+}
+
+fn rates() -> ActuatorAvailableRatesOfChange {
+    ActuatorAvailableRatesOfChange::new(0.0, 0.0, 0.0, 0.0, 0.0, 0.0)
+}
+
+// Gives the tasks on the runtime the chance to run until the condition holds.
+fn run_until(runtime: &Runtime, condition: impl Fn() -> bool) -> bool {
+    runtime.block_on(async {
+        for _ in 0..100 {
+            if condition() {
+                return true;
+            }
+
+            tokio::task::yield_now().await;
+        }
+
+        condition()
+    })
+}
+
+#[test]
+fn when_the_async_actuator_publishes_a_state_it_should_update_the_actuator() {
+    let runtime = runtime();
+    let processor = AsyncHardwareChangeProcessor::new(runtime.handle().clone());
+    let (state_sender, _) = watch::channel((JointState::new(0.5, None, None, None), rates()));
+    let (command_sender, _command_receiver) = mpsc::channel(1);
+    let hardware = MockAsyncActuator {
+        state_sender,
+        command_sender,
+    };
+
+    let actuator = Actuator::new_async(&hardware, &processor).unwrap();
+    assert_eq!(1, processor.number_of_active_tasks());
+
+    // The state that was published before the actuator was created is used
+    assert!(run_until(&runtime, || actuator.value().unwrap().position() == 0.5));
+
+    hardware
+        .state_sender
+        .send((JointState::new(1.0, Some(2.0), None, None), rates()))
+        .unwrap();
+    assert!(run_until(&runtime, || actuator.value().unwrap().position() == 1.0));
+    assert_eq!(&Some(2.0), actuator.value().unwrap().velocity());
+
+    // States that are not finite are rejected
+    hardware
+        .state_sender
+        .send((JointState::new(f64::NAN, None, None, None), rates()))
+        .unwrap();
+    assert!(run_until(&runtime, || actuator.rejected_state_count() == 1));
+    assert_eq!(1.0, actuator.value().unwrap().position());
+}
+
+#[test]
+fn when_updating_the_state_of_an_async_actuator_it_should_send_the_command() {
+    let runtime = runtime();
+    let processor = AsyncHardwareChangeProcessor::new(runtime.handle().clone());
+    let (state_sender, _) = watch::channel((JointState::new(0.0, None, None, None), rates()));
+    let (command_sender, mut command_receiver) = mpsc::channel(1);
+    let hardware = MockAsyncActuator {
+        state_sender,
+        command_sender,
+    };
+    let actuator = Actuator::new_async(&hardware, &processor).unwrap();
+
+    let command = JointState::new(1.0, Some(0.5), None, None);
+    actuator.update_state(command).unwrap();
+
+    // The channel is full until the hardware receives the command
+    assert!(matches!(
+        actuator.update_state(command),
+        Err(Error::Channel { .. })
+    ));
+    assert_eq!(command, command_receiver.try_recv().unwrap());
+}
+
+#[test]
+fn when_the_async_sensor_publishes_a_state_it_should_update_the_sensor() {
+    let runtime = runtime();
+    let processor = AsyncHardwareChangeProcessor::new(runtime.handle().clone());
+    let (state_sender, _) = watch::channel(JointState::new(0.0, None, None, None));
+    let hardware = MockAsyncSensor { state_sender };
+
+    let sensor = JointSensor::new_async(&hardware, &processor).unwrap();
+    assert_eq!(NumberSpaceType::LinearUnlimited, sensor.number_space_type());
+
+    hardware
+        .state_sender
+        .send(JointState::new(3.0, None, None, None))
+        .unwrap();
+    assert!(run_until(&runtime, || sensor.value().unwrap().position() == 3.0));
+
+    // The task stops once the hardware is gone
+    drop(hardware);
+    assert!(run_until(&runtime, || processor.number_of_active_tasks() == 0));
+}
@@ -0,0 +1,93 @@
+//! Defines the middleware that processes the commands sent to an actuator.
+//!
+//! Each [Actuator](crate::model_elements::frame_elements::Actuator) holds an ordered chain of
+//! [CommandMiddleware]. When a command is sent with
+//! [Actuator::update_state](crate::model_elements::frame_elements::Actuator::update_state) it is
+//! passed through each middleware in the order in which they were added, before being sent to
+//! the hardware. A middleware can transform the command, e.g. to convert units, compensate for a
+//! deadband or clamp the command to a safe range, or it can veto the command, in which case the
+//! command is not sent to the hardware at all.
+//!
+//! Any closure that takes a [JointState] and returns a [CommandOutcome] can be used as a
+//! middleware.
+//!
+//! ```
+//! use swerve_vehicle_descriptors::hardware::command_middleware::{CommandMiddleware, CommandOutcome};
+//! use swerve_vehicle_descriptors::hardware::joint_state::JointState;
+//!
+//! let clamp = |command: JointState| {
+//!     CommandOutcome::Forward(JointState::new(
+//!         command.position().clamp(-1.0, 1.0),
+//!         *command.velocity(),
+//!         *command.acceleration(),
+//!         *command.jerk(),
+//!     ))
+//! };
+//!
+//! let outcome = clamp.process(JointState::new(2.0, None, None, None));
+//! assert_eq!(outcome, CommandOutcome::Forward(JointState::new(1.0, None, None, None)));
+//! ```
+
+use super::joint_state::JointState;
+
+#[cfg(test)]
+#[path = "command_middleware_tests.rs"]
+mod command_middleware_tests;
+
+/// Describes the result of passing a command through a [CommandMiddleware].
+#[derive(Clone, Debug, PartialEq)]
+pub enum CommandOutcome {
+    /// The command should be passed on to the next middleware, or to the hardware if this was
+    /// the last middleware in the chain.
+    Forward(JointState),
+
+    /// The command should not be sent to the hardware.
+    Veto {
+        /// The reason why the command was vetoed.
+        reason: String,
+    },
+}
+
+/// Defines the interface for middleware that transforms or vetoes the commands that are sent
+/// to an actuator.
+///
+/// The middleware is executed on the thread that sends the command, so it should not block.
+pub trait CommandMiddleware: Send + Sync {
+    /// Processes the given command and returns the command that should be passed on, or a veto.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'command' - The command as produced by the previous middleware in the chain.
+    fn process(&self, command: JointState) -> CommandOutcome;
+}
+
+impl<F> CommandMiddleware for F
+where
+    F: Fn(JointState) -> CommandOutcome + Send + Sync,
+{
+    fn process(&self, command: JointState) -> CommandOutcome {
+        self(command)
+    }
+}
+
+/// Passes the command through each of the middleware in order. Returns the final command, or
+/// the first veto.
+///
+/// ## Parameters
+///
+/// * 'chain' - The middleware in the order in which they should be executed.
+/// * 'command' - The command that should be processed.
+pub(crate) fn process_command(
+    chain: &[Box<dyn CommandMiddleware>],
+    command: JointState,
+) -> CommandOutcome {
+    let mut current = command;
+    for middleware in chain {
+        match middleware.process(current) {
+            CommandOutcome::Forward(next) => current = next,
+            veto => return veto,
+        }
+    }
+
+    CommandOutcome::Forward(current)
+}
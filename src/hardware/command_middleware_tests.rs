@@ -0,0 +1,76 @@
+use super::*;
+
+fn offset_by(offset: f64) -> impl Fn(JointState) -> CommandOutcome {
+    move |command: JointState| {
+        CommandOutcome::Forward(JointState::new(
+            command.position() + offset,
+            *command.velocity(),
+            *command.acceleration(),
+            *command.jerk(),
+        ))
+    }
+}
+
+fn scale_by(factor: f64) -> impl Fn(JointState) -> CommandOutcome {
+    move |command: JointState| {
+        CommandOutcome::Forward(JointState::new(
+            command.position() * factor,
+            *command.velocity(),
+            *command.acceleration(),
+            *command.jerk(),
+        ))
+    }
+}
+
+#[test]
+fn when_processing_a_command_without_middleware_it_should_forward_the_command() {
+    let command = JointState::new(1.0, Some(2.0), None, None);
+    let outcome = process_command(&[], command);
+    assert_eq!(outcome, CommandOutcome::Forward(command));
+}
+
+#[test]
+fn when_processing_a_command_it_should_execute_the_middleware_in_order() {
+    let chain: Vec<Box<dyn CommandMiddleware>> =
+        vec![Box::new(offset_by(1.0)), Box::new(scale_by(2.0))];
+
+    let outcome = process_command(&chain, JointState::new(1.0, Some(2.0), None, None));
+    assert_eq!(
+        outcome,
+        CommandOutcome::Forward(JointState::new(4.0, Some(2.0), None, None))
+    );
+
+    let chain: Vec<Box<dyn CommandMiddleware>> =
+        vec![Box::new(scale_by(2.0)), Box::new(offset_by(1.0))];
+
+    let outcome = process_command(&chain, JointState::new(1.0, Some(2.0), None, None));
+    assert_eq!(
+        outcome,
+        CommandOutcome::Forward(JointState::new(3.0, Some(2.0), None, None))
+    );
+}
+
+#[test]
+fn when_a_middleware_vetoes_a_command_it_should_stop_the_chain() {
+    let executed = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+    let executed_clone = executed.clone();
+
+    let chain: Vec<Box<dyn CommandMiddleware>> = vec![
+        Box::new(|_: JointState| CommandOutcome::Veto {
+            reason: "too fast".to_string(),
+        }),
+        Box::new(move |command: JointState| {
+            executed_clone.store(true, std::sync::atomic::Ordering::SeqCst);
+            CommandOutcome::Forward(command)
+        }),
+    ];
+
+    let outcome = process_command(&chain, JointState::new(1.0, None, None, None));
+    assert_eq!(
+        outcome,
+        CommandOutcome::Veto {
+            reason: "too fast".to_string()
+        }
+    );
+    assert!(!executed.load(std::sync::atomic::Ordering::SeqCst));
+}
@@ -0,0 +1,70 @@
+//! Defines the health of the actuators of a vehicle.
+//!
+//! The health of an [Actuator](crate::model_elements::frame_elements::Actuator) is derived from
+//! the connection with the hardware, the time since the last state was received from the
+//! hardware and the last state itself:
+//!
+//! * An actuator is [ActuatorHealth::Faulted] when the channel to or from the hardware is
+//!   disconnected, or when the last state received from the hardware is outside of the
+//!   [JointConstraint](crate::model_elements::frame_elements::JointConstraint) of the actuator.
+//!   A disconnection is remembered until it is cleared with
+//!   [Actuator::clear_fault](crate::model_elements::frame_elements::Actuator::clear_fault).
+//! * An actuator is [ActuatorHealth::Stale] when it has a stale timeout and no state was
+//!   received from the hardware within that timeout, see
+//!   [Actuator::set_stale_timeout](crate::model_elements::frame_elements::Actuator::set_stale_timeout).
+//! * Otherwise the actuator is [ActuatorHealth::Ok].
+
+use std::fmt::Display;
+
+/// Defines the reasons why an actuator is faulted.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ActuatorFault {
+    /// The channel used to send commands to, or receive states from, the hardware was
+    /// disconnected, e.g. because the driver of the hardware stopped.
+    Disconnected,
+
+    /// The last state received from the hardware is outside of the limits of the actuator.
+    OutOfRange {
+        /// The quantity that is outside of its limits, e.g. `position`.
+        quantity: String,
+
+        /// The value of the quantity.
+        value: f64,
+    },
+}
+
+impl Display for ActuatorFault {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ActuatorFault::Disconnected => write!(f, "the hardware is disconnected"),
+            ActuatorFault::OutOfRange { quantity, value } => {
+                write!(f, "the {} {} is out of range", quantity, value)
+            }
+        }
+    }
+}
+
+/// Defines the health of an actuator.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ActuatorHealth {
+    /// The actuator is connected and reports valid states in time.
+    Ok,
+
+    /// The actuator has not reported a state within its stale timeout.
+    Stale,
+
+    /// The actuator has failed.
+    Faulted(ActuatorFault),
+}
+
+impl ActuatorHealth {
+    /// Returns a value indicating whether the actuator is faulted.
+    pub fn is_faulted(&self) -> bool {
+        matches!(self, ActuatorHealth::Faulted(_))
+    }
+
+    /// Returns a value indicating whether the actuator is healthy.
+    pub fn is_ok(&self) -> bool {
+        matches!(self, ActuatorHealth::Ok)
+    }
+}
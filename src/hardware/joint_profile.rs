@@ -0,0 +1,131 @@
+//! Provides time-parameterized profiles of joint states.
+//!
+//! A [JointProfile] describes how the state of a joint should change over time. Instead of
+//! sending a single set-point to an actuator, a profile is submitted with
+//! [Actuator::submit_profile](crate::model_elements::frame_elements::Actuator::submit_profile)
+//! after which the [HardwareChangeProcessor](crate::change_notification_processing::HardwareChangeProcessor)
+//! interpolates the profile and sends the interpolated state to the hardware each time it
+//! processes the hardware updates.
+
+use std::time::Duration;
+
+use crate::Error;
+
+use super::joint_state::JointState;
+
+#[cfg(test)]
+#[path = "joint_profile_tests.rs"]
+mod joint_profile_tests;
+
+/// Describes the desired state of a joint as a function of the time since the start of the
+/// profile.
+///
+/// The profile stores a set of points, each consisting of the time since the start of the
+/// profile and the desired state at that time. The states between the points are linearly
+/// interpolated. Before the first point the profile holds the state of the first point and
+/// after the last point it holds the state of the last point.
+#[derive(Clone, Debug, PartialEq)]
+pub struct JointProfile {
+    /// The times and the desired states of the points, in strictly increasing order of time.
+    points: Vec<(Duration, JointState)>,
+}
+
+impl JointProfile {
+    /// Returns the time of the last point of the profile, i.e. the time after which the state
+    /// no longer changes.
+    pub fn duration(&self) -> Duration {
+        self.points[self.points.len() - 1].0
+    }
+
+    /// Returns the state of the last point of the profile.
+    pub fn final_state(&self) -> &JointState {
+        &self.points[self.points.len() - 1].1
+    }
+
+    /// Creates a new [JointProfile] instance.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'points' - The times since the start of the profile and the desired states at those
+    ///   times. The times must be strictly increasing.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::InvalidJointProfile] - Returned when no points are provided, when a state is
+    ///   not finite or when the times are not strictly increasing.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use swerve_vehicle_descriptors::hardware::{
+    ///     joint_profile::JointProfile, joint_state::JointState,
+    /// };
+    ///
+    /// let profile = JointProfile::new(vec![
+    ///     (Duration::ZERO, JointState::new(0.0, None, None, None)),
+    ///     (Duration::from_secs(1), JointState::new(1.0, None, None, None)),
+    /// ])
+    /// .unwrap();
+    ///
+    /// let state = profile.state_at(Duration::from_millis(250));
+    /// assert_eq!(0.25, state.position());
+    /// ```
+    pub fn new(points: Vec<(Duration, JointState)>) -> Result<Self, Error> {
+        if points.is_empty() {
+            return Err(Error::InvalidJointProfile);
+        }
+
+        if points.iter().any(|(_, state)| !state.is_finite()) {
+            return Err(Error::InvalidJointProfile);
+        }
+
+        if points.windows(2).any(|pair| pair[0].0 >= pair[1].0) {
+            return Err(Error::InvalidJointProfile);
+        }
+
+        Ok(Self { points })
+    }
+
+    /// Returns the times and the desired states of the points of the profile.
+    pub fn points(&self) -> &[(Duration, JointState)] {
+        &self.points
+    }
+
+    /// Returns the desired state of the joint at the given time.
+    ///
+    /// The position and the derivatives of the position are interpolated linearly between the
+    /// points on either side of the given time. A derivative that is not known for one of these
+    /// points is not known for the interpolated state either.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'elapsed' - The time since the start of the profile.
+    pub fn state_at(&self, elapsed: Duration) -> JointState {
+        let index = self.points.partition_point(|(time, _)| *time <= elapsed);
+        if index == 0 {
+            return self.points[0].1;
+        }
+
+        if index == self.points.len() {
+            return *self.final_state();
+        }
+
+        let (start_time, start) = &self.points[index - 1];
+        let (end_time, end) = &self.points[index];
+        let fraction =
+            (elapsed - *start_time).as_secs_f64() / (*end_time - *start_time).as_secs_f64();
+        let interpolate = |a: f64, b: f64| a + (b - a) * fraction;
+        let interpolate_option = |a: &Option<f64>, b: &Option<f64>| match (a, b) {
+            (Some(a), Some(b)) => Some(interpolate(*a, *b)),
+            _ => None,
+        };
+
+        JointState::new(
+            interpolate(start.position(), end.position()),
+            interpolate_option(start.velocity(), end.velocity()),
+            interpolate_option(start.acceleration(), end.acceleration()),
+            interpolate_option(start.jerk(), end.jerk()),
+        )
+    }
+}
@@ -0,0 +1,60 @@
+use std::time::Duration;
+
+use crate::{hardware::joint_state::JointState, Error};
+
+use super::JointProfile;
+
+fn state(position: f64, velocity: Option<f64>) -> JointState {
+    JointState::new(position, velocity, None, None)
+}
+
+#[test]
+fn when_creating_a_profile_with_invalid_points_it_should_error() {
+    assert_eq!(Err(Error::InvalidJointProfile), JointProfile::new(vec![]));
+    assert_eq!(
+        Err(Error::InvalidJointProfile),
+        JointProfile::new(vec![
+            (Duration::ZERO, state(0.0, None)),
+            (Duration::from_secs(1), state(f64::NAN, None)),
+        ])
+    );
+    assert_eq!(
+        Err(Error::InvalidJointProfile),
+        JointProfile::new(vec![
+            (Duration::from_secs(1), state(0.0, None)),
+            (Duration::from_secs(1), state(1.0, None)),
+        ])
+    );
+}
+
+#[test]
+fn when_getting_the_state_of_a_profile_it_should_interpolate_between_the_points() {
+    let profile = JointProfile::new(vec![
+        (Duration::from_millis(100), state(1.0, Some(0.0))),
+        (Duration::from_millis(300), state(2.0, Some(4.0))),
+        (Duration::from_millis(500), state(0.0, None)),
+    ])
+    .unwrap();
+    assert_eq!(Duration::from_millis(500), profile.duration());
+    assert_eq!(&state(0.0, None), profile.final_state());
+    assert_eq!(3, profile.points().len());
+
+    // Outside of the profile the state of the nearest point is held
+    assert_eq!(state(1.0, Some(0.0)), profile.state_at(Duration::ZERO));
+    assert_eq!(state(0.0, None), profile.state_at(Duration::from_secs(1)));
+
+    assert_eq!(
+        state(1.0, Some(0.0)),
+        profile.state_at(Duration::from_millis(100))
+    );
+    assert_eq!(
+        state(1.5, Some(2.0)),
+        profile.state_at(Duration::from_millis(200))
+    );
+
+    // A derivative that is unknown at either end is unknown in between
+    assert_eq!(
+        state(1.0, None),
+        profile.state_at(Duration::from_millis(400))
+    );
+}
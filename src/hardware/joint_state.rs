@@ -1,5 +1,9 @@
 //! Provides structures that describe the joint state
 
+use std::time::{Duration, Instant};
+
+use crate::units::{Meters, MetersPerSecond, Radians, RadiansPerSecond};
+
 #[cfg(test)]
 #[path = "joint_state_tests.rs"]
 mod joint_state_tests;
@@ -15,6 +19,11 @@ mod joint_state_tests;
 /// All values are assumed to be in the range of the [minimum, maximum] value
 /// for the joint. These minimum and maximum values are specified by the
 /// [JointStateRange].
+///
+/// The values are stored in SI units, i.e. radians for a revolute joint and meters for a
+/// prismatic joint. The [JointState::new_angular] and [JointState::new_linear] functions, and
+/// the matching accessors, use the types from the [units](crate::units) module so that the
+/// unit of the position and the velocity is checked by the compiler.
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub struct JointState {
     /// The position of the joint.
@@ -36,11 +45,40 @@ impl JointState {
         &self.acceleration
     }
 
+    /// Returns the position of a revolute joint.
+    pub fn angular_position(&self) -> Radians {
+        Radians::new(self.position)
+    }
+
+    /// Returns the velocity of a revolute joint, or [None] if the velocity is not known.
+    pub fn angular_velocity(&self) -> Option<RadiansPerSecond> {
+        self.velocity.map(RadiansPerSecond::new)
+    }
+
+    /// Returns a value indicating whether the position and all the known derivatives of the
+    /// position are finite, i.e. not NaN or infinite.
+    pub fn is_finite(&self) -> bool {
+        self.position.is_finite()
+            && [self.velocity, self.acceleration, self.jerk]
+                .iter()
+                .all(|v| v.map_or(true, f64::is_finite))
+    }
+
     /// Returns the current jerk of the joint.
     pub fn jerk(&self) -> &Option<f64> {
         &self.jerk
     }
 
+    /// Returns the position of a prismatic joint.
+    pub fn linear_position(&self) -> Meters {
+        Meters::new(self.position)
+    }
+
+    /// Returns the velocity of a prismatic joint, or [None] if the velocity is not known.
+    pub fn linear_velocity(&self) -> Option<MetersPerSecond> {
+        self.velocity.map(MetersPerSecond::new)
+    }
+
     /// Returns the current position of the joint
     pub fn position(&self) -> f64 {
         self.position
@@ -72,6 +110,75 @@ impl JointState {
             jerk,
         }
     }
+
+    /// Creates a new [JointState] instance for a revolute joint, with an unknown acceleration
+    /// and jerk.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'position' - The current angle of the joint, either in [Radians] or in
+    ///   [Degrees](crate::units::Degrees).
+    /// * 'velocity' - The current angular velocity of the joint
+    pub fn new_angular(position: impl Into<Radians>, velocity: Option<RadiansPerSecond>) -> Self {
+        Self::new(
+            position.into().value(),
+            velocity.map(|v| v.value()),
+            None,
+            None,
+        )
+    }
+
+    /// Creates a new [JointState] instance for a prismatic joint, with an unknown acceleration
+    /// and jerk.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'position' - The current position of the joint
+    /// * 'velocity' - The current linear velocity of the joint
+    pub fn new_linear(position: Meters, velocity: Option<MetersPerSecond>) -> Self {
+        Self::new(position.value(), velocity.map(|v| v.value()), None, None)
+    }
+}
+
+/// Stores a [JointState] together with the time at which it was received from the hardware.
+///
+/// The time is captured when the change processor stores the state, after the state was
+/// processed by the state middleware, so it describes how fresh the state is rather than when
+/// it was measured by the hardware.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TimestampedJointState {
+    /// The state of the joint.
+    state: JointState,
+
+    /// The time at which the state was received.
+    received_at: Instant,
+}
+
+impl TimestampedJointState {
+    /// Returns the amount of time that has passed since the state was received.
+    pub fn age(&self) -> Duration {
+        self.received_at.elapsed()
+    }
+
+    /// Creates a new [TimestampedJointState] instance
+    ///
+    /// ## Parameters
+    ///
+    /// * 'state' - The state of the joint
+    /// * 'received_at' - The time at which the state was received
+    pub fn new(state: JointState, received_at: Instant) -> Self {
+        Self { state, received_at }
+    }
+
+    /// Returns the time at which the state was received.
+    pub fn received_at(&self) -> Instant {
+        self.received_at
+    }
+
+    /// Returns the state of the joint.
+    pub fn state(&self) -> &JointState {
+        &self.state
+    }
 }
 
 /// Stores the maximum and minimum values for the [JointState] of an
@@ -86,6 +193,38 @@ pub struct JointStateRange {
 }
 
 impl JointStateRange {
+    /// Returns the minimum and maximum position of a revolute joint.
+    pub fn angular_position_limits(&self) -> (Radians, Radians) {
+        (
+            self.minimum.angular_position(),
+            self.maximum.angular_position(),
+        )
+    }
+
+    /// Returns the minimum and maximum velocity of a revolute joint, or [None] if either of
+    /// the limits is not known.
+    pub fn angular_velocity_limits(&self) -> Option<(RadiansPerSecond, RadiansPerSecond)> {
+        self.minimum
+            .angular_velocity()
+            .zip(self.maximum.angular_velocity())
+    }
+
+    /// Returns the minimum and maximum position of a prismatic joint.
+    pub fn linear_position_limits(&self) -> (Meters, Meters) {
+        (
+            self.minimum.linear_position(),
+            self.maximum.linear_position(),
+        )
+    }
+
+    /// Returns the minimum and maximum velocity of a prismatic joint, or [None] if either of
+    /// the limits is not known.
+    pub fn linear_velocity_limits(&self) -> Option<(MetersPerSecond, MetersPerSecond)> {
+        self.minimum
+            .linear_velocity()
+            .zip(self.maximum.linear_velocity())
+    }
+
     /// Gets the maximum acceleration for the joint.
     pub fn maximum_acceleration(&self) -> &Option<f64> {
         self.maximum.acceleration()
@@ -1,4 +1,5 @@
 use super::*;
+use crate::units::Degrees;
 
 #[test]
 fn test_new_joint_state() {
@@ -41,3 +42,82 @@ fn test_joint_state_defaults() {
     assert!(joint_state.acceleration().is_none());
     assert!(joint_state.jerk().is_none());
 }
+
+#[test]
+fn test_joint_state_is_finite() {
+    assert!(JointState::new(1.0, None, None, None).is_finite());
+    assert!(JointState::new(1.0, Some(2.0), Some(3.0), Some(4.0)).is_finite());
+
+    assert!(!JointState::new(f64::NAN, None, None, None).is_finite());
+    assert!(!JointState::new(f64::INFINITY, None, None, None).is_finite());
+    assert!(!JointState::new(1.0, Some(f64::NAN), None, None).is_finite());
+    assert!(!JointState::new(1.0, None, Some(f64::NEG_INFINITY), None).is_finite());
+    assert!(!JointState::new(1.0, None, None, Some(f64::NAN)).is_finite());
+}
+
+#[test]
+fn test_timestamped_joint_state() {
+    let state = JointState::new(1.0, Some(2.0), None, None);
+    let received_at = Instant::now() - Duration::from_millis(50);
+
+    let timestamped = TimestampedJointState::new(state, received_at);
+    assert_eq!(&state, timestamped.state());
+    assert_eq!(received_at, timestamped.received_at());
+    assert!(timestamped.age() >= Duration::from_millis(50));
+}
+
+#[test]
+fn test_typed_joint_state() {
+    let joint_state =
+        JointState::new_angular(Degrees::new(180.0), Some(RadiansPerSecond::new(2.0)));
+    assert!((std::f64::consts::PI - joint_state.position()).abs() < 1e-12);
+    assert_eq!(
+        joint_state.angular_position(),
+        Radians::new(joint_state.position())
+    );
+    assert_eq!(
+        Some(RadiansPerSecond::new(2.0)),
+        joint_state.angular_velocity()
+    );
+    assert!(joint_state.acceleration().is_none());
+    assert!(joint_state.jerk().is_none());
+
+    let joint_state = JointState::new_linear(Meters::new(0.1), None);
+    assert_eq!(0.1, joint_state.position());
+    assert_eq!(Meters::new(0.1), joint_state.linear_position());
+    assert_eq!(None, joint_state.linear_velocity());
+}
+
+#[test]
+fn test_typed_joint_state_range() {
+    let range = JointStateRange::new(
+        JointState::new(-1.0, Some(-2.0), None, None),
+        JointState::new(1.0, None, None, None),
+    );
+
+    assert_eq!(
+        (Radians::new(-1.0), Radians::new(1.0)),
+        range.angular_position_limits()
+    );
+    assert_eq!(
+        (Meters::new(-1.0), Meters::new(1.0)),
+        range.linear_position_limits()
+    );
+
+    // Both limits are needed for the velocity limits
+    assert_eq!(None, range.angular_velocity_limits());
+    assert_eq!(None, range.linear_velocity_limits());
+
+    let range = JointStateRange::new(
+        JointState::new(-1.0, Some(-2.0), None, None),
+        JointState::new(1.0, Some(2.0), None, None),
+    );
+    assert_eq!(
+        Some((RadiansPerSecond::new(-2.0), RadiansPerSecond::new(2.0))),
+        range.angular_velocity_limits()
+    );
+    assert_eq!(
+        Some((MetersPerSecond::new(-2.0), MetersPerSecond::new(2.0))),
+        range.linear_velocity_limits()
+    );
+}
@@ -1,8 +1,12 @@
 //! Defines the interface for sensors
 
-use crossbeam_channel::{Receiver, Sender};
+use crossbeam_channel::Receiver;
 
-use crate::{change_notification_processing::ChangeID, number_space::NumberSpaceType, Error};
+use crate::{
+    change_notification_processing::{ChangeID, ChangeNotifier},
+    number_space::NumberSpaceType,
+    Error,
+};
 
 use super::joint_state::{JointState, JointStateRange};
 
@@ -21,5 +25,5 @@ pub trait HardwareSensor {
     /// Stores the notification function for updating the software actuator
     /// and the [ChangeID] that informs the software actuator which hardware
     /// actuator has been updated.
-    fn on_change(&mut self, id: ChangeID, notifier: Sender<ChangeID>);
+    fn on_change(&mut self, id: ChangeID, notifier: ChangeNotifier);
 }
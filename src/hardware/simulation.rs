@@ -0,0 +1,737 @@
+//! Provides simulated hardware that behaves deterministically.
+//!
+//! A [SimulatedActuator] implements the [HardwareActuator] trait and follows the commands that it
+//! receives with the dynamics described by a [SimulatedDynamics], i.e. a first-order lag that is
+//! limited in velocity and acceleration, with optional measurement noise. A [SimulatedSensor]
+//! implements the [HardwareSensor] trait and reports either a fixed state or the state of a
+//! [SimulatedActuator].
+//!
+//! Simulated hardware never changes on its own. The states are advanced in fixed steps by a
+//! [SimulationClock], or by calling [SimulatedActuator::step] and [SimulatedSensor::step]
+//! directly. The noise is drawn from a pseudo-random generator with a fixed seed. Combined with
+//! a [HardwareChangeProcessor::manual](crate::change_notification_processing::HardwareChangeProcessor::manual)
+//! processor a complete model can be simulated, and tested, without real hardware and without
+//! waiting for a background thread.
+//!
+//! ## Examples
+//!
+//! ```
+//! use std::time::Duration;
+//! use swerve_vehicle_descriptors::change_notification_processing::HardwareChangeProcessor;
+//! use swerve_vehicle_descriptors::hardware::joint_state::{JointState, JointStateRange};
+//! use swerve_vehicle_descriptors::hardware::simulation::{
+//!     SimulatedActuator, SimulatedDynamics, SimulationClock,
+//! };
+//! use swerve_vehicle_descriptors::model_elements::frame_elements::Actuator;
+//! use swerve_vehicle_descriptors::number_space::NumberSpaceType;
+//!
+//! let processor = HardwareChangeProcessor::manual();
+//! let mut hardware = SimulatedActuator::new(
+//!     NumberSpaceType::LinearUnlimited,
+//!     JointStateRange::new(
+//!         JointState::new(-10.0, None, None, None),
+//!         JointState::new(10.0, None, None, None),
+//!     ),
+//!     SimulatedDynamics::new().with_velocity_limits(-1.0, 1.0),
+//! );
+//! let actuator = Actuator::new(&mut hardware, &processor).unwrap();
+//!
+//! let mut clock = SimulationClock::new();
+//! clock.add_actuator(&hardware);
+//!
+//! actuator
+//!     .update_state(JointState::new(0.5, None, None, None))
+//!     .unwrap();
+//! for _ in 0..10 {
+//!     clock.tick(Duration::from_millis(100));
+//!     processor.process_pending();
+//! }
+//!
+//! assert_eq!(Duration::from_secs(1), clock.elapsed());
+//! assert!((0.5 - actuator.value().unwrap().position()).abs() < 1e-9);
+//! ```
+
+use std::{
+    f64::consts::PI,
+    sync::{Arc, Mutex, MutexGuard},
+    time::Duration,
+};
+
+use crossbeam_channel::{Receiver, Sender};
+
+use crate::{
+    change_notification_processing::{ChangeID, ChangeNotifier},
+    number_space::{to_number_space, NumberSpaceType},
+    Error,
+};
+
+use super::{
+    actuator_interface::{ActuatorAvailableRatesOfChange, HardwareActuator},
+    joint_state::{JointState, JointStateRange},
+    sensor_interface::HardwareSensor,
+};
+
+#[cfg(test)]
+#[path = "simulation_tests.rs"]
+mod simulation_tests;
+
+/// The seed of the noise generator when no seed is provided.
+const DEFAULT_NOISE_SEED: u64 = 0x5eed;
+
+/// Describes how a [SimulatedActuator] follows the commands that it receives.
+///
+/// The position of the actuator follows the commanded position with a first-order lag. A command
+/// that has a velocity moves the commanded position along with that velocity until the next
+/// command is received. The velocity and the acceleration of the actuator are limited to the
+/// given limits. Noise, drawn from a normal distribution, is added to the reported position and
+/// velocity, but not to the internal state of the actuator.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SimulatedDynamics {
+    /// The time constant of the first-order lag. A zero time constant reaches the commanded
+    /// position in a single step, unless the limits prevent that.
+    time_constant: Duration,
+
+    /// The minimum and maximum velocity, or [None] if the velocity is not limited.
+    velocity_limits: Option<(f64, f64)>,
+
+    /// The minimum and maximum acceleration, or [None] if the acceleration is not limited.
+    acceleration_limits: Option<(f64, f64)>,
+
+    /// The standard deviation of the noise on the reported position.
+    position_noise: f64,
+
+    /// The standard deviation of the noise on the reported velocity.
+    velocity_noise: f64,
+
+    /// The seed of the noise generator.
+    seed: u64,
+}
+
+impl SimulatedDynamics {
+    /// Returns the minimum and maximum acceleration, or [None] if the acceleration is not
+    /// limited.
+    pub fn acceleration_limits(&self) -> Option<(f64, f64)> {
+        self.acceleration_limits
+    }
+
+    /// Creates a new [SimulatedDynamics] instance without lag, without limits and without noise.
+    pub fn new() -> Self {
+        Self {
+            time_constant: Duration::ZERO,
+            velocity_limits: None,
+            acceleration_limits: None,
+            position_noise: 0.0,
+            velocity_noise: 0.0,
+            seed: DEFAULT_NOISE_SEED,
+        }
+    }
+
+    /// Returns the standard deviation of the noise on the reported position.
+    pub fn position_noise(&self) -> f64 {
+        self.position_noise
+    }
+
+    /// Returns the seed of the noise generator.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Returns the time constant of the first-order lag.
+    pub fn time_constant(&self) -> Duration {
+        self.time_constant
+    }
+
+    /// Returns the minimum and maximum velocity, or [None] if the velocity is not limited.
+    pub fn velocity_limits(&self) -> Option<(f64, f64)> {
+        self.velocity_limits
+    }
+
+    /// Returns the standard deviation of the noise on the reported velocity.
+    pub fn velocity_noise(&self) -> f64 {
+        self.velocity_noise
+    }
+
+    /// Returns the dynamics with the acceleration limited to the given range.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'minimum' - The minimum acceleration, normally a negative number.
+    /// * 'maximum' - The maximum acceleration.
+    pub fn with_acceleration_limits(mut self, minimum: f64, maximum: f64) -> Self {
+        self.acceleration_limits = Some((minimum, maximum));
+        self
+    }
+
+    /// Returns the dynamics with noise added to the reported states.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'position' - The standard deviation of the noise on the reported position.
+    /// * 'velocity' - The standard deviation of the noise on the reported velocity.
+    /// * 'seed' - The seed of the noise generator. Actuators with the same seed report the
+    ///   same noise.
+    pub fn with_noise(mut self, position: f64, velocity: f64, seed: u64) -> Self {
+        self.position_noise = position;
+        self.velocity_noise = velocity;
+        self.seed = seed;
+        self
+    }
+
+    /// Returns the dynamics with the given time constant for the first-order lag.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'time_constant' - The time it takes to cover 63% of the distance to the commanded
+    ///   position.
+    pub fn with_time_constant(mut self, time_constant: Duration) -> Self {
+        self.time_constant = time_constant;
+        self
+    }
+
+    /// Returns the dynamics with the velocity limited to the given range.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'minimum' - The minimum velocity, normally a negative number.
+    /// * 'maximum' - The maximum velocity.
+    pub fn with_velocity_limits(mut self, minimum: f64, maximum: f64) -> Self {
+        self.velocity_limits = Some((minimum, maximum));
+        self
+    }
+}
+
+impl Default for SimulatedDynamics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A deterministic source of normally distributed noise.
+#[derive(Clone, Debug)]
+struct NoiseSource {
+    /// The state of the SplitMix64 generator.
+    state: u64,
+}
+
+impl NoiseSource {
+    fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// Returns a sample from a normal distribution with a mean of zero and the given standard
+    /// deviation.
+    fn sample(&mut self, standard_deviation: f64) -> f64 {
+        if standard_deviation == 0.0 {
+            return 0.0;
+        }
+
+        // Box-Muller transform. The first uniform sample must not be zero.
+        let u1 = 1.0 - self.next_uniform();
+        let u2 = self.next_uniform();
+        standard_deviation * (-2.0 * u1.ln()).sqrt() * (2.0 * PI * u2).cos()
+    }
+
+    /// Returns a uniformly distributed number in [0, 1).
+    fn next_uniform(&mut self) -> f64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^= z >> 31;
+        (z >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+/// The state that is shared between the copies of a [SimulatedActuator].
+struct SimulatedActuatorState {
+    /// The motion type of the actuator.
+    motion_type: NumberSpaceType,
+
+    /// The range of the actuator.
+    range: JointStateRange,
+
+    /// The dynamics of the actuator.
+    dynamics: SimulatedDynamics,
+
+    /// The true state of the actuator.
+    state: JointState,
+
+    /// The last command and the time that has passed since it was received.
+    command: Option<(JointState, f64)>,
+
+    /// The noise generator.
+    noise: NoiseSource,
+
+    /// The notifier and the [ChangeID] for the software actuator.
+    notifier: Option<(ChangeID, ChangeNotifier)>,
+}
+
+/// A simulated actuator that follows its commands with the given [SimulatedDynamics].
+///
+/// The copies of a [SimulatedActuator] share the same state, so that a copy can be handed to a
+/// [SimulationClock] while the original is used to create an
+/// [Actuator](crate::model_elements::frame_elements::Actuator).
+#[derive(Clone)]
+pub struct SimulatedActuator {
+    /// The state that is shared between the copies.
+    inner: Arc<Mutex<SimulatedActuatorState>>,
+
+    /// The sender for the states that are reported to the software actuator.
+    state_sender: Sender<(JointState, ActuatorAvailableRatesOfChange)>,
+
+    /// The receiver for the states that are reported to the software actuator.
+    state_receiver: Receiver<(JointState, ActuatorAvailableRatesOfChange)>,
+
+    /// The sender for the commands from the software actuator.
+    command_sender: Sender<JointState>,
+
+    /// The receiver for the commands from the software actuator.
+    command_receiver: Receiver<JointState>,
+}
+
+impl SimulatedActuator {
+    /// Returns the dynamics of the actuator.
+    pub fn dynamics(&self) -> SimulatedDynamics {
+        self.lock().dynamics
+    }
+
+    /// Creates a new [SimulatedActuator] instance that is at rest at position zero.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'motion_type' - The [NumberSpaceType] that describes the motion of the actuator.
+    /// * 'range' - The minimum and maximum states of the actuator.
+    /// * 'dynamics' - Describes how the actuator follows its commands.
+    pub fn new(
+        motion_type: NumberSpaceType,
+        range: JointStateRange,
+        dynamics: SimulatedDynamics,
+    ) -> Self {
+        let (state_sender, state_receiver) = crossbeam_channel::unbounded();
+        let (command_sender, command_receiver) = crossbeam_channel::unbounded();
+        Self {
+            inner: Arc::new(Mutex::new(SimulatedActuatorState {
+                motion_type,
+                range,
+                dynamics,
+                state: JointState::new(0.0, Some(0.0), Some(0.0), None),
+                command: None,
+                noise: NoiseSource::new(dynamics.seed),
+                notifier: None,
+            })),
+            state_sender,
+            state_receiver,
+            command_sender,
+            command_receiver,
+        }
+    }
+
+    /// Moves the actuator to the given state and reports that state.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'state' - The new state of the actuator.
+    pub fn set_state(&self, state: JointState) {
+        let mut inner = self.lock();
+        inner.state = state;
+        self.publish(&mut inner);
+    }
+
+    /// Returns the true state of the actuator, i.e. without noise.
+    pub fn state(&self) -> JointState {
+        self.lock().state
+    }
+
+    /// Advances the actuator by the given time step and reports the new state.
+    ///
+    /// The commands that were received since the last step are processed first. The last of
+    /// those commands becomes the new target of the actuator.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'time_step' - The amount of time that has passed since the last step.
+    pub fn step(&self, time_step: Duration) {
+        let mut inner = self.lock();
+        if let Some(command) = self.command_receiver.try_iter().last() {
+            inner.command = Some((command, 0.0));
+        }
+
+        let dt = time_step.as_secs_f64();
+        if dt > 0.0 {
+            let space = to_number_space(inner.motion_type);
+            let dynamics = inner.dynamics;
+            let position = inner.state.position();
+            let velocity = inner.state.velocity().unwrap_or(0.0);
+
+            // Follow the commanded position, which moves along with the commanded velocity
+            let mut desired_velocity = match inner.command.as_mut() {
+                Some((command, elapsed)) => {
+                    *elapsed += dt;
+                    let target = space.normalize_value(
+                        command.position() + command.velocity().unwrap_or(0.0) * *elapsed,
+                    );
+                    let time_constant = dynamics.time_constant.as_secs_f64().max(dt);
+                    space.smallest_distance_between_values(position, target) / time_constant
+                }
+                None => 0.0,
+            };
+            // Not using f64::clamp because that panics when the limits are not ordered
+            if let Some((minimum, maximum)) = dynamics.velocity_limits {
+                desired_velocity = desired_velocity.max(minimum).min(maximum);
+            }
+
+            let mut acceleration = (desired_velocity - velocity) / dt;
+            if let Some((minimum, maximum)) = dynamics.acceleration_limits {
+                acceleration = acceleration.max(minimum).min(maximum);
+            }
+
+            let new_velocity = velocity + acceleration * dt;
+            inner.state = JointState::new(
+                space.normalize_value(position + new_velocity * dt),
+                Some(new_velocity),
+                Some(acceleration),
+                None,
+            );
+        }
+
+        self.publish(&mut inner);
+    }
+
+    fn lock(&self) -> MutexGuard<'_, SimulatedActuatorState> {
+        self.inner.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    /// Sends the current state, with noise, to the software actuator. Nothing is sent until a
+    /// software actuator is connected.
+    fn publish(&self, inner: &mut SimulatedActuatorState) {
+        let (id, notifier) = match &inner.notifier {
+            Some((id, notifier)) => (*id, notifier.clone()),
+            None => return,
+        };
+
+        let dynamics = inner.dynamics;
+        let position_noise = inner.noise.sample(dynamics.position_noise);
+        let velocity_noise = inner.noise.sample(dynamics.velocity_noise);
+        let measured = JointState::new(
+            to_number_space(inner.motion_type)
+                .normalize_value(inner.state.position() + position_noise),
+            inner.state.velocity().map(|v| v + velocity_noise),
+            *inner.state.acceleration(),
+            *inner.state.jerk(),
+        );
+
+        let (minimum_velocity, maximum_velocity) = dynamics.velocity_limits.unwrap_or((
+            inner.range.minimum_velocity().unwrap_or(-f64::MAX),
+            inner.range.maximum_velocity().unwrap_or(f64::MAX),
+        ));
+        let (minimum_acceleration, maximum_acceleration) =
+            dynamics.acceleration_limits.unwrap_or((
+                inner.range.minimum_acceleration().unwrap_or(-f64::MAX),
+                inner.range.maximum_acceleration().unwrap_or(f64::MAX),
+            ));
+        let rates = ActuatorAvailableRatesOfChange::new(
+            minimum_velocity,
+            maximum_velocity,
+            minimum_acceleration,
+            maximum_acceleration,
+            -f64::MAX,
+            f64::MAX,
+        );
+
+        // The receiver is owned by this actuator, so sending the state can not fail
+        let _ = self.state_sender.send((measured, rates));
+        let _ = notifier.send(id);
+    }
+}
+
+impl HardwareActuator for SimulatedActuator {
+    fn actuator_motion_type(&self) -> NumberSpaceType {
+        self.lock().motion_type
+    }
+
+    fn actuator_range(&self) -> JointStateRange {
+        self.lock().range
+    }
+
+    fn command_sender(&self) -> Result<Sender<JointState>, Error> {
+        Ok(self.command_sender.clone())
+    }
+
+    fn current_state_receiver(
+        &self,
+    ) -> Result<Receiver<(JointState, ActuatorAvailableRatesOfChange)>, Error> {
+        Ok(self.state_receiver.clone())
+    }
+
+    fn on_change(&mut self, id: ChangeID, notifier: ChangeNotifier) {
+        let mut inner = self.lock();
+        inner.notifier = Some((id, notifier));
+
+        // Provide the software actuator with the initial state
+        self.publish(&mut inner);
+    }
+}
+
+/// The source of the states that are reported by a [SimulatedSensor].
+enum SensorSource {
+    /// The sensor reports a fixed state.
+    Fixed(JointState),
+
+    /// The sensor reports the state of a simulated actuator.
+    Actuator(Arc<Mutex<SimulatedActuatorState>>),
+}
+
+/// The state that is shared between the copies of a [SimulatedSensor].
+struct SimulatedSensorState {
+    /// The motion type of the joint.
+    motion_type: NumberSpaceType,
+
+    /// The range of the joint.
+    range: JointStateRange,
+
+    /// The source of the reported states.
+    source: SensorSource,
+
+    /// The standard deviation of the noise on the reported position.
+    position_noise: f64,
+
+    /// The standard deviation of the noise on the reported velocity.
+    velocity_noise: f64,
+
+    /// The noise generator.
+    noise: NoiseSource,
+
+    /// The notifier and the [ChangeID] for the software sensor.
+    notifier: Option<(ChangeID, ChangeNotifier)>,
+}
+
+/// A simulated sensor that reports either a fixed state or the state of a [SimulatedActuator].
+///
+/// The copies of a [SimulatedSensor] share the same state, so that a copy can be handed to a
+/// [SimulationClock] while the original is used to create a
+/// [JointSensor](crate::model_elements::frame_elements::JointSensor).
+#[derive(Clone)]
+pub struct SimulatedSensor {
+    /// The state that is shared between the copies.
+    inner: Arc<Mutex<SimulatedSensorState>>,
+
+    /// The sender for the states that are reported to the software sensor.
+    state_sender: Sender<JointState>,
+
+    /// The receiver for the states that are reported to the software sensor.
+    state_receiver: Receiver<JointState>,
+}
+
+impl SimulatedSensor {
+    /// Creates a new [SimulatedSensor] instance that reports the true state of the given
+    /// actuator, without the noise of the actuator.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'actuator' - The actuator of which the state is reported.
+    pub fn measuring(actuator: &SimulatedActuator) -> Self {
+        let (motion_type, range) = {
+            let inner = actuator.lock();
+            (inner.motion_type, inner.range)
+        };
+        Self::with_source(
+            motion_type,
+            range,
+            SensorSource::Actuator(actuator.inner.clone()),
+        )
+    }
+
+    /// Creates a new [SimulatedSensor] instance that reports the given state.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'motion_type' - The [NumberSpaceType] that describes the motion of the joint.
+    /// * 'range' - The minimum and maximum states of the joint.
+    /// * 'state' - The state that is reported.
+    pub fn new(motion_type: NumberSpaceType, range: JointStateRange, state: JointState) -> Self {
+        Self::with_source(motion_type, range, SensorSource::Fixed(state))
+    }
+
+    /// Changes the state that is reported by the sensor. A sensor that measured an actuator
+    /// reports the given state from now on.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'state' - The state that is reported.
+    pub fn set_state(&self, state: JointState) {
+        self.lock().source = SensorSource::Fixed(state);
+    }
+
+    /// Returns the true state that the sensor measures, i.e. without noise.
+    pub fn state(&self) -> JointState {
+        Self::true_state(&self.lock())
+    }
+
+    /// Reports the current state, with noise, to the software sensor. Nothing is reported until
+    /// a software sensor is connected.
+    pub fn step(&self) {
+        let mut inner = self.lock();
+        self.publish(&mut inner);
+    }
+
+    /// Returns the sensor with noise added to the reported states.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'position' - The standard deviation of the noise on the reported position.
+    /// * 'velocity' - The standard deviation of the noise on the reported velocity.
+    /// * 'seed' - The seed of the noise generator.
+    pub fn with_noise(self, position: f64, velocity: f64, seed: u64) -> Self {
+        {
+            let mut inner = self.lock();
+            inner.position_noise = position;
+            inner.velocity_noise = velocity;
+            inner.noise = NoiseSource::new(seed);
+        }
+        self
+    }
+
+    fn lock(&self) -> MutexGuard<'_, SimulatedSensorState> {
+        self.inner.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    /// Sends the current state, with noise, to the software sensor.
+    fn publish(&self, inner: &mut SimulatedSensorState) {
+        let (id, notifier) = match &inner.notifier {
+            Some((id, notifier)) => (*id, notifier.clone()),
+            None => return,
+        };
+
+        let state = Self::true_state(inner);
+        let (position_noise, velocity_noise) = (inner.position_noise, inner.velocity_noise);
+        let position_noise = inner.noise.sample(position_noise);
+        let velocity_noise = inner.noise.sample(velocity_noise);
+        let measured = JointState::new(
+            to_number_space(inner.motion_type).normalize_value(state.position() + position_noise),
+            state.velocity().map(|v| v + velocity_noise),
+            *state.acceleration(),
+            *state.jerk(),
+        );
+
+        // The receiver is owned by this sensor, so sending the state can not fail
+        let _ = self.state_sender.send(measured);
+        let _ = notifier.send(id);
+    }
+
+    /// Returns the state of the source of the sensor.
+    fn true_state(inner: &SimulatedSensorState) -> JointState {
+        match &inner.source {
+            SensorSource::Fixed(state) => *state,
+            SensorSource::Actuator(actuator) => {
+                actuator.lock().unwrap_or_else(|err| err.into_inner()).state
+            }
+        }
+    }
+
+    fn with_source(
+        motion_type: NumberSpaceType,
+        range: JointStateRange,
+        source: SensorSource,
+    ) -> Self {
+        let (state_sender, state_receiver) = crossbeam_channel::unbounded();
+        Self {
+            inner: Arc::new(Mutex::new(SimulatedSensorState {
+                motion_type,
+                range,
+                source,
+                position_noise: 0.0,
+                velocity_noise: 0.0,
+                noise: NoiseSource::new(DEFAULT_NOISE_SEED),
+                notifier: None,
+            })),
+            state_sender,
+            state_receiver,
+        }
+    }
+}
+
+impl HardwareSensor for SimulatedSensor {
+    fn current_state_receiver(&self) -> Result<Receiver<JointState>, Error> {
+        Ok(self.state_receiver.clone())
+    }
+
+    fn joint_motion_type(&self) -> NumberSpaceType {
+        self.lock().motion_type
+    }
+
+    fn joint_range(&self) -> JointStateRange {
+        self.lock().range
+    }
+
+    fn on_change(&mut self, id: ChangeID, notifier: ChangeNotifier) {
+        let mut inner = self.lock();
+        inner.notifier = Some((id, notifier));
+
+        // Provide the software sensor with the initial state
+        self.publish(&mut inner);
+    }
+}
+
+/// A clock that advances a set of simulated hardware in steps of a given size.
+///
+/// The clock does not follow the wall clock time. Time only passes when
+/// [SimulationClock::tick] is called, which makes the simulation deterministic.
+#[derive(Clone, Default)]
+pub struct SimulationClock {
+    /// The amount of simulated time that has passed.
+    elapsed: Duration,
+
+    /// The actuators that are advanced by the clock.
+    actuators: Vec<SimulatedActuator>,
+
+    /// The sensors that are advanced by the clock.
+    sensors: Vec<SimulatedSensor>,
+}
+
+impl SimulationClock {
+    /// Adds an actuator that is advanced each time the clock ticks.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'actuator' - The actuator. The clock keeps a copy that shares its state.
+    pub fn add_actuator(&mut self, actuator: &SimulatedActuator) {
+        self.actuators.push(actuator.clone());
+    }
+
+    /// Adds a sensor that reports its state each time the clock ticks.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'sensor' - The sensor. The clock keeps a copy that shares its state.
+    pub fn add_sensor(&mut self, sensor: &SimulatedSensor) {
+        self.sensors.push(sensor.clone());
+    }
+
+    /// Returns the amount of simulated time that has passed.
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    /// Creates a new [SimulationClock] instance without any hardware.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Advances the clock, and all the hardware, by the given time step. The actuators are
+    /// advanced first, in the order in which they were added, followed by the sensors.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'time_step' - The amount of simulated time that passes.
+    pub fn tick(&mut self, time_step: Duration) {
+        for actuator in self.actuators.iter() {
+            actuator.step(time_step);
+        }
+
+        for sensor in self.sensors.iter() {
+            sensor.step();
+        }
+
+        self.elapsed += time_step;
+    }
+}
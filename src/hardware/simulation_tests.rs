@@ -0,0 +1,212 @@
+use std::{f64::consts::PI, time::Duration};
+
+use crate::{
+    change_notification_processing::HardwareChangeProcessor,
+    hardware::{
+        actuator_interface::HardwareActuator,
+        joint_state::{JointState, JointStateRange},
+    },
+    model_elements::frame_elements::{Actuator, JointSensor},
+    number_space::NumberSpaceType,
+};
+
+use super::{NoiseSource, SimulatedActuator, SimulatedDynamics, SimulatedSensor, SimulationClock};
+
+fn range() -> JointStateRange {
+    JointStateRange::new(
+        JointState::new(-100.0, None, None, None),
+        JointState::new(100.0, None, None, None),
+    )
+}
+
+fn linear_actuator(dynamics: SimulatedDynamics) -> SimulatedActuator {
+    SimulatedActuator::new(NumberSpaceType::LinearUnlimited, range(), dynamics)
+}
+
+fn command(actuator: &SimulatedActuator, position: f64, velocity: Option<f64>) {
+    actuator
+        .command_sender()
+        .unwrap()
+        .send(JointState::new(position, velocity, None, None))
+        .unwrap();
+}
+
+#[test]
+fn when_stepping_without_lag_or_limits_it_should_reach_the_command_in_one_step() {
+    let actuator = linear_actuator(SimulatedDynamics::new());
+    assert_eq!(0.0, actuator.state().position());
+
+    command(&actuator, 2.0, None);
+    actuator.step(Duration::from_millis(100));
+    assert!((2.0 - actuator.state().position()).abs() < 1e-12);
+    assert!((20.0 - actuator.state().velocity().unwrap()).abs() < 1e-9);
+
+    // Without a new command the actuator stays at the commanded position
+    actuator.step(Duration::from_millis(100));
+    assert!((2.0 - actuator.state().position()).abs() < 1e-12);
+    assert_eq!(Some(0.0), *actuator.state().velocity());
+}
+
+#[test]
+fn when_stepping_with_a_time_constant_it_should_follow_a_first_order_lag() {
+    let actuator =
+        linear_actuator(SimulatedDynamics::new().with_time_constant(Duration::from_millis(500)));
+
+    command(&actuator, 1.0, None);
+    let mut expected = 0.0;
+    for _ in 0..10 {
+        actuator.step(Duration::from_millis(100));
+        expected += 0.2 * (1.0 - expected);
+        assert!((expected - actuator.state().position()).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn when_stepping_with_limits_it_should_limit_the_velocity_and_the_acceleration() {
+    let actuator = linear_actuator(
+        SimulatedDynamics::new()
+            .with_velocity_limits(-1.0, 1.0)
+            .with_acceleration_limits(-4.0, 4.0),
+    );
+
+    command(&actuator, 10.0, None);
+    actuator.step(Duration::from_millis(100));
+    assert!((0.4 - actuator.state().velocity().unwrap()).abs() < 1e-12);
+    assert_eq!(Some(4.0), *actuator.state().acceleration());
+
+    for _ in 0..10 {
+        actuator.step(Duration::from_millis(100));
+    }
+    assert!((1.0 - actuator.state().velocity().unwrap()).abs() < 1e-12);
+    assert!(actuator.state().position() < 1.1);
+
+    // A commanded velocity moves the target along
+    let actuator = linear_actuator(SimulatedDynamics::new());
+    command(&actuator, 0.0, Some(2.0));
+    for _ in 0..5 {
+        actuator.step(Duration::from_millis(100));
+    }
+    assert!((1.0 - actuator.state().position()).abs() < 1e-12);
+}
+
+#[test]
+fn when_stepping_with_unordered_limits_it_should_not_panic() {
+    let actuator = linear_actuator(
+        SimulatedDynamics::new()
+            .with_velocity_limits(1.0, -1.0)
+            .with_acceleration_limits(4.0, -4.0),
+    );
+
+    command(&actuator, 10.0, None);
+    actuator.step(Duration::from_millis(100));
+    assert!(actuator.state().position().is_finite());
+    assert!(actuator.state().velocity().unwrap().is_finite());
+}
+
+#[test]
+fn when_stepping_an_angular_actuator_it_should_take_the_shortest_way() {
+    let actuator = SimulatedActuator::new(
+        NumberSpaceType::AngularLimited {
+            start_angle_in_radians: 0.0,
+        },
+        range(),
+        SimulatedDynamics::new().with_velocity_limits(-1.0, 1.0),
+    );
+    actuator.set_state(JointState::new(0.1, Some(0.0), None, None));
+
+    command(&actuator, 2.0 * PI - 0.1, None);
+    actuator.step(Duration::from_millis(100));
+    assert!((0.0 - actuator.state().position()).abs() < 1e-12);
+    actuator.step(Duration::from_millis(100));
+    assert!((2.0 * PI - 0.1 - actuator.state().position()).abs() < 1e-12);
+}
+
+#[test]
+fn when_adding_noise_it_should_be_deterministic() {
+    let mut first = NoiseSource::new(42);
+    let mut second = NoiseSource::new(42);
+    let mut other = NoiseSource::new(7);
+    let samples: Vec<f64> = (0..1000).map(|_| first.sample(0.1)).collect();
+    for sample in samples.iter() {
+        assert_eq!(*sample, second.sample(0.1));
+    }
+    assert_ne!(samples[0], other.sample(0.1));
+    assert_eq!(0.0, first.sample(0.0));
+
+    let mean = samples.iter().sum::<f64>() / samples.len() as f64;
+    let variance = samples.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / samples.len() as f64;
+    assert!(mean.abs() < 0.01);
+    assert!((0.1 - variance.sqrt()).abs() < 0.01);
+}
+
+#[test]
+fn when_simulating_a_model_it_should_update_the_actuators_without_waiting() {
+    let processor = HardwareChangeProcessor::manual();
+    let mut hardware = linear_actuator(
+        SimulatedDynamics::new()
+            .with_velocity_limits(-1.0, 1.0)
+            .with_noise(0.01, 0.0, 3),
+    );
+    hardware.set_state(JointState::new(0.25, Some(0.0), None, None));
+    let actuator = Actuator::new(&mut hardware, &processor).unwrap();
+
+    // The initial state is reported as soon as the actuator is connected
+    assert_eq!(1, processor.process_pending());
+    assert!((0.25 - actuator.value().unwrap().position()).abs() < 0.05);
+
+    let mut sensor_hardware = SimulatedSensor::measuring(&hardware);
+    let sensor = JointSensor::new(&mut sensor_hardware, &processor).unwrap();
+    assert_eq!(1, processor.process_pending());
+
+    let mut clock = SimulationClock::new();
+    clock.add_actuator(&hardware);
+    clock.add_sensor(&sensor_hardware);
+
+    actuator
+        .update_state(JointState::new(1.0, None, None, None))
+        .unwrap();
+    for _ in 0..5 {
+        clock.tick(Duration::from_millis(100));
+        assert_eq!(2, processor.process_pending());
+    }
+
+    assert_eq!(Duration::from_millis(500), clock.elapsed());
+    assert!((0.75 - hardware.state().position()).abs() < 1e-12);
+    assert_eq!(hardware.state(), sensor.value().unwrap());
+    assert!((0.75 - actuator.value().unwrap().position()).abs() < 0.05);
+    assert_eq!(
+        (-1.0, 1.0),
+        (
+            actuator.available_rates_of_change().minimum_velocity(),
+            actuator.available_rates_of_change().maximum_velocity()
+        )
+    );
+
+    // A sensor with a fixed state keeps reporting that state
+    sensor_hardware.set_state(JointState::new(-1.0, None, None, None));
+    clock.tick(Duration::from_millis(100));
+    processor.process_pending();
+    assert_eq!(-1.0, sensor.value().unwrap().position());
+    assert!((0.85 - hardware.state().position()).abs() < 1e-12);
+}
+
+#[test]
+fn when_creating_a_sensor_it_should_report_the_given_state() {
+    let processor = HardwareChangeProcessor::manual();
+    let mut hardware = SimulatedSensor::new(
+        NumberSpaceType::LinearUnlimited,
+        range(),
+        JointState::new(0.5, Some(0.1), None, None),
+    )
+    .with_noise(0.1, 0.1, 11);
+    let sensor = JointSensor::new(&mut hardware, &processor).unwrap();
+    processor.process_pending();
+
+    let value = sensor.value().unwrap();
+    assert_ne!(0.5, value.position());
+    assert!((0.5 - value.position()).abs() < 0.5);
+    assert_eq!(
+        JointState::new(0.5, Some(0.1), None, None),
+        hardware.state()
+    );
+}
@@ -0,0 +1,158 @@
+//! Defines the policies used to combine the joint states reported by an actuator and a sensor
+//! for the same joint.
+//!
+//! A joint can have both an [Actuator](crate::model_elements::frame_elements::Actuator) and a
+//! [JointSensor](crate::model_elements::frame_elements::JointSensor), e.g. a steering motor with
+//! an external absolute encoder. The
+//! [MotionModel](crate::model_elements::model::MotionModel) uses a [StateFusion] to combine the
+//! two states into a single state for the joint. By default the state of the actuator is used.
+//!
+//! Any closure that takes the state of the actuator and the state of the sensor and returns a
+//! [JointState] can be used as a fusion policy. The [PreferActuator], [PreferSensor] and
+//! [WeightedAverage] provide the most common cases.
+//!
+//! ```
+//! use swerve_vehicle_descriptors::hardware::joint_state::JointState;
+//! use swerve_vehicle_descriptors::hardware::state_fusion::{StateFusion, WeightedAverage};
+//! use swerve_vehicle_descriptors::number_space::{to_number_space, NumberSpaceType};
+//!
+//! let space = to_number_space(NumberSpaceType::LinearUnlimited);
+//! let fusion = WeightedAverage::new(0.25);
+//!
+//! let state = fusion.fuse(
+//!     JointState::new(1.0, Some(2.0), None, None),
+//!     JointState::new(2.0, None, None, None),
+//!     space.as_ref(),
+//! );
+//! assert_eq!(JointState::new(1.25, Some(2.0), None, None), state);
+//! ```
+
+use crate::number_space::RealNumberValueSpace;
+
+use super::joint_state::JointState;
+
+#[cfg(test)]
+#[path = "state_fusion_tests.rs"]
+mod state_fusion_tests;
+
+/// Defines the interface for the policies that combine the state reported by the actuator of a
+/// joint with the state reported by the sensor of the same joint.
+pub trait StateFusion: Send + Sync {
+    /// Returns the combined state of the joint.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'actuator' - The state reported by the actuator.
+    /// * 'sensor' - The state reported by the sensor.
+    /// * 'number_space' - The number space of the actuator, used to determine the distance
+    ///   between the two positions.
+    fn fuse(
+        &self,
+        actuator: JointState,
+        sensor: JointState,
+        number_space: &dyn RealNumberValueSpace,
+    ) -> JointState;
+}
+
+impl<F> StateFusion for F
+where
+    F: Fn(JointState, JointState) -> JointState + Send + Sync,
+{
+    fn fuse(
+        &self,
+        actuator: JointState,
+        sensor: JointState,
+        _number_space: &dyn RealNumberValueSpace,
+    ) -> JointState {
+        self(actuator, sensor)
+    }
+}
+
+/// A [StateFusion] that always uses the state reported by the actuator.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct PreferActuator;
+
+impl StateFusion for PreferActuator {
+    fn fuse(
+        &self,
+        actuator: JointState,
+        _sensor: JointState,
+        _number_space: &dyn RealNumberValueSpace,
+    ) -> JointState {
+        actuator
+    }
+}
+
+/// A [StateFusion] that always uses the state reported by the sensor.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct PreferSensor;
+
+impl StateFusion for PreferSensor {
+    fn fuse(
+        &self,
+        _actuator: JointState,
+        sensor: JointState,
+        _number_space: &dyn RealNumberValueSpace,
+    ) -> JointState {
+        sensor
+    }
+}
+
+/// A [StateFusion] that returns the weighted average of the two states.
+///
+/// The position is moved from the position of the actuator towards the position of the sensor
+/// along the smallest distance in the number space of the actuator, so that angular positions
+/// on either side of the boundary of the number space are averaged correctly. The velocity,
+/// acceleration and jerk are averaged when both states provide them, otherwise the value that
+/// is provided is used.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct WeightedAverage {
+    /// The weight of the sensor state, between 0.0 and 1.0. The weight of the actuator state
+    /// is `1.0 - sensor_weight`.
+    sensor_weight: f64,
+}
+
+impl WeightedAverage {
+    /// Creates a new [WeightedAverage] instance.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'sensor_weight' - The weight of the sensor state. The value is clamped to the range
+    ///   0.0 to 1.0. The weight of the actuator state is `1.0 - sensor_weight`.
+    pub fn new(sensor_weight: f64) -> Self {
+        Self {
+            sensor_weight: sensor_weight.clamp(0.0, 1.0),
+        }
+    }
+
+    /// Returns the weight of the sensor state.
+    pub fn sensor_weight(&self) -> f64 {
+        self.sensor_weight
+    }
+
+    fn average(&self, actuator: &Option<f64>, sensor: &Option<f64>) -> Option<f64> {
+        match (actuator, sensor) {
+            (Some(a), Some(s)) => Some(a + self.sensor_weight * (s - a)),
+            (Some(a), None) => Some(*a),
+            (None, s) => *s,
+        }
+    }
+}
+
+impl StateFusion for WeightedAverage {
+    fn fuse(
+        &self,
+        actuator: JointState,
+        sensor: JointState,
+        number_space: &dyn RealNumberValueSpace,
+    ) -> JointState {
+        let distance =
+            number_space.smallest_distance_between_values(actuator.position(), sensor.position());
+        JointState::new(
+            actuator.position() + self.sensor_weight * distance,
+            self.average(actuator.velocity(), sensor.velocity()),
+            self.average(actuator.acceleration(), sensor.acceleration()),
+            self.average(actuator.jerk(), sensor.jerk()),
+        )
+    }
+}
@@ -0,0 +1,67 @@
+use std::f64::consts::PI;
+
+use crate::number_space::{to_number_space, NumberSpaceType};
+
+use super::{JointState, PreferActuator, PreferSensor, StateFusion, WeightedAverage};
+
+fn states() -> (JointState, JointState) {
+    (
+        JointState::new(1.0, Some(1.0), None, Some(4.0)),
+        JointState::new(2.0, Some(3.0), Some(2.0), None),
+    )
+}
+
+#[test]
+fn when_preferring_a_source_it_should_return_the_state_of_that_source() {
+    let space = to_number_space(NumberSpaceType::LinearUnlimited);
+    let (actuator, sensor) = states();
+
+    assert_eq!(
+        actuator,
+        PreferActuator.fuse(actuator, sensor, space.as_ref())
+    );
+    assert_eq!(sensor, PreferSensor.fuse(actuator, sensor, space.as_ref()));
+}
+
+#[test]
+fn when_averaging_it_should_weigh_the_states() {
+    let space = to_number_space(NumberSpaceType::LinearUnlimited);
+    let (actuator, sensor) = states();
+
+    let state = WeightedAverage::new(0.5).fuse(actuator, sensor, space.as_ref());
+    assert_eq!(JointState::new(1.5, Some(2.0), Some(2.0), Some(4.0)), state);
+
+    assert_eq!(
+        actuator.position(),
+        WeightedAverage::new(-1.0)
+            .fuse(actuator, sensor, space.as_ref())
+            .position()
+    );
+    assert_eq!(1.0, WeightedAverage::new(2.0).sensor_weight());
+}
+
+#[test]
+fn when_averaging_angles_it_should_average_across_the_boundary() {
+    let space = to_number_space(NumberSpaceType::AngularLimited {
+        start_angle_in_radians: 0.0,
+    });
+    let actuator = JointState::new(2.0 * PI - 0.1, None, None, None);
+    let sensor = JointState::new(0.1, None, None, None);
+
+    let state = WeightedAverage::new(0.5).fuse(actuator, sensor, space.as_ref());
+    assert!((2.0 * PI - state.position()).abs() < 1e-12);
+}
+
+#[test]
+fn when_using_a_closure_it_should_call_the_closure() {
+    let space = to_number_space(NumberSpaceType::LinearUnlimited);
+    let (actuator, sensor) = states();
+    let fusion = |a: JointState, s: JointState| {
+        JointState::new(a.position().max(s.position()), None, None, None)
+    };
+
+    assert_eq!(
+        2.0,
+        fusion.fuse(actuator, sensor, space.as_ref()).position()
+    );
+}
@@ -0,0 +1,176 @@
+//! Defines the middleware that processes the joint states received from the hardware.
+//!
+//! Each [Actuator](crate::model_elements::frame_elements::Actuator) and
+//! [JointSensor](crate::model_elements::frame_elements::JointSensor) holds an ordered chain of
+//! [StateMiddleware]. When a new [JointState] is received from the hardware it is passed
+//! through each middleware in the order in which they were added, before it is stored. A
+//! middleware can transform the state, e.g. to convert the units used by the device, or it can
+//! reject the state, e.g. because it is an outlier. A rejected state is discarded and the last
+//! accepted state is kept.
+//!
+//! The middleware is executed on the thread of the
+//! [HardwareChangeProcessor](crate::change_notification_processing::HardwareChangeProcessor).
+//! States that are not finite are rejected before they reach the middleware.
+//!
+//! Any closure that takes a [JointState] and returns a [StateOutcome] can be used as a
+//! middleware. The [LinearConversion] and [PositionJumpFilter] provide the most common cases.
+//!
+//! ```
+//! use swerve_vehicle_descriptors::hardware::joint_state::JointState;
+//! use swerve_vehicle_descriptors::hardware::state_middleware::{
+//!     LinearConversion, StateMiddleware, StateOutcome
+//! };
+//!
+//! // The device reports the position in degrees
+//! let mut to_radians = LinearConversion::new(std::f64::consts::PI / 180.0, 0.0);
+//!
+//! let outcome = to_radians.process(JointState::new(180.0, None, None, None));
+//! assert_eq!(
+//!     outcome,
+//!     StateOutcome::Accept(JointState::new(std::f64::consts::PI, None, None, None))
+//! );
+//! ```
+
+use super::joint_state::JointState;
+
+#[cfg(test)]
+#[path = "state_middleware_tests.rs"]
+mod state_middleware_tests;
+
+/// Describes the result of passing a joint state through a [StateMiddleware].
+#[derive(Clone, Debug, PartialEq)]
+pub enum StateOutcome {
+    /// The state should be passed on to the next middleware, or stored if this was the last
+    /// middleware in the chain.
+    Accept(JointState),
+
+    /// The state should be discarded.
+    Reject,
+}
+
+/// Defines the interface for middleware that transforms or rejects the joint states that are
+/// received from the hardware.
+///
+/// Unlike [CommandMiddleware](super::command_middleware::CommandMiddleware) the state middleware
+/// is only ever executed from a single thread, so it can keep track of the states it has seen.
+pub trait StateMiddleware: Send {
+    /// Processes the given state and returns the state that should be passed on, or a rejection.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'state' - The state as produced by the previous middleware in the chain.
+    fn process(&mut self, state: JointState) -> StateOutcome;
+}
+
+impl<F> StateMiddleware for F
+where
+    F: FnMut(JointState) -> StateOutcome + Send,
+{
+    fn process(&mut self, state: JointState) -> StateOutcome {
+        self(state)
+    }
+}
+
+/// A [StateMiddleware] that converts the joint state with a linear function, i.e.
+/// `position * scale + offset`. The velocity, acceleration and jerk are multiplied by the scale.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LinearConversion {
+    /// The factor with which the values are multiplied.
+    scale: f64,
+
+    /// The offset that is added to the position after it is scaled.
+    offset: f64,
+}
+
+impl LinearConversion {
+    /// Creates a new [LinearConversion] instance.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'scale' - The factor with which the values are multiplied.
+    /// * 'offset' - The offset that is added to the position after it is scaled.
+    pub fn new(scale: f64, offset: f64) -> Self {
+        Self { scale, offset }
+    }
+}
+
+impl StateMiddleware for LinearConversion {
+    fn process(&mut self, state: JointState) -> StateOutcome {
+        StateOutcome::Accept(JointState::new(
+            state.position() * self.scale + self.offset,
+            state.velocity().map(|v| v * self.scale),
+            state.acceleration().map(|a| a * self.scale),
+            state.jerk().map(|j| j * self.scale),
+        ))
+    }
+}
+
+/// A [StateMiddleware] that rejects the states for which the position differs from the position
+/// of the last accepted state by more than a given amount. The first state is always accepted.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PositionJumpFilter {
+    /// The largest allowed difference between two consecutive positions.
+    maximum_step: f64,
+
+    /// The position of the last accepted state.
+    last_position: Option<f64>,
+}
+
+impl PositionJumpFilter {
+    /// Creates a new [PositionJumpFilter] instance.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'maximum_step' - The largest allowed difference between the positions of two
+    ///   consecutive states.
+    pub fn new(maximum_step: f64) -> Self {
+        Self {
+            maximum_step,
+            last_position: None,
+        }
+    }
+}
+
+impl StateMiddleware for PositionJumpFilter {
+    fn process(&mut self, state: JointState) -> StateOutcome {
+        if let Some(last) = self.last_position {
+            if (state.position() - last).abs() > self.maximum_step {
+                return StateOutcome::Reject;
+            }
+        }
+
+        self.last_position = Some(state.position());
+        StateOutcome::Accept(state)
+    }
+}
+
+/// Passes the state through each of the middleware in order. Returns the final state, or
+/// [None] if the state was rejected by one of the middleware or if the state is not finite
+/// before or after it was processed.
+///
+/// ## Parameters
+///
+/// * 'chain' - The middleware in the order in which they should be executed.
+/// * 'state' - The state that should be processed.
+pub(crate) fn process_state(
+    chain: &mut [Box<dyn StateMiddleware>],
+    state: JointState,
+) -> Option<JointState> {
+    if !state.is_finite() {
+        return None;
+    }
+
+    let mut current = state;
+    for middleware in chain.iter_mut() {
+        match middleware.process(current) {
+            StateOutcome::Accept(next) => current = next,
+            StateOutcome::Reject => return None,
+        }
+    }
+
+    if current.is_finite() {
+        Some(current)
+    } else {
+        None
+    }
+}
@@ -0,0 +1,69 @@
+use super::*;
+
+#[test]
+fn when_converting_a_state_it_should_scale_and_offset_the_values() {
+    let mut conversion = LinearConversion::new(2.0, 1.0);
+
+    let outcome = conversion.process(JointState::new(3.0, Some(1.0), Some(2.0), None));
+    assert_eq!(
+        StateOutcome::Accept(JointState::new(7.0, Some(2.0), Some(4.0), None)),
+        outcome
+    );
+}
+
+#[test]
+fn when_filtering_position_jumps_it_should_reject_large_steps() {
+    let mut filter = PositionJumpFilter::new(0.5);
+
+    let first = JointState::new(10.0, None, None, None);
+    assert_eq!(StateOutcome::Accept(first), filter.process(first));
+
+    let small_step = JointState::new(10.4, None, None, None);
+    assert_eq!(StateOutcome::Accept(small_step), filter.process(small_step));
+
+    // The step is measured against the last accepted state
+    let large_step = JointState::new(11.0, None, None, None);
+    assert_eq!(StateOutcome::Reject, filter.process(large_step));
+
+    let negative_step = JointState::new(9.95, None, None, None);
+    assert_eq!(
+        StateOutcome::Accept(negative_step),
+        filter.process(negative_step)
+    );
+}
+
+#[test]
+fn when_processing_a_state_it_should_execute_the_middleware_in_order() {
+    let mut chain: Vec<Box<dyn StateMiddleware>> = vec![
+        Box::new(LinearConversion::new(2.0, 0.0)),
+        Box::new(LinearConversion::new(1.0, 1.0)),
+    ];
+
+    let result = process_state(&mut chain, JointState::new(1.0, None, None, None));
+    assert_eq!(Some(JointState::new(3.0, None, None, None)), result);
+}
+
+#[test]
+fn when_a_middleware_rejects_a_state_it_should_stop_the_chain() {
+    let mut chain: Vec<Box<dyn StateMiddleware>> = vec![
+        Box::new(|_: JointState| StateOutcome::Reject),
+        Box::new(|_: JointState| -> StateOutcome {
+            panic!("The middleware after a rejection should not be executed")
+        }),
+    ];
+
+    let result = process_state(&mut chain, JointState::new(1.0, None, None, None));
+    assert_eq!(None, result);
+}
+
+#[test]
+fn when_processing_a_non_finite_state_it_should_reject_the_state() {
+    let mut chain: Vec<Box<dyn StateMiddleware>> = vec![];
+    let result = process_state(&mut chain, JointState::new(f64::NAN, None, None, None));
+    assert_eq!(None, result);
+
+    let mut chain: Vec<Box<dyn StateMiddleware>> =
+        vec![Box::new(LinearConversion::new(f64::INFINITY, 0.0))];
+    let result = process_state(&mut chain, JointState::new(1.0, None, None, None));
+    assert_eq!(None, result);
+}
@@ -0,0 +1,61 @@
+//! Provides the macros that emit the [tracing](https://docs.rs/tracing) spans and events of the
+//! crate.
+//!
+//! When the `tracing` feature is enabled the crate reports:
+//!
+//! * Spans for the construction of a model and for the transform queries, with the [FrameID]s
+//!   of the frames as fields.
+//! * Events for the frames that are added to a model, the joint states that are received from
+//!   or rejected by the hardware, the notifications that are discarded by the
+//!   [HardwareChangeProcessor](crate::change_notification_processing::HardwareChangeProcessor)
+//!   and the issues that are found when a model is validated.
+//!
+//! The spans and events are only recorded when a subscriber is installed by the application.
+//! Without the `tracing` feature the macros expand to nothing, so the instrumentation has no cost.
+//!
+//! [FrameID]: crate::model_elements::frame_elements::FrameID
+
+#[cfg(all(test, feature = "tracing"))]
+#[path = "instrumentation_tests.rs"]
+mod instrumentation_tests;
+
+/// Emits a tracing event with the given level, e.g. `event!(DEBUG, frame = %id, "message")`.
+#[cfg(feature = "tracing")]
+macro_rules! event {
+    ($level:ident, $($arg:tt)+) => {
+        ::tracing::event!(::tracing::Level::$level, $($arg)+)
+    };
+}
+
+/// Emits a tracing event with the given level, e.g. `event!(DEBUG, frame = %id, "message")`.
+#[cfg(not(feature = "tracing"))]
+macro_rules! event {
+    ($level:ident, $($arg:tt)+) => {};
+}
+
+/// Creates and enters a tracing span with the given level, e.g.
+/// `let _span = span!(TRACE, "name", frame = %id);`. The span is exited when the returned
+/// guard is dropped.
+#[cfg(feature = "tracing")]
+macro_rules! span {
+    ($level:ident, $($arg:tt)+) => {
+        ::tracing::span!(::tracing::Level::$level, $($arg)+).entered()
+    };
+}
+
+/// Creates and enters a tracing span with the given level, e.g.
+/// `let _span = span!(TRACE, "name", frame = %id);`. The span is exited when the returned
+/// guard is dropped.
+#[cfg(not(feature = "tracing"))]
+macro_rules! span {
+    ($level:ident, $($arg:tt)+) => {
+        $crate::instrumentation::DisabledSpan
+    };
+}
+
+pub(crate) use event;
+pub(crate) use span;
+
+/// The guard that is returned by the `span` macro when the `tracing` feature is disabled.
+#[cfg(not(feature = "tracing"))]
+pub(crate) struct DisabledSpan;
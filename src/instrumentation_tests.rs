@@ -0,0 +1,224 @@
+use std::{
+    fmt::{self, Write},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use nalgebra::{Translation3, UnitQuaternion, Vector3};
+use tracing::{
+    field::{Field, Visit},
+    span::{Attributes, Id, Record},
+    Event, Metadata, Subscriber,
+};
+
+use crate::{
+    change_notification_processing::HardwareChangeProcessor,
+    hardware::{
+        joint_state::{JointState, JointStateRange},
+        simulation::SimulatedSensor,
+    },
+    model_elements::{
+        frame_elements::{FrameDofType, JointConstraint, JointSensor},
+        model::{MotionModel, WheelProperties},
+        module_spec::{BodySpec, ModuleSpec},
+    },
+    number_space::NumberSpaceType,
+    test_fixtures::physical_properties,
+};
+
+// Records each span and event as a line of text with the level, the name or the message and the
+// fields.
+#[derive(Clone, Default)]
+struct RecordingSubscriber {
+    records: Arc<Mutex<Vec<String>>>,
+    next_id: Arc<AtomicU64>,
+}
+
+impl RecordingSubscriber {
+    fn contains(&self, parts: &[&str]) -> bool {
+        self.records
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|record| parts.iter().all(|part| record.contains(part)))
+    }
+}
+
+impl Subscriber for RecordingSubscriber {
+    fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+        let mut record = format!(
+            "{} span {}",
+            span.metadata().level(),
+            span.metadata().name()
+        );
+        span.record(&mut FieldWriter(&mut record));
+        self.records.lock().unwrap().push(record);
+        Id::from_u64(self.next_id.fetch_add(1, Ordering::SeqCst) + 1)
+    }
+
+    fn record(&self, _span: &Id, _values: &Record<'_>) {}
+
+    fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut record = format!("{} event", event.metadata().level());
+        event.record(&mut FieldWriter(&mut record));
+        self.records.lock().unwrap().push(record);
+    }
+
+    fn enter(&self, _span: &Id) {}
+
+    fn exit(&self, _span: &Id) {}
+}
+
+struct FieldWriter<'a>(&'a mut String);
+
+impl Visit for FieldWriter<'_> {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        let _ = write!(self.0, " {}={:?}", field.name(), value);
+    }
+}
+
+fn body_spec() -> BodySpec {
+    BodySpec::new(
+        "body".to_string(),
+        Translation3::identity(),
+        UnitQuaternion::identity(),
+        physical_properties(),
+    )
+}
+
+fn module_spec(name: &str, wheel_radius: f64) -> ModuleSpec {
+    ModuleSpec::new(
+        name.to_string(),
+        Translation3::new(1.0, 0.5, 0.0),
+        UnitQuaternion::identity(),
+        WheelProperties::new(wheel_radius, 0.05, Vector3::zeros()),
+        physical_properties(),
+        physical_properties(),
+    )
+}
+
+#[test]
+fn when_building_a_model_it_should_trace_the_construction() {
+    let subscriber = RecordingSubscriber::default();
+    tracing::subscriber::with_default(subscriber.clone(), || {
+        MotionModel::builder()
+            .body(body_spec())
+            .drive_module(module_spec("front_left", 0.1))
+            .build()
+            .unwrap();
+    });
+
+    assert!(subscriber.contains(&["DEBUG span build_model", "modules=1"]));
+    assert!(subscriber.contains(&["Added a frame to the model", "name=\"body\""]));
+    assert!(subscriber.contains(&["Added a frame to the model", "name=\"front_left"]));
+
+    let subscriber = RecordingSubscriber::default();
+    tracing::subscriber::with_default(subscriber.clone(), || {
+        let result = MotionModel::builder()
+            .body(body_spec())
+            .drive_module(module_spec("invalid", 0.0))
+            .build();
+        assert!(result.is_err());
+    });
+
+    assert!(subscriber.contains(&[
+        "WARN event",
+        "The module specification is not valid",
+        "module=\"invalid\""
+    ]));
+    assert!(subscriber.contains(&["WARN event", "The model could not be built", "errors=1"]));
+}
+
+#[test]
+fn when_querying_a_transform_it_should_trace_the_frames() {
+    let model = MotionModel::builder()
+        .body(body_spec())
+        .drive_module(module_spec("front_left", 0.1))
+        .build()
+        .unwrap();
+    let body = *model.body().unwrap();
+    let wheel = *model.drive_modules().next().unwrap().frames().wheel();
+
+    let subscriber = RecordingSubscriber::default();
+    tracing::subscriber::with_default(subscriber.clone(), || {
+        model.isometry_to_body(&wheel).unwrap();
+    });
+
+    assert!(subscriber.contains(&[
+        "TRACE span isometry_to_ancestor",
+        &format!("from={}", wheel),
+        &format!("to={}", body)
+    ]));
+}
+
+#[test]
+fn when_a_joint_state_is_rejected_it_should_trace_the_frame() {
+    let processor = HardwareChangeProcessor::manual();
+    let mut model = MotionModel::new();
+    let body = model
+        .add_body(
+            "body".to_string(),
+            Translation3::identity(),
+            UnitQuaternion::identity(),
+            physical_properties(),
+        )
+        .unwrap();
+    let suspension = model
+        .add_suspension_element(
+            "suspension".to_string(),
+            FrameDofType::PrismaticZ,
+            body,
+            Translation3::identity(),
+            UnitQuaternion::identity(),
+            physical_properties(),
+            JointConstraint::new(),
+        )
+        .unwrap();
+
+    let mut hardware = SimulatedSensor::new(
+        NumberSpaceType::LinearUnlimited,
+        JointStateRange::new(
+            JointState::new(-1.0, Some(-1.0), None, None),
+            JointState::new(1.0, Some(1.0), None, None),
+        ),
+        JointState::new(f64::NAN, None, None, None),
+    );
+    model
+        .add_joint_sensor(
+            &suspension,
+            JointSensor::new(&mut hardware, &processor).unwrap(),
+        )
+        .unwrap();
+
+    let subscriber = RecordingSubscriber::default();
+    tracing::subscriber::with_default(subscriber.clone(), || {
+        hardware.step();
+        processor.process_pending();
+    });
+
+    assert!(subscriber.contains(&[
+        "WARN event",
+        "Rejected a state from the joint sensor",
+        &format!("frame=Some({:?})", suspension)
+    ]));
+}
+
+#[test]
+fn when_a_model_is_not_valid_it_should_trace_the_issues() {
+    let model = MotionModel::new();
+
+    let subscriber = RecordingSubscriber::default();
+    tracing::subscriber::with_default(subscriber.clone(), || {
+        assert!(!model.validate().is_valid());
+    });
+
+    assert!(subscriber.contains(&["WARN event", "The model is not valid", "frame=None"]));
+}
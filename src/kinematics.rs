@@ -0,0 +1,145 @@
+//! The `kinematics` module contains the calculations that describe the motion of the vehicle
+//! based on the geometry stored in the [MotionModel].
+//!
+//! The [odometry] module provides the functions used to estimate the motion of the body from the
+//! current state of the wheel modules and the [ik] module provides the steering angles and wheel
+//! velocities needed to realize a desired body motion. The [command] module sends the commands that
+//! realize a desired body motion to the actuators. The [steering] module provides the functions
+//! used to evaluate the steering configuration of the wheel modules. The [limits] module provides
+//! the functions used to keep the motion of the body within the limits of the active operating
+//! mode. The [jacobian] module provides the geometric Jacobian that maps the joint velocities to
+//! the twist of a frame and the [module_geometry] module provides a summary of the static geometry
+//! of the wheel modules. The [mass_properties] module provides the mass, the center of mass and the
+//! spatial inertia of the vehicle. The [configuration] module provides a joint-space distance that
+//! is used to compare configurations of the vehicle and the [drift] module tracks how far the live
+//! configuration drifts away from the nominal configuration over a session. The [transform_cache]
+//! module provides the functions used to compute the transforms of all the frames at once and the
+//! [transform_stream] module provides a stream of those transforms at a fixed rate. The
+//! [stability] module provides the margin of the vehicle against tipping over and the [footprint]
+//! module provides the area of the ground that is covered by the vehicle. The [snapshot]
+//! module provides a view of the model in which the states of all the joints are read at the same
+//! moment, so that queries for different frames are consistent with each other. The [realtime]
+//! module provides transform queries that neither allocate memory nor wait for the hardware, for
+//! use in real-time control loops. The [rolling_radius] module provides the functions used to
+//! calibrate the effective rolling radius of the wheels against an external ground truth and the
+//! [workspace] module provides the region that the contact point of a wheel can reach.
+//!
+//! ## Conventions
+//!
+//! * Twists are stored as a 6 element vector with the linear velocity first followed by the
+//!   angular velocity, i.e. `[v_x, v_y, v_z, ω_x, ω_y, ω_z]`, expressed in the body
+//!   reference frame.
+//! * The steering angle of a wheel is the angle, around the body z-axis, between the body
+//!   x-axis and the direction in which the wheel rolls.
+//! * Wheels roll along their x-axis and rotate around their y-axis.
+//!
+
+use nalgebra::Vector2;
+
+use crate::{
+    model_elements::{frame_elements::FrameID, model::MotionModel},
+    Error,
+};
+
+pub mod command;
+pub mod configuration;
+pub mod drift;
+pub mod footprint;
+pub mod ik;
+pub mod jacobian;
+pub mod limits;
+pub mod mass_properties;
+pub mod module_geometry;
+pub mod odometry;
+pub mod realtime;
+pub mod rolling_radius;
+pub mod snapshot;
+pub mod stability;
+pub mod steering;
+pub mod transform_cache;
+pub mod transform_stream;
+pub mod workspace;
+
+/// Returns the position of the given wheel in the xy-plane of the body reference frame
+/// together with the steering angle of the wheel, based on the current configuration of
+/// the model.
+///
+/// The rotation of the wheel around its own axle is ignored so that the position and
+/// heading only depend on the joints between the body and the wheel.
+///
+/// ## Parameters
+///
+/// * 'model' - The model that contains the wheel.
+/// * 'wheel' - The [FrameID] of the wheel.
+///
+/// ## Errors
+///
+/// * [Error::MissingFrameElement] - Returned when the wheel is not part of the model.
+pub(crate) fn wheel_position_and_heading_in_body(
+    model: &MotionModel,
+    wheel: &FrameID,
+) -> Result<(Vector2<f64>, f64), Error> {
+    wheel_position_and_heading_in_body_with(model, wheel, |id| model.current_joint_position(id))
+}
+
+/// Returns the position of the given wheel in the xy-plane of the body reference frame
+/// together with the steering angle of the wheel, with the given steering joint at its zero
+/// position and all the other joints at their current position.
+///
+/// ## Parameters
+///
+/// * 'model' - The model that contains the wheel.
+/// * 'wheel' - The [FrameID] of the wheel.
+/// * 'steering_frame' - The [FrameID] of the steering frame of the wheel.
+///
+/// ## Errors
+///
+/// * [Error::MissingFrameElement] - Returned when the wheel is not part of the model.
+pub(crate) fn wheel_position_and_heading_in_body_at_zero_steering(
+    model: &MotionModel,
+    wheel: &FrameID,
+    steering_frame: &FrameID,
+) -> Result<(Vector2<f64>, f64), Error> {
+    wheel_position_and_heading_in_body_with(model, wheel, |id| {
+        if id == steering_frame {
+            None
+        } else {
+            model.current_joint_position(id)
+        }
+    })
+}
+
+/// Returns the position of the given wheel in the xy-plane of the body reference frame
+/// together with the steering angle of the wheel, using the joint positions provided by the
+/// 'joint_position' function.
+///
+/// ## Parameters
+///
+/// * 'model' - The model that contains the wheel.
+/// * 'wheel' - The [FrameID] of the wheel.
+/// * 'joint_position' - Returns the position of the joint for a given [FrameID], or [None]
+///   if the joint should be assumed to be at its zero position.
+///
+/// ## Errors
+///
+/// * [Error::MissingFrameElement] - Returned when the wheel is not part of the model.
+pub(crate) fn wheel_position_and_heading_in_body_with<F>(
+    model: &MotionModel,
+    wheel: &FrameID,
+    joint_position: F,
+) -> Result<(Vector2<f64>, f64), Error>
+where
+    F: Fn(&FrameID) -> Option<f64>,
+{
+    let parent = model.parent_of(wheel)?;
+    let parent_to_body =
+        model.homogeneous_transform_to_ancestor_with(parent, model.body()?, joint_position)?;
+    let wheel_to_parent = model.static_transform_to_parent(wheel)?;
+
+    let wheel_to_body = parent_to_body * wheel_to_parent.to_homogeneous();
+
+    let position = Vector2::new(wheel_to_body[(0, 3)], wheel_to_body[(1, 3)]);
+    let heading = wheel_to_body[(1, 0)].atan2(wheel_to_body[(0, 0)]);
+
+    Ok((position, heading))
+}
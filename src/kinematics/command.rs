@@ -0,0 +1,225 @@
+//! Provides the functions used to turn a desired body twist into commands for the actuators of
+//! the wheel modules.
+//!
+//! The steering angles and wheel velocities are computed with the inverse kinematics of the
+//! [ik](super::ik) module. Each wheel module can realize a set-point in two ways, either by
+//! steering towards the set-point angle or by steering to the opposite angle and reversing the
+//! wheel. The option that needs the smallest steering motion is used. The commands are then
+//! limited to what the actuators can achieve before the next command is sent, based on the
+//! [ActuatorAvailableRatesOfChange] reported by the hardware.
+
+use std::{collections::HashMap, f64::consts::PI, time::Duration};
+
+use nalgebra::Vector6;
+
+use crate::{
+    hardware::{actuator_interface::ActuatorAvailableRatesOfChange, joint_state::JointState},
+    model_elements::{
+        frame_elements::{Actuator, FrameID},
+        model::MotionModel,
+    },
+    number_space::wrap_angle,
+    Error,
+};
+
+#[cfg(test)]
+#[path = "command_tests.rs"]
+mod command_tests;
+
+/// Stores the settings that limit the commands that are sent for a body twist.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CommandLimits {
+    /// The amount of time between two commands.
+    time_step: Duration,
+
+    /// A value indicating whether the body twist is limited to the speed limits of the active
+    /// operating mode.
+    apply_operating_mode_limits: bool,
+}
+
+impl CommandLimits {
+    /// Returns a value indicating whether the body twist is limited to the speed limits of the
+    /// active operating mode.
+    pub fn applies_operating_mode_limits(&self) -> bool {
+        self.apply_operating_mode_limits
+    }
+
+    /// Creates a new [CommandLimits] instance that applies the speed limits of the active
+    /// operating mode.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'time_step' - The amount of time between two commands. The change in the commands
+    ///   is limited to what the actuators can achieve in this amount of time.
+    pub fn new(time_step: Duration) -> Self {
+        Self {
+            time_step,
+            apply_operating_mode_limits: true,
+        }
+    }
+
+    /// Returns the amount of time between two commands.
+    pub fn time_step(&self) -> Duration {
+        self.time_step
+    }
+
+    /// Returns the limits without the speed limits of the active operating mode.
+    pub fn without_operating_mode_limits(mut self) -> Self {
+        self.apply_operating_mode_limits = false;
+        self
+    }
+}
+
+impl MotionModel {
+    /// Computes the commands that realize the given body twist and sends them to the steering
+    /// and wheel actuators of each wheel module. Returns the commands that were sent, stored by
+    /// the [FrameID] of the actuated frame element.
+    ///
+    /// The steering commands contain the position of the steering joint and the wheel commands
+    /// contain the rotational velocity of the wheel. Each command is limited to the change that
+    /// the actuator can achieve within the time step, based on the rates of change that the
+    /// actuator last reported. Actuators that have not reported a state yet are not limited.
+    /// The commands pass through the command middleware and the joint constraint of each
+    /// actuator, see [Actuator::update_state].
+    ///
+    /// All the commands are computed before any of them is sent, so a missing actuator does not
+    /// leave the vehicle with a partially updated set of commands. When sending a command fails
+    /// the commands that were sent before the failure are not revoked.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'twist' - The desired twist of the body as `[v_x, v_y, v_z, ω_x, ω_y, ω_z]` in the
+    ///   body reference frame. Only the planar components, `v_x`, `v_y` and `ω_z`, are used.
+    /// * 'limits' - The settings that limit the commands.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the model has no wheels or when a
+    ///   steering frame or wheel has no actuator.
+    /// * [Error::NoSteeringFramesInChain] - Returned when a wheel has no steering frame.
+    /// * [Error::MissingWheelRadius] - Returned when the radius of a wheel is not known.
+    /// * [Error::FailedToReadActuatorJointState] - Returned when the state of an actuator could
+    ///   not be read.
+    /// * [Error::CommandVetoed] - Returned when a command was vetoed by the middleware of an
+    ///   actuator.
+    /// * [Error::JointLimitExceeded] - Returned when a command violates the joint constraint
+    ///   of an actuator that rejects such commands.
+    /// * [Error::Channel] - Returned when a command could not be sent to the hardware.
+    pub fn command_body_twist(
+        &self,
+        twist: Vector6<f64>,
+        limits: &CommandLimits,
+    ) -> Result<HashMap<FrameID, JointState>, Error> {
+        let twist = if limits.apply_operating_mode_limits {
+            self.limit_twist(&twist)
+        } else {
+            twist
+        };
+
+        let time_step = limits.time_step.as_secs_f64();
+        let setpoints = self.steering_and_wheel_rates_for_body_twist(twist)?;
+
+        let mut commands = Vec::with_capacity(2 * setpoints.len());
+        for (wheel, setpoint) in setpoints.iter() {
+            let steering_actuator = self.actuator_for(setpoint.steering_frame())?;
+            let wheel_actuator = self.actuator_for(wheel)?;
+
+            // Steer to the opposite angle and reverse the wheel if that is closer
+            let steering_state = steering_actuator.value()?;
+            let current = steering_state.position();
+            let space = steering_actuator.numberspace();
+            let forward = setpoint.steering_joint_position();
+            let reverse = wrap_angle(forward + PI);
+            let (target, direction) = if space
+                .smallest_distance_between_values(current, reverse)
+                .abs()
+                < space
+                    .smallest_distance_between_values(current, forward)
+                    .abs()
+            {
+                (reverse, -1.0)
+            } else {
+                (forward, 1.0)
+            };
+
+            let change = space.smallest_distance_between_values(current, target);
+            let change = match available_rates_of_change(steering_actuator) {
+                Some(rates) => clamp(
+                    change,
+                    rates.minimum_velocity() * time_step,
+                    rates.maximum_velocity() * time_step,
+                ),
+                None => change,
+            };
+            let steering_velocity = if time_step > 0.0 {
+                change / time_step
+            } else {
+                0.0
+            };
+            commands.push((
+                setpoint.steering_frame(),
+                steering_actuator,
+                JointState::new(
+                    space.normalize_value(current + change),
+                    Some(steering_velocity),
+                    None,
+                    None,
+                ),
+            ));
+
+            let wheel_state = wheel_actuator.value()?;
+            let target_velocity = direction * setpoint.wheel_angular_velocity();
+            let velocity = match available_rates_of_change(wheel_actuator) {
+                Some(rates) => {
+                    let current_velocity = wheel_state.velocity().unwrap_or(0.0);
+                    let change = clamp(
+                        target_velocity - current_velocity,
+                        rates.minimum_acceleration() * time_step,
+                        rates.maximum_acceleration() * time_step,
+                    );
+                    clamp(
+                        current_velocity + change,
+                        rates.minimum_velocity(),
+                        rates.maximum_velocity(),
+                    )
+                }
+                None => target_velocity,
+            };
+            commands.push((
+                wheel,
+                wheel_actuator,
+                JointState::new(
+                    wheel_actuator
+                        .numberspace()
+                        .normalize_value(wheel_state.position() + velocity * time_step),
+                    Some(velocity),
+                    None,
+                    None,
+                ),
+            ));
+        }
+
+        let mut result = HashMap::with_capacity(commands.len());
+        for (frame_id, actuator, command) in commands {
+            actuator.update_state(command)?;
+            result.insert(*frame_id, command);
+        }
+
+        Ok(result)
+    }
+}
+
+/// Returns the rates of change that the given actuator last reported, or [None] if the actuator
+/// has not received a state from the hardware yet.
+fn available_rates_of_change(actuator: &Actuator) -> Option<ActuatorAvailableRatesOfChange> {
+    actuator
+        .last_update()
+        .map(|_| actuator.available_rates_of_change())
+}
+
+/// Returns the given value limited to the range between the minimum and the maximum. Unlike
+/// [f64::clamp] this does not panic when the hardware reports a minimum that is larger than
+/// the maximum, in which case the maximum wins.
+fn clamp(value: f64, minimum: f64, maximum: f64) -> f64 {
+    value.max(minimum).min(maximum)
+}
@@ -0,0 +1,187 @@
+use std::{f64::consts::FRAC_PI_2, time::Duration};
+
+use nalgebra::{Translation3, UnitQuaternion, Vector3, Vector6};
+
+use crate::{
+    change_notification_processing::HardwareChangeProcessor,
+    hardware::{actuator_interface::ActuatorAvailableRatesOfChange, joint_state::JointState},
+    model_elements::{
+        frame_elements::Actuator,
+        model::{MotionModel, WheelProperties},
+        module_spec::{BodySpec, ModuleFrames, ModuleSpec},
+        operating_mode::OperatingMode,
+    },
+    number_space::NumberSpaceType,
+    test_fixtures::physical_properties,
+    test_support::MockActuator,
+    Error,
+};
+
+use super::CommandLimits;
+
+// A vehicle with two modules, placed at (1.0, ±0.5), with wheels with a radius of 0.1. The
+// hardware is stored per module as (steering, wheel).
+struct TestVehicle {
+    model: MotionModel,
+    frames: Vec<ModuleFrames>,
+    hardware: Vec<(MockActuator, MockActuator)>,
+    _change_processor: HardwareChangeProcessor,
+}
+
+impl TestVehicle {
+    fn new() -> Self {
+        let change_processor = HardwareChangeProcessor::new(1000);
+        let mut hardware = vec![];
+        let modules = [0.5, -0.5]
+            .iter()
+            .map(|y| {
+                let mut steering = MockActuator::new(NumberSpaceType::LinearUnlimited);
+                let mut wheel = MockActuator::new(NumberSpaceType::LinearUnlimited);
+                let module = ModuleSpec::new(
+                    "module".to_string(),
+                    Translation3::new(1.0, *y, 0.0),
+                    UnitQuaternion::identity(),
+                    WheelProperties::new(0.1, 0.05, Vector3::zeros()),
+                    physical_properties(),
+                    physical_properties(),
+                )
+                .with_wheel_offset(Translation3::new(0.0, 0.0, -0.1))
+                .with_steering_actuator(Actuator::new(&mut steering, &change_processor).unwrap())
+                .with_wheel_actuator(Actuator::new(&mut wheel, &change_processor).unwrap());
+                hardware.push((steering, wheel));
+                module
+            })
+            .collect();
+
+        let body = BodySpec::new(
+            "body".to_string(),
+            Translation3::identity(),
+            UnitQuaternion::identity(),
+            physical_properties(),
+        );
+        let (model, frames) = MotionModel::with_modules(body, modules).unwrap();
+
+        Self {
+            model,
+            frames,
+            hardware,
+            _change_processor: change_processor,
+        }
+    }
+}
+
+#[test]
+fn when_commanding_a_body_twist_it_should_send_the_commands_to_the_actuators() {
+    let vehicle = TestVehicle::new();
+    let limits = CommandLimits::new(Duration::from_millis(20));
+
+    let commands = vehicle
+        .model
+        .command_body_twist(Vector6::new(0.0, 1.0, 0.0, 0.0, 0.0, 0.0), &limits)
+        .unwrap();
+    assert_eq!(4, commands.len());
+
+    for (frames, (steering, wheel)) in vehicle.frames.iter().zip(vehicle.hardware.iter()) {
+        // Moving sideways means steering by a quarter turn and rolling at 1.0 / 0.1 rad/s
+        let steering_command = steering.commands()[0];
+        assert!((FRAC_PI_2 - steering_command.position().abs()).abs() < 1e-9);
+        assert_eq!(&steering_command, commands.get(frames.steering()).unwrap());
+
+        let wheel_command = wheel.commands()[0];
+        let velocity = wheel_command.velocity().unwrap();
+        assert!((10.0 - velocity.abs()).abs() < 1e-9);
+        assert!((velocity * 0.02 - wheel_command.position()).abs() < 1e-9);
+
+        // A reversed wheel is paired with the opposite steering angle
+        assert_eq!(
+            steering_command.position().signum(),
+            velocity.signum(),
+            "{:?} {:?}",
+            steering_command,
+            wheel_command
+        );
+    }
+}
+
+#[test]
+fn when_commanding_a_body_twist_it_should_respect_the_available_rates_of_change() {
+    let vehicle = TestVehicle::new();
+    let rates = ActuatorAvailableRatesOfChange::new(-1.0, 1.0, -5.0, 5.0, 0.0, 0.0);
+    for (steering, wheel) in vehicle.hardware.iter() {
+        // The copies share the channels of the hardware
+        for hardware in [steering, wheel] {
+            hardware
+                .clone()
+                .with_rates_of_change(rates)
+                .send_state(JointState::new(0.0, Some(0.0), None, None));
+        }
+    }
+    std::thread::sleep(Duration::from_millis(20));
+
+    let limits = CommandLimits::new(Duration::from_millis(100));
+    vehicle
+        .model
+        .command_body_twist(Vector6::new(0.0, 1.0, 0.0, 0.0, 0.0, 0.0), &limits)
+        .unwrap();
+
+    for (steering, wheel) in vehicle.hardware.iter() {
+        // The steering joint moves at most 1.0 rad/s for 0.1 s
+        let steering_command = steering.commands()[0];
+        assert!((0.1 - steering_command.position().abs()).abs() < 1e-9);
+        assert!((1.0 - steering_command.velocity().unwrap().abs()).abs() < 1e-9);
+
+        // The wheel accelerates at most 5.0 rad/s^2 for 0.1 s
+        let wheel_command = wheel.commands()[0];
+        assert!((0.5 - wheel_command.velocity().unwrap().abs()).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn when_commanding_a_body_twist_it_should_apply_the_operating_mode_limits() {
+    let mut vehicle = TestVehicle::new();
+    vehicle
+        .model
+        .add_operating_mode(OperatingMode::new("slow".to_string(), 0.5, 1.0, 1.0))
+        .unwrap();
+    vehicle.model.set_operating_mode("slow").unwrap();
+
+    let twist = Vector6::new(1.0, 0.0, 0.0, 0.0, 0.0, 0.0);
+    let limits = CommandLimits::new(Duration::from_millis(20));
+    let commands = vehicle.model.command_body_twist(twist, &limits).unwrap();
+    let wheel = vehicle.frames[0].wheel();
+    assert!((5.0 - commands[wheel].velocity().unwrap()).abs() < 1e-9);
+
+    let limits = limits.without_operating_mode_limits();
+    assert!(!limits.applies_operating_mode_limits());
+    let commands = vehicle.model.command_body_twist(twist, &limits).unwrap();
+    assert!((10.0 - commands[wheel].velocity().unwrap()).abs() < 1e-9);
+}
+
+#[test]
+fn when_commanding_a_body_twist_without_actuators_it_should_error() {
+    let body = BodySpec::new(
+        "body".to_string(),
+        Translation3::identity(),
+        UnitQuaternion::identity(),
+        physical_properties(),
+    );
+    let module = ModuleSpec::new(
+        "module".to_string(),
+        Translation3::new(1.0, 0.0, 0.0),
+        UnitQuaternion::identity(),
+        WheelProperties::new(0.1, 0.05, Vector3::zeros()),
+        physical_properties(),
+        physical_properties(),
+    )
+    .with_wheel_offset(Translation3::new(0.0, 0.0, -0.1));
+    let (model, frames) = MotionModel::with_modules(body, vec![module]).unwrap();
+
+    let result = model.command_body_twist(
+        Vector6::new(1.0, 0.0, 0.0, 0.0, 0.0, 0.0),
+        &CommandLimits::new(Duration::from_millis(20)),
+    );
+    assert!(matches!(
+        result,
+        Err(Error::MissingFrameElement { id }) if id == *frames[0].steering()
+    ));
+}
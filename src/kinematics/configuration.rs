@@ -0,0 +1,170 @@
+//! Provides the functions used to compare the configurations of the vehicle, i.e. the positions
+//! of all the joints of the vehicle.
+//!
+//! The distance between two configurations is the weighted Euclidean distance in joint space,
+//! i.e. `sqrt(Σ w_i * d_i²)` where `d_i` is the smallest distance between the positions of
+//! joint `i` and `w_i` is the weight of the joint. The distance between two positions is
+//! computed in the number space of the actuator of the joint, so that the distance between two
+//! angles is measured across the boundary of the angular range when that is shorter.
+//!
+//! Revolute joints are measured in radians and prismatic joints are measured in meters. The
+//! [ConfigurationWeights] are used to balance the contribution of the different joint types, or
+//! of individual joints.
+
+use std::collections::HashMap;
+
+use crate::{
+    model_elements::{
+        frame_elements::{FrameDofType, FrameID},
+        model::MotionModel,
+    },
+    Error,
+};
+
+#[cfg(test)]
+#[path = "configuration_tests.rs"]
+mod configuration_tests;
+
+/// Stores the weights that are used to compute the distance between two configurations.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConfigurationWeights {
+    /// The weight for the revolute joints.
+    revolute: f64,
+
+    /// The weight for the prismatic joints.
+    prismatic: f64,
+
+    /// The weights for individual joints, which take precedence over the weights for the
+    /// joint types.
+    joints: HashMap<FrameID, f64>,
+}
+
+impl ConfigurationWeights {
+    /// Creates a new [ConfigurationWeights] instance.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'revolute' - The weight for the revolute joints, per radian squared.
+    /// * 'prismatic' - The weight for the prismatic joints, per meter squared.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use swerve_vehicle_descriptors::kinematics::configuration::ConfigurationWeights;
+    /// use swerve_vehicle_descriptors::model_elements::frame_elements::{FrameDofType, FrameID};
+    ///
+    /// let wheel = FrameID::new();
+    /// let weights = ConfigurationWeights::new(1.0, 10.0).with_joint_weight(wheel, 0.0);
+    ///
+    /// assert_eq!(10.0, weights.weight_for(&FrameID::new(), FrameDofType::PrismaticZ));
+    /// assert_eq!(0.0, weights.weight_for(&wheel, FrameDofType::RevoluteY));
+    /// ```
+    pub fn new(revolute: f64, prismatic: f64) -> Self {
+        Self {
+            revolute,
+            prismatic,
+            joints: HashMap::new(),
+        }
+    }
+
+    /// Returns the weight for the given joint.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'joint' - The [FrameID] of the joint.
+    /// * 'degree_of_freedom' - The kind of motion of the joint.
+    pub fn weight_for(&self, joint: &FrameID, degree_of_freedom: FrameDofType) -> f64 {
+        if let Some(weight) = self.joints.get(joint) {
+            return *weight;
+        }
+
+        if degree_of_freedom.is_revolute() {
+            self.revolute
+        } else if degree_of_freedom.is_prismatic() {
+            self.prismatic
+        } else {
+            0.0
+        }
+    }
+
+    /// Sets the weight for an individual joint, replacing the weight for the type of the joint.
+    /// A weight of 0.0 excludes the joint from the distance, e.g. for the wheel joints which
+    /// rotate continuously.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'joint' - The [FrameID] of the joint.
+    /// * 'weight' - The weight of the joint.
+    pub fn with_joint_weight(mut self, joint: FrameID, weight: f64) -> Self {
+        self.joints.insert(joint, weight);
+        self
+    }
+}
+
+impl Default for ConfigurationWeights {
+    fn default() -> Self {
+        Self::new(1.0, 1.0)
+    }
+}
+
+impl MotionModel {
+    /// Returns the weighted joint-space distance between two configurations of the vehicle.
+    ///
+    /// See the [module documentation](self) for a description of the metric.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'a' - The position of the joints in the first configuration, stored by the [FrameID]
+    ///   of the joint. Joints that are not in the collection use their current position.
+    /// * 'b' - The position of the joints in the second configuration, stored by the [FrameID]
+    ///   of the joint. Joints that are not in the collection use their current position.
+    /// * 'weights' - The weights for the joints.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when one of the configurations contains a
+    ///   [FrameID] that is not part of the model.
+    /// * [Error::InvalidFrameID] - Returned when one of the configurations contains a [FrameID]
+    ///   of a frame that does not move relative to its parent.
+    pub fn configuration_distance(
+        &self,
+        a: &HashMap<FrameID, f64>,
+        b: &HashMap<FrameID, f64>,
+        weights: &ConfigurationWeights,
+    ) -> Result<f64, Error> {
+        for id in a.keys().chain(b.keys()) {
+            if self.frame_degree_of_freedom(id)? == FrameDofType::Static {
+                return Err(Error::InvalidFrameID { id: *id });
+            }
+        }
+
+        let mut joints: Vec<FrameID> = self
+            .chassis_elements()
+            .map(|e| *e.reference_frame())
+            .collect();
+        joints.sort();
+
+        let mut sum = 0.0;
+        for joint in joints.iter() {
+            let degree_of_freedom = self.frame_degree_of_freedom(joint)?;
+            let weight = weights.weight_for(joint, degree_of_freedom);
+            if degree_of_freedom == FrameDofType::Static || weight == 0.0 {
+                continue;
+            }
+
+            let current = self.current_joint_position(joint).unwrap_or(0.0);
+            let start = a.get(joint).copied().unwrap_or(current);
+            let end = b.get(joint).copied().unwrap_or(current);
+            let distance = match self.actuator_for(joint) {
+                Ok(actuator) => actuator
+                    .numberspace()
+                    .smallest_distance_between_values(start, end),
+                Err(_) => end - start,
+            };
+
+            sum += weight * distance * distance;
+        }
+
+        Ok(sum.sqrt())
+    }
+}
@@ -0,0 +1,223 @@
+use std::{collections::HashMap, f64::consts::PI};
+
+use float_cmp::{ApproxEq, F64Margin};
+use nalgebra::{Translation3, UnitQuaternion};
+
+use crate::{
+    change_notification_processing::HardwareChangeProcessor,
+    model_elements::{
+        frame_elements::{Actuator, FrameDofType, FrameID, JointConstraint},
+        model::MotionModel,
+    },
+    number_space::NumberSpaceType,
+    test_fixtures::physical_properties,
+    test_support::MockActuator,
+    Error,
+};
+
+use super::ConfigurationWeights;
+
+// A vehicle with a single module that consists of a suspension frame without an actuator, a
+// steering frame with an angular actuator and a wheel frame with a linear actuator.
+struct TestVehicle {
+    model: MotionModel,
+    body: FrameID,
+    suspension: FrameID,
+    steering: FrameID,
+    wheel: FrameID,
+    _change_processor: Box<HardwareChangeProcessor>,
+}
+
+impl TestVehicle {
+    fn new() -> Self {
+        let change_processor = Box::new(HardwareChangeProcessor::new(1000));
+        let mut model = MotionModel::new();
+        let body = model
+            .add_body(
+                "body".to_string(),
+                Translation3::identity(),
+                UnitQuaternion::identity(),
+                physical_properties(),
+            )
+            .unwrap();
+
+        let suspension = model
+            .add_suspension_element(
+                "suspension".to_string(),
+                FrameDofType::PrismaticZ,
+                body,
+                Translation3::new(1.0, 0.5, 0.0),
+                UnitQuaternion::identity(),
+                physical_properties(),
+                JointConstraint::new(),
+            )
+            .unwrap();
+
+        let mut steering_hardware = MockActuator::new(NumberSpaceType::AngularLimited {
+            start_angle_in_radians: -PI,
+        });
+        let steering = model
+            .add_steering_element(
+                "steering".to_string(),
+                suspension,
+                Translation3::identity(),
+                UnitQuaternion::identity(),
+                physical_properties(),
+                Actuator::new(&mut steering_hardware, &change_processor).unwrap(),
+            )
+            .unwrap();
+
+        let mut wheel_hardware = MockActuator::new(NumberSpaceType::LinearUnlimited);
+        let wheel = model
+            .add_wheel(
+                "wheel".to_string(),
+                steering,
+                Translation3::new(0.0, 0.0, -0.1),
+                UnitQuaternion::identity(),
+                physical_properties(),
+                Actuator::new(&mut wheel_hardware, &change_processor).unwrap(),
+            )
+            .unwrap();
+
+        Self {
+            model,
+            body,
+            suspension,
+            steering,
+            wheel,
+            _change_processor: change_processor,
+        }
+    }
+}
+
+fn assert_approx_eq(expected: f64, actual: f64) {
+    assert!(
+        actual.approx_eq(
+            expected,
+            F64Margin {
+                ulps: 2,
+                epsilon: 1e-9
+            }
+        ),
+        "Expected {} and {} to be equal within 2 ulps or 1e-9",
+        expected,
+        actual
+    );
+}
+
+// ConfigurationWeights
+
+#[test]
+fn when_getting_the_weight_for_a_joint_it_should_use_the_joint_type() {
+    let weights = ConfigurationWeights::new(2.0, 3.0);
+    let joint = FrameID::new();
+
+    assert_eq!(0.0, weights.weight_for(&joint, FrameDofType::Static));
+    assert_eq!(2.0, weights.weight_for(&joint, FrameDofType::RevoluteX));
+    assert_eq!(2.0, weights.weight_for(&joint, FrameDofType::RevoluteZ));
+    assert_eq!(3.0, weights.weight_for(&joint, FrameDofType::PrismaticY));
+
+    let weights = weights.with_joint_weight(joint, 5.0);
+    assert_eq!(5.0, weights.weight_for(&joint, FrameDofType::RevoluteX));
+    assert_eq!(
+        2.0,
+        weights.weight_for(&FrameID::new(), FrameDofType::RevoluteX)
+    );
+
+    assert_eq!(ConfigurationWeights::new(1.0, 1.0), Default::default());
+}
+
+// MotionModel::configuration_distance
+
+#[test]
+fn when_comparing_a_configuration_with_itself_it_should_have_no_distance() {
+    let vehicle = TestVehicle::new();
+    let configuration = HashMap::from([
+        (vehicle.suspension, 0.05),
+        (vehicle.steering, 1.0),
+        (vehicle.wheel, 3.0),
+    ]);
+
+    let distance = vehicle
+        .model
+        .configuration_distance(
+            &configuration,
+            &configuration,
+            &ConfigurationWeights::default(),
+        )
+        .unwrap();
+    assert_eq!(0.0, distance);
+}
+
+#[test]
+fn when_comparing_angles_it_should_measure_across_the_boundary() {
+    let vehicle = TestVehicle::new();
+    let a = HashMap::from([(vehicle.steering, PI - 0.1)]);
+    let b = HashMap::from([(vehicle.steering, -PI + 0.1)]);
+
+    let distance = vehicle
+        .model
+        .configuration_distance(&a, &b, &ConfigurationWeights::default())
+        .unwrap();
+    assert_approx_eq(0.2, distance);
+}
+
+#[test]
+fn when_comparing_configurations_it_should_weigh_the_joint_types() {
+    let vehicle = TestVehicle::new();
+    let a = HashMap::from([
+        (vehicle.suspension, 0.0),
+        (vehicle.steering, 0.0),
+        (vehicle.wheel, 0.0),
+    ]);
+    let b = HashMap::from([
+        (vehicle.suspension, 0.1),
+        (vehicle.steering, 0.3),
+        (vehicle.wheel, 10.0),
+    ]);
+
+    let weights = ConfigurationWeights::new(1.0, 16.0).with_joint_weight(vehicle.wheel, 0.0);
+    let distance = vehicle
+        .model
+        .configuration_distance(&a, &b, &weights)
+        .unwrap();
+
+    // sqrt(16 * 0.1^2 + 0.3^2)
+    assert_approx_eq(0.5, distance);
+}
+
+#[test]
+fn when_comparing_partial_configurations_it_should_use_the_current_positions() {
+    let vehicle = TestVehicle::new();
+    let a = HashMap::from([(vehicle.steering, 0.4)]);
+    let b = HashMap::from([(vehicle.suspension, 0.3)]);
+
+    let distance = vehicle
+        .model
+        .configuration_distance(&a, &b, &ConfigurationWeights::default())
+        .unwrap();
+    assert_approx_eq(0.5, distance);
+}
+
+#[test]
+fn when_comparing_configurations_with_invalid_joints_it_should_error() {
+    let vehicle = TestVehicle::new();
+    let weights = ConfigurationWeights::default();
+
+    let unknown = FrameID::new();
+    let a = HashMap::from([(unknown, 0.0)]);
+    assert_eq!(
+        Err(Error::MissingFrameElement { id: unknown }),
+        vehicle
+            .model
+            .configuration_distance(&a, &HashMap::new(), &weights)
+    );
+
+    let b = HashMap::from([(vehicle.body, 0.0)]);
+    assert_eq!(
+        Err(Error::InvalidFrameID { id: vehicle.body }),
+        vehicle
+            .model
+            .configuration_distance(&HashMap::new(), &b, &weights)
+    );
+}
@@ -0,0 +1,381 @@
+//! Provides the functions used to track how far the live configuration of the vehicle drifts
+//! away from the nominal configuration, i.e. the configuration with all the joints in their zero
+//! position.
+//!
+//! A [DriftMonitor] samples the model at regular intervals over a session. For each actuated
+//! joint, except for the wheel joints which rotate continuously, it tracks the offset of the joint
+//! from its zero position. For each wheel it tracks the offset of the wheel center, in the
+//! xy-plane of the body, from the nominal position of the wheel. The [DriftReport] summarizes
+//! these offsets together with the rate at which they change, which can be used to detect steering
+//! modules that slowly wear or slip over time.
+//!
+//! The trend of an offset is the slope of the least-squares line through the samples, in units
+//! per second.
+
+use std::collections::{BTreeMap, HashSet};
+
+use nalgebra::Vector2;
+
+use crate::{
+    model_elements::{frame_elements::FrameID, model::MotionModel},
+    number_space::wrap_angle,
+    Error,
+};
+
+use super::{wheel_position_and_heading_in_body, wheel_position_and_heading_in_body_with};
+
+#[cfg(test)]
+#[path = "drift_tests.rs"]
+mod drift_tests;
+
+/// Stores the statistics for the samples of a single offset.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DriftStatistics {
+    /// The number of samples.
+    number_of_samples: usize,
+
+    /// The most recent sample.
+    current: f64,
+
+    /// The smallest sample.
+    minimum: f64,
+
+    /// The largest sample.
+    maximum: f64,
+
+    /// The sum of the samples.
+    sum: f64,
+
+    /// The sum of the sample times, relative to the start of the session.
+    sum_of_times: f64,
+
+    /// The sum of the squares of the sample times.
+    sum_of_squared_times: f64,
+
+    /// The sum of the products of the sample times and the samples.
+    sum_of_time_value_products: f64,
+}
+
+impl DriftStatistics {
+    /// Adds a sample to the statistics.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'time' - The time of the sample in seconds, relative to the start of the session.
+    /// * 'value' - The sample.
+    fn add(&mut self, time: f64, value: f64) {
+        if self.number_of_samples == 0 {
+            self.minimum = value;
+            self.maximum = value;
+        } else {
+            self.minimum = self.minimum.min(value);
+            self.maximum = self.maximum.max(value);
+        }
+
+        self.number_of_samples += 1;
+        self.current = value;
+        self.sum += value;
+        self.sum_of_times += time;
+        self.sum_of_squared_times += time * time;
+        self.sum_of_time_value_products += time * value;
+    }
+
+    /// Returns the most recent sample.
+    pub fn current(&self) -> f64 {
+        self.current
+    }
+
+    /// Returns the largest sample.
+    pub fn maximum(&self) -> f64 {
+        self.maximum
+    }
+
+    /// Returns the mean of the samples.
+    pub fn mean(&self) -> f64 {
+        if self.number_of_samples == 0 {
+            return 0.0;
+        }
+
+        self.sum / self.number_of_samples as f64
+    }
+
+    /// Returns the smallest sample.
+    pub fn minimum(&self) -> f64 {
+        self.minimum
+    }
+
+    /// Creates a new [DriftStatistics] instance without any samples.
+    fn new() -> Self {
+        Self {
+            number_of_samples: 0,
+            current: 0.0,
+            minimum: 0.0,
+            maximum: 0.0,
+            sum: 0.0,
+            sum_of_times: 0.0,
+            sum_of_squared_times: 0.0,
+            sum_of_time_value_products: 0.0,
+        }
+    }
+
+    /// Returns the number of samples.
+    pub fn number_of_samples(&self) -> usize {
+        self.number_of_samples
+    }
+
+    /// Returns the rate at which the samples change, in units per second, or [None] if the
+    /// samples were not taken at two or more different times.
+    pub fn trend(&self) -> Option<f64> {
+        let n = self.number_of_samples as f64;
+        let denominator = n * self.sum_of_squared_times - self.sum_of_times * self.sum_of_times;
+        if self.number_of_samples < 2 || denominator.abs() < f64::EPSILON {
+            return None;
+        }
+
+        Some((n * self.sum_of_time_value_products - self.sum_of_times * self.sum) / denominator)
+    }
+}
+
+/// Describes the drift of a single joint from its zero position.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct JointDrift {
+    /// The [FrameID] of the joint.
+    frame: FrameID,
+
+    /// The statistics for the offset of the joint from its zero position.
+    offset: DriftStatistics,
+}
+
+impl JointDrift {
+    /// Returns the [FrameID] of the joint.
+    pub fn frame(&self) -> &FrameID {
+        &self.frame
+    }
+
+    /// Returns the statistics for the offset of the joint from its zero position, in radians for
+    /// revolute joints and in meters for prismatic joints.
+    pub fn offset(&self) -> &DriftStatistics {
+        &self.offset
+    }
+}
+
+/// Describes the drift of the position of a wheel from its nominal position.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct WheelDrift {
+    /// The [FrameID] of the wheel.
+    wheel: FrameID,
+
+    /// The most recent offset of the wheel center from its nominal position.
+    position_offset: Vector2<f64>,
+
+    /// The most recent offset of the heading of the wheel from its nominal heading.
+    heading_offset: f64,
+
+    /// The statistics for the distance between the wheel center and its nominal position.
+    distance: DriftStatistics,
+}
+
+impl WheelDrift {
+    /// Returns the statistics for the distance, in meters, between the center of the wheel and
+    /// its nominal position in the xy-plane of the body.
+    pub fn distance(&self) -> &DriftStatistics {
+        &self.distance
+    }
+
+    /// Returns the most recent offset, in radians, of the heading of the wheel from its nominal
+    /// heading. The offset is in the range (-π, π].
+    pub fn heading_offset(&self) -> f64 {
+        self.heading_offset
+    }
+
+    /// Returns the most recent offset, in meters, of the center of the wheel from its nominal
+    /// position in the xy-plane of the body.
+    pub fn position_offset(&self) -> &Vector2<f64> {
+        &self.position_offset
+    }
+
+    /// Returns the [FrameID] of the wheel.
+    pub fn wheel(&self) -> &FrameID {
+        &self.wheel
+    }
+}
+
+/// Summarizes the drift of the joints and the wheels over a session.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DriftReport {
+    /// The time, in seconds, between the first and the last sample.
+    duration: f64,
+
+    /// The drift of the joints, ordered by [FrameID].
+    joints: Vec<JointDrift>,
+
+    /// The drift of the wheels, ordered by [FrameID].
+    wheels: Vec<WheelDrift>,
+}
+
+impl DriftReport {
+    /// Returns the time, in seconds, between the first and the last sample.
+    pub fn duration(&self) -> f64 {
+        self.duration
+    }
+
+    /// Returns the drift of the given joint, or [None] if the joint was not sampled.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'frame' - The [FrameID] of the joint.
+    pub fn joint(&self, frame: &FrameID) -> Option<&JointDrift> {
+        self.joints.iter().find(|j| j.frame == *frame)
+    }
+
+    /// Returns the drift of the joints, ordered by [FrameID].
+    pub fn joints(&self) -> &[JointDrift] {
+        &self.joints
+    }
+
+    /// Returns the drift of the given wheel, or [None] if the wheel was not sampled.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'wheel' - The [FrameID] of the wheel.
+    pub fn wheel(&self, wheel: &FrameID) -> Option<&WheelDrift> {
+        self.wheels.iter().find(|w| w.wheel == *wheel)
+    }
+
+    /// Returns the drift of the wheels, ordered by [FrameID].
+    pub fn wheels(&self) -> &[WheelDrift] {
+        &self.wheels
+    }
+}
+
+/// Samples the configuration of a [MotionModel] over a session and generates a [DriftReport].
+///
+/// ```
+/// use swerve_vehicle_descriptors::kinematics::drift::DriftMonitor;
+///
+/// let monitor = DriftMonitor::new();
+/// let report = monitor.report();
+///
+/// assert_eq!(0, monitor.number_of_samples());
+/// assert!(report.joints().is_empty());
+/// ```
+#[derive(Clone, Debug)]
+pub struct DriftMonitor {
+    /// The time of the first sample in seconds.
+    start_time: Option<f64>,
+
+    /// The time of the last sample in seconds.
+    last_time: f64,
+
+    /// The number of times the model was sampled.
+    number_of_samples: usize,
+
+    /// The drift of the joints, stored by [FrameID].
+    joints: BTreeMap<FrameID, JointDrift>,
+
+    /// The drift of the wheels, stored by [FrameID].
+    wheels: BTreeMap<FrameID, WheelDrift>,
+}
+
+impl DriftMonitor {
+    /// Creates a new [DriftMonitor] instance without any samples.
+    pub fn new() -> Self {
+        Self {
+            start_time: None,
+            last_time: 0.0,
+            number_of_samples: 0,
+            joints: BTreeMap::new(),
+            wheels: BTreeMap::new(),
+        }
+    }
+
+    /// Returns the number of times the model was sampled.
+    pub fn number_of_samples(&self) -> usize {
+        self.number_of_samples
+    }
+
+    /// Samples the current joint states of the model and the positions of the wheels.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'model' - The model that should be sampled.
+    /// * 'time' - The time of the sample in seconds. The samples should be recorded in the
+    ///   order in which they were taken.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the model has no body or no wheels.
+    pub fn record(&mut self, model: &MotionModel, time: f64) -> Result<(), Error> {
+        let wheels: HashSet<FrameID> = model.wheels()?.into_iter().copied().collect();
+        let start_time = *self.start_time.get_or_insert(time);
+        let relative_time = time - start_time;
+
+        for element in model.chassis_elements() {
+            let frame = *element.reference_frame();
+            if wheels.contains(&frame) {
+                continue;
+            }
+
+            let actuator = match model.actuator_for(&frame) {
+                Ok(actuator) => actuator,
+                Err(_) => continue,
+            };
+
+            let position = actuator.value()?.position();
+            let offset = actuator
+                .numberspace()
+                .smallest_distance_between_values(0.0, position);
+
+            self.joints
+                .entry(frame)
+                .or_insert_with(|| JointDrift {
+                    frame,
+                    offset: DriftStatistics::new(),
+                })
+                .offset
+                .add(relative_time, offset);
+        }
+
+        for wheel in wheels {
+            let (nominal_position, nominal_heading) =
+                wheel_position_and_heading_in_body_with(model, &wheel, |_| None)?;
+            let (position, heading) = wheel_position_and_heading_in_body(model, &wheel)?;
+
+            let drift = self.wheels.entry(wheel).or_insert_with(|| WheelDrift {
+                wheel,
+                position_offset: Vector2::zeros(),
+                heading_offset: 0.0,
+                distance: DriftStatistics::new(),
+            });
+            drift.position_offset = position - nominal_position;
+            drift.heading_offset = wrap_angle(heading - nominal_heading);
+            drift
+                .distance
+                .add(relative_time, drift.position_offset.norm());
+        }
+
+        self.last_time = time;
+        self.number_of_samples += 1;
+        Ok(())
+    }
+
+    /// Returns the report for the samples that were recorded so far.
+    pub fn report(&self) -> DriftReport {
+        DriftReport {
+            duration: self.start_time.map_or(0.0, |start| self.last_time - start),
+            joints: self.joints.values().copied().collect(),
+            wheels: self.wheels.values().copied().collect(),
+        }
+    }
+
+    /// Removes all the samples so that a new session can be started.
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+}
+
+impl Default for DriftMonitor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
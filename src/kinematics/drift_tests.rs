@@ -0,0 +1,209 @@
+use std::{f64::consts::PI, time::Duration};
+
+use float_cmp::{ApproxEq, F64Margin};
+use nalgebra::{Translation3, UnitQuaternion, Vector2};
+
+use crate::{
+    change_notification_processing::HardwareChangeProcessor,
+    hardware::joint_state::{JointState, JointStateRange},
+    model_elements::{
+        frame_elements::{Actuator, FrameID},
+        model::MotionModel,
+    },
+    number_space::NumberSpaceType,
+    test_fixtures::physical_properties,
+    test_support::MockActuator,
+};
+
+use super::{DriftMonitor, DriftStatistics};
+
+// Returns the hardware for a joint that reports angles in the range [-π, π).
+fn angular_hardware() -> MockActuator {
+    MockActuator::new(NumberSpaceType::AngularLimited {
+        start_angle_in_radians: -PI,
+    })
+    .with_range(JointStateRange::new(
+        JointState::new(-PI, None, None, None),
+        JointState::new(PI, None, None, None),
+    ))
+}
+
+// A vehicle with a single module in which the wheel is 0.1 meter in front of the steering
+// frame.
+struct TestVehicle {
+    model: MotionModel,
+    steering: FrameID,
+    wheel: FrameID,
+    steering_hardware: MockActuator,
+    wheel_hardware: MockActuator,
+    _change_processor: Box<HardwareChangeProcessor>,
+}
+
+impl TestVehicle {
+    fn new() -> Self {
+        let change_processor = Box::new(HardwareChangeProcessor::new(1000));
+        let mut model = MotionModel::new();
+        let body = model
+            .add_body(
+                "body".to_string(),
+                Translation3::identity(),
+                UnitQuaternion::identity(),
+                physical_properties(),
+            )
+            .unwrap();
+
+        let mut steering_hardware = angular_hardware();
+        let steering = model
+            .add_steering_element(
+                "steering".to_string(),
+                body,
+                Translation3::new(1.0, 0.5, 0.0),
+                UnitQuaternion::identity(),
+                physical_properties(),
+                Actuator::new(&mut steering_hardware, &change_processor).unwrap(),
+            )
+            .unwrap();
+
+        let mut wheel_hardware = angular_hardware();
+        let wheel = model
+            .add_wheel(
+                "wheel".to_string(),
+                steering,
+                Translation3::new(0.1, 0.0, -0.1),
+                UnitQuaternion::identity(),
+                physical_properties(),
+                Actuator::new(&mut wheel_hardware, &change_processor).unwrap(),
+            )
+            .unwrap();
+
+        Self {
+            model,
+            steering,
+            wheel,
+            steering_hardware,
+            wheel_hardware,
+            _change_processor: change_processor,
+        }
+    }
+}
+
+fn assert_approx_eq(expected: f64, actual: f64) {
+    assert!(
+        actual.approx_eq(
+            expected,
+            F64Margin {
+                ulps: 2,
+                epsilon: 1e-9
+            }
+        ),
+        "Expected {} and {} to be equal within 2 ulps or 1e-9",
+        expected,
+        actual
+    );
+}
+
+// DriftStatistics
+
+#[test]
+fn when_adding_samples_to_the_statistics_it_should_track_the_trend() {
+    let mut statistics = DriftStatistics::new();
+    assert_eq!(0, statistics.number_of_samples());
+    assert_eq!(0.0, statistics.mean());
+    assert_eq!(None, statistics.trend());
+
+    statistics.add(0.0, 1.0);
+    assert_eq!(None, statistics.trend());
+
+    statistics.add(1.0, 1.5);
+    statistics.add(2.0, 1.9);
+    statistics.add(3.0, 2.6);
+
+    assert_eq!(4, statistics.number_of_samples());
+    assert_eq!(2.6, statistics.current());
+    assert_eq!(1.0, statistics.minimum());
+    assert_eq!(2.6, statistics.maximum());
+    assert_approx_eq(1.75, statistics.mean());
+    assert_approx_eq(0.52, statistics.trend().unwrap());
+}
+
+#[test]
+fn when_adding_samples_at_the_same_time_it_should_not_have_a_trend() {
+    let mut statistics = DriftStatistics::new();
+    statistics.add(5.0, 1.0);
+    statistics.add(5.0, 2.0);
+
+    assert_eq!(None, statistics.trend());
+}
+
+// DriftMonitor
+
+#[test]
+fn when_recording_the_model_it_should_report_the_offsets_from_the_nominal_configuration() {
+    let vehicle = TestVehicle::new();
+    let mut monitor = DriftMonitor::new();
+
+    for (time, steering_angle) in [(10.0, 0.0), (11.0, 0.1), (12.0, 0.2)] {
+        vehicle.steering_hardware.send_position(steering_angle);
+        vehicle.wheel_hardware.send_position(3.0 * time);
+        std::thread::sleep(Duration::from_millis(20));
+        monitor.record(&vehicle.model, time).unwrap();
+    }
+
+    assert_eq!(3, monitor.number_of_samples());
+
+    let report = monitor.report();
+    assert_approx_eq(2.0, report.duration());
+
+    // The wheel rotates continuously so it is not tracked as a joint
+    assert_eq!(1, report.joints().len());
+    assert!(report.joint(&vehicle.wheel).is_none());
+
+    let steering = report.joint(&vehicle.steering).unwrap();
+    assert_eq!(&vehicle.steering, steering.frame());
+    assert_approx_eq(0.2, steering.offset().current());
+    assert_approx_eq(0.0, steering.offset().minimum());
+    assert_approx_eq(0.2, steering.offset().maximum());
+    assert_approx_eq(0.1, steering.offset().mean());
+    assert_approx_eq(0.1, steering.offset().trend().unwrap());
+
+    assert_eq!(1, report.wheels().len());
+    let wheel = report.wheel(&vehicle.wheel).unwrap();
+    assert_eq!(&vehicle.wheel, wheel.wheel());
+    assert_approx_eq(0.2, wheel.heading_offset());
+
+    // The steering joint rotates around the z-axis of its parent, i.e. the body
+    let nominal = Vector2::new(1.1, 0.5);
+    let rotated = nalgebra::Rotation2::new(0.2) * nominal;
+    let expected_offset = rotated - nominal;
+    assert!((expected_offset - wheel.position_offset()).norm() < 1e-9);
+    assert_approx_eq(expected_offset.norm(), wheel.distance().current());
+    assert_approx_eq(0.0, wheel.distance().minimum());
+    assert!(wheel.distance().trend().unwrap() > 0.0);
+}
+
+#[test]
+fn when_resetting_the_monitor_it_should_remove_all_the_samples() {
+    let vehicle = TestVehicle::new();
+    let mut monitor = DriftMonitor::default();
+
+    monitor.record(&vehicle.model, 1.0).unwrap();
+    monitor.record(&vehicle.model, 2.0).unwrap();
+    assert_eq!(2, monitor.number_of_samples());
+
+    monitor.reset();
+    assert_eq!(0, monitor.number_of_samples());
+
+    let report = monitor.report();
+    assert_eq!(0.0, report.duration());
+    assert!(report.joints().is_empty());
+    assert!(report.wheels().is_empty());
+}
+
+#[test]
+fn when_recording_a_model_without_wheels_it_should_error() {
+    let model = MotionModel::new();
+    let mut monitor = DriftMonitor::new();
+
+    assert!(monitor.record(&model, 0.0).is_err());
+    assert_eq!(0, monitor.number_of_samples());
+}
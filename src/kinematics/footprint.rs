@@ -0,0 +1,209 @@
+//! Provides the footprint of the vehicle, i.e. the area of the ground plane that is covered by
+//! the vehicle, for use by path planners.
+//!
+//! The footprint is the convex hull of the [collision geometry](crate::model_elements::collision)
+//! of all the chassis elements, posed for the current positions of the joints and projected onto
+//! the xy-plane of the body reference frame. Curved shapes are approximated by polygons that
+//! enclose the shape. Mesh shapes are not loaded by this crate and are therefore not part of the
+//! footprint, a primitive shape that encloses the mesh should be added for the footprint instead.
+//!
+//! The swept footprint is the area that is covered by the vehicle while it moves with a
+//! constant body twist for a given duration. It is the convex hull of the footprint at a number
+//! of poses along the path of the body, so the area that the vehicle covers during a turn is
+//! approximated by its convex hull.
+
+use std::time::Duration;
+
+use nalgebra::{Point3, Rotation2, Vector2, Vector3, Vector6};
+
+use crate::{
+    envelope::convex_hull,
+    model_elements::{collision::CollisionShape, model::MotionModel},
+    Error,
+};
+
+#[cfg(test)]
+#[path = "footprint_tests.rs"]
+mod footprint_tests;
+
+/// The number of line segments that is used to approximate a circle.
+const CIRCLE_SEGMENTS: usize = 16;
+
+/// The number of intervals into which the path of the body is divided when computing the swept
+/// footprint.
+const SWEPT_FOOTPRINT_STEPS: usize = 16;
+
+/// The angular velocity, in radians per second, below which the body is assumed to move in a
+/// straight line.
+const STRAIGHT_LINE_TOLERANCE: f64 = 1e-9;
+
+impl MotionModel {
+    /// Returns the corners of the footprint of the vehicle, in counter-clockwise order, in the
+    /// xy-plane of the body reference frame for the current positions of the joints.
+    ///
+    /// Returns an empty polygon when the model has no collision geometry.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the model has no body.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use nalgebra::{Isometry3, Matrix3, Translation3, UnitQuaternion, Vector3};
+    /// use swerve_vehicle_descriptors::model_elements::collision::{
+    ///     CollisionGeometry, CollisionShape,
+    /// };
+    /// use swerve_vehicle_descriptors::model_elements::model::{
+    ///     ChassisElementPhysicalProperties, MotionModel,
+    /// };
+    ///
+    /// let mut model = MotionModel::new();
+    /// let body = model
+    ///     .add_body(
+    ///         "body".to_string(),
+    ///         Translation3::identity(),
+    ///         UnitQuaternion::identity(),
+    ///         ChassisElementPhysicalProperties::from_mass_properties(
+    ///             10.0,
+    ///             Vector3::zeros(),
+    ///             Matrix3::identity(),
+    ///         ),
+    ///     )
+    ///     .unwrap();
+    /// model
+    ///     .add_collision_geometry(
+    ///         &body,
+    ///         CollisionGeometry::new(
+    ///             CollisionShape::Box {
+    ///                 size: Vector3::new(1.0, 0.6, 0.2),
+    ///             },
+    ///             Isometry3::identity(),
+    ///         )
+    ///         .unwrap(),
+    ///     )
+    ///     .unwrap();
+    ///
+    /// let footprint = model.footprint_polygon().unwrap();
+    /// assert_eq!(4, footprint.len());
+    /// ```
+    pub fn footprint_polygon(&self) -> Result<Vec<Vector2<f64>>, Error> {
+        let points = self.footprint_points()?;
+        Ok(convex_hull(&points))
+    }
+
+    /// Returns the corners of the area that is covered by the vehicle while it moves with the
+    /// given twist for the given duration, in counter-clockwise order, in the xy-plane of the
+    /// body reference frame at the start of the motion.
+    ///
+    /// Only the planar part of the twist, i.e. `v_x`, `v_y` and `ω_z`, is used. The positions of
+    /// the joints are assumed to stay the same during the motion.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'twist' - The twist of the body as `[v_x, v_y, v_z, ω_x, ω_y, ω_z]` in the body
+    ///   reference frame.
+    /// * 'duration' - The duration of the motion.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the model has no body.
+    pub fn swept_footprint(
+        &self,
+        twist: &Vector6<f64>,
+        duration: Duration,
+    ) -> Result<Vec<Vector2<f64>>, Error> {
+        let points = self.footprint_points()?;
+        let velocity = Vector2::new(twist[0], twist[1]);
+        let angular_velocity = twist[5];
+
+        let mut swept = Vec::with_capacity(points.len() * (SWEPT_FOOTPRINT_STEPS + 1));
+        for step in 0..=SWEPT_FOOTPRINT_STEPS {
+            let time = duration.as_secs_f64() * step as f64 / SWEPT_FOOTPRINT_STEPS as f64;
+            let (rotation, translation) = planar_pose_at(&velocity, angular_velocity, time);
+            swept.extend(points.iter().map(|p| rotation * p + translation));
+        }
+
+        Ok(convex_hull(&swept))
+    }
+
+    /// Returns the points of the collision shapes of all the chassis elements, projected onto
+    /// the xy-plane of the body reference frame.
+    fn footprint_points(&self) -> Result<Vec<Vector2<f64>>, Error> {
+        let mut points = Vec::new();
+        for shape in self.collision_shapes_in_body()? {
+            let pose = shape.pose();
+            let mut project = |p: Vector3<f64>| {
+                let in_body = pose * Point3::from(p);
+                points.push(Vector2::new(in_body.x, in_body.y));
+            };
+
+            match shape.shape() {
+                CollisionShape::Box { size } => {
+                    let half = size * 0.5;
+                    for x in [-half.x, half.x] {
+                        for y in [-half.y, half.y] {
+                            for z in [-half.z, half.z] {
+                                project(Vector3::new(x, y, z));
+                            }
+                        }
+                    }
+                }
+                CollisionShape::Cylinder { radius, length } => {
+                    for z in [-0.5 * length, 0.5 * length] {
+                        for p in circle_points(*radius) {
+                            project(Vector3::new(p.x, p.y, z));
+                        }
+                    }
+                }
+                CollisionShape::Mesh { .. } => {}
+                CollisionShape::Sphere { radius } => {
+                    // The outline of a sphere is a circle around the projected center
+                    let center = pose.translation.vector;
+                    for p in circle_points(*radius) {
+                        points.push(Vector2::new(center.x + p.x, center.y + p.y));
+                    }
+                }
+            }
+        }
+
+        Ok(points)
+    }
+}
+
+/// Returns the corners of a regular polygon that encloses a circle with the given radius around
+/// the origin.
+fn circle_points(radius: f64) -> Vec<Vector2<f64>> {
+    let step = 2.0 * std::f64::consts::PI / CIRCLE_SEGMENTS as f64;
+
+    // The corners lie outside the circle so that the edges touch the circle
+    let corner_radius = radius / (0.5 * step).cos();
+    (0..CIRCLE_SEGMENTS)
+        .map(|i| {
+            let angle = i as f64 * step;
+            Vector2::new(corner_radius * angle.cos(), corner_radius * angle.sin())
+        })
+        .collect()
+}
+
+/// Returns the rotation and the translation of the body after moving with the given planar
+/// twist for the given time, relative to the pose of the body at the start of the motion.
+fn planar_pose_at(
+    velocity: &Vector2<f64>,
+    angular_velocity: f64,
+    time: f64,
+) -> (Rotation2<f64>, Vector2<f64>) {
+    let angle = angular_velocity * time;
+    if angular_velocity.abs() < STRAIGHT_LINE_TOLERANCE {
+        return (Rotation2::identity(), velocity * time);
+    }
+
+    // The body moves along a circular arc, integrate the body velocity over the rotation
+    let (sin, cos) = angle.sin_cos();
+    let translation = Vector2::new(
+        sin * velocity.x - (1.0 - cos) * velocity.y,
+        (1.0 - cos) * velocity.x + sin * velocity.y,
+    ) / angular_velocity;
+
+    (Rotation2::new(angle), translation)
+}
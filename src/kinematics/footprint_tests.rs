@@ -0,0 +1,164 @@
+use std::{f64::consts::PI, time::Duration};
+
+use nalgebra::{Isometry3, Translation3, UnitQuaternion, Vector2, Vector3, Vector6};
+
+use crate::{
+    model_elements::{
+        collision::{CollisionGeometry, CollisionShape},
+        frame_elements::FrameID,
+        model::MotionModel,
+    },
+    test_fixtures::physical_properties,
+};
+
+// Builds a body with a box of 2.0 by 1.0 meters as its collision geometry.
+fn build_model() -> (MotionModel, FrameID) {
+    let mut model = MotionModel::new();
+    let body = model
+        .add_body(
+            "body".to_string(),
+            Translation3::identity(),
+            UnitQuaternion::identity(),
+            physical_properties(),
+        )
+        .unwrap();
+    model
+        .add_collision_geometry(
+            &body,
+            CollisionGeometry::new(
+                CollisionShape::Box {
+                    size: Vector3::new(2.0, 1.0, 0.5),
+                },
+                Isometry3::identity(),
+            )
+            .unwrap(),
+        )
+        .unwrap();
+
+    (model, body)
+}
+
+fn contains(polygon: &[Vector2<f64>], point: &Vector2<f64>) -> bool {
+    (0..polygon.len()).all(|i| {
+        let edge = polygon[(i + 1) % polygon.len()] - polygon[i];
+        edge.perp(&(point - polygon[i])) >= -1e-9
+    })
+}
+
+#[test]
+fn when_the_model_has_no_collision_geometry_it_should_return_an_empty_footprint() {
+    let mut model = MotionModel::new();
+
+    model
+        .add_body(
+            "body".to_string(),
+            Translation3::identity(),
+            UnitQuaternion::identity(),
+            physical_properties(),
+        )
+        .unwrap();
+    assert!(model.footprint_polygon().unwrap().is_empty());
+}
+
+#[test]
+fn when_computing_the_footprint_it_should_enclose_all_the_shapes() {
+    let (mut model, body) = build_model();
+
+    let footprint = model.footprint_polygon().unwrap();
+    assert_eq!(4, footprint.len());
+    for corner in [
+        Vector2::new(1.0, 0.5),
+        Vector2::new(-1.0, 0.5),
+        Vector2::new(-1.0, -0.5),
+        Vector2::new(1.0, -0.5),
+    ] {
+        assert!(footprint.contains(&corner));
+    }
+
+    // A bumper on a rotated arm and a wheel shaped cylinder extend the footprint
+    let arm = model
+        .add_static_chassis_element(
+            "arm".to_string(),
+            body,
+            Translation3::new(1.0, 0.0, 0.0),
+            UnitQuaternion::from_euler_angles(0.0, 0.0, 0.5 * PI),
+            physical_properties(),
+        )
+        .unwrap();
+    model
+        .add_collision_geometry(
+            &arm,
+            CollisionGeometry::new(
+                CollisionShape::Sphere { radius: 0.25 },
+                Isometry3::translation(0.0, -0.5, 0.0),
+            )
+            .unwrap(),
+        )
+        .unwrap();
+    model
+        .add_collision_geometry(
+            &body,
+            CollisionGeometry::new(
+                CollisionShape::Cylinder {
+                    radius: 0.3,
+                    length: 0.2,
+                },
+                Isometry3::new(
+                    Vector3::new(0.0, 0.8, 0.0),
+                    Vector3::new(0.5 * PI, 0.0, 0.0),
+                ),
+            )
+            .unwrap(),
+        )
+        .unwrap();
+    model
+        .add_collision_geometry(
+            &body,
+            CollisionGeometry::new(
+                CollisionShape::Mesh {
+                    uri: "package://vehicle/mast.stl".to_string(),
+                    scale: Vector3::repeat(10.0),
+                },
+                Isometry3::identity(),
+            )
+            .unwrap(),
+        )
+        .unwrap();
+
+    let footprint = model.footprint_polygon().unwrap();
+    assert!(contains(&footprint, &Vector2::new(1.75, 0.0)));
+    assert!(!contains(&footprint, &Vector2::new(1.8, 0.0)));
+
+    // The axis of the cylinder is along the body y-axis, so it covers y from 0.7 to 0.9
+    assert!(contains(&footprint, &Vector2::new(0.0, 0.9)));
+    assert!(!contains(&footprint, &Vector2::new(0.0, 0.95)));
+    assert!(footprint.iter().all(|p| p.x.abs() < 2.0 && p.y.abs() < 2.0));
+}
+
+#[test]
+fn when_computing_the_swept_footprint_it_should_cover_the_path_of_the_vehicle() {
+    let (model, _) = build_model();
+
+    let standing = model
+        .swept_footprint(&Vector6::zeros(), Duration::from_secs(1))
+        .unwrap();
+    assert_eq!(model.footprint_polygon().unwrap(), standing);
+
+    let forward = Vector6::new(1.0, 0.0, 0.0, 0.0, 0.0, 0.0);
+    let swept = model
+        .swept_footprint(&forward, Duration::from_secs(2))
+        .unwrap();
+    assert_eq!(4, swept.len());
+    assert!(contains(&swept, &Vector2::new(3.0, 0.5)));
+    assert!(!contains(&swept, &Vector2::new(3.1, 0.0)));
+
+    // Turning on the spot for a quarter turn sweeps the corners over a circle
+    let turning = Vector6::new(0.0, 0.0, 0.0, 0.0, 0.0, 0.5 * PI);
+    let swept = model
+        .swept_footprint(&turning, Duration::from_secs(1))
+        .unwrap();
+    let radius = Vector2::new(1.0, 0.5).norm();
+    assert!(contains(&swept, &Vector2::new(0.0, 0.99 * radius)));
+    assert!(contains(&swept, &Vector2::new(-0.5, 1.0)));
+    assert!(!contains(&swept, &Vector2::new(0.0, 1.01 * radius)));
+}
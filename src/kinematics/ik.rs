@@ -0,0 +1,136 @@
+//! Provides the inverse kinematics for the wheel modules, i.e. the functions used to compute
+//! the steering angles and wheel velocities that realize a desired motion of the body.
+//!
+//! For a planar body twist `[v_x, v_y, ω_z]` the velocity of the contact point of a wheel at
+//! position `(x, y)` in the body reference frame is
+//!
+//! ```text
+//! v_wheel_x = v_x - ω_z * y
+//! v_wheel_y = v_y + ω_z * x
+//! ```
+//!
+//! The wheel has to point in the direction of this velocity and roll at a rate equal to the
+//! speed of the contact point divided by the radius of the wheel.
+
+use std::collections::HashMap;
+
+use nalgebra::Vector6;
+
+use crate::{
+    model_elements::{frame_elements::FrameID, model::MotionModel},
+    number_space::wrap_angle,
+    Error,
+};
+
+use super::{
+    wheel_position_and_heading_in_body, wheel_position_and_heading_in_body_at_zero_steering,
+};
+
+#[cfg(test)]
+#[path = "ik_tests.rs"]
+mod ik_tests;
+
+/// The speed, in meters per second, of the wheel contact point below which the steering angle
+/// is undefined.
+const MINIMUM_CONTACT_POINT_SPEED: f64 = 1e-9;
+
+/// Stores the steering angle and the wheel velocity that a wheel module needs in order to
+/// realize a given body twist.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ModuleSetpoint {
+    /// The [FrameID] of the steering frame of the module.
+    steering_frame: FrameID,
+
+    /// The steering angle of the wheel in radians.
+    steering_angle: f64,
+
+    /// The position of the steering joint in radians.
+    steering_joint_position: f64,
+
+    /// The rotational velocity of the wheel in radians per second.
+    wheel_angular_velocity: f64,
+}
+
+impl ModuleSetpoint {
+    /// Returns the [FrameID] of the steering frame of the module.
+    pub fn steering_frame(&self) -> &FrameID {
+        &self.steering_frame
+    }
+
+    /// Returns the steering angle of the wheel in radians, i.e. the angle around the body
+    /// z-axis between the body x-axis and the direction in which the wheel should roll. The
+    /// angle is in the range (-π, π].
+    pub fn steering_angle(&self) -> f64 {
+        self.steering_angle
+    }
+
+    /// Returns the position, in radians, of the steering joint that results in the
+    /// [steering angle](ModuleSetpoint::steering_angle). The position is in the range (-π, π].
+    ///
+    /// The position is computed by assuming that the steering joint rotates around an axis
+    /// that is parallel to the body z-axis.
+    pub fn steering_joint_position(&self) -> f64 {
+        self.steering_joint_position
+    }
+
+    /// Returns the rotational velocity of the wheel in radians per second.
+    pub fn wheel_angular_velocity(&self) -> f64 {
+        self.wheel_angular_velocity
+    }
+}
+
+impl MotionModel {
+    /// Returns the steering angle and wheel velocity that each wheel needs in order to realize
+    /// the given body twist, stored by the [FrameID] of the wheel.
+    ///
+    /// The positions of the wheels are taken from the current configuration of the model, with
+    /// the steering joints at their zero position. When the contact point of a wheel should
+    /// not move the wheel keeps its current steering angle.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'twist' - The desired twist of the body as `[v_x, v_y, v_z, ω_x, ω_y, ω_z]` in the
+    ///   body reference frame. Only the planar components, `v_x`, `v_y` and `ω_z`, are used.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the model has no wheels.
+    /// * [Error::NoSteeringFramesInChain] - Returned when a wheel has no steering frame.
+    /// * [Error::MissingWheelRadius] - Returned when the radius of a wheel is not known.
+    pub fn steering_and_wheel_rates_for_body_twist(
+        &self,
+        twist: Vector6<f64>,
+    ) -> Result<HashMap<FrameID, ModuleSetpoint>, Error> {
+        let mut result = HashMap::with_capacity(self.number_of_wheels());
+        for wheel in self.wheels()? {
+            let steering_frame = *self.steering_frame_for_wheel(wheel)?;
+            let radius = self.wheel_rolling_radius(wheel)?;
+
+            let (position, zero_heading) =
+                wheel_position_and_heading_in_body_at_zero_steering(self, wheel, &steering_frame)?;
+
+            let velocity_x = twist[0] - twist[5] * position.y;
+            let velocity_y = twist[1] + twist[5] * position.x;
+            let speed = velocity_x.hypot(velocity_y);
+
+            let steering_angle = if speed < MINIMUM_CONTACT_POINT_SPEED {
+                let (_, current_heading) = wheel_position_and_heading_in_body(self, wheel)?;
+                wrap_angle(current_heading)
+            } else {
+                velocity_y.atan2(velocity_x)
+            };
+
+            result.insert(
+                *wheel,
+                ModuleSetpoint {
+                    steering_frame,
+                    steering_angle,
+                    steering_joint_position: wrap_angle(steering_angle - zero_heading),
+                    wheel_angular_velocity: speed / radius,
+                },
+            );
+        }
+
+        Ok(result)
+    }
+}
@@ -0,0 +1,295 @@
+use std::{collections::HashMap, f64::consts::PI, time::Duration};
+
+use float_cmp::{ApproxEq, F64Margin};
+use nalgebra::{Translation3, UnitQuaternion, Vector6};
+
+use crate::{
+    change_notification_processing::HardwareChangeProcessor,
+    hardware::joint_state::JointState,
+    model_elements::{
+        frame_elements::{Actuator, FrameDofType, FrameID, JointConstraint},
+        model::MotionModel,
+    },
+    number_space::NumberSpaceType,
+    test_fixtures::physical_properties,
+    test_support::MockActuator,
+    Error,
+};
+
+// A vehicle with 4 modules, placed at (±1.0, ±0.5). Each module consists of a suspension frame,
+// a steering frame located on the suspension frame and a wheel frame 0.1 below the steering
+// frame. The steering frames are rotated around the z-axis by the given yaw, all other frames
+// have the same orientation as the body.
+struct TestVehicle {
+    model: MotionModel,
+    module_positions: Vec<(f64, f64)>,
+    steering_frames: Vec<FrameID>,
+    wheels: Vec<FrameID>,
+    steering_actuators: Vec<MockActuator>,
+    _wheel_actuators: Vec<MockActuator>,
+    _change_processor: Box<HardwareChangeProcessor>,
+}
+
+impl TestVehicle {
+    fn new(steering_yaw: f64) -> Self {
+        let change_processor = Box::new(HardwareChangeProcessor::new(1000));
+        let mut model = MotionModel::new();
+        let body = model
+            .add_body(
+                "body".to_string(),
+                Translation3::identity(),
+                UnitQuaternion::identity(),
+                physical_properties(),
+            )
+            .unwrap();
+
+        let module_positions = vec![(1.0, 0.5), (-1.0, 0.5), (-1.0, -0.5), (1.0, -0.5)];
+        let mut steering_frames = vec![];
+        let mut wheels = vec![];
+        let mut steering_actuators = vec![];
+        let mut wheel_actuators = vec![];
+        for (x, y) in module_positions.iter() {
+            let suspension = model
+                .add_suspension_element(
+                    "suspension".to_string(),
+                    FrameDofType::PrismaticZ,
+                    body,
+                    Translation3::new(*x, *y, 0.0),
+                    UnitQuaternion::identity(),
+                    physical_properties(),
+                    JointConstraint::new(),
+                )
+                .unwrap();
+
+            let mut steering_hardware = MockActuator::new(NumberSpaceType::LinearUnlimited);
+            let steering_actuator =
+                Actuator::new(&mut steering_hardware, &change_processor).unwrap();
+            let steering = model
+                .add_steering_element(
+                    "steering".to_string(),
+                    suspension,
+                    Translation3::identity(),
+                    UnitQuaternion::from_euler_angles(0.0, 0.0, steering_yaw),
+                    physical_properties(),
+                    steering_actuator,
+                )
+                .unwrap();
+
+            let mut wheel_hardware = MockActuator::new(NumberSpaceType::LinearUnlimited);
+            let wheel_actuator = Actuator::new(&mut wheel_hardware, &change_processor).unwrap();
+            let wheel = model
+                .add_wheel(
+                    "wheel".to_string(),
+                    steering,
+                    Translation3::new(0.0, 0.0, -0.1),
+                    UnitQuaternion::identity(),
+                    physical_properties(),
+                    wheel_actuator,
+                )
+                .unwrap();
+            model.set_wheel_radius(&wheel, 0.1).unwrap();
+
+            steering_frames.push(steering);
+            wheels.push(wheel);
+            steering_actuators.push(steering_hardware);
+            wheel_actuators.push(wheel_hardware);
+        }
+
+        Self {
+            model,
+            module_positions,
+            steering_frames,
+            wheels,
+            steering_actuators,
+            _wheel_actuators: wheel_actuators,
+            _change_processor: change_processor,
+        }
+    }
+
+    fn set_steering_angle(&self, index: usize, steering_angle: f64) {
+        self.steering_actuators[index].send_state(JointState::new(
+            steering_angle,
+            Some(0.0),
+            None,
+            None,
+        ));
+    }
+
+    fn wait_for_updates(&self) {
+        std::thread::sleep(Duration::from_millis(50));
+    }
+}
+
+fn assert_approx_eq(expected: f64, actual: f64) {
+    assert!(
+        actual.approx_eq(
+            expected,
+            F64Margin {
+                ulps: 2,
+                epsilon: 1e-9
+            }
+        ),
+        "Expected {} and {} to be equal within 2 ulps or 1e-9",
+        expected,
+        actual
+    );
+}
+
+#[test]
+fn when_computing_setpoints_without_wheels_it_should_error() {
+    let model = MotionModel::new();
+
+    let result = model.steering_and_wheel_rates_for_body_twist(Vector6::zeros());
+    assert!(result.is_err());
+}
+
+#[test]
+fn when_computing_setpoints_without_wheel_radius_it_should_error() {
+    let change_processor = Box::new(HardwareChangeProcessor::new(1000));
+    let mut model = MotionModel::new();
+    let body = model
+        .add_body(
+            "body".to_string(),
+            Translation3::identity(),
+            UnitQuaternion::identity(),
+            physical_properties(),
+        )
+        .unwrap();
+
+    let mut steering_hardware = MockActuator::new(NumberSpaceType::LinearUnlimited);
+    let steering = model
+        .add_steering_element(
+            "steering".to_string(),
+            body,
+            Translation3::new(1.0, 0.0, 0.0),
+            UnitQuaternion::identity(),
+            physical_properties(),
+            Actuator::new(&mut steering_hardware, &change_processor).unwrap(),
+        )
+        .unwrap();
+
+    let mut wheel_hardware = MockActuator::new(NumberSpaceType::LinearUnlimited);
+    let wheel = model
+        .add_wheel(
+            "wheel".to_string(),
+            steering,
+            Translation3::new(0.0, 0.0, -0.1),
+            UnitQuaternion::identity(),
+            physical_properties(),
+            Actuator::new(&mut wheel_hardware, &change_processor).unwrap(),
+        )
+        .unwrap();
+
+    let result = model.steering_and_wheel_rates_for_body_twist(Vector6::zeros());
+    assert_eq!(Err(Error::MissingWheelRadius { id: wheel }), result);
+}
+
+#[test]
+fn when_computing_setpoints_for_straight_motion_it_should_point_all_wheels_in_the_same_direction() {
+    let vehicle = TestVehicle::new(0.0);
+
+    let twist = Vector6::new(0.0, 1.0, 0.0, 0.0, 0.0, 0.0);
+    let setpoints = vehicle
+        .model
+        .steering_and_wheel_rates_for_body_twist(twist)
+        .unwrap();
+
+    assert_eq!(4, setpoints.len());
+    for (index, wheel) in vehicle.wheels.iter().enumerate() {
+        let setpoint = setpoints[wheel];
+        assert_eq!(&vehicle.steering_frames[index], setpoint.steering_frame());
+        assert_approx_eq(0.5 * PI, setpoint.steering_angle());
+        assert_approx_eq(0.5 * PI, setpoint.steering_joint_position());
+        assert_approx_eq(10.0, setpoint.wheel_angular_velocity());
+    }
+}
+
+#[test]
+fn when_computing_setpoints_for_a_rotation_it_should_point_the_wheels_tangentially() {
+    let vehicle = TestVehicle::new(0.0);
+
+    let twist = Vector6::new(0.0, 0.0, 0.0, 0.0, 0.0, 1.0);
+    let setpoints = vehicle
+        .model
+        .steering_and_wheel_rates_for_body_twist(twist)
+        .unwrap();
+
+    for (index, wheel) in vehicle.wheels.iter().enumerate() {
+        let (x, y) = vehicle.module_positions[index];
+        let setpoint = setpoints[wheel];
+
+        assert_approx_eq(x.atan2(-y), setpoint.steering_angle());
+        assert_approx_eq(
+            (x * x + y * y).sqrt() / 0.1,
+            setpoint.wheel_angular_velocity(),
+        );
+    }
+}
+
+#[test]
+fn when_computing_setpoints_with_rotated_steering_frames_it_should_offset_the_joint_position() {
+    let vehicle = TestVehicle::new(0.25 * PI);
+
+    let twist = Vector6::new(1.0, 0.0, 0.0, 0.0, 0.0, 0.0);
+    let setpoints = vehicle
+        .model
+        .steering_and_wheel_rates_for_body_twist(twist)
+        .unwrap();
+
+    for wheel in vehicle.wheels.iter() {
+        let setpoint = setpoints[wheel];
+        assert_approx_eq(0.0, setpoint.steering_angle());
+        assert_approx_eq(-0.25 * PI, setpoint.steering_joint_position());
+        assert_approx_eq(10.0, setpoint.wheel_angular_velocity());
+    }
+}
+
+#[test]
+fn when_computing_setpoints_for_a_stationary_wheel_it_should_keep_the_current_steering_angle() {
+    let vehicle = TestVehicle::new(0.0);
+    for index in 0..4 {
+        vehicle.set_steering_angle(index, 0.3);
+    }
+    vehicle.wait_for_updates();
+
+    let setpoints = vehicle
+        .model
+        .steering_and_wheel_rates_for_body_twist(Vector6::zeros())
+        .unwrap();
+
+    for wheel in vehicle.wheels.iter() {
+        let setpoint = setpoints[wheel];
+        assert_approx_eq(0.3, setpoint.steering_angle());
+        assert_approx_eq(0.3, setpoint.steering_joint_position());
+        assert_approx_eq(0.0, setpoint.wheel_angular_velocity());
+    }
+}
+
+#[test]
+fn when_applying_the_setpoints_the_odometry_should_return_the_twist() {
+    let vehicle = TestVehicle::new(0.1);
+
+    let twist = Vector6::new(0.4, -0.3, 0.0, 0.0, 0.0, 0.7);
+    let setpoints = vehicle
+        .model
+        .steering_and_wheel_rates_for_body_twist(twist)
+        .unwrap();
+
+    let mut joint_states = HashMap::new();
+    for (wheel, setpoint) in setpoints.iter() {
+        joint_states.insert(
+            *setpoint.steering_frame(),
+            JointState::new(setpoint.steering_joint_position(), None, None, None),
+        );
+        joint_states.insert(
+            *wheel,
+            JointState::new(0.0, Some(setpoint.wheel_angular_velocity()), None, None),
+        );
+    }
+
+    let consistency = vehicle.model.twist_consistency(&joint_states).unwrap();
+    for (a, b) in twist.iter().zip(consistency.twist().iter()) {
+        assert_approx_eq(*a, *b);
+    }
+    assert!(consistency.inconsistent_wheels(1e-9).is_empty());
+}
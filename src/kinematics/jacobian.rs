@@ -0,0 +1,384 @@
+//! Provides the geometric Jacobian of the frames of the vehicle.
+//!
+//! The Jacobian of a frame maps the velocities of the joints between the body and the frame to
+//! the twist of the frame relative to the body. The twist is expressed in the body reference
+//! frame and describes the motion of the origin of the frame, using the twist conventions of the
+//! [kinematics](crate::kinematics) module.
+//!
+//! Combining the Jacobian with the joint velocities reported by the hardware gives the twist of
+//! any frame, see [MotionModel::velocity_of_frame_in_body]. Adding the joint accelerations gives
+//! the spatial acceleration of a frame, see [MotionModel::acceleration_of_frame_in_body].
+
+use nalgebra::{DVector, Matrix4, Matrix6xX, Vector3, Vector6};
+
+use crate::{
+    hardware::joint_state::JointState,
+    model_elements::{
+        frame_elements::{FrameDofType, FrameID},
+        model::MotionModel,
+    },
+    Error,
+};
+
+#[cfg(test)]
+#[path = "jacobian_tests.rs"]
+mod jacobian_tests;
+
+/// The step, in meters or radians, used to differentiate the transforms of the joints that are
+/// described by a kinematics table.
+const KINEMATICS_TABLE_STEP: f64 = 1e-6;
+
+/// The time step, in seconds, used to differentiate the Jacobian along the current joint
+/// velocities.
+const JACOBIAN_TIME_STEP: f64 = 1e-6;
+
+/// Stores the geometric Jacobian for a frame of the vehicle.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FrameJacobian {
+    /// The ID of the frame for which the Jacobian was computed.
+    frame: FrameID,
+
+    /// The joints that move the frame, ordered from the body to the frame.
+    joints: Vec<FrameID>,
+
+    /// The 6xN matrix that maps the joint velocities to the twist of the frame.
+    matrix: Matrix6xX<f64>,
+}
+
+impl FrameJacobian {
+    /// Returns the column of the Jacobian for the given joint, or [None] if the joint does not
+    /// move the frame.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'joint' - The [FrameID] of the joint.
+    pub fn column_for(&self, joint: &FrameID) -> Option<Vector6<f64>> {
+        self.joints
+            .iter()
+            .position(|j| j == joint)
+            .map(|index| self.matrix.column(index).into_owned())
+    }
+
+    /// Returns the [FrameID] of the frame for which the Jacobian was computed.
+    pub fn frame(&self) -> &FrameID {
+        &self.frame
+    }
+
+    /// Returns the [FrameID] of the joints that move the frame, ordered from the body to the
+    /// frame. The order of the joints matches the order of the columns of the matrix.
+    pub fn joints(&self) -> &[FrameID] {
+        &self.joints
+    }
+
+    /// Returns the 6xN matrix that maps the joint velocities to the twist of the frame.
+    pub fn matrix(&self) -> &Matrix6xX<f64> {
+        &self.matrix
+    }
+}
+
+impl MotionModel {
+    /// Returns the spatial acceleration of the given frame relative to the body, based on the
+    /// current joint positions, velocities and accelerations.
+    ///
+    /// The acceleration is the time derivative of the twist returned by
+    /// [MotionModel::velocity_of_frame_in_body], i.e. `[a_x, a_y, a_z, α_x, α_y, α_z]` for the
+    /// origin of the frame, expressed in the body reference frame. It includes the contribution
+    /// of the joint accelerations as well as the velocity dependent terms, e.g. the centripetal
+    /// acceleration of a wheel that is steered. Joints that have no actuator or sensor, or for
+    /// which the hardware does not report a velocity or acceleration, are assumed to be at rest or
+    /// to move at a constant velocity respectively.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'frame_id' - The [FrameID] of the frame.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the [ReferenceFrame](crate::model_elements::frame_elements::ReferenceFrame)
+    ///   is not part of the model.
+    pub fn acceleration_of_frame_in_body(&self, frame_id: &FrameID) -> Result<Vector6<f64>, Error> {
+        self.acceleration_of_frame_in_body_with(frame_id, |id| self.joint_state_for(id).ok())
+    }
+
+    /// Returns the spatial acceleration of the given frame relative to the body using the joint
+    /// states provided by the 'joint_state' function.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'frame_id' - The [FrameID] of the frame.
+    /// * 'joint_state' - Returns the state of the joint for a given [FrameID], or [None] if the
+    ///   joint should be assumed to be at rest at its zero position.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the frame is not part of the model.
+    pub(crate) fn acceleration_of_frame_in_body_with<F>(
+        &self,
+        frame_id: &FrameID,
+        joint_state: F,
+    ) -> Result<Vector6<f64>, Error>
+    where
+        F: Fn(&FrameID) -> Option<JointState>,
+    {
+        let position = |joint: &FrameID| joint_state(joint).map(|s| s.position());
+        let velocity = |joint: &FrameID| joint_state(joint).and_then(|s| *s.velocity());
+        let acceleration = |joint: &FrameID| joint_state(joint).and_then(|s| *s.acceleration());
+
+        let jacobian = self.jacobian_for_with(frame_id, position)?;
+        let joint_values = |value: &dyn Fn(&FrameID) -> Option<f64>| {
+            DVector::from_iterator(
+                jacobian.joints.len(),
+                jacobian
+                    .joints
+                    .iter()
+                    .map(|joint| value(joint).unwrap_or(0.0)),
+            )
+        };
+        let velocities = joint_values(&velocity);
+        let accelerations = joint_values(&acceleration);
+
+        // Differentiate the Jacobian by moving the joints along their current velocities
+        let moved_jacobian = |time: f64| {
+            self.jacobian_for_with(frame_id, |joint| {
+                position(joint).map(|p| p + velocity(joint).unwrap_or(0.0) * time)
+            })
+        };
+        let jacobian_derivative = (moved_jacobian(JACOBIAN_TIME_STEP)?.matrix
+            - moved_jacobian(-JACOBIAN_TIME_STEP)?.matrix)
+            / (2.0 * JACOBIAN_TIME_STEP);
+
+        Ok(&jacobian.matrix * accelerations + jacobian_derivative * velocities)
+    }
+
+    /// Returns the geometric Jacobian for the given frame based on the current joint positions.
+    ///
+    /// The Jacobian has a column for each of the joints between the body and the frame that can
+    /// move, including the joint of the frame itself. Joints are included whether or not they
+    /// are actuated. Multiplying the Jacobian with the joint velocities, in the order given by
+    /// [FrameJacobian::joints], returns the twist of the origin of the frame relative to the body,
+    /// expressed in the body reference frame.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'frame_id' - The [FrameID] of the frame.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the [ReferenceFrame](crate::model_elements::frame_elements::ReferenceFrame)
+    ///   is not part of the model.
+    pub fn jacobian_for(&self, frame_id: &FrameID) -> Result<FrameJacobian, Error> {
+        self.jacobian_for_with(frame_id, |id| self.current_joint_position(id))
+    }
+
+    /// Returns the geometric Jacobian for the given frame using the joint positions provided by
+    /// the 'joint_position' function.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'frame_id' - The [FrameID] of the frame.
+    /// * 'joint_position' - Returns the position of the joint for a given [FrameID], or [None]
+    ///   if the joint should be assumed to be at its zero position.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the frame is not part of the model.
+    pub(crate) fn jacobian_for_with<F>(
+        &self,
+        frame_id: &FrameID,
+        joint_position: F,
+    ) -> Result<FrameJacobian, Error>
+    where
+        F: Fn(&FrameID) -> Option<f64>,
+    {
+        let body = *self.body()?;
+        let frame_to_body =
+            self.homogeneous_transform_to_ancestor_with(frame_id, &body, &joint_position)?;
+        let frame_origin = translation_of(&frame_to_body);
+
+        // Collect the joints from the frame to the body
+        let mut chain = Vec::new();
+        let mut current = *frame_id;
+        while current != body {
+            let parent = *self.parent_of(&current)?;
+            chain.push((current, parent));
+            current = parent;
+        }
+
+        let mut joints = Vec::new();
+        let mut columns = Vec::new();
+        for (joint, parent) in chain.iter().rev() {
+            let parent_to_body =
+                self.homogeneous_transform_to_ancestor_with(parent, &body, &joint_position)?;
+            let column = match self.kinematics_table(joint) {
+                Some(_) => self.table_jacobian_column(
+                    joint,
+                    &parent_to_body,
+                    &frame_origin,
+                    joint_position(joint).unwrap_or(0.0),
+                ),
+                None => axis_jacobian_column(
+                    self.frame_degree_of_freedom(joint)?,
+                    &parent_to_body,
+                    &frame_origin,
+                ),
+            };
+
+            if let Some(c) = column {
+                joints.push(*joint);
+                columns.push(c);
+            }
+        }
+
+        let matrix = if columns.is_empty() {
+            Matrix6xX::zeros(0)
+        } else {
+            Matrix6xX::from_columns(&columns)
+        };
+
+        Ok(FrameJacobian {
+            frame: *frame_id,
+            joints,
+            matrix,
+        })
+    }
+
+    /// Returns the twist of the given frame relative to the body, based on the current joint
+    /// positions and velocities.
+    ///
+    /// The joint velocities are propagated from the body down the chain to the frame. The twist
+    /// is expressed in the body reference frame and describes the motion of the origin of the
+    /// frame, i.e. `[v_x, v_y, v_z, ω_x, ω_y, ω_z]`. Joints that have no actuator or sensor, or
+    /// for which the hardware does not report a velocity, are assumed to be at rest, unless they
+    /// are part of a kinematic loop, in which case their velocity is inferred from the
+    /// [LoopClosure](crate::model_elements::loop_closure::LoopClosure) constraints.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'frame_id' - The [FrameID] of the frame.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the [ReferenceFrame](crate::model_elements::frame_elements::ReferenceFrame)
+    ///   is not part of the model.
+    pub fn velocity_of_frame_in_body(&self, frame_id: &FrameID) -> Result<Vector6<f64>, Error> {
+        self.velocity_of_frame_in_body_with(frame_id, |id| self.joint_state_for(id).ok())
+    }
+
+    /// Returns the twist of the given frame relative to the body using the joint states provided
+    /// by the 'joint_state' function.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'frame_id' - The [FrameID] of the frame.
+    /// * 'joint_state' - Returns the state of the joint for a given [FrameID], or [None] if the
+    ///   joint should be assumed to be at rest at its zero position.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the frame is not part of the model.
+    pub(crate) fn velocity_of_frame_in_body_with<F>(
+        &self,
+        frame_id: &FrameID,
+        joint_state: F,
+    ) -> Result<Vector6<f64>, Error>
+    where
+        F: Fn(&FrameID) -> Option<JointState>,
+    {
+        let jacobian =
+            self.jacobian_for_with(frame_id, |id| joint_state(id).map(|s| s.position()))?;
+        let inferred = self.loop_closure_joint_velocities_with(&joint_state)?;
+        let velocities = DVector::from_iterator(
+            jacobian.joints.len(),
+            jacobian.joints.iter().map(|joint| {
+                joint_state(joint)
+                    .and_then(|s| *s.velocity())
+                    .or_else(|| inferred.get(joint).copied())
+                    .unwrap_or(0.0)
+            }),
+        );
+
+        Ok(jacobian.matrix * velocities)
+    }
+
+    /// Returns the Jacobian column for a joint that is described by a kinematics table. The
+    /// column is computed by differentiating the table at the given joint position.
+    ///
+    /// Returns [None] if the joint has no kinematics table.
+    fn table_jacobian_column(
+        &self,
+        joint: &FrameID,
+        parent_to_body: &Matrix4<f64>,
+        frame_origin: &Vector3<f64>,
+        position: f64,
+    ) -> Option<Vector6<f64>> {
+        let table = self.kinematics_table(joint)?;
+
+        // Use a one-sided difference at the ends of the table
+        let lower = (position - KINEMATICS_TABLE_STEP).max(table.minimum_joint_value());
+        let upper = (position + KINEMATICS_TABLE_STEP).min(table.maximum_joint_value());
+        let step = upper - lower;
+        if step <= 0.0 {
+            return Some(Vector6::zeros());
+        }
+
+        let lower_transform = table.transform_at(lower);
+        let upper_transform = table.transform_at(upper);
+        let current = table.transform_at(position);
+
+        // Velocity of the joint origin and angular velocity, both in the parent frame
+        let linear =
+            (upper_transform.translation.vector - lower_transform.translation.vector) / step;
+        let angular =
+            (upper_transform.rotation * lower_transform.rotation.inverse()).scaled_axis() / step;
+
+        let rotation = parent_to_body.fixed_view::<3, 3>(0, 0).into_owned();
+        let joint_origin = (parent_to_body * current.to_homogeneous())
+            .fixed_view::<3, 1>(0, 3)
+            .into_owned();
+
+        let angular_in_body = rotation * angular;
+        let linear_in_body =
+            rotation * linear + angular_in_body.cross(&(frame_origin - joint_origin));
+
+        Some(twist_from(&linear_in_body, &angular_in_body))
+    }
+}
+
+/// Returns the Jacobian column for a joint that moves along, or rotates around, one of the
+/// axes of the parent frame.
+///
+/// Returns [None] if the joint is static.
+fn axis_jacobian_column(
+    dof: FrameDofType,
+    parent_to_body: &Matrix4<f64>,
+    frame_origin: &Vector3<f64>,
+) -> Option<Vector6<f64>> {
+    let axis = dof.axis()?;
+    let is_revolute = dof.is_revolute();
+
+    // The joint motion is applied in the parent frame, around the origin of the parent frame
+    let axis_in_body = parent_to_body.fixed_view::<3, 3>(0, 0) * axis;
+    let column = if is_revolute {
+        let joint_origin = translation_of(parent_to_body);
+        twist_from(
+            &axis_in_body.cross(&(frame_origin - joint_origin)),
+            &axis_in_body,
+        )
+    } else {
+        twist_from(&axis_in_body, &Vector3::zeros())
+    };
+
+    Some(column)
+}
+
+/// Returns the translation part of the given homogeneous transform.
+pub(crate) fn translation_of(transform: &Matrix4<f64>) -> Vector3<f64> {
+    transform.fixed_view::<3, 1>(0, 3).into_owned()
+}
+
+/// Returns the twist with the given linear and angular velocities.
+fn twist_from(linear: &Vector3<f64>, angular: &Vector3<f64>) -> Vector6<f64> {
+    Vector6::new(
+        linear.x, linear.y, linear.z, angular.x, angular.y, angular.z,
+    )
+}
@@ -0,0 +1,279 @@
+use std::{collections::HashMap, f64::consts::PI, time::Duration};
+
+use nalgebra::{Isometry3, Matrix4, Translation3, UnitQuaternion, Vector3, Vector6};
+
+use crate::{
+    hardware::joint_state::JointState,
+    model_elements::{
+        frame_elements::FrameID,
+        kinematics_table::{JointKinematicsTable, TableInterpolation},
+        model::MotionModel,
+    },
+    test_fixtures::TestModule,
+    Error,
+};
+
+// Computes the Jacobian column for the given joint by differentiating the transform from the
+// frame to the body.
+fn numerical_jacobian_column(
+    model: &MotionModel,
+    frame: &FrameID,
+    joint: &FrameID,
+    positions: &HashMap<FrameID, f64>,
+) -> Vector6<f64> {
+    let step = 1e-6;
+    let transform_at = |offset: f64| -> Matrix4<f64> {
+        let mut p = positions.clone();
+        *p.entry(*joint).or_insert(0.0) += offset;
+        model
+            .homogeneous_transform_to_ancestor_with(frame, model.body().unwrap(), |id| {
+                p.get(id).copied()
+            })
+            .unwrap()
+    };
+
+    let lower = transform_at(-step);
+    let upper = transform_at(step);
+
+    let linear = (upper.fixed_view::<3, 1>(0, 3) - lower.fixed_view::<3, 1>(0, 3)) / (2.0 * step);
+    // The angular velocity is the skew-symmetric matrix dR/dq * R^T
+    let rotation = transform_at(0.0).fixed_view::<3, 3>(0, 0).into_owned();
+    let derivative =
+        (upper.fixed_view::<3, 3>(0, 0) - lower.fixed_view::<3, 3>(0, 0)) / (2.0 * step);
+    let skew = derivative * rotation.transpose();
+    let angular = Vector3::new(skew[(2, 1)], skew[(0, 2)], skew[(1, 0)]);
+
+    Vector6::new(
+        linear[0], linear[1], linear[2], angular.x, angular.y, angular.z,
+    )
+}
+
+fn assert_matches_numerical_jacobian(
+    model: &MotionModel,
+    frame: &FrameID,
+    positions: &HashMap<FrameID, f64>,
+) {
+    let jacobian = model
+        .jacobian_for_with(frame, |id| positions.get(id).copied())
+        .unwrap();
+    for joint in jacobian.joints() {
+        let expected = numerical_jacobian_column(model, frame, joint, positions);
+        let actual = jacobian.column_for(joint).unwrap();
+        assert!(
+            (expected - actual).norm() < 1e-6,
+            "Column for joint {} was {} but expected {}",
+            joint,
+            actual,
+            expected
+        );
+    }
+}
+
+#[test]
+fn when_computing_the_jacobian_it_should_include_the_moving_joints_from_the_body() {
+    let module = TestModule::new();
+
+    let jacobian = module.model.jacobian_for(&module.wheel).unwrap();
+    assert_eq!(&module.wheel, jacobian.frame());
+    assert_eq!(
+        &[module.suspension, module.steering, module.wheel],
+        jacobian.joints()
+    );
+    assert_eq!((6, 3), jacobian.matrix().shape());
+
+    // The suspension moves along the z-axis of the body
+    let suspension = jacobian.column_for(&module.suspension).unwrap();
+    assert!((suspension - Vector6::new(0.0, 0.0, 1.0, 0.0, 0.0, 0.0)).norm() < 1e-12);
+
+    // The steering rotates around the z-axis through the origin of the suspension frame
+    let steering = jacobian.column_for(&module.steering).unwrap();
+    let arm = Vector3::new(0.2 * (PI / 6.0).cos(), 0.2 * (PI / 6.0).sin(), -0.2);
+    let expected_linear = Vector3::z().cross(&arm);
+    assert!(
+        (steering
+            - Vector6::new(
+                expected_linear.x,
+                expected_linear.y,
+                expected_linear.z,
+                0.0,
+                0.0,
+                1.0
+            ))
+        .norm()
+            < 1e-12
+    );
+
+    assert!(jacobian.column_for(&module.body).is_none());
+}
+
+#[test]
+fn when_computing_the_jacobian_it_should_use_the_current_joint_positions() {
+    let module = TestModule::new();
+
+    module.steering_actuator.send_position(0.5 * PI);
+    std::thread::sleep(Duration::from_millis(50));
+
+    let jacobian = module.model.jacobian_for(&module.wheel).unwrap();
+
+    let mut positions = HashMap::new();
+    positions.insert(module.steering, 0.5 * PI);
+    let expected = module
+        .model
+        .jacobian_for_with(&module.wheel, |id| positions.get(id).copied())
+        .unwrap();
+
+    assert!((expected.matrix() - jacobian.matrix()).norm() < 1e-12);
+}
+
+#[test]
+fn when_computing_the_jacobian_it_should_match_the_derivative_of_the_transforms() {
+    let module = TestModule::new();
+
+    let mut positions = HashMap::new();
+    positions.insert(module.suspension, 0.05);
+    positions.insert(module.steering, 1.2);
+    positions.insert(module.wheel, -0.7);
+
+    assert_matches_numerical_jacobian(&module.model, &module.wheel, &positions);
+    assert_matches_numerical_jacobian(&module.model, &module.steering, &positions);
+}
+
+#[test]
+fn when_computing_the_jacobian_with_a_kinematics_table_it_should_match_the_derivative_of_the_table()
+{
+    let mut module = TestModule::new();
+
+    // The suspension swings on an arm, so the frame moves along an arc and rotates
+    let samples = (0..=10)
+        .map(|i| {
+            let angle = -0.2 + 0.04 * i as f64;
+            let transform = Isometry3::from_parts(
+                Translation3::new(1.0 + 0.3 * angle.cos(), 0.5, 0.3 * angle.sin()),
+                UnitQuaternion::from_euler_angles(0.0, -angle, PI / 6.0),
+            );
+            (angle, transform)
+        })
+        .collect();
+    let table = JointKinematicsTable::new(samples, TableInterpolation::CubicSpline).unwrap();
+    module
+        .model
+        .set_kinematics_table(&module.suspension, table)
+        .unwrap();
+
+    let mut positions = HashMap::new();
+    positions.insert(module.suspension, 0.05);
+    positions.insert(module.steering, -0.4);
+
+    assert_matches_numerical_jacobian(&module.model, &module.wheel, &positions);
+}
+
+#[test]
+fn when_computing_the_jacobian_for_the_body_it_should_be_empty() {
+    let module = TestModule::new();
+
+    let jacobian = module.model.jacobian_for(&module.body).unwrap();
+    assert!(jacobian.joints().is_empty());
+    assert_eq!((6, 0), jacobian.matrix().shape());
+}
+
+#[test]
+fn when_computing_the_jacobian_for_an_unknown_frame_it_should_error() {
+    let module = TestModule::new();
+
+    let id = FrameID::new();
+    assert_eq!(
+        Err(Error::MissingFrameElement { id }),
+        module.model.jacobian_for(&id)
+    );
+}
+
+#[test]
+fn when_computing_the_velocity_of_a_frame_it_should_propagate_the_joint_velocities() {
+    let module = TestModule::new();
+
+    let jacobian = module.model.jacobian_for(&module.wheel).unwrap();
+    assert_eq!(
+        Vector6::zeros(),
+        module
+            .model
+            .velocity_of_frame_in_body(&module.wheel)
+            .unwrap()
+    );
+
+    module
+        .steering_actuator
+        .send_state(JointState::new(0.0, Some(2.0), None, None));
+    std::thread::sleep(Duration::from_millis(50));
+
+    // The suspension has no actuator, so only the steering joint moves the wheel
+    let expected = 2.0 * jacobian.column_for(&module.steering).unwrap();
+    let twist = module
+        .model
+        .velocity_of_frame_in_body(&module.wheel)
+        .unwrap();
+    assert!((expected - twist).norm() < 1e-12);
+    assert!((2.0 - twist[5]).abs() < 1e-12);
+
+    assert_eq!(
+        Vector6::zeros(),
+        module
+            .model
+            .velocity_of_frame_in_body(&module.body)
+            .unwrap()
+    );
+
+    let id = FrameID::new();
+    assert_eq!(
+        Err(Error::MissingFrameElement { id }),
+        module.model.velocity_of_frame_in_body(&id)
+    );
+}
+
+#[test]
+fn when_computing_the_acceleration_of_a_frame_it_should_include_the_velocity_terms() {
+    let module = TestModule::new();
+
+    assert_eq!(
+        Vector6::zeros(),
+        module
+            .model
+            .acceleration_of_frame_in_body(&module.wheel)
+            .unwrap()
+    );
+
+    module
+        .steering_actuator
+        .send_state(JointState::new(0.0, Some(2.0), Some(3.0), None));
+    std::thread::sleep(Duration::from_millis(50));
+
+    // The wheel moves on a circle around the steering axis, which goes through the origin of
+    // the suspension frame
+    let arm = Vector3::new(0.2 * (PI / 6.0).cos(), 0.2 * (PI / 6.0).sin(), -0.2);
+    let tangential = 3.0 * Vector3::z().cross(&arm);
+    let centripetal = -4.0 * Vector3::new(arm.x, arm.y, 0.0);
+    let expected_linear = tangential + centripetal;
+
+    let acceleration = module
+        .model
+        .acceleration_of_frame_in_body(&module.wheel)
+        .unwrap();
+    assert!(
+        (acceleration
+            - Vector6::new(
+                expected_linear.x,
+                expected_linear.y,
+                expected_linear.z,
+                0.0,
+                0.0,
+                3.0
+            ))
+        .norm()
+            < 1e-6
+    );
+
+    let id = FrameID::new();
+    assert_eq!(
+        Err(Error::MissingFrameElement { id }),
+        module.model.acceleration_of_frame_in_body(&id)
+    );
+}
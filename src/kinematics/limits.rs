@@ -0,0 +1,72 @@
+//! Provides the functions used to check and enforce the speed limits of the vehicle body.
+//!
+//! The limits are taken from the active
+//! [OperatingMode](crate::model_elements::operating_mode::OperatingMode) of the model. When no
+//! operating mode is active the motion of the body is not limited.
+
+use nalgebra::Vector6;
+
+use crate::model_elements::model::MotionModel;
+
+#[cfg(test)]
+#[path = "limits_tests.rs"]
+mod limits_tests;
+
+impl MotionModel {
+    /// Returns a value indicating if the given body twist is within the speed limits of the
+    /// active operating mode.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'twist' - The body twist as `[v_x, v_y, v_z, ω_x, ω_y, ω_z]` in the body reference frame.
+    pub fn is_twist_within_limits(&self, twist: &Vector6<f64>) -> bool {
+        self.twist_limit_factor(twist) >= 1.0
+    }
+
+    /// Returns the given body twist, scaled down so that it is within the speed limits of the
+    /// active operating mode.
+    ///
+    /// The linear and angular components are scaled by the same factor so that the direction
+    /// of motion and the curvature of the path are preserved. Twists that are within the limits
+    /// are returned unchanged.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'twist' - The body twist as `[v_x, v_y, v_z, ω_x, ω_y, ω_z]` in the body reference frame.
+    pub fn limit_twist(&self, twist: &Vector6<f64>) -> Vector6<f64> {
+        let factor = self.twist_limit_factor(twist);
+        if factor >= 1.0 {
+            *twist
+        } else {
+            twist * factor
+        }
+    }
+
+    /// Returns the largest factor, up to and including 1.0, by which the given twist can be
+    /// multiplied so that it is within the limits of the active operating mode. Returns a value
+    /// larger than or equal to 1.0 if the twist is within the limits.
+    fn twist_limit_factor(&self, twist: &Vector6<f64>) -> f64 {
+        let mode = match self.operating_mode() {
+            Some(m) => m,
+            None => return 1.0,
+        };
+
+        let linear_speed = twist.fixed_rows::<3>(0).norm();
+        let angular_speed = twist.fixed_rows::<3>(3).norm();
+
+        let linear_factor = limit_factor(linear_speed, mode.effective_maximum_linear_speed());
+        let angular_factor = limit_factor(angular_speed, mode.effective_maximum_angular_speed());
+
+        linear_factor.min(angular_factor)
+    }
+}
+
+/// Returns the factor by which the given speed should be multiplied to be at the maximum speed,
+/// or 1.0 if the speed is below the maximum.
+fn limit_factor(speed: f64, maximum_speed: f64) -> f64 {
+    if speed <= maximum_speed {
+        1.0
+    } else {
+        maximum_speed.max(0.0) / speed
+    }
+}
@@ -0,0 +1,99 @@
+use nalgebra::{Translation3, UnitQuaternion, Vector6};
+
+use crate::{
+    model_elements::{model::MotionModel, operating_mode::OperatingMode},
+    test_fixtures::physical_properties,
+    Error,
+};
+
+fn create_model() -> MotionModel {
+    let mut model = MotionModel::new();
+    model
+        .add_body(
+            "body".to_string(),
+            Translation3::identity(),
+            UnitQuaternion::identity(),
+            physical_properties(),
+        )
+        .unwrap();
+
+    model
+        .add_operating_mode(OperatingMode::new("indoor".to_string(), 1.0, 1.0, 0.5))
+        .unwrap();
+    model
+        .add_operating_mode(OperatingMode::new("outdoor".to_string(), 3.0, 2.0, 1.0))
+        .unwrap();
+
+    model
+}
+
+#[test]
+fn when_limiting_twist_without_operating_mode_it_should_not_limit() {
+    let model = create_model();
+    let twist = Vector6::new(10.0, 0.0, 0.0, 0.0, 0.0, 10.0);
+
+    assert!(model.is_twist_within_limits(&twist));
+    assert_eq!(twist, model.limit_twist(&twist));
+}
+
+#[test]
+fn when_limiting_twist_within_the_limits_it_should_return_the_twist() {
+    let mut model = create_model();
+    model.set_operating_mode("indoor").unwrap();
+    let twist = Vector6::new(0.3, 0.4, 0.0, 0.0, 0.0, 0.5);
+
+    assert!(model.is_twist_within_limits(&twist));
+    assert_eq!(twist, model.limit_twist(&twist));
+}
+
+#[test]
+fn when_limiting_twist_outside_the_limits_it_should_scale_the_twist() {
+    let mut model = create_model();
+    model.set_operating_mode("indoor").unwrap();
+    let twist = Vector6::new(0.6, 0.8, 0.0, 0.0, 0.0, 0.5);
+
+    assert!(!model.is_twist_within_limits(&twist));
+
+    let limited = model.limit_twist(&twist);
+    assert!((limited - twist * 0.5).norm() < 1e-12);
+    assert!(model.is_twist_within_limits(&limited));
+}
+
+#[test]
+fn when_switching_operating_mode_it_should_use_the_new_limits() {
+    let mut model = create_model();
+    let twist = Vector6::new(2.0, 0.0, 0.0, 0.0, 0.0, 1.5);
+
+    model.set_operating_mode("indoor").unwrap();
+    assert!(!model.is_twist_within_limits(&twist));
+
+    model.set_operating_mode("outdoor").unwrap();
+    assert!(model.is_twist_within_limits(&twist));
+    assert_eq!("outdoor", model.operating_mode().unwrap().name());
+
+    // The angular speed is the limiting factor
+    let fast_turn = Vector6::new(1.0, 0.0, 0.0, 0.0, 0.0, 4.0);
+    let limited = model.limit_twist(&fast_turn);
+    assert!((limited - fast_turn * 0.5).norm() < 1e-12);
+
+    model.clear_operating_mode();
+    assert!(model.operating_mode().is_none());
+    assert!(model.is_twist_within_limits(&fast_turn));
+}
+
+#[test]
+fn when_setting_unknown_operating_mode_it_should_error() {
+    let mut model = create_model();
+
+    let result = model.set_operating_mode("underwater");
+    assert_eq!(
+        Err(Error::MissingOperatingMode {
+            name: "underwater".to_string()
+        }),
+        result
+    );
+
+    let mut names: Vec<&str> = model.operating_modes().collect();
+    names.sort();
+    assert_eq!(vec!["indoor", "outdoor"], names);
+}
@@ -0,0 +1,193 @@
+//! Provides the functions used to compute the mass properties of the vehicle.
+//!
+//! The mass properties are computed by combining the [ChassisElementPhysicalProperties] of
+//! each of the chassis elements, taking into account the current position of the joints.
+//!
+//! Spatial inertias use the same ordering as the twists, i.e. the linear part first followed by
+//! the angular part, so that the kinetic energy of an element with twist `V` is `0.5 V^T M V`.
+//! For an element with mass `m`, center of mass `c` and moment of inertia `I_c` around the
+//! center of mass, the spatial inertia about the origin of the element reference frame is
+//!
+//! ```text
+//! | m 1      -m [c]x               |
+//! | m [c]x   I_c - m [c]x [c]x     |
+//! ```
+//!
+//! where `[c]x` is the skew-symmetric matrix of `c`.
+//!
+//! [ChassisElementPhysicalProperties]: crate::model_elements::model::ChassisElementPhysicalProperties
+
+use nalgebra::{Matrix3, Matrix6, Vector3};
+
+use crate::{
+    model_elements::{frame_elements::FrameID, model::MotionModel, wrench::Wrench},
+    Error,
+};
+
+#[cfg(test)]
+#[path = "mass_properties_tests.rs"]
+mod mass_properties_tests;
+
+/// Describes the mass of a single chassis element in the body reference frame.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct ElementMass {
+    /// The mass in kilograms.
+    pub(crate) mass: f64,
+
+    /// The position of the center of mass in the body reference frame.
+    pub(crate) center_of_mass: Vector3<f64>,
+
+    /// The moment of inertia of the element around its center of mass, along the axes of the
+    /// body reference frame.
+    pub(crate) moment_of_inertia: Matrix3<f64>,
+}
+
+/// Returns the total mass and the center of mass of the given elements. The center of mass is
+/// zero when the total mass is zero.
+///
+/// ## Parameters
+///
+/// * 'elements' - The elements for which the center of mass should be computed.
+pub(crate) fn combined_center_of_mass(elements: &[ElementMass]) -> (f64, Vector3<f64>) {
+    let mass: f64 = elements.iter().map(|e| e.mass).sum();
+    if mass <= 0.0 {
+        return (mass, Vector3::zeros());
+    }
+
+    let weighted = elements
+        .iter()
+        .fold(Vector3::zeros(), |acc, e| acc + e.center_of_mass * e.mass);
+    (mass, weighted / mass)
+}
+
+/// Returns the mass of each of the chassis elements of the model in the body reference frame,
+/// using the joint positions provided by the 'joint_position' function.
+///
+/// It is assumed that the moment of inertia of each chassis element is given around its center
+/// of mass, along the axes of the reference frame of the element.
+///
+/// ## Parameters
+///
+/// * 'model' - The model that contains the chassis elements.
+/// * 'joint_position' - Returns the position of the joint for a given [FrameID], or [None]
+///   if the joint should be assumed to be at its zero position.
+///
+/// ## Errors
+///
+/// * [Error::MissingFrameElement] - Returned when the model has no body.
+pub(crate) fn element_masses_in_body_with<F>(
+    model: &MotionModel,
+    joint_position: F,
+) -> Result<Vec<ElementMass>, Error>
+where
+    F: Fn(&FrameID) -> Option<f64>,
+{
+    let body = *model.body()?;
+
+    let mut result = Vec::new();
+    let mut stack = vec![(body, nalgebra::Matrix4::identity())];
+    while let Some((frame, to_body)) = stack.pop() {
+        for child in model.children_of(&frame)? {
+            let to_parent =
+                model.homogeneous_transform_to_ancestor_with(child, &frame, &joint_position)?;
+            stack.push((*child, to_body * to_parent));
+        }
+
+        let element = model.chassis_element(&frame)?;
+        let rotation: Matrix3<f64> = to_body.fixed_view::<3, 3>(0, 0).into();
+        let translation: Vector3<f64> = to_body.fixed_view::<3, 1>(0, 3).into();
+        result.push(ElementMass {
+            mass: element.mass_in_kg(),
+            center_of_mass: rotation * element.center_of_mass() + translation,
+            moment_of_inertia: rotation * element.moment_of_inertia() * rotation.transpose(),
+        });
+    }
+
+    Ok(result)
+}
+
+impl MotionModel {
+    /// Returns the spatial inertia of the vehicle about the origin of the body reference frame,
+    /// along the axes of the body reference frame, taking into account the current position of
+    /// the joints.
+    ///
+    /// The spatial inertia of each chassis element is assumed to be given about the origin of
+    /// the reference frame of the element, see the [module documentation](self). The spatial
+    /// inertias are moved to the body reference frame with the parallel axis theorem and summed.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the model has no body.
+    pub fn composite_spatial_inertia(&self) -> Result<Matrix6<f64>, Error> {
+        let transforms = self.transforms_to_body_all()?;
+
+        let mut result = Matrix6::zeros();
+        for (frame, to_body) in transforms.iter() {
+            let element = self.chassis_element(frame)?;
+            let rotation: Matrix3<f64> = to_body.fixed_view::<3, 3>(0, 0).into();
+            let translation: Vector3<f64> = to_body.fixed_view::<3, 1>(0, 3).into();
+
+            // Maps a twist expressed in the body frame onto the twist of the element, expressed
+            // in the element frame.
+            let inverse_rotation = rotation.transpose();
+            let mut body_to_element = Matrix6::zeros();
+            body_to_element
+                .fixed_view_mut::<3, 3>(0, 0)
+                .copy_from(&inverse_rotation);
+            body_to_element
+                .fixed_view_mut::<3, 3>(0, 3)
+                .copy_from(&(-inverse_rotation * translation.cross_matrix()));
+            body_to_element
+                .fixed_view_mut::<3, 3>(3, 3)
+                .copy_from(&inverse_rotation);
+
+            result += body_to_element.transpose() * element.spatial_inertia() * body_to_element;
+        }
+
+        Ok(result)
+    }
+
+    /// Returns the position of the center of mass of the vehicle in the body reference frame,
+    /// taking into account the current position of the joints.
+    ///
+    /// The center of mass is the origin of the body reference frame when the total mass of
+    /// the vehicle is zero.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the model has no body.
+    pub fn center_of_mass_in_body(&self) -> Result<Vector3<f64>, Error> {
+        let elements = element_masses_in_body_with(self, |id| self.current_joint_position(id))?;
+        let (_, center_of_mass) = combined_center_of_mass(&elements);
+        Ok(center_of_mass)
+    }
+
+    /// Returns the sum of the external forces that act on the vehicle, as a [Wrench] that acts
+    /// on the origin of the body reference frame along the axes of the body reference frame,
+    /// taking into account the current position of the joints.
+    ///
+    /// The sum includes the weight of the vehicle, which acts on the center of mass, and the
+    /// external wrenches that are attached to the reference frames with
+    /// [MotionModel::apply_wrench].
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the model has no body.
+    pub fn net_external_wrench_in_body(&self) -> Result<Wrench, Error> {
+        let elements = element_masses_in_body_with(self, |id| self.current_joint_position(id))?;
+        let (mass, center_of_mass) = combined_center_of_mass(&elements);
+
+        let mut result = Wrench::from_force_at(mass * self.gravity_in_body(), &center_of_mass);
+        for (frame, wrench) in self.external_wrenches() {
+            result = result + wrench.transformed(&self.isometry_to_body(frame)?);
+        }
+
+        Ok(result)
+    }
+
+    /// Returns the total mass of the vehicle in kilograms, i.e. the sum of the masses of all
+    /// the chassis elements.
+    pub fn total_mass(&self) -> f64 {
+        self.chassis_elements().map(|e| e.mass_in_kg()).sum()
+    }
+}
@@ -0,0 +1,257 @@
+use std::{f64::consts::PI, time::Duration};
+
+use nalgebra::{Matrix3, Matrix6, Translation3, UnitQuaternion, Vector3, Vector6};
+
+use crate::{
+    change_notification_processing::HardwareChangeProcessor,
+    model_elements::{
+        frame_elements::{Actuator, FrameID},
+        model::{ChassisElementPhysicalProperties, MotionModel},
+        wrench::Wrench,
+    },
+    number_space::NumberSpaceType,
+    test_fixtures::physical_properties_with_mass,
+    test_support::MockActuator,
+    Error,
+};
+
+#[test]
+fn when_getting_the_mass_of_an_empty_model_it_should_be_zero() {
+    let model = MotionModel::new();
+
+    assert_eq!(0.0, model.total_mass());
+    assert!(matches!(
+        model.center_of_mass_in_body(),
+        Err(Error::MissingFrameElement { .. })
+    ));
+}
+
+#[test]
+fn when_getting_the_center_of_mass_it_should_follow_the_joints() {
+    let change_processor = HardwareChangeProcessor::new(1000);
+    let mut model = MotionModel::new();
+    let body = model
+        .add_body(
+            "body".to_string(),
+            Translation3::identity(),
+            UnitQuaternion::identity(),
+            physical_properties_with_mass(6.0, Vector3::new(0.0, 0.0, 0.5)),
+        )
+        .unwrap();
+
+    // A 2 kg steering frame at (1.0, 0.0, 0.0) with the center of mass 0.5 along its x-axis
+    let mut steering_actuator = MockActuator::new(NumberSpaceType::LinearUnlimited);
+    let steering = model
+        .add_steering_element(
+            "steering".to_string(),
+            body,
+            Translation3::new(1.0, 0.0, 0.0),
+            UnitQuaternion::identity(),
+            physical_properties_with_mass(2.0, Vector3::new(0.5, 0.0, 0.0)),
+            Actuator::new(&mut steering_actuator, &change_processor).unwrap(),
+        )
+        .unwrap();
+
+    let mut wheel_actuator = MockActuator::new(NumberSpaceType::LinearUnlimited);
+    model
+        .add_wheel(
+            "wheel".to_string(),
+            steering,
+            Translation3::new(0.0, 0.0, -0.5),
+            UnitQuaternion::identity(),
+            physical_properties_with_mass(2.0, Vector3::zeros()),
+            Actuator::new(&mut wheel_actuator, &change_processor).unwrap(),
+        )
+        .unwrap();
+
+    assert_eq!(10.0, model.total_mass());
+
+    // (6 * (0, 0, 0.5) + 2 * (1.5, 0, 0) + 2 * (1, 0, -0.5)) / 10
+    let center_of_mass = model.center_of_mass_in_body().unwrap();
+    assert!((center_of_mass - Vector3::new(0.5, 0.0, 0.2)).norm() < 1e-12);
+
+    // The joint rotates the steering frame around the z-axis of the body, which moves the
+    // center of mass of the steering frame to (0.0, 1.5, 0.0) and the wheel to (0.0, 1.0, -0.5)
+    steering_actuator.send_position(0.5 * PI);
+    std::thread::sleep(Duration::from_millis(50));
+
+    let center_of_mass = model.center_of_mass_in_body().unwrap();
+    assert!((center_of_mass - Vector3::new(0.0, 0.5, 0.2)).norm() < 1e-12);
+    assert_eq!(10.0, model.total_mass());
+}
+
+#[test]
+fn when_getting_the_net_external_wrench_it_should_combine_the_weight_and_the_wrenches() {
+    let mut model = MotionModel::new();
+    let body = model
+        .add_body(
+            "body".to_string(),
+            Translation3::identity(),
+            UnitQuaternion::identity(),
+            physical_properties_with_mass(8.0, Vector3::new(0.0, 0.0, 0.5)),
+        )
+        .unwrap();
+    let mount = model
+        .add_static_chassis_element(
+            "mount".to_string(),
+            body,
+            Translation3::new(1.0, 0.0, 0.0),
+            UnitQuaternion::from_euler_angles(0.0, 0.0, 0.5 * PI),
+            physical_properties_with_mass(2.0, Vector3::zeros()),
+        )
+        .unwrap();
+    model.set_gravity(Vector3::new(0.0, 0.0, -10.0));
+
+    // The center of mass is at (0.2, 0.0, 0.4)
+    let wrench = model.net_external_wrench_in_body().unwrap();
+    assert!((Vector3::new(0.0, 0.0, -100.0) - wrench.force()).norm() < 1e-12);
+    assert!((Vector3::new(0.0, 20.0, 0.0) - wrench.torque()).norm() < 1e-12);
+
+    // A payload pushes down on the mount and pulls along the x-axis of the mount
+    model
+        .apply_wrench(
+            &mount,
+            Wrench::new(Vector3::new(5.0, 0.0, -50.0), Vector3::zeros()),
+        )
+        .unwrap();
+    let wrench = model.net_external_wrench_in_body().unwrap();
+    assert!((Vector6::new(0.0, 5.0, -150.0, 0.0, 70.0, 5.0) - wrench.to_vector()).norm() < 1e-12);
+
+    // Gravity is expressed in the world frame
+    model.set_body_pose_in_world(nalgebra::Isometry3::from_parts(
+        Translation3::new(3.0, 2.0, 0.0),
+        UnitQuaternion::from_euler_angles(0.5 * PI, 0.0, 0.0),
+    ));
+    model.remove_external_wrench(&mount);
+    let wrench = model.net_external_wrench_in_body().unwrap();
+    assert!((Vector3::new(0.0, -100.0, 0.0) - wrench.force()).norm() < 1e-12);
+}
+
+fn spatial_inertia(mass: f64, center_of_mass: Vector3<f64>, inertia: Matrix3<f64>) -> Matrix6<f64> {
+    let c = center_of_mass.cross_matrix();
+    let mut result = Matrix6::zeros();
+    result
+        .fixed_view_mut::<3, 3>(0, 0)
+        .copy_from(&(Matrix3::identity() * mass));
+    result.fixed_view_mut::<3, 3>(0, 3).copy_from(&(-c * mass));
+    result.fixed_view_mut::<3, 3>(3, 0).copy_from(&(c * mass));
+    result
+        .fixed_view_mut::<3, 3>(3, 3)
+        .copy_from(&(inertia - c * c * mass));
+    result
+}
+
+fn consistent_physical_properties(
+    mass: f64,
+    center_of_mass: Vector3<f64>,
+    inertia: Matrix3<f64>,
+) -> ChassisElementPhysicalProperties {
+    ChassisElementPhysicalProperties::new(
+        mass,
+        center_of_mass,
+        inertia,
+        spatial_inertia(mass, center_of_mass, inertia),
+    )
+}
+
+// Computes the expected composite spatial inertia from the mass, center of mass and moment of
+// inertia of the elements.
+fn expected_composite_spatial_inertia(model: &MotionModel, frames: &[FrameID]) -> Matrix6<f64> {
+    let mut elements = vec![];
+    for frame in frames {
+        let element = model.chassis_element(frame).unwrap();
+        let to_body = model.homogeneous_transform_to_body(frame).unwrap();
+        let rotation: Matrix3<f64> = to_body.fixed_view::<3, 3>(0, 0).into();
+        let translation: Vector3<f64> = to_body.fixed_view::<3, 1>(0, 3).into();
+        elements.push((
+            element.mass_in_kg(),
+            rotation * element.center_of_mass() + translation,
+            rotation * element.moment_of_inertia() * rotation.transpose(),
+        ));
+    }
+
+    let mass: f64 = elements.iter().map(|(m, _, _)| m).sum();
+    let center_of_mass = elements
+        .iter()
+        .fold(Vector3::zeros(), |acc, (m, c, _)| acc + c * *m)
+        / mass;
+    let inertia = elements.iter().fold(Matrix3::zeros(), |acc, (m, c, i)| {
+        let d = c - center_of_mass;
+        acc + i + (Matrix3::identity() * d.norm_squared() - d * d.transpose()) * *m
+    });
+
+    spatial_inertia(mass, center_of_mass, inertia)
+}
+
+#[test]
+fn when_getting_the_composite_spatial_inertia_it_should_combine_the_elements() {
+    let change_processor = HardwareChangeProcessor::new(1000);
+    let mut model = MotionModel::new();
+    let body = model
+        .add_body(
+            "body".to_string(),
+            Translation3::identity(),
+            UnitQuaternion::identity(),
+            consistent_physical_properties(
+                6.0,
+                Vector3::new(0.1, 0.0, 0.5),
+                Matrix3::from_diagonal(&Vector3::new(1.0, 2.0, 3.0)),
+            ),
+        )
+        .unwrap();
+
+    let mut steering_actuator = MockActuator::new(NumberSpaceType::LinearUnlimited);
+    let steering = model
+        .add_steering_element(
+            "steering".to_string(),
+            body,
+            Translation3::new(1.0, 0.5, 0.0),
+            UnitQuaternion::from_euler_angles(0.0, 0.0, PI / 6.0),
+            consistent_physical_properties(
+                2.0,
+                Vector3::new(0.5, 0.0, 0.0),
+                Matrix3::from_diagonal(&Vector3::new(0.1, 0.2, 0.3)),
+            ),
+            Actuator::new(&mut steering_actuator, &change_processor).unwrap(),
+        )
+        .unwrap();
+
+    let mut wheel_actuator = MockActuator::new(NumberSpaceType::LinearUnlimited);
+    let wheel = model
+        .add_wheel(
+            "wheel".to_string(),
+            steering,
+            Translation3::new(0.1, 0.0, -0.5),
+            UnitQuaternion::from_euler_angles(0.2, 0.0, 0.0),
+            consistent_physical_properties(
+                2.0,
+                Vector3::new(0.0, 0.05, 0.0),
+                Matrix3::from_diagonal(&Vector3::new(0.4, 0.5, 0.4)),
+            ),
+            Actuator::new(&mut wheel_actuator, &change_processor).unwrap(),
+        )
+        .unwrap();
+
+    let frames = [body, steering, wheel];
+    let composite = model.composite_spatial_inertia().unwrap();
+    let expected = expected_composite_spatial_inertia(&model, &frames);
+    assert!(
+        (composite - expected).norm() < 1e-12,
+        "Composite inertia was {} but expected {}",
+        composite,
+        expected
+    );
+    assert!((composite - composite.transpose()).norm() < 1e-12);
+
+    // A pure translation only sees the total mass
+    let twist = Vector6::new(1.0, 2.0, 0.0, 0.0, 0.0, 0.0);
+    let energy = 0.5 * (twist.transpose() * composite * twist)[(0, 0)];
+    assert!((energy - 0.5 * 10.0 * 5.0).abs() < 1e-12);
+
+    steering_actuator.send_position(0.25 * PI);
+    std::thread::sleep(Duration::from_millis(50));
+
+    let composite = model.composite_spatial_inertia().unwrap();
+    let expected = expected_composite_spatial_inertia(&model, &frames);
+    assert!((composite - expected).norm() < 1e-12);
+}
@@ -0,0 +1,172 @@
+//! Provides a summary of the static geometry of the wheel modules.
+//!
+//! The geometry is computed from the transforms of the model with all the joints in their zero
+//! position, so that reports and controllers can use the same values without deriving them
+//! from the transforms themselves.
+
+use nalgebra::{Matrix4, Vector3};
+
+use crate::{
+    model_elements::{frame_elements::FrameID, model::MotionModel},
+    Error,
+};
+
+use super::jacobian::translation_of;
+
+#[cfg(test)]
+#[path = "module_geometry_tests.rs"]
+mod module_geometry_tests;
+
+/// Describes the static geometry of a wheel module, i.e. a wheel and the steering frame that
+/// steers it, with all the joints in their zero position.
+///
+/// All distances are in meters and all angles are in radians.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ModuleGeometry {
+    /// The ID of the wheel frame.
+    wheel: FrameID,
+
+    /// The ID of the steering frame.
+    steering_frame: FrameID,
+
+    /// The position of the center of the wheel in the body reference frame.
+    wheel_position: Vector3<f64>,
+
+    /// The perpendicular distance between the steering axis and the center of the wheel.
+    steering_axis_offset: f64,
+
+    /// The angle between the steering axis and the vertical, measured in the rolling direction
+    /// of the wheel.
+    caster_angle: f64,
+
+    /// The angle between the wheel plane and the vertical.
+    camber_angle: f64,
+}
+
+impl ModuleGeometry {
+    /// Returns the camber angle of the wheel, i.e. the angle between the plane of the wheel and
+    /// the z-axis of the body, measured perpendicular to the rolling direction of the wheel.
+    ///
+    /// The angle is positive when the top of the wheel leans away from the body origin.
+    pub fn camber_angle(&self) -> f64 {
+        self.camber_angle
+    }
+
+    /// Returns the caster angle of the steering axis, i.e. the angle between the steering axis
+    /// and the z-axis of the body, measured in the rolling direction of the wheel.
+    ///
+    /// The angle is positive when the top of the steering axis leans backwards, i.e. against
+    /// the rolling direction of the wheel.
+    pub fn caster_angle(&self) -> f64 {
+        self.caster_angle
+    }
+
+    /// Returns the perpendicular distance between the steering axis and the center of the wheel.
+    pub fn steering_axis_offset(&self) -> f64 {
+        self.steering_axis_offset
+    }
+
+    /// Returns the [FrameID] of the steering frame of the module.
+    pub fn steering_frame(&self) -> &FrameID {
+        &self.steering_frame
+    }
+
+    /// Returns the lateral position of the center of the wheel relative to the body origin,
+    /// i.e. the contribution of the module to the track width of the vehicle.
+    pub fn track_contribution(&self) -> f64 {
+        self.wheel_position.y
+    }
+
+    /// Returns the [FrameID] of the wheel of the module.
+    pub fn wheel(&self) -> &FrameID {
+        &self.wheel
+    }
+
+    /// Returns the longitudinal position of the center of the wheel relative to the body origin,
+    /// i.e. the contribution of the module to the wheelbase of the vehicle.
+    pub fn wheelbase_contribution(&self) -> f64 {
+        self.wheel_position.x
+    }
+
+    /// Returns the position of the center of the wheel in the body reference frame.
+    pub fn wheel_position(&self) -> &Vector3<f64> {
+        &self.wheel_position
+    }
+}
+
+impl MotionModel {
+    /// Returns the static geometry of the wheel module that contains the given wheel.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'wheel_frame' - The [FrameID] of the wheel.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the wheel is not part of the model.
+    /// * [Error::InvalidFrameID] - Returned when the frame is not a wheel.
+    /// * [Error::NoSteeringFramesInChain] - Returned when the wheel has no steering frame.
+    pub fn module_geometry(&self, wheel_frame: &FrameID) -> Result<ModuleGeometry, Error> {
+        self.reference_frame(wheel_frame)?;
+        if !self.wheels()?.contains(&wheel_frame) {
+            return Err(Error::InvalidFrameID { id: *wheel_frame });
+        }
+
+        let steering_frame = *self.steering_frame_for_wheel(wheel_frame)?;
+        let body = self.body()?;
+
+        let wheel_to_body =
+            self.homogeneous_transform_to_ancestor_with(wheel_frame, body, |_| None)?;
+        let wheel_position = translation_of(&wheel_to_body);
+
+        // The steering joint rotates around the axis of its degree of freedom in the parent of
+        // the steering frame, through the origin of that frame.
+        let steering_parent = self.parent_of(&steering_frame)?;
+        let steering_parent_to_body =
+            self.homogeneous_transform_to_ancestor_with(steering_parent, body, |_| None)?;
+        let axis_origin = translation_of(&steering_parent_to_body);
+        let steering_axis = self
+            .frame_degree_of_freedom(&steering_frame)?
+            .axis()
+            .unwrap_or_else(Vector3::z);
+        let mut axis = steering_parent_to_body.fixed_view::<3, 3>(0, 0) * steering_axis;
+        if axis.z < 0.0 {
+            axis = -axis;
+        }
+
+        let to_wheel = wheel_position - axis_origin;
+        let steering_axis_offset = (to_wheel - axis * to_wheel.dot(&axis)).norm();
+
+        // Directions in the ground plane relative to the rolling direction of the wheel
+        let up = Vector3::z();
+        let rolling = column_of(&wheel_to_body, 0);
+        let forward = Vector3::new(rolling.x, rolling.y, 0.0)
+            .try_normalize(f64::EPSILON)
+            .unwrap_or_else(Vector3::x);
+        let left = up.cross(&forward);
+
+        let caster_angle = (-axis.dot(&forward)).atan2(axis.dot(&up));
+
+        let outward = if left.dot(&wheel_position) < 0.0 {
+            -1.0
+        } else {
+            1.0
+        };
+        let wheel_up = column_of(&wheel_to_body, 2);
+        let camber_angle = (outward * wheel_up.dot(&left)).atan2(wheel_up.dot(&up));
+
+        Ok(ModuleGeometry {
+            wheel: *wheel_frame,
+            steering_frame,
+            wheel_position,
+            steering_axis_offset,
+            caster_angle,
+            camber_angle,
+        })
+    }
+}
+
+/// Returns the given column of the rotation part of the homogeneous transform.
+fn column_of(transform: &Matrix4<f64>, index: usize) -> Vector3<f64> {
+    transform.fixed_view::<3, 1>(0, index).into_owned()
+}
@@ -0,0 +1,258 @@
+use std::f64::consts::PI;
+
+use nalgebra::{Translation3, UnitQuaternion, Vector3};
+
+use crate::{
+    change_notification_processing::HardwareChangeProcessor,
+    model_elements::{
+        frame_elements::{Actuator, FrameDofType, FrameID, JointConstraint},
+        model::MotionModel,
+    },
+    number_space::NumberSpaceType,
+    test_fixtures::physical_properties,
+    test_support::MockActuator,
+    Error,
+};
+
+// Adds a module with a suspension frame at the given position, a steering frame at the origin
+// of the suspension frame and a wheel 0.1 below and 'wheel_offset' in front of the steering
+// frame.
+fn add_module(
+    model: &mut MotionModel,
+    change_processor: &HardwareChangeProcessor,
+    position: Vector3<f64>,
+    suspension_orientation: UnitQuaternion<f64>,
+    wheel_offset: f64,
+    wheel_orientation: UnitQuaternion<f64>,
+) -> (FrameID, FrameID) {
+    let body = *model.body().unwrap();
+    let suspension = model
+        .add_suspension_element(
+            "suspension".to_string(),
+            FrameDofType::PrismaticZ,
+            body,
+            Translation3::from(position),
+            suspension_orientation,
+            physical_properties(),
+            JointConstraint::new(),
+        )
+        .unwrap();
+
+    let steering = model
+        .add_steering_element(
+            "steering".to_string(),
+            suspension,
+            Translation3::identity(),
+            UnitQuaternion::identity(),
+            physical_properties(),
+            Actuator::new(
+                &mut MockActuator::new(NumberSpaceType::LinearUnlimited),
+                change_processor,
+            )
+            .unwrap(),
+        )
+        .unwrap();
+
+    let wheel = model
+        .add_wheel(
+            "wheel".to_string(),
+            steering,
+            Translation3::new(wheel_offset, 0.0, -0.1),
+            wheel_orientation,
+            physical_properties(),
+            Actuator::new(
+                &mut MockActuator::new(NumberSpaceType::LinearUnlimited),
+                change_processor,
+            )
+            .unwrap(),
+        )
+        .unwrap();
+
+    (steering, wheel)
+}
+
+fn create_model() -> MotionModel {
+    let mut model = MotionModel::new();
+    model
+        .add_body(
+            "body".to_string(),
+            Translation3::identity(),
+            UnitQuaternion::identity(),
+            physical_properties(),
+        )
+        .unwrap();
+    model
+}
+
+#[test]
+fn when_getting_the_module_geometry_of_a_straight_module_it_should_have_no_angles() {
+    let change_processor = HardwareChangeProcessor::new(10);
+    let mut model = create_model();
+    let (steering, wheel) = add_module(
+        &mut model,
+        &change_processor,
+        Vector3::new(1.0, 0.5, 0.0),
+        UnitQuaternion::identity(),
+        0.05,
+        UnitQuaternion::identity(),
+    );
+
+    let geometry = model.module_geometry(&wheel).unwrap();
+    assert_eq!(&wheel, geometry.wheel());
+    assert_eq!(&steering, geometry.steering_frame());
+    assert!((geometry.wheelbase_contribution() - 1.05).abs() < 1e-12);
+    assert!((geometry.track_contribution() - 0.5).abs() < 1e-12);
+    assert!((geometry.wheel_position() - Vector3::new(1.05, 0.5, -0.1)).norm() < 1e-12);
+    assert!((geometry.steering_axis_offset() - 0.05).abs() < 1e-12);
+    assert!(geometry.caster_angle().abs() < 1e-12);
+    assert!(geometry.camber_angle().abs() < 1e-12);
+}
+
+#[test]
+fn when_getting_the_module_geometry_of_a_tilted_module_it_should_return_the_angles() {
+    let change_processor = HardwareChangeProcessor::new(10);
+    let mut model = create_model();
+
+    // The steering axis leans backwards
+    let caster = 10.0 * PI / 180.0;
+    let (_, caster_wheel) = add_module(
+        &mut model,
+        &change_processor,
+        Vector3::new(1.0, 0.5, 0.0),
+        UnitQuaternion::from_euler_angles(0.0, -caster, 0.0),
+        0.0,
+        UnitQuaternion::identity(),
+    );
+
+    let geometry = model.module_geometry(&caster_wheel).unwrap();
+    assert!((geometry.caster_angle() - caster).abs() < 1e-12);
+    assert!(geometry.camber_angle().abs() < 1e-12);
+    assert!(geometry.steering_axis_offset().abs() < 1e-12);
+
+    // The top of the wheels lean outwards
+    let camber = 2.0 * PI / 180.0;
+    let (_, left_wheel) = add_module(
+        &mut model,
+        &change_processor,
+        Vector3::new(-1.0, 0.5, 0.0),
+        UnitQuaternion::identity(),
+        0.0,
+        UnitQuaternion::from_euler_angles(-camber, 0.0, 0.0),
+    );
+    let (_, right_wheel) = add_module(
+        &mut model,
+        &change_processor,
+        Vector3::new(-1.0, -0.5, 0.0),
+        UnitQuaternion::identity(),
+        0.0,
+        UnitQuaternion::from_euler_angles(camber, 0.0, 0.0),
+    );
+
+    for wheel in [left_wheel, right_wheel] {
+        let geometry = model.module_geometry(&wheel).unwrap();
+        assert!(geometry.caster_angle().abs() < 1e-12);
+        assert!((geometry.camber_angle() - camber).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn when_getting_the_module_geometry_of_a_module_with_an_x_steering_axis_it_should_use_that_axis() {
+    let change_processor = HardwareChangeProcessor::new(10);
+    let mut model = create_model();
+    let body = *model.body().unwrap();
+
+    // The x-axis of the steering parent points up and leans backwards
+    let caster = 10.0 * PI / 180.0;
+    let tilt = UnitQuaternion::from_euler_angles(0.0, -(0.5 * PI + caster), 0.0);
+    let mount = model
+        .add_static_chassis_element(
+            "mount".to_string(),
+            body,
+            Translation3::new(1.0, 0.5, 0.0),
+            tilt,
+            physical_properties(),
+        )
+        .unwrap();
+    let steering = model
+        .add_steering_element_with_degree_of_freedom(
+            "steering".to_string(),
+            FrameDofType::RevoluteX,
+            mount,
+            Translation3::identity(),
+            UnitQuaternion::identity(),
+            physical_properties(),
+            Actuator::new(
+                &mut MockActuator::new(NumberSpaceType::LinearUnlimited),
+                &change_processor,
+            )
+            .unwrap(),
+        )
+        .unwrap();
+    let wheel = model
+        .add_wheel(
+            "wheel".to_string(),
+            steering,
+            Translation3::new(0.0, 0.0, -0.1),
+            tilt.inverse(),
+            physical_properties(),
+            Actuator::new(
+                &mut MockActuator::new(NumberSpaceType::LinearUnlimited),
+                &change_processor,
+            )
+            .unwrap(),
+        )
+        .unwrap();
+
+    let geometry = model.module_geometry(&wheel).unwrap();
+    assert_eq!(&steering, geometry.steering_frame());
+    assert!((geometry.caster_angle() - caster).abs() < 1e-12);
+    assert!((geometry.steering_axis_offset() - 0.1).abs() < 1e-12);
+}
+
+#[test]
+fn when_getting_the_module_geometry_of_a_rotated_module_it_should_use_the_body_axes() {
+    let change_processor = HardwareChangeProcessor::new(10);
+    let mut model = create_model();
+
+    // The module is rotated so that the wheel rolls along the body y-axis
+    let (_, wheel) = add_module(
+        &mut model,
+        &change_processor,
+        Vector3::new(-1.0, 0.5, 0.0),
+        UnitQuaternion::from_euler_angles(0.0, 0.0, 0.5 * PI),
+        0.1,
+        UnitQuaternion::identity(),
+    );
+
+    let geometry = model.module_geometry(&wheel).unwrap();
+    assert!((geometry.wheelbase_contribution() + 1.0).abs() < 1e-12);
+    assert!((geometry.track_contribution() - 0.6).abs() < 1e-12);
+    assert!((geometry.steering_axis_offset() - 0.1).abs() < 1e-12);
+    assert!(geometry.caster_angle().abs() < 1e-12);
+    assert!(geometry.camber_angle().abs() < 1e-12);
+}
+
+#[test]
+fn when_getting_the_module_geometry_for_an_invalid_frame_it_should_error() {
+    let change_processor = HardwareChangeProcessor::new(10);
+    let mut model = create_model();
+    let (steering, _) = add_module(
+        &mut model,
+        &change_processor,
+        Vector3::new(1.0, 0.5, 0.0),
+        UnitQuaternion::identity(),
+        0.0,
+        UnitQuaternion::identity(),
+    );
+
+    assert_eq!(
+        Err(Error::InvalidFrameID { id: steering }),
+        model.module_geometry(&steering)
+    );
+
+    let id = FrameID::new();
+    assert_eq!(
+        Err(Error::MissingFrameElement { id }),
+        model.module_geometry(&id)
+    );
+}
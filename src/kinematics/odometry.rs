@@ -0,0 +1,680 @@
+//! Provides the functions used to estimate the motion of the vehicle body from the current
+//! state of the wheel modules.
+//!
+//! The motion of the body is estimated by computing the velocity of the contact point of each
+//! wheel with the ground, based on the steering angle and the rotational velocity of the wheel,
+//! and then finding the planar body twist that best fits all the contact point velocities in a
+//! least-squares sense. The same fit is used to check if the measured states of the wheel modules
+//! are consistent with each other, either as a velocity residual per wheel, see
+//! [TwistConsistency], or as the instantaneous center of rotation implied by each wheel, see
+//! [RotationCenterConsistency].
+
+use std::collections::HashMap;
+
+use nalgebra::{DMatrix, DVector, Vector2, Vector3, Vector6};
+
+use crate::{
+    hardware::joint_state::JointState,
+    model_elements::{frame_elements::FrameID, model::MotionModel},
+    Error,
+};
+
+use super::wheel_position_and_heading_in_body_with;
+
+#[cfg(test)]
+#[path = "odometry_tests.rs"]
+mod odometry_tests;
+
+/// The minimum number of wheel measurements required to estimate the planar body twist.
+pub(crate) const MINIMUM_NUMBER_OF_WHEEL_MEASUREMENTS: usize = 2;
+
+/// The angular velocity, in radians per second, below which the body is considered to be
+/// translating, in which case the instantaneous center of rotation is at infinity.
+const MINIMUM_ANGULAR_VELOCITY: f64 = 1e-6;
+
+/// The speed, in meters per second, below which a wheel is considered to be standing still, in
+/// which case the slip of the wheel is not defined.
+const MINIMUM_WHEEL_SPEED: f64 = 1e-6;
+
+/// Stores the estimated slip for a single wheel.
+///
+/// The slip ratio describes the difference between the speed of the wheel rim and the speed of
+/// the wheel contact point over the ground. It is defined as `s = (ωr - v) / (ωr)`, where `ω` is
+/// the rotational velocity of the wheel, `r` is the radius of the wheel and `v` is the speed
+/// of the contact point. A slip ratio of 0.0 indicates pure rolling, a positive slip ratio
+/// indicates that the wheel is spinning faster than the vehicle is moving and a negative
+/// slip ratio indicates that the wheel is spinning slower than the vehicle is moving.
+///
+/// The slip angle is the angle, in radians, between the direction in which the wheel is
+/// pointing and the direction in which the contact point is moving. The angle is positive
+/// for a counter-clockwise rotation around the body z-axis.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct WheelSlip {
+    /// The slip ratio of the wheel.
+    slip_ratio: f64,
+
+    /// The slip angle of the wheel in radians.
+    slip_angle: f64,
+}
+
+impl WheelSlip {
+    /// Creates a new [WheelSlip] instance.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'slip_ratio' - The slip ratio of the wheel.
+    /// * 'slip_angle' - The slip angle of the wheel in radians.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use swerve_vehicle_descriptors::kinematics::odometry::WheelSlip;
+    ///
+    /// let slip = WheelSlip::new(0.1, 0.05);
+    ///
+    /// assert_eq!(slip.slip_ratio(), 0.1);
+    /// assert_eq!(slip.slip_angle(), 0.05);
+    /// ```
+    pub fn new(slip_ratio: f64, slip_angle: f64) -> Self {
+        Self {
+            slip_ratio,
+            slip_angle,
+        }
+    }
+
+    /// Creates a new [WheelSlip] instance that describes a wheel that is rolling
+    /// without any slip.
+    pub fn none() -> Self {
+        Self::new(0.0, 0.0)
+    }
+
+    /// Returns the slip angle of the wheel in radians.
+    pub fn slip_angle(&self) -> f64 {
+        self.slip_angle
+    }
+
+    /// Returns the slip ratio of the wheel.
+    pub fn slip_ratio(&self) -> f64 {
+        self.slip_ratio
+    }
+}
+
+impl Default for WheelSlip {
+    fn default() -> Self {
+        Self::none()
+    }
+}
+
+/// Stores the estimated slip of a wheel together with the velocities from which it was
+/// computed.
+///
+/// The slip compares the speed of the wheel rim, based on the measured velocity of the wheel,
+/// with the velocity of the wheel contact point over the ground, based on the estimated body
+/// twist. The difference between the commanded and the measured velocity of the wheel shows how
+/// well the wheel actuator follows its commands.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct WheelSlipEstimate {
+    /// The velocity, in radians per second, of the last command that was sent to the wheel
+    /// actuator, or [None] if no command with a velocity has been sent.
+    commanded_velocity: Option<f64>,
+
+    /// The measured velocity of the wheel in radians per second.
+    measured_velocity: f64,
+
+    /// The velocity of the wheel contact point over the ground, as predicted by the estimated
+    /// body twist, in the xy-plane of the body reference frame.
+    ground_velocity: Vector2<f64>,
+
+    /// The estimated slip, or [None] if the wheel is standing still.
+    slip: Option<WheelSlip>,
+}
+
+impl WheelSlipEstimate {
+    /// Returns the velocity, in radians per second, of the last command that was sent to the
+    /// wheel actuator, or [None] if no command with a velocity has been sent.
+    pub fn commanded_velocity(&self) -> Option<f64> {
+        self.commanded_velocity
+    }
+
+    /// Returns the velocity of the wheel contact point over the ground, as predicted by the
+    /// estimated body twist, in the xy-plane of the body reference frame.
+    pub fn ground_velocity(&self) -> &Vector2<f64> {
+        &self.ground_velocity
+    }
+
+    /// Returns the measured velocity of the wheel in radians per second.
+    pub fn measured_velocity(&self) -> f64 {
+        self.measured_velocity
+    }
+
+    /// Returns the estimated [WheelSlip], or [None] if the wheel rim is not moving, in which
+    /// case the slip ratio is not defined.
+    pub fn slip(&self) -> Option<&WheelSlip> {
+        self.slip.as_ref()
+    }
+
+    /// Returns the difference, in radians per second, between the commanded and the measured
+    /// velocity of the wheel, or [None] if no command with a velocity has been sent.
+    pub fn tracking_error(&self) -> Option<f64> {
+        self.commanded_velocity
+            .map(|commanded| commanded - self.measured_velocity)
+    }
+}
+
+/// Stores the instantaneous centers of rotation (ICR) implied by the measured states of the
+/// wheel modules.
+///
+/// The center of rotation of a wheel is the point, on the line through the wheel perpendicular
+/// to the rolling direction, around which the wheel moves at its measured speed when the body
+/// rotates at the estimated angular velocity. When all the steering angles and wheel velocities
+/// are consistent the centers of all the wheels coincide with the center of rotation of the body.
+/// A wheel with a center that is far away from the center of rotation of the body often has a
+/// mis-calibrated steering encoder.
+///
+/// When the body is not rotating the center of rotation is at infinity and no centers are
+/// available.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RotationCenterConsistency {
+    /// The center of rotation of the body in the xy-plane of the body reference frame, or [None]
+    /// if the body is not rotating.
+    center_of_rotation: Option<Vector2<f64>>,
+
+    /// The center of rotation implied by each wheel, stored by the [FrameID] of the wheel.
+    wheel_centers: HashMap<FrameID, Vector2<f64>>,
+}
+
+impl RotationCenterConsistency {
+    /// Returns the center of rotation of the body, based on the least-squares estimate of the
+    /// body twist, in the xy-plane of the body reference frame. Returns [None] if the body is
+    /// not rotating.
+    pub fn center_of_rotation(&self) -> Option<&Vector2<f64>> {
+        self.center_of_rotation.as_ref()
+    }
+
+    /// Returns the distance, in meters, between the center of rotation implied by the given
+    /// wheel and the center of rotation of the body, or [None] if the wheel did not provide a
+    /// measurement or the body is not rotating.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'wheel' - The [FrameID] of the wheel.
+    pub fn deviation(&self, wheel: &FrameID) -> Option<f64> {
+        let center = self.center_of_rotation.as_ref()?;
+        self.wheel_centers.get(wheel).map(|c| (c - center).norm())
+    }
+
+    /// Returns the root-mean-square distance, in meters, between the centers of rotation implied
+    /// by the wheels and the center of rotation of the body, or [None] if the body is not
+    /// rotating.
+    pub fn spread(&self) -> Option<f64> {
+        let center = self.center_of_rotation.as_ref()?;
+        let squared_sum: f64 = self
+            .wheel_centers
+            .values()
+            .map(|c| (c - center).norm_squared())
+            .sum();
+        Some((squared_sum / self.wheel_centers.len() as f64).sqrt())
+    }
+
+    /// Returns the center of rotation implied by the given wheel, in the xy-plane of the body
+    /// reference frame, or [None] if the wheel did not provide a measurement or the body is not
+    /// rotating.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'wheel' - The [FrameID] of the wheel.
+    pub fn wheel_center(&self, wheel: &FrameID) -> Option<&Vector2<f64>> {
+        self.wheel_centers.get(wheel)
+    }
+
+    /// Returns the centers of rotation implied by all the wheels that provided a measurement.
+    /// The collection is empty when the body is not rotating.
+    pub fn wheel_centers(&self) -> &HashMap<FrameID, Vector2<f64>> {
+        &self.wheel_centers
+    }
+}
+
+/// Stores the result of a consistency check between the measured states of the wheel modules
+/// and the rigid body motion of the vehicle.
+///
+/// The residual of a module is the magnitude, in meters per second, of the difference between
+/// the measured velocity of the wheel contact point and the velocity predicted by the body
+/// twist that best fits the measurements of the other modules. A module with a large residual
+/// disagrees with the rest of the vehicle, which may indicate a broken encoder or a slipping
+/// wheel.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TwistConsistency {
+    /// The least-squares body twist based on all the wheel measurements.
+    twist: Vector6<f64>,
+
+    /// The residual for each wheel, stored by the [FrameID] of the wheel.
+    residuals: HashMap<FrameID, f64>,
+}
+
+impl TwistConsistency {
+    /// Returns the [FrameID] of the wheels that have a residual larger than the given
+    /// threshold, ordered from the largest to the smallest residual.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'threshold' - The maximum allowed residual in meters per second.
+    pub fn inconsistent_wheels(&self, threshold: f64) -> Vec<FrameID> {
+        let mut wheels: Vec<(FrameID, f64)> = self
+            .residuals
+            .iter()
+            .filter(|(_, r)| **r > threshold)
+            .map(|(id, r)| (*id, *r))
+            .collect();
+        wheels.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+        wheels.into_iter().map(|(id, _)| id).collect()
+    }
+
+    /// Returns the residual, in meters per second, for the given wheel or [None] if
+    /// the wheel did not provide a measurement.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'wheel' - The [FrameID] of the wheel.
+    pub fn residual(&self, wheel: &FrameID) -> Option<f64> {
+        self.residuals.get(wheel).copied()
+    }
+
+    /// Returns the residuals for all the wheels that provided a measurement.
+    pub fn residuals(&self) -> &HashMap<FrameID, f64> {
+        &self.residuals
+    }
+
+    /// Returns the least-squares estimate of the body twist as
+    /// `[v_x, v_y, v_z, ω_x, ω_y, ω_z]` in the body reference frame.
+    pub fn twist(&self) -> &Vector6<f64> {
+        &self.twist
+    }
+}
+
+/// Stores the velocity of the contact point of a wheel, expressed in the body
+/// reference frame.
+#[derive(Clone)]
+pub(crate) struct WheelGroundVelocity {
+    /// The [FrameID] of the wheel.
+    pub(crate) wheel: FrameID,
+
+    /// The position of the wheel in the xy-plane of the body reference frame.
+    pub(crate) position: Vector2<f64>,
+
+    /// The velocity of the wheel contact point in the xy-plane of the body reference frame.
+    pub(crate) velocity: Vector2<f64>,
+}
+
+impl MotionModel {
+    /// Returns the estimated twist of the body, based on the current steering angles and wheel
+    /// velocities as reported by the actuators.
+    ///
+    /// The velocity of the contact point of each wheel is computed from the steering angle and
+    /// the rotational velocity of the wheel. The planar body twist that best fits all the
+    /// contact point velocities, in a least-squares sense, is returned as
+    /// `[v_x, v_y, v_z, ω_x, ω_y, ω_z]` in the body reference frame. Only the `v_x`, `v_y` and
+    /// `ω_z` components will be non-zero.
+    ///
+    /// Wheels are assumed to roll without slip, use [MotionModel::body_twist_with_slip] to
+    /// correct for a known slip. Wheels that do not report a velocity or that are not in
+    /// contact with the ground are ignored.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the model has no wheels or when a wheel
+    ///   has no actuator.
+    /// * [Error::MissingWheelRadius] - Returned when the radius of a wheel is not known.
+    /// * [Error::InsufficientWheelMeasurements] - Returned when fewer than two wheels report
+    ///   a velocity.
+    pub fn body_twist_from_joint_states(&self) -> Result<Vector6<f64>, Error> {
+        self.body_twist_with_slip(&HashMap::new())
+    }
+
+    /// Returns the estimated twist of the body, based on the current steering angles and wheel
+    /// velocities, corrected for the given wheel slip.
+    ///
+    /// The twist is returned as `[v_x, v_y, v_z, ω_x, ω_y, ω_z]` in the body reference frame. As
+    /// the estimate is based on the planar motion of the wheels only the `v_x`, `v_y` and `ω_z`
+    /// components will be non-zero.
+    ///
+    /// Wheels that do not report a velocity or that are not in contact with the ground are
+    /// ignored.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'slip' - The estimated slip for each wheel. Wheels that are not in the collection are
+    ///   assumed to be rolling without slip.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the model has no wheels or when a wheel
+    ///   has no actuator.
+    /// * [Error::MissingWheelRadius] - Returned when the radius of a wheel is not known.
+    /// * [Error::InsufficientWheelMeasurements] - Returned when fewer than two wheels report
+    ///   a velocity.
+    pub fn body_twist_with_slip(
+        &self,
+        slip: &HashMap<FrameID, WheelSlip>,
+    ) -> Result<Vector6<f64>, Error> {
+        let ground_velocities = self.wheel_ground_velocities(&HashMap::new(), slip)?;
+        let planar_twist = estimate_planar_twist(&ground_velocities)?;
+
+        Ok(planar_to_body_twist(&planar_twist))
+    }
+
+    /// Returns the instantaneous centers of rotation implied by the given steering angles and
+    /// wheel velocities, see [RotationCenterConsistency].
+    ///
+    /// The angular velocity of the body is estimated from all the wheel measurements in a
+    /// least-squares sense. The center of rotation of a wheel is found by dividing the velocity
+    /// of its contact point by that angular velocity.
+    ///
+    /// Wheels are assumed to roll without slip. Wheels that do not report a velocity or that are
+    /// not in contact with the ground are ignored.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'joint_states' - The measured states of the joints, stored by the [FrameID] of the
+    ///   joint. Joints that are not in the collection use the current state of their actuator.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the model has no wheels or when a wheel
+    ///   has no actuator.
+    /// * [Error::MissingWheelRadius] - Returned when the radius of a wheel is not known.
+    /// * [Error::InsufficientWheelMeasurements] - Returned when fewer than two wheels report
+    ///   a velocity.
+    pub fn rotation_center_consistency(
+        &self,
+        joint_states: &HashMap<FrameID, JointState>,
+    ) -> Result<RotationCenterConsistency, Error> {
+        let ground_velocities = self.wheel_ground_velocities(joint_states, &HashMap::new())?;
+        let planar_twist = estimate_planar_twist(&ground_velocities)?;
+
+        let angular_velocity = planar_twist.z;
+        if angular_velocity.abs() < MINIMUM_ANGULAR_VELOCITY {
+            return Ok(RotationCenterConsistency {
+                center_of_rotation: None,
+                wheel_centers: HashMap::new(),
+            });
+        }
+
+        // A point p moves with v + ω × p, which is zero at the center of rotation
+        let center_of_rotation = Vector2::new(
+            -planar_twist.y / angular_velocity,
+            planar_twist.x / angular_velocity,
+        );
+        let wheel_centers = ground_velocities
+            .iter()
+            .map(|g| {
+                let center =
+                    g.position + Vector2::new(-g.velocity.y, g.velocity.x) / angular_velocity;
+                (g.wheel, center)
+            })
+            .collect();
+
+        Ok(RotationCenterConsistency {
+            center_of_rotation: Some(center_of_rotation),
+            wheel_centers,
+        })
+    }
+
+    /// Returns the estimated slip of each wheel that is in contact with the ground, stored by
+    /// the [FrameID] of the wheel.
+    ///
+    /// The body twist is estimated from the measured steering angles and wheel velocities, see
+    /// [MotionModel::body_twist_from_joint_states]. The measured state of a joint combines the
+    /// states of its actuator and sensor, see [MotionModel::joint_state_for]. For each wheel the
+    /// velocity of the contact point predicted by the body twist is compared to the speed of the
+    /// wheel rim to give the [WheelSlip] of the wheel. The commanded velocity of a wheel is taken
+    /// from the last command sent to its actuator, see
+    /// [Actuator::last_command](crate::model_elements::frame_elements::Actuator::last_command).
+    ///
+    /// Because the body twist is a best fit of the wheel velocities, slip that is the same for
+    /// all the wheels, e.g. all the wheels spinning on ice, does not show up in the estimates.
+    /// Wheels that do not report a velocity are ignored.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the model has no wheels or when a wheel
+    ///   has no actuator.
+    /// * [Error::MissingWheelRadius] - Returned when the radius of a wheel is not known.
+    /// * [Error::InsufficientWheelMeasurements] - Returned when fewer than two wheels report
+    ///   a velocity.
+    pub fn wheel_slip_estimates(&self) -> Result<HashMap<FrameID, WheelSlipEstimate>, Error> {
+        let mut measured_states = HashMap::new();
+        for wheel in self.wheels_in_contact()? {
+            if let Ok(state) = self.joint_state_for(wheel) {
+                measured_states.insert(*wheel, state);
+            }
+        }
+
+        let ground_velocities = self.wheel_ground_velocities(&measured_states, &HashMap::new())?;
+        let planar_twist = estimate_planar_twist(&ground_velocities)?;
+
+        let mut result = HashMap::with_capacity(ground_velocities.len());
+        for g in ground_velocities.iter() {
+            let measured_velocity = measured_states
+                .get(&g.wheel)
+                .and_then(|s| *s.velocity())
+                .unwrap_or(0.0);
+            let commanded_velocity = self
+                .actuator_for(&g.wheel)?
+                .last_command()
+                .and_then(|c| *c.state().velocity());
+
+            let ground_velocity = Vector2::new(
+                planar_twist.x - planar_twist.z * g.position.y,
+                planar_twist.y + planar_twist.z * g.position.x,
+            );
+
+            // The slip is measured along the direction in which the wheel rim moves, so that
+            // it has the same sign when driving forwards and backwards
+            let rim_speed = g.velocity.norm();
+            let slip = if rim_speed < MINIMUM_WHEEL_SPEED {
+                None
+            } else {
+                let direction = g.velocity / rim_speed;
+                let speed_along_rim = ground_velocity.dot(&direction);
+                let slip_angle = if ground_velocity.norm() < MINIMUM_WHEEL_SPEED {
+                    0.0
+                } else {
+                    direction.perp(&ground_velocity).atan2(speed_along_rim)
+                };
+
+                Some(WheelSlip::new(
+                    (rim_speed - speed_along_rim) / rim_speed,
+                    slip_angle,
+                ))
+            };
+
+            result.insert(
+                g.wheel,
+                WheelSlipEstimate {
+                    commanded_velocity,
+                    measured_velocity,
+                    ground_velocity,
+                    slip,
+                },
+            );
+        }
+
+        Ok(result)
+    }
+
+    /// Checks if the given joint states are consistent with a rigid body motion of the vehicle.
+    ///
+    /// The body twist is estimated from all the wheel measurements in a least-squares sense.
+    /// Then for each wheel the twist is estimated from the measurements of the other wheels and
+    /// compared to the measurement of the wheel, resulting in a residual per wheel. If there are
+    /// only two wheels with measurements the residuals are computed against the twist based on
+    /// all the measurements.
+    ///
+    /// Wheels are assumed to roll without slip. Wheels that do not report a velocity or that are
+    /// not in contact with the ground are ignored.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'joint_states' - The measured states of the joints, stored by the [FrameID] of the
+    ///   joint. The position of the joints is used to compute the wheel positions and steering
+    ///   angles and the velocity of the wheel joints is used as the wheel rotational velocity.
+    ///   Joints that are not in the collection use the current state of their actuator.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the model has no wheels or when a wheel
+    ///   has no actuator.
+    /// * [Error::MissingWheelRadius] - Returned when the radius of a wheel is not known.
+    /// * [Error::InsufficientWheelMeasurements] - Returned when fewer than two wheels report
+    ///   a velocity.
+    pub fn twist_consistency(
+        &self,
+        joint_states: &HashMap<FrameID, JointState>,
+    ) -> Result<TwistConsistency, Error> {
+        let ground_velocities = self.wheel_ground_velocities(joint_states, &HashMap::new())?;
+        let planar_twist = estimate_planar_twist(&ground_velocities)?;
+
+        let leave_one_out = ground_velocities.len() > MINIMUM_NUMBER_OF_WHEEL_MEASUREMENTS;
+        let mut residuals = HashMap::with_capacity(ground_velocities.len());
+        for (index, g) in ground_velocities.iter().enumerate() {
+            let reference_twist = if leave_one_out {
+                let others: Vec<WheelGroundVelocity> = ground_velocities
+                    .iter()
+                    .enumerate()
+                    .filter(|(i, _)| *i != index)
+                    .map(|(_, other)| other.clone())
+                    .collect();
+                estimate_planar_twist(&others)?
+            } else {
+                planar_twist
+            };
+
+            let predicted = Vector2::new(
+                reference_twist.x - reference_twist.z * g.position.y,
+                reference_twist.y + reference_twist.z * g.position.x,
+            );
+            residuals.insert(g.wheel, (g.velocity - predicted).norm());
+        }
+
+        Ok(TwistConsistency {
+            twist: planar_to_body_twist(&planar_twist),
+            residuals,
+        })
+    }
+
+    /// Returns the velocities of the contact points of all the wheels that are in contact with
+    /// the ground and report a velocity.
+    fn wheel_ground_velocities(
+        &self,
+        joint_states: &HashMap<FrameID, JointState>,
+        slip: &HashMap<FrameID, WheelSlip>,
+    ) -> Result<Vec<WheelGroundVelocity>, Error> {
+        let mut ground_velocities = Vec::with_capacity(self.number_of_wheels());
+        for wheel in self.wheels_in_contact()? {
+            let wheel_slip = slip.get(wheel).copied().unwrap_or_default();
+            if let Some(v) = self.wheel_ground_velocity(wheel, joint_states, &wheel_slip)? {
+                ground_velocities.push(v);
+            }
+        }
+
+        Ok(ground_velocities)
+    }
+
+    /// Returns the velocity of the contact point of the given wheel, based on the given joint
+    /// states and slip, or [None] if the wheel does not report a velocity.
+    fn wheel_ground_velocity(
+        &self,
+        wheel: &FrameID,
+        joint_states: &HashMap<FrameID, JointState>,
+        slip: &WheelSlip,
+    ) -> Result<Option<WheelGroundVelocity>, Error> {
+        let state = match joint_states.get(wheel) {
+            Some(s) => *s,
+            None => self.actuator_for(wheel)?.value()?,
+        };
+        let angular_velocity = match state.velocity() {
+            Some(v) => *v,
+            None => return Ok(None),
+        };
+
+        let radius = self.wheel_rolling_radius(wheel)?;
+        let (position, heading) = wheel_position_and_heading_in_body_with(self, wheel, |id| {
+            joint_states
+                .get(id)
+                .map(|s| s.position())
+                .or_else(|| self.current_joint_position(id))
+        })?;
+
+        let speed = angular_velocity * radius * (1.0 - slip.slip_ratio());
+        let direction = heading + slip.slip_angle();
+
+        Ok(Some(WheelGroundVelocity {
+            wheel: *wheel,
+            position,
+            velocity: Vector2::new(speed * direction.cos(), speed * direction.sin()),
+        }))
+    }
+}
+
+/// Converts a planar twist, `[v_x, v_y, ω_z]`, into a body twist.
+fn planar_to_body_twist(planar_twist: &Vector3<f64>) -> Vector6<f64> {
+    Vector6::new(
+        planar_twist.x,
+        planar_twist.y,
+        0.0,
+        0.0,
+        0.0,
+        planar_twist.z,
+    )
+}
+
+/// Returns the planar body twist, as `[v_x, v_y, ω_z]`, that best fits the given wheel
+/// contact point velocities in a least-squares sense.
+///
+/// For each wheel the velocity of the contact point is related to the body twist by
+///
+/// ```text
+/// v_wheel_x = v_x - ω_z * y_wheel
+/// v_wheel_y = v_y + ω_z * x_wheel
+/// ```
+///
+/// ## Errors
+///
+/// * [Error::InsufficientWheelMeasurements] - Returned when fewer than two wheel velocities
+///   are provided.
+pub(crate) fn estimate_planar_twist(
+    ground_velocities: &[WheelGroundVelocity],
+) -> Result<Vector3<f64>, Error> {
+    let count = ground_velocities.len();
+    if count < MINIMUM_NUMBER_OF_WHEEL_MEASUREMENTS {
+        return Err(Error::InsufficientWheelMeasurements {
+            required: MINIMUM_NUMBER_OF_WHEEL_MEASUREMENTS,
+            available: count,
+        });
+    }
+
+    let mut a = DMatrix::<f64>::zeros(2 * count, 3);
+    let mut b = DVector::<f64>::zeros(2 * count);
+    for (index, g) in ground_velocities.iter().enumerate() {
+        let row = 2 * index;
+
+        a[(row, 0)] = 1.0;
+        a[(row, 2)] = -g.position.y;
+        b[row] = g.velocity.x;
+
+        a[(row + 1, 1)] = 1.0;
+        a[(row + 1, 2)] = g.position.x;
+        b[row + 1] = g.velocity.y;
+    }
+
+    let solution =
+        a.svd(true, true)
+            .solve(&b, 1e-12)
+            .map_err(|_| Error::InsufficientWheelMeasurements {
+                required: MINIMUM_NUMBER_OF_WHEEL_MEASUREMENTS,
+                available: count,
+            })?;
+
+    Ok(Vector3::new(solution[0], solution[1], solution[2]))
+}
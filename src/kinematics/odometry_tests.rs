@@ -0,0 +1,616 @@
+use std::{collections::HashMap, f64::consts::PI, time::Duration};
+
+use float_cmp::{ApproxEq, F64Margin};
+use nalgebra::{Translation3, UnitQuaternion, Vector2, Vector3, Vector6};
+
+use crate::{
+    change_notification_processing::HardwareChangeProcessor,
+    hardware::joint_state::JointState,
+    model_elements::{
+        frame_elements::{Actuator, FrameDofType, FrameID, JointConstraint},
+        model::MotionModel,
+    },
+    number_space::NumberSpaceType,
+    test_fixtures::physical_properties,
+    test_support::MockActuator,
+    Error,
+};
+
+use super::{RotationCenterConsistency, WheelSlip};
+
+// A vehicle with 4 modules, placed at (±1.0, ±0.5). Each module consists of a suspension frame,
+// a steering frame located on the suspension frame and a wheel frame 0.1 below the steering
+// frame. All frames have the same orientation as the body.
+struct TestVehicle {
+    model: MotionModel,
+    module_positions: Vec<(f64, f64)>,
+    suspension_frames: Vec<FrameID>,
+    steering_frames: Vec<FrameID>,
+    wheels: Vec<FrameID>,
+    steering_actuators: Vec<MockActuator>,
+    wheel_actuators: Vec<MockActuator>,
+    _change_processor: Box<HardwareChangeProcessor>,
+}
+
+impl TestVehicle {
+    fn new() -> Self {
+        let change_processor = Box::new(HardwareChangeProcessor::new(1000));
+        let mut model = MotionModel::new();
+        let body = model
+            .add_body(
+                "body".to_string(),
+                Translation3::identity(),
+                UnitQuaternion::identity(),
+                physical_properties(),
+            )
+            .unwrap();
+
+        let module_positions = vec![(1.0, 0.5), (-1.0, 0.5), (-1.0, -0.5), (1.0, -0.5)];
+        let mut suspension_frames = vec![];
+        let mut steering_frames = vec![];
+        let mut wheels = vec![];
+        let mut steering_actuators = vec![];
+        let mut wheel_actuators = vec![];
+        for (x, y) in module_positions.iter() {
+            let suspension = model
+                .add_suspension_element(
+                    "suspension".to_string(),
+                    FrameDofType::PrismaticZ,
+                    body,
+                    Translation3::new(*x, *y, 0.0),
+                    UnitQuaternion::identity(),
+                    physical_properties(),
+                    JointConstraint::new(),
+                )
+                .unwrap();
+
+            let mut steering_hardware = MockActuator::new(NumberSpaceType::LinearUnlimited);
+            let steering_actuator =
+                Actuator::new(&mut steering_hardware, &change_processor).unwrap();
+            let steering = model
+                .add_steering_element(
+                    "steering".to_string(),
+                    suspension,
+                    Translation3::identity(),
+                    UnitQuaternion::identity(),
+                    physical_properties(),
+                    steering_actuator,
+                )
+                .unwrap();
+
+            let mut wheel_hardware = MockActuator::new(NumberSpaceType::LinearUnlimited);
+            let wheel_actuator = Actuator::new(&mut wheel_hardware, &change_processor).unwrap();
+            let wheel = model
+                .add_wheel(
+                    "wheel".to_string(),
+                    steering,
+                    Translation3::new(0.0, 0.0, -0.1),
+                    UnitQuaternion::identity(),
+                    physical_properties(),
+                    wheel_actuator,
+                )
+                .unwrap();
+            model.set_wheel_radius(&wheel, 0.1).unwrap();
+
+            suspension_frames.push(suspension);
+            steering_frames.push(steering);
+            wheels.push(wheel);
+            steering_actuators.push(steering_hardware);
+            wheel_actuators.push(wheel_hardware);
+        }
+
+        Self {
+            model,
+            module_positions,
+            suspension_frames,
+            steering_frames,
+            wheels,
+            steering_actuators,
+            wheel_actuators,
+            _change_processor: change_processor,
+        }
+    }
+
+    fn set_module_state(&self, index: usize, steering_angle: f64, wheel_velocity: Option<f64>) {
+        self.steering_actuators[index].send_state(JointState::new(
+            steering_angle,
+            Some(0.0),
+            None,
+            None,
+        ));
+        self.wheel_actuators[index].send_state(JointState::new(0.0, wheel_velocity, None, None));
+    }
+
+    fn wait_for_updates(&self) {
+        std::thread::sleep(Duration::from_millis(50));
+    }
+}
+
+fn assert_twist_approx_eq(expected: Vector6<f64>, actual: Vector6<f64>) {
+    for (a, b) in expected.iter().zip(actual.iter()) {
+        assert!(
+            a.approx_eq(
+                *b,
+                F64Margin {
+                    ulps: 2,
+                    epsilon: 1e-6
+                }
+            ),
+            "Expected {} and {} to be equal within 2 ulps or 1e-6",
+            expected,
+            actual
+        );
+    }
+}
+
+// WheelSlip
+
+#[test]
+fn when_creating_wheel_slip_it_should_store_the_values() {
+    let slip = WheelSlip::new(0.2, -0.1);
+
+    assert_eq!(0.2, slip.slip_ratio());
+    assert_eq!(-0.1, slip.slip_angle());
+
+    let none = WheelSlip::none();
+    assert_eq!(0.0, none.slip_ratio());
+    assert_eq!(0.0, none.slip_angle());
+    assert_eq!(none, WheelSlip::default());
+}
+
+// MotionModel::body_twist_from_joint_states
+
+#[test]
+fn when_estimating_twist_from_joint_states_without_wheels_it_should_error() {
+    let model = MotionModel::new();
+
+    let result = model.body_twist_from_joint_states();
+    assert!(result.is_err());
+}
+
+#[test]
+fn when_estimating_twist_from_joint_states_it_should_combine_translation_and_rotation() {
+    let vehicle = TestVehicle::new();
+    let linear_velocity = Vector3::new(0.3, -0.2, 0.0);
+    let angular_velocity = 0.4;
+    for (index, (x, y)) in vehicle.module_positions.iter().enumerate() {
+        let velocity_x = linear_velocity.x - angular_velocity * y;
+        let velocity_y = linear_velocity.y + angular_velocity * x;
+        let speed = velocity_x.hypot(velocity_y);
+        vehicle.set_module_state(index, velocity_y.atan2(velocity_x), Some(speed / 0.1));
+    }
+    vehicle.wait_for_updates();
+
+    let twist = vehicle.model.body_twist_from_joint_states().unwrap();
+
+    assert_twist_approx_eq(
+        Vector6::new(
+            linear_velocity.x,
+            linear_velocity.y,
+            0.0,
+            0.0,
+            0.0,
+            angular_velocity,
+        ),
+        twist,
+    );
+}
+
+#[test]
+fn when_estimating_twist_from_inconsistent_joint_states_it_should_return_the_best_fit() {
+    let vehicle = TestVehicle::new();
+    vehicle.set_module_state(0, 0.0, Some(14.0));
+    vehicle.set_module_state(1, 0.0, Some(14.0));
+    vehicle.set_module_state(2, 0.0, Some(6.0));
+    vehicle.set_module_state(3, 0.0, Some(6.0));
+    vehicle.wait_for_updates();
+
+    let twist = vehicle.model.body_twist_from_joint_states().unwrap();
+
+    // The left wheels move faster than the right wheels, which implies a clockwise rotation.
+    // The wheels point forward so they cannot provide the lateral velocity that the rotation
+    // requires. Minimizing the errors of both gives ω = -Σ(y u) / Σ(x² + y²) = -0.8 / 5.0.
+    assert_twist_approx_eq(Vector6::new(1.0, 0.0, 0.0, 0.0, 0.0, -0.16), twist);
+}
+
+// MotionModel::body_twist_with_slip
+
+#[test]
+fn when_estimating_twist_without_wheels_it_should_error() {
+    let model = MotionModel::new();
+
+    let result = model.body_twist_with_slip(&HashMap::new());
+    assert!(result.is_err());
+}
+
+#[test]
+fn when_estimating_twist_with_a_wheel_off_the_ground_it_should_ignore_the_wheel() {
+    let mut vehicle = TestVehicle::new();
+    vehicle.set_module_state(0, 0.0, Some(100.0));
+    for index in 1..4 {
+        vehicle.set_module_state(index, 0.0, Some(10.0));
+    }
+    vehicle.wait_for_updates();
+
+    vehicle
+        .model
+        .set_suspension_droop_position(&vehicle.suspension_frames[0], 0.0)
+        .unwrap();
+    let mut wheel_loads = HashMap::new();
+    wheel_loads.insert(vehicle.wheels[0], 0.0);
+    vehicle
+        .model
+        .update_ground_contact(&HashMap::new(), &wheel_loads)
+        .unwrap();
+
+    let twist = vehicle.model.body_twist_with_slip(&HashMap::new()).unwrap();
+
+    assert_twist_approx_eq(Vector6::new(1.0, 0.0, 0.0, 0.0, 0.0, 0.0), twist);
+}
+
+#[test]
+fn when_estimating_twist_without_wheel_radius_it_should_error() {
+    let change_processor = Box::new(HardwareChangeProcessor::new(1000));
+    let mut model = MotionModel::new();
+    let body = model
+        .add_body(
+            "body".to_string(),
+            Translation3::identity(),
+            UnitQuaternion::identity(),
+            physical_properties(),
+        )
+        .unwrap();
+
+    let mut steering_hardware = MockActuator::new(NumberSpaceType::LinearUnlimited);
+    let steering = model
+        .add_steering_element(
+            "steering".to_string(),
+            body,
+            Translation3::new(1.0, 0.0, 0.0),
+            UnitQuaternion::identity(),
+            physical_properties(),
+            Actuator::new(&mut steering_hardware, &change_processor).unwrap(),
+        )
+        .unwrap();
+
+    let mut wheel_hardware = MockActuator::new(NumberSpaceType::LinearUnlimited);
+    let wheel = model
+        .add_wheel(
+            "wheel".to_string(),
+            steering,
+            Translation3::new(0.0, 0.0, -0.1),
+            UnitQuaternion::identity(),
+            physical_properties(),
+            Actuator::new(&mut wheel_hardware, &change_processor).unwrap(),
+        )
+        .unwrap();
+
+    let result = model.body_twist_with_slip(&HashMap::new());
+    assert_eq!(Err(Error::MissingWheelRadius { id: wheel }), result);
+}
+
+#[test]
+fn when_estimating_twist_with_too_few_wheel_velocities_it_should_error() {
+    let vehicle = TestVehicle::new();
+    vehicle.set_module_state(0, 0.0, Some(1.0));
+    for index in 1..4 {
+        vehicle.set_module_state(index, 0.0, None);
+    }
+    vehicle.wait_for_updates();
+
+    let result = vehicle.model.body_twist_with_slip(&HashMap::new());
+    assert_eq!(
+        Err(Error::InsufficientWheelMeasurements {
+            required: 2,
+            available: 1
+        }),
+        result
+    );
+}
+
+#[test]
+fn when_estimating_twist_with_straight_motion_and_no_slip_it_should_return_the_twist() {
+    let vehicle = TestVehicle::new();
+    for index in 0..4 {
+        vehicle.set_module_state(index, 0.5 * PI, Some(10.0));
+    }
+    vehicle.wait_for_updates();
+
+    let twist = vehicle.model.body_twist_with_slip(&HashMap::new()).unwrap();
+
+    assert_twist_approx_eq(Vector6::new(0.0, 1.0, 0.0, 0.0, 0.0, 0.0), twist);
+}
+
+#[test]
+fn when_estimating_twist_with_rotation_and_no_slip_it_should_return_the_twist() {
+    let vehicle = TestVehicle::new();
+    let angular_velocity = 0.5;
+    for (index, (x, y)) in vehicle.module_positions.iter().enumerate() {
+        // The contact point velocity for a pure rotation is ω × r
+        let heading = x.atan2(-y);
+        let speed = angular_velocity * (x * x + y * y).sqrt();
+        vehicle.set_module_state(index, heading, Some(speed / 0.1));
+    }
+    vehicle.wait_for_updates();
+
+    let twist = vehicle.model.body_twist_with_slip(&HashMap::new()).unwrap();
+
+    assert_twist_approx_eq(
+        Vector6::new(0.0, 0.0, 0.0, 0.0, 0.0, angular_velocity),
+        twist,
+    );
+}
+
+#[test]
+fn when_estimating_twist_with_slip_ratio_it_should_correct_the_wheel_speed() {
+    let vehicle = TestVehicle::new();
+    for index in 0..4 {
+        vehicle.set_module_state(index, 0.0, Some(10.0));
+    }
+    vehicle.wait_for_updates();
+
+    let slip: HashMap<FrameID, WheelSlip> = vehicle
+        .wheels
+        .iter()
+        .map(|w| (*w, WheelSlip::new(0.5, 0.0)))
+        .collect();
+    let twist = vehicle.model.body_twist_with_slip(&slip).unwrap();
+
+    assert_twist_approx_eq(Vector6::new(0.5, 0.0, 0.0, 0.0, 0.0, 0.0), twist);
+}
+
+#[test]
+fn when_estimating_twist_with_slip_angle_it_should_correct_the_wheel_direction() {
+    let vehicle = TestVehicle::new();
+    for index in 0..4 {
+        vehicle.set_module_state(index, 0.0, Some(10.0));
+    }
+    vehicle.wait_for_updates();
+
+    let slip: HashMap<FrameID, WheelSlip> = vehicle
+        .wheels
+        .iter()
+        .map(|w| (*w, WheelSlip::new(0.0, 0.5 * PI)))
+        .collect();
+    let twist = vehicle.model.body_twist_with_slip(&slip).unwrap();
+
+    assert_twist_approx_eq(Vector6::new(0.0, 1.0, 0.0, 0.0, 0.0, 0.0), twist);
+}
+
+// MotionModel::twist_consistency
+
+fn consistent_rotation_states(
+    vehicle: &TestVehicle,
+    angular_velocity: f64,
+) -> HashMap<FrameID, JointState> {
+    let mut states = HashMap::new();
+    for (index, (x, y)) in vehicle.module_positions.iter().enumerate() {
+        let heading = x.atan2(-y);
+        let speed = angular_velocity * (x * x + y * y).sqrt();
+        states.insert(
+            vehicle.steering_frames[index],
+            JointState::new(heading, None, None, None),
+        );
+        states.insert(
+            vehicle.wheels[index],
+            JointState::new(0.0, Some(speed / 0.1), None, None),
+        );
+    }
+
+    states
+}
+
+#[test]
+fn when_checking_consistency_with_consistent_states_it_should_have_no_residuals() {
+    let vehicle = TestVehicle::new();
+    let states = consistent_rotation_states(&vehicle, 0.5);
+
+    let consistency = vehicle.model.twist_consistency(&states).unwrap();
+
+    assert_twist_approx_eq(
+        Vector6::new(0.0, 0.0, 0.0, 0.0, 0.0, 0.5),
+        *consistency.twist(),
+    );
+    assert_eq!(4, consistency.residuals().len());
+    for wheel in vehicle.wheels.iter() {
+        assert!(consistency.residual(wheel).unwrap() < 1e-9);
+    }
+    assert!(consistency.inconsistent_wheels(1e-6).is_empty());
+}
+
+#[test]
+fn when_checking_consistency_with_a_faulty_wheel_it_should_flag_the_wheel() {
+    let vehicle = TestVehicle::new();
+    let mut states = consistent_rotation_states(&vehicle, 0.5);
+    states.insert(
+        vehicle.wheels[2],
+        JointState::new(0.0, Some(0.0), None, None),
+    );
+
+    let consistency = vehicle.model.twist_consistency(&states).unwrap();
+
+    // The residual of the faulty wheel is the difference between the expected and the
+    // measured velocity
+    let expected_residual = 0.5 * (1.0f64 + 0.25).sqrt();
+    assert!((consistency.residual(&vehicle.wheels[2]).unwrap() - expected_residual).abs() < 1e-9);
+
+    let flagged = consistency.inconsistent_wheels(0.0);
+    assert_eq!(vehicle.wheels[2], flagged[0]);
+    assert_eq!(
+        vec![vehicle.wheels[2]],
+        consistency.inconsistent_wheels(0.5)
+    );
+}
+
+#[test]
+fn when_checking_consistency_without_joint_states_it_should_use_the_actuator_states() {
+    let vehicle = TestVehicle::new();
+    for index in 0..4 {
+        vehicle.set_module_state(index, 0.0, Some(10.0));
+    }
+    vehicle.wait_for_updates();
+
+    let consistency = vehicle.model.twist_consistency(&HashMap::new()).unwrap();
+
+    assert_twist_approx_eq(
+        Vector6::new(1.0, 0.0, 0.0, 0.0, 0.0, 0.0),
+        *consistency.twist(),
+    );
+    assert!(consistency.inconsistent_wheels(1e-6).is_empty());
+}
+
+#[test]
+fn when_checking_consistency_with_too_few_measurements_it_should_error() {
+    let vehicle = TestVehicle::new();
+    let mut states = HashMap::new();
+    for (index, wheel) in vehicle.wheels.iter().enumerate() {
+        let velocity = if index == 0 { Some(1.0) } else { None };
+        states.insert(*wheel, JointState::new(0.0, velocity, None, None));
+    }
+
+    let result = vehicle.model.twist_consistency(&states);
+    assert_eq!(
+        Err(Error::InsufficientWheelMeasurements {
+            required: 2,
+            available: 1
+        }),
+        result
+    );
+}
+
+// MotionModel::rotation_center_consistency
+
+#[test]
+fn when_checking_the_rotation_centers_with_consistent_states_it_should_have_no_spread() {
+    let vehicle = TestVehicle::new();
+    let states = consistent_rotation_states(&vehicle, 0.5);
+
+    let consistency = vehicle.model.rotation_center_consistency(&states).unwrap();
+
+    assert!(consistency.center_of_rotation().unwrap().norm() < 1e-9);
+    assert_eq!(4, consistency.wheel_centers().len());
+    for wheel in vehicle.wheels.iter() {
+        assert!(consistency.wheel_center(wheel).unwrap().norm() < 1e-9);
+        assert!(consistency.deviation(wheel).unwrap() < 1e-9);
+    }
+    assert!(consistency.spread().unwrap() < 1e-9);
+}
+
+#[test]
+fn when_checking_the_rotation_centers_with_a_misaligned_steering_it_should_flag_the_wheel() {
+    let vehicle = TestVehicle::new();
+    let mut states = consistent_rotation_states(&vehicle, 0.5);
+    let (x, y) = vehicle.module_positions[1];
+    states.insert(
+        vehicle.steering_frames[1],
+        JointState::new(x.atan2(-y) + 0.2, None, None, None),
+    );
+
+    let consistency = vehicle.model.rotation_center_consistency(&states).unwrap();
+
+    let deviations: Vec<f64> = vehicle
+        .wheels
+        .iter()
+        .map(|w| consistency.deviation(w).unwrap())
+        .collect();
+    let largest = deviations
+        .iter()
+        .enumerate()
+        .max_by(|a, b| a.1.total_cmp(b.1))
+        .map(|(index, _)| index);
+    assert_eq!(Some(1), largest);
+    assert!(consistency.spread().unwrap() > 0.05);
+}
+
+#[test]
+fn when_checking_the_rotation_centers_while_translating_it_should_have_no_centers() {
+    let vehicle = TestVehicle::new();
+    let mut states = HashMap::new();
+    for wheel in vehicle.wheels.iter() {
+        states.insert(*wheel, JointState::new(0.0, Some(10.0), None, None));
+    }
+
+    let consistency = vehicle.model.rotation_center_consistency(&states).unwrap();
+    assert_eq!(
+        RotationCenterConsistency {
+            center_of_rotation: None,
+            wheel_centers: HashMap::new(),
+        },
+        consistency
+    );
+    assert_eq!(None, consistency.spread());
+}
+
+// MotionModel::wheel_slip_estimates
+
+#[test]
+fn when_estimating_the_slip_of_rolling_wheels_it_should_return_no_slip() {
+    let vehicle = TestVehicle::new();
+    for index in 0..4 {
+        vehicle.set_module_state(index, 0.3, Some(10.0));
+    }
+    vehicle.wait_for_updates();
+
+    let estimates = vehicle.model.wheel_slip_estimates().unwrap();
+    assert_eq!(4, estimates.len());
+    for wheel in vehicle.wheels.iter() {
+        let estimate = estimates.get(wheel).unwrap();
+        assert_eq!(10.0, estimate.measured_velocity());
+        assert_eq!(None, estimate.commanded_velocity());
+        assert_eq!(None, estimate.tracking_error());
+        assert!(
+            (Vector2::new(0.3f64.cos(), 0.3f64.sin()) - estimate.ground_velocity()).norm() < 1e-9
+        );
+
+        let slip = estimate.slip().unwrap();
+        assert!(slip.slip_ratio().abs() < 1e-9);
+        assert!(slip.slip_angle().abs() < 1e-9);
+    }
+}
+
+#[test]
+fn when_estimating_the_slip_of_a_spinning_wheel_it_should_have_the_largest_slip_ratio() {
+    let vehicle = TestVehicle::new();
+    for index in 0..4 {
+        let velocity = if index == 2 { 15.0 } else { 10.0 };
+        vehicle.set_module_state(index, 0.0, Some(velocity));
+        vehicle
+            .model
+            .actuator_for(&vehicle.wheels[index])
+            .unwrap()
+            .update_state(JointState::new(0.0, Some(10.0), None, None))
+            .unwrap();
+    }
+    vehicle.wait_for_updates();
+
+    let estimates = vehicle.model.wheel_slip_estimates().unwrap();
+    let spinning = estimates.get(&vehicle.wheels[2]).unwrap();
+    assert_eq!(Some(10.0), spinning.commanded_velocity());
+    assert_eq!(Some(-5.0), spinning.tracking_error());
+
+    let spinning_ratio = spinning.slip().unwrap().slip_ratio();
+    assert!(spinning_ratio > 0.0);
+    for (index, wheel) in vehicle.wheels.iter().enumerate() {
+        let estimate = estimates.get(wheel).unwrap();
+        if index != 2 {
+            assert_eq!(Some(0.0), estimate.tracking_error());
+            assert!(estimate.slip().unwrap().slip_ratio() < spinning_ratio);
+        }
+    }
+}
+
+#[test]
+fn when_estimating_the_slip_of_a_stationary_wheel_it_should_not_have_a_slip() {
+    let vehicle = TestVehicle::new();
+    for index in 0..4 {
+        let velocity = if index == 0 { 0.0 } else { 10.0 };
+        vehicle.set_module_state(index, 0.0, Some(velocity));
+    }
+    vehicle.wait_for_updates();
+
+    let estimates = vehicle.model.wheel_slip_estimates().unwrap();
+    let stationary = estimates.get(&vehicle.wheels[0]).unwrap();
+    assert_eq!(0.0, stationary.measured_velocity());
+    assert_eq!(None, stationary.slip());
+    assert!(stationary.ground_velocity().norm() > 0.0);
+}
@@ -0,0 +1,385 @@
+//! Provides transform queries that are suitable for use in real-time control loops.
+//!
+//! The transform queries on the [MotionModel] build chains of frames, wait for the locks on the
+//! joint states and return new values, which makes their run time unpredictable. A
+//! [RealtimeTransforms] allocates all the storage it needs when it is created. After that
+//! [RealtimeTransforms::update] reads the joint states without waiting for the hardware and
+//! the queries write their results into values provided by the caller, so neither allocates
+//! memory nor blocks on a lock as long as the structure of the model does not change.
+
+use std::collections::{HashMap, VecDeque};
+
+use nalgebra::{Isometry3, Matrix4};
+
+use crate::{
+    model_elements::{
+        frame_elements::{FrameDofType, FrameID},
+        model::MotionModel,
+    },
+    Error,
+};
+
+#[cfg(test)]
+#[path = "realtime_tests.rs"]
+mod realtime_tests;
+
+/// The static description of a single frame of the model.
+#[derive(Clone, Debug)]
+struct RealtimeFrame {
+    /// The ID of the frame.
+    id: FrameID,
+
+    /// The index of the parent of the frame, or [None] for the body.
+    parent: Option<usize>,
+
+    /// The transform to the parent with the joint at its zero position.
+    to_parent: Isometry3<f64>,
+
+    /// The degree of freedom of the joint that connects the frame to its parent.
+    dof: FrameDofType,
+
+    /// Indicates if the joint has an actuator or a sensor.
+    has_state: bool,
+}
+
+/// Stores the transforms from the frames of a [MotionModel] to the body frame in pre-allocated
+/// storage, so that they can be queried without allocating memory or waiting on a lock.
+///
+/// The transforms are computed by [RealtimeTransforms::update] from the joint states at the
+/// time of the update. When the state of a joint is being written by the hardware during the
+/// update the previous position of the joint is used, so an update never waits for the
+/// hardware. The queries only read the stored transforms.
+///
+/// Changes to the structure of the model, as tracked by [MotionModel::revision], are picked up
+/// by the next update. Rebuilding the storage for the new structure allocates memory.
+///
+/// A [StateFusion](crate::hardware::state_fusion::StateFusion) that allocates memory also
+/// makes the updates allocate memory for joints that have both an actuator and a sensor.
+///
+/// ## Examples
+///
+/// ```
+/// use nalgebra::{Matrix3, Matrix4, Matrix6, Translation3, UnitQuaternion, Vector3};
+/// use swerve_vehicle_descriptors::kinematics::realtime::RealtimeTransforms;
+/// use swerve_vehicle_descriptors::model_elements::model::{ChassisElementPhysicalProperties, MotionModel};
+///
+/// let mut model = MotionModel::new();
+/// let body = model.add_body(
+///     "body".to_string(),
+///     Translation3::identity(),
+///     UnitQuaternion::identity(),
+///     ChassisElementPhysicalProperties::new(
+///         10.0,
+///         Vector3::zeros(),
+///         Matrix3::identity(),
+///         Matrix6::identity(),
+///     ),
+/// ).unwrap();
+///
+/// let mut transforms = RealtimeTransforms::new(&model).unwrap();
+/// let mut transform = Matrix4::zeros();
+///
+/// // In the control loop
+/// transforms.update(&model).unwrap();
+/// transforms.homogeneous_transform_to_body_into(&body, &mut transform).unwrap();
+/// assert_eq!(Matrix4::identity(), transform);
+/// ```
+#[derive(Clone, Debug)]
+pub struct RealtimeTransforms {
+    /// The revision of the model for which the storage was built.
+    revision: u64,
+
+    /// The frames ordered from the body outwards, so that each frame is listed after its parent.
+    frames: Vec<RealtimeFrame>,
+
+    /// The index of each frame in the frame list.
+    indices: HashMap<FrameID, usize>,
+
+    /// The position of the joint of each frame at the last update.
+    positions: Vec<f64>,
+
+    /// The transform from each frame to the body at the last update.
+    to_body: Vec<Isometry3<f64>>,
+
+    /// The pose of the body in the world at the last update.
+    body_pose_in_world: Isometry3<f64>,
+}
+
+impl RealtimeTransforms {
+    /// Writes the homogeneous transform matrix from the 'from' frame to the 'to' frame, at the
+    /// last update, into 'result'.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'from' - The source element for which the transform is requested
+    /// * 'to' - The target element
+    /// * 'result' - The matrix that receives the transform.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when either frame was not part of the model at
+    ///   the last update.
+    pub fn homogeneous_transform_between_frames_into(
+        &self,
+        from: &FrameID,
+        to: &FrameID,
+        result: &mut Matrix4<f64>,
+    ) -> Result<(), Error> {
+        let from_to_body = self.transform_to_body(from)?;
+        let to_to_body = self.transform_to_body(to)?;
+        *result = (to_to_body.inverse() * from_to_body).to_homogeneous();
+        Ok(())
+    }
+
+    /// Writes the homogeneous transform matrix from the given frame to the body frame, at the
+    /// last update, into 'result'.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'frame_id' - The source element for which the transform is requested
+    /// * 'result' - The matrix that receives the transform.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the frame was not part of the model at the
+    ///   last update.
+    pub fn homogeneous_transform_to_body_into(
+        &self,
+        frame_id: &FrameID,
+        result: &mut Matrix4<f64>,
+    ) -> Result<(), Error> {
+        *result = self.transform_to_body(frame_id)?.to_homogeneous();
+        Ok(())
+    }
+
+    /// Writes the homogeneous transform matrix from the given frame to the world frame, at the
+    /// last update, into 'result'.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'frame_id' - The source element for which the transform is requested
+    /// * 'result' - The matrix that receives the transform.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the frame was not part of the model at the
+    ///   last update.
+    pub fn homogeneous_transform_to_world_into(
+        &self,
+        frame_id: &FrameID,
+        result: &mut Matrix4<f64>,
+    ) -> Result<(), Error> {
+        *result = (self.body_pose_in_world * self.transform_to_body(frame_id)?).to_homogeneous();
+        Ok(())
+    }
+
+    /// Writes the transform from the 'from' frame to the 'to' frame, at the last update, into
+    /// 'result'.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'from' - The source element for which the transform is requested
+    /// * 'to' - The target element
+    /// * 'result' - The isometry that receives the transform.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when either frame was not part of the model at
+    ///   the last update.
+    pub fn isometry_between_frames_into(
+        &self,
+        from: &FrameID,
+        to: &FrameID,
+        result: &mut Isometry3<f64>,
+    ) -> Result<(), Error> {
+        let from_to_body = self.transform_to_body(from)?;
+        let to_to_body = self.transform_to_body(to)?;
+        *result = to_to_body.inverse() * from_to_body;
+        Ok(())
+    }
+
+    /// Writes the transform from the given frame to the body frame, at the last update, into
+    /// 'result'.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'frame_id' - The source element for which the transform is requested
+    /// * 'result' - The isometry that receives the transform.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the frame was not part of the model at the
+    ///   last update.
+    pub fn isometry_to_body_into(
+        &self,
+        frame_id: &FrameID,
+        result: &mut Isometry3<f64>,
+    ) -> Result<(), Error> {
+        *result = *self.transform_to_body(frame_id)?;
+        Ok(())
+    }
+
+    /// Writes the transform from the given frame to the world frame, at the last update, into
+    /// 'result'.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'frame_id' - The source element for which the transform is requested
+    /// * 'result' - The isometry that receives the transform.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the frame was not part of the model at the
+    ///   last update.
+    pub fn isometry_to_world_into(
+        &self,
+        frame_id: &FrameID,
+        result: &mut Isometry3<f64>,
+    ) -> Result<(), Error> {
+        *result = self.body_pose_in_world * self.transform_to_body(frame_id)?;
+        Ok(())
+    }
+
+    /// Returns the position of the joint of the given frame at the last update, or [None] if
+    /// the joint has neither an actuator nor a sensor.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'frame_id' - The [FrameID] of the joint.
+    pub fn joint_position(&self, frame_id: &FrameID) -> Option<f64> {
+        let index = *self.indices.get(frame_id)?;
+        if self.frames[index].has_state {
+            Some(self.positions[index])
+        } else {
+            None
+        }
+    }
+
+    /// Creates a new [RealtimeTransforms] instance for the given model and computes the
+    /// transforms for the current state of the model.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'model' - The model for which the transforms are computed.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the model has no body.
+    pub fn new(model: &MotionModel) -> Result<Self, Error> {
+        let mut result = Self {
+            revision: model.revision(),
+            frames: Vec::new(),
+            indices: HashMap::new(),
+            positions: Vec::new(),
+            to_body: Vec::new(),
+            body_pose_in_world: Isometry3::identity(),
+        };
+        result.rebuild(model)?;
+        result.update(model)?;
+
+        Ok(result)
+    }
+
+    /// Reads the joint states of the model and recomputes the transforms of all the frames.
+    ///
+    /// The joint states are read without waiting for the hardware. Returns the number of joints
+    /// for which the state was being written at the time of the update, and for which the
+    /// position of the previous update was used.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'model' - The model from which the transforms were created.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the structure of the model changed and the
+    ///   model no longer has a body.
+    pub fn update(&mut self, model: &MotionModel) -> Result<usize, Error> {
+        if self.revision != model.revision() {
+            self.rebuild(model)?;
+        }
+
+        let mut stale = 0;
+        for index in 0..self.frames.len() {
+            let frame = &self.frames[index];
+            let parent = match frame.parent {
+                Some(p) => p,
+                None => {
+                    self.to_body[index] = Isometry3::identity();
+                    continue;
+                }
+            };
+
+            let position = if frame.has_state {
+                match model.try_current_joint_position(&frame.id) {
+                    Some(p) => {
+                        self.positions[index] = p;
+                        Some(p)
+                    }
+                    None => {
+                        stale += 1;
+                        Some(self.positions[index])
+                    }
+                }
+            } else {
+                None
+            };
+
+            // Joints without a known position are assumed to be at their zero position
+            let to_parent = match (model.kinematics_table(&frame.id), position) {
+                (Some(table), position) => table.transform_at(position.unwrap_or(0.0)),
+                (None, Some(position)) => {
+                    model.transform_for_motion(position, frame.dof, &frame.to_parent)
+                }
+                (None, None) => frame.to_parent,
+            };
+
+            self.to_body[index] = self.to_body[parent] * to_parent;
+        }
+
+        self.body_pose_in_world = *model.body_pose_in_world();
+        Ok(stale)
+    }
+
+    /// Rebuilds the storage for the current structure of the model.
+    fn rebuild(&mut self, model: &MotionModel) -> Result<(), Error> {
+        self.frames.clear();
+        self.indices.clear();
+
+        let tree = model.kinematic_tree();
+        let body = *model.body()?;
+        let mut queue = VecDeque::from([(body, None)]);
+        while let Some((frame, parent)) = queue.pop_front() {
+            let index = self.frames.len();
+            let element = tree.element(&frame)?;
+            self.frames.push(RealtimeFrame {
+                id: frame,
+                parent,
+                to_parent: match parent {
+                    Some(_) => *tree.homogeneous_transform_to_parent(&frame)?,
+                    None => Isometry3::identity(),
+                },
+                dof: element.degree_of_freedom_kind(),
+                has_state: model.is_actuated(&frame) || model.has_sensor(&frame),
+            });
+            self.indices.insert(frame, index);
+
+            for child in model.children_of(&frame)? {
+                queue.push_back((*child, Some(index)));
+            }
+        }
+
+        self.positions = vec![0.0; self.frames.len()];
+        self.to_body = vec![Isometry3::identity(); self.frames.len()];
+        self.revision = model.revision();
+        Ok(())
+    }
+
+    /// Returns the stored transform from the given frame to the body.
+    fn transform_to_body(&self, frame_id: &FrameID) -> Result<&Isometry3<f64>, Error> {
+        match self.indices.get(frame_id) {
+            Some(index) => Ok(&self.to_body[*index]),
+            None => Err(Error::MissingFrameElement { id: *frame_id }),
+        }
+    }
+}
@@ -0,0 +1,206 @@
+use std::{f64::consts::PI, time::Duration};
+
+use nalgebra::{Isometry3, Matrix4, Translation3, UnitQuaternion};
+
+use crate::{
+    change_notification_processing::HardwareChangeProcessor,
+    hardware::{
+        joint_state::{JointState, JointStateRange},
+        simulation::{SimulatedActuator, SimulatedDynamics},
+    },
+    model_elements::{
+        frame_elements::{Actuator, FrameDofType, FrameID, JointConstraint},
+        model::MotionModel,
+    },
+    number_space::NumberSpaceType,
+    test_fixtures::physical_properties,
+    Error,
+};
+
+use super::RealtimeTransforms;
+
+fn simulated_actuator(motion_type: NumberSpaceType) -> SimulatedActuator {
+    SimulatedActuator::new(
+        motion_type,
+        JointStateRange::new(
+            JointState::new(-100.0, None, None, None),
+            JointState::new(100.0, None, None, None),
+        ),
+        SimulatedDynamics::new(),
+    )
+}
+
+// A single module attached to the body at (1.0, 0.5, 0.0), rotated 30 degrees around the
+// z-axis. The module consists of a suspension frame with a prismatic z joint without an
+// actuator, a steering frame 0.2 in front of and 0.1 below the suspension frame, and a wheel
+// frame 0.1 below the steering frame.
+struct TestModule {
+    model: MotionModel,
+    body: FrameID,
+    suspension: FrameID,
+    steering: FrameID,
+    wheel: FrameID,
+    steering_hardware: SimulatedActuator,
+    processor: HardwareChangeProcessor,
+}
+
+impl TestModule {
+    fn new() -> Self {
+        let processor = HardwareChangeProcessor::manual();
+        let mut model = MotionModel::new();
+        let body = model
+            .add_body(
+                "body".to_string(),
+                Translation3::identity(),
+                UnitQuaternion::identity(),
+                physical_properties(),
+            )
+            .unwrap();
+
+        let suspension = model
+            .add_suspension_element(
+                "suspension".to_string(),
+                FrameDofType::PrismaticZ,
+                body,
+                Translation3::new(1.0, 0.5, 0.0),
+                UnitQuaternion::from_euler_angles(0.0, 0.0, PI / 6.0),
+                physical_properties(),
+                JointConstraint::new(),
+            )
+            .unwrap();
+
+        let mut steering_hardware = simulated_actuator(NumberSpaceType::AngularLimited {
+            start_angle_in_radians: 0.0,
+        });
+        let steering = model
+            .add_steering_element(
+                "steering".to_string(),
+                suspension,
+                Translation3::new(0.2, 0.0, -0.1),
+                UnitQuaternion::identity(),
+                physical_properties(),
+                Actuator::new(&mut steering_hardware, &processor).unwrap(),
+            )
+            .unwrap();
+
+        let mut wheel_hardware = simulated_actuator(NumberSpaceType::LinearUnlimited);
+        let wheel = model
+            .add_wheel(
+                "wheel".to_string(),
+                steering,
+                Translation3::new(0.0, 0.0, -0.1),
+                UnitQuaternion::identity(),
+                physical_properties(),
+                Actuator::new(&mut wheel_hardware, &processor).unwrap(),
+            )
+            .unwrap();
+        processor.process_pending();
+
+        Self {
+            model,
+            body,
+            suspension,
+            steering,
+            wheel,
+            steering_hardware,
+            processor,
+        }
+    }
+
+    fn set_steering_angle(&self, angle: f64) {
+        self.steering_hardware
+            .set_state(JointState::new(angle, Some(0.0), None, None));
+        self.steering_hardware.step(Duration::from_millis(10));
+        self.processor.process_pending();
+    }
+}
+
+#[test]
+fn when_updating_it_should_match_the_transforms_of_the_model() {
+    let mut module = TestModule::new();
+    module.set_steering_angle(0.25 * PI);
+    module
+        .model
+        .set_body_pose_in_world(Isometry3::translation(1.0, 2.0, 3.0));
+
+    let mut transforms = RealtimeTransforms::new(&module.model).unwrap();
+    assert_eq!(Some(0.25 * PI), transforms.joint_position(&module.steering));
+    assert_eq!(None, transforms.joint_position(&module.suspension));
+
+    let mut matrix = Matrix4::zeros();
+    let mut isometry = Isometry3::identity();
+    for frame in [
+        module.body,
+        module.suspension,
+        module.steering,
+        module.wheel,
+    ] {
+        transforms
+            .homogeneous_transform_to_body_into(&frame, &mut matrix)
+            .unwrap();
+        let expected = module.model.homogeneous_transform_to_body(&frame).unwrap();
+        assert!((expected - matrix).norm() < 1e-12);
+
+        transforms
+            .isometry_to_world_into(&frame, &mut isometry)
+            .unwrap();
+        let expected = module.model.isometry_to_world(&frame).unwrap();
+        assert!((expected.to_homogeneous() - isometry.to_homogeneous()).norm() < 1e-12);
+
+        transforms
+            .homogeneous_transform_between_frames_into(&module.wheel, &frame, &mut matrix)
+            .unwrap();
+        let expected = module
+            .model
+            .homogeneous_transform_between_frames(&module.wheel, &frame)
+            .unwrap();
+        assert!((expected - matrix).norm() < 1e-12);
+    }
+
+    // The transforms only change when the transforms are updated
+    module.set_steering_angle(-0.5 * PI);
+    transforms
+        .homogeneous_transform_to_body_into(&module.wheel, &mut matrix)
+        .unwrap();
+    let expected = module
+        .model
+        .homogeneous_transform_to_body(&module.wheel)
+        .unwrap();
+    assert!((expected - matrix).norm() > 1e-3);
+
+    assert_eq!(0, transforms.update(&module.model).unwrap());
+    transforms
+        .homogeneous_transform_to_body_into(&module.wheel, &mut matrix)
+        .unwrap();
+    assert!((expected - matrix).norm() < 1e-12);
+}
+
+#[test]
+fn when_the_model_changes_it_should_rebuild_on_the_next_update() {
+    let mut module = TestModule::new();
+    let mut transforms = RealtimeTransforms::new(&module.model).unwrap();
+
+    let mut matrix = Matrix4::zeros();
+    let result = transforms.homogeneous_transform_to_body_into(&FrameID::new(), &mut matrix);
+    assert!(matches!(result, Err(Error::MissingFrameElement { .. })));
+
+    let extra = module
+        .model
+        .add_static_chassis_element(
+            "extra".to_string(),
+            module.body,
+            Translation3::new(0.0, 0.0, 1.0),
+            UnitQuaternion::identity(),
+            physical_properties(),
+        )
+        .unwrap();
+    assert!(transforms
+        .homogeneous_transform_to_body_into(&extra, &mut matrix)
+        .is_err());
+
+    transforms.update(&module.model).unwrap();
+    transforms
+        .homogeneous_transform_to_body_into(&extra, &mut matrix)
+        .unwrap();
+    assert_eq!(1.0, matrix[(2, 3)]);
+}
@@ -0,0 +1,183 @@
+//! Provides the functions used to calibrate the effective rolling radius of the wheels.
+//!
+//! Tire wear and load change the distance a wheel travels for each revolution. The odometry and
+//! the inverse kinematics use the rolling radius of a wheel, see
+//! [WheelProperties::rolling_radius](crate::model_elements::model::WheelProperties::rolling_radius),
+//! which defaults to the radius of the wheel.
+//!
+//! A [RollingRadiusEstimator] accumulates samples of the rotation of a wheel, as reported by the
+//! wheel encoder, and of the distance the wheel travelled according to an external ground truth,
+//! e.g. a motion capture system or a surveyed track. The rolling radius is the least-squares
+//! ratio between the two, i.e. `Σ(θ d) / Σ(θ²)`. The estimate is stored in the model with
+//! [MotionModel::calibrate_rolling_radius].
+//!
+//! # Examples
+//!
+//! ```
+//! use nalgebra::{Matrix3, Translation3, UnitQuaternion, Vector3};
+//! use swerve_vehicle_descriptors::kinematics::rolling_radius::RollingRadiusEstimator;
+//! use swerve_vehicle_descriptors::model_elements::model::{
+//!     ChassisElementPhysicalProperties, MotionModel, WheelProperties,
+//! };
+//! use swerve_vehicle_descriptors::model_elements::module_spec::ModuleSpec;
+//!
+//! let properties =
+//!     ChassisElementPhysicalProperties::from_mass_properties(1.0, Vector3::zeros(), Matrix3::identity());
+//! let mut model = MotionModel::new();
+//! model
+//!     .add_body(
+//!         "body".to_string(),
+//!         Translation3::identity(),
+//!         UnitQuaternion::identity(),
+//!         properties,
+//!     )
+//!     .unwrap();
+//! let frames = model
+//!     .add_drive_module(
+//!         ModuleSpec::new(
+//!             "front_left".to_string(),
+//!             Translation3::new(1.0, 0.5, 0.0),
+//!             UnitQuaternion::identity(),
+//!             WheelProperties::new(0.1, 0.05, Vector3::zeros()),
+//!             properties,
+//!             properties,
+//!         )
+//!         .with_wheel_offset(Translation3::new(0.0, 0.0, -0.1)),
+//!     )
+//!     .unwrap();
+//!
+//! // The worn wheel travelled 0.98 meters for each 10 radians of rotation
+//! let mut estimator = RollingRadiusEstimator::new(*frames.wheel());
+//! estimator.add_sample(10.0, 0.98);
+//! estimator.add_sample(20.0, 1.96);
+//!
+//! let rolling_radius = model.calibrate_rolling_radius(&estimator).unwrap();
+//! assert!((rolling_radius - 0.098).abs() < 1e-12);
+//! assert_eq!(0.1, model.wheel_radius(frames.wheel()).unwrap());
+//! ```
+
+use crate::{
+    model_elements::{frame_elements::FrameID, model::MotionModel},
+    Error,
+};
+
+#[cfg(test)]
+#[path = "rolling_radius_tests.rs"]
+mod rolling_radius_tests;
+
+/// Estimates the effective rolling radius of a wheel from the rotation of the wheel and the
+/// distance the wheel travelled according to an external ground truth.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RollingRadiusEstimator {
+    /// The wheel for which the rolling radius is estimated.
+    wheel: FrameID,
+
+    /// The number of samples.
+    number_of_samples: usize,
+
+    /// The sum of the squares of the wheel rotations.
+    sum_of_squared_rotations: f64,
+
+    /// The sum of the products of the wheel rotations and the ground truth distances.
+    sum_of_rotation_distance_products: f64,
+}
+
+impl RollingRadiusEstimator {
+    /// Adds a sample to the estimator.
+    ///
+    /// A sample describes a stretch of driving, preferably in a straight line and without
+    /// wheel slip. Samples with a larger rotation carry more weight.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'wheel_rotation' - The angle, in radians, the wheel rotated over the stretch, as
+    ///   reported by the wheel encoder.
+    /// * 'ground_truth_distance' - The distance, in meters, the wheel travelled over the stretch
+    ///   according to the ground truth. The distance has the same sign as the rotation when the
+    ///   wheel rolls in its forward direction.
+    pub fn add_sample(&mut self, wheel_rotation: f64, ground_truth_distance: f64) {
+        if !wheel_rotation.is_finite() || !ground_truth_distance.is_finite() {
+            return;
+        }
+
+        self.number_of_samples += 1;
+        self.sum_of_squared_rotations += wheel_rotation * wheel_rotation;
+        self.sum_of_rotation_distance_products += wheel_rotation * ground_truth_distance;
+    }
+
+    /// Returns the estimated rolling radius, in meters.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::InsufficientWheelMeasurements] - Returned when none of the samples has a
+    ///   rotation of the wheel.
+    /// * [Error::InvalidRollingRadius] - Returned when the estimated rolling radius is not a
+    ///   positive number, e.g. because the ground truth distances have the wrong sign.
+    pub fn estimate(&self) -> Result<f64, Error> {
+        if self.sum_of_squared_rotations == 0.0 {
+            return Err(Error::InsufficientWheelMeasurements {
+                required: 1,
+                available: 0,
+            });
+        }
+
+        let radius = self.sum_of_rotation_distance_products / self.sum_of_squared_rotations;
+        if !radius.is_finite() || radius <= 0.0 {
+            return Err(Error::InvalidRollingRadius { radius });
+        }
+
+        Ok(radius)
+    }
+
+    /// Creates a new [RollingRadiusEstimator] instance without any samples.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'wheel' - The [FrameID] of the wheel for which the rolling radius is estimated.
+    pub fn new(wheel: FrameID) -> Self {
+        Self {
+            wheel,
+            number_of_samples: 0,
+            sum_of_squared_rotations: 0.0,
+            sum_of_rotation_distance_products: 0.0,
+        }
+    }
+
+    /// Returns the number of samples that were added to the estimator.
+    pub fn number_of_samples(&self) -> usize {
+        self.number_of_samples
+    }
+
+    /// Returns the [FrameID] of the wheel for which the rolling radius is estimated.
+    pub fn wheel(&self) -> &FrameID {
+        &self.wheel
+    }
+}
+
+impl MotionModel {
+    /// Stores the rolling radius estimated by the given estimator as the rolling radius of the
+    /// wheel of the estimator and returns it.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'estimator' - The estimator that holds the samples for the wheel.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::InsufficientWheelMeasurements] - Returned when the estimator has no samples
+    ///   with a rotation of the wheel.
+    /// * [Error::InvalidRollingRadius] - Returned when the estimated rolling radius is not a
+    ///   positive number.
+    /// * [Error::MissingFrameElement] - Returned when the wheel is not part of the model.
+    /// * [Error::InvalidFrameID] - Returned when the frame of the estimator is not a wheel.
+    /// * [Error::MissingWheelRadius] - Returned when the wheel has no
+    ///   [WheelProperties](crate::model_elements::model::WheelProperties).
+    pub fn calibrate_rolling_radius(
+        &mut self,
+        estimator: &RollingRadiusEstimator,
+    ) -> Result<f64, Error> {
+        let radius = estimator.estimate()?;
+        self.set_wheel_rolling_radius(estimator.wheel(), Some(radius))?;
+        Ok(radius)
+    }
+}
@@ -0,0 +1,140 @@
+use nalgebra::{Translation3, UnitQuaternion, Vector3, Vector6};
+
+use crate::{
+    model_elements::{
+        frame_elements::FrameID,
+        model::{MotionModel, WheelProperties},
+        module_spec::{ModuleFrames, ModuleSpec},
+    },
+    test_fixtures::physical_properties,
+    Error,
+};
+
+use super::RollingRadiusEstimator;
+
+fn create_model() -> (MotionModel, ModuleFrames) {
+    let properties = physical_properties();
+    let mut model = MotionModel::new();
+    model
+        .add_body(
+            "body".to_string(),
+            Translation3::identity(),
+            UnitQuaternion::identity(),
+            properties,
+        )
+        .unwrap();
+    let frames = model
+        .add_drive_module(
+            ModuleSpec::new(
+                "front_left".to_string(),
+                Translation3::new(1.0, 0.5, 0.0),
+                UnitQuaternion::identity(),
+                WheelProperties::new(0.1, 0.05, Vector3::zeros()),
+                properties,
+                properties,
+            )
+            .with_wheel_offset(Translation3::new(0.0, 0.0, -0.1)),
+        )
+        .unwrap();
+
+    (model, frames)
+}
+
+#[test]
+fn when_estimating_the_rolling_radius_it_should_fit_the_samples() {
+    let wheel = FrameID::new();
+    let mut estimator = RollingRadiusEstimator::new(wheel);
+    assert_eq!(&wheel, estimator.wheel());
+    assert_eq!(
+        Err(Error::InsufficientWheelMeasurements {
+            required: 1,
+            available: 0
+        }),
+        estimator.estimate()
+    );
+
+    // Samples with a rotation of zero or with invalid values carry no information
+    estimator.add_sample(0.0, 0.1);
+    estimator.add_sample(f64::NAN, 1.0);
+    assert_eq!(1, estimator.number_of_samples());
+    assert!(estimator.estimate().is_err());
+
+    // Noisy samples around a radius of 0.095, driving forwards and backwards
+    for (rotation, distance) in [(10.0, 0.96), (-20.0, -1.89), (5.0, 0.475)] {
+        estimator.add_sample(rotation, distance);
+    }
+    let expected = (10.0 * 0.96 + 20.0 * 1.89 + 5.0 * 0.475) / (100.0 + 400.0 + 25.0);
+    assert!((estimator.estimate().unwrap() - expected).abs() < 1e-12);
+
+    let mut reversed = RollingRadiusEstimator::new(wheel);
+    reversed.add_sample(10.0, -1.0);
+    assert_eq!(
+        Err(Error::InvalidRollingRadius { radius: -0.1 }),
+        reversed.estimate()
+    );
+}
+
+#[test]
+fn when_setting_the_rolling_radius_it_should_be_used_by_the_inverse_kinematics() {
+    let (mut model, frames) = create_model();
+    let wheel = *frames.wheel();
+    let twist = Vector6::new(1.0, 0.0, 0.0, 0.0, 0.0, 0.0);
+
+    assert_eq!(Ok(0.1), model.wheel_rolling_radius(&wheel));
+    let setpoints = model
+        .steering_and_wheel_rates_for_body_twist(twist)
+        .unwrap();
+    assert!((setpoints[&wheel].wheel_angular_velocity() - 10.0).abs() < 1e-12);
+
+    let revision = model.revision();
+    let mut estimator = RollingRadiusEstimator::new(wheel);
+    estimator.add_sample(10.0, 0.8);
+    assert_eq!(Ok(0.08), model.calibrate_rolling_radius(&estimator));
+    assert!(model.revision() > revision);
+    assert_eq!(Ok(0.08), model.wheel_rolling_radius(&wheel));
+    assert_eq!(Ok(0.1), model.wheel_radius(&wheel));
+
+    let setpoints = model
+        .steering_and_wheel_rates_for_body_twist(twist)
+        .unwrap();
+    assert!((setpoints[&wheel].wheel_angular_velocity() - 12.5).abs() < 1e-12);
+
+    let properties = model.wheel_properties(&wheel).unwrap();
+    assert_eq!(
+        &WheelProperties::new(0.1, 0.05, Vector3::zeros()).with_rolling_radius(0.08),
+        properties
+    );
+
+    // Clearing the rolling radius reverts to the radius of the wheel
+    model.set_wheel_rolling_radius(&wheel, None).unwrap();
+    assert_eq!(Ok(0.1), model.wheel_rolling_radius(&wheel));
+}
+
+#[test]
+fn when_setting_an_invalid_rolling_radius_it_should_return_an_error() {
+    let (mut model, frames) = create_model();
+    let wheel = *frames.wheel();
+
+    for radius in [0.0, -0.1, f64::INFINITY] {
+        assert_eq!(
+            Err(Error::InvalidRollingRadius { radius }),
+            model.set_wheel_rolling_radius(&wheel, Some(radius))
+        );
+    }
+    assert_eq!(
+        Err(Error::InvalidFrameID {
+            id: *frames.steering()
+        }),
+        model.set_wheel_rolling_radius(frames.steering(), Some(0.1))
+    );
+
+    let missing = FrameID::new();
+    assert_eq!(
+        Err(Error::MissingFrameElement { id: missing }),
+        model.set_wheel_rolling_radius(&missing, Some(0.1))
+    );
+    assert_eq!(
+        Err(Error::MissingFrameElement { id: missing }),
+        model.wheel_rolling_radius(&missing)
+    );
+}
@@ -0,0 +1,304 @@
+//! Provides a consistent view of the state of the joints of a [MotionModel].
+//!
+//! The states of the joints are updated asynchronously by the hardware. Querying the model for
+//! the transforms of several frames may therefore mix joint states from different instants, e.g.
+//! the transform of one wheel may be computed before and the transform of another wheel after
+//! an update of the steering actuators. A [ModelSnapshot] reads the state of all the joints once
+//! and answers all the transform and kinematics queries from those states.
+
+use std::{collections::HashMap, time::Instant};
+
+use nalgebra::{Isometry3, Matrix4, Vector6};
+
+use crate::{
+    hardware::joint_state::JointState,
+    model_elements::{frame_elements::FrameID, model::MotionModel},
+    Error,
+};
+
+use super::jacobian::FrameJacobian;
+
+#[cfg(test)]
+#[path = "snapshot_tests.rs"]
+mod snapshot_tests;
+
+/// An immutable view of a [MotionModel] with the states of all the joints frozen at the moment
+/// the snapshot was taken.
+///
+/// Joints that have neither an actuator nor a sensor are assumed to be at their zero position,
+/// in the same way as they are for the queries on the [MotionModel].
+///
+/// The snapshot borrows the model, so the structure of the model cannot change while the
+/// snapshot exists.
+#[derive(Clone)]
+pub struct ModelSnapshot<'a> {
+    /// The model from which the snapshot was taken.
+    model: &'a MotionModel,
+
+    /// The states of the joints that have an actuator or a sensor.
+    joint_states: HashMap<FrameID, JointState>,
+
+    /// The time at which the snapshot was taken.
+    taken_at: Instant,
+}
+
+impl<'a> ModelSnapshot<'a> {
+    /// Returns the spatial acceleration of the given frame relative to the body, based on the
+    /// joint states of the snapshot. See [MotionModel::acceleration_of_frame_in_body].
+    ///
+    /// ## Parameters
+    ///
+    /// * 'frame_id' - The [FrameID] of the frame.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the frame is not part of the model.
+    pub fn acceleration_of_frame_in_body(&self, frame_id: &FrameID) -> Result<Vector6<f64>, Error> {
+        self.model
+            .acceleration_of_frame_in_body_with(frame_id, |id| self.joint_states.get(id).copied())
+    }
+
+    /// Returns the homogeneous transform matrix from the 'from' frame to the 'to' frame, based on
+    /// the joint states of the snapshot. See [MotionModel::homogeneous_transform_between_frames].
+    ///
+    /// ## Parameters
+    ///
+    /// * 'from' - The source element for which the transform is requested
+    /// * 'to' - The target element
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when either frame is not part of the model
+    pub fn homogeneous_transform_between_frames(
+        &self,
+        from: &FrameID,
+        to: &FrameID,
+    ) -> Result<Matrix4<f64>, Error> {
+        self.isometry_between_frames(from, to)
+            .map(|transform| transform.to_homogeneous())
+    }
+
+    /// Returns the homogeneous transform matrix from the given frame to a parent element further
+    /// up the chain, based on the joint states of the snapshot. See
+    /// [MotionModel::homogeneous_transform_to_ancestor].
+    ///
+    /// ## Parameters
+    ///
+    /// * 'from' - The source element for which the transform is requested
+    /// * 'to' - The target parent element.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the frame is not part of the model or when
+    ///   'to' is not in the chain from 'from' to the body.
+    pub fn homogeneous_transform_to_ancestor(
+        &self,
+        from: &FrameID,
+        to: &FrameID,
+    ) -> Result<Matrix4<f64>, Error> {
+        self.isometry_to_ancestor(from, to)
+            .map(|transform| transform.to_homogeneous())
+    }
+
+    /// Returns the homogeneous transform matrix from the given frame to the body frame, based on
+    /// the joint states of the snapshot. See [MotionModel::homogeneous_transform_to_body].
+    ///
+    /// ## Parameters
+    ///
+    /// * 'frame_id' - The source element for which the transform is requested
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the frame is not part of the model
+    pub fn homogeneous_transform_to_body(&self, frame_id: &FrameID) -> Result<Matrix4<f64>, Error> {
+        self.isometry_to_body(frame_id)
+            .map(|transform| transform.to_homogeneous())
+    }
+
+    /// Returns the homogeneous transform matrix from the given frame to the world frame, based on
+    /// the joint states of the snapshot and the pose of the body in the world. See
+    /// [MotionModel::homogeneous_transform_to_world].
+    ///
+    /// ## Parameters
+    ///
+    /// * 'frame_id' - The source element for which the transform is requested
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the frame is not part of the model
+    pub fn homogeneous_transform_to_world(
+        &self,
+        frame_id: &FrameID,
+    ) -> Result<Matrix4<f64>, Error> {
+        self.isometry_to_world(frame_id)
+            .map(|transform| transform.to_homogeneous())
+    }
+
+    /// Returns the transform from the 'from' frame to the 'to' frame, based on the joint states
+    /// of the snapshot. See [MotionModel::isometry_between_frames].
+    ///
+    /// ## Parameters
+    ///
+    /// * 'from' - The source element for which the transform is requested
+    /// * 'to' - The target element
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when either frame is not part of the model
+    pub fn isometry_between_frames(
+        &self,
+        from: &FrameID,
+        to: &FrameID,
+    ) -> Result<Isometry3<f64>, Error> {
+        if self.model.is_ancestor(from, to) {
+            return self.isometry_to_ancestor(from, to);
+        }
+
+        let from_to_body = self.isometry_to_body(from)?;
+        let to_to_body = self.isometry_to_body(to)?;
+        Ok(to_to_body.inverse() * from_to_body)
+    }
+
+    /// Returns the transform from the given frame to a parent element further up the chain,
+    /// based on the joint states of the snapshot. See [MotionModel::isometry_to_ancestor].
+    ///
+    /// ## Parameters
+    ///
+    /// * 'from' - The source element for which the transform is requested
+    /// * 'to' - The target parent element.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the frame is not part of the model or when
+    ///   'to' is not in the chain from 'from' to the body.
+    pub fn isometry_to_ancestor(
+        &self,
+        from: &FrameID,
+        to: &FrameID,
+    ) -> Result<Isometry3<f64>, Error> {
+        self.model
+            .isometry_to_ancestor_with(from, to, |id| self.joint_position(id))
+    }
+
+    /// Returns the transform from the given frame to the body frame, based on the joint states
+    /// of the snapshot. See [MotionModel::isometry_to_body].
+    ///
+    /// ## Parameters
+    ///
+    /// * 'frame_id' - The source element for which the transform is requested
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the frame is not part of the model
+    pub fn isometry_to_body(&self, frame_id: &FrameID) -> Result<Isometry3<f64>, Error> {
+        let body = self.model.body()?;
+        self.isometry_to_ancestor(frame_id, body)
+    }
+
+    /// Returns the transform from the given frame to the world frame, based on the joint states
+    /// of the snapshot and the pose of the body in the world. See
+    /// [MotionModel::isometry_to_world].
+    ///
+    /// ## Parameters
+    ///
+    /// * 'frame_id' - The source element for which the transform is requested
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the frame is not part of the model
+    pub fn isometry_to_world(&self, frame_id: &FrameID) -> Result<Isometry3<f64>, Error> {
+        let to_body = self.isometry_to_body(frame_id)?;
+        Ok(self.model.body_pose_in_world() * to_body)
+    }
+
+    /// Returns the geometric Jacobian for the given frame, based on the joint positions of the
+    /// snapshot. See [MotionModel::jacobian_for].
+    ///
+    /// ## Parameters
+    ///
+    /// * 'frame_id' - The [FrameID] of the frame.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the frame is not part of the model.
+    pub fn jacobian_for(&self, frame_id: &FrameID) -> Result<FrameJacobian, Error> {
+        self.model
+            .jacobian_for_with(frame_id, |id| self.joint_position(id))
+    }
+
+    /// Returns the state of the joint of the given frame at the moment the snapshot was taken,
+    /// or [None] if the joint has neither an actuator nor a sensor.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'frame_id' - The [FrameID] of the joint.
+    pub fn joint_state(&self, frame_id: &FrameID) -> Option<&JointState> {
+        self.joint_states.get(frame_id)
+    }
+
+    /// Returns the states of all the joints that have an actuator or a sensor.
+    pub fn joint_states(&self) -> impl Iterator<Item = (&FrameID, &JointState)> {
+        self.joint_states.iter()
+    }
+
+    /// Returns the model from which the snapshot was taken.
+    pub fn model(&self) -> &'a MotionModel {
+        self.model
+    }
+
+    /// Returns the time at which the snapshot was taken.
+    pub fn taken_at(&self) -> Instant {
+        self.taken_at
+    }
+
+    /// Returns the twist of the given frame relative to the body, based on the joint states of
+    /// the snapshot. See [MotionModel::velocity_of_frame_in_body].
+    ///
+    /// ## Parameters
+    ///
+    /// * 'frame_id' - The [FrameID] of the frame.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the frame is not part of the model.
+    pub fn velocity_of_frame_in_body(&self, frame_id: &FrameID) -> Result<Vector6<f64>, Error> {
+        self.model
+            .velocity_of_frame_in_body_with(frame_id, |id| self.joint_states.get(id).copied())
+    }
+
+    /// Returns the position of the joint of the given frame, or [None] if the joint has neither
+    /// an actuator nor a sensor.
+    fn joint_position(&self, frame_id: &FrameID) -> Option<f64> {
+        self.joint_states
+            .get(frame_id)
+            .map(|state| state.position())
+    }
+}
+
+impl MotionModel {
+    /// Returns a [ModelSnapshot] that contains the current state of all the joints of the model.
+    ///
+    /// The state of each joint is read once, when the snapshot is taken, so that all the queries
+    /// on the snapshot use the same joint states even if the hardware updates the joints in the
+    /// meantime.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::FailedToReadActuatorJointState] - Returned when the state of a joint could not
+    ///   be read.
+    pub fn snapshot(&self) -> Result<ModelSnapshot<'_>, Error> {
+        let mut joint_states = HashMap::new();
+        for frame in self.kinematic_tree().elements() {
+            let id = frame.id();
+            if self.is_actuated(id) || self.has_sensor(id) {
+                joint_states.insert(*id, self.joint_state_for(id)?);
+            }
+        }
+
+        Ok(ModelSnapshot {
+            model: self,
+            joint_states,
+            taken_at: Instant::now(),
+        })
+    }
+}
@@ -0,0 +1,131 @@
+use std::{f64::consts::PI, time::Duration};
+
+use nalgebra::Isometry3;
+
+use crate::{
+    hardware::joint_state::JointState, model_elements::frame_elements::FrameID,
+    test_fixtures::TestModule, Error,
+};
+
+#[test]
+fn when_taking_a_snapshot_it_should_match_the_current_state_of_the_model() {
+    let module = TestModule::new();
+    module
+        .steering_actuator
+        .send_state(JointState::new(0.25 * PI, Some(0.5), Some(-1.0), None));
+    std::thread::sleep(Duration::from_millis(50));
+
+    let snapshot = module.model.snapshot().unwrap();
+    assert_eq!(2, snapshot.joint_states().count());
+    assert_eq!(
+        Some(&JointState::new(0.25 * PI, Some(0.5), Some(-1.0), None)),
+        snapshot.joint_state(&module.steering)
+    );
+    assert_eq!(None, snapshot.joint_state(&module.suspension));
+
+    for frame in [
+        module.body,
+        module.suspension,
+        module.steering,
+        module.wheel,
+    ] {
+        let expected = module.model.homogeneous_transform_to_body(&frame).unwrap();
+        let actual = snapshot.homogeneous_transform_to_body(&frame).unwrap();
+        assert!((expected - actual).norm() < 1e-12);
+
+        let expected = module.model.isometry_to_world(&frame).unwrap();
+        let actual = snapshot.isometry_to_world(&frame).unwrap();
+        assert!((expected.to_homogeneous() - actual.to_homogeneous()).norm() < 1e-12);
+    }
+
+    let expected = module
+        .model
+        .homogeneous_transform_between_frames(&module.suspension, &module.wheel)
+        .unwrap();
+    let actual = snapshot
+        .homogeneous_transform_between_frames(&module.suspension, &module.wheel)
+        .unwrap();
+    assert!((expected - actual).norm() < 1e-12);
+
+    let expected = module.model.jacobian_for(&module.wheel).unwrap();
+    let actual = snapshot.jacobian_for(&module.wheel).unwrap();
+    assert_eq!(expected.joints(), actual.joints());
+    assert!((expected.matrix() - actual.matrix()).norm() < 1e-12);
+
+    let expected = module
+        .model
+        .velocity_of_frame_in_body(&module.wheel)
+        .unwrap();
+    let actual = snapshot.velocity_of_frame_in_body(&module.wheel).unwrap();
+    assert!((expected - actual).norm() < 1e-12);
+
+    let expected = module
+        .model
+        .acceleration_of_frame_in_body(&module.wheel)
+        .unwrap();
+    let actual = snapshot
+        .acceleration_of_frame_in_body(&module.wheel)
+        .unwrap();
+    assert!((expected - actual).norm() < 1e-12);
+}
+
+#[test]
+fn when_the_joints_move_after_taking_a_snapshot_it_should_keep_the_frozen_state() {
+    let mut module = TestModule::new();
+    module.steering_actuator.send_position(0.0);
+    std::thread::sleep(Duration::from_millis(50));
+
+    let before = module
+        .model
+        .homogeneous_transform_to_body(&module.wheel)
+        .unwrap();
+    {
+        let snapshot = module.model.snapshot().unwrap();
+
+        module.steering_actuator.send_position(0.5 * PI);
+        std::thread::sleep(Duration::from_millis(50));
+
+        let actual = snapshot
+            .homogeneous_transform_to_body(&module.wheel)
+            .unwrap();
+        assert!((before - actual).norm() < 1e-12);
+        assert_eq!(
+            Some(0.0),
+            snapshot
+                .joint_state(&module.steering)
+                .map(|state| state.position())
+        );
+
+        let moved = module
+            .model
+            .homogeneous_transform_to_body(&module.wheel)
+            .unwrap();
+        assert!((before - moved).norm() > 1e-3);
+    }
+
+    module
+        .model
+        .set_body_pose_in_world(Isometry3::translation(1.0, 2.0, 3.0));
+    let snapshot = module.model.snapshot().unwrap();
+    let expected = module.model.isometry_to_world(&module.wheel).unwrap();
+    let actual = snapshot.isometry_to_world(&module.wheel).unwrap();
+    assert!((expected.to_homogeneous() - actual.to_homogeneous()).norm() < 1e-12);
+    assert!(snapshot.taken_at() <= std::time::Instant::now());
+}
+
+#[test]
+fn when_querying_a_snapshot_for_an_unknown_frame_it_should_error() {
+    let module = TestModule::new();
+    let snapshot = module.model.snapshot().unwrap();
+
+    let id = FrameID::new();
+    assert_eq!(
+        Err(Error::MissingFrameElement { id }),
+        snapshot.isometry_to_body(&id)
+    );
+    assert_eq!(
+        Err(Error::MissingFrameElement { id }),
+        snapshot.isometry_between_frames(&module.wheel, &id)
+    );
+    assert!(snapshot.jacobian_for(&id).is_err());
+}
@@ -0,0 +1,147 @@
+//! Provides the functions used to evaluate the risk of the vehicle tipping over.
+//!
+//! The support polygon is the convex hull of the contact points of the wheels that are in
+//! contact with the ground. The vehicle is statically stable as long as the center of mass,
+//! projected along the direction of gravity, lies inside the support polygon. The distance
+//! between the projected center of mass and the closest edge of the support polygon is a
+//! measure of how close the vehicle is to tipping over.
+//!
+//! Unlike the margins in the [envelope](crate::envelope) module, which are computed for the
+//! joints in their zero position, the margins in this module use the current position of the
+//! joints, the current ground contact state of the wheels and the current direction of gravity
+//! relative to the body.
+
+use nalgebra::{Vector2, Vector3};
+
+use crate::{
+    envelope::convex_hull,
+    model_elements::{frame_elements::FrameID, model::MotionModel},
+    Error,
+};
+
+#[cfg(test)]
+#[path = "stability_tests.rs"]
+mod stability_tests;
+
+/// Describes how far the vehicle is from tipping over.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SupportMargin {
+    /// The distance between the projected center of mass and the closest edge of the support
+    /// polygon. Negative when the projected center of mass is outside the support polygon.
+    distance_to_edge: f64,
+
+    /// The wheels at the start and the end of the closest edge of the support polygon.
+    edge: (FrameID, FrameID),
+
+    /// The wheels that form the corners of the support polygon, in counter-clockwise order
+    /// when looking against the direction of gravity.
+    support_polygon: Vec<FrameID>,
+}
+
+impl SupportMargin {
+    /// Returns the distance, in meters, between the projected center of mass and the closest
+    /// edge of the support polygon. The distance is negative when the projected center of mass
+    /// is outside the support polygon.
+    pub fn distance_to_edge(&self) -> f64 {
+        self.distance_to_edge
+    }
+
+    /// Returns the wheels at the start and the end of the closest edge of the support polygon,
+    /// i.e. the edge over which the vehicle would tip.
+    pub fn edge(&self) -> (&FrameID, &FrameID) {
+        (&self.edge.0, &self.edge.1)
+    }
+
+    /// Returns a value indicating whether the projected center of mass lies inside the support
+    /// polygon.
+    pub fn is_stable(&self) -> bool {
+        self.distance_to_edge > 0.0
+    }
+
+    /// Returns the wheels that form the corners of the support polygon, in counter-clockwise
+    /// order when looking against the direction of gravity.
+    pub fn support_polygon(&self) -> &[FrameID] {
+        &self.support_polygon
+    }
+}
+
+impl MotionModel {
+    /// Returns the static stability margin of the vehicle, based on the current position of
+    /// the joints, the wheels that are in contact with the ground and the direction of gravity.
+    ///
+    /// The contact points of the wheels and the center of mass are projected along the
+    /// direction of gravity, see [MotionModel::gravity_in_body]. When no gravity is set the
+    /// points are projected along the z-axis of the body.
+    ///
+    /// Returns [None] when the wheels in contact with the ground do not form a support
+    /// polygon, e.g. because fewer than three wheels touch the ground.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the model has no body.
+    /// * [Error::MissingWheelRadius] - Returned when no properties have been set for one of the
+    ///   wheels that are in contact with the ground.
+    pub fn stability_margin(&self) -> Result<Option<SupportMargin>, Error> {
+        let gravity = self.gravity_in_body();
+        let down = if gravity.norm() > 0.0 {
+            gravity.normalize()
+        } else {
+            -Vector3::z()
+        };
+
+        // The axes of the plane perpendicular to gravity. For a level vehicle these are the
+        // x-axis and the y-axis of the body.
+        let reference = if down.x.abs() < 0.9 {
+            Vector3::x()
+        } else {
+            Vector3::y()
+        };
+        let u = (reference - down * down.dot(&reference)).normalize();
+        let v = u.cross(&down);
+        let project = |p: &Vector3<f64>| Vector2::new(p.dot(&u), p.dot(&v));
+
+        let mut contacts = Vec::new();
+        for wheel in self.wheels_in_contact()? {
+            let point = self.wheel_contact_point_in_body(wheel)?;
+            contacts.push((*wheel, project(&point)));
+        }
+
+        let points: Vec<Vector2<f64>> = contacts.iter().map(|(_, p)| *p).collect();
+        let hull = convex_hull(&points);
+        if hull.len() < 3 {
+            return Ok(None);
+        }
+
+        let support_polygon: Vec<FrameID> = hull
+            .iter()
+            .filter_map(|corner| {
+                contacts
+                    .iter()
+                    .find(|(_, p)| p == corner)
+                    .map(|(wheel, _)| *wheel)
+            })
+            .collect();
+
+        let center_of_mass = project(&self.center_of_mass_in_body()?);
+        let mut distance_to_edge = f64::INFINITY;
+        let mut edge = (support_polygon[0], support_polygon[1]);
+        for index in 0..hull.len() {
+            let next = (index + 1) % hull.len();
+            let direction = hull[next] - hull[index];
+
+            // Positive when the point is to the left of the edge, i.e. inside the polygon
+            let distance = direction.perp(&(center_of_mass - hull[index])) / direction.norm();
+            if distance < distance_to_edge {
+                distance_to_edge = distance;
+                edge = (support_polygon[index], support_polygon[next]);
+            }
+        }
+
+        Ok(Some(SupportMargin {
+            distance_to_edge,
+            edge,
+            support_polygon,
+        }))
+    }
+}
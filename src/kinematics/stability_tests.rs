@@ -0,0 +1,120 @@
+use nalgebra::{Isometry3, Translation3, UnitQuaternion, Vector3};
+
+use crate::{
+    model_elements::{
+        frame_elements::FrameID,
+        model::{MotionModel, WheelProperties},
+        module_spec::MotionModelBuilder,
+        templates::ModuleTemplate,
+    },
+    test_fixtures::physical_properties_with_mass,
+};
+
+// Creates a vehicle with the wheel contact points at (+-1.0, +-0.5, -0.2) and the center of
+// mass at (1/9, 1/18, 13/90)
+fn create_model() -> MotionModel {
+    let template = ModuleTemplate::new(
+        WheelProperties::new(0.1, 0.05, Vector3::zeros()),
+        physical_properties_with_mass(1.0, Vector3::zeros()),
+        physical_properties_with_mass(1.0, Vector3::zeros()),
+    )
+    .with_wheel_offset(Translation3::new(0.0, 0.0, -0.1));
+
+    MotionModelBuilder::rectangular_swerve(
+        4,
+        2.0,
+        1.0,
+        physical_properties_with_mass(10.0, Vector3::new(0.2, 0.1, 0.3)),
+        &template,
+    )
+    .unwrap()
+    .build()
+    .unwrap()
+}
+
+fn wheel_of(model: &MotionModel, module: &str) -> FrameID {
+    *model
+        .drive_modules()
+        .find(|m| m.name() == module)
+        .unwrap()
+        .frames()
+        .wheel()
+}
+
+#[test]
+fn when_getting_the_stability_margin_it_should_return_the_closest_edge() {
+    let model = create_model();
+
+    let margin = model.stability_margin().unwrap().unwrap();
+    assert!(margin.is_stable());
+    assert!((0.5 - 1.0 / 18.0 - margin.distance_to_edge()).abs() < 1e-12);
+    assert_eq!(
+        (
+            &wheel_of(&model, "front_left"),
+            &wheel_of(&model, "rear_left")
+        ),
+        margin.edge()
+    );
+
+    // The corners are ordered counter-clockwise
+    assert_eq!(
+        vec![
+            wheel_of(&model, "rear_right"),
+            wheel_of(&model, "front_right"),
+            wheel_of(&model, "front_left"),
+            wheel_of(&model, "rear_left"),
+        ],
+        margin.support_polygon()
+    );
+}
+
+#[test]
+fn when_getting_the_stability_margin_on_a_slope_it_should_project_along_gravity() {
+    let mut model = create_model();
+
+    // Rolling the body to the right moves the center of mass towards the right wheels
+    let angle: f64 = 0.3;
+    model.set_body_pose_in_world(Isometry3::from_parts(
+        Translation3::identity(),
+        UnitQuaternion::from_euler_angles(angle, 0.0, 0.0),
+    ));
+
+    let margin = model.stability_margin().unwrap().unwrap();
+    assert_eq!(
+        (
+            &wheel_of(&model, "rear_right"),
+            &wheel_of(&model, "front_right")
+        ),
+        margin.edge()
+    );
+
+    // The edge lies in the plane y = -0.5, z = -0.2 of the body
+    let center_of_mass = model.center_of_mass_in_body().unwrap();
+    let lateral = center_of_mass.y + 0.5;
+    let height = center_of_mass.z + 0.2;
+    let expected = lateral * angle.cos() - height * angle.sin();
+    assert!((expected - margin.distance_to_edge()).abs() < 1e-12);
+
+    // Tipping the vehicle past the tip-over angle makes it unstable
+    model.set_body_pose_in_world(Isometry3::from_parts(
+        Translation3::identity(),
+        UnitQuaternion::from_euler_angles(lateral.atan2(height) + 0.1, 0.0, 0.0),
+    ));
+    let margin = model.stability_margin().unwrap().unwrap();
+    assert!(!margin.is_stable());
+}
+
+#[test]
+fn when_getting_the_stability_margin_without_a_support_polygon_it_should_return_none() {
+    let mut model = MotionModel::new();
+    model
+        .add_body(
+            "body".to_string(),
+            Translation3::identity(),
+            UnitQuaternion::identity(),
+            physical_properties_with_mass(1.0, Vector3::zeros()),
+        )
+        .unwrap();
+
+    assert_eq!(Ok(None), model.stability_margin());
+}
@@ -0,0 +1,238 @@
+//! Provides the functions used to evaluate the steering configuration of the wheel modules.
+//!
+//! A set of steering angles is kinematically consistent when the axles of all the wheels
+//! intersect in a single point, the instantaneous centre of rotation (ICR). When the axles
+//! do not share a common ICR the wheels cannot all roll without sliding sideways, which
+//! causes wheel scrub.
+//!
+//! The module also provides the steering configurations that are commonly used while the
+//! vehicle is stationary. In the parallel configuration all the wheels point in the same
+//! direction. In the cross, or X-lock, configuration each wheel points towards the center of the
+//! wheels so that the rolling directions of the wheels form an X. Because no body motion is
+//! consistent with these rolling directions the vehicle resists being pushed or rotated.
+
+use std::{collections::HashMap, f64::consts::PI};
+
+use nalgebra::{DMatrix, Vector2};
+
+use crate::{
+    hardware::joint_state::JointState,
+    model_elements::{frame_elements::FrameID, model::MotionModel},
+    number_space::{shortest_angular_distance, wrap_angle},
+    Error,
+};
+
+use super::{
+    odometry::MINIMUM_NUMBER_OF_WHEEL_MEASUREMENTS, wheel_position_and_heading_in_body,
+    wheel_position_and_heading_in_body_at_zero_steering,
+};
+
+/// The distance, in meters, between a wheel and the center of the wheels below which the wheel
+/// is considered to be at the center.
+const MINIMUM_DISTANCE_TO_CENTER: f64 = 1e-9;
+
+#[cfg(test)]
+#[path = "steering_tests.rs"]
+mod steering_tests;
+
+impl MotionModel {
+    /// Returns the states for the steering joints that point all the wheels in the given
+    /// direction, stored by the [FrameID] of the steering frame.
+    ///
+    /// The positions of the steering joints are in the range (-π, π] and the velocities are
+    /// zero. The positions of the wheels are taken from the current configuration of the model,
+    /// with the steering joints at their zero position.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'heading' - The steering angle, in radians, for all the wheels, i.e. the angle around
+    ///   the body z-axis between the body x-axis and the rolling direction of the wheels.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the model has no wheels.
+    /// * [Error::NoSteeringFramesInChain] - Returned when a wheel has no steering frame.
+    pub fn parallel_steering_configuration(
+        &self,
+        heading: f64,
+    ) -> Result<HashMap<FrameID, JointState>, Error> {
+        let mut result = HashMap::with_capacity(self.number_of_wheels());
+        for wheel in self.wheels()? {
+            let steering_frame = *self.steering_frame_for_wheel(wheel)?;
+            let (_, zero_heading) =
+                wheel_position_and_heading_in_body_at_zero_steering(self, wheel, &steering_frame)?;
+
+            result.insert(
+                steering_frame,
+                JointState::new(wrap_angle(heading - zero_heading), Some(0.0), None, None),
+            );
+        }
+
+        Ok(result)
+    }
+
+    /// Returns the wheel scrub implied by the given steering angles.
+    ///
+    /// The scrub is computed by finding the planar body motion that is most consistent with the
+    /// rolling directions of the wheels and then calculating the root-mean-square lateral
+    /// sliding velocity of the wheels for that motion. The body motion is normalized so that
+    /// the metric is dimensionless. The angular velocity is scaled by the root-mean-square
+    /// distance between the wheels and the body origin.
+    ///
+    /// A value of 0.0 indicates that the wheel axles intersect in a common instantaneous centre
+    /// of rotation and the wheels can roll without scrubbing. Larger values indicate that the
+    /// wheels have to slide sideways. The maximum value is 1.0.
+    ///
+    /// The positions of the wheels are taken from the current configuration of the model.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'steering_angles' - The steering angle, in radians, for each wheel. The steering
+    ///   angle is the angle between the body x-axis and the rolling direction of the wheel.
+    ///   Wheels that are not in the collection are ignored.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when one of the wheels is not part of the model.
+    /// * [Error::InvalidFrameID] - Returned when one of the frames is not a wheel.
+    /// * [Error::InsufficientWheelMeasurements] - Returned when fewer than two steering angles
+    ///   are provided.
+    pub fn steering_scrub(&self, steering_angles: &HashMap<FrameID, f64>) -> Result<f64, Error> {
+        let mut wheels = Vec::with_capacity(steering_angles.len());
+        let model_wheels = self.wheels()?;
+        for (wheel, angle) in steering_angles {
+            self.reference_frame(wheel)?;
+            if !model_wheels.contains(&wheel) {
+                return Err(Error::InvalidFrameID { id: *wheel });
+            }
+
+            let (position, _) = wheel_position_and_heading_in_body(self, wheel)?;
+            wheels.push((position.x, position.y, *angle));
+        }
+
+        scrub_for_wheels(&wheels)
+    }
+
+    /// Returns the states for the steering joints that put the wheels in the cross, or X-lock,
+    /// configuration, stored by the [FrameID] of the steering frame.
+    ///
+    /// Each wheel is pointed along the line between the center of the wheels and the wheel. A
+    /// wheel rolls along that line in either direction, so of the two possible positions of the
+    /// steering joint the one closest to the current position is used. Wheels that are at the
+    /// center of the wheels are pointed along the body x-axis.
+    ///
+    /// The positions of the steering joints are in the range (-π, π] and the velocities are
+    /// zero. The positions of the wheels are taken from the current configuration of the model,
+    /// with the steering joints at their zero position.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the model has no wheels.
+    /// * [Error::NoSteeringFramesInChain] - Returned when a wheel has no steering frame.
+    pub fn x_lock_steering_configuration(&self) -> Result<HashMap<FrameID, JointState>, Error> {
+        let mut modules = Vec::with_capacity(self.number_of_wheels());
+        for wheel in self.wheels()? {
+            let steering_frame = *self.steering_frame_for_wheel(wheel)?;
+            let (position, zero_heading) =
+                wheel_position_and_heading_in_body_at_zero_steering(self, wheel, &steering_frame)?;
+            modules.push((steering_frame, position, zero_heading));
+        }
+
+        let center = modules
+            .iter()
+            .fold(Vector2::zeros(), |sum, (_, position, _)| sum + position)
+            / modules.len() as f64;
+
+        let mut result = HashMap::with_capacity(modules.len());
+        for (steering_frame, position, zero_heading) in modules {
+            let direction = position - center;
+            let heading = if direction.norm() < MINIMUM_DISTANCE_TO_CENTER {
+                0.0
+            } else {
+                direction.y.atan2(direction.x)
+            };
+
+            let current = self.current_joint_position(&steering_frame).unwrap_or(0.0);
+            let forward = wrap_angle(heading - zero_heading);
+            let reverse = wrap_angle(forward + PI);
+            let joint_position = if shortest_angular_distance(current, reverse).abs()
+                < shortest_angular_distance(current, forward).abs()
+            {
+                reverse
+            } else {
+                forward
+            };
+
+            result.insert(
+                steering_frame,
+                JointState::new(joint_position, Some(0.0), None, None),
+            );
+        }
+
+        Ok(result)
+    }
+
+    /// Returns the wheel scrub implied by the current steering angles of the wheels.
+    ///
+    /// See [MotionModel::steering_scrub] for a description of the metric.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the model has no wheels.
+    /// * [Error::InsufficientWheelMeasurements] - Returned when the model has fewer than two
+    ///   wheels.
+    pub fn current_steering_scrub(&self) -> Result<f64, Error> {
+        let mut wheels = Vec::with_capacity(self.number_of_wheels());
+        for wheel in self.wheels()? {
+            let (position, heading) = wheel_position_and_heading_in_body(self, wheel)?;
+            wheels.push((position.x, position.y, heading));
+        }
+
+        scrub_for_wheels(&wheels)
+    }
+}
+
+/// Returns the normalized root-mean-square lateral sliding velocity for the wheels, given
+/// as `(x, y, steering angle)`, for the body motion that minimizes the sliding.
+///
+/// Each wheel adds the rolling constraint `n · (v + ω × r) = 0`, where `n` is the lateral
+/// direction of the wheel and `r` is the position of the wheel. The smallest singular value
+/// of the constraint matrix is the smallest sliding velocity for a unit body motion.
+fn scrub_for_wheels(wheels: &[(f64, f64, f64)]) -> Result<f64, Error> {
+    let count = wheels.len();
+    if count < MINIMUM_NUMBER_OF_WHEEL_MEASUREMENTS {
+        return Err(Error::InsufficientWheelMeasurements {
+            required: MINIMUM_NUMBER_OF_WHEEL_MEASUREMENTS,
+            available: count,
+        });
+    }
+
+    // With fewer constraints than degrees of freedom there is always a body motion
+    // that satisfies all the constraints
+    if count < 3 {
+        return Ok(0.0);
+    }
+
+    let squared_distance_sum: f64 = wheels.iter().map(|(x, y, _)| x * x + y * y).sum();
+    let characteristic_length = if squared_distance_sum > 0.0 {
+        (squared_distance_sum / count as f64).sqrt()
+    } else {
+        1.0
+    };
+
+    let mut a = DMatrix::<f64>::zeros(count, 3);
+    for (row, (x, y, angle)) in wheels.iter().enumerate() {
+        let (sin, cos) = angle.sin_cos();
+        a[(row, 0)] = -sin;
+        a[(row, 1)] = cos;
+        a[(row, 2)] = (sin * y + cos * x) / characteristic_length;
+    }
+
+    let smallest_singular_value = a
+        .singular_values()
+        .iter()
+        .copied()
+        .fold(f64::INFINITY, f64::min);
+
+    Ok(smallest_singular_value / (count as f64).sqrt())
+}
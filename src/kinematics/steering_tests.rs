@@ -0,0 +1,297 @@
+use std::{collections::HashMap, f64::consts::PI, time::Duration};
+
+use nalgebra::{Translation3, UnitQuaternion};
+
+use crate::{
+    change_notification_processing::HardwareChangeProcessor,
+    model_elements::{
+        frame_elements::{Actuator, FrameID},
+        model::MotionModel,
+    },
+    number_space::NumberSpaceType,
+    test_fixtures::physical_properties,
+    test_support::MockActuator,
+    Error,
+};
+
+// A vehicle with 4 modules, placed at (±1.0, ±0.5). Each module consists of a steering frame
+// attached to the body and a wheel frame 0.1 below the steering frame. All frames have the
+// same orientation as the body.
+struct TestVehicle {
+    model: MotionModel,
+    module_positions: Vec<(f64, f64)>,
+    wheels: Vec<FrameID>,
+    steering_actuators: Vec<MockActuator>,
+    _wheel_actuators: Vec<MockActuator>,
+    _change_processor: Box<HardwareChangeProcessor>,
+}
+
+impl TestVehicle {
+    fn new() -> Self {
+        let change_processor = Box::new(HardwareChangeProcessor::new(1000));
+        let mut model = MotionModel::new();
+        let body = model
+            .add_body(
+                "body".to_string(),
+                Translation3::identity(),
+                UnitQuaternion::identity(),
+                physical_properties(),
+            )
+            .unwrap();
+
+        let module_positions = vec![(1.0, 0.5), (-1.0, 0.5), (-1.0, -0.5), (1.0, -0.5)];
+        let mut wheels = vec![];
+        let mut steering_actuators = vec![];
+        let mut wheel_actuators = vec![];
+        for (x, y) in module_positions.iter() {
+            let mut steering_hardware = MockActuator::new(NumberSpaceType::LinearUnlimited);
+            let steering_actuator =
+                Actuator::new(&mut steering_hardware, &change_processor).unwrap();
+            let steering = model
+                .add_steering_element(
+                    "steering".to_string(),
+                    body,
+                    Translation3::new(*x, *y, 0.0),
+                    UnitQuaternion::identity(),
+                    physical_properties(),
+                    steering_actuator,
+                )
+                .unwrap();
+
+            let mut wheel_hardware = MockActuator::new(NumberSpaceType::LinearUnlimited);
+            let wheel_actuator = Actuator::new(&mut wheel_hardware, &change_processor).unwrap();
+            let wheel = model
+                .add_wheel(
+                    "wheel".to_string(),
+                    steering,
+                    Translation3::new(0.0, 0.0, -0.1),
+                    UnitQuaternion::identity(),
+                    physical_properties(),
+                    wheel_actuator,
+                )
+                .unwrap();
+
+            wheels.push(wheel);
+            steering_actuators.push(steering_hardware);
+            wheel_actuators.push(wheel_hardware);
+        }
+
+        Self {
+            model,
+            module_positions,
+            wheels,
+            steering_actuators,
+            _wheel_actuators: wheel_actuators,
+            _change_processor: change_processor,
+        }
+    }
+
+    fn rotation_angles(&self) -> Vec<f64> {
+        self.module_positions
+            .iter()
+            .map(|(x, y)| x.atan2(-y))
+            .collect()
+    }
+
+    fn steering_angles(&self, angles: &[f64]) -> HashMap<FrameID, f64> {
+        self.wheels
+            .iter()
+            .zip(angles.iter())
+            .map(|(w, a)| (*w, *a))
+            .collect()
+    }
+}
+
+#[test]
+fn when_computing_scrub_with_parallel_wheels_it_should_return_zero() {
+    let vehicle = TestVehicle::new();
+    let angles = vehicle.steering_angles(&[0.3, 0.3, 0.3, 0.3]);
+
+    let scrub = vehicle.model.steering_scrub(&angles).unwrap();
+    assert!(scrub.abs() < 1e-9, "Expected no scrub but got {}", scrub);
+}
+
+#[test]
+fn when_computing_scrub_with_rotation_around_the_body_origin_it_should_return_zero() {
+    let vehicle = TestVehicle::new();
+    let angles = vehicle.steering_angles(&vehicle.rotation_angles());
+
+    let scrub = vehicle.model.steering_scrub(&angles).unwrap();
+    assert!(scrub.abs() < 1e-9, "Expected no scrub but got {}", scrub);
+}
+
+#[test]
+fn when_computing_scrub_with_opposing_wheels_it_should_return_a_positive_value() {
+    let vehicle = TestVehicle::new();
+    let small = vehicle
+        .model
+        .steering_scrub(&vehicle.steering_angles(&[0.1, 0.0, 0.0, 0.0]))
+        .unwrap();
+    let large = vehicle
+        .model
+        .steering_scrub(&vehicle.steering_angles(&[0.5 * PI, 0.0, 0.0, 0.5 * PI]))
+        .unwrap();
+
+    assert!(small > 0.0);
+    assert!(large > small);
+    assert!(large <= 1.0);
+}
+
+#[test]
+fn when_computing_scrub_with_two_wheels_it_should_return_zero() {
+    let vehicle = TestVehicle::new();
+    let mut angles = HashMap::new();
+    angles.insert(vehicle.wheels[0], 0.0);
+    angles.insert(vehicle.wheels[1], 0.5 * PI);
+
+    let scrub = vehicle.model.steering_scrub(&angles).unwrap();
+    assert_eq!(0.0, scrub);
+}
+
+#[test]
+fn when_computing_scrub_with_one_wheel_it_should_error() {
+    let vehicle = TestVehicle::new();
+    let mut angles = HashMap::new();
+    angles.insert(vehicle.wheels[0], 0.0);
+
+    let result = vehicle.model.steering_scrub(&angles);
+    assert_eq!(
+        Err(Error::InsufficientWheelMeasurements {
+            required: 2,
+            available: 1
+        }),
+        result
+    );
+}
+
+#[test]
+fn when_computing_scrub_with_a_frame_that_is_not_a_wheel_it_should_error() {
+    let vehicle = TestVehicle::new();
+    let mut angles = vehicle.steering_angles(&[0.0, 0.0, 0.0, 0.0]);
+    let body = *vehicle.model.body().unwrap();
+    angles.insert(body, 0.0);
+
+    let result = vehicle.model.steering_scrub(&angles);
+    assert_eq!(Err(Error::InvalidFrameID { id: body }), result);
+}
+
+#[test]
+fn when_computing_scrub_with_an_unknown_frame_it_should_error() {
+    let vehicle = TestVehicle::new();
+    let mut angles = vehicle.steering_angles(&[0.0, 0.0, 0.0, 0.0]);
+    angles.insert(FrameID::new(), 0.0);
+
+    let result = vehicle.model.steering_scrub(&angles);
+    assert!(result.is_err());
+}
+
+#[test]
+fn when_computing_current_scrub_it_should_use_the_steering_actuators() {
+    let vehicle = TestVehicle::new();
+    for (actuator, angle) in vehicle
+        .steering_actuators
+        .iter()
+        .zip(vehicle.rotation_angles())
+    {
+        actuator.send_position(angle);
+    }
+    std::thread::sleep(Duration::from_millis(50));
+
+    let consistent = vehicle.model.current_steering_scrub().unwrap();
+    assert!(
+        consistent.abs() < 1e-9,
+        "Expected no scrub but got {}",
+        consistent
+    );
+
+    vehicle.steering_actuators[0].send_position(0.0);
+    std::thread::sleep(Duration::from_millis(50));
+
+    let inconsistent = vehicle.model.current_steering_scrub().unwrap();
+    assert!(inconsistent > 0.0);
+}
+
+#[test]
+fn when_computing_the_parallel_configuration_it_should_point_all_wheels_at_the_heading() {
+    let vehicle = TestVehicle::new();
+    let configuration = vehicle.model.parallel_steering_configuration(0.3).unwrap();
+
+    assert_eq!(4, configuration.len());
+    for wheel in vehicle.wheels.iter() {
+        let steering = vehicle.model.steering_frame_for_wheel(wheel).unwrap();
+        let state = configuration.get(steering).unwrap();
+        assert!((0.3 - state.position()).abs() < 1e-9);
+        assert_eq!(&Some(0.0), state.velocity());
+    }
+
+    let angles: Vec<f64> = vehicle
+        .wheels
+        .iter()
+        .map(|w| {
+            let steering = vehicle.model.steering_frame_for_wheel(w).unwrap();
+            configuration.get(steering).unwrap().position()
+        })
+        .collect();
+    let scrub = vehicle
+        .model
+        .steering_scrub(&vehicle.steering_angles(&angles))
+        .unwrap();
+    assert!(scrub.abs() < 1e-9, "Expected no scrub but got {}", scrub);
+}
+
+#[test]
+fn when_computing_the_x_lock_configuration_it_should_point_the_wheels_at_the_center() {
+    let vehicle = TestVehicle::new();
+    let configuration = vehicle.model.x_lock_steering_configuration().unwrap();
+
+    // With all the steering joints at zero the joint positions closest to zero are used, which
+    // point the wheels either towards or away from the center.
+    assert_eq!(4, configuration.len());
+    for (wheel, (x, y)) in vehicle.wheels.iter().zip(vehicle.module_positions.iter()) {
+        let steering = vehicle.model.steering_frame_for_wheel(wheel).unwrap();
+        let state = configuration.get(steering).unwrap();
+        let expected = (y / x).atan();
+        assert!(
+            (expected - state.position()).abs() < 1e-9,
+            "Expected {} but got {}",
+            expected,
+            state.position()
+        );
+        assert_eq!(&Some(0.0), state.velocity());
+    }
+}
+
+#[test]
+fn when_computing_the_x_lock_configuration_it_should_use_the_closest_joint_position() {
+    let vehicle = TestVehicle::new();
+    for actuator in vehicle.steering_actuators.iter() {
+        actuator.send_position(PI - 0.1);
+    }
+    std::thread::sleep(Duration::from_millis(50));
+
+    let configuration = vehicle.model.x_lock_steering_configuration().unwrap();
+
+    // The rear left wheel is closest to its X-lock angle while the front left wheel is closest
+    // to the X-lock angle rotated by half a turn.
+    let front_left = vehicle
+        .model
+        .steering_frame_for_wheel(&vehicle.wheels[0])
+        .unwrap();
+    let expected = 0.5_f64.atan2(1.0) - PI;
+    assert!((expected - configuration.get(front_left).unwrap().position()).abs() < 1e-9);
+
+    let rear_left = vehicle
+        .model
+        .steering_frame_for_wheel(&vehicle.wheels[1])
+        .unwrap();
+    let expected = 0.5_f64.atan2(-1.0);
+    assert!((expected - configuration.get(rear_left).unwrap().position()).abs() < 1e-9);
+}
+
+#[test]
+fn when_computing_steering_configurations_without_wheels_it_should_error() {
+    let model = MotionModel::new();
+
+    assert!(model.parallel_steering_configuration(0.0).is_err());
+    assert!(model.x_lock_steering_configuration().is_err());
+}
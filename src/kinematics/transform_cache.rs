@@ -0,0 +1,216 @@
+//! Provides a cache for the transforms from the frames of the vehicle to the body.
+//!
+//! Computing the transform to the body for each frame separately repeats the work for the
+//! ancestors that the frames share. The [TransformCache] walks the kinematic tree once and
+//! stores the transform for each frame. When the state of an actuator changes only the
+//! transforms of the frames that are attached to the actuated frame are recomputed.
+
+use std::collections::{HashMap, VecDeque};
+
+use nalgebra::Matrix4;
+
+use crate::{
+    model_elements::{frame_elements::FrameID, model::MotionModel},
+    Error,
+};
+
+#[cfg(test)]
+#[path = "transform_cache_tests.rs"]
+mod transform_cache_tests;
+
+/// Stores the transforms from the frames of a [MotionModel] to the body frame.
+///
+/// The cache is brought up to date with the current state of the model each time it is
+/// queried. Changes to the structure of the model, as tracked by [MotionModel::revision],
+/// invalidate all the transforms. Changes to the state of an actuator only invalidate the
+/// transforms of the actuated frame and the frames that are attached to it.
+///
+/// A cache should only be used with a single model.
+#[derive(Clone, Debug, Default)]
+pub struct TransformCache {
+    /// The revision of the model for which the cache was built, or [None] if the cache is empty.
+    revision: Option<u64>,
+
+    /// The frames ordered from the body outwards, so that each frame is listed after its parent.
+    order: Vec<FrameID>,
+
+    /// The parent of each frame, except the body.
+    parents: HashMap<FrameID, FrameID>,
+
+    /// The direct children of each frame.
+    children: HashMap<FrameID, Vec<FrameID>>,
+
+    /// The state version and the position of each actuated joint at the time the transforms
+    /// were computed.
+    joint_states: HashMap<FrameID, (u64, f64)>,
+
+    /// The transforms to the body that are up to date.
+    transforms: HashMap<FrameID, Matrix4<f64>>,
+}
+
+impl TransformCache {
+    /// Clears the cache so that all transforms are recomputed on the next query.
+    pub fn invalidate(&mut self) {
+        self.revision = None;
+        self.transforms.clear();
+    }
+
+    /// Creates a new, empty, [TransformCache] instance.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the transform from the given frame to the body frame, based on the current
+    /// state of the model.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'model' - The model that contains the frame.
+    /// * 'frame_id' - The [FrameID] of the frame.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the frame is not part of the model.
+    pub fn transform_to_body(
+        &mut self,
+        model: &MotionModel,
+        frame_id: &FrameID,
+    ) -> Result<Matrix4<f64>, Error> {
+        match self.transforms_to_body(model)?.get(frame_id) {
+            Some(t) => Ok(*t),
+            None => Err(Error::MissingFrameElement { id: *frame_id }),
+        }
+    }
+
+    /// Returns the transforms from all the frames of the model to the body frame, based on the
+    /// current state of the model.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'model' - The model for which the transforms should be returned.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the model has no body.
+    pub fn transforms_to_body(
+        &mut self,
+        model: &MotionModel,
+    ) -> Result<&HashMap<FrameID, Matrix4<f64>>, Error> {
+        if self.revision != Some(model.revision()) {
+            self.rebuild(model)?;
+        }
+
+        self.invalidate_moved_joints(model);
+
+        for frame in self.order.iter() {
+            if self.transforms.contains_key(frame) {
+                continue;
+            }
+
+            let transform = match self.parents.get(frame) {
+                Some(parent) => {
+                    let to_parent =
+                        model.homogeneous_transform_to_ancestor_with(frame, parent, |id| {
+                            self.joint_states.get(id).map(|(_, position)| *position)
+                        })?;
+                    let parent_to_body = match self.transforms.get(parent) {
+                        Some(t) => t,
+                        None => {
+                            // The frames are ordered so that the parent is always computed first
+                            return Err(Error::InternalInconsistency {
+                                context: format!(
+                                    "the transform for parent {} of frame {} was not computed",
+                                    parent, frame
+                                ),
+                            });
+                        }
+                    };
+                    parent_to_body * to_parent
+                }
+                None => Matrix4::identity(),
+            };
+
+            self.transforms.insert(*frame, transform);
+        }
+
+        Ok(&self.transforms)
+    }
+
+    /// Removes the transforms for the frames that are moved by a joint for which the position
+    /// has changed since the transforms were computed.
+    fn invalidate_moved_joints(&mut self, model: &MotionModel) {
+        let mut moved = Vec::new();
+        for frame in self.order.iter() {
+            let version = match model.joint_state_version(frame) {
+                Some(v) => v,
+                None => continue,
+            };
+
+            let cached = self.joint_states.get(frame).copied();
+            if cached.map(|(v, _)| v) == Some(version) {
+                continue;
+            }
+
+            let position = model.current_joint_position(frame).unwrap_or(0.0);
+            self.joint_states.insert(*frame, (version, position));
+            if cached.map(|(_, p)| p) != Some(position) {
+                moved.push(*frame);
+            }
+        }
+
+        let mut queue: VecDeque<FrameID> = moved.into_iter().collect();
+        while let Some(frame) = queue.pop_front() {
+            if self.transforms.remove(&frame).is_none() {
+                // Already invalidated together with the rest of its subtree
+                continue;
+            }
+
+            if let Some(children) = self.children.get(&frame) {
+                queue.extend(children.iter());
+            }
+        }
+    }
+
+    /// Rebuilds the structure of the cache from the model and removes all the transforms.
+    fn rebuild(&mut self, model: &MotionModel) -> Result<(), Error> {
+        self.order.clear();
+        self.parents.clear();
+        self.children.clear();
+        self.joint_states.clear();
+        self.transforms.clear();
+
+        let body = *model.body()?;
+        let mut queue = VecDeque::from([body]);
+        while let Some(frame) = queue.pop_front() {
+            let children: Vec<FrameID> = model.children_of(&frame)?.into_iter().copied().collect();
+            for child in children.iter() {
+                self.parents.insert(*child, frame);
+                queue.push_back(*child);
+            }
+
+            self.children.insert(frame, children);
+            self.order.push(frame);
+        }
+
+        self.revision = Some(model.revision());
+        Ok(())
+    }
+}
+
+impl MotionModel {
+    /// Returns the transforms from all the frames of the model to the body frame, taking into
+    /// account the current position of the joints.
+    ///
+    /// The kinematic tree is walked once so that the transforms of the ancestors that the frames
+    /// share are only computed once. Use a [TransformCache] to also reuse the transforms between
+    /// calls.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the model has no body.
+    pub fn transforms_to_body_all(&self) -> Result<HashMap<FrameID, Matrix4<f64>>, Error> {
+        let mut cache = TransformCache::new();
+        cache.transforms_to_body(self)?;
+        Ok(cache.transforms)
+    }
+}
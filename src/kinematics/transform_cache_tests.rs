@@ -0,0 +1,281 @@
+use std::{f64::consts::PI, time::Duration};
+
+use nalgebra::{Matrix4, Translation3, UnitQuaternion};
+
+use crate::{
+    change_notification_processing::HardwareChangeProcessor,
+    hardware::{
+        joint_state::{JointState, JointStateRange},
+        simulation::SimulatedSensor,
+    },
+    model_elements::{
+        frame_elements::{Actuator, FrameDofType, FrameID, JointConstraint, JointSensor},
+        model::MotionModel,
+    },
+    number_space::NumberSpaceType,
+    test_fixtures::physical_properties,
+    test_support::MockActuator,
+    Error,
+};
+
+use super::TransformCache;
+
+// A vehicle with 2 modules, placed at (1.0, ±0.5). Each module consists of a suspension frame
+// attached to the body, a steering frame 0.1 below the suspension frame and a wheel frame 0.1
+// below the steering frame.
+struct TestVehicle {
+    model: MotionModel,
+    suspensions: Vec<FrameID>,
+    steering_frames: Vec<FrameID>,
+    wheels: Vec<FrameID>,
+    steering_actuators: Vec<MockActuator>,
+    _wheel_actuators: Vec<MockActuator>,
+    _change_processor: Box<HardwareChangeProcessor>,
+}
+
+impl TestVehicle {
+    fn new() -> Self {
+        let change_processor = Box::new(HardwareChangeProcessor::new(1000));
+        let mut model = MotionModel::new();
+        let body = model
+            .add_body(
+                "body".to_string(),
+                Translation3::identity(),
+                UnitQuaternion::identity(),
+                physical_properties(),
+            )
+            .unwrap();
+
+        let mut suspensions = vec![];
+        let mut steering_frames = vec![];
+        let mut wheels = vec![];
+        let mut steering_actuators = vec![];
+        let mut wheel_actuators = vec![];
+        for y in [0.5, -0.5] {
+            let suspension = model
+                .add_suspension_element(
+                    "suspension".to_string(),
+                    FrameDofType::PrismaticZ,
+                    body,
+                    Translation3::new(1.0, y, 0.0),
+                    UnitQuaternion::from_euler_angles(0.0, 0.0, PI / 6.0),
+                    physical_properties(),
+                    JointConstraint::new(),
+                )
+                .unwrap();
+
+            let mut steering_hardware = MockActuator::new(NumberSpaceType::LinearUnlimited);
+            let steering = model
+                .add_steering_element(
+                    "steering".to_string(),
+                    suspension,
+                    Translation3::new(0.0, 0.0, -0.1),
+                    UnitQuaternion::identity(),
+                    physical_properties(),
+                    Actuator::new(&mut steering_hardware, &change_processor).unwrap(),
+                )
+                .unwrap();
+
+            let mut wheel_hardware = MockActuator::new(NumberSpaceType::LinearUnlimited);
+            let wheel = model
+                .add_wheel(
+                    "wheel".to_string(),
+                    steering,
+                    Translation3::new(0.05, 0.0, -0.1),
+                    UnitQuaternion::identity(),
+                    physical_properties(),
+                    Actuator::new(&mut wheel_hardware, &change_processor).unwrap(),
+                )
+                .unwrap();
+
+            suspensions.push(suspension);
+            steering_frames.push(steering);
+            wheels.push(wheel);
+            steering_actuators.push(steering_hardware);
+            wheel_actuators.push(wheel_hardware);
+        }
+
+        Self {
+            model,
+            suspensions,
+            steering_frames,
+            wheels,
+            steering_actuators,
+            _wheel_actuators: wheel_actuators,
+            _change_processor: change_processor,
+        }
+    }
+
+    fn frames(&self) -> Vec<FrameID> {
+        let mut frames = vec![*self.model.body().unwrap()];
+        frames.extend(self.suspensions.iter());
+        frames.extend(self.steering_frames.iter());
+        frames.extend(self.wheels.iter());
+        frames
+    }
+}
+
+fn assert_transforms_match_model(vehicle: &TestVehicle, cache: &mut TransformCache) {
+    for frame in vehicle.frames() {
+        let expected = vehicle.model.homogeneous_transform_to_body(&frame).unwrap();
+        let actual = cache.transform_to_body(&vehicle.model, &frame).unwrap();
+        assert!(
+            (expected - actual).norm() < 1e-12,
+            "Transform for frame {} was {} but expected {}",
+            frame,
+            actual,
+            expected
+        );
+    }
+}
+
+#[test]
+fn when_getting_all_transforms_to_body_it_should_match_the_individual_transforms() {
+    let vehicle = TestVehicle::new();
+
+    vehicle.steering_actuators[0].send_position(0.5 * PI);
+    std::thread::sleep(Duration::from_millis(50));
+
+    let transforms = vehicle.model.transforms_to_body_all().unwrap();
+    assert_eq!(vehicle.frames().len(), transforms.len());
+    for frame in vehicle.frames() {
+        let expected = vehicle.model.homogeneous_transform_to_body(&frame).unwrap();
+        assert!((expected - transforms[&frame]).norm() < 1e-12);
+    }
+
+    assert_eq!(
+        Matrix4::identity(),
+        transforms[vehicle.model.body().unwrap()]
+    );
+}
+
+#[test]
+fn when_a_joint_moves_it_should_update_the_cached_transforms() {
+    let vehicle = TestVehicle::new();
+    let mut cache = TransformCache::new();
+    assert_transforms_match_model(&vehicle, &mut cache);
+
+    vehicle.steering_actuators[1].send_position(-0.25 * PI);
+    std::thread::sleep(Duration::from_millis(50));
+
+    assert_transforms_match_model(&vehicle, &mut cache);
+}
+
+#[test]
+fn when_a_joint_moves_it_should_only_invalidate_the_attached_frames() {
+    let vehicle = TestVehicle::new();
+    let mut cache = TransformCache::new();
+    cache.transforms_to_body(&vehicle.model).unwrap();
+
+    vehicle.steering_actuators[0].send_position(0.5 * PI);
+    std::thread::sleep(Duration::from_millis(50));
+
+    cache.invalidate_moved_joints(&vehicle.model);
+    assert!(!cache.transforms.contains_key(&vehicle.steering_frames[0]));
+    assert!(!cache.transforms.contains_key(&vehicle.wheels[0]));
+    assert!(cache.transforms.contains_key(&vehicle.suspensions[0]));
+    assert!(cache.transforms.contains_key(&vehicle.steering_frames[1]));
+    assert!(cache.transforms.contains_key(&vehicle.wheels[1]));
+
+    // An update with the same position does not invalidate anything
+    cache.transforms_to_body(&vehicle.model).unwrap();
+    vehicle.steering_actuators[0].send_position(0.5 * PI);
+    std::thread::sleep(Duration::from_millis(50));
+
+    cache.invalidate_moved_joints(&vehicle.model);
+    assert_eq!(vehicle.frames().len(), cache.transforms.len());
+}
+
+#[test]
+fn when_the_model_structure_changes_it_should_rebuild_the_cache() {
+    let mut vehicle = TestVehicle::new();
+    let mut cache = TransformCache::new();
+    assert_transforms_match_model(&vehicle, &mut cache);
+
+    vehicle
+        .model
+        .set_transform_to_parent(
+            &vehicle.suspensions[0],
+            Translation3::new(1.2, 0.6, 0.0),
+            UnitQuaternion::identity(),
+        )
+        .unwrap();
+
+    assert_transforms_match_model(&vehicle, &mut cache);
+
+    cache.invalidate();
+    assert_transforms_match_model(&vehicle, &mut cache);
+}
+
+#[test]
+fn when_getting_the_cached_transform_for_an_unknown_frame_it_should_error() {
+    let vehicle = TestVehicle::new();
+    let mut cache = TransformCache::new();
+
+    let id = FrameID::new();
+    assert_eq!(
+        Err(Error::MissingFrameElement { id }),
+        cache.transform_to_body(&vehicle.model, &id)
+    );
+
+    let empty_model = MotionModel::new();
+    assert!(TransformCache::new()
+        .transforms_to_body(&empty_model)
+        .is_err());
+}
+
+#[test]
+fn when_a_joint_with_only_a_sensor_moves_it_should_update_the_cached_transforms() {
+    let processor = HardwareChangeProcessor::manual();
+    let mut model = MotionModel::new();
+    let body = model
+        .add_body(
+            "body".to_string(),
+            Translation3::identity(),
+            UnitQuaternion::identity(),
+            physical_properties(),
+        )
+        .unwrap();
+    let suspension = model
+        .add_suspension_element(
+            "suspension".to_string(),
+            FrameDofType::PrismaticZ,
+            body,
+            Translation3::identity(),
+            UnitQuaternion::identity(),
+            physical_properties(),
+            JointConstraint::new(),
+        )
+        .unwrap();
+
+    let mut hardware = SimulatedSensor::new(
+        NumberSpaceType::LinearUnlimited,
+        JointStateRange::new(
+            JointState::new(-1.0, None, None, None),
+            JointState::new(1.0, None, None, None),
+        ),
+        JointState::new(0.25, None, None, None),
+    );
+    model
+        .add_joint_sensor(
+            &suspension,
+            JointSensor::new(&mut hardware, &processor).unwrap(),
+        )
+        .unwrap();
+
+    let mut cache = TransformCache::new();
+    assert_eq!(
+        0.0,
+        cache.transform_to_body(&model, &suspension).unwrap()[(2, 3)]
+    );
+
+    hardware.step();
+    processor.process_pending();
+
+    let expected = model.homogeneous_transform_to_body(&suspension).unwrap();
+    assert_eq!(0.25, expected[(2, 3)]);
+    assert_eq!(
+        expected,
+        cache.transform_to_body(&model, &suspension).unwrap()
+    );
+}
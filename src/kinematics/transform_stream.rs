@@ -0,0 +1,191 @@
+//! Provides a rate limited stream of the transforms of the frames of the vehicle.
+//!
+//! Loggers and visualizers generally want to sample the pose of each frame at a steady rate.
+//! The ticks of a [TransformStream] are generated by a periodic task on the
+//! [HardwareChangeProcessor] that also stores the joint states in the model. On each tick the
+//! stream returns the transforms from all the frames to the body based on the joint states that
+//! the processor had stored at that time.
+
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use crossbeam_channel::{Receiver, TrySendError};
+use nalgebra::Matrix4;
+
+use crate::{
+    change_notification_processing::HardwareChangeProcessor,
+    model_elements::{frame_elements::FrameID, model::MotionModel},
+    Error,
+};
+
+use super::transform_cache::TransformCache;
+
+#[cfg(test)]
+#[path = "transform_stream_tests.rs"]
+mod transform_stream_tests;
+
+/// Stores the transforms of the frames of a [MotionModel] at a given moment in time.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TransformSample {
+    /// The time at which the sample was taken, measured from the moment the stream was created.
+    timestamp: Duration,
+
+    /// The transforms from each frame to the body frame.
+    transforms: HashMap<FrameID, Matrix4<f64>>,
+}
+
+impl TransformSample {
+    /// Returns the time at which the sample was taken, measured from the moment the stream was
+    /// created.
+    pub fn timestamp(&self) -> Duration {
+        self.timestamp
+    }
+
+    /// Returns the transform from the given frame to the body frame, or [None] if the frame
+    /// is not part of the sample.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'frame_id' - The [FrameID] of the frame.
+    pub fn transform_to_body(&self, frame_id: &FrameID) -> Option<&Matrix4<f64>> {
+        self.transforms.get(frame_id)
+    }
+
+    /// Returns the transforms from each frame to the body frame.
+    pub fn transforms(&self) -> &HashMap<FrameID, Matrix4<f64>> {
+        &self.transforms
+    }
+}
+
+/// An iterator that returns the transforms of all the frames of a [MotionModel] at a fixed
+/// rate.
+///
+/// Each call to [Iterator::next] blocks until the next tick of the stream. When the consumer
+/// falls behind the ticks that were missed are dropped, so that the stream never returns a
+/// burst of samples to catch up. The stream never ends, use [Iterator::take] or a similar
+/// adapter to limit the number of samples.
+///
+/// The ticks are generated by the [HardwareChangeProcessor], so the stream never returns
+/// samples faster than the processing rate of the processor and it doesn't return samples
+/// while the processor is paused. For a processor created with
+/// [HardwareChangeProcessor::manual] the ticks are only generated when
+/// [HardwareChangeProcessor::process_pending] is called.
+///
+/// The transforms are computed with a [TransformCache] so that only the transforms of the
+/// frames that moved since the previous sample are recomputed.
+pub struct TransformStream<'a> {
+    /// The model from which the transforms are read.
+    model: &'a MotionModel,
+
+    /// The cache that stores the transforms between samples.
+    cache: TransformCache,
+
+    /// The moment at which the stream was created.
+    start: Instant,
+
+    /// The receiver that delivers the ticks of the stream from the change processor.
+    ticker: Receiver<Instant>,
+}
+
+impl<'a> TransformStream<'a> {
+    /// Creates a new [TransformStream] instance.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'model' - The model from which the transforms are read.
+    /// * 'change_processor' - The processor that stores the joint states in the model and
+    ///   generates the ticks of the stream.
+    /// * 'rate_in_hz' - The rate at which samples are returned.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::InvalidStreamRate] - Returned when the rate is not a finite, positive number.
+    pub fn new(
+        model: &'a MotionModel,
+        change_processor: &HardwareChangeProcessor,
+        rate_in_hz: f64,
+    ) -> Result<Self, Error> {
+        if !rate_in_hz.is_finite() || rate_in_hz <= 0.0 {
+            return Err(Error::InvalidStreamRate { rate: rate_in_hz });
+        }
+
+        // Very small rates lead to periods that don't fit in a Duration and very large rates
+        // lead to periods that are rounded down to zero.
+        let period_in_seconds = 1.0 / rate_in_hz;
+        if period_in_seconds >= u64::MAX as f64
+            || Duration::from_secs_f64(period_in_seconds).is_zero()
+        {
+            return Err(Error::InvalidStreamRate { rate: rate_in_hz });
+        }
+
+        let period = Duration::from_secs_f64(period_in_seconds);
+
+        // The channel holds a single tick so that the ticks are dropped while the consumer is
+        // behind. The task ends once the stream has been dropped.
+        let (sender, ticker) = crossbeam_channel::bounded(1);
+        let start = Instant::now();
+        let mut next_tick = start;
+        change_processor.add_periodic(Box::new(move || {
+            let now = Instant::now();
+            if now < next_tick {
+                return true;
+            }
+
+            // The processor may run the task late, so the next tick is measured from this tick
+            // to keep the ticks at least one period apart.
+            next_tick = now + period;
+
+            !matches!(sender.try_send(now), Err(TrySendError::Disconnected(_)))
+        }));
+
+        Ok(Self {
+            model,
+            cache: TransformCache::new(),
+            start,
+            ticker,
+        })
+    }
+}
+
+impl Iterator for TransformStream<'_> {
+    type Item = Result<TransformSample, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // The sending side only disconnects when the change processor is dropped.
+        let tick = self.ticker.recv().ok()?;
+
+        let transforms = match self.cache.transforms_to_body(self.model) {
+            Ok(t) => t.clone(),
+            Err(e) => return Some(Err(e)),
+        };
+
+        Some(Ok(TransformSample {
+            timestamp: tick.saturating_duration_since(self.start),
+            transforms,
+        }))
+    }
+}
+
+impl MotionModel {
+    /// Returns a [TransformStream] that returns the transforms of all the frames of the model
+    /// at the given rate.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'change_processor' - The processor that stores the joint states in the model and
+    ///   generates the ticks of the stream.
+    /// * 'rate_in_hz' - The rate at which samples are returned.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::InvalidStreamRate] - Returned when the rate is not a finite, positive number.
+    pub fn transform_stream(
+        &self,
+        change_processor: &HardwareChangeProcessor,
+        rate_in_hz: f64,
+    ) -> Result<TransformStream<'_>, Error> {
+        TransformStream::new(self, change_processor, rate_in_hz)
+    }
+}
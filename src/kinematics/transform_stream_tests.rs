@@ -0,0 +1,186 @@
+use std::{f64::consts::PI, time::Duration};
+
+use nalgebra::{Translation3, UnitQuaternion};
+
+use crate::{
+    change_notification_processing::HardwareChangeProcessor,
+    model_elements::{
+        frame_elements::{Actuator, FrameID},
+        model::MotionModel,
+    },
+    number_space::NumberSpaceType,
+    test_fixtures::physical_properties,
+    test_support::MockActuator,
+    Error,
+};
+
+use super::TransformStream;
+
+// A vehicle with a single module that consists of a steering frame attached to the body at
+// (1.0, 0.5, 0.0) and a wheel frame 0.1 below the steering frame.
+struct TestVehicle {
+    model: MotionModel,
+    steering: FrameID,
+    wheel: FrameID,
+    steering_actuator: MockActuator,
+    _wheel_actuator: MockActuator,
+    change_processor: Box<HardwareChangeProcessor>,
+}
+
+impl TestVehicle {
+    fn new() -> Self {
+        let change_processor = Box::new(HardwareChangeProcessor::new(1000));
+        let mut model = MotionModel::new();
+        let body = model
+            .add_body(
+                "body".to_string(),
+                Translation3::identity(),
+                UnitQuaternion::identity(),
+                physical_properties(),
+            )
+            .unwrap();
+
+        let mut steering_actuator = MockActuator::new(NumberSpaceType::LinearUnlimited);
+        let steering = model
+            .add_steering_element(
+                "steering".to_string(),
+                body,
+                Translation3::new(1.0, 0.5, 0.0),
+                UnitQuaternion::identity(),
+                physical_properties(),
+                Actuator::new(&mut steering_actuator, &change_processor).unwrap(),
+            )
+            .unwrap();
+
+        let mut wheel_actuator = MockActuator::new(NumberSpaceType::LinearUnlimited);
+        let wheel = model
+            .add_wheel(
+                "wheel".to_string(),
+                steering,
+                Translation3::new(0.0, 0.0, -0.1),
+                UnitQuaternion::identity(),
+                physical_properties(),
+                Actuator::new(&mut wheel_actuator, &change_processor).unwrap(),
+            )
+            .unwrap();
+
+        Self {
+            model,
+            steering,
+            wheel,
+            steering_actuator,
+            _wheel_actuator: wheel_actuator,
+            change_processor,
+        }
+    }
+}
+
+#[test]
+fn when_creating_a_stream_with_an_invalid_rate_it_should_error() {
+    let vehicle = TestVehicle::new();
+    for rate in [0.0, -10.0, f64::NAN, f64::INFINITY, 1e-300, 1e300] {
+        let result = TransformStream::new(&vehicle.model, &vehicle.change_processor, rate);
+        assert!(matches!(result, Err(Error::InvalidStreamRate { .. })));
+    }
+}
+
+#[test]
+fn when_streaming_it_should_return_the_transforms_of_all_frames() {
+    let vehicle = TestVehicle::new();
+    let mut stream = vehicle
+        .model
+        .transform_stream(&vehicle.change_processor, 100.0)
+        .unwrap();
+
+    let sample = stream.next().unwrap().unwrap();
+    assert_eq!(3, sample.transforms().len());
+    for frame in [
+        *vehicle.model.body().unwrap(),
+        vehicle.steering,
+        vehicle.wheel,
+    ] {
+        let expected = vehicle.model.homogeneous_transform_to_body(&frame).unwrap();
+        let actual = sample.transform_to_body(&frame).unwrap();
+        assert!((expected - actual).norm() < 1e-12);
+    }
+}
+
+#[test]
+fn when_streaming_it_should_return_samples_at_the_given_rate() {
+    let vehicle = TestVehicle::new();
+    let stream = vehicle
+        .model
+        .transform_stream(&vehicle.change_processor, 100.0)
+        .unwrap();
+
+    let timestamps: Vec<Duration> = stream
+        .take(5)
+        .map(|sample| sample.unwrap().timestamp())
+        .collect();
+
+    for pair in timestamps.windows(2) {
+        let interval = pair[1] - pair[0];
+        assert!(
+            interval >= Duration::from_millis(9),
+            "Samples were {:?} apart",
+            interval
+        );
+    }
+
+    assert!(timestamps[4] >= Duration::from_millis(40));
+}
+
+#[test]
+fn when_a_joint_moves_between_samples_it_should_return_the_updated_transforms() {
+    let vehicle = TestVehicle::new();
+    let mut stream = vehicle
+        .model
+        .transform_stream(&vehicle.change_processor, 100.0)
+        .unwrap();
+
+    let before = stream.next().unwrap().unwrap();
+    assert!((before.transform_to_body(&vehicle.wheel).unwrap()[(0, 0)] - 1.0).abs() < 1e-12);
+
+    vehicle.steering_actuator.send_position(0.5 * PI);
+    std::thread::sleep(Duration::from_millis(50));
+
+    let after = stream.next().unwrap().unwrap();
+    assert!(after.timestamp() > before.timestamp());
+
+    let expected = vehicle
+        .model
+        .homogeneous_transform_to_body(&vehicle.wheel)
+        .unwrap();
+    let actual = after.transform_to_body(&vehicle.wheel).unwrap();
+    assert!((expected - actual).norm() < 1e-12);
+    assert!(actual[(0, 0)].abs() < 1e-12);
+}
+
+#[test]
+fn when_the_change_processor_runs_it_should_generate_the_ticks_of_the_stream() {
+    let change_processor = HardwareChangeProcessor::manual();
+    let mut model = MotionModel::new();
+    let body = model
+        .add_body(
+            "body".to_string(),
+            Translation3::identity(),
+            UnitQuaternion::identity(),
+            physical_properties(),
+        )
+        .unwrap();
+
+    let mut stream = model.transform_stream(&change_processor, 1000.0).unwrap();
+    assert!(stream.ticker.is_empty());
+
+    change_processor.process_pending();
+    assert_eq!(1, stream.ticker.len());
+
+    // The consumer is behind, so the second tick is dropped
+    std::thread::sleep(Duration::from_millis(5));
+    change_processor.process_pending();
+    assert_eq!(1, stream.ticker.len());
+
+    let sample = stream.next().unwrap().unwrap();
+    assert!(sample.transform_to_body(&body).is_some());
+    assert!(stream.ticker.is_empty());
+}
@@ -0,0 +1,261 @@
+//! Provides the reachable region of the contact point of a wheel, i.e. the workspace of the
+//! chain of joints between the body and the wheel.
+//!
+//! The workspace is sampled by moving every joint in the chain between the body and the wheel
+//! through its range of motion, as given by the position limits of the
+//! [JointConstraint](crate::model_elements::frame_elements::JointConstraint) of the joint.
+//! Revolute joints without position limits are sampled over a full rotation. The rotation of the
+//! wheel around its axle does not move the contact point and is not sampled.
+//!
+//! The bounding box of the sampled contact points can be compared with the geometry of the
+//! chassis to check that a module cannot collide with the chassis anywhere in its travel.
+//!
+//! # Examples
+//!
+//! ```
+//! use nalgebra::{Matrix3, Translation3, UnitQuaternion, Vector3};
+//! use swerve_vehicle_descriptors::model_elements::frame_elements::{FrameDofType, JointConstraint};
+//! use swerve_vehicle_descriptors::model_elements::model::{
+//!     ChassisElementPhysicalProperties, MotionModel, WheelProperties,
+//! };
+//! use swerve_vehicle_descriptors::model_elements::module_spec::ModuleSpec;
+//!
+//! let properties =
+//!     ChassisElementPhysicalProperties::from_mass_properties(1.0, Vector3::zeros(), Matrix3::identity());
+//! let mut model = MotionModel::new();
+//! model
+//!     .add_body(
+//!         "body".to_string(),
+//!         Translation3::identity(),
+//!         UnitQuaternion::identity(),
+//!         properties,
+//!     )
+//!     .unwrap();
+//! let frames = model
+//!     .add_drive_module(
+//!         ModuleSpec::new(
+//!             "front_left".to_string(),
+//!             Translation3::new(1.0, 0.5, 0.0),
+//!             UnitQuaternion::identity(),
+//!             WheelProperties::new(0.1, 0.05, Vector3::zeros()),
+//!             properties,
+//!             properties,
+//!         )
+//!         .with_suspension(
+//!             FrameDofType::PrismaticZ,
+//!             properties,
+//!             JointConstraint::new().with_position_limits(-0.05, 0.05),
+//!         )
+//!         .with_wheel_offset(Translation3::new(0.0, 0.0, -0.1)),
+//!     )
+//!     .unwrap();
+//!
+//! let workspace = model.wheel_workspace(frames.wheel(), 5).unwrap();
+//! let (minimum, maximum) = workspace.bounding_box();
+//!
+//! // The contact point is 0.2 m below the module and moves up and down with the suspension
+//! assert!((minimum.z + 0.25).abs() < 1e-12);
+//! assert!((maximum.z + 0.15).abs() < 1e-12);
+//! ```
+
+use std::{collections::HashMap, f64::consts::PI};
+
+use nalgebra::{Point3, Vector3};
+
+use crate::{
+    model_elements::{frame_elements::FrameID, model::MotionModel},
+    Error,
+};
+
+#[cfg(test)]
+#[path = "workspace_tests.rs"]
+mod workspace_tests;
+
+/// The smallest number of samples that is taken for each joint.
+const MINIMUM_SAMPLES_PER_JOINT: usize = 2;
+
+/// The sampled region that the contact point of a wheel can reach relative to the body.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Workspace {
+    /// The wheel for which the workspace was computed.
+    wheel: FrameID,
+
+    /// The sampled positions of the contact point in the body reference frame.
+    points: Vec<Vector3<f64>>,
+
+    /// The corner of the bounding box with the smallest coordinates.
+    minimum: Vector3<f64>,
+
+    /// The corner of the bounding box with the largest coordinates.
+    maximum: Vector3<f64>,
+}
+
+impl Workspace {
+    /// Returns the corners of the axis-aligned bounding box, in the body reference frame, of
+    /// the sampled contact points as the corner with the smallest coordinates followed by the
+    /// corner with the largest coordinates.
+    pub fn bounding_box(&self) -> (Vector3<f64>, Vector3<f64>) {
+        (self.minimum, self.maximum)
+    }
+
+    /// Returns a value indicating whether the given point, in the body reference frame, lies
+    /// inside the bounding box of the workspace.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'point' - The point in the body reference frame.
+    pub fn contains(&self, point: &Vector3<f64>) -> bool {
+        (0..3).all(|i| point[i] >= self.minimum[i] && point[i] <= self.maximum[i])
+    }
+
+    /// Returns the sampled positions of the contact point in the body reference frame.
+    pub fn points(&self) -> &[Vector3<f64>] {
+        &self.points
+    }
+
+    /// Returns the [FrameID] of the wheel for which the workspace was computed.
+    pub fn wheel(&self) -> &FrameID {
+        &self.wheel
+    }
+}
+
+impl MotionModel {
+    /// Returns the region that the contact point of the given wheel can reach relative to the
+    /// body when the joints between the body and the wheel move through their range of motion.
+    ///
+    /// Each joint is sampled at evenly spaced positions between its position limits, including
+    /// the limits. Revolute joints without position limits are sampled over a full rotation.
+    /// The workspace contains one point for each combination of the joint samples, so the
+    /// number of points grows exponentially with the number of joints in the chain.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'wheel_frame' - The [FrameID] of the wheel.
+    /// * 'samples_per_joint' - The number of positions at which each joint is sampled. At least
+    ///   two positions are sampled.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the wheel is not part of the model.
+    /// * [Error::InvalidFrameID] - Returned when the frame is not a wheel.
+    /// * [Error::MissingWheelRadius] - Returned when no radius has been set for the wheel.
+    /// * [Error::MissingJointLimits] - Returned when a prismatic joint in the chain has no
+    ///   position limits.
+    pub fn wheel_workspace(
+        &self,
+        wheel_frame: &FrameID,
+        samples_per_joint: usize,
+    ) -> Result<Workspace, Error> {
+        let contact_point = Point3::from(self.wheel_properties(wheel_frame)?.contact_point());
+        let body = *self.body()?;
+
+        // The positions at which each of the joints between the body and the wheel is sampled
+        let mut joints = Vec::new();
+        for frame in self.chain_to_body(wheel_frame)? {
+            if frame == wheel_frame {
+                continue;
+            }
+
+            if let Some(positions) = self.sampled_joint_positions(frame, samples_per_joint)? {
+                joints.push((*frame, positions));
+            }
+        }
+
+        let number_of_points = number_of_configurations(&joints);
+        let mut points = Vec::with_capacity(number_of_points);
+        let mut configuration: HashMap<FrameID, f64> = HashMap::with_capacity(joints.len());
+        for index in 0..number_of_points {
+            set_configuration(&joints, index, &mut configuration);
+
+            // The wheel itself, and any joint outside the chain, is kept at its zero position
+            let wheel_to_body = self.isometry_to_ancestor_with(wheel_frame, &body, |id| {
+                Some(configuration.get(id).copied().unwrap_or(0.0))
+            })?;
+            points.push((wheel_to_body * contact_point).coords);
+        }
+
+        let mut minimum = Vector3::repeat(f64::INFINITY);
+        let mut maximum = Vector3::repeat(f64::NEG_INFINITY);
+        for point in &points {
+            minimum = minimum.inf(point);
+            maximum = maximum.sup(point);
+        }
+
+        Ok(Workspace {
+            wheel: *wheel_frame,
+            points,
+            minimum,
+            maximum,
+        })
+    }
+
+    /// Returns the evenly spaced positions at which the joint of the given frame is sampled,
+    /// or [None] if the frame does not move relative to its parent.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'frame_id' - The [FrameID] of the joint.
+    /// * 'samples_per_joint' - The number of positions at which the joint is sampled. At least
+    ///   two positions are sampled.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the frame is not part of the model.
+    /// * [Error::MissingJointLimits] - Returned when the joint is prismatic and has no position
+    ///   limits.
+    pub(crate) fn sampled_joint_positions(
+        &self,
+        frame_id: &FrameID,
+        samples_per_joint: usize,
+    ) -> Result<Option<Vec<f64>>, Error> {
+        let dof = self.frame_degree_of_freedom(frame_id)?;
+        if dof.axis().is_none() {
+            return Ok(None);
+        }
+
+        let samples = samples_per_joint.max(MINIMUM_SAMPLES_PER_JOINT);
+        let positions = match self.joint_constraint(frame_id)?.position_limits() {
+            Some((minimum, maximum)) => (0..samples)
+                .map(|i| minimum + (maximum - minimum) * i as f64 / (samples - 1) as f64)
+                .collect(),
+            None if dof.is_revolute() => (0..samples)
+                .map(|i| -PI + 2.0 * PI * i as f64 / samples as f64)
+                .collect(),
+            None => return Err(Error::MissingJointLimits { id: *frame_id }),
+        };
+        Ok(Some(positions))
+    }
+}
+
+/// Returns the number of combinations of the sampled positions of the given joints.
+///
+/// ## Parameters
+///
+/// * 'joints' - The joints together with the positions at which each joint is sampled.
+pub(crate) fn number_of_configurations(joints: &[(FrameID, Vec<f64>)]) -> usize {
+    joints
+        .iter()
+        .map(|(_, positions)| positions.len())
+        .product()
+}
+
+/// Stores the positions of the combination with the given index of the sampled positions of the
+/// given joints in the configuration.
+///
+/// ## Parameters
+///
+/// * 'joints' - The joints together with the positions at which each joint is sampled.
+/// * 'index' - The index of the combination, smaller than [number_of_configurations].
+/// * 'configuration' - The map that receives the position of each joint.
+pub(crate) fn set_configuration(
+    joints: &[(FrameID, Vec<f64>)],
+    index: usize,
+    configuration: &mut HashMap<FrameID, f64>,
+) {
+    // Decode the index of the combination into the index of the sample for each joint
+    let mut remainder = index;
+    for (frame, positions) in joints {
+        configuration.insert(*frame, positions[remainder % positions.len()]);
+        remainder /= positions.len();
+    }
+}
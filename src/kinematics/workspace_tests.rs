@@ -0,0 +1,83 @@
+use nalgebra::{Translation3, UnitQuaternion, Vector3};
+
+use crate::{
+    model_elements::{
+        frame_elements::{FrameDofType, JointConstraint},
+        model::{MotionModel, WheelProperties},
+        module_spec::{ModuleFrames, ModuleSpec},
+    },
+    test_fixtures::physical_properties,
+    Error,
+};
+
+fn create_model(suspension_constraint: JointConstraint) -> (MotionModel, ModuleFrames) {
+    let properties = physical_properties();
+    let mut model = MotionModel::new();
+    model
+        .add_body(
+            "body".to_string(),
+            Translation3::identity(),
+            UnitQuaternion::identity(),
+            properties,
+        )
+        .unwrap();
+
+    // The wheel is offset sideways from the steering axis so that steering moves the contact point
+    let frames = model
+        .add_drive_module(
+            ModuleSpec::new(
+                "front_left".to_string(),
+                Translation3::new(1.0, 0.5, 0.0),
+                UnitQuaternion::identity(),
+                WheelProperties::new(0.1, 0.05, Vector3::zeros()),
+                properties,
+                properties,
+            )
+            .with_suspension(FrameDofType::PrismaticZ, properties, suspension_constraint)
+            .with_wheel_offset(Translation3::new(0.0, 0.05, -0.1)),
+        )
+        .unwrap();
+    (model, frames)
+}
+
+#[test]
+fn when_computing_the_workspace_it_should_sample_the_range_of_each_joint() {
+    let (model, frames) = create_model(JointConstraint::new().with_position_limits(-0.05, 0.05));
+
+    let workspace = model.wheel_workspace(frames.wheel(), 4).unwrap();
+    assert_eq!(frames.wheel(), workspace.wheel());
+    assert_eq!(4 * 4, workspace.points().len());
+
+    // The steering joint has no limits and is sampled over a full rotation
+    let (minimum, maximum) = workspace.bounding_box();
+    assert!((minimum - Vector3::new(0.95, 0.45, -0.25)).norm() < 1e-12);
+    assert!((maximum - Vector3::new(1.05, 0.55, -0.15)).norm() < 1e-12);
+
+    assert!(workspace.contains(&Vector3::new(1.0, 0.5, -0.2)));
+    assert!(!workspace.contains(&Vector3::new(1.0, 0.5, -0.1)));
+
+    // The limits of the suspension are always sampled
+    let workspace = model.wheel_workspace(frames.wheel(), 0).unwrap();
+    assert_eq!(2 * 2, workspace.points().len());
+    let (minimum, maximum) = workspace.bounding_box();
+    assert!((minimum.z + 0.25).abs() < 1e-12);
+    assert!((maximum.z + 0.15).abs() < 1e-12);
+}
+
+#[test]
+fn when_computing_the_workspace_it_should_require_limits_for_prismatic_joints() {
+    let (model, frames) = create_model(JointConstraint::new());
+    assert_eq!(
+        Err(Error::MissingJointLimits {
+            id: *frames.suspension().unwrap()
+        }),
+        model.wheel_workspace(frames.wheel(), 4)
+    );
+
+    assert_eq!(
+        Err(Error::InvalidFrameID {
+            id: *frames.steering()
+        }),
+        model.wheel_workspace(frames.steering(), 4)
+    );
+}
@@ -29,7 +29,7 @@
 //! use crossbeam_channel::{ Receiver, Sender };
 //! use nalgebra::{ Matrix3, Matrix4, Matrix6, Translation3, UnitQuaternion, Vector3 };
 //! use swerve_vehicle_descriptors::Error;
-//! use swerve_vehicle_descriptors::change_notification_processing::{ ChangeID, HardwareChangeProcessor };
+//! use swerve_vehicle_descriptors::change_notification_processing::{ ChangeID, ChangeNotifier, HardwareChangeProcessor };
 //! use swerve_vehicle_descriptors::hardware::actuator_interface::{ HardwareActuator, ActuatorAvailableRatesOfChange };
 //! use swerve_vehicle_descriptors::hardware::joint_state::{ JointState, JointStateRange };
 //! use swerve_vehicle_descriptors::number_space::NumberSpaceType;
@@ -68,7 +68,7 @@
 //!     receiver: Receiver<(JointState, ActuatorAvailableRatesOfChange)>,
 //!     sender: Sender<(JointState, ActuatorAvailableRatesOfChange)>,
 //!     command_sender: Sender<JointState>,
-//!     update_sender: Option<Sender<ChangeID>>,
+//!     update_sender: Option<ChangeNotifier>,
 //!     id: Option<ChangeID>,
 //! }
 //!
@@ -87,7 +87,7 @@
 //!         Ok(self.command_sender.clone())
 //!     }
 //!
-//!     fn on_change(&mut self, id: ChangeID, sender: Sender<ChangeID>) {
+//!     fn on_change(&mut self, id: ChangeID, sender: ChangeNotifier) {
 //!         self.id = Some(id);
 //!         self.update_sender = Some(sender);
 //!     }
@@ -274,7 +274,7 @@
 //! ```
 //! use crossbeam_channel::{Receiver, Sender};
 //! use swerve_vehicle_descriptors::Error;
-//! use swerve_vehicle_descriptors::change_notification_processing::ChangeID;
+//! use swerve_vehicle_descriptors::change_notification_processing::{ChangeID, ChangeNotifier};
 //! use swerve_vehicle_descriptors::hardware::sensor_interface::HardwareSensor;
 //! use swerve_vehicle_descriptors::hardware::joint_state::{JointState, JointStateRange};
 //! use swerve_vehicle_descriptors::number_space::NumberSpaceType;
@@ -287,7 +287,7 @@
 //!      // the sender. And then also notify that the state has changed through
 //!      // the 'update_sender'
 //!      sender: Sender<JointState>,
-//!      update_sender: Option<Sender<ChangeID>>,
+//!      update_sender: Option<ChangeNotifier>,
 //!      id: Option<ChangeID>,
 //! }
 //!
@@ -346,7 +346,7 @@
 //!         JointStateRange::new(minimum, maximum)
 //!     }
 //!
-//!     fn on_change(&mut self, id: ChangeID, notifier: Sender<ChangeID>) {
+//!     fn on_change(&mut self, id: ChangeID, notifier: ChangeNotifier) {
 //!         self.id = Some(id);
 //!         self.update_sender = Some(notifier);
 //!     }
@@ -366,7 +366,7 @@
 //! ```
 //! use crossbeam_channel::{Receiver, Sender};
 //! use swerve_vehicle_descriptors::Error;
-//! use swerve_vehicle_descriptors::change_notification_processing::ChangeID;
+//! use swerve_vehicle_descriptors::change_notification_processing::{ChangeID, ChangeNotifier};
 //! use swerve_vehicle_descriptors::hardware::actuator_interface::{ HardwareActuator, ActuatorAvailableRatesOfChange };
 //! use swerve_vehicle_descriptors::hardware::joint_state::{JointState, JointStateRange};
 //! use swerve_vehicle_descriptors::number_space::NumberSpaceType;
@@ -379,7 +379,7 @@
 //!     // the sender. And then also notify that the state has changed through
 //!     // the 'update_sender'
 //!     sender: Sender<(JointState, ActuatorAvailableRatesOfChange)>,
-//!     update_sender: Option<Sender<ChangeID>>,
+//!     update_sender: Option<ChangeNotifier>,
 //!     id: Option<ChangeID>,
 //!     // The channel sender used to send the desired state to the hardware
 //!     command_sender: Sender<JointState>,
@@ -459,26 +459,101 @@
 //!         Ok(self.receiver.clone())
 //!     }
 //!
-//!     fn on_change(&mut self, id: ChangeID, notifier: Sender<ChangeID>) {
+//!     fn on_change(&mut self, id: ChangeID, notifier: ChangeNotifier) {
 //!         self.id = Some(id);
 //!         self.update_sender = Some(notifier);
 //!     }
 //! }
 //! ```
 
-use model_elements::frame_elements::FrameID;
+use model_elements::{
+    frame_elements::{FrameDofType, FrameID},
+    stable_id::StableFrameID,
+};
+use nalgebra::Vector3;
 use thiserror::Error;
 
+#[cfg(test)]
+#[path = "lib_tests.rs"]
+mod lib_tests;
+
+#[cfg(feature = "binary")]
+pub mod binary;
 pub mod change_notification_processing;
+pub mod dot;
+pub mod envelope;
 pub mod hardware;
+mod instrumentation;
+pub mod kinematics;
 pub mod number_space;
+#[cfg(feature = "ros2")]
+pub mod ros2;
+pub mod sdf;
+#[cfg(feature = "soak")]
+pub mod soak;
+pub mod stable;
+#[cfg(test)]
+mod test_fixtures;
+#[cfg(any(test, feature = "test_support"))]
+pub mod test_support;
+pub mod test_vectors;
+pub mod units;
+pub mod urdf;
 
 pub mod model_elements;
 
 /// Defines the different errors for the swerve model crate.
-#[derive(Debug, Error, PartialEq)]
+///
+/// Errors that are caused by another error, e.g. an [Error::Io] or an [Error::Channel], keep the
+/// original error as their [source](std::error::Error::source). Use [Error::kind] to find the
+/// kind of an error without matching on its data.
+#[derive(Debug, Error)]
 #[non_exhaustive]
 pub enum Error {
+    /// Indicates that the background thread of a
+    /// [HardwareChangeProcessor](change_notification_processing::HardwareChangeProcessor)
+    /// panicked, e.g. because one of its tasks panicked.
+    #[error("The background thread of the hardware change processor panicked.")]
+    ChangeProcessorFailed,
+
+    /// Indicates that a value could not be sent through a channel, e.g. because the hardware
+    /// closed its end of the channel or the channel is full.
+    #[error("{context}")]
+    Channel {
+        /// The description of the operation that failed.
+        context: String,
+        /// The error that was returned by the channel.
+        #[source]
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+
+    /// Indicates that a command for an actuator was vetoed by one of the command middleware.
+    #[error("The actuator command was vetoed: {reason}")]
+    CommandVetoed {
+        /// The reason why the command was vetoed.
+        reason: String,
+    },
+
+    /// Indicates that an alias was added to a frame element while the alias already refers to
+    /// another frame element.
+    ///
+    /// * 'alias' - The alias that is already in use.
+    #[error("The alias {alias} already refers to another frame element.")]
+    DuplicateFrameAlias {
+        /// The alias that is already in use.
+        alias: String,
+    },
+
+    /// Indicates that a stable ID was assigned to a frame element while another frame element
+    /// already has the same stable ID.
+    ///
+    /// * 'id' - The stable ID that is already in use.
+    #[error("The stable frame ID {id} is already assigned to another frame element.")]
+    DuplicateStableFrameID {
+        /// The stable ID that is already in use.
+        id: StableFrameID,
+    },
+
     /// Indicates that we failed to compute the transformation between two reference frames.
     #[error("Failed to compute the transform between {from:?} and {to:?}")]
     FailedToComputeTransform {
@@ -492,10 +567,6 @@ pub enum Error {
     #[error("Failed to read the joint state for the given actuator.")]
     FailedToReadActuatorJointState,
 
-    /// Indicates that we failed to set a joint state for a given actuator.
-    #[error("Failed to set the joint state for the given actuator.")]
-    FailedToSetActuatorJointState,
-
     /// Indicates that a user tried to add a frame element to a model or kinematic tree that
     /// already contains a frame element with the same ID.
     ///
@@ -506,6 +577,50 @@ pub enum Error {
         id: FrameID,
     },
 
+    /// Indicates that a sensor was provided for a frame element with a number space that does
+    /// not match the degree of freedom of the frame element, e.g. an angular sensor for a
+    /// prismatic joint.
+    #[error("The number space of the sensor does not match the degree of freedom of the frame element with id {id:?}.")]
+    IncompatibleNumberSpace {
+        /// The ID of the frame element.
+        id: FrameID,
+    },
+
+    /// Indicates that there were not enough wheel measurements available to estimate the
+    /// motion of the body.
+    #[error("Unable to estimate the body motion. Needed {required} wheel measurements but only {available} were available.")]
+    InsufficientWheelMeasurements {
+        /// The number of wheel measurements that are required.
+        required: usize,
+        /// The number of wheel measurements that were available.
+        available: usize,
+    },
+
+    /// Indicates that the internal data of the model is no longer consistent, e.g. a frame
+    /// element refers to a parent that is not stored in the model. This points to a bug in the
+    /// crate. The error is returned instead of panicking so that the process that controls the
+    /// vehicle can respond to the problem.
+    #[error("The model is in an inconsistent state: {context}")]
+    InternalInconsistency {
+        /// The description of the inconsistency.
+        context: String,
+    },
+
+    /// Indicates that the binary format of a model could not be written or read, e.g. because
+    /// the data is corrupted.
+    #[error("The binary model is not valid: {reason}")]
+    InvalidBinaryModel {
+        /// The reason why the binary model is not valid.
+        reason: String,
+    },
+
+    /// Indicates that a collision shape was provided that is not valid, e.g. a sphere with a
+    /// negative radius.
+    #[error(
+        "The collision shape is not valid. All dimensions should be finite, positive numbers."
+    )]
+    InvalidCollisionShape,
+
     /// Indicates that a frame element or frame ID was provided that is not valid, e.g.
     /// not stored in the collection.
     #[error("The frame element with id {id:?} is not a valid element for the operation.")]
@@ -514,6 +629,139 @@ pub enum Error {
         id: FrameID,
     },
 
+    /// Indicates that a joint calibration was provided that is not valid, e.g. it has a scale
+    /// of zero.
+    #[error("The joint calibration with offset {offset} and scale {scale} is not valid. Both should be finite and the scale should not be zero.")]
+    InvalidJointCalibration {
+        /// The offset of the calibration.
+        offset: f64,
+
+        /// The scale of the calibration.
+        scale: f64,
+    },
+
+    /// Indicates that a joint profile was provided that is not valid, e.g. it has no points or
+    /// the times of the points are not strictly increasing.
+    #[error("The joint profile is not valid. It should have at least one point, with finite states and strictly increasing times.")]
+    InvalidJointProfile,
+
+    /// Indicates that a joint transmission was provided that is not valid, e.g. it has a gear
+    /// ratio of zero.
+    #[error("The joint transmission with gear ratio {gear_ratio} is not valid. The gear ratio should be a finite, positive number.")]
+    InvalidJointTransmission {
+        /// The gear ratio of the transmission.
+        gear_ratio: f64,
+    },
+
+    /// Indicates that a kinematics table was provided that is not valid, e.g. it has fewer than
+    /// two samples or the joint values are not strictly increasing.
+    #[error("The kinematics table is not valid. It should have at least two samples with finite, strictly increasing joint values.")]
+    InvalidKinematicsTable,
+
+    /// Indicates that a loop closure constraint was provided that is not valid, e.g. it
+    /// connects a frame to itself or it is not part of the model.
+    #[error("The loop closure is not valid. It should connect two different frames of the model and a distance constraint should have a finite, positive length.")]
+    InvalidLoopClosure,
+
+    /// Indicates that a material was provided that is not valid, e.g. a component of its color
+    /// is larger than one.
+    #[error("The material {name} is not valid. The color components should be between 0 and 1.")]
+    InvalidMaterial {
+        /// The name of the material.
+        name: String,
+    },
+
+    /// Indicates that the specification of a wheel module is not valid.
+    #[error("The specification of the module {name} is not valid: {reason}")]
+    InvalidModuleSpecification {
+        /// The name of the module.
+        name: String,
+        /// The reason why the specification is not valid.
+        reason: String,
+    },
+
+    /// Indicates that a rolling radius was provided for a wheel that is not valid, e.g. it is
+    /// zero or negative.
+    #[error("The rolling radius {radius} is not valid. It should be a finite, positive number.")]
+    InvalidRollingRadius {
+        /// The rolling radius that was provided.
+        radius: f64,
+    },
+
+    /// Indicates that a ROS message could not be applied to the model, e.g. because it refers to
+    /// a joint that is not part of the model.
+    #[error("The ROS message is not valid: {reason}")]
+    InvalidRosMessage {
+        /// The reason why the message is not valid.
+        reason: String,
+    },
+
+    /// Indicates that a text could not be parsed as a [StableFrameID].
+    #[error("The value {value} is not a valid stable frame ID.")]
+    InvalidStableFrameID {
+        /// The text that could not be parsed.
+        value: String,
+    },
+
+    /// Indicates that a steering frame was requested with a degree of freedom that does not
+    /// rotate the wheel, e.g. a prismatic degree of freedom.
+    #[error("The degree of freedom {degree_of_freedom:?} is not valid for a steering frame. Steering frames should have a revolute degree of freedom.")]
+    InvalidSteeringDegreeOfFreedom {
+        /// The degree of freedom that was requested.
+        degree_of_freedom: FrameDofType,
+    },
+
+    /// Indicates that a stream was requested with a rate that is not a finite, positive number.
+    #[error("The stream rate {rate} is not valid. It should be a finite, positive number of samples per second.")]
+    InvalidStreamRate {
+        /// The requested rate in Hz.
+        rate: f64,
+    },
+
+    /// Indicates that a suspension spring was provided that is not valid, e.g. it has a
+    /// stiffness of zero.
+    #[error("The suspension spring with stiffness {stiffness}, damping {damping} and preload {preload} is not valid. The stiffness should be a finite, positive number and the damping and preload should be finite, non-negative numbers.")]
+    InvalidSuspensionSpring {
+        /// The stiffness of the spring.
+        stiffness: f64,
+
+        /// The damping coefficient of the damper.
+        damping: f64,
+
+        /// The preload of the spring.
+        preload: f64,
+    },
+
+    /// Indicates that the dimensions that were provided for a vehicle template are not valid.
+    #[error("The dimensions of the template are not valid: {reason}")]
+    InvalidTemplate {
+        /// The reason why the dimensions are not valid.
+        reason: String,
+    },
+
+    /// Indicates that data could not be read or written.
+    #[error("{context}")]
+    Io {
+        /// The description of the operation that failed.
+        context: String,
+        /// The error that was returned by the reader or writer.
+        #[source]
+        source: std::io::Error,
+    },
+
+    /// Indicates that a value of a joint state is outside of the limits of the joint.
+    #[error("The joint {quantity} {value} is outside of the limits [{minimum}, {maximum}].")]
+    JointLimitExceeded {
+        /// The name of the value that is outside of the limits, e.g. 'position'.
+        quantity: String,
+        /// The value.
+        value: f64,
+        /// The minimum allowed value.
+        minimum: f64,
+        /// The maximum allowed value.
+        maximum: f64,
+    },
+
     /// Indicates that a frame element with a given ID was expected to exist, but it did not.
     #[error("Expected a frame element with id {id:?} to be present, but it was not.")]
     MissingFrameElement {
@@ -521,6 +769,64 @@ pub enum Error {
         id: FrameID,
     },
 
+    /// Indicates that an orientation of the inertial measurement unit was provided, but no frame
+    /// of the model is registered as the inertial measurement unit.
+    #[error("No frame is registered as the inertial measurement unit.")]
+    MissingImuFrame,
+
+    /// Indicates that a joint has no position limits where the range of motion of the joint is
+    /// needed.
+    #[error("The joint with id {id:?} has no position limits.")]
+    MissingJointLimits {
+        /// The ID of the frame element of the joint.
+        id: FrameID,
+    },
+
+    /// Indicates that the state of a joint at a given time is not available, e.g. because the
+    /// time is before the oldest state in the history of the joint.
+    #[error("The history of the joint with id {id:?} does not contain the requested time.")]
+    MissingJointStateHistory {
+        /// The ID of the frame element of the joint.
+        id: FrameID,
+    },
+
+    /// Indicates that an operating mode was requested that is not known to the model.
+    #[error("The operating mode {name} is not known.")]
+    MissingOperatingMode {
+        /// The name of the operating mode.
+        name: String,
+    },
+
+    /// Indicates that a parameter was requested that is not part of a
+    /// [ParameterSet](crate::model_elements::parameters::ParameterSet).
+    #[error("The parameter {name} is not known.")]
+    MissingParameter {
+        /// The name of the parameter.
+        name: String,
+    },
+
+    /// Indicates that a wheel radius was expected to be known for a wheel, but it was not.
+    #[error("Expected the radius of the wheel with id {id:?} to be known, but it was not.")]
+    MissingWheelRadius {
+        /// The ID of the wheel frame element.
+        id: FrameID,
+    },
+
+    /// Indicates that a model could not be built from its specifications. Contains all the
+    /// errors that were found, rather than only the first one.
+    #[error("The model could not be built because of {} error(s): {errors:?}", .errors.len())]
+    ModelBuildFailed {
+        /// The errors that were found while building the model.
+        errors: Vec<Error>,
+    },
+
+    /// Indicates that a serialized model could not be migrated to the current schema version.
+    #[error("The model could not be migrated: {reason}")]
+    ModelMigrationFailed {
+        /// The reason why the migration failed.
+        reason: String,
+    },
+
     /// Indicates that there already is a frame in the chain of frame elements that is
     /// a steering frame.
     ///
@@ -541,4 +847,464 @@ pub enum Error {
         /// The ID of the parent frame below which the frame is being added.
         id: FrameID,
     },
+
+    /// Indicates that a joint state with a position, velocity, acceleration or jerk that is not
+    /// finite was reported while the audit mode of the model is enabled.
+    #[error("The state of the joint with id {id:?} contains a value that is not finite.")]
+    NonFiniteJointState {
+        /// The ID of the frame element of the joint.
+        id: FrameID,
+    },
+
+    /// Indicates that an orientation that is not normalized was provided while the audit mode of
+    /// the model is enabled.
+    #[error("The orientation has a norm of {norm}. Orientations should be unit quaternions.")]
+    NonUnitOrientation {
+        /// The norm of the quaternion of the orientation.
+        norm: f64,
+    },
+
+    /// Indicates that a steering frame or a wheel does not rotate around the axis that is
+    /// expected by the conventions of the crate while the audit mode of the model is enabled.
+    #[error("The frame {name} rotates around {axis:?}. Steering frames should rotate around the z-axis and wheels around the y-axis of their parent.")]
+    UnexpectedRotationAxis {
+        /// The name of the frame.
+        name: String,
+        /// The axis around which the frame rotates.
+        axis: Vector3<f64>,
+    },
+
+    /// Indicates that a serialized model has a schema version that cannot be loaded, e.g.
+    /// because it was written by a newer version of the crate.
+    #[error("The schema version {version} of the model is not supported.")]
+    UnsupportedSchemaVersion {
+        /// The schema version.
+        version: u32,
+    },
+}
+
+/// Defines the kind of an [error](enum@Error) without the data that describes the failure.
+///
+/// An [ErrorKind] can be copied and compared, e.g. to decide how to respond to an error or to
+/// check an error in a test without having to construct its source.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// The kind of an [Error::ChangeProcessorFailed].
+    ChangeProcessorFailed,
+
+    /// The kind of an [Error::Channel].
+    Channel,
+
+    /// The kind of an [Error::CommandVetoed].
+    CommandVetoed,
+
+    /// The kind of an [Error::DuplicateFrameAlias].
+    DuplicateFrameAlias,
+
+    /// The kind of an [Error::DuplicateStableFrameID].
+    DuplicateStableFrameID,
+
+    /// The kind of an [Error::FailedToComputeTransform].
+    FailedToComputeTransform,
+
+    /// The kind of an [Error::FailedToReadActuatorJointState].
+    FailedToReadActuatorJointState,
+
+    /// The kind of an [Error::FrameElementAlreadyExists].
+    FrameElementAlreadyExists,
+
+    /// The kind of an [Error::IncompatibleNumberSpace].
+    IncompatibleNumberSpace,
+
+    /// The kind of an [Error::InsufficientWheelMeasurements].
+    InsufficientWheelMeasurements,
+
+    /// The kind of an [Error::InternalInconsistency].
+    InternalInconsistency,
+
+    /// The kind of an [Error::InvalidBinaryModel].
+    InvalidBinaryModel,
+
+    /// The kind of an [Error::InvalidCollisionShape].
+    InvalidCollisionShape,
+
+    /// The kind of an [Error::InvalidFrameID].
+    InvalidFrameID,
+
+    /// The kind of an [Error::InvalidJointCalibration].
+    InvalidJointCalibration,
+
+    /// The kind of an [Error::InvalidJointProfile].
+    InvalidJointProfile,
+
+    /// The kind of an [Error::InvalidJointTransmission].
+    InvalidJointTransmission,
+
+    /// The kind of an [Error::InvalidKinematicsTable].
+    InvalidKinematicsTable,
+
+    /// The kind of an [Error::InvalidLoopClosure].
+    InvalidLoopClosure,
+
+    /// The kind of an [Error::InvalidMaterial].
+    InvalidMaterial,
+
+    /// The kind of an [Error::InvalidModuleSpecification].
+    InvalidModuleSpecification,
+
+    /// The kind of an [Error::InvalidRollingRadius].
+    InvalidRollingRadius,
+
+    /// The kind of an [Error::InvalidRosMessage].
+    InvalidRosMessage,
+
+    /// The kind of an [Error::InvalidStableFrameID].
+    InvalidStableFrameID,
+
+    /// The kind of an [Error::InvalidSteeringDegreeOfFreedom].
+    InvalidSteeringDegreeOfFreedom,
+
+    /// The kind of an [Error::InvalidStreamRate].
+    InvalidStreamRate,
+
+    /// The kind of an [Error::InvalidSuspensionSpring].
+    InvalidSuspensionSpring,
+
+    /// The kind of an [Error::InvalidTemplate].
+    InvalidTemplate,
+
+    /// The kind of an [Error::Io].
+    Io,
+
+    /// The kind of an [Error::JointLimitExceeded].
+    JointLimitExceeded,
+
+    /// The kind of an [Error::MissingFrameElement].
+    MissingFrameElement,
+
+    /// The kind of an [Error::MissingImuFrame].
+    MissingImuFrame,
+
+    /// The kind of an [Error::MissingJointLimits].
+    MissingJointLimits,
+
+    /// The kind of an [Error::MissingJointStateHistory].
+    MissingJointStateHistory,
+
+    /// The kind of an [Error::MissingOperatingMode].
+    MissingOperatingMode,
+
+    /// The kind of an [Error::MissingParameter].
+    MissingParameter,
+
+    /// The kind of an [Error::MissingWheelRadius].
+    MissingWheelRadius,
+
+    /// The kind of an [Error::ModelBuildFailed].
+    ModelBuildFailed,
+
+    /// The kind of an [Error::ModelMigrationFailed].
+    ModelMigrationFailed,
+
+    /// The kind of an [Error::MultipleSteeringFramesInChain].
+    MultipleSteeringFramesInChain,
+
+    /// The kind of an [Error::NoSteeringFramesInChain].
+    NoSteeringFramesInChain,
+
+    /// The kind of an [Error::NonFiniteJointState].
+    NonFiniteJointState,
+
+    /// The kind of an [Error::NonUnitOrientation].
+    NonUnitOrientation,
+
+    /// The kind of an [Error::UnexpectedRotationAxis].
+    UnexpectedRotationAxis,
+
+    /// The kind of an [Error::UnsupportedSchemaVersion].
+    UnsupportedSchemaVersion,
+}
+
+impl Error {
+    /// Returns the [ErrorKind] of the error.
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Error::ChangeProcessorFailed => ErrorKind::ChangeProcessorFailed,
+            Error::Channel { .. } => ErrorKind::Channel,
+            Error::CommandVetoed { .. } => ErrorKind::CommandVetoed,
+            Error::DuplicateFrameAlias { .. } => ErrorKind::DuplicateFrameAlias,
+            Error::DuplicateStableFrameID { .. } => ErrorKind::DuplicateStableFrameID,
+            Error::FailedToComputeTransform { .. } => ErrorKind::FailedToComputeTransform,
+            Error::FailedToReadActuatorJointState => ErrorKind::FailedToReadActuatorJointState,
+            Error::FrameElementAlreadyExists { .. } => ErrorKind::FrameElementAlreadyExists,
+            Error::IncompatibleNumberSpace { .. } => ErrorKind::IncompatibleNumberSpace,
+            Error::InsufficientWheelMeasurements { .. } => ErrorKind::InsufficientWheelMeasurements,
+            Error::InternalInconsistency { .. } => ErrorKind::InternalInconsistency,
+            Error::InvalidBinaryModel { .. } => ErrorKind::InvalidBinaryModel,
+            Error::InvalidCollisionShape => ErrorKind::InvalidCollisionShape,
+            Error::InvalidFrameID { .. } => ErrorKind::InvalidFrameID,
+            Error::InvalidJointCalibration { .. } => ErrorKind::InvalidJointCalibration,
+            Error::InvalidJointProfile => ErrorKind::InvalidJointProfile,
+            Error::InvalidJointTransmission { .. } => ErrorKind::InvalidJointTransmission,
+            Error::InvalidKinematicsTable => ErrorKind::InvalidKinematicsTable,
+            Error::InvalidLoopClosure => ErrorKind::InvalidLoopClosure,
+            Error::InvalidMaterial { .. } => ErrorKind::InvalidMaterial,
+            Error::InvalidModuleSpecification { .. } => ErrorKind::InvalidModuleSpecification,
+            Error::InvalidRollingRadius { .. } => ErrorKind::InvalidRollingRadius,
+            Error::InvalidRosMessage { .. } => ErrorKind::InvalidRosMessage,
+            Error::InvalidStableFrameID { .. } => ErrorKind::InvalidStableFrameID,
+            Error::InvalidSteeringDegreeOfFreedom { .. } => {
+                ErrorKind::InvalidSteeringDegreeOfFreedom
+            }
+            Error::InvalidStreamRate { .. } => ErrorKind::InvalidStreamRate,
+            Error::InvalidSuspensionSpring { .. } => ErrorKind::InvalidSuspensionSpring,
+            Error::InvalidTemplate { .. } => ErrorKind::InvalidTemplate,
+            Error::Io { .. } => ErrorKind::Io,
+            Error::JointLimitExceeded { .. } => ErrorKind::JointLimitExceeded,
+            Error::MissingFrameElement { .. } => ErrorKind::MissingFrameElement,
+            Error::MissingImuFrame => ErrorKind::MissingImuFrame,
+            Error::MissingJointLimits { .. } => ErrorKind::MissingJointLimits,
+            Error::MissingJointStateHistory { .. } => ErrorKind::MissingJointStateHistory,
+            Error::MissingOperatingMode { .. } => ErrorKind::MissingOperatingMode,
+            Error::MissingParameter { .. } => ErrorKind::MissingParameter,
+            Error::MissingWheelRadius { .. } => ErrorKind::MissingWheelRadius,
+            Error::ModelBuildFailed { .. } => ErrorKind::ModelBuildFailed,
+            Error::ModelMigrationFailed { .. } => ErrorKind::ModelMigrationFailed,
+            Error::MultipleSteeringFramesInChain { .. } => ErrorKind::MultipleSteeringFramesInChain,
+            Error::NoSteeringFramesInChain { .. } => ErrorKind::NoSteeringFramesInChain,
+            Error::NonFiniteJointState { .. } => ErrorKind::NonFiniteJointState,
+            Error::NonUnitOrientation { .. } => ErrorKind::NonUnitOrientation,
+            Error::UnexpectedRotationAxis { .. } => ErrorKind::UnexpectedRotationAxis,
+            Error::UnsupportedSchemaVersion { .. } => ErrorKind::UnsupportedSchemaVersion,
+        }
+    }
+}
+
+impl PartialEq for Error {
+    /// Compares two errors field by field. The source errors of the [Error::Channel] and
+    /// [Error::Io] variants cannot be compared directly, so they are compared by their messages
+    /// and, for I/O errors, by their [std::io::ErrorKind].
+    fn eq(&self, other: &Self) -> bool {
+        // There is no catch-all arm for 'self' so that a new variant can't be added without
+        // deciding how it is compared.
+        match self {
+            Error::ChangeProcessorFailed => matches!(other, Error::ChangeProcessorFailed),
+            Error::Channel { context, source } => match other {
+                Error::Channel {
+                    context: r_context,
+                    source: r_source,
+                } => context == r_context && source.to_string() == r_source.to_string(),
+                _ => false,
+            },
+            Error::CommandVetoed { reason } => match other {
+                Error::CommandVetoed { reason: r_reason } => reason == r_reason,
+                _ => false,
+            },
+            Error::DuplicateFrameAlias { alias } => match other {
+                Error::DuplicateFrameAlias { alias: r_alias } => alias == r_alias,
+                _ => false,
+            },
+            Error::DuplicateStableFrameID { id } => match other {
+                Error::DuplicateStableFrameID { id: r_id } => id == r_id,
+                _ => false,
+            },
+            Error::FailedToComputeTransform { from, to } => match other {
+                Error::FailedToComputeTransform {
+                    from: r_from,
+                    to: r_to,
+                } => from == r_from && to == r_to,
+                _ => false,
+            },
+            Error::FailedToReadActuatorJointState => {
+                matches!(other, Error::FailedToReadActuatorJointState)
+            }
+            Error::FrameElementAlreadyExists { id } => match other {
+                Error::FrameElementAlreadyExists { id: r_id } => id == r_id,
+                _ => false,
+            },
+            Error::IncompatibleNumberSpace { id } => match other {
+                Error::IncompatibleNumberSpace { id: r_id } => id == r_id,
+                _ => false,
+            },
+            Error::InsufficientWheelMeasurements {
+                required,
+                available,
+            } => match other {
+                Error::InsufficientWheelMeasurements {
+                    required: r_required,
+                    available: r_available,
+                } => required == r_required && available == r_available,
+                _ => false,
+            },
+            Error::InternalInconsistency { context } => match other {
+                Error::InternalInconsistency { context: r_context } => context == r_context,
+                _ => false,
+            },
+            Error::InvalidBinaryModel { reason } => match other {
+                Error::InvalidBinaryModel { reason: r_reason } => reason == r_reason,
+                _ => false,
+            },
+            Error::InvalidCollisionShape => matches!(other, Error::InvalidCollisionShape),
+            Error::InvalidFrameID { id } => match other {
+                Error::InvalidFrameID { id: r_id } => id == r_id,
+                _ => false,
+            },
+            Error::InvalidJointCalibration { offset, scale } => match other {
+                Error::InvalidJointCalibration {
+                    offset: r_offset,
+                    scale: r_scale,
+                } => offset == r_offset && scale == r_scale,
+                _ => false,
+            },
+            Error::InvalidJointProfile => matches!(other, Error::InvalidJointProfile),
+            Error::InvalidJointTransmission { gear_ratio } => match other {
+                Error::InvalidJointTransmission {
+                    gear_ratio: r_gear_ratio,
+                } => gear_ratio == r_gear_ratio,
+                _ => false,
+            },
+            Error::InvalidKinematicsTable => matches!(other, Error::InvalidKinematicsTable),
+            Error::InvalidLoopClosure => matches!(other, Error::InvalidLoopClosure),
+            Error::InvalidMaterial { name } => match other {
+                Error::InvalidMaterial { name: r_name } => name == r_name,
+                _ => false,
+            },
+            Error::InvalidModuleSpecification { name, reason } => match other {
+                Error::InvalidModuleSpecification {
+                    name: r_name,
+                    reason: r_reason,
+                } => name == r_name && reason == r_reason,
+                _ => false,
+            },
+            Error::InvalidRollingRadius { radius } => match other {
+                Error::InvalidRollingRadius { radius: r_radius } => radius == r_radius,
+                _ => false,
+            },
+            Error::InvalidRosMessage { reason } => match other {
+                Error::InvalidRosMessage { reason: r_reason } => reason == r_reason,
+                _ => false,
+            },
+            Error::InvalidStableFrameID { value } => match other {
+                Error::InvalidStableFrameID { value: r_value } => value == r_value,
+                _ => false,
+            },
+            Error::InvalidSteeringDegreeOfFreedom { degree_of_freedom } => match other {
+                Error::InvalidSteeringDegreeOfFreedom {
+                    degree_of_freedom: r_degree_of_freedom,
+                } => degree_of_freedom == r_degree_of_freedom,
+                _ => false,
+            },
+            Error::InvalidStreamRate { rate } => match other {
+                Error::InvalidStreamRate { rate: r_rate } => rate == r_rate,
+                _ => false,
+            },
+            Error::InvalidSuspensionSpring {
+                stiffness,
+                damping,
+                preload,
+            } => match other {
+                Error::InvalidSuspensionSpring {
+                    stiffness: r_stiffness,
+                    damping: r_damping,
+                    preload: r_preload,
+                } => stiffness == r_stiffness && damping == r_damping && preload == r_preload,
+                _ => false,
+            },
+            Error::InvalidTemplate { reason } => match other {
+                Error::InvalidTemplate { reason: r_reason } => reason == r_reason,
+                _ => false,
+            },
+            Error::Io { context, source } => match other {
+                Error::Io {
+                    context: r_context,
+                    source: r_source,
+                } => {
+                    context == r_context
+                        && source.kind() == r_source.kind()
+                        && source.to_string() == r_source.to_string()
+                }
+                _ => false,
+            },
+            Error::JointLimitExceeded {
+                quantity,
+                value,
+                minimum,
+                maximum,
+            } => match other {
+                Error::JointLimitExceeded {
+                    quantity: r_quantity,
+                    value: r_value,
+                    minimum: r_minimum,
+                    maximum: r_maximum,
+                } => {
+                    quantity == r_quantity
+                        && value == r_value
+                        && minimum == r_minimum
+                        && maximum == r_maximum
+                }
+                _ => false,
+            },
+            Error::MissingFrameElement { id } => match other {
+                Error::MissingFrameElement { id: r_id } => id == r_id,
+                _ => false,
+            },
+            Error::MissingImuFrame => matches!(other, Error::MissingImuFrame),
+            Error::MissingJointLimits { id } => match other {
+                Error::MissingJointLimits { id: r_id } => id == r_id,
+                _ => false,
+            },
+            Error::MissingJointStateHistory { id } => match other {
+                Error::MissingJointStateHistory { id: r_id } => id == r_id,
+                _ => false,
+            },
+            Error::MissingOperatingMode { name } => match other {
+                Error::MissingOperatingMode { name: r_name } => name == r_name,
+                _ => false,
+            },
+            Error::MissingParameter { name } => match other {
+                Error::MissingParameter { name: r_name } => name == r_name,
+                _ => false,
+            },
+            Error::MissingWheelRadius { id } => match other {
+                Error::MissingWheelRadius { id: r_id } => id == r_id,
+                _ => false,
+            },
+            Error::ModelBuildFailed { errors } => match other {
+                Error::ModelBuildFailed { errors: r_errors } => errors == r_errors,
+                _ => false,
+            },
+            Error::ModelMigrationFailed { reason } => match other {
+                Error::ModelMigrationFailed { reason: r_reason } => reason == r_reason,
+                _ => false,
+            },
+            Error::MultipleSteeringFramesInChain { id } => match other {
+                Error::MultipleSteeringFramesInChain { id: r_id } => id == r_id,
+                _ => false,
+            },
+            Error::NoSteeringFramesInChain { id } => match other {
+                Error::NoSteeringFramesInChain { id: r_id } => id == r_id,
+                _ => false,
+            },
+            Error::NonFiniteJointState { id } => match other {
+                Error::NonFiniteJointState { id: r_id } => id == r_id,
+                _ => false,
+            },
+            Error::NonUnitOrientation { norm } => match other {
+                Error::NonUnitOrientation { norm: r_norm } => norm == r_norm,
+                _ => false,
+            },
+            Error::UnexpectedRotationAxis { name, axis } => match other {
+                Error::UnexpectedRotationAxis {
+                    name: r_name,
+                    axis: r_axis,
+                } => name == r_name && axis == r_axis,
+                _ => false,
+            },
+            Error::UnsupportedSchemaVersion { version } => match other {
+                Error::UnsupportedSchemaVersion { version: r_version } => version == r_version,
+                _ => false,
+            },
+        }
+    }
 }
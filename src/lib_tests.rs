@@ -0,0 +1,108 @@
+use std::error::Error as _;
+
+use crate::{model_elements::frame_elements::FrameID, Error, ErrorKind};
+
+fn io_error(context: &str, kind: std::io::ErrorKind, message: &str) -> Error {
+    Error::Io {
+        context: context.to_string(),
+        source: std::io::Error::new(kind, message),
+    }
+}
+
+#[test]
+fn when_getting_the_kind_of_an_error_it_should_match_the_variant() {
+    let id = FrameID::new();
+    assert_eq!(
+        ErrorKind::MissingFrameElement,
+        Error::MissingFrameElement { id }.kind()
+    );
+    assert_eq!(
+        ErrorKind::ChangeProcessorFailed,
+        Error::ChangeProcessorFailed.kind()
+    );
+    assert_eq!(
+        ErrorKind::Io,
+        io_error(
+            "Failed to write the model.",
+            std::io::ErrorKind::Other,
+            "disk full"
+        )
+        .kind()
+    );
+    assert_eq!(
+        ErrorKind::Channel,
+        Error::Channel {
+            context: "Failed to send the command to the actuator.".to_string(),
+            source: Box::new(crossbeam_channel::SendError(1.0)),
+        }
+        .kind()
+    );
+    assert_ne!(
+        ErrorKind::InvalidFrameID,
+        Error::MissingFrameElement { id }.kind()
+    );
+}
+
+#[test]
+fn when_an_error_has_a_source_it_should_keep_the_source() {
+    let error = io_error(
+        "Failed to write the model.",
+        std::io::ErrorKind::PermissionDenied,
+        "access denied",
+    );
+    assert_eq!("Failed to write the model.", error.to_string());
+
+    let source = error.source().unwrap();
+    assert_eq!("access denied", source.to_string());
+    assert_eq!(
+        std::io::ErrorKind::PermissionDenied,
+        source.downcast_ref::<std::io::Error>().unwrap().kind()
+    );
+
+    assert!(Error::ChangeProcessorFailed.source().is_none());
+}
+
+#[test]
+fn when_comparing_errors_it_should_compare_the_data_and_the_sources() {
+    let id = FrameID::new();
+    assert_eq!(
+        Error::MissingFrameElement { id },
+        Error::MissingFrameElement { id }
+    );
+    assert_ne!(
+        Error::MissingFrameElement { id },
+        Error::MissingFrameElement { id: FrameID::new() }
+    );
+    assert_ne!(
+        Error::MissingFrameElement { id },
+        Error::InvalidFrameID { id }
+    );
+
+    // The messages of these errors do not contain the ID of the frame
+    assert_ne!(
+        Error::MultipleSteeringFramesInChain { id },
+        Error::MultipleSteeringFramesInChain { id: FrameID::new() }
+    );
+    assert_ne!(
+        Error::NoSteeringFramesInChain { id },
+        Error::NoSteeringFramesInChain { id: FrameID::new() }
+    );
+
+    let context = "Failed to read the model.";
+    assert_eq!(
+        io_error(context, std::io::ErrorKind::NotFound, "no such file"),
+        io_error(context, std::io::ErrorKind::NotFound, "no such file")
+    );
+    assert_ne!(
+        io_error(context, std::io::ErrorKind::NotFound, "no such file"),
+        io_error(context, std::io::ErrorKind::NotFound, "is a directory")
+    );
+    assert_ne!(
+        io_error(context, std::io::ErrorKind::NotFound, "no such file"),
+        io_error(
+            "Failed to write the model.",
+            std::io::ErrorKind::NotFound,
+            "no such file"
+        )
+    );
+}
@@ -12,9 +12,36 @@
 //!   frame
 //! - a [JointConstraint](frame_elements::JointConstraint) to provide limitations on the motion of
 //!   the frame.
+//! - a [JointCalibration](frame_elements::JointCalibration) to convert the raw values of the
+//!   hardware of the frame, e.g. an encoder zero offset, into the state of the joint.
+//! - a [JointTransmission](frame_elements::JointTransmission) to describe the gear ratio and the
+//!   direction between the hardware and the joint.
 //!
 //! The [Model](model::MotionModel) stores all the frame elements and their relationships in a
-//! directed tree.
+//! directed tree. Joints that do not move along a single axis can be described with a
+//! [JointKinematicsTable](kinematics_table::JointKinematicsTable). The model also tracks which
+//! wheels are in contact with the ground, see the [ground_contact] module, and notifies consumers
+//! of structural changes, see the [model_change] module. A model that consists of a body and a
+//! number of similar wheel modules can be created in a single call, see the [module_spec] module.
+//! The layouts of common symmetric vehicles are available in the [templates] module and mirrored
+//! copies of parts of a model are created with the functions in the [mirror] module.
+//! Whether a model describes a valid swerve vehicle is checked with
+//! [MotionModel::validate](model::MotionModel::validate), see the [validation] module.
+//! External forces that act on the vehicle are described in the [wrench] module. Identifiers that
+//! refer to the frame elements across process restarts are provided by the [stable_id] module.
+//! The frames on which cameras, IMUs and other perception sensors are mounted are described in
+//! the [sensor_mount] module. The attitude of the vehicle is derived from its inertial
+//! measurement unit in the [imu] module. The collision geometry of the chassis elements is
+//! described in the [collision] module, the collisions between the chassis elements are detected
+//! with the functions in the [self_collision] module and the geometry used to render the vehicle
+//! in the [visual] module. Two models are compared with the functions in the [diff] module. Joints
+//! with more than one degree of freedom, e.g. ball joints, are added with the functions in the
+//! [multi_dof] module and the constraints that close kinematic loops, e.g. the loop of a double
+//! wishbone suspension, are described in the [loop_closure] module. The dimensions of a family of
+//! vehicles can be derived from named parameters, see the [parameters] module. The springs and
+//! dampers of the suspension, and the static sag they cause, are described in the [suspension]
+//! module. The opt-in strict mode that checks the orientations and joint states that enter the
+//! model is provided by the [audit] module.
 //!
 //! # Examples
 //!
@@ -26,7 +53,7 @@
 //! use crossbeam_channel::{ Receiver, Sender };
 //! use nalgebra::{ Matrix3, Matrix4, Matrix6, Translation3, UnitQuaternion, Vector3 };
 //! use swerve_vehicle_descriptors::Error;
-//! use swerve_vehicle_descriptors::change_notification_processing::{ ChangeID, HardwareChangeProcessor };
+//! use swerve_vehicle_descriptors::change_notification_processing::{ ChangeID, ChangeNotifier, HardwareChangeProcessor };
 //! use swerve_vehicle_descriptors::hardware::actuator_interface::{ HardwareActuator, ActuatorAvailableRatesOfChange };
 //! use swerve_vehicle_descriptors::hardware::joint_state::{ JointState, JointStateRange };
 //! use swerve_vehicle_descriptors::number_space::NumberSpaceType;
@@ -65,7 +92,7 @@
 //!     receiver: Receiver<(JointState, ActuatorAvailableRatesOfChange)>,
 //!     sender: Sender<(JointState, ActuatorAvailableRatesOfChange)>,
 //!     command_sender: Sender<JointState>,
-//!     update_sender: Option<Sender<ChangeID>>,
+//!     update_sender: Option<ChangeNotifier>,
 //!     id: Option<ChangeID>,
 //! }
 //!
@@ -84,7 +111,7 @@
 //!         Ok(self.command_sender.clone())
 //!     }
 //!
-//!     fn on_change(&mut self, id: ChangeID, sender: Sender<ChangeID>) {
+//!     fn on_change(&mut self, id: ChangeID, sender: ChangeNotifier) {
 //!         self.id = Some(id);
 //!         self.update_sender = Some(sender);
 //!     }
@@ -261,5 +288,29 @@
 //!
 //! ```
 
+pub mod audit;
+pub mod collision;
+pub mod diff;
 pub mod frame_elements;
+pub mod ground_contact;
+pub mod imu;
+pub mod kinematics_table;
+pub mod loop_closure;
+#[cfg(feature = "serde")]
+pub mod migration;
+pub mod mirror;
 pub mod model;
+pub mod model_change;
+pub mod model_event;
+pub mod module_spec;
+pub mod multi_dof;
+pub mod operating_mode;
+pub mod parameters;
+pub mod self_collision;
+pub mod sensor_mount;
+pub mod stable_id;
+pub mod suspension;
+pub mod templates;
+pub mod validation;
+pub mod visual;
+pub mod wrench;
@@ -0,0 +1,206 @@
+//! Provides the audit mode of the model, an opt-in strict mode in which the orientations and
+//! angles that enter the model are checked against the conventions of the crate.
+//!
+//! By default the model accepts any [UnitQuaternion], even one that was created without being
+//! normalized, and joint states as they are reported by the hardware. When the audit mode is
+//! enabled with [MotionModel::set_audit_mode] the model rejects:
+//!
+//! * Orientations that are not normalized within [ORIENTATION_NORM_TOLERANCE], both for the
+//!   frames of the model and for the orientation reported by the inertial measurement unit.
+//! * Steering frames that do not rotate around the z-axis and wheels that do not rotate around
+//!   the y-axis of their parent, within [ROTATION_AXIS_TOLERANCE]. See the conventions in the
+//!   [kinematics](crate::kinematics) module.
+//! * Joint states with a position, velocity, acceleration or jerk that is not finite.
+//!
+//! Violations are returned as errors instead of being corrected, so that bad IMU or encoder data
+//! is caught at the boundary of the model.
+//!
+//! # Examples
+//!
+//! ```
+//! use nalgebra::{Matrix3, Quaternion, Translation3, UnitQuaternion, Vector3};
+//! use swerve_vehicle_descriptors::model_elements::model::{
+//!     ChassisElementPhysicalProperties, MotionModel,
+//! };
+//! use swerve_vehicle_descriptors::Error;
+//!
+//! let properties =
+//!     ChassisElementPhysicalProperties::from_mass_properties(1.0, Vector3::zeros(), Matrix3::identity());
+//! let mut model = MotionModel::new();
+//! let body = model
+//!     .add_body(
+//!         "body".to_string(),
+//!         Translation3::identity(),
+//!         UnitQuaternion::identity(),
+//!         properties,
+//!     )
+//!     .unwrap();
+//! model.set_audit_mode(true).unwrap();
+//!
+//! // A quaternion that was never normalized
+//! let orientation = UnitQuaternion::new_unchecked(Quaternion::new(2.0, 0.0, 0.0, 0.0));
+//! let result = model.add_static_chassis_element(
+//!     "sensor".to_string(),
+//!     body,
+//!     Translation3::identity(),
+//!     orientation,
+//!     properties,
+//! );
+//! assert_eq!(Err(Error::NonUnitOrientation { norm: 2.0 }), result);
+//! ```
+
+use nalgebra::{UnitQuaternion, Vector3};
+
+use crate::{hardware::joint_state::JointState, Error};
+
+use super::{
+    frame_elements::{FrameID, FrameRole, ReferenceFrame},
+    model::MotionModel,
+};
+
+#[cfg(test)]
+#[path = "audit_tests.rs"]
+mod audit_tests;
+
+/// The largest difference between the norm of an orientation quaternion and one that is accepted
+/// in audit mode.
+pub const ORIENTATION_NORM_TOLERANCE: f64 = 1e-6;
+
+/// The largest sine of the angle between the rotation axis of a steering frame or a wheel and
+/// its expected axis that is accepted in audit mode.
+pub const ROTATION_AXIS_TOLERANCE: f64 = 1e-6;
+
+impl MotionModel {
+    /// Returns a value indicating whether the audit mode is enabled.
+    pub fn is_audit_mode_enabled(&self) -> bool {
+        self.audit_mode
+    }
+
+    /// Enables or disables the audit mode.
+    ///
+    /// The frames and the IMU orientation that are already part of the model are checked when
+    /// the audit mode is enabled. The audit mode stays disabled when any of them violates the
+    /// conventions.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'enabled' - A value indicating whether the audit mode should be enabled.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::NonUnitOrientation] - Returned when the orientation of a frame, or of the IMU,
+    ///   is not normalized.
+    /// * [Error::UnexpectedRotationAxis] - Returned when a steering frame or a wheel does not
+    ///   rotate around its expected axis.
+    pub fn set_audit_mode(&mut self, enabled: bool) -> Result<(), Error> {
+        if enabled && !self.audit_mode {
+            for frame in self.kinematic_tree().elements() {
+                let orientation = if self.is_body(frame.id()) {
+                    &self.body_pose_in_world().rotation
+                } else {
+                    &self.static_transform_to_parent(frame.id())?.rotation
+                };
+                audit_orientation(orientation)?;
+                audit_rotation_axis(frame)?;
+            }
+
+            if let Some(orientation) = &self.imu_orientation {
+                audit_orientation(orientation)?;
+            }
+        }
+
+        self.audit_mode = enabled;
+        Ok(())
+    }
+
+    /// Checks, in audit mode, the orientation and the rotation axis of a frame that is about to
+    /// be added to the model.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::NonUnitOrientation] - Returned when the orientation is not normalized.
+    /// * [Error::UnexpectedRotationAxis] - Returned when the frame is a steering frame or a
+    ///   wheel that does not rotate around its expected axis.
+    pub(super) fn audit_new_frame(
+        &self,
+        frame: &ReferenceFrame,
+        orientation_relative_to_parent: &UnitQuaternion<f64>,
+    ) -> Result<(), Error> {
+        if !self.audit_mode {
+            return Ok(());
+        }
+
+        audit_orientation(orientation_relative_to_parent)?;
+        audit_rotation_axis(frame)
+    }
+
+    /// Checks, in audit mode, that the given joint state only contains finite values.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::NonFiniteJointState] - Returned when a value of the state is not finite.
+    pub(super) fn audit_joint_state(
+        &self,
+        frame_id: &FrameID,
+        state: JointState,
+    ) -> Result<JointState, Error> {
+        if !self.audit_mode {
+            return Ok(state);
+        }
+
+        if !state.is_finite() {
+            return Err(Error::NonFiniteJointState { id: *frame_id });
+        }
+
+        Ok(state)
+    }
+
+    /// Checks, in audit mode, that the given orientation is normalized.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::NonUnitOrientation] - Returned when the orientation is not normalized.
+    pub(super) fn audit_orientation(&self, orientation: &UnitQuaternion<f64>) -> Result<(), Error> {
+        if !self.audit_mode {
+            return Ok(());
+        }
+
+        audit_orientation(orientation)
+    }
+}
+
+/// Returns an error when the given orientation is not normalized within
+/// [ORIENTATION_NORM_TOLERANCE].
+fn audit_orientation(orientation: &UnitQuaternion<f64>) -> Result<(), Error> {
+    let norm = orientation.quaternion().norm();
+    if !norm.is_finite() || (norm - 1.0).abs() > ORIENTATION_NORM_TOLERANCE {
+        return Err(Error::NonUnitOrientation { norm });
+    }
+
+    Ok(())
+}
+
+/// Returns an error when the given frame is a steering frame that does not rotate around the
+/// z-axis, or a wheel that does not rotate around the y-axis, of its parent.
+fn audit_rotation_axis(frame: &ReferenceFrame) -> Result<(), Error> {
+    let expected = match frame.role() {
+        FrameRole::Steering => Vector3::z(),
+        FrameRole::Wheel => Vector3::y(),
+        _ => return Ok(()),
+    };
+
+    let degree_of_freedom = frame.degree_of_freedom_kind();
+    let axis = match degree_of_freedom.axis() {
+        Some(axis) if degree_of_freedom.is_revolute() => axis,
+        _ => return Ok(()),
+    };
+
+    if axis.normalize().cross(&expected).norm() > ROTATION_AXIS_TOLERANCE {
+        return Err(Error::UnexpectedRotationAxis {
+            name: frame.name().to_string(),
+            axis,
+        });
+    }
+
+    Ok(())
+}
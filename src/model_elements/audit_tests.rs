@@ -0,0 +1,207 @@
+use nalgebra::{Isometry3, Quaternion, Translation3, Unit, UnitQuaternion, Vector3};
+
+use crate::{
+    change_notification_processing::HardwareChangeProcessor,
+    hardware::{
+        joint_state::{JointState, JointStateRange},
+        simulation::SimulatedSensor,
+    },
+    model_elements::{
+        frame_elements::{FrameDofType, FrameID, JointCalibration, JointConstraint, JointSensor},
+        model::MotionModel,
+    },
+    number_space::NumberSpaceType,
+    test_fixtures::physical_properties,
+    Error,
+};
+
+fn create_model() -> (MotionModel, FrameID) {
+    let mut model = MotionModel::new();
+    let body = model
+        .add_body(
+            "body".to_string(),
+            Translation3::identity(),
+            UnitQuaternion::identity(),
+            physical_properties(),
+        )
+        .unwrap();
+    (model, body)
+}
+
+fn unnormalized() -> UnitQuaternion<f64> {
+    UnitQuaternion::new_unchecked(Quaternion::new(0.5, 0.0, 0.0, 0.0))
+}
+
+#[test]
+fn when_the_audit_mode_is_disabled_it_should_accept_unnormalized_orientations() {
+    let (mut model, body) = create_model();
+    assert!(!model.is_audit_mode_enabled());
+
+    let frame = model
+        .add_static_chassis_element(
+            "sensor".to_string(),
+            body,
+            Translation3::identity(),
+            unnormalized(),
+            physical_properties(),
+        )
+        .unwrap();
+
+    // The existing frame prevents the audit mode from being enabled
+    assert_eq!(
+        Err(Error::NonUnitOrientation { norm: 0.5 }),
+        model.set_audit_mode(true)
+    );
+    assert!(!model.is_audit_mode_enabled());
+
+    model
+        .set_transform_to_parent(&frame, Translation3::identity(), UnitQuaternion::identity())
+        .unwrap();
+    model.set_audit_mode(true).unwrap();
+    assert!(model.is_audit_mode_enabled());
+
+    model.set_audit_mode(false).unwrap();
+    assert!(!model.is_audit_mode_enabled());
+}
+
+#[test]
+fn when_the_audit_mode_is_enabled_it_should_reject_unnormalized_orientations() {
+    let (mut model, body) = create_model();
+    model.set_audit_mode(true).unwrap();
+
+    assert_eq!(
+        Err(Error::NonUnitOrientation { norm: 0.5 }),
+        model.add_suspension_element(
+            "suspension".to_string(),
+            FrameDofType::PrismaticZ,
+            body,
+            Translation3::identity(),
+            unnormalized(),
+            physical_properties(),
+            JointConstraint::new(),
+        )
+    );
+    assert_eq!(1, model.kinematic_tree().elements().count());
+
+    let imu = model
+        .add_static_chassis_element(
+            "imu".to_string(),
+            body,
+            Translation3::identity(),
+            UnitQuaternion::identity(),
+            physical_properties(),
+        )
+        .unwrap();
+    assert!(matches!(
+        model.set_transform_to_parent(&imu, Translation3::identity(), unnormalized()),
+        Err(Error::NonUnitOrientation { .. })
+    ));
+    assert_eq!(
+        Isometry3::identity(),
+        *model.static_transform_to_parent(&imu).unwrap()
+    );
+
+    model.set_imu_frame(Some(imu)).unwrap();
+    let nan = UnitQuaternion::new_unchecked(Quaternion::new(f64::NAN, 0.0, 0.0, 0.0));
+    assert!(matches!(
+        model.update_imu_orientation(nan),
+        Err(Error::NonUnitOrientation { .. })
+    ));
+    assert_eq!(None, model.imu_orientation());
+}
+
+#[test]
+fn when_the_audit_mode_is_enabled_it_should_check_the_steering_axis() {
+    let (mut model, body) = create_model();
+    let tilted = FrameDofType::RevoluteAxis(Unit::new_normalize(Vector3::new(1.0, 0.0, 1.0)));
+
+    model.set_audit_mode(true).unwrap();
+    assert_eq!(
+        Err(Error::UnexpectedRotationAxis {
+            name: "steering".to_string(),
+            axis: tilted.axis().unwrap(),
+        }),
+        model.add_steering_element_with_optional_actuator(
+            "steering".to_string(),
+            tilted,
+            body,
+            Translation3::identity(),
+            UnitQuaternion::identity(),
+            physical_properties(),
+            None,
+        )
+    );
+
+    // A steering axis along the negative z-axis follows the convention
+    model
+        .add_steering_element_with_optional_actuator(
+            "steering".to_string(),
+            FrameDofType::RevoluteAxis(-Vector3::z_axis()),
+            body,
+            Translation3::identity(),
+            UnitQuaternion::identity(),
+            physical_properties(),
+            None,
+        )
+        .unwrap();
+
+    // Frames that were added before the audit mode was enabled are checked when it is enabled
+    let (mut model, body) = create_model();
+    model
+        .add_steering_element_with_optional_actuator(
+            "steering".to_string(),
+            tilted,
+            body,
+            Translation3::identity(),
+            UnitQuaternion::identity(),
+            physical_properties(),
+            None,
+        )
+        .unwrap();
+    assert!(matches!(
+        model.set_audit_mode(true),
+        Err(Error::UnexpectedRotationAxis { .. })
+    ));
+}
+
+#[test]
+fn when_the_audit_mode_is_enabled_it_should_reject_joint_states_that_are_not_finite() {
+    let processor = HardwareChangeProcessor::manual();
+    let (mut model, body) = create_model();
+    let suspension = model
+        .add_suspension_element(
+            "suspension".to_string(),
+            FrameDofType::PrismaticZ,
+            body,
+            Translation3::identity(),
+            UnitQuaternion::identity(),
+            physical_properties(),
+            JointConstraint::new(),
+        )
+        .unwrap();
+
+    let mut hardware = SimulatedSensor::new(
+        NumberSpaceType::LinearUnlimited,
+        JointStateRange::new(
+            JointState::new(-1.0, Some(-1.0), None, None),
+            JointState::new(1.0, Some(4.0), None, None),
+        ),
+        JointState::new(0.1, Some(4.0), None, None),
+    );
+
+    // The raw state is finite but the velocity overflows when it is calibrated
+    let mut sensor = JointSensor::new(&mut hardware, &processor).unwrap();
+    sensor.set_calibration(JointCalibration::new(0.0, f64::MAX).unwrap());
+    model.add_joint_sensor(&suspension, sensor).unwrap();
+    hardware.step();
+    processor.process_pending();
+
+    let state = model.joint_state_for(&suspension).unwrap();
+    assert_eq!(Some(f64::INFINITY), *state.velocity());
+
+    model.set_audit_mode(true).unwrap();
+    assert_eq!(
+        Err(Error::NonFiniteJointState { id: suspension }),
+        model.joint_state_for(&suspension)
+    );
+}
@@ -0,0 +1,296 @@
+//! Describes the collision geometry of the chassis elements of a vehicle.
+//!
+//! Each chassis element can have any number of [CollisionGeometry] instances, which are added
+//! with [MotionModel::add_collision_geometry]. A collision geometry is a simple [CollisionShape]
+//! together with the pose of the shape relative to the reference frame of the element. The shapes
+//! of all the elements, posed for the current positions of the joints, are provided by
+//! [MotionModel::collision_shapes_in_body], e.g. to compute the footprint of the vehicle or to
+//! export the vehicle to a simulator.
+
+use nalgebra::{Isometry3, Vector3};
+
+use crate::Error;
+
+use super::{frame_elements::FrameID, model::MotionModel};
+
+#[cfg(test)]
+#[path = "collision_tests.rs"]
+mod collision_tests;
+
+/// Defines the shapes that are used to describe the collision geometry of a chassis element.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CollisionShape {
+    /// A box that is centered on the origin of the shape.
+    Box {
+        /// The length of the sides of the box along the x, y and z axes, in meters.
+        size: Vector3<f64>,
+    },
+
+    /// A cylinder that is centered on the origin of the shape, with its axis along the z-axis.
+    Cylinder {
+        /// The radius of the cylinder, in meters.
+        radius: f64,
+
+        /// The length of the cylinder along the z-axis, in meters.
+        length: f64,
+    },
+
+    /// A mesh that is stored outside of the model, e.g. in an STL or a DAE file.
+    Mesh {
+        /// The location of the mesh, e.g. `package://vehicle/meshes/body.stl`.
+        uri: String,
+
+        /// The scale factors that are applied to the mesh along the x, y and z axes.
+        scale: Vector3<f64>,
+    },
+
+    /// A sphere that is centered on the origin of the shape.
+    Sphere {
+        /// The radius of the sphere, in meters.
+        radius: f64,
+    },
+}
+
+impl CollisionShape {
+    /// Returns a value indicating whether all the dimensions of the shape are finite, positive
+    /// numbers.
+    pub(super) fn has_valid_dimensions(&self) -> bool {
+        let is_valid = |value: f64| value.is_finite() && value > 0.0;
+        match self {
+            CollisionShape::Box { size } => size.iter().all(|v| is_valid(*v)),
+            CollisionShape::Cylinder { radius, length } => is_valid(*radius) && is_valid(*length),
+            CollisionShape::Mesh { uri, scale } => {
+                !uri.is_empty() && scale.iter().all(|v| is_valid(*v))
+            }
+            CollisionShape::Sphere { radius } => is_valid(*radius),
+        }
+    }
+
+    /// Returns the point of the shape, in the frame of the shape, that lies furthest in the
+    /// given direction, or [None] for a mesh because meshes are not loaded by this crate.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'direction' - The direction, in the frame of the shape. Does not need to be normalized.
+    pub(crate) fn support_point(&self, direction: &Vector3<f64>) -> Option<Vector3<f64>> {
+        let half_along = |value: f64, half: f64| if value < 0.0 { -half } else { half };
+        match self {
+            CollisionShape::Box { size } => Some(Vector3::new(
+                half_along(direction.x, 0.5 * size.x),
+                half_along(direction.y, 0.5 * size.y),
+                half_along(direction.z, 0.5 * size.z),
+            )),
+            CollisionShape::Cylinder { radius, length } => {
+                let radial = Vector3::new(direction.x, direction.y, 0.0);
+                let radial_norm = radial.norm();
+                let rim = if radial_norm > 0.0 {
+                    radial * (*radius / radial_norm)
+                } else {
+                    Vector3::zeros()
+                };
+                Some(rim + Vector3::new(0.0, 0.0, half_along(direction.z, 0.5 * length)))
+            }
+            CollisionShape::Mesh { .. } => None,
+            CollisionShape::Sphere { radius } => {
+                let norm = direction.norm();
+                if norm > 0.0 {
+                    Some(direction * (*radius / norm))
+                } else {
+                    Some(Vector3::new(*radius, 0.0, 0.0))
+                }
+            }
+        }
+    }
+}
+
+/// Describes a [CollisionShape] and the pose of the shape relative to the reference frame of the
+/// chassis element to which it belongs.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CollisionGeometry {
+    /// The shape.
+    shape: CollisionShape,
+
+    /// The transform from the frame of the shape to the reference frame of the element.
+    origin: Isometry3<f64>,
+}
+
+impl CollisionGeometry {
+    /// Creates a new [CollisionGeometry] instance.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'shape' - The shape.
+    /// * 'origin' - The position and orientation of the shape relative to the reference frame of
+    ///   the element.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::InvalidCollisionShape] - Returned when a dimension of the shape is not a finite,
+    ///   positive number, or when a mesh has no location.
+    pub fn new(shape: CollisionShape, origin: Isometry3<f64>) -> Result<Self, Error> {
+        if !shape.has_valid_dimensions() {
+            return Err(Error::InvalidCollisionShape);
+        }
+
+        Ok(Self { shape, origin })
+    }
+
+    /// Returns the transform from the frame of the shape to the reference frame of the element.
+    pub fn origin(&self) -> &Isometry3<f64> {
+        &self.origin
+    }
+
+    /// Returns the shape.
+    pub fn shape(&self) -> &CollisionShape {
+        &self.shape
+    }
+}
+
+/// Describes a [CollisionShape] of a chassis element, posed for the positions of the joints at
+/// the time it was retrieved.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PosedCollisionShape {
+    /// The frame of the element to which the shape belongs.
+    frame: FrameID,
+
+    /// The shape.
+    shape: CollisionShape,
+
+    /// The transform from the frame of the shape to the body frame.
+    pose: Isometry3<f64>,
+}
+
+impl PosedCollisionShape {
+    /// Returns the [FrameID] of the element to which the shape belongs.
+    pub fn frame(&self) -> FrameID {
+        self.frame
+    }
+
+    /// Returns the transform from the frame of the shape to the body frame.
+    pub fn pose(&self) -> &Isometry3<f64> {
+        &self.pose
+    }
+
+    /// Returns the shape.
+    pub fn shape(&self) -> &CollisionShape {
+        &self.shape
+    }
+}
+
+impl MotionModel {
+    /// Adds a [CollisionGeometry] to the given chassis element.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'frame_id' - The [FrameID] of the element.
+    /// * 'geometry' - The collision geometry, relative to the reference frame of the element.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the element is not part of the model.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use nalgebra::{Isometry3, Matrix3, Translation3, UnitQuaternion, Vector3};
+    /// use swerve_vehicle_descriptors::model_elements::collision::{
+    ///     CollisionGeometry, CollisionShape,
+    /// };
+    /// use swerve_vehicle_descriptors::model_elements::model::{
+    ///     ChassisElementPhysicalProperties, MotionModel,
+    /// };
+    ///
+    /// let mut model = MotionModel::new();
+    /// let body = model
+    ///     .add_body(
+    ///         "body".to_string(),
+    ///         Translation3::new(0.0, 0.0, 0.3),
+    ///         UnitQuaternion::identity(),
+    ///         ChassisElementPhysicalProperties::from_mass_properties(
+    ///             10.0,
+    ///             Vector3::zeros(),
+    ///             Matrix3::identity(),
+    ///         ),
+    ///     )
+    ///     .unwrap();
+    ///
+    /// let geometry = CollisionGeometry::new(
+    ///     CollisionShape::Box {
+    ///         size: Vector3::new(1.0, 0.6, 0.2),
+    ///     },
+    ///     Isometry3::translation(0.0, 0.0, 0.1),
+    /// )
+    /// .unwrap();
+    /// model.add_collision_geometry(&body, geometry).unwrap();
+    ///
+    /// let shapes = model.collision_shapes_in_body().unwrap();
+    /// assert_eq!(1, shapes.len());
+    /// assert_eq!(0.1, shapes[0].pose().translation.z);
+    /// ```
+    pub fn add_collision_geometry(
+        &mut self,
+        frame_id: &FrameID,
+        geometry: CollisionGeometry,
+    ) -> Result<(), Error> {
+        if !self.kinematic_tree().has_element(frame_id) {
+            return Err(Error::MissingFrameElement { id: *frame_id });
+        }
+
+        self.collision_geometries
+            .entry(*frame_id)
+            .or_default()
+            .push(geometry);
+        Ok(())
+    }
+
+    /// Returns the collision geometry of the given chassis element, in the order in which it was
+    /// added. Elements without collision geometry return an empty slice.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'frame_id' - The [FrameID] of the element.
+    pub fn collision_geometries(&self, frame_id: &FrameID) -> &[CollisionGeometry] {
+        self.collision_geometries
+            .get(frame_id)
+            .map(|geometries| geometries.as_slice())
+            .unwrap_or(&[])
+    }
+
+    /// Returns the collision shapes of all the chassis elements, ordered by [FrameID], with the
+    /// pose of each shape relative to the body frame for the current positions of the joints.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the model has no body.
+    pub fn collision_shapes_in_body(&self) -> Result<Vec<PosedCollisionShape>, Error> {
+        let mut frames: Vec<&FrameID> = self.collision_geometries.keys().collect();
+        frames.sort();
+
+        let mut shapes = Vec::new();
+        for frame_id in frames {
+            let frame_to_body = self.isometry_to_body(frame_id)?;
+            for geometry in &self.collision_geometries[frame_id] {
+                shapes.push(PosedCollisionShape {
+                    frame: *frame_id,
+                    shape: geometry.shape.clone(),
+                    pose: frame_to_body * geometry.origin,
+                });
+            }
+        }
+
+        Ok(shapes)
+    }
+
+    /// Removes all the collision geometry of the given chassis element and returns it.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'frame_id' - The [FrameID] of the element.
+    pub fn remove_collision_geometries(&mut self, frame_id: &FrameID) -> Vec<CollisionGeometry> {
+        self.collision_geometries
+            .remove(frame_id)
+            .unwrap_or_default()
+    }
+}
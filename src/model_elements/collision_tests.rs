@@ -0,0 +1,165 @@
+use std::f64::consts::PI;
+
+use nalgebra::{Isometry3, Translation3, UnitQuaternion, Vector3};
+
+use crate::{
+    model_elements::{frame_elements::FrameID, model::MotionModel},
+    test_fixtures::physical_properties,
+    Error,
+};
+
+use super::{CollisionGeometry, CollisionShape};
+
+fn sphere(radius: f64) -> CollisionShape {
+    CollisionShape::Sphere { radius }
+}
+
+// Builds a body with an arm that is 1.0 in front of the body and rotated 90 degrees around the
+// z-axis.
+fn build_model() -> (MotionModel, FrameID, FrameID) {
+    let mut model = MotionModel::new();
+    let body = model
+        .add_body(
+            "body".to_string(),
+            Translation3::identity(),
+            UnitQuaternion::identity(),
+            physical_properties(),
+        )
+        .unwrap();
+
+    let arm = model
+        .add_static_chassis_element(
+            "arm".to_string(),
+            body,
+            Translation3::new(1.0, 0.0, 0.0),
+            UnitQuaternion::from_euler_angles(0.0, 0.0, 0.5 * PI),
+            physical_properties(),
+        )
+        .unwrap();
+
+    (model, body, arm)
+}
+
+#[test]
+fn when_creating_a_collision_geometry_it_should_reject_invalid_dimensions() {
+    let invalid = [
+        sphere(0.0),
+        sphere(f64::NAN),
+        CollisionShape::Box {
+            size: Vector3::new(1.0, -1.0, 1.0),
+        },
+        CollisionShape::Cylinder {
+            radius: 0.1,
+            length: f64::INFINITY,
+        },
+        CollisionShape::Mesh {
+            uri: String::new(),
+            scale: Vector3::repeat(1.0),
+        },
+    ];
+    for shape in invalid {
+        assert_eq!(
+            Err(Error::InvalidCollisionShape),
+            CollisionGeometry::new(shape, Isometry3::identity())
+        );
+    }
+
+    let origin = Isometry3::translation(0.0, 0.0, 0.5);
+    let geometry = CollisionGeometry::new(
+        CollisionShape::Mesh {
+            uri: "package://vehicle/meshes/body.stl".to_string(),
+            scale: Vector3::repeat(0.001),
+        },
+        origin,
+    )
+    .unwrap();
+    assert_eq!(&origin, geometry.origin());
+    assert!(matches!(geometry.shape(), CollisionShape::Mesh { .. }));
+}
+
+#[test]
+fn when_adding_collision_geometry_it_should_be_stored_per_element() {
+    let (mut model, body, arm) = build_model();
+    assert!(model.collision_geometries(&arm).is_empty());
+
+    let first = CollisionGeometry::new(sphere(0.1), Isometry3::identity()).unwrap();
+    let second =
+        CollisionGeometry::new(sphere(0.2), Isometry3::translation(0.5, 0.0, 0.0)).unwrap();
+    model.add_collision_geometry(&arm, first.clone()).unwrap();
+    model.add_collision_geometry(&arm, second.clone()).unwrap();
+    assert_eq!(&[first.clone(), second], model.collision_geometries(&arm));
+    assert!(model.collision_geometries(&body).is_empty());
+
+    let missing = FrameID::new();
+    assert_eq!(
+        Err(Error::MissingFrameElement { id: missing }),
+        model.add_collision_geometry(&missing, first)
+    );
+
+    assert_eq!(2, model.remove_collision_geometries(&arm).len());
+    assert!(model.collision_geometries(&arm).is_empty());
+    assert!(model.remove_collision_geometries(&arm).is_empty());
+}
+
+#[test]
+fn when_getting_the_collision_shapes_it_should_pose_them_in_the_body_frame() {
+    let (mut model, body, arm) = build_model();
+    model
+        .add_collision_geometry(
+            &body,
+            CollisionGeometry::new(
+                CollisionShape::Box {
+                    size: Vector3::new(1.0, 0.5, 0.2),
+                },
+                Isometry3::identity(),
+            )
+            .unwrap(),
+        )
+        .unwrap();
+    model
+        .add_collision_geometry(
+            &arm,
+            CollisionGeometry::new(sphere(0.1), Isometry3::translation(0.5, 0.0, 0.0)).unwrap(),
+        )
+        .unwrap();
+
+    let shapes = model.collision_shapes_in_body().unwrap();
+    assert_eq!(2, shapes.len());
+
+    let body_shape = shapes.iter().find(|s| s.frame() == body).unwrap();
+    assert_eq!(&Isometry3::identity(), body_shape.pose());
+
+    // The offset of the sphere is along the x-axis of the arm, which is the y-axis of the body
+    let arm_shape = shapes.iter().find(|s| s.frame() == arm).unwrap();
+    assert_eq!(&sphere(0.1), arm_shape.shape());
+    assert!((arm_shape.pose().translation.vector - Vector3::new(1.0, 0.5, 0.0)).norm() < 1e-12);
+
+    model.remove_element(&arm).unwrap();
+    assert_eq!(1, model.collision_shapes_in_body().unwrap().len());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn when_serializing_it_should_store_the_collision_geometry() {
+    let (mut model, _, arm) = build_model();
+    model
+        .add_collision_geometry(
+            &arm,
+            CollisionGeometry::new(
+                CollisionShape::Cylinder {
+                    radius: 0.1,
+                    length: 0.3,
+                },
+                Isometry3::translation(0.0, 0.0, 0.2),
+            )
+            .unwrap(),
+        )
+        .unwrap();
+
+    let json = serde_json::to_string(&model).unwrap();
+    let loaded: MotionModel = serde_json::from_str(&json).unwrap();
+    assert_eq!(
+        model.collision_geometries(&arm),
+        loaded.collision_geometries(&arm)
+    );
+}
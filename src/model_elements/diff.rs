@@ -0,0 +1,178 @@
+//! Provides the differences between two [MotionModel] instances, e.g. to verify that a
+//! deserialized model matches the configuration of the vehicle as it was built, or to review the
+//! changes to the description of a vehicle.
+//!
+//! The frame elements of the two models are matched by their [FrameID]. A model that is
+//! deserialized keeps the [FrameID] values of the model that was serialized, so a model can be
+//! compared with an earlier version of the same description. Models that were built separately
+//! have different [FrameID] values for all their frame elements.
+
+use super::{frame_elements::FrameID, model::MotionModel};
+
+#[cfg(test)]
+#[path = "diff_tests.rs"]
+mod diff_tests;
+
+/// Describes the differences between two [MotionModel] instances. All the lists are ordered by
+/// [FrameID].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ModelDiff {
+    /// The frame elements that are only part of the other model.
+    added_frames: Vec<FrameID>,
+
+    /// The frame elements that are only part of the original model.
+    removed_frames: Vec<FrameID>,
+
+    /// The frame elements of which the parent, the degree of freedom or the static transform to
+    /// the parent changed.
+    changed_poses: Vec<FrameID>,
+
+    /// The frame elements of which the mass, the center of mass or the moments of inertia
+    /// changed.
+    changed_physical_properties: Vec<FrameID>,
+
+    /// The frame elements of which the joint constraint changed.
+    changed_constraints: Vec<FrameID>,
+}
+
+impl ModelDiff {
+    /// Returns the frame elements that are only part of the other model.
+    pub fn added_frames(&self) -> &[FrameID] {
+        &self.added_frames
+    }
+
+    /// Returns the frame elements of which the joint constraint changed.
+    pub fn changed_constraints(&self) -> &[FrameID] {
+        &self.changed_constraints
+    }
+
+    /// Returns the frame elements of which the mass, the center of mass or the moments of
+    /// inertia changed.
+    pub fn changed_physical_properties(&self) -> &[FrameID] {
+        &self.changed_physical_properties
+    }
+
+    /// Returns the frame elements of which the parent, the degree of freedom or the static
+    /// transform to the parent changed.
+    pub fn changed_poses(&self) -> &[FrameID] {
+        &self.changed_poses
+    }
+
+    /// Returns a value indicating whether the two models are the same.
+    pub fn is_empty(&self) -> bool {
+        self.added_frames.is_empty()
+            && self.removed_frames.is_empty()
+            && self.changed_poses.is_empty()
+            && self.changed_physical_properties.is_empty()
+            && self.changed_constraints.is_empty()
+    }
+
+    /// Returns the frame elements that are only part of the original model.
+    pub fn removed_frames(&self) -> &[FrameID] {
+        &self.removed_frames
+    }
+}
+
+impl MotionModel {
+    /// Returns the differences between this model and the 'other' model. Frames that are added
+    /// in the other model are reported as added and frames that are missing from the other model
+    /// are reported as removed.
+    ///
+    /// Values are compared exactly, so a model only matches a model with the same values, e.g.
+    /// a model that was serialized and deserialized again.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'other' - The model to compare with.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use nalgebra::{Matrix3, Translation3, UnitQuaternion, Vector3};
+    /// use swerve_vehicle_descriptors::model_elements::model::{
+    ///     ChassisElementPhysicalProperties, MotionModel,
+    /// };
+    ///
+    /// let properties =
+    ///     ChassisElementPhysicalProperties::from_mass_properties(1.0, Vector3::zeros(), Matrix3::identity());
+    /// let mut model = MotionModel::new();
+    /// let body = model
+    ///     .add_body(
+    ///         "body".to_string(),
+    ///         Translation3::identity(),
+    ///         UnitQuaternion::identity(),
+    ///         properties.clone(),
+    ///     )
+    ///     .unwrap();
+    /// assert!(model.diff(&model).is_empty());
+    ///
+    /// let mut other = MotionModel::new();
+    /// other
+    ///     .add_body(
+    ///         "body".to_string(),
+    ///         Translation3::identity(),
+    ///         UnitQuaternion::identity(),
+    ///         properties,
+    ///     )
+    ///     .unwrap();
+    ///
+    /// // Models that were built separately do not share any frames
+    /// let diff = model.diff(&other);
+    /// assert_eq!(&[body], diff.removed_frames());
+    /// assert_eq!(1, diff.added_frames().len());
+    /// ```
+    pub fn diff(&self, other: &MotionModel) -> ModelDiff {
+        let mut diff = ModelDiff::default();
+
+        for frame in other.kinematic_tree().elements() {
+            if !self.kinematic_tree().has_element(frame.id()) {
+                diff.added_frames.push(*frame.id());
+            }
+        }
+
+        for frame in self.kinematic_tree().elements() {
+            let id = frame.id();
+            if !other.kinematic_tree().has_element(id) {
+                diff.removed_frames.push(*id);
+                continue;
+            }
+
+            let pose_changed = self.parent_of(id).ok() != other.parent_of(id).ok()
+                || self.frame_degree_of_freedom(id).ok() != other.frame_degree_of_freedom(id).ok()
+                || self.static_transform_to_parent(id).ok()
+                    != other.static_transform_to_parent(id).ok();
+            if pose_changed {
+                diff.changed_poses.push(*id);
+            }
+
+            let properties_changed = match (self.chassis_element(id), other.chassis_element(id)) {
+                (Ok(element), Ok(other_element)) => {
+                    element.mass_in_kg() != other_element.mass_in_kg()
+                        || element.center_of_mass() != other_element.center_of_mass()
+                        || element.moment_of_inertia() != other_element.moment_of_inertia()
+                        || element.spatial_inertia() != other_element.spatial_inertia()
+                }
+                (element, other_element) => element.is_ok() != other_element.is_ok(),
+            };
+            if properties_changed {
+                diff.changed_physical_properties.push(*id);
+            }
+
+            if self.joint_constraint(id).ok() != other.joint_constraint(id).ok() {
+                diff.changed_constraints.push(*id);
+            }
+        }
+
+        for frames in [
+            &mut diff.added_frames,
+            &mut diff.removed_frames,
+            &mut diff.changed_poses,
+            &mut diff.changed_physical_properties,
+            &mut diff.changed_constraints,
+        ] {
+            frames.sort();
+        }
+
+        diff
+    }
+}
@@ -0,0 +1,122 @@
+use nalgebra::{Translation3, UnitQuaternion};
+
+use crate::{
+    model_elements::{
+        frame_elements::{FrameDofType, FrameID, JointConstraint},
+        model::MotionModel,
+    },
+    test_fixtures::physical_properties,
+};
+
+// Builds a body with a suspension element.
+fn build_model() -> (MotionModel, FrameID, FrameID) {
+    let mut model = MotionModel::new();
+    let body = model
+        .add_body(
+            "body".to_string(),
+            Translation3::identity(),
+            UnitQuaternion::identity(),
+            physical_properties(),
+        )
+        .unwrap();
+    let suspension = model
+        .add_suspension_element(
+            "suspension".to_string(),
+            FrameDofType::PrismaticZ,
+            body,
+            Translation3::new(1.0, 0.5, 0.0),
+            UnitQuaternion::identity(),
+            physical_properties(),
+            JointConstraint::new(),
+        )
+        .unwrap();
+
+    (model, body, suspension)
+}
+
+#[test]
+fn when_comparing_separately_built_models_it_should_report_all_frames() {
+    let (model, body, suspension) = build_model();
+    assert!(model.diff(&model).is_empty());
+
+    let (other, other_body, other_suspension) = build_model();
+    let diff = model.diff(&other);
+    assert!(!diff.is_empty());
+
+    let mut removed = vec![body, suspension];
+    removed.sort();
+    assert_eq!(removed.as_slice(), diff.removed_frames());
+
+    let mut added = vec![other_body, other_suspension];
+    added.sort();
+    assert_eq!(added.as_slice(), diff.added_frames());
+    assert!(diff.changed_poses().is_empty());
+    assert!(diff.changed_physical_properties().is_empty());
+    assert!(diff.changed_constraints().is_empty());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn when_comparing_a_deserialized_model_it_should_report_the_changes() {
+    let (model, body, suspension) = build_model();
+
+    let loaded: MotionModel =
+        serde_json::from_str(&serde_json::to_string(&model).unwrap()).unwrap();
+    assert!(model.diff(&loaded).is_empty());
+
+    // Change the mass of the body in the stored description
+    let mut description = serde_json::to_value(&model).unwrap();
+    for entry in description["chassis_elements"].as_array_mut().unwrap() {
+        if serde_json::from_value::<FrameID>(entry[0].clone()).unwrap() == body {
+            entry[1]["mass_in_kg"] = serde_json::json!(2.0);
+        }
+    }
+    let mut changed: MotionModel = serde_json::from_value(description).unwrap();
+    changed
+        .set_joint_constraint(
+            &suspension,
+            JointConstraint::new().with_position_limits(-0.1, 0.1),
+        )
+        .unwrap();
+    let extra = changed
+        .add_static_chassis_element(
+            "bumper".to_string(),
+            body,
+            Translation3::new(1.0, 0.0, 0.0),
+            UnitQuaternion::identity(),
+            physical_properties(),
+        )
+        .unwrap();
+
+    let diff = model.diff(&changed);
+    assert_eq!(&[extra], diff.added_frames());
+    assert!(diff.removed_frames().is_empty());
+    assert!(diff.changed_poses().is_empty());
+    assert_eq!(&[body], diff.changed_physical_properties());
+    assert_eq!(&[suspension], diff.changed_constraints());
+
+    let reverse = changed.diff(&model);
+    assert_eq!(&[extra], reverse.removed_frames());
+    assert_eq!(&[suspension], reverse.changed_constraints());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn when_a_calibrated_pose_is_written_back_it_should_report_the_changed_pose() {
+    let (model, _, suspension) = build_model();
+
+    let mut calibrated: MotionModel =
+        serde_json::from_str(&serde_json::to_string(&model).unwrap()).unwrap();
+    calibrated
+        .set_transform_to_parent(
+            &suspension,
+            Translation3::new(1.01, 0.49, 0.0),
+            UnitQuaternion::from_euler_angles(0.0, 0.0, 0.01),
+        )
+        .unwrap();
+
+    let diff = model.diff(&calibrated);
+    assert_eq!(&[suspension], diff.changed_poses());
+    assert!(diff.changed_physical_properties().is_empty());
+    assert!(diff.changed_constraints().is_empty());
+}
@@ -3,34 +3,54 @@
 extern crate nalgebra as na;
 
 use std::{
+    collections::VecDeque,
     fmt::Display,
     sync::{
-        atomic::{AtomicUsize, Ordering},
+        atomic::{AtomicU64, AtomicUsize, Ordering},
         Arc, Mutex,
     },
+    time::{Duration, Instant},
 };
 
 use crossbeam_channel::Sender;
-use na::{Matrix3, Matrix6, Vector3};
+use na::{Matrix3, Matrix6, Unit, Vector3};
 
 use crate::{
-    change_notification_processing::HardwareChangeProcessor,
+    change_notification_processing::{ChangeID, HardwareChangeProcessor},
     hardware::{
         actuator_interface::{ActuatorAvailableRatesOfChange, HardwareActuator},
-        joint_state::JointState,
+        command_middleware::{process_command, CommandMiddleware, CommandOutcome},
+        health::{ActuatorFault, ActuatorHealth},
+        joint_profile::JointProfile,
+        joint_state::{JointState, TimestampedJointState},
         sensor_interface::HardwareSensor,
+        state_middleware::{process_state, StateMiddleware},
     },
+    instrumentation::event,
     Error,
 };
 
-use crate::number_space::{to_number_space, RealNumberValueSpace};
+use crate::number_space::{to_number_space, NumberSpaceType, RealNumberValueSpace};
+
+use super::model_event::{publish_to_target, ModelEvent, ModelEventPublisher, ModelEventTarget};
+
+#[cfg(feature = "tracing")]
+use super::model_event::target_frame;
+
+#[cfg(feature = "async")]
+use crate::{
+    change_notification_processing::AsyncHardwareChangeProcessor,
+    hardware::async_interface::{AsyncHardwareActuator, AsyncHardwareSensor},
+};
 
 #[cfg(test)]
+#[allow(clippy::clone_on_copy, clippy::needless_range_loop)]
 #[path = "frame_elements_tests.rs"]
 mod frame_elements_tests;
 
 /// Defines the degree-of-freedom for a frame element relative to the parent.
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum FrameDofType {
     /// The frame element is static relative to the parent.
     Static,
@@ -58,6 +78,74 @@ pub enum FrameDofType {
     /// the parent frame. The element translates along the Y-axis of the element
     /// connection point with the parent frame.
     PrismaticZ,
+    /// The frame element has a rotational degree-of-freedom relative to the parent frame. The
+    /// element rotates around the given axis, expressed in the parent frame, through the
+    /// element connection point with the parent frame.
+    RevoluteAxis(Unit<Vector3<f64>>),
+    /// The frame element has a linear translation degree-of-freedom relative to the parent
+    /// frame. The element translates along the given axis, expressed in the parent frame.
+    PrismaticAxis(Unit<Vector3<f64>>),
+}
+
+impl FrameDofType {
+    /// Returns the axis, in the parent frame, along which the element moves or around which
+    /// the element rotates, or [None] if the element is static.
+    pub fn axis(&self) -> Option<Vector3<f64>> {
+        match self {
+            FrameDofType::Static => None,
+            FrameDofType::RevoluteX | FrameDofType::PrismaticX => Some(Vector3::x()),
+            FrameDofType::RevoluteY | FrameDofType::PrismaticY => Some(Vector3::y()),
+            FrameDofType::RevoluteZ | FrameDofType::PrismaticZ => Some(Vector3::z()),
+            FrameDofType::RevoluteAxis(axis) | FrameDofType::PrismaticAxis(axis) => {
+                Some(axis.into_inner())
+            }
+        }
+    }
+
+    /// Returns a value indicating whether the element translates relative to its parent.
+    pub fn is_prismatic(&self) -> bool {
+        matches!(
+            self,
+            FrameDofType::PrismaticX
+                | FrameDofType::PrismaticY
+                | FrameDofType::PrismaticZ
+                | FrameDofType::PrismaticAxis(_)
+        )
+    }
+
+    /// Returns a value indicating whether the element rotates relative to its parent.
+    pub fn is_revolute(&self) -> bool {
+        matches!(
+            self,
+            FrameDofType::RevoluteX
+                | FrameDofType::RevoluteY
+                | FrameDofType::RevoluteZ
+                | FrameDofType::RevoluteAxis(_)
+        )
+    }
+}
+
+/// Defines the role a frame element plays in the vehicle.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FrameRole {
+    /// The frame element is the body of the vehicle.
+    Body,
+    /// The frame element is part of the suspension between a wheel and the body.
+    Suspension,
+    /// The frame element steers a wheel.
+    Steering,
+    /// The frame element is a wheel.
+    Wheel,
+    /// The frame element is rigidly attached to its parent, e.g. a bumper or a sensor mount.
+    Attachment,
+    /// The frame element is moved by an actuator but it is not part of a module, e.g. a lift.
+    Actuated,
+    /// The frame element has no specific role. The [KinematicTree](crate::model_elements::model::KinematicTree)
+    /// assumes that frames without a role are wheels if they rotate around the y-axis and have
+    /// no children.
+    #[default]
+    Generic,
 }
 
 /// The FrameID counter value for the 'NONE' ID.
@@ -117,11 +205,80 @@ impl AsRef<FrameID> for FrameID {
     }
 }
 
+#[cfg(feature = "serde")]
+impl serde::Serialize for FrameID {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_u64(self.id as u64)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for FrameID {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let id = <usize as serde::Deserialize>::deserialize(deserializer)?;
+
+        // Make sure that IDs created after loading a model do not clash with the loaded IDs
+        if id != NONE_FRAME_ID {
+            let next = id.checked_add(1).ok_or_else(|| {
+                <D::Error as serde::de::Error>::custom(format!(
+                    "the frame ID {} leaves no room for new frame IDs",
+                    id
+                ))
+            })?;
+            FRAME_ID_COUNTER.fetch_max(next, Ordering::SeqCst);
+        }
+
+        Ok(Self { id })
+    }
+}
+
+/// Serializes a map with [FrameID] keys as a list of key-value pairs, ordered by key, so that
+/// the map can be stored in formats that only allow string keys, e.g. JSON or TOML.
+#[cfg(feature = "serde")]
+pub(crate) mod frame_id_map {
+    use std::collections::HashMap;
+
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::FrameID;
+
+    /// Serializes the map as a list of key-value pairs.
+    pub(crate) fn serialize<S, V>(
+        map: &HashMap<FrameID, V>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+        V: Serialize,
+    {
+        let mut entries: Vec<(&FrameID, &V)> = map.iter().collect();
+        entries.sort_by_key(|(k, _)| **k);
+        serializer.collect_seq(entries)
+    }
+
+    /// Deserializes the map from a list of key-value pairs.
+    pub(crate) fn deserialize<'de, D, V>(deserializer: D) -> Result<HashMap<FrameID, V>, D::Error>
+    where
+        D: Deserializer<'de>,
+        V: Deserialize<'de>,
+    {
+        let entries = Vec::<(FrameID, V)>::deserialize(deserializer)?;
+        Ok(entries.into_iter().collect())
+    }
+}
+
 /// Defines a single reference frame for use in a robotic model.
 ///
 /// The frame has a cartesian right-handed coordinate system with the origin
 /// defined at the joint location to the parent frame, or in the geometric middle
 /// if there is no parent frame.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ReferenceFrame {
     /// The human readable name for the element.
     name: String,
@@ -134,14 +291,20 @@ pub struct ReferenceFrame {
     /// - Rotational / Revolute around one of the axes
     /// - Translational / Prismatic along one of the axes
     ///
-    /// An element can only have 1 degree of freedom. For cases where multiple degrees of freedom
-    /// are required it is necessary to define multiple elements and child elements.
+    /// An element has at most 1 degree of freedom. A joint with multiple degrees of freedom is a
+    /// chain of elements that is created with
+    /// [MotionModel::add_multi_dof_joint](crate::model_elements::model::MotionModel::add_multi_dof_joint),
+    /// see the [multi_dof](crate::model_elements::multi_dof) module.
     degree_of_freedom_kind: FrameDofType,
 
     /// The homogeneous transform from the current frame to the parent frame at displacement = 0
     /// Homogeneous transform is 4x4 matrix: 3x4 matrix = [R|t] on top, bottom row = [0 0 0 1]
     // frame_transform_to_parent: Matrix4<f64>,
     is_actuated: bool,
+
+    /// The role the element plays in the vehicle.
+    #[cfg_attr(feature = "serde", serde(default))]
+    role: FrameRole,
 }
 
 impl ReferenceFrame {
@@ -150,8 +313,13 @@ impl ReferenceFrame {
     /// - Rotational / Revolute around one of the axes
     /// - Translational / Prismatic along one of the axes
     ///
-    /// An element can only have 1 degree of freedom. For cases where multiple degrees of freedom
-    /// are required it is necessary to define multiple elements and child elements.
+    /// An element has at most 1 degree of freedom. The joints with multiple degrees of freedom,
+    /// see [MultiDofJointKind](crate::model_elements::multi_dof::MultiDofJointKind), are added with
+    /// [MotionModel::add_multi_dof_joint](crate::model_elements::model::MotionModel::add_multi_dof_joint)
+    /// as a chain of elements that each have one of the degrees of freedom, see the
+    /// [multi_dof](crate::model_elements::multi_dof) module. The combined state of such a joint is
+    /// provided by
+    /// [MotionModel::multi_dof_joint_state](crate::model_elements::model::MotionModel::multi_dof_joint_state).
     pub fn degree_of_freedom_kind(&self) -> FrameDofType {
         self.degree_of_freedom_kind
     }
@@ -171,18 +339,47 @@ impl ReferenceFrame {
         self.name.as_ref()
     }
 
-    /// Creates a new ReferenceFrame.
+    /// Creates a new ReferenceFrame with the [FrameRole::Generic] role.
     pub fn new(name: String, degree_of_freedom_kind: FrameDofType, is_actuated: bool) -> Self {
+        Self::new_with_role(
+            name,
+            degree_of_freedom_kind,
+            is_actuated,
+            FrameRole::Generic,
+        )
+    }
+
+    /// Creates a new ReferenceFrame with the given role.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'name' - The human readable name for the element.
+    /// * 'degree_of_freedom_kind' - The degree of freedom of the element relative to its parent.
+    /// * 'is_actuated' - A flag indicating if the element is moved by an actuator.
+    /// * 'role' - The role the element plays in the vehicle.
+    pub fn new_with_role(
+        name: String,
+        degree_of_freedom_kind: FrameDofType,
+        is_actuated: bool,
+        role: FrameRole,
+    ) -> Self {
         Self {
             name,
             id: FrameID::new(),
             degree_of_freedom_kind,
             is_actuated,
+            role,
         }
     }
+
+    /// Returns the role the element plays in the vehicle.
+    pub fn role(&self) -> FrameRole {
+        self.role
+    }
 }
 
 /// Defines a part of the chassis that has its own [ReferenceFrame]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ChassisElement {
     /// Defines the mass of the element in kg.
     mass_in_kg: f64,
@@ -278,20 +475,216 @@ pub struct JointSensor {
     // (and ideally overlapping)
     /// The current state of the actuator. Updated by a closure function which is invoked
     /// by the [HardwareChangeProcessor]
-    current_state: Arc<Mutex<JointState>>,
+    current_state: Arc<Mutex<CurrentSensorState>>,
 
     /// The number space for the actuator. Used to determine how the actuator behaves at
     /// the extremes of the number range, i.e. for linear it will stop, but for revolute
     /// it will continue on the other side of the number range.
     number_space: Box<dyn RealNumberValueSpace>,
+
+    /// The kind of number space for the sensor, as reported by the hardware.
+    number_space_type: NumberSpaceType,
+
+    /// The middleware that processes the states received from the hardware, in order, before
+    /// they are stored.
+    state_middleware: Arc<Mutex<Vec<Box<dyn StateMiddleware>>>>,
+
+    /// The transmission and the calibration that convert the states received from the hardware
+    /// into the states of the joint. Shared with the task that processes the states.
+    conversion: Arc<Mutex<HardwareConversion>>,
+
+    /// The number of times the state of the sensor has been updated by the
+    /// [HardwareChangeProcessor]. Used to detect changes without locking the state.
+    state_version: Arc<AtomicU64>,
+
+    /// The number of states received from the hardware that were rejected because they
+    /// contained values that are not finite or because they were rejected by the middleware.
+    rejected_state_count: Arc<AtomicU64>,
+
+    /// The frame element the sensor is attached to and the publisher of the model that
+    /// contains it. Used to publish the events for the sensor.
+    event_target: ModelEventTarget,
+
+    /// The ID with which the hardware notifies the [HardwareChangeProcessor] of new states, or
+    /// [None] if the hardware publishes its states through a tokio channel.
+    change_id: Option<ChangeID>,
 }
 
 impl JointSensor {
+    /// Adds a [StateMiddleware] to the end of the chain of middleware that processes the
+    /// states received from the hardware. The middleware is executed after all the middleware
+    /// that was added before it.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'middleware' - The middleware that should be added.
+    pub fn add_state_middleware(&mut self, middleware: impl StateMiddleware + 'static) {
+        add_to_state_middleware(&self.state_middleware, Box::new(middleware));
+    }
+
+    /// Returns the calibration that converts the states received from the hardware into the
+    /// states of the joint.
+    pub fn calibration(&self) -> JointCalibration {
+        self.conversion
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .calibration
+    }
+
+    /// Returns the [ChangeID] with which the hardware notifies the [HardwareChangeProcessor] of
+    /// new states, or [None] if the hardware publishes its states through a tokio channel. Used
+    /// to relate the [metrics](crate::change_notification_processing::metrics) of the processor
+    /// to the joint.
+    pub fn change_id(&self) -> Option<ChangeID> {
+        self.change_id
+    }
+
+    /// Returns the states received from the hardware that are stored in the history of the
+    /// sensor, oldest first. The history is empty unless a capacity was set with
+    /// [JointSensor::set_history_capacity].
+    pub fn history(&self) -> Vec<TimestampedJointState> {
+        self.current_state
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .history
+            .states
+            .iter()
+            .copied()
+            .collect()
+    }
+
+    /// Returns the maximum number of states that are stored in the history of the sensor.
+    pub fn history_capacity(&self) -> usize {
+        self.current_state
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .history
+            .capacity
+    }
+
+    /// Returns the last state received from the hardware together with the time at which it
+    /// was received, or [None] if no state has been received yet.
+    pub fn last_update(&self) -> Option<TimestampedJointState> {
+        let state = self
+            .current_state
+            .lock()
+            .unwrap_or_else(|err| err.into_inner());
+        state
+            .received_at
+            .map(|received_at| TimestampedJointState::new(state.state, received_at))
+    }
+
     /// Returns the number space for the sensor
     pub fn numberspace(&self) -> &dyn RealNumberValueSpace {
         self.number_space.as_ref()
     }
 
+    /// Returns the kind of number space for the sensor
+    pub fn number_space_type(&self) -> NumberSpaceType {
+        self.number_space_type
+    }
+
+    /// Returns the number of states received from the hardware that were rejected because
+    /// they contained a NaN or infinite value, or because they were rejected by a
+    /// [StateMiddleware]. Rejected states are discarded and the sensor keeps the last valid
+    /// state.
+    pub fn rejected_state_count(&self) -> u64 {
+        self.rejected_state_count.load(Ordering::SeqCst)
+    }
+
+    /// Sets the calibration that converts the states received from the hardware into the
+    /// states of the joint. The calibration is applied after the [StateMiddleware] and the
+    /// [JointTransmission], to the states that are received from then on.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'calibration' - The calibration of the sensor.
+    pub fn set_calibration(&mut self, calibration: JointCalibration) {
+        self.conversion
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .calibration = calibration;
+    }
+
+    /// Sets the maximum number of states received from the hardware that are stored in the
+    /// history of the sensor, see [JointSensor::state_at]. The oldest states are removed when the
+    /// history is full. A capacity of zero, the default, disables the history.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'capacity' - The maximum number of states in the history.
+    pub fn set_history_capacity(&mut self, capacity: usize) {
+        self.current_state
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .history
+            .set_capacity(capacity);
+    }
+
+    /// Sets the [JointTransmission] between the hardware and the joint. The transmission is
+    /// applied after the [StateMiddleware] and before the [JointCalibration], to the states
+    /// that are received from then on.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'transmission' - The transmission between the hardware and the joint.
+    pub fn set_transmission(&mut self, transmission: JointTransmission) {
+        self.conversion
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .transmission = transmission;
+    }
+
+    /// Links the sensor to the frame element it is attached to, so that the events for the
+    /// sensor are published to the subscribers of the model. Pass [None] when the sensor is
+    /// removed from the model.
+    pub(crate) fn set_event_target(&self, target: Option<(FrameID, ModelEventPublisher)>) {
+        *self
+            .event_target
+            .lock()
+            .unwrap_or_else(|err| err.into_inner()) = target;
+    }
+
+    /// Returns the state of the joint at the given time, interpolated between the states in
+    /// the history of the sensor. Positions are interpolated along the shortest path in the number
+    /// space of the sensor. The newest state is returned for times after the newest state in
+    /// the history.
+    ///
+    /// Returns [None] when the time is before the oldest state in the history, or when the
+    /// history is empty.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'time' - The time at which the state is required, e.g. the time at which a camera
+    ///   image was captured.
+    pub fn state_at(&self, time: Instant) -> Option<JointState> {
+        self.current_state
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .history
+            .state_at(time, self.number_space.as_ref())
+    }
+
+    /// Returns the number of times the state of the sensor has been updated. The value
+    /// changes each time a new state is received from the hardware.
+    pub(crate) fn state_version(&self) -> u64 {
+        self.state_version.load(Ordering::SeqCst)
+    }
+
+    /// Returns the [JointTransmission] between the hardware and the joint.
+    pub fn transmission(&self) -> JointTransmission {
+        self.conversion
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .transmission
+    }
+
+    /// Returns the sensor value at the current time without waiting for the lock on the state,
+    /// or [None] if the state is being updated by the hardware at this moment.
+    pub fn try_value(&self) -> Option<JointState> {
+        self.current_state.try_lock().ok().map(|r| r.state)
+    }
+
     /// Returns the sensor value at the current time.
     #[cfg_attr(test, mutants::skip)] // Cannot easily check mutations as this is a threaded lock situation
     pub fn value(&self) -> Result<JointState, Error> {
@@ -299,12 +692,7 @@ impl JointSensor {
         while retries < 3 {
             match self.current_state.lock() {
                 Ok(r) => {
-                    return Ok(JointState::new(
-                        r.position(),
-                        *r.velocity(),
-                        *r.acceleration(),
-                        *r.jerk(),
-                    ));
+                    return Ok(r.state);
                 }
                 Err(_) => {
                     // Failed to lock. Wait and try again.
@@ -326,38 +714,88 @@ impl JointSensor {
         sensor: &mut impl HardwareSensor,
         change_processor: &HardwareChangeProcessor,
     ) -> Result<Self, Error> {
-        // Initially set the current state and the rates of change to be zero. These values will be overwritten
-        // as soon as we get our first set of data from the actual actuator.
-        let current_state = Arc::new(Mutex::new(JointState::new(
-            0.0,
-            Some(0.0),
-            Some(0.0),
-            Some(0.0),
-        )));
-        let current_state_clone = current_state.clone();
-
-        let number_space = to_number_space(sensor.joint_motion_type());
-        let result = Self {
-            current_state,
-            number_space,
-        };
+        let mut result = Self::with_number_space_type(sensor.joint_motion_type());
+        let update = result.state_updater();
 
         let state_reciever = sensor.current_state_receiver()?;
+        let drain_waiting_states = change_processor.discards_notifications();
         let on_notify_of_change = Box::new(move || {
-            let result = state_reciever.recv();
-            if result.is_err() {
-                // Something isn't right. Nothing we can do. Just continue with the code
-                return;
+            if drain_waiting_states {
+                // Process all the waiting states so that no states are left behind when the
+                // change processor coalesces or drops notifications. States that arrive while
+                // processing are left for the next notification.
+                let number_of_states = state_reciever.len();
+                for state in state_reciever.try_iter().take(number_of_states) {
+                    update(state);
+                }
+            } else if let Ok(state) = state_reciever.recv() {
+                update(state);
             }
 
-            let s = result.unwrap();
+            // If the receive failed something isn't right. Nothing we can do. Just continue
+            // with the code
+        });
+
+        let (sender, id) = change_processor.add(on_notify_of_change)?;
+        sensor.on_change(id, sender);
+        result.change_id = Some(id);
+
+        Ok(result)
+    }
+
+    /// Creates a new [JointSensor] instance for hardware that publishes its state through a
+    /// tokio channel. This function is only available when the `async` feature is enabled.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'sensor' - The hardware interface that points to the actual sensor.
+    /// * 'change_processor' - The change processor that runs the task that processes updates
+    ///   from the hardware sensor.
+    #[cfg(feature = "async")]
+    pub fn new_async(
+        sensor: &impl AsyncHardwareSensor,
+        change_processor: &AsyncHardwareChangeProcessor,
+    ) -> Result<Self, Error> {
+        let result = Self::with_number_space_type(sensor.joint_motion_type());
+        change_processor.watch(sensor.current_state_receiver()?, result.state_updater());
+
+        Ok(result)
+    }
+
+    /// Returns the closure that processes a state received from the hardware and stores it
+    /// as the current state of the sensor.
+    fn state_updater(&self) -> impl Fn(JointState) + Send + Sync + 'static {
+        let current_state = self.current_state.clone();
+        let state_version = self.state_version.clone();
+        let state_middleware = self.state_middleware.clone();
+        let conversion = self.conversion.clone();
+        let rejected_state_count = self.rejected_state_count.clone();
+        let event_target = self.event_target.clone();
+        move |state| {
+            // A corrupted state would poison every value derived from it, so keep the
+            // last valid state instead.
+            let s = match process_state_with(&state_middleware, state) {
+                Some(s) => convert_to_joint(&conversion, s),
+                None => {
+                    rejected_state_count.fetch_add(1, Ordering::SeqCst);
+                    event!(WARN, frame = ?target_frame(&event_target), "Rejected a state from the joint sensor");
+                    return;
+                }
+            };
+            event!(TRACE, frame = ?target_frame(&event_target), position = s.position(), "Received a state from the joint sensor");
 
             let mut retries = 0;
             while retries < 3 {
-                match current_state_clone.lock() {
+                match current_state.lock() {
                     Ok(r) => {
                         let mut mutable_state = r;
-                        *mutable_state = s;
+                        let received_at = Instant::now();
+                        mutable_state.state = s;
+                        mutable_state.received_at = Some(received_at);
+                        mutable_state
+                            .history
+                            .push(TimestampedJointState::new(s, received_at));
+                        state_version.fetch_add(1, Ordering::SeqCst);
                         break;
                     }
                     Err(_) => {
@@ -367,28 +805,64 @@ impl JointSensor {
                 };
             }
 
-            // Updated, yay
-        });
-
-        let (sender, id) = match change_processor.add(on_notify_of_change) {
-            Ok(r) => r,
-            Err(e) => return Err(e),
-        };
-        sensor.on_change(id, sender);
+            publish_to_target(&event_target, |frame| ModelEvent::JointStateUpdated {
+                frame,
+            });
+        }
+    }
 
-        Ok(result)
+    /// Creates a new [JointSensor] instance with the given kind of number space and a zero
+    /// state.
+    fn with_number_space_type(number_space_type: NumberSpaceType) -> Self {
+        // Initially set the current state and the rates of change to be zero. These values will be overwritten
+        // as soon as we get our first set of data from the actual sensor.
+        Self {
+            current_state: Arc::new(Mutex::new(CurrentSensorState {
+                state: JointState::new(0.0, Some(0.0), Some(0.0), Some(0.0)),
+                received_at: None,
+                history: StateHistory::default(),
+            })),
+            number_space: to_number_space(number_space_type),
+            number_space_type,
+            state_middleware: Arc::new(Mutex::new(Vec::new())),
+            conversion: Arc::new(Mutex::new(HardwareConversion::default())),
+            state_version: Arc::new(AtomicU64::new(0)),
+            rejected_state_count: Arc::new(AtomicU64::new(0)),
+            event_target: Arc::new(Mutex::new(None)),
+            change_id: None,
+        }
     }
 }
 
+/// Stores the current state of a sensor.
+struct CurrentSensorState {
+    /// The current state of the joint.
+    state: JointState,
+
+    /// The time at which the current state was received, or [None] if the state has not been
+    /// received from the hardware yet.
+    received_at: Option<Instant>,
+
+    /// The last states received from the hardware.
+    history: StateHistory,
+}
+
 /// Stores the current state and achievable rates of change for an actuator at a given point in time.
 struct CurrentActuatorState {
     /// The current state of the reference frame attached to the moving part of the actuator
     state: JointState,
 
+    /// The time at which the current state was received, or [None] if the state has not been
+    /// received from the hardware yet.
+    received_at: Option<Instant>,
+
     /// The maximum and minimum rates of change available for the actuator at the current 'state',
     /// i.e. the maximum and minimum values of velocity, acceleration and jerk that the actuator
     /// could attain at the current state.
     rates_of_change: ActuatorAvailableRatesOfChange,
+
+    /// The last states received from the hardware.
+    history: StateHistory,
 }
 
 impl CurrentActuatorState {
@@ -403,8 +877,139 @@ impl CurrentActuatorState {
     fn new(state: JointState, rates_of_change: ActuatorAvailableRatesOfChange) -> Self {
         Self {
             state,
+            received_at: None,
             rates_of_change,
+            history: StateHistory::default(),
+        }
+    }
+}
+
+/// Stores the last states received from the hardware of a joint, oldest first.
+#[derive(Default)]
+struct StateHistory {
+    /// The maximum number of states that are stored. No states are stored when the capacity
+    /// is zero.
+    capacity: usize,
+
+    /// The stored states, ordered by the time at which they were received.
+    states: VecDeque<TimestampedJointState>,
+}
+
+impl StateHistory {
+    /// Stores the given state, removing the oldest state if the history is full.
+    fn push(&mut self, state: TimestampedJointState) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        while self.states.len() >= self.capacity {
+            self.states.pop_front();
+        }
+        self.states.push_back(state);
+    }
+
+    /// Changes the maximum number of states that are stored, removing the oldest states if
+    /// there are more states than the new capacity.
+    fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        while self.states.len() > capacity {
+            self.states.pop_front();
+        }
+    }
+
+    /// Returns the state at the given time, interpolated between the stored states on either
+    /// side of the time. Returns the newest state for times after the newest state and [None]
+    /// for times before the oldest state.
+    fn state_at(
+        &self,
+        time: Instant,
+        number_space: &dyn RealNumberValueSpace,
+    ) -> Option<JointState> {
+        let index = self
+            .states
+            .partition_point(|state| state.received_at() <= time);
+        if index == 0 {
+            return None;
+        }
+
+        let start = &self.states[index - 1];
+        let end = match self.states.get(index) {
+            Some(end) => end,
+            None => return Some(*start.state()),
+        };
+
+        let fraction = (time - start.received_at()).as_secs_f64()
+            / (end.received_at() - start.received_at()).as_secs_f64();
+        let interpolate = |a: f64, b: f64| a + (b - a) * fraction;
+        let interpolate_option = |a: &Option<f64>, b: &Option<f64>| match (a, b) {
+            (Some(a), Some(b)) => Some(interpolate(*a, *b)),
+            _ => None,
+        };
+
+        let (start, end) = (start.state(), end.state());
+        Some(JointState::new(
+            number_space.interpolate_values(start.position(), end.position(), fraction),
+            interpolate_option(start.velocity(), end.velocity()),
+            interpolate_option(start.acceleration(), end.acceleration()),
+            interpolate_option(start.jerk(), end.jerk()),
+        ))
+    }
+}
+
+/// Stores the [JointProfile] that an actuator is following.
+struct ActiveProfile {
+    /// The ID of the periodic task that dispatches the profile. Used by the task to detect that
+    /// the profile was replaced or cancelled.
+    id: ChangeID,
+
+    /// The profile, after its points were processed by the command middleware and the
+    /// joint constraint.
+    profile: JointProfile,
+
+    /// The time at which the profile was submitted.
+    started_at: Instant,
+}
+
+/// Defines the channels through which the commands are sent to the hardware of an [Actuator].
+#[derive(Clone)]
+enum CommandSender {
+    /// The crossbeam channel of a [HardwareActuator].
+    Channel(Sender<JointState>),
+
+    /// The tokio channel of an [AsyncHardwareActuator].
+    #[cfg(feature = "async")]
+    Async(tokio::sync::mpsc::Sender<JointState>),
+}
+
+impl CommandSender {
+    /// Sends the command to the hardware without waiting. Records an
+    /// [ActuatorFault::Disconnected] in 'fault' when the hardware closed the channel.
+    fn send(&self, command: JointState, fault: &Mutex<Option<ActuatorFault>>) -> Result<(), Error> {
+        let (is_disconnected, source): (bool, Box<dyn std::error::Error + Send + Sync>) = match self
+        {
+            // A crossbeam channel only fails to send when the receiver is gone
+            CommandSender::Channel(sender) => match sender.send(command) {
+                Ok(()) => return Ok(()),
+                Err(error) => (true, Box::new(error)),
+            },
+            #[cfg(feature = "async")]
+            CommandSender::Async(sender) => match sender.try_send(command) {
+                Ok(()) => return Ok(()),
+                Err(error) => (
+                    matches!(error, tokio::sync::mpsc::error::TrySendError::Closed(_)),
+                    Box::new(error),
+                ),
+            },
+        };
+
+        if is_disconnected {
+            record_fault(fault, ActuatorFault::Disconnected);
         }
+
+        Err(Error::Channel {
+            context: "Failed to send the command to the actuator.".to_string(),
+            source,
+        })
     }
 }
 
@@ -412,9 +1017,12 @@ impl CurrentActuatorState {
 ///
 /// ## Notes
 ///
-/// * It is assumed that once reference frames and/or chassis elements are created they
-///   are never removed and will live for the application life time. This is reflected
-///   in the fact that you cannot remove an actuator.
+/// * An actuator lives as long as the reference frame it is attached to. Use
+///   [MotionModel::replace_actuator](super::model::MotionModel::replace_actuator) to swap the
+///   actuator for a reference frame.
+/// * States received from the hardware that contain NaN or infinite values are rejected and
+///   counted, see [Actuator::rejected_state_count]. The actuator keeps the last valid state
+///   so that the transforms computed from it stay valid.
 pub struct Actuator {
     // Might need a reference frame upon which the actuator acts, i.e. the velocity is determined
     // as the relative velocity between two reference frames, one attached to the non-moving part
@@ -430,136 +1038,950 @@ pub struct Actuator {
     /// it will continue on the other side of the number range.
     number_space: Box<dyn RealNumberValueSpace>,
 
+    /// The kind of number space for the actuator.
+    number_space_type: NumberSpaceType,
+
     // TODO: The command sender should be sending a joint state to achieve and the
     //       approach to achieve it, i.e. the velocity, acceleration and jerk as well
     //       as the profile to achieve this.
     /// The channel sender that is used to send a state change command to the actuator
-    command_sender: Sender<JointState>,
-}
+    command_sender: CommandSender,
 
-impl Actuator {
-    /// Returns the number space for the actuator
-    pub fn numberspace(&self) -> &dyn RealNumberValueSpace {
-        self.number_space.as_ref()
-    }
+    /// The middleware that processes the commands, in order, before they are sent to the
+    /// hardware.
+    command_middleware: Vec<Box<dyn CommandMiddleware>>,
 
-    /// Gets the current joint state for the actuator
-    #[cfg_attr(test, mutants::skip)] // Cannot easily check mutations as this is a threaded lock situation
-    pub fn value(&self) -> Result<JointState, Error> {
-        let mut retries = 0;
-        while retries < 3 {
-            match self.current_state.lock() {
-                Ok(r) => {
-                    return Ok(JointState::new(
-                        r.state.position(),
-                        *r.state.velocity(),
-                        *r.state.acceleration(),
-                        *r.state.jerk(),
-                    ));
-                }
-                Err(_) => {
-                    // Failed to lock. Wait and try again.
-                    retries += 1;
-                }
-            };
-        }
+    /// The limits on the commands for the actuator.
+    constraint: JointConstraint,
 
-        Err(Error::FailedToReadActuatorJointState)
-    }
+    /// Determines how the commands that are outside of the [JointConstraint] are handled.
+    constraint_enforcement: ConstraintEnforcement,
 
-    /// Creates a new [Actuator] instance with the given get and set functions
-    ///
-    /// ## Parameters
-    ///
-    /// * 'actuator' - The hardware interface that points to the actual actuator.
-    /// * 'change_processor' - The change processor that will process updates from the hardware actuator
-    pub fn new(
-        actuator: &mut impl HardwareActuator,
-        change_processor: &HardwareChangeProcessor,
-    ) -> Result<Self, Error> {
-        // Initially set the current state and the rates of change to be zero. These values will be overwritten
-        // as soon as we get our first set of data from the actual actuator.
-        let current_state = Arc::new(Mutex::new(CurrentActuatorState::new(
-            JointState::new(0.0, Some(0.0), Some(0.0), Some(0.0)),
-            ActuatorAvailableRatesOfChange::new(0.0, 0.0, 0.0, 0.0, 0.0, 0.0),
-        )));
-        let current_state_clone = current_state.clone();
-
-        let number_space = to_number_space(actuator.actuator_motion_type());
-        let command_sender = actuator.command_sender()?;
-        let result = Self {
-            current_state,
-            number_space,
-            command_sender,
-        };
+    /// The middleware that processes the states received from the hardware, in order, before
+    /// they are stored.
+    state_middleware: Arc<Mutex<Vec<Box<dyn StateMiddleware>>>>,
 
-        let state_reciever = actuator.current_state_receiver()?;
-        let on_notify_of_change = Box::new(move || {
-            let result = state_reciever.recv();
-            if result.is_err() {
-                // Something isn't right. Nothing we can do. Just continue with the code
-                return;
-            }
+    /// The transmission and the calibration that convert the states received from the hardware
+    /// into the states of the joint, and the commands into raw values. Shared with the task
+    /// that processes the states and the task that dispatches the profile.
+    conversion: Arc<Mutex<HardwareConversion>>,
 
-            let (s, c) = result.unwrap();
+    /// The number of times the state of the actuator has been updated by the
+    /// [HardwareChangeProcessor]. Used to detect changes without locking the state.
+    state_version: Arc<AtomicU64>,
 
-            let mut retries = 0;
-            while retries < 3 {
-                match current_state_clone.lock() {
-                    Ok(r) => {
-                        let mut mutable_state = r;
-                        mutable_state.state = s;
-                        mutable_state.rates_of_change = c;
-                        break;
-                    }
-                    Err(_) => {
-                        // Failed to lock. Wait and try again.
-                        retries += 1;
-                    }
-                };
-            }
+    /// The number of states received from the hardware that were rejected because they
+    /// contained values that are not finite or because they were rejected by the middleware.
+    rejected_state_count: Arc<AtomicU64>,
 
-            // Updated, yay
-        });
+    /// The frame element the actuator is attached to and the publisher of the model that
+    /// contains it. Used to publish the events for the actuator.
+    event_target: ModelEventTarget,
 
-        let (sender, id) = match change_processor.add(on_notify_of_change) {
-            Ok(r) => r,
-            Err(e) => return Err(e),
-        };
-        actuator.on_change(id, sender);
+    /// The profile that the actuator is following, or [None] if the actuator is not following
+    /// a profile. Shared with the task that dispatches the profile.
+    active_profile: Arc<Mutex<Option<ActiveProfile>>>,
 
-        Ok(result)
-    }
+    /// The last command that was sent to the hardware together with the time at which it was
+    /// sent. Shared with the task that dispatches the profile.
+    last_command: Arc<Mutex<Option<TimestampedJointState>>>,
 
-    /// Sets the desired actuator state.
+    /// The fault that was detected on the connection with the hardware, if any. Shared with
+    /// the task that processes the states and the task that dispatches the profile.
+    fault: Arc<Mutex<Option<ActuatorFault>>>,
+
+    /// The longest time between two states received from the hardware before the actuator is
+    /// considered stale, or [None] if the actuator never becomes stale.
+    stale_timeout: Option<Duration>,
+
+    /// The ID with which the hardware notifies the [HardwareChangeProcessor] of new states, or
+    /// [None] if the hardware publishes its states through a tokio channel.
+    change_id: Option<ChangeID>,
+}
+
+impl Actuator {
+    /// Adds a [CommandMiddleware] to the end of the chain of middleware that processes the
+    /// commands for the actuator. The middleware is executed after all the middleware that
+    /// was added before it.
     ///
     /// ## Parameters
     ///
-    /// * 'new_state' - The desired state
-    ///
-    /// ## Returns
+    /// * 'middleware' - The middleware that should be added.
+    pub fn add_command_middleware(&mut self, middleware: impl CommandMiddleware + 'static) {
+        self.command_middleware.push(Box::new(middleware));
+    }
+
+    /// Adds a [StateMiddleware] to the end of the chain of middleware that processes the
+    /// states received from the hardware. The middleware is executed after all the middleware
+    /// that was added before it.
     ///
-    /// A result indicating if the setting of the new desired state was
-    /// successful or not.
+    /// ## Parameters
     ///
-    pub fn update_state(&self, new_state: JointState) -> Result<(), Error> {
-        // Until https://github.com/rust-lang/rust/issues/99301 is fixed we can't send an error type
-        // with generics (i.e. SendError<JointState>) into a thiserror source / backtrace error translator
-        self.command_sender
-            .send(new_state)
-            .map_err(|_source| Error::FailedToSetActuatorJointState {})
+    /// * 'middleware' - The middleware that should be added.
+    pub fn add_state_middleware(&mut self, middleware: impl StateMiddleware + 'static) {
+        add_to_state_middleware(&self.state_middleware, Box::new(middleware));
     }
-}
 
-/// Defines a single constraint on a joint or element
-pub struct JointConstraint {
-    // state change + notification
-}
+    /// Returns the rates of change that the actuator can currently achieve, as last reported
+    /// by the hardware. The rates are zero until the first state is received.
+    pub fn available_rates_of_change(&self) -> ActuatorAvailableRatesOfChange {
+        self.current_state
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .rates_of_change
+    }
+
+    /// Returns the calibration that converts the states received from the hardware into the
+    /// states of the joint, and the commands into the raw values of the hardware.
+    pub fn calibration(&self) -> JointCalibration {
+        self.conversion
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .calibration
+    }
+
+    /// Returns the [ChangeID] with which the hardware notifies the [HardwareChangeProcessor] of
+    /// new states, or [None] if the hardware publishes its states through a tokio channel. Used
+    /// to relate the [metrics](crate::change_notification_processing::metrics) of the processor
+    /// to the joint.
+    pub fn change_id(&self) -> Option<ChangeID> {
+        self.change_id
+    }
+
+    /// Stops following the [JointProfile] that was submitted with [Actuator::submit_profile].
+    /// The last command that was sent to the hardware is not revoked.
+    pub fn cancel_profile(&self) {
+        *self
+            .active_profile
+            .lock()
+            .unwrap_or_else(|err| err.into_inner()) = None;
+    }
+
+    /// Removes all the [CommandMiddleware] from the actuator so that commands are sent to the
+    /// hardware unchanged.
+    pub fn clear_command_middleware(&mut self) {
+        self.command_middleware.clear();
+    }
+
+    /// Clears the fault that was detected on the connection with the hardware, e.g. after the
+    /// driver of the hardware was restarted. The fault is detected again if the connection is
+    /// still broken the next time it is used.
+    pub fn clear_fault(&self) {
+        *self.fault.lock().unwrap_or_else(|err| err.into_inner()) = None;
+    }
+
+    /// Returns the [JointConstraint] that limits the commands for the actuator.
+    pub fn constraint(&self) -> &JointConstraint {
+        &self.constraint
+    }
+
+    /// Returns how the commands that are outside of the [JointConstraint] are handled.
+    pub fn constraint_enforcement(&self) -> ConstraintEnforcement {
+        self.constraint_enforcement
+    }
+
+    /// Returns a value indicating whether the actuator is following a [JointProfile], i.e. a
+    /// profile was submitted and the last point of the profile has not been sent yet.
+    pub fn has_active_profile(&self) -> bool {
+        self.active_profile
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .is_some()
+    }
+
+    /// Returns the health of the actuator, see the [health](crate::hardware::health) module.
+    ///
+    /// A fault takes precedence over a stale state, i.e. a disconnected actuator is reported
+    /// as faulted even if it has not reported a state for a long time.
+    pub fn health(&self) -> ActuatorHealth {
+        if let Some(fault) = self
+            .fault
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .clone()
+        {
+            return ActuatorHealth::Faulted(fault);
+        }
+
+        let last_update = self.last_update();
+        if let Some(update) = last_update.as_ref() {
+            if let Err(Error::JointLimitExceeded {
+                quantity, value, ..
+            }) = self.constraint.validate(update.state())
+            {
+                return ActuatorHealth::Faulted(ActuatorFault::OutOfRange { quantity, value });
+            }
+        }
+
+        match (self.stale_timeout, last_update) {
+            (Some(_), None) => ActuatorHealth::Stale,
+            (Some(timeout), Some(update)) if update.age() > timeout => ActuatorHealth::Stale,
+            _ => ActuatorHealth::Ok,
+        }
+    }
+
+    /// Returns the states received from the hardware that are stored in the history of the
+    /// actuator, oldest first. The history is empty unless a capacity was set with
+    /// [Actuator::set_history_capacity].
+    pub fn history(&self) -> Vec<TimestampedJointState> {
+        self.current_state
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .history
+            .states
+            .iter()
+            .copied()
+            .collect()
+    }
+
+    /// Returns the maximum number of states that are stored in the history of the actuator.
+    pub fn history_capacity(&self) -> usize {
+        self.current_state
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .history
+            .capacity
+    }
+
+    /// Returns the number of [CommandMiddleware] that process the commands for the actuator.
+    pub fn number_of_command_middleware(&self) -> usize {
+        self.command_middleware.len()
+    }
+
+    /// Returns the last command that was sent to the hardware together with the time at which
+    /// it was sent, or [None] if no command has been sent yet. The command is the state after
+    /// it was processed by the [CommandMiddleware] and the [JointConstraint] of the actuator.
+    pub fn last_command(&self) -> Option<TimestampedJointState> {
+        *self
+            .last_command
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+    }
+
+    /// Returns the last state received from the hardware together with the time at which it
+    /// was received, or [None] if no state has been received yet.
+    pub fn last_update(&self) -> Option<TimestampedJointState> {
+        let state = self
+            .current_state
+            .lock()
+            .unwrap_or_else(|err| err.into_inner());
+        state
+            .received_at
+            .map(|received_at| TimestampedJointState::new(state.state, received_at))
+    }
+
+    /// Returns the number space for the actuator
+    pub fn numberspace(&self) -> &dyn RealNumberValueSpace {
+        self.number_space.as_ref()
+    }
+
+    /// Returns the kind of number space for the actuator.
+    pub fn number_space_type(&self) -> NumberSpaceType {
+        self.number_space_type
+    }
+
+    /// Returns the number of states received from the hardware that were rejected because
+    /// they contained a NaN or infinite value, or because they were rejected by a
+    /// [StateMiddleware]. Rejected states are discarded and the actuator keeps the last valid
+    /// state.
+    pub fn rejected_state_count(&self) -> u64 {
+        self.rejected_state_count.load(Ordering::SeqCst)
+    }
+
+    /// Sets the calibration that converts the states received from the hardware into the
+    /// states of the joint, and the commands into the raw values of the hardware. The
+    /// calibration is applied after the [StateMiddleware] and the [JointTransmission] to the
+    /// states that are received from then on, and after the [CommandMiddleware] and the
+    /// [JointConstraint] to the commands.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'calibration' - The calibration of the actuator.
+    pub fn set_calibration(&mut self, calibration: JointCalibration) {
+        self.conversion
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .calibration = calibration;
+    }
+
+    /// Sets the [JointConstraint] that limits the commands for the actuator.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'constraint' - The limits on the commands.
+    pub fn set_constraint(&mut self, constraint: JointConstraint) {
+        self.constraint = constraint;
+    }
+
+    /// Sets how the commands that are outside of the [JointConstraint] are handled.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'enforcement' - The way in which the constraint is enforced.
+    pub fn set_constraint_enforcement(&mut self, enforcement: ConstraintEnforcement) {
+        self.constraint_enforcement = enforcement;
+    }
+
+    /// Sets the maximum number of states received from the hardware that are stored in the
+    /// history of the actuator, see [Actuator::state_at]. The oldest states are removed when the
+    /// history is full. A capacity of zero, the default, disables the history.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'capacity' - The maximum number of states in the history.
+    pub fn set_history_capacity(&mut self, capacity: usize) {
+        self.current_state
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .history
+            .set_capacity(capacity);
+    }
+
+    /// Sets the longest time between two states received from the hardware before the actuator
+    /// is considered stale. Pass [None] to never consider the actuator stale.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'timeout' - The stale timeout.
+    pub fn set_stale_timeout(&mut self, timeout: Option<Duration>) {
+        self.stale_timeout = timeout;
+    }
+
+    /// Links the actuator to the frame element it is attached to, so that the events for the
+    /// actuator are published to the subscribers of the model. Pass [None] when the actuator is
+    /// removed from the model.
+    pub(crate) fn set_event_target(&self, target: Option<(FrameID, ModelEventPublisher)>) {
+        *self
+            .event_target
+            .lock()
+            .unwrap_or_else(|err| err.into_inner()) = target;
+    }
+
+    /// Sets the [JointTransmission] between the hardware and the joint. The transmission is
+    /// applied after the [StateMiddleware] and before the [JointCalibration] to the states
+    /// that are received from then on, and after the [JointCalibration] to the commands.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'transmission' - The transmission between the hardware and the joint.
+    pub fn set_transmission(&mut self, transmission: JointTransmission) {
+        self.conversion
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .transmission = transmission;
+    }
+
+    /// Returns the longest time between two states received from the hardware before the
+    /// actuator is considered stale, or [None] if the actuator never becomes stale.
+    pub fn stale_timeout(&self) -> Option<Duration> {
+        self.stale_timeout
+    }
+
+    /// Returns the number of times the state of the actuator has been updated. The value
+    /// changes each time a new state is received from the hardware.
+    pub(crate) fn state_version(&self) -> u64 {
+        self.state_version.load(Ordering::SeqCst)
+    }
+
+    /// Returns the state of the joint at the given time, interpolated between the states in
+    /// the history of the actuator. Positions are interpolated along the shortest path in the number
+    /// space of the actuator. The newest state is returned for times after the newest state in
+    /// the history.
+    ///
+    /// Returns [None] when the time is before the oldest state in the history, or when the
+    /// history is empty.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'time' - The time at which the state is required, e.g. the time at which a camera
+    ///   image was captured.
+    pub fn state_at(&self, time: Instant) -> Option<JointState> {
+        self.current_state
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .history
+            .state_at(time, self.number_space.as_ref())
+    }
+
+    /// Submits a [JointProfile] that the actuator should follow, replacing the profile that the
+    /// actuator was following.
+    ///
+    /// The profile starts when it is submitted. Once per processing period the change processor
+    /// interpolates the profile at the time since the start and sends the interpolated state to
+    /// the hardware, until the last point of the profile has been sent. Each point of the
+    /// profile passes through the [CommandMiddleware] and the [JointConstraint] of the actuator
+    /// when the profile is submitted, the interpolated states are sent to the hardware as is.
+    /// The positions are interpolated along the shortest path in the number space of the
+    /// actuator, so a profile for a periodic joint can cross the boundaries of the number space.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'profile' - The profile that the actuator should follow.
+    /// * 'change_processor' - The change processor that dispatches the profile. The profile is
+    ///   not dispatched while the processor is paused.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::CommandVetoed] - Returned when one of the middleware vetoed a point of the
+    ///   profile.
+    /// * [Error::JointLimitExceeded] - Returned when a point of the profile is outside of the
+    ///   [JointConstraint] and the constraint enforcement is [ConstraintEnforcement::Reject].
+    /// * [Error::InvalidJointProfile] - Returned when the middleware changed a point of the
+    ///   profile into a state that is not finite.
+    pub fn submit_profile(
+        &self,
+        profile: JointProfile,
+        change_processor: &HardwareChangeProcessor,
+    ) -> Result<(), Error> {
+        let mut points = Vec::with_capacity(profile.points().len());
+        let mut previous_position: Option<f64> = None;
+        for (time, state) in profile.points() {
+            let command = self.prepare_command(*state)?;
+
+            // Unwrap the positions so that the linear interpolation follows the shortest path
+            let position = match previous_position {
+                Some(previous) => {
+                    previous
+                        + self
+                            .number_space
+                            .smallest_distance_between_values(previous, command.position())
+                }
+                None => command.position(),
+            };
+            previous_position = Some(position);
+            points.push((
+                *time,
+                JointState::new(
+                    position,
+                    *command.velocity(),
+                    *command.acceleration(),
+                    *command.jerk(),
+                ),
+            ));
+        }
+
+        let id = ChangeID::new();
+        *self
+            .active_profile
+            .lock()
+            .unwrap_or_else(|err| err.into_inner()) = Some(ActiveProfile {
+            id,
+            profile: JointProfile::new(points)?,
+            started_at: Instant::now(),
+        });
+
+        // The profile lock is released before the task is added, because the change processor
+        // holds its own lock while it executes the task.
+        let active_profile = self.active_profile.clone();
+        let command_sender = self.command_sender.clone();
+        let event_target = self.event_target.clone();
+        let last_command = self.last_command.clone();
+        let fault = self.fault.clone();
+        let conversion = self.conversion.clone();
+        let number_space_type = self.number_space_type;
+        change_processor.add_periodic(Box::new(move || {
+            let mut active = active_profile.lock().unwrap_or_else(|err| err.into_inner());
+            let (state, is_finished) = match active.as_ref() {
+                Some(active) if active.id == id => {
+                    let elapsed = active.started_at.elapsed();
+                    (
+                        active.profile.state_at(elapsed),
+                        elapsed >= active.profile.duration(),
+                    )
+                }
+                // The profile was replaced or cancelled
+                _ => return false,
+            };
+
+            let command = JointState::new(
+                to_number_space(number_space_type).normalize_value(state.position()),
+                *state.velocity(),
+                *state.acceleration(),
+                *state.jerk(),
+            );
+            let is_sent = command_sender
+                .send(convert_to_hardware(&conversion, command), &fault)
+                .is_ok();
+            if is_sent {
+                record_command(&last_command, command);
+                publish_to_target(&event_target, |frame| ModelEvent::ActuatorCommandSent {
+                    frame,
+                });
+            }
+
+            // Stop following the profile when the hardware is no longer listening
+            if is_finished || !is_sent {
+                *active = None;
+                return false;
+            }
+
+            true
+        }));
+
+        Ok(())
+    }
+
+    /// Returns the [JointTransmission] between the hardware and the joint.
+    pub fn transmission(&self) -> JointTransmission {
+        self.conversion
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .transmission
+    }
+
+    /// Gets the current joint state for the actuator without waiting for the lock on the state,
+    /// or [None] if the state is being updated by the hardware at this moment.
+    pub fn try_value(&self) -> Option<JointState> {
+        self.current_state.try_lock().ok().map(|r| r.state)
+    }
+
+    /// Gets the current joint state for the actuator
+    #[cfg_attr(test, mutants::skip)] // Cannot easily check mutations as this is a threaded lock situation
+    pub fn value(&self) -> Result<JointState, Error> {
+        let mut retries = 0;
+        while retries < 3 {
+            match self.current_state.lock() {
+                Ok(r) => {
+                    return Ok(JointState::new(
+                        r.state.position(),
+                        *r.state.velocity(),
+                        *r.state.acceleration(),
+                        *r.state.jerk(),
+                    ));
+                }
+                Err(_) => {
+                    // Failed to lock. Wait and try again.
+                    retries += 1;
+                }
+            };
+        }
+
+        Err(Error::FailedToReadActuatorJointState)
+    }
+
+    /// Creates a new [Actuator] instance with the given get and set functions
+    ///
+    /// ## Parameters
+    ///
+    /// * 'actuator' - The hardware interface that points to the actual actuator.
+    /// * 'change_processor' - The change processor that will process updates from the hardware actuator
+    pub fn new(
+        actuator: &mut impl HardwareActuator,
+        change_processor: &HardwareChangeProcessor,
+    ) -> Result<Self, Error> {
+        let command_sender = CommandSender::Channel(actuator.command_sender()?);
+        let mut result = Self::with_command_sender(actuator.actuator_motion_type(), command_sender);
+        let update = result.state_updater();
+        let fault = result.fault.clone();
+
+        let state_reciever = actuator.current_state_receiver()?;
+        let drain_waiting_states = change_processor.discards_notifications();
+        let on_notify_of_change = Box::new(move || {
+            if drain_waiting_states {
+                // Process all the waiting states so that no states are left behind when the
+                // change processor coalesces or drops notifications. States that arrive while
+                // processing are left for the next notification.
+                let number_of_states = state_reciever.len();
+                for state in state_reciever.try_iter().take(number_of_states) {
+                    update(state);
+                }
+            } else if let Ok(state) = state_reciever.recv() {
+                update(state);
+            } else {
+                // The hardware notified a change but dropped its end of the state channel
+                record_fault(&fault, ActuatorFault::Disconnected);
+            }
+        });
+
+        let (sender, id) = change_processor.add(on_notify_of_change)?;
+        actuator.on_change(id, sender);
+        result.change_id = Some(id);
+
+        Ok(result)
+    }
+
+    /// Creates a new [Actuator] instance for hardware that publishes its state and receives
+    /// its commands through tokio channels. This function is only available when the `async`
+    /// feature is enabled.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'actuator' - The hardware interface that points to the actual actuator.
+    /// * 'change_processor' - The change processor that runs the task that processes updates
+    ///   from the hardware actuator.
+    #[cfg(feature = "async")]
+    pub fn new_async(
+        actuator: &impl AsyncHardwareActuator,
+        change_processor: &AsyncHardwareChangeProcessor,
+    ) -> Result<Self, Error> {
+        let command_sender = CommandSender::Async(actuator.command_sender()?);
+        let result = Self::with_command_sender(actuator.actuator_motion_type(), command_sender);
+        change_processor.watch(actuator.current_state_receiver()?, result.state_updater());
+
+        Ok(result)
+    }
+
+    /// Sets the desired actuator state.
+    ///
+    /// The state is passed through the [CommandMiddleware] of the actuator and then checked
+    /// against the [JointConstraint] of the actuator before it is sent to the hardware.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'new_state' - The desired state
+    ///
+    /// ## Returns
+    ///
+    /// A result indicating if the setting of the new desired state was
+    /// successful or not.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::CommandVetoed] - Returned when one of the middleware vetoed the command.
+    /// * [Error::JointLimitExceeded] - Returned when the command is outside of the
+    ///   [JointConstraint] and the constraint enforcement is [ConstraintEnforcement::Reject].
+    /// * [Error::Channel] - Returned when the command could not be sent to the hardware. The
+    ///   source of the error is the error of the channel.
+    pub fn update_state(&self, new_state: JointState) -> Result<(), Error> {
+        let command = self.prepare_command(new_state)?;
+        self.command_sender
+            .send(convert_to_hardware(&self.conversion, command), &self.fault)?;
+        record_command(&self.last_command, command);
+        publish_to_target(&self.event_target, |frame| {
+            ModelEvent::ActuatorCommandSent { frame }
+        });
+        Ok(())
+    }
+
+    /// Passes the command through the [CommandMiddleware] and the [JointConstraint] of the
+    /// actuator and returns the command that should be sent to the hardware.
+    fn prepare_command(&self, new_state: JointState) -> Result<JointState, Error> {
+        let command = match process_command(&self.command_middleware, new_state) {
+            CommandOutcome::Forward(command) => command,
+            CommandOutcome::Veto { reason } => return Err(Error::CommandVetoed { reason }),
+        };
+
+        match self.constraint_enforcement {
+            ConstraintEnforcement::Disabled => Ok(command),
+            ConstraintEnforcement::Clamp => Ok(self.constraint.clamp(command)),
+            ConstraintEnforcement::Reject => {
+                self.constraint.validate(&command)?;
+                Ok(command)
+            }
+        }
+    }
+
+    /// Returns the closure that processes a state received from the hardware and stores it
+    /// as the current state of the actuator.
+    fn state_updater(
+        &self,
+    ) -> impl Fn((JointState, ActuatorAvailableRatesOfChange)) + Send + Sync + 'static {
+        let current_state = self.current_state.clone();
+        let state_version = self.state_version.clone();
+        let state_middleware = self.state_middleware.clone();
+        let conversion = self.conversion.clone();
+        let rejected_state_count = self.rejected_state_count.clone();
+        let event_target = self.event_target.clone();
+        move |(state, rates_of_change)| {
+            // A corrupted state would poison every transform derived from it, so keep the
+            // last valid state instead. The rates of change are checked first so that stateful
+            // middleware does not record the states that are rejected anyway.
+            let processed = if rates_of_change.is_finite() {
+                process_state_with(&state_middleware, state)
+            } else {
+                None
+            };
+            let s = match processed {
+                Some(s) => convert_to_joint(&conversion, s),
+                None => {
+                    rejected_state_count.fetch_add(1, Ordering::SeqCst);
+                    event!(WARN, frame = ?target_frame(&event_target), "Rejected a state from the actuator");
+                    return;
+                }
+            };
+            event!(TRACE, frame = ?target_frame(&event_target), position = s.position(), "Received a state from the actuator");
+
+            let mut retries = 0;
+            while retries < 3 {
+                match current_state.lock() {
+                    Ok(r) => {
+                        let mut mutable_state = r;
+                        let received_at = Instant::now();
+                        mutable_state.state = s;
+                        mutable_state.received_at = Some(received_at);
+                        mutable_state.rates_of_change = rates_of_change;
+                        mutable_state
+                            .history
+                            .push(TimestampedJointState::new(s, received_at));
+                        state_version.fetch_add(1, Ordering::SeqCst);
+                        break;
+                    }
+                    Err(_) => {
+                        // Failed to lock. Wait and try again.
+                        retries += 1;
+                    }
+                };
+            }
+
+            publish_to_target(&event_target, |frame| ModelEvent::JointStateUpdated {
+                frame,
+            });
+        }
+    }
+
+    /// Creates a new [Actuator] instance with the given kind of number space and command
+    /// channel, and a zero state.
+    fn with_command_sender(
+        number_space_type: NumberSpaceType,
+        command_sender: CommandSender,
+    ) -> Self {
+        // Initially set the current state and the rates of change to be zero. These values will be overwritten
+        // as soon as we get our first set of data from the actual actuator.
+        Self {
+            current_state: Arc::new(Mutex::new(CurrentActuatorState::new(
+                JointState::new(0.0, Some(0.0), Some(0.0), Some(0.0)),
+                ActuatorAvailableRatesOfChange::new(0.0, 0.0, 0.0, 0.0, 0.0, 0.0),
+            ))),
+            number_space: to_number_space(number_space_type),
+            number_space_type,
+            command_sender,
+            command_middleware: Vec::new(),
+            constraint: JointConstraint::new(),
+            constraint_enforcement: ConstraintEnforcement::Disabled,
+            state_middleware: Arc::new(Mutex::new(Vec::new())),
+            conversion: Arc::new(Mutex::new(HardwareConversion::default())),
+            state_version: Arc::new(AtomicU64::new(0)),
+            rejected_state_count: Arc::new(AtomicU64::new(0)),
+            event_target: Arc::new(Mutex::new(None)),
+            active_profile: Arc::new(Mutex::new(None)),
+            last_command: Arc::new(Mutex::new(None)),
+            fault: Arc::new(Mutex::new(None)),
+            stale_timeout: None,
+            change_id: None,
+        }
+    }
+}
+
+/// Stores the given command as the last command that was sent to the hardware.
+fn record_command(last_command: &Mutex<Option<TimestampedJointState>>, command: JointState) {
+    // A poisoned lock only means that another thread panicked, the command itself is still valid
+    let mut guard = last_command.lock().unwrap_or_else(|err| err.into_inner());
+    *guard = Some(TimestampedJointState::new(command, Instant::now()));
+}
+
+/// Stores the given fault as the fault of an actuator, unless a fault was already recorded.
+fn record_fault(fault: &Mutex<Option<ActuatorFault>>, new_fault: ActuatorFault) {
+    let mut guard = fault.lock().unwrap_or_else(|err| err.into_inner());
+    if guard.is_none() {
+        *guard = Some(new_fault);
+    }
+}
+
+/// Adds the middleware to the end of the given chain of [StateMiddleware].
+fn add_to_state_middleware(
+    chain: &Mutex<Vec<Box<dyn StateMiddleware>>>,
+    middleware: Box<dyn StateMiddleware>,
+) {
+    // A poisoned lock only means that a middleware panicked, the chain itself is still valid
+    let mut guard = chain.lock().unwrap_or_else(|err| err.into_inner());
+    guard.push(middleware);
+}
+
+/// Converts the state of the joint into the command for the hardware with the given
+/// conversion.
+fn convert_to_hardware(conversion: &Mutex<HardwareConversion>, state: JointState) -> JointState {
+    conversion
+        .lock()
+        .unwrap_or_else(|err| err.into_inner())
+        .to_hardware(state)
+}
+
+/// Converts the state received from the hardware into the state of the joint with the given
+/// conversion.
+fn convert_to_joint(conversion: &Mutex<HardwareConversion>, state: JointState) -> JointState {
+    conversion
+        .lock()
+        .unwrap_or_else(|err| err.into_inner())
+        .to_joint(state)
+}
+
+/// Passes the state through the given chain of [StateMiddleware]. Returns [None] if the state
+/// was rejected.
+fn process_state_with(
+    chain: &Mutex<Vec<Box<dyn StateMiddleware>>>,
+    state: JointState,
+) -> Option<JointState> {
+    let mut guard = chain.lock().unwrap_or_else(|err| err.into_inner());
+    process_state(guard.as_mut_slice(), state)
+}
+
+/// Defines how an [Actuator] handles the commands that fall outside of its [JointConstraint].
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ConstraintEnforcement {
+    /// The commands are sent to the hardware without checking the constraint.
+    #[default]
+    Disabled,
+    /// The values of the command that are outside of the limits are set to the nearest limit.
+    Clamp,
+    /// The commands with values outside of the limits are not sent to the hardware.
+    Reject,
+}
+
+/// Defines the limits on the motion of a single joint.
+///
+/// Each limit is stored as a `(minimum, maximum)` pair in the units of the degree-of-freedom of
+/// the joint, i.e. radians for revolute joints and meters for prismatic joints. A limit that is
+/// not set does not constrain the joint.
+///
+/// ```
+/// use swerve_vehicle_descriptors::hardware::joint_state::JointState;
+/// use swerve_vehicle_descriptors::model_elements::frame_elements::JointConstraint;
+///
+/// let constraint = JointConstraint::new()
+///     .with_position_limits(-0.1, 0.1)
+///     .with_velocity_limits(-1.0, 1.0);
+///
+/// assert!(constraint.validate(&JointState::new(0.05, Some(0.5), None, None)).is_ok());
+/// assert!(constraint.validate(&JointState::new(0.15, Some(0.5), None, None)).is_err());
+/// assert_eq!(
+///     JointState::new(0.1, Some(-1.0), None, None),
+///     constraint.clamp(JointState::new(0.15, Some(-2.0), None, None))
+/// );
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct JointConstraint {
+    /// The minimum and maximum position of the joint.
+    position: Option<(f64, f64)>,
+
+    /// The minimum and maximum velocity of the joint.
+    velocity: Option<(f64, f64)>,
+
+    /// The minimum and maximum acceleration of the joint.
+    acceleration: Option<(f64, f64)>,
+
+    /// The minimum and maximum effort, i.e. force or torque, of the joint.
+    effort: Option<(f64, f64)>,
+}
 
 impl JointConstraint {
-    /// Creates a new [JointConstraint] instance.
+    /// Returns the minimum and maximum acceleration of the joint, if they are limited.
+    pub fn acceleration_limits(&self) -> Option<(f64, f64)> {
+        self.acceleration
+    }
+
+    /// Returns a copy of the given state with the position, velocity and acceleration clamped
+    /// to the limits of the constraint.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'state' - The state that should be clamped.
+    pub fn clamp(&self, state: JointState) -> JointState {
+        JointState::new(
+            clamp_to(state.position(), self.position),
+            state.velocity().map(|v| clamp_to(v, self.velocity)),
+            state.acceleration().map(|a| clamp_to(a, self.acceleration)),
+            *state.jerk(),
+        )
+    }
+
+    /// Returns the minimum and maximum effort, i.e. force or torque, of the joint, if they are
+    /// limited.
+    ///
+    /// A [JointState] does not carry the effort so the effort limits are not used by
+    /// [JointConstraint::clamp] and [JointConstraint::validate].
+    pub fn effort_limits(&self) -> Option<(f64, f64)> {
+        self.effort
+    }
+
+    /// Returns a value indicating whether the constraint limits any of the values of the joint.
+    pub fn is_limited(&self) -> bool {
+        self.position.is_some()
+            || self.velocity.is_some()
+            || self.acceleration.is_some()
+            || self.effort.is_some()
+    }
+
+    /// Creates a new [JointConstraint] instance that does not limit the joint.
     pub fn new() -> Self {
-        Self {}
+        Self {
+            position: None,
+            velocity: None,
+            acceleration: None,
+            effort: None,
+        }
+    }
+
+    /// Returns the minimum and maximum position of the joint, if they are limited.
+    pub fn position_limits(&self) -> Option<(f64, f64)> {
+        self.position
+    }
+
+    /// Checks that the position, velocity and acceleration of the given state are within the
+    /// limits of the constraint. Values that are not known are not checked.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'state' - The state that should be checked.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::JointLimitExceeded] - Returned for the first value that is outside of its
+    ///   limits.
+    pub fn validate(&self, state: &JointState) -> Result<(), Error> {
+        check_limit("position", Some(state.position()), self.position)?;
+        check_limit("velocity", *state.velocity(), self.velocity)?;
+        check_limit("acceleration", *state.acceleration(), self.acceleration)
+    }
+
+    /// Returns the minimum and maximum velocity of the joint, if they are limited.
+    pub fn velocity_limits(&self) -> Option<(f64, f64)> {
+        self.velocity
+    }
+
+    /// Returns a copy of the constraint with the given acceleration limits.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'minimum' - The minimum acceleration of the joint.
+    /// * 'maximum' - The maximum acceleration of the joint.
+    pub fn with_acceleration_limits(mut self, minimum: f64, maximum: f64) -> Self {
+        self.acceleration = Some((minimum, maximum));
+        self
+    }
+
+    /// Returns a copy of the constraint with the given effort limits.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'minimum' - The minimum effort of the joint.
+    /// * 'maximum' - The maximum effort of the joint.
+    pub fn with_effort_limits(mut self, minimum: f64, maximum: f64) -> Self {
+        self.effort = Some((minimum, maximum));
+        self
+    }
+
+    /// Returns a copy of the constraint with the given position limits.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'minimum' - The minimum position of the joint.
+    /// * 'maximum' - The maximum position of the joint.
+    pub fn with_position_limits(mut self, minimum: f64, maximum: f64) -> Self {
+        self.position = Some((minimum, maximum));
+        self
+    }
+
+    /// Returns a copy of the constraint with the given velocity limits.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'minimum' - The minimum velocity of the joint.
+    /// * 'maximum' - The maximum velocity of the joint.
+    pub fn with_velocity_limits(mut self, minimum: f64, maximum: f64) -> Self {
+        self.velocity = Some((minimum, maximum));
+        self
     }
 }
 
@@ -568,3 +1990,299 @@ impl Default for JointConstraint {
         Self::new()
     }
 }
+
+/// Defines the calibration of the hardware of a single joint, i.e. the conversion between the
+/// raw values of the hardware, e.g. the position of an encoder, and the state of the joint.
+///
+/// A raw state is converted with `position * scale + offset`. The velocity, acceleration and
+/// jerk are multiplied by the scale. The commands for an [Actuator] are converted back into raw
+/// values before they are sent to the hardware.
+///
+/// ```
+/// use swerve_vehicle_descriptors::hardware::joint_state::JointState;
+/// use swerve_vehicle_descriptors::model_elements::frame_elements::JointCalibration;
+///
+/// // The encoder reads 1.0 when the joint is at 0.25 and counts in the opposite direction
+/// let calibration = JointCalibration::new(0.25, -1.0).unwrap();
+///
+/// assert_eq!(
+///     JointState::new(-0.75, Some(-2.0), None, None),
+///     calibration.apply(JointState::new(1.0, Some(2.0), None, None))
+/// );
+/// assert_eq!(
+///     JointState::new(1.0, Some(2.0), None, None),
+///     calibration.apply_inverse(JointState::new(-0.75, Some(-2.0), None, None))
+/// );
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct JointCalibration {
+    /// The value that is added to the raw position after it is scaled.
+    offset: f64,
+
+    /// The factor with which the raw values are multiplied.
+    scale: f64,
+}
+
+impl JointCalibration {
+    /// Converts the given raw state, as received from the hardware, into the state of the
+    /// joint.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'state' - The raw state.
+    pub fn apply(&self, state: JointState) -> JointState {
+        JointState::new(
+            state.position() * self.scale + self.offset,
+            state.velocity().map(|v| v * self.scale),
+            state.acceleration().map(|a| a * self.scale),
+            state.jerk().map(|j| j * self.scale),
+        )
+    }
+
+    /// Converts the given state of the joint into the raw state of the hardware.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'state' - The state of the joint.
+    pub fn apply_inverse(&self, state: JointState) -> JointState {
+        JointState::new(
+            (state.position() - self.offset) / self.scale,
+            state.velocity().map(|v| v / self.scale),
+            state.acceleration().map(|a| a / self.scale),
+            state.jerk().map(|j| j / self.scale),
+        )
+    }
+
+    /// Returns the calibration that does not change the raw values.
+    pub fn identity() -> Self {
+        Self {
+            offset: 0.0,
+            scale: 1.0,
+        }
+    }
+
+    /// Returns a value indicating whether the calibration leaves the raw values unchanged.
+    pub fn is_identity(&self) -> bool {
+        *self == Self::identity()
+    }
+
+    /// Creates a new [JointCalibration] instance.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'offset' - The value that is added to the raw position after it is scaled, i.e. the
+    ///   position of the joint when the hardware reports zero.
+    /// * 'scale' - The factor with which the raw values are multiplied.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::InvalidJointCalibration] - Returned when the offset is not finite, or when the
+    ///   scale is zero or not finite.
+    pub fn new(offset: f64, scale: f64) -> Result<Self, Error> {
+        if !offset.is_finite() || !scale.is_finite() || scale == 0.0 {
+            return Err(Error::InvalidJointCalibration { offset, scale });
+        }
+
+        Ok(Self { offset, scale })
+    }
+
+    /// Returns the value that is added to the raw position after it is scaled.
+    pub fn offset(&self) -> f64 {
+        self.offset
+    }
+
+    /// Returns the factor with which the raw values are multiplied.
+    pub fn scale(&self) -> f64 {
+        self.scale
+    }
+}
+
+impl Default for JointCalibration {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+/// Defines the transmission between the hardware of a single joint and the joint, e.g. a
+/// gearbox between a motor and a steering column, or a motor that is wired in reverse.
+///
+/// A state of the hardware is converted into a state of the joint by dividing the position,
+/// velocity, acceleration and jerk by the gear ratio, and by negating them when the direction
+/// is inverted. The commands for an [Actuator] are converted back into the units of the
+/// hardware before they are sent to the hardware. The transmission is applied before the
+/// [JointCalibration] for the states and after it for the commands.
+///
+/// ```
+/// use swerve_vehicle_descriptors::hardware::joint_state::JointState;
+/// use swerve_vehicle_descriptors::model_elements::frame_elements::JointTransmission;
+///
+/// // The motor turns 4 times for each turn of the joint, in the opposite direction
+/// let transmission = JointTransmission::new(true, 4.0).unwrap();
+///
+/// assert_eq!(
+///     JointState::new(-0.5, Some(0.25), None, None),
+///     transmission.apply(JointState::new(2.0, Some(-1.0), None, None))
+/// );
+/// assert_eq!(
+///     JointState::new(2.0, Some(-1.0), None, None),
+///     transmission.apply_inverse(JointState::new(-0.5, Some(0.25), None, None))
+/// );
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct JointTransmission {
+    /// A flag indicating whether the joint moves in the opposite direction of the hardware.
+    inverted: bool,
+
+    /// The number of units that the hardware moves for each unit that the joint moves.
+    gear_ratio: f64,
+}
+
+impl JointTransmission {
+    /// Converts the given state of the hardware into the state of the joint.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'state' - The state of the hardware.
+    pub fn apply(&self, state: JointState) -> JointState {
+        let factor = self.direction() / self.gear_ratio;
+        JointState::new(
+            state.position() * factor,
+            state.velocity().map(|v| v * factor),
+            state.acceleration().map(|a| a * factor),
+            state.jerk().map(|j| j * factor),
+        )
+    }
+
+    /// Converts the given state of the joint into the state of the hardware.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'state' - The state of the joint.
+    pub fn apply_inverse(&self, state: JointState) -> JointState {
+        let factor = self.direction() * self.gear_ratio;
+        JointState::new(
+            state.position() * factor,
+            state.velocity().map(|v| v * factor),
+            state.acceleration().map(|a| a * factor),
+            state.jerk().map(|j| j * factor),
+        )
+    }
+
+    /// Returns the number of units that the hardware moves for each unit that the joint moves.
+    pub fn gear_ratio(&self) -> f64 {
+        self.gear_ratio
+    }
+
+    /// Returns the transmission that does not change the values of the hardware.
+    pub fn identity() -> Self {
+        Self {
+            inverted: false,
+            gear_ratio: 1.0,
+        }
+    }
+
+    /// Returns a value indicating whether the transmission leaves the values of the hardware
+    /// unchanged.
+    pub fn is_identity(&self) -> bool {
+        *self == Self::identity()
+    }
+
+    /// Returns a value indicating whether the joint moves in the opposite direction of the
+    /// hardware.
+    pub fn is_inverted(&self) -> bool {
+        self.inverted
+    }
+
+    /// Creates a new [JointTransmission] instance.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'inverted' - A flag indicating whether the joint moves in the opposite direction of
+    ///   the hardware.
+    /// * 'gear_ratio' - The number of units that the hardware moves for each unit that the
+    ///   joint moves.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::InvalidJointTransmission] - Returned when the gear ratio is not a finite,
+    ///   positive number.
+    pub fn new(inverted: bool, gear_ratio: f64) -> Result<Self, Error> {
+        if !gear_ratio.is_finite() || gear_ratio <= 0.0 {
+            return Err(Error::InvalidJointTransmission { gear_ratio });
+        }
+
+        Ok(Self {
+            inverted,
+            gear_ratio,
+        })
+    }
+
+    /// Returns the sign with which the values of the hardware are multiplied.
+    fn direction(&self) -> f64 {
+        if self.inverted {
+            -1.0
+        } else {
+            1.0
+        }
+    }
+}
+
+impl Default for JointTransmission {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+/// Stores the conversion between the values of the hardware of a joint and the state of the
+/// joint.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+struct HardwareConversion {
+    /// The transmission between the hardware and the joint.
+    transmission: JointTransmission,
+
+    /// The calibration of the joint, applied to the output of the transmission.
+    calibration: JointCalibration,
+}
+
+impl HardwareConversion {
+    /// Converts the given state of the joint into the state of the hardware.
+    fn to_hardware(self, state: JointState) -> JointState {
+        self.transmission
+            .apply_inverse(self.calibration.apply_inverse(state))
+    }
+
+    /// Converts the given state of the hardware into the state of the joint.
+    fn to_joint(self, state: JointState) -> JointState {
+        self.calibration.apply(self.transmission.apply(state))
+    }
+}
+
+/// Returns an error when the value is known and outside of the given limits.
+fn check_limit(
+    quantity: &str,
+    value: Option<f64>,
+    limits: Option<(f64, f64)>,
+) -> Result<(), Error> {
+    match (value, limits) {
+        (Some(value), Some((minimum, maximum))) if value < minimum || value > maximum => {
+            Err(Error::JointLimitExceeded {
+                quantity: quantity.to_string(),
+                value,
+                minimum,
+                maximum,
+            })
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Returns the value limited to the given range, or the value itself if there is no range.
+pub(crate) fn clamp_to(value: f64, limits: Option<(f64, f64)>) -> f64 {
+    match limits {
+        // Not using f64::clamp because that panics when the limits are not ordered
+        Some((minimum, maximum)) => value.max(minimum).min(maximum),
+        None => value,
+    }
+}
@@ -1,12 +1,42 @@
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use crossbeam_channel::Receiver;
 
 use crate::{
-    change_notification_processing::ChangeID, model_elements::frame_elements::*,
+    change_notification_processing::{ChangeID, ChangeNotifier},
+    hardware::{
+        health::{ActuatorFault, ActuatorHealth},
+        state_middleware::{LinearConversion, PositionJumpFilter, StateOutcome},
+    },
+    model_elements::frame_elements::*,
     number_space::NumberSpaceType,
+    ErrorKind,
 };
 
+// FrameDofType tests
+
+#[test]
+fn when_getting_the_axis_of_a_degree_of_freedom_it_should_return_the_axis_in_the_parent() {
+    let axis = nalgebra::Unit::new_normalize(nalgebra::Vector3::new(1.0, 0.0, 1.0));
+
+    assert_eq!(None, FrameDofType::Static.axis());
+    assert_eq!(
+        Some(nalgebra::Vector3::y()),
+        FrameDofType::PrismaticY.axis()
+    );
+    assert_eq!(
+        Some(axis.into_inner()),
+        FrameDofType::RevoluteAxis(axis).axis()
+    );
+
+    assert!(FrameDofType::RevoluteAxis(axis).is_revolute());
+    assert!(!FrameDofType::RevoluteAxis(axis).is_prismatic());
+    assert!(FrameDofType::PrismaticAxis(axis).is_prismatic());
+    assert!(!FrameDofType::PrismaticAxis(axis).is_revolute());
+    assert!(!FrameDofType::Static.is_revolute());
+    assert!(!FrameDofType::Static.is_prismatic());
+}
+
 // FrameID tests
 
 #[test]
@@ -68,6 +98,29 @@ fn when_displaying_an_id_it_should_write_out_the_internal_id_number() {
 
 // ReferenceFrame tests
 
+#[cfg(feature = "serde")]
+#[test]
+fn when_deserializing_an_id_new_ids_should_not_clash_with_it() {
+    let id: FrameID = serde_json::from_str("1000000").unwrap();
+    assert_eq!("1000000", serde_json::to_string(&id).unwrap());
+
+    let new_id = FrameID::new();
+    assert!(new_id > id);
+
+    let none: FrameID = serde_json::from_str("0").unwrap();
+    assert!(none.is_none());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn when_deserializing_the_largest_id_it_should_error() {
+    let result = serde_json::from_str::<FrameID>(&u64::MAX.to_string());
+    assert!(result.is_err());
+
+    let new_id = FrameID::new();
+    assert!(!new_id.is_none());
+}
+
 #[test]
 fn when_creating_reference_frame_should_be_initialized() {
     let name = "a".to_string();
@@ -80,6 +133,22 @@ fn when_creating_reference_frame_should_be_initialized() {
     assert_ne!(&FrameID::none(), element.id());
     assert_eq!(degree_of_freedom_kind, element.degree_of_freedom_kind());
     assert_eq!(is_actuated, element.is_actuated());
+    assert_eq!(FrameRole::Generic, element.role());
+}
+
+#[test]
+fn when_creating_reference_frame_with_role_should_store_the_role() {
+    let element = ReferenceFrame::new_with_role(
+        "a".to_string(),
+        FrameDofType::RevoluteZ,
+        true,
+        FrameRole::Steering,
+    );
+
+    assert_eq!("a", element.name());
+    assert_eq!(FrameDofType::RevoluteZ, element.degree_of_freedom_kind());
+    assert!(element.is_actuated());
+    assert_eq!(FrameRole::Steering, element.role());
 }
 
 // ChassisElement
@@ -129,7 +198,7 @@ fn when_creating_chassis_element_should_be_initialized() {
 struct MockHardwareSensor {
     receiver: Receiver<JointState>,
     sender: Sender<JointState>,
-    update_sender: Option<Sender<ChangeID>>,
+    update_sender: Option<ChangeNotifier>,
     id: Option<ChangeID>,
 }
 
@@ -142,7 +211,7 @@ impl HardwareSensor for MockHardwareSensor {
         Ok(self.receiver.clone())
     }
 
-    fn on_change(&mut self, id: ChangeID, sender: Sender<ChangeID>) {
+    fn on_change(&mut self, id: ChangeID, sender: ChangeNotifier) {
         self.id = Some(id);
         self.update_sender = Some(sender);
     }
@@ -156,7 +225,7 @@ struct MockHardwareActuator {
     receiver: Receiver<(JointState, ActuatorAvailableRatesOfChange)>,
     sender: Sender<(JointState, ActuatorAvailableRatesOfChange)>,
     command_sender: Sender<JointState>,
-    update_sender: Option<Sender<ChangeID>>,
+    update_sender: Option<ChangeNotifier>,
     id: Option<ChangeID>,
 }
 
@@ -175,7 +244,7 @@ impl HardwareActuator for MockHardwareActuator {
         Ok(self.command_sender.clone())
     }
 
-    fn on_change(&mut self, id: ChangeID, sender: Sender<ChangeID>) {
+    fn on_change(&mut self, id: ChangeID, sender: ChangeNotifier) {
         self.id = Some(id);
         self.update_sender = Some(sender);
     }
@@ -301,3 +370,704 @@ fn test_actuator_set_value() {
     assert!(cmd_result.is_ok());
     assert_eq!(cmd_result.unwrap(), state);
 }
+
+#[test]
+fn when_setting_a_value_it_should_pass_the_command_through_the_middleware() {
+    let (sender, receiver) = crossbeam_channel::unbounded();
+    let (cmd_sender, cmd_receiver) = crossbeam_channel::unbounded();
+    let mut actuator = MockHardwareActuator {
+        receiver,
+        sender,
+        command_sender: cmd_sender,
+        update_sender: None,
+        id: None,
+    };
+    let change_processor = Box::new(HardwareChangeProcessor::new(10));
+
+    let mut actuator_instance = Actuator::new(&mut actuator, &change_processor).unwrap();
+    actuator_instance.add_command_middleware(|command: JointState| {
+        CommandOutcome::Forward(JointState::new(
+            command.position().clamp(-1.0, 1.0),
+            *command.velocity(),
+            *command.acceleration(),
+            *command.jerk(),
+        ))
+    });
+    actuator_instance.add_command_middleware(|command: JointState| {
+        if command.velocity().map_or(false, |v| v.abs() > 5.0) {
+            CommandOutcome::Veto {
+                reason: "velocity too high".to_string(),
+            }
+        } else {
+            CommandOutcome::Forward(command)
+        }
+    });
+    assert_eq!(2, actuator_instance.number_of_command_middleware());
+
+    let result = actuator_instance.update_state(JointState::new(2.0, Some(2.0), None, None));
+    assert!(result.is_ok());
+    assert_eq!(
+        JointState::new(1.0, Some(2.0), None, None),
+        cmd_receiver.recv().unwrap()
+    );
+
+    let result = actuator_instance.update_state(JointState::new(0.5, Some(6.0), None, None));
+    assert_eq!(
+        Err(Error::CommandVetoed {
+            reason: "velocity too high".to_string()
+        }),
+        result
+    );
+    assert!(cmd_receiver.try_recv().is_err());
+
+    actuator_instance.clear_command_middleware();
+    assert_eq!(0, actuator_instance.number_of_command_middleware());
+
+    let state = JointState::new(0.5, Some(6.0), None, None);
+    assert!(actuator_instance.update_state(state).is_ok());
+    assert_eq!(state, cmd_receiver.recv().unwrap());
+}
+
+#[test]
+fn when_setting_a_value_it_should_record_the_last_command() {
+    let (sender, receiver) = crossbeam_channel::unbounded();
+    let (cmd_sender, cmd_receiver) = crossbeam_channel::unbounded();
+    let mut actuator = MockHardwareActuator {
+        receiver,
+        sender,
+        command_sender: cmd_sender,
+        update_sender: None,
+        id: None,
+    };
+    let change_processor = Box::new(HardwareChangeProcessor::new(10));
+
+    let mut actuator_instance = Actuator::new(&mut actuator, &change_processor).unwrap();
+    assert_eq!(None, actuator_instance.last_command());
+
+    actuator_instance.add_command_middleware(|command: JointState| {
+        CommandOutcome::Forward(JointState::new(
+            command.position().clamp(-1.0, 1.0),
+            *command.velocity(),
+            *command.acceleration(),
+            *command.jerk(),
+        ))
+    });
+    actuator_instance
+        .update_state(JointState::new(2.0, Some(3.0), None, None))
+        .unwrap();
+    assert_eq!(
+        JointState::new(1.0, Some(3.0), None, None),
+        *actuator_instance.last_command().unwrap().state()
+    );
+
+    // Commands that are not sent are not recorded
+    drop(cmd_receiver);
+    assert!(actuator_instance
+        .update_state(JointState::new(0.5, Some(1.0), None, None))
+        .is_err());
+    assert_eq!(
+        JointState::new(1.0, Some(3.0), None, None),
+        *actuator_instance.last_command().unwrap().state()
+    );
+}
+
+#[test]
+fn when_receiving_a_sensor_state_it_should_pass_the_state_through_the_middleware() {
+    let (sender, receiver) = crossbeam_channel::unbounded();
+    let mut hardware_sensor = MockHardwareSensor {
+        receiver,
+        sender,
+        update_sender: None,
+        id: None,
+    };
+    let change_processor = Box::new(HardwareChangeProcessor::new(1000));
+
+    let mut sensor = JointSensor::new(&mut hardware_sensor, &change_processor).unwrap();
+    sensor.add_state_middleware(LinearConversion::new(0.5, 0.0));
+    sensor.add_state_middleware(PositionJumpFilter::new(1.0));
+
+    let update_sender = hardware_sensor.update_sender.clone().unwrap();
+    let send = |position: f64| {
+        hardware_sensor
+            .sender
+            .send(JointState::new(position, Some(2.0), None, None))
+            .unwrap();
+        update_sender.send(hardware_sensor.id.unwrap()).unwrap();
+        std::thread::sleep(Duration::from_millis(20));
+    };
+
+    send(2.0);
+    assert_eq!(
+        JointState::new(1.0, Some(1.0), None, None),
+        sensor.value().unwrap()
+    );
+    assert_eq!(0, sensor.rejected_state_count());
+
+    // After the conversion the position jumps by 5.0, which is rejected
+    send(12.0);
+    assert_eq!(
+        JointState::new(1.0, Some(1.0), None, None),
+        sensor.value().unwrap()
+    );
+    assert_eq!(1, sensor.rejected_state_count());
+}
+
+#[test]
+fn when_receiving_an_actuator_state_it_should_pass_the_state_through_the_middleware() {
+    let (sender, receiver) = crossbeam_channel::unbounded();
+    let (cmd_sender, _cmd_receiver) = crossbeam_channel::unbounded();
+    let mut hardware_actuator = MockHardwareActuator {
+        receiver,
+        sender,
+        command_sender: cmd_sender,
+        update_sender: None,
+        id: None,
+    };
+    let change_processor = Box::new(HardwareChangeProcessor::new(1000));
+
+    let mut actuator = Actuator::new(&mut hardware_actuator, &change_processor).unwrap();
+    actuator.add_state_middleware(|state: JointState| {
+        if state.position() < 0.0 {
+            StateOutcome::Reject
+        } else {
+            StateOutcome::Accept(state)
+        }
+    });
+
+    let rates_of_change = ActuatorAvailableRatesOfChange::new(1.0, 1.0, 1.0, 1.0, 1.0, 1.0);
+    let update_sender = hardware_actuator.update_sender.clone().unwrap();
+    let send = |position: f64| {
+        hardware_actuator
+            .sender
+            .send((JointState::new(position, None, None, None), rates_of_change))
+            .unwrap();
+        update_sender.send(hardware_actuator.id.unwrap()).unwrap();
+        std::thread::sleep(Duration::from_millis(20));
+    };
+
+    send(1.0);
+    send(-1.0);
+
+    assert_eq!(
+        JointState::new(1.0, None, None, None),
+        actuator.value().unwrap()
+    );
+    assert_eq!(1, actuator.rejected_state_count());
+    assert_eq!(1, actuator.state_version());
+}
+
+#[test]
+fn when_receiving_an_actuator_state_with_invalid_rates_of_change_it_should_not_pass_the_state_to_the_middleware(
+) {
+    let (sender, receiver) = crossbeam_channel::unbounded();
+    let (cmd_sender, _cmd_receiver) = crossbeam_channel::unbounded();
+    let mut hardware_actuator = MockHardwareActuator {
+        receiver,
+        sender,
+        command_sender: cmd_sender,
+        update_sender: None,
+        id: None,
+    };
+    let change_processor = Box::new(HardwareChangeProcessor::new(1000));
+
+    let mut actuator = Actuator::new(&mut hardware_actuator, &change_processor).unwrap();
+    actuator.add_state_middleware(PositionJumpFilter::new(1.0));
+
+    let valid_rates = ActuatorAvailableRatesOfChange::new(1.0, 1.0, 1.0, 1.0, 1.0, 1.0);
+    let invalid_rates = ActuatorAvailableRatesOfChange::new(f64::NAN, 1.0, 1.0, 1.0, 1.0, 1.0);
+    let update_sender = hardware_actuator.update_sender.clone().unwrap();
+    let send = |position: f64, rates_of_change: ActuatorAvailableRatesOfChange| {
+        hardware_actuator
+            .sender
+            .send((JointState::new(position, None, None, None), rates_of_change))
+            .unwrap();
+        update_sender.send(hardware_actuator.id.unwrap()).unwrap();
+        std::thread::sleep(Duration::from_millis(20));
+    };
+
+    send(0.0, valid_rates);
+    send(0.9, invalid_rates);
+
+    // The filter never saw the rejected state, so the jump is measured from the first position
+    send(-0.5, valid_rates);
+
+    assert_eq!(
+        JointState::new(-0.5, None, None, None),
+        actuator.value().unwrap()
+    );
+    assert_eq!(1, actuator.rejected_state_count());
+    assert_eq!(2, actuator.state_version());
+}
+
+#[test]
+fn when_setting_a_value_it_should_enforce_the_joint_constraint() {
+    let (sender, receiver) = crossbeam_channel::unbounded();
+    let (cmd_sender, cmd_receiver) = crossbeam_channel::unbounded();
+    let mut actuator = MockHardwareActuator {
+        receiver,
+        sender,
+        command_sender: cmd_sender,
+        update_sender: None,
+        id: None,
+    };
+    let change_processor = Box::new(HardwareChangeProcessor::new(10));
+
+    let mut actuator_instance = Actuator::new(&mut actuator, &change_processor).unwrap();
+    actuator_instance.set_constraint(
+        JointConstraint::new()
+            .with_position_limits(-1.0, 1.0)
+            .with_velocity_limits(-2.0, 2.0),
+    );
+    assert_eq!(
+        ConstraintEnforcement::Disabled,
+        actuator_instance.constraint_enforcement()
+    );
+
+    let state = JointState::new(2.0, Some(3.0), None, None);
+    assert!(actuator_instance.update_state(state).is_ok());
+    assert_eq!(state, cmd_receiver.recv().unwrap());
+
+    actuator_instance.set_constraint_enforcement(ConstraintEnforcement::Clamp);
+    assert!(actuator_instance.update_state(state).is_ok());
+    assert_eq!(
+        JointState::new(1.0, Some(2.0), None, None),
+        cmd_receiver.recv().unwrap()
+    );
+
+    actuator_instance.set_constraint_enforcement(ConstraintEnforcement::Reject);
+    assert_eq!(
+        Err(Error::JointLimitExceeded {
+            quantity: "position".to_string(),
+            value: 2.0,
+            minimum: -1.0,
+            maximum: 1.0
+        }),
+        actuator_instance.update_state(state)
+    );
+    assert!(cmd_receiver.try_recv().is_err());
+
+    let state = JointState::new(0.5, Some(-1.0), None, None);
+    assert!(actuator_instance.update_state(state).is_ok());
+    assert_eq!(state, cmd_receiver.recv().unwrap());
+}
+
+#[test]
+fn when_submitting_a_profile_it_should_send_the_interpolated_states() {
+    let (sender, receiver) = crossbeam_channel::unbounded();
+    let (cmd_sender, cmd_receiver) = crossbeam_channel::unbounded();
+    let mut actuator = MockHardwareActuator {
+        receiver,
+        sender,
+        command_sender: cmd_sender,
+        update_sender: None,
+        id: None,
+    };
+    let change_processor = Box::new(HardwareChangeProcessor::new(100));
+
+    let actuator_instance = Actuator::new(&mut actuator, &change_processor).unwrap();
+    let profile = JointProfile::new(vec![
+        (Duration::ZERO, JointState::new(0.0, Some(10.0), None, None)),
+        (
+            Duration::from_millis(100),
+            JointState::new(1.0, Some(10.0), None, None),
+        ),
+    ])
+    .unwrap();
+    actuator_instance
+        .submit_profile(profile, &change_processor)
+        .unwrap();
+    assert!(actuator_instance.has_active_profile());
+
+    std::thread::sleep(Duration::from_millis(300));
+    assert!(!actuator_instance.has_active_profile());
+
+    let commands: Vec<_> = cmd_receiver.try_iter().collect();
+    assert!(commands.len() > 2, "{:?}", commands);
+    assert!(commands
+        .windows(2)
+        .all(|pair| pair[0].position() <= pair[1].position()));
+    assert_eq!(
+        &JointState::new(1.0, Some(10.0), None, None),
+        commands.last().unwrap()
+    );
+
+    // A cancelled profile is no longer dispatched
+    let profile = JointProfile::new(vec![(
+        Duration::from_secs(10),
+        JointState::new(2.0, None, None, None),
+    )])
+    .unwrap();
+    actuator_instance
+        .submit_profile(profile, &change_processor)
+        .unwrap();
+    std::thread::sleep(Duration::from_millis(20));
+    actuator_instance.cancel_profile();
+    assert!(!actuator_instance.has_active_profile());
+
+    std::thread::sleep(Duration::from_millis(20));
+    cmd_receiver.try_iter().count();
+    std::thread::sleep(Duration::from_millis(20));
+    assert!(cmd_receiver.try_recv().is_err());
+}
+
+#[test]
+fn when_submitting_a_profile_it_should_enforce_the_joint_constraint_on_the_points() {
+    let (sender, receiver) = crossbeam_channel::unbounded();
+    let (cmd_sender, _cmd_receiver) = crossbeam_channel::unbounded();
+    let mut actuator = MockHardwareActuator {
+        receiver,
+        sender,
+        command_sender: cmd_sender,
+        update_sender: None,
+        id: None,
+    };
+    let change_processor = Box::new(HardwareChangeProcessor::new(100));
+
+    let mut actuator_instance = Actuator::new(&mut actuator, &change_processor).unwrap();
+    actuator_instance.set_constraint(JointConstraint::new().with_position_limits(-1.0, 1.0));
+    actuator_instance.set_constraint_enforcement(ConstraintEnforcement::Reject);
+
+    let profile = JointProfile::new(vec![
+        (Duration::ZERO, JointState::new(0.0, None, None, None)),
+        (
+            Duration::from_millis(100),
+            JointState::new(2.0, None, None, None),
+        ),
+    ])
+    .unwrap();
+    assert_eq!(
+        Err(Error::JointLimitExceeded {
+            quantity: "position".to_string(),
+            value: 2.0,
+            minimum: -1.0,
+            maximum: 1.0
+        }),
+        actuator_instance.submit_profile(profile, &change_processor)
+    );
+    assert!(!actuator_instance.has_active_profile());
+}
+
+// JointConstraint tests
+
+#[test]
+fn when_creating_a_joint_constraint_it_should_not_limit_the_joint() {
+    let constraint = JointConstraint::new();
+    assert!(!constraint.is_limited());
+    assert_eq!(None, constraint.position_limits());
+    assert_eq!(None, constraint.velocity_limits());
+    assert_eq!(None, constraint.acceleration_limits());
+    assert_eq!(None, constraint.effort_limits());
+
+    let state = JointState::new(1e6, Some(-1e6), Some(1e6), Some(1e6));
+    assert!(constraint.validate(&state).is_ok());
+    assert_eq!(state, constraint.clamp(state));
+}
+
+#[test]
+fn when_validating_a_state_against_a_joint_constraint_it_should_check_each_limit() {
+    let constraint = JointConstraint::new()
+        .with_position_limits(-0.5, 0.5)
+        .with_velocity_limits(-1.0, 1.0)
+        .with_acceleration_limits(-2.0, 2.0)
+        .with_effort_limits(-10.0, 10.0);
+    assert!(constraint.is_limited());
+    assert_eq!(Some((-0.5, 0.5)), constraint.position_limits());
+    assert_eq!(Some((-1.0, 1.0)), constraint.velocity_limits());
+    assert_eq!(Some((-2.0, 2.0)), constraint.acceleration_limits());
+    assert_eq!(Some((-10.0, 10.0)), constraint.effort_limits());
+
+    // The limits are inclusive and unknown values are not checked
+    assert!(constraint
+        .validate(&JointState::new(0.5, Some(-1.0), None, Some(100.0)))
+        .is_ok());
+
+    assert_eq!(
+        Err(Error::JointLimitExceeded {
+            quantity: "velocity".to_string(),
+            value: 1.5,
+            minimum: -1.0,
+            maximum: 1.0
+        }),
+        constraint.validate(&JointState::new(0.0, Some(1.5), None, None))
+    );
+    assert_eq!(
+        Err(Error::JointLimitExceeded {
+            quantity: "acceleration".to_string(),
+            value: -3.0,
+            minimum: -2.0,
+            maximum: 2.0
+        }),
+        constraint.validate(&JointState::new(0.0, Some(0.0), Some(-3.0), None))
+    );
+}
+
+#[test]
+fn when_clamping_a_state_to_a_joint_constraint_it_should_only_change_the_limited_values() {
+    let constraint = JointConstraint::new()
+        .with_position_limits(-0.5, 0.5)
+        .with_acceleration_limits(-2.0, 2.0);
+
+    assert_eq!(
+        JointState::new(-0.5, Some(10.0), Some(2.0), Some(5.0)),
+        constraint.clamp(JointState::new(-0.7, Some(10.0), Some(3.0), Some(5.0)))
+    );
+    assert_eq!(
+        JointState::new(0.25, None, None, None),
+        constraint.clamp(JointState::new(0.25, None, None, None))
+    );
+}
+
+#[test]
+fn when_monitoring_an_actuator_it_should_report_its_health() {
+    let (sender, receiver) = crossbeam_channel::unbounded();
+    let (cmd_sender, cmd_receiver) = crossbeam_channel::unbounded();
+    let mut hardware_actuator = MockHardwareActuator {
+        receiver,
+        sender,
+        command_sender: cmd_sender,
+        update_sender: None,
+        id: None,
+    };
+    let change_processor = HardwareChangeProcessor::manual();
+
+    let mut actuator = Actuator::new(&mut hardware_actuator, &change_processor).unwrap();
+    assert_eq!(ActuatorHealth::Ok, actuator.health());
+
+    // Without a state the actuator is stale as soon as it has a timeout
+    actuator.set_stale_timeout(Some(Duration::from_secs(60)));
+    assert_eq!(Some(Duration::from_secs(60)), actuator.stale_timeout());
+    assert_eq!(ActuatorHealth::Stale, actuator.health());
+
+    let update_sender = hardware_actuator.update_sender.clone().unwrap();
+    let send = |position: f64| {
+        hardware_actuator
+            .sender
+            .send((
+                JointState::new(position, None, None, None),
+                ActuatorAvailableRatesOfChange::new(1.0, 1.0, 1.0, 1.0, 1.0, 1.0),
+            ))
+            .unwrap();
+        update_sender.send(hardware_actuator.id.unwrap()).unwrap();
+        change_processor.process_pending();
+    };
+
+    send(1.0);
+    assert!(actuator.health().is_ok());
+
+    actuator.set_stale_timeout(Some(Duration::ZERO));
+    std::thread::sleep(Duration::from_millis(2));
+    assert_eq!(ActuatorHealth::Stale, actuator.health());
+    actuator.set_stale_timeout(None);
+
+    actuator.set_constraint(JointConstraint::new().with_position_limits(-0.5, 0.5));
+    assert_eq!(
+        ActuatorHealth::Faulted(ActuatorFault::OutOfRange {
+            quantity: "position".to_string(),
+            value: 1.0
+        }),
+        actuator.health()
+    );
+    send(0.25);
+    assert_eq!(ActuatorHealth::Ok, actuator.health());
+
+    // A disconnection is latched until it is cleared
+    drop(cmd_receiver);
+    let error = actuator
+        .update_state(JointState::new(0.0, None, None, None))
+        .unwrap_err();
+    assert_eq!(ErrorKind::Channel, error.kind());
+    assert!(std::error::Error::source(&error).is_some());
+    assert_eq!(
+        ActuatorHealth::Faulted(ActuatorFault::Disconnected),
+        actuator.health()
+    );
+    send(0.0);
+    assert!(actuator.health().is_faulted());
+
+    actuator.clear_fault();
+    assert_eq!(ActuatorHealth::Ok, actuator.health());
+}
+
+#[test]
+fn when_creating_a_joint_calibration_it_should_reject_invalid_values() {
+    assert!(JointCalibration::default().is_identity());
+    assert!(!JointCalibration::new(0.1, 1.0).unwrap().is_identity());
+
+    for (offset, scale) in [(0.0, 0.0), (f64::NAN, 1.0), (0.0, f64::INFINITY)] {
+        assert!(matches!(
+            JointCalibration::new(offset, scale),
+            Err(Error::InvalidJointCalibration { .. })
+        ));
+    }
+}
+
+#[test]
+fn when_calibrating_an_actuator_it_should_convert_the_states_and_the_commands() {
+    let (sender, receiver) = crossbeam_channel::unbounded();
+    let (cmd_sender, cmd_receiver) = crossbeam_channel::unbounded();
+    let mut hardware_actuator = MockHardwareActuator {
+        receiver,
+        sender,
+        command_sender: cmd_sender,
+        update_sender: None,
+        id: None,
+    };
+    let change_processor = HardwareChangeProcessor::manual();
+
+    let mut actuator = Actuator::new(&mut hardware_actuator, &change_processor).unwrap();
+    actuator.add_state_middleware(LinearConversion::new(0.5, 0.0));
+    actuator.set_calibration(JointCalibration::new(-1.0, 2.0).unwrap());
+    assert_eq!(-1.0, actuator.calibration().offset());
+    assert_eq!(2.0, actuator.calibration().scale());
+
+    // The calibration is applied after the middleware
+    hardware_actuator
+        .sender
+        .send((
+            JointState::new(3.0, Some(1.0), None, None),
+            ActuatorAvailableRatesOfChange::new(1.0, 1.0, 1.0, 1.0, 1.0, 1.0),
+        ))
+        .unwrap();
+    hardware_actuator
+        .update_sender
+        .as_ref()
+        .unwrap()
+        .send(hardware_actuator.id.unwrap())
+        .unwrap();
+    change_processor.process_pending();
+    assert_eq!(
+        JointState::new(2.0, Some(1.0), None, None),
+        actuator.value().unwrap()
+    );
+
+    // The hardware receives the raw command, the actuator records the joint command
+    actuator
+        .update_state(JointState::new(2.0, Some(1.0), None, None))
+        .unwrap();
+    assert_eq!(
+        JointState::new(1.5, Some(0.5), None, None),
+        cmd_receiver.recv().unwrap()
+    );
+    assert_eq!(
+        JointState::new(2.0, Some(1.0), None, None),
+        *actuator.last_command().unwrap().state()
+    );
+}
+
+#[test]
+fn when_creating_a_joint_transmission_it_should_reject_invalid_gear_ratios() {
+    assert!(JointTransmission::default().is_identity());
+    let transmission = JointTransmission::new(true, 1.0).unwrap();
+    assert!(transmission.is_inverted());
+    assert!(!transmission.is_identity());
+
+    for gear_ratio in [0.0, -2.0, f64::NAN, f64::INFINITY] {
+        assert!(matches!(
+            JointTransmission::new(false, gear_ratio),
+            Err(Error::InvalidJointTransmission { .. })
+        ));
+    }
+}
+
+#[test]
+fn when_an_actuator_has_a_transmission_it_should_apply_it_before_the_calibration() {
+    let (sender, receiver) = crossbeam_channel::unbounded();
+    let (cmd_sender, cmd_receiver) = crossbeam_channel::unbounded();
+    let mut hardware_actuator = MockHardwareActuator {
+        receiver,
+        sender,
+        command_sender: cmd_sender,
+        update_sender: None,
+        id: None,
+    };
+    let change_processor = HardwareChangeProcessor::manual();
+
+    let mut actuator = Actuator::new(&mut hardware_actuator, &change_processor).unwrap();
+    actuator.set_transmission(JointTransmission::new(true, 10.0).unwrap());
+    actuator.set_calibration(JointCalibration::new(0.5, 1.0).unwrap());
+    assert_eq!(10.0, actuator.transmission().gear_ratio());
+
+    hardware_actuator
+        .sender
+        .send((
+            JointState::new(20.0, Some(5.0), None, None),
+            ActuatorAvailableRatesOfChange::new(1.0, 1.0, 1.0, 1.0, 1.0, 1.0),
+        ))
+        .unwrap();
+    hardware_actuator
+        .update_sender
+        .as_ref()
+        .unwrap()
+        .send(hardware_actuator.id.unwrap())
+        .unwrap();
+    change_processor.process_pending();
+    assert_eq!(
+        JointState::new(-1.5, Some(-0.5), None, None),
+        actuator.value().unwrap()
+    );
+
+    actuator
+        .update_state(JointState::new(-1.5, Some(-0.5), None, None))
+        .unwrap();
+    assert_eq!(
+        JointState::new(20.0, Some(5.0), None, None),
+        cmd_receiver.recv().unwrap()
+    );
+}
+
+#[test]
+fn when_an_actuator_has_a_history_it_should_interpolate_the_past_states() {
+    let (sender, receiver) = crossbeam_channel::unbounded();
+    let (cmd_sender, _cmd_receiver) = crossbeam_channel::unbounded();
+    let mut hardware_actuator = MockHardwareActuator {
+        receiver,
+        sender,
+        command_sender: cmd_sender,
+        update_sender: None,
+        id: None,
+    };
+    let change_processor = HardwareChangeProcessor::manual();
+
+    let mut actuator = Actuator::new(&mut hardware_actuator, &change_processor).unwrap();
+    let update_sender = hardware_actuator.update_sender.clone().unwrap();
+    let send = |position: f64| {
+        hardware_actuator
+            .sender
+            .send((
+                JointState::new(position, Some(2.0 * position), None, None),
+                ActuatorAvailableRatesOfChange::new(1.0, 1.0, 1.0, 1.0, 1.0, 1.0),
+            ))
+            .unwrap();
+        update_sender.send(hardware_actuator.id.unwrap()).unwrap();
+        change_processor.process_pending();
+        std::thread::sleep(Duration::from_millis(1));
+    };
+
+    // Without a capacity no states are stored
+    send(0.0);
+    assert_eq!(0, actuator.history_capacity());
+    assert!(actuator.history().is_empty());
+    assert_eq!(None, actuator.state_at(Instant::now()));
+
+    actuator.set_history_capacity(2);
+    for position in [1.0, 2.0, 3.0] {
+        send(position);
+    }
+
+    let history = actuator.history();
+    assert_eq!(2, history.len());
+    assert_eq!(2.0, history[0].state().position());
+    assert_eq!(3.0, history[1].state().position());
+
+    let start = history[0].received_at();
+    let middle = start + (history[1].received_at() - start) / 2;
+    let state = actuator.state_at(middle).unwrap();
+    assert!((state.position() - 2.5).abs() < 1e-6);
+    assert!((state.velocity().unwrap() - 5.0).abs() < 1e-6);
+
+    assert_eq!(None, actuator.state_at(start - Duration::from_millis(1)));
+    assert_eq!(Some(*history[1].state()), actuator.state_at(Instant::now()));
+
+    actuator.set_history_capacity(1);
+    assert_eq!(vec![history[1]], actuator.history());
+}
@@ -0,0 +1,106 @@
+//! Defines the types used to track whether the wheels of the vehicle are in contact with the
+//! ground.
+//!
+//! A wheel is considered to have lost contact with the ground when the suspension between the
+//! wheel and the body is at full droop, i.e. fully extended, and the estimated load on the
+//! wheel is close to zero. Once a wheel has lost contact with the ground the velocity of the
+//! wheel no longer says anything about the motion of the vehicle, so the wheel is excluded
+//! from the odometry calculations until contact is restored.
+
+use super::frame_elements::FrameID;
+
+#[cfg(test)]
+#[path = "ground_contact_tests.rs"]
+mod ground_contact_tests;
+
+/// Defines the events that are sent when the ground contact state of a wheel changes.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GroundContactEvent {
+    /// Indicates that the wheel has lost contact with the ground.
+    ContactLost {
+        /// The ID of the wheel frame element.
+        wheel: FrameID,
+    },
+
+    /// Indicates that the wheel has regained contact with the ground.
+    ContactRestored {
+        /// The ID of the wheel frame element.
+        wheel: FrameID,
+    },
+}
+
+impl GroundContactEvent {
+    /// Returns the [FrameID] of the wheel the event applies to.
+    pub fn wheel(&self) -> &FrameID {
+        match self {
+            GroundContactEvent::ContactLost { wheel } => wheel,
+            GroundContactEvent::ContactRestored { wheel } => wheel,
+        }
+    }
+}
+
+/// Defines the thresholds that are used to determine if a wheel is in contact with the ground.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GroundContactThresholds {
+    /// The maximum distance, in meters or radians, between the position of a suspension joint
+    /// and its full droop position for the joint to be considered at full droop.
+    droop_tolerance: f64,
+
+    /// The load, in Newton, below which the wheel is considered to be unloaded.
+    minimum_load: f64,
+}
+
+impl GroundContactThresholds {
+    /// Returns the maximum distance, in meters or radians, between the position of a suspension
+    /// joint and its full droop position for the joint to be considered at full droop.
+    pub fn droop_tolerance(&self) -> f64 {
+        self.droop_tolerance
+    }
+
+    /// Returns true if a suspension joint at the given position is at full droop.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'position' - The current position of the suspension joint.
+    /// * 'droop_position' - The position of the suspension joint at full droop.
+    pub fn is_at_full_droop(&self, position: f64, droop_position: f64) -> bool {
+        (position - droop_position).abs() <= self.droop_tolerance
+    }
+
+    /// Returns true if the given wheel load is considered to be close to zero.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'load' - The estimated load on the wheel in Newton.
+    pub fn is_unloaded(&self, load: f64) -> bool {
+        load <= self.minimum_load
+    }
+
+    /// Returns the load, in Newton, below which the wheel is considered to be unloaded.
+    pub fn minimum_load(&self) -> f64 {
+        self.minimum_load
+    }
+
+    /// Creates a new [GroundContactThresholds] instance.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'droop_tolerance' - The maximum distance, in meters or radians, between the position of
+    ///   a suspension joint and its full droop position for the joint to be considered at full
+    ///   droop.
+    /// * 'minimum_load' - The load, in Newton, below which the wheel is considered to be unloaded.
+    pub fn new(droop_tolerance: f64, minimum_load: f64) -> Self {
+        Self {
+            droop_tolerance,
+            minimum_load,
+        }
+    }
+}
+
+impl Default for GroundContactThresholds {
+    fn default() -> Self {
+        Self::new(1e-3, 1.0)
+    }
+}
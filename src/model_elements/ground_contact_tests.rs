@@ -0,0 +1,28 @@
+use crate::model_elements::frame_elements::FrameID;
+
+use super::{GroundContactEvent, GroundContactThresholds};
+
+#[test]
+fn when_getting_the_wheel_of_an_event_it_should_return_the_wheel() {
+    let wheel = FrameID::new();
+
+    assert_eq!(&wheel, GroundContactEvent::ContactLost { wheel }.wheel());
+    assert_eq!(
+        &wheel,
+        GroundContactEvent::ContactRestored { wheel }.wheel()
+    );
+}
+
+#[test]
+fn when_checking_thresholds_it_should_compare_against_the_limits() {
+    let thresholds = GroundContactThresholds::new(0.01, 5.0);
+
+    assert_eq!(0.01, thresholds.droop_tolerance());
+    assert_eq!(5.0, thresholds.minimum_load());
+
+    assert!(thresholds.is_at_full_droop(-0.105, -0.1));
+    assert!(!thresholds.is_at_full_droop(-0.05, -0.1));
+
+    assert!(thresholds.is_unloaded(4.0));
+    assert!(!thresholds.is_unloaded(6.0));
+}
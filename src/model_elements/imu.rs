@@ -0,0 +1,144 @@
+//! Provides the attitude of a vehicle from the orientation reported by its inertial measurement
+//! unit (IMU).
+//!
+//! One frame of a [MotionModel] can be registered as the frame of the IMU with
+//! [MotionModel::set_imu_frame]. The orientation of the IMU in the world, as reported by the IMU,
+//! is passed to the model with [MotionModel::update_imu_orientation]. The IMU is often not
+//! mounted directly on the body, e.g. it is mounted on a suspended sensor platform, so the
+//! orientation of the body is derived from the orientation of the IMU and the current transform
+//! between the IMU frame and the body frame, see [MotionModel::body_orientation_in_world].
+//!
+//! As long as no IMU orientation is known the orientation of the body is taken from
+//! [MotionModel::body_pose_in_world].
+
+use nalgebra::UnitQuaternion;
+
+use crate::Error;
+
+use super::{frame_elements::FrameID, model::MotionModel};
+
+#[cfg(test)]
+#[path = "imu_tests.rs"]
+mod imu_tests;
+
+impl MotionModel {
+    /// Returns the orientation of the body in the world frame.
+    ///
+    /// When an IMU orientation is known the orientation is derived from the orientation of the
+    /// IMU and the current transform between the IMU frame and the body frame, which takes the
+    /// current positions of the joints between the IMU and the body into account. Otherwise the
+    /// orientation of [MotionModel::body_pose_in_world] is returned.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use std::f64::consts::PI;
+    /// use nalgebra::{Isometry3, Matrix3, Translation3, UnitQuaternion, Vector3};
+    /// use swerve_vehicle_descriptors::model_elements::model::{
+    ///     ChassisElementPhysicalProperties, MotionModel,
+    /// };
+    ///
+    /// let properties = ChassisElementPhysicalProperties::from_mass_properties(
+    ///     1.0,
+    ///     Vector3::zeros(),
+    ///     Matrix3::identity(),
+    /// );
+    /// let mut model = MotionModel::new();
+    /// let body = model
+    ///     .add_body(
+    ///         "body".to_string(),
+    ///         Translation3::identity(),
+    ///         UnitQuaternion::identity(),
+    ///         properties.clone(),
+    ///     )
+    ///     .unwrap();
+    ///
+    /// // The IMU is mounted upside down
+    /// let imu = model
+    ///     .add_static_chassis_element(
+    ///         "imu".to_string(),
+    ///         body,
+    ///         Translation3::new(0.0, 0.0, 0.1),
+    ///         UnitQuaternion::from_euler_angles(PI, 0.0, 0.0),
+    ///         properties,
+    ///     )
+    ///     .unwrap();
+    /// model.set_imu_frame(Some(imu)).unwrap();
+    ///
+    /// // The vehicle is level, so the IMU reports that it is upside down
+    /// model
+    ///     .update_imu_orientation(UnitQuaternion::from_euler_angles(PI, 0.0, 0.0))
+    ///     .unwrap();
+    /// assert!(model.body_orientation_in_world().angle() < 1e-12);
+    /// ```
+    pub fn body_orientation_in_world(&self) -> UnitQuaternion<f64> {
+        let from_imu = match (self.imu_frame, self.imu_orientation) {
+            (Some(frame_id), Some(orientation)) => self
+                .isometry_to_body(&frame_id)
+                .ok()
+                .map(|imu_to_body| orientation * imu_to_body.rotation.inverse()),
+            _ => None,
+        };
+
+        from_imu.unwrap_or(self.body_pose_in_world().rotation)
+    }
+
+    /// Returns the [FrameID] of the frame that is registered as the IMU, or [None] if no frame
+    /// is registered.
+    pub fn imu_frame(&self) -> Option<FrameID> {
+        self.imu_frame
+    }
+
+    /// Returns the last orientation of the IMU in the world frame, or [None] if no orientation
+    /// was received since the IMU frame was registered.
+    pub fn imu_orientation(&self) -> Option<&UnitQuaternion<f64>> {
+        self.imu_orientation.as_ref()
+    }
+
+    /// Registers the frame of the IMU, replacing the frame that was registered before. Any IMU
+    /// orientation that was received for the previous frame is discarded.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'frame_id' - The [FrameID] of the IMU frame, or [None] to remove the IMU.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the frame is not part of the model.
+    pub fn set_imu_frame(&mut self, frame_id: Option<FrameID>) -> Result<(), Error> {
+        if let Some(id) = frame_id {
+            if !self.kinematic_tree().has_element(&id) {
+                return Err(Error::MissingFrameElement { id });
+            }
+        }
+
+        self.imu_frame = frame_id;
+        self.imu_orientation = None;
+        Ok(())
+    }
+
+    /// Updates the orientation of the IMU, as reported by the IMU.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'orientation' - The rotation that turns directions in the IMU frame into directions in
+    ///   the world frame.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingImuFrame] - Returned when no frame is registered as the IMU.
+    /// * [Error::NonUnitOrientation] - Returned, in audit mode, when the orientation is not
+    ///   normalized.
+    pub fn update_imu_orientation(
+        &mut self,
+        orientation: UnitQuaternion<f64>,
+    ) -> Result<(), Error> {
+        if self.imu_frame.is_none() {
+            return Err(Error::MissingImuFrame);
+        }
+
+        self.audit_orientation(&orientation)?;
+        self.imu_orientation = Some(orientation);
+        Ok(())
+    }
+}
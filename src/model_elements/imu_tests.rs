@@ -0,0 +1,168 @@
+use std::{f64::consts::PI, time::Duration};
+
+use nalgebra::{Isometry3, Translation3, UnitQuaternion, Vector3};
+
+use crate::{
+    change_notification_processing::HardwareChangeProcessor,
+    hardware::{
+        joint_state::{JointState, JointStateRange},
+        simulation::{SimulatedActuator, SimulatedDynamics},
+    },
+    model_elements::{
+        frame_elements::{Actuator, FrameDofType, FrameID, JointConstraint},
+        model::MotionModel,
+    },
+    number_space::NumberSpaceType,
+    test_fixtures::physical_properties,
+    Error,
+};
+
+// A body with an IMU that is mounted on a platform which can rotate around the z-axis of the
+// body.
+struct TestVehicle {
+    model: MotionModel,
+    imu: FrameID,
+    platform_hardware: SimulatedActuator,
+    processor: HardwareChangeProcessor,
+}
+
+impl TestVehicle {
+    fn new() -> Self {
+        let processor = HardwareChangeProcessor::manual();
+        let mut model = MotionModel::new();
+        let body = model
+            .add_body(
+                "body".to_string(),
+                Translation3::identity(),
+                UnitQuaternion::identity(),
+                physical_properties(),
+            )
+            .unwrap();
+
+        let suspension = model
+            .add_suspension_element(
+                "suspension".to_string(),
+                FrameDofType::PrismaticZ,
+                body,
+                Translation3::new(0.0, 0.0, 0.5),
+                UnitQuaternion::identity(),
+                physical_properties(),
+                JointConstraint::new(),
+            )
+            .unwrap();
+
+        let mut platform_hardware = SimulatedActuator::new(
+            NumberSpaceType::AngularLimited {
+                start_angle_in_radians: 0.0,
+            },
+            JointStateRange::new(
+                JointState::new(-100.0, None, None, None),
+                JointState::new(100.0, None, None, None),
+            ),
+            SimulatedDynamics::new(),
+        );
+        let platform = model
+            .add_steering_element(
+                "platform".to_string(),
+                suspension,
+                Translation3::identity(),
+                UnitQuaternion::identity(),
+                physical_properties(),
+                Actuator::new(&mut platform_hardware, &processor).unwrap(),
+            )
+            .unwrap();
+
+        let imu = model
+            .add_static_chassis_element(
+                "imu".to_string(),
+                platform,
+                Translation3::new(0.1, 0.0, 0.0),
+                UnitQuaternion::identity(),
+                physical_properties(),
+            )
+            .unwrap();
+        processor.process_pending();
+
+        Self {
+            model,
+            imu,
+            platform_hardware,
+            processor,
+        }
+    }
+
+    fn set_platform_angle(&self, angle: f64) {
+        self.platform_hardware
+            .set_state(JointState::new(angle, Some(0.0), None, None));
+        self.platform_hardware.step(Duration::from_millis(10));
+        self.processor.process_pending();
+    }
+}
+
+#[test]
+fn when_no_imu_orientation_is_known_it_should_use_the_body_pose() {
+    let mut vehicle = TestVehicle::new();
+    let pose = Isometry3::new(Vector3::new(1.0, 2.0, 0.0), Vector3::new(0.0, 0.0, 0.5));
+    vehicle.model.set_body_pose_in_world(pose);
+    assert_eq!(pose.rotation, vehicle.model.body_orientation_in_world());
+
+    assert_eq!(
+        Err(Error::MissingImuFrame),
+        vehicle
+            .model
+            .update_imu_orientation(UnitQuaternion::identity())
+    );
+
+    vehicle.model.set_imu_frame(Some(vehicle.imu)).unwrap();
+    assert_eq!(Some(vehicle.imu), vehicle.model.imu_frame());
+    assert_eq!(None, vehicle.model.imu_orientation());
+    assert_eq!(pose.rotation, vehicle.model.body_orientation_in_world());
+
+    let missing = FrameID::new();
+    assert_eq!(
+        Err(Error::MissingFrameElement { id: missing }),
+        vehicle.model.set_imu_frame(Some(missing))
+    );
+}
+
+#[test]
+fn when_the_imu_reports_an_orientation_it_should_account_for_the_joints_to_the_body() {
+    let mut vehicle = TestVehicle::new();
+    vehicle.model.set_imu_frame(Some(vehicle.imu)).unwrap();
+
+    // The platform is turned 90 degrees, so the IMU is turned 90 degrees relative to the body
+    vehicle.set_platform_angle(0.5 * PI);
+
+    // The body is pitched 0.1 radians in the world
+    let body = UnitQuaternion::from_euler_angles(0.0, 0.1, 0.0);
+    let imu = body * UnitQuaternion::from_euler_angles(0.0, 0.0, 0.5 * PI);
+    vehicle.model.update_imu_orientation(imu).unwrap();
+    assert_eq!(Some(&imu), vehicle.model.imu_orientation());
+    assert!(vehicle.model.body_orientation_in_world().angle_to(&body) < 1e-12);
+
+    let expected_gravity = body.inverse() * vehicle.model.gravity();
+    assert!((expected_gravity - vehicle.model.gravity_in_body()).norm() < 1e-12);
+
+    // Moving the platform changes the orientation of the body that is derived from the same
+    // IMU orientation
+    vehicle.set_platform_angle(0.0);
+    assert!(vehicle.model.body_orientation_in_world().angle_to(&imu) < 1e-12);
+}
+
+#[test]
+fn when_removing_the_imu_frame_it_should_remove_the_imu() {
+    let mut vehicle = TestVehicle::new();
+    vehicle.model.set_imu_frame(Some(vehicle.imu)).unwrap();
+    vehicle
+        .model
+        .update_imu_orientation(UnitQuaternion::from_euler_angles(0.2, 0.0, 0.0))
+        .unwrap();
+
+    vehicle.model.remove_element(&vehicle.imu).unwrap();
+    assert_eq!(None, vehicle.model.imu_frame());
+    assert_eq!(None, vehicle.model.imu_orientation());
+    assert_eq!(
+        UnitQuaternion::identity(),
+        vehicle.model.body_orientation_in_world()
+    );
+}
@@ -0,0 +1,210 @@
+//! Defines lookup tables that describe the transform of a frame element relative to its parent
+//! as a function of the joint variable.
+//!
+//! Not all joints move along a single axis. For instance the wheel of a double wishbone
+//! suspension moves along a curved path while changing its camber angle. For these joints the
+//! transform from the child frame to the parent frame can be described by a
+//! [JointKinematicsTable] which interpolates between a set of known transforms.
+
+use nalgebra::{Isometry3, Translation3, Vector3};
+
+use crate::Error;
+
+#[cfg(test)]
+#[path = "kinematics_table_tests.rs"]
+mod kinematics_table_tests;
+
+/// Defines how the translation between two samples in a [JointKinematicsTable] is interpolated.
+///
+/// The rotation between two samples is always interpolated using a spherical linear
+/// interpolation.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TableInterpolation {
+    /// Linear interpolation between the two samples on either side of the joint value.
+    Linear,
+
+    /// Cubic Hermite interpolation with the tangents computed from the neighbouring samples
+    /// (Catmull-Rom spline). This results in a smooth path that passes through all samples.
+    CubicSpline,
+}
+
+/// Describes the transform from a child frame to its parent frame as a function of the
+/// joint variable.
+///
+/// The table stores a set of samples, each consisting of a joint value and the transform from
+/// the child frame to the parent frame at that joint value. The transform for joint values
+/// between the samples is interpolated. Joint values outside the range of the table are
+/// clamped to the first or last sample.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct JointKinematicsTable {
+    /// The joint values of the samples, in strictly increasing order.
+    joint_values: Vec<f64>,
+
+    /// The transforms from the child frame to the parent frame for each of the joint values.
+    transforms: Vec<Isometry3<f64>>,
+
+    /// The interpolation method used for the translation.
+    interpolation: TableInterpolation,
+}
+
+impl JointKinematicsTable {
+    /// Creates a new [JointKinematicsTable] instance.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'samples' - The joint values and the transforms from the child frame to the parent
+    ///   frame at those joint values. The joint values must be strictly increasing.
+    /// * 'interpolation' - The interpolation method used for the translation.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::InvalidKinematicsTable] - Returned when fewer than two samples are provided,
+    ///   when a joint value is not finite or when the joint values are not strictly increasing.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use nalgebra::{Isometry3, Translation3, UnitQuaternion};
+    /// use swerve_vehicle_descriptors::model_elements::kinematics_table::{
+    ///     JointKinematicsTable, TableInterpolation,
+    /// };
+    ///
+    /// let table = JointKinematicsTable::new(
+    ///     vec![
+    ///         (0.0, Isometry3::from_parts(Translation3::new(0.0, 0.0, 0.0), UnitQuaternion::identity())),
+    ///         (1.0, Isometry3::from_parts(Translation3::new(0.1, 0.0, 1.0), UnitQuaternion::identity())),
+    ///     ],
+    ///     TableInterpolation::Linear,
+    /// )
+    /// .unwrap();
+    ///
+    /// let transform = table.transform_at(0.5);
+    /// assert_eq!(transform.translation.vector.z, 0.5);
+    /// ```
+    pub fn new(
+        samples: Vec<(f64, Isometry3<f64>)>,
+        interpolation: TableInterpolation,
+    ) -> Result<Self, Error> {
+        if samples.len() < 2 {
+            return Err(Error::InvalidKinematicsTable);
+        }
+
+        if samples.iter().any(|(value, _)| !value.is_finite()) {
+            return Err(Error::InvalidKinematicsTable);
+        }
+
+        if samples.windows(2).any(|pair| pair[0].0 >= pair[1].0) {
+            return Err(Error::InvalidKinematicsTable);
+        }
+
+        let (joint_values, transforms) = samples.into_iter().unzip();
+        Ok(Self {
+            joint_values,
+            transforms,
+            interpolation,
+        })
+    }
+
+    /// Returns the interpolation method used for the translation.
+    pub fn interpolation(&self) -> TableInterpolation {
+        self.interpolation
+    }
+
+    /// Returns the largest joint value in the table.
+    pub fn maximum_joint_value(&self) -> f64 {
+        self.joint_values[self.joint_values.len() - 1]
+    }
+
+    /// Returns the smallest joint value in the table.
+    pub fn minimum_joint_value(&self) -> f64 {
+        self.joint_values[0]
+    }
+
+    /// Returns the number of samples in the table.
+    pub fn number_of_samples(&self) -> usize {
+        self.joint_values.len()
+    }
+
+    /// Returns the transform from the child frame to the parent frame for the given joint value.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'joint_value' - The value of the joint variable. Values outside the range of the table
+    ///   are clamped to the range of the table.
+    pub fn transform_at(&self, joint_value: f64) -> Isometry3<f64> {
+        let last = self.joint_values.len() - 1;
+        if joint_value.is_nan() || joint_value <= self.joint_values[0] {
+            return self.transforms[0];
+        }
+
+        if joint_value >= self.joint_values[last] {
+            return self.transforms[last];
+        }
+
+        // The index of the first sample with a joint value larger than the given value. Because of
+        // the checks above this is always in the range [1, last].
+        let upper = self.joint_values.partition_point(|v| *v <= joint_value);
+        let lower = upper - 1;
+
+        let span = self.joint_values[upper] - self.joint_values[lower];
+        let t = (joint_value - self.joint_values[lower]) / span;
+
+        let rotation = self.transforms[lower]
+            .rotation
+            .slerp(&self.transforms[upper].rotation, t);
+
+        let translation = match self.interpolation {
+            TableInterpolation::Linear => self.transforms[lower]
+                .translation
+                .vector
+                .lerp(&self.transforms[upper].translation.vector, t),
+            TableInterpolation::CubicSpline => self.cubic_translation(lower, upper, t),
+        };
+
+        Isometry3::from_parts(Translation3::from(translation), rotation)
+    }
+
+    /// Returns the translation between the given samples using a cubic Hermite spline.
+    /// Returns a copy of the table in which each transform is replaced by the result of the
+    /// given function. The joint values are not changed.
+    pub(super) fn map_transforms(&self, map: impl Fn(&Isometry3<f64>) -> Isometry3<f64>) -> Self {
+        Self {
+            joint_values: self.joint_values.clone(),
+            transforms: self.transforms.iter().map(map).collect(),
+            interpolation: self.interpolation,
+        }
+    }
+
+    fn cubic_translation(&self, lower: usize, upper: usize, t: f64) -> Vector3<f64> {
+        let span = self.joint_values[upper] - self.joint_values[lower];
+        let p0 = self.transforms[lower].translation.vector;
+        let p1 = self.transforms[upper].translation.vector;
+
+        // Tangents, scaled to the span of the interval
+        let m0 = self.tangent(lower) * span;
+        let m1 = self.tangent(upper) * span;
+
+        let t2 = t * t;
+        let t3 = t2 * t;
+        let h00 = 2.0 * t3 - 3.0 * t2 + 1.0;
+        let h10 = t3 - 2.0 * t2 + t;
+        let h01 = -2.0 * t3 + 3.0 * t2;
+        let h11 = t3 - t2;
+
+        p0 * h00 + m0 * h10 + p1 * h01 + m1 * h11
+    }
+
+    /// Returns the derivative of the translation with respect to the joint value at the given
+    /// sample, computed with finite differences.
+    fn tangent(&self, index: usize) -> Vector3<f64> {
+        let last = self.joint_values.len() - 1;
+        let previous = if index == 0 { 0 } else { index - 1 };
+        let next = if index == last { last } else { index + 1 };
+
+        let difference =
+            self.transforms[next].translation.vector - self.transforms[previous].translation.vector;
+        difference / (self.joint_values[next] - self.joint_values[previous])
+    }
+}
@@ -0,0 +1,132 @@
+use std::f64::consts::PI;
+
+use nalgebra::{Isometry3, Translation3, UnitQuaternion, Vector3};
+
+use crate::Error;
+
+use super::{JointKinematicsTable, TableInterpolation};
+
+fn sample(x: f64, z: f64, angle: f64) -> Isometry3<f64> {
+    Isometry3::from_parts(
+        Translation3::new(x, 0.0, z),
+        UnitQuaternion::from_axis_angle(&Vector3::x_axis(), angle),
+    )
+}
+
+fn curved_table(interpolation: TableInterpolation) -> JointKinematicsTable {
+    JointKinematicsTable::new(
+        vec![
+            (-0.1, sample(0.01, -0.1, -0.02)),
+            (0.0, sample(0.0, 0.0, 0.0)),
+            (0.1, sample(0.01, 0.1, 0.02)),
+            (0.2, sample(0.04, 0.2, 0.04)),
+        ],
+        interpolation,
+    )
+    .unwrap()
+}
+
+#[test]
+fn when_creating_table_with_too_few_samples_it_should_error() {
+    let result = JointKinematicsTable::new(
+        vec![(0.0, Isometry3::identity())],
+        TableInterpolation::Linear,
+    );
+
+    assert_eq!(Err(Error::InvalidKinematicsTable), result);
+}
+
+#[test]
+fn when_creating_table_with_unordered_samples_it_should_error() {
+    let result = JointKinematicsTable::new(
+        vec![
+            (0.0, Isometry3::identity()),
+            (0.2, Isometry3::identity()),
+            (0.1, Isometry3::identity()),
+        ],
+        TableInterpolation::Linear,
+    );
+    assert_eq!(Err(Error::InvalidKinematicsTable), result);
+
+    let duplicate = JointKinematicsTable::new(
+        vec![(0.0, Isometry3::identity()), (0.0, Isometry3::identity())],
+        TableInterpolation::Linear,
+    );
+    assert_eq!(Err(Error::InvalidKinematicsTable), duplicate);
+}
+
+#[test]
+fn when_creating_table_with_non_finite_samples_it_should_error() {
+    let result = JointKinematicsTable::new(
+        vec![
+            (0.0, Isometry3::identity()),
+            (f64::NAN, Isometry3::identity()),
+        ],
+        TableInterpolation::CubicSpline,
+    );
+
+    assert_eq!(Err(Error::InvalidKinematicsTable), result);
+}
+
+#[test]
+fn when_creating_table_it_should_store_the_samples() {
+    let table = curved_table(TableInterpolation::CubicSpline);
+
+    assert_eq!(4, table.number_of_samples());
+    assert_eq!(-0.1, table.minimum_joint_value());
+    assert_eq!(0.2, table.maximum_joint_value());
+    assert_eq!(TableInterpolation::CubicSpline, table.interpolation());
+}
+
+#[test]
+fn when_evaluating_table_at_a_sample_it_should_return_the_sample() {
+    for interpolation in [TableInterpolation::Linear, TableInterpolation::CubicSpline] {
+        let table = curved_table(interpolation);
+
+        let transform = table.transform_at(0.1);
+        let expected = sample(0.01, 0.1, 0.02);
+        assert!((transform.translation.vector - expected.translation.vector).norm() < 1e-12);
+        assert!(transform.rotation.angle_to(&expected.rotation) < 1e-12);
+    }
+}
+
+#[test]
+fn when_evaluating_table_outside_the_range_it_should_clamp() {
+    let table = curved_table(TableInterpolation::Linear);
+
+    assert_eq!(sample(0.01, -0.1, -0.02), table.transform_at(-1.0));
+    assert_eq!(sample(0.04, 0.2, 0.04), table.transform_at(1.0));
+}
+
+#[test]
+fn when_evaluating_table_with_linear_interpolation_it_should_interpolate() {
+    let table = JointKinematicsTable::new(
+        vec![
+            (0.0, sample(0.0, 0.0, 0.0)),
+            (1.0, sample(1.0, 2.0, 0.5 * PI)),
+        ],
+        TableInterpolation::Linear,
+    )
+    .unwrap();
+
+    let transform = table.transform_at(0.25);
+    assert!((transform.translation.vector - Vector3::new(0.25, 0.0, 0.5)).norm() < 1e-12);
+    assert!((transform.rotation.angle() - 0.125 * PI).abs() < 1e-12);
+}
+
+#[test]
+fn when_evaluating_table_with_cubic_interpolation_it_should_follow_the_curve() {
+    // Samples of the curve x = z^2
+    let samples = (-3..=3)
+        .map(|i| {
+            let z = 0.1 * i as f64;
+            (z, sample(z * z, z, 0.0))
+        })
+        .collect();
+    let cubic = JointKinematicsTable::new(samples, TableInterpolation::CubicSpline).unwrap();
+
+    let z = 0.05;
+    let transform = cubic.transform_at(z);
+    assert!((transform.translation.vector.z - z).abs() < 1e-12);
+    assert!((transform.translation.vector.x - z * z).abs() < 1e-12);
+}
@@ -0,0 +1,400 @@
+//! Provides the constraints that close the kinematic loops of a vehicle, e.g. the loops formed
+//! by a double wishbone suspension or a four-bar steering linkage.
+//!
+//! The frame elements of a [MotionModel] form a tree, which cannot express a closed loop
+//! directly. A loop is therefore described by the tree plus a [LoopClosure] constraint between
+//! two frames, one at the end of each of the branches of the loop. The [LoopClosureKind] of the
+//! constraint determines how the two frames are tied together.
+//!
+//! The constraints are taken into account in the following places:
+//!
+//! * [MotionModel::validate] reports a
+//!   [LoopClosureViolated](super::validation::ValidationIssue::LoopClosureViolated) issue for
+//!   each constraint that is not satisfied by the current joint positions.
+//! * [MotionModel::velocity_of_frame_in_body](crate::model_elements::model::MotionModel::velocity_of_frame_in_body)
+//!   infers the velocities of the joints of a loop that have no measured velocity from the
+//!   velocities of the joints that do, such that the constraints hold.
+//!
+//! The positions of the unmeasured joints are not solved for and the forces in the loop are
+//! not computed, i.e. the dynamics of the loops are out of scope.
+//!
+//! # Examples
+//!
+//! ```
+//! use nalgebra::{Matrix3, Translation3, UnitQuaternion, Vector3};
+//! use swerve_vehicle_descriptors::model_elements::frame_elements::{FrameDofType, JointConstraint};
+//! use swerve_vehicle_descriptors::model_elements::loop_closure::LoopClosureKind;
+//! use swerve_vehicle_descriptors::model_elements::model::{
+//!     ChassisElementPhysicalProperties, MotionModel,
+//! };
+//!
+//! let properties =
+//!     ChassisElementPhysicalProperties::from_mass_properties(1.0, Vector3::zeros(), Matrix3::identity());
+//! let mut model = MotionModel::new();
+//! let body = model
+//!     .add_body(
+//!         "body".to_string(),
+//!         Translation3::identity(),
+//!         UnitQuaternion::identity(),
+//!         properties,
+//!     )
+//!     .unwrap();
+//!
+//! // The upper and lower arms of a wishbone suspension, both connected to the upright
+//! let mut arms = Vec::new();
+//! for (name, height) in [("upper-arm", 0.2), ("lower-arm", 0.0)] {
+//!     let arm = model
+//!         .add_suspension_element(
+//!             name.to_string(),
+//!             FrameDofType::RevoluteX,
+//!             body,
+//!             Translation3::new(0.0, 0.3, height),
+//!             UnitQuaternion::identity(),
+//!             properties,
+//!             JointConstraint::new(),
+//!         )
+//!         .unwrap();
+//!     let end = model
+//!         .add_static_chassis_element(
+//!             format!("{}-end", name),
+//!             arm,
+//!             Translation3::new(0.0, 0.2, 0.0),
+//!             UnitQuaternion::identity(),
+//!             properties,
+//!         )
+//!         .unwrap();
+//!     arms.push(end);
+//! }
+//!
+//! // The upright keeps the ends of the arms at a fixed distance
+//! let closure = model
+//!     .add_loop_closure(arms[0], arms[1], LoopClosureKind::Distance { length: 0.2 })
+//!     .unwrap();
+//! assert_eq!(1, model.loop_closures().len());
+//! assert!(model.loop_closure_error(&closure).unwrap() < 1e-12);
+//! ```
+
+use std::collections::HashMap;
+
+use nalgebra::{DMatrix, DVector, Vector6};
+
+use crate::{hardware::joint_state::JointState, Error};
+
+use super::{frame_elements::FrameID, model::MotionModel, model_change::ModelChange};
+
+#[cfg(test)]
+#[path = "loop_closure_tests.rs"]
+mod loop_closure_tests;
+
+/// The tolerance used when solving for the velocities of the joints of a loop.
+const VELOCITY_SOLVER_TOLERANCE: f64 = 1e-12;
+
+/// Defines how the two frames of a [LoopClosure] are tied together.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LoopClosureKind {
+    /// The origins of the two frames coincide, while the frames are free to rotate relative to
+    /// each other, e.g. a ball joint that connects a tie rod to a steering arm.
+    Coincident,
+
+    /// The origins of the two frames are a fixed distance apart, e.g. a rigid link with a ball
+    /// joint at each end.
+    Distance {
+        /// The distance, in meters, between the origins of the frames.
+        length: f64,
+    },
+
+    /// The two frames coincide and have the same orientation, i.e. they are rigidly connected.
+    Fixed,
+}
+
+/// Describes a constraint that closes a kinematic loop between two frames of a [MotionModel].
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LoopClosure {
+    /// The first frame of the constraint.
+    first: FrameID,
+
+    /// The second frame of the constraint.
+    second: FrameID,
+
+    /// The way in which the frames are tied together.
+    kind: LoopClosureKind,
+}
+
+impl LoopClosure {
+    /// Returns a value indicating whether the given frame is one of the frames of the
+    /// constraint.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'frame_id' - The [FrameID] of the frame.
+    pub fn contains(&self, frame_id: &FrameID) -> bool {
+        self.first == *frame_id || self.second == *frame_id
+    }
+
+    /// Returns the [FrameID] of the first frame of the constraint.
+    pub fn first(&self) -> &FrameID {
+        &self.first
+    }
+
+    /// Returns the way in which the frames are tied together.
+    pub fn kind(&self) -> LoopClosureKind {
+        self.kind
+    }
+
+    /// Returns the [FrameID] of the second frame of the constraint.
+    pub fn second(&self) -> &FrameID {
+        &self.second
+    }
+}
+
+impl MotionModel {
+    /// Adds a constraint that closes a kinematic loop between two frames of the model.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'first' - The [FrameID] of the first frame, at the end of one branch of the loop.
+    /// * 'second' - The [FrameID] of the second frame, at the end of the other branch of the
+    ///   loop.
+    /// * 'kind' - The way in which the frames are tied together.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when either frame is not part of the model.
+    /// * [Error::InvalidLoopClosure] - Returned when both frames are the same frame or when the
+    ///   length of a [LoopClosureKind::Distance] constraint is not a finite, positive number.
+    pub fn add_loop_closure(
+        &mut self,
+        first: FrameID,
+        second: FrameID,
+        kind: LoopClosureKind,
+    ) -> Result<LoopClosure, Error> {
+        for frame_id in [&first, &second] {
+            if !self.kinematic_tree().has_element(frame_id) {
+                return Err(Error::MissingFrameElement { id: *frame_id });
+            }
+        }
+
+        let has_valid_length = match kind {
+            LoopClosureKind::Distance { length } => length.is_finite() && length > 0.0,
+            LoopClosureKind::Coincident | LoopClosureKind::Fixed => true,
+        };
+        if first == second || !has_valid_length {
+            return Err(Error::InvalidLoopClosure);
+        }
+
+        let closure = LoopClosure {
+            first,
+            second,
+            kind,
+        };
+        self.loop_closures.push(closure);
+        self.invalidate(ModelChange::LoopClosureChanged { frame: first });
+        Ok(closure)
+    }
+
+    /// Returns the amount by which the given constraint is violated for the current positions
+    /// of the joints. The error is zero when the constraint is satisfied.
+    ///
+    /// * For a [LoopClosureKind::Coincident] constraint the error is the distance, in meters,
+    ///   between the origins of the frames.
+    /// * For a [LoopClosureKind::Distance] constraint the error is the difference, in meters,
+    ///   between the distance between the origins of the frames and the length of the
+    ///   constraint.
+    /// * For a [LoopClosureKind::Fixed] constraint the error is the norm of the vector that
+    ///   combines the distance between the origins, in meters, and the rotation between the
+    ///   frames, in radians.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'closure' - The constraint.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when either frame is not part of the model.
+    pub fn loop_closure_error(&self, closure: &LoopClosure) -> Result<f64, Error> {
+        let first_to_body = self.isometry_to_body(&closure.first)?;
+        let second_to_body = self.isometry_to_body(&closure.second)?;
+        let offset = second_to_body.translation.vector - first_to_body.translation.vector;
+
+        Ok(match closure.kind {
+            LoopClosureKind::Coincident => offset.norm(),
+            LoopClosureKind::Distance { length } => (offset.norm() - length).abs(),
+            LoopClosureKind::Fixed => {
+                let rotation = first_to_body.rotation.inverse() * second_to_body.rotation;
+                Vector6::new(
+                    offset.x,
+                    offset.y,
+                    offset.z,
+                    rotation.scaled_axis().x,
+                    rotation.scaled_axis().y,
+                    rotation.scaled_axis().z,
+                )
+                .norm()
+            }
+        })
+    }
+
+    /// Returns the constraints that close the kinematic loops of the model, in the order in
+    /// which they were added. A constraint is removed when one of its frames is removed from
+    /// the model.
+    pub fn loop_closures(&self) -> &[LoopClosure] {
+        &self.loop_closures
+    }
+
+    /// Removes the given constraint from the model.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'closure' - The constraint that should be removed.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::InvalidLoopClosure] - Returned when the constraint is not part of the model.
+    pub fn remove_loop_closure(&mut self, closure: &LoopClosure) -> Result<(), Error> {
+        let index = self
+            .loop_closures
+            .iter()
+            .position(|c| c == closure)
+            .ok_or(Error::InvalidLoopClosure)?;
+
+        self.loop_closures.remove(index);
+        self.invalidate(ModelChange::LoopClosureChanged {
+            frame: closure.first,
+        });
+        Ok(())
+    }
+
+    /// Returns the velocities of the joints of the kinematic loops that have no measured
+    /// velocity, such that the velocities of all the joints satisfy the loop closure
+    /// constraints. The velocities are the least squares solution of the constraints, using
+    /// the joint states provided by the 'joint_state' function.
+    ///
+    /// Returns an empty map when the model has no loop closures or when all the joints of the
+    /// loops have a measured velocity.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'joint_state' - Returns the state of the joint for a given [FrameID], or [None] if the
+    ///   joint has no known state.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when a frame of a constraint is not part of the
+    ///   model.
+    pub(crate) fn loop_closure_joint_velocities_with<F>(
+        &self,
+        joint_state: &F,
+    ) -> Result<HashMap<FrameID, f64>, Error>
+    where
+        F: Fn(&FrameID) -> Option<JointState>,
+    {
+        if self.loop_closures.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let joint_position = |id: &FrameID| joint_state(id).map(|s| s.position());
+
+        // Each constraint contributes one or more rows that relate the joint velocities to the
+        // rate of change of the constraint, which must be zero.
+        let mut joints: Vec<FrameID> = Vec::new();
+        let mut rows: Vec<Vec<(FrameID, f64)>> = Vec::new();
+        for closure in &self.loop_closures {
+            let first = self.jacobian_for_with(&closure.first, joint_position)?;
+            let second = self.jacobian_for_with(&closure.second, joint_position)?;
+            let offset = self
+                .isometry_to_ancestor_with(&closure.second, self.body()?, joint_position)?
+                .translation
+                .vector
+                - self
+                    .isometry_to_ancestor_with(&closure.first, self.body()?, joint_position)?
+                    .translation
+                    .vector;
+
+            // The weights of the rows of the twist, i.e. [v_x, v_y, v_z, ω_x, ω_y, ω_z]
+            let weights: Vec<Vector6<f64>> = match closure.kind {
+                LoopClosureKind::Coincident => (0..3)
+                    .map(|i| Vector6::from_fn(|r, _| if r == i { 1.0 } else { 0.0 }))
+                    .collect(),
+                LoopClosureKind::Distance { .. } => {
+                    if offset.norm() == 0.0 {
+                        continue;
+                    }
+
+                    let direction = offset.normalize();
+                    vec![Vector6::new(
+                        direction.x,
+                        direction.y,
+                        direction.z,
+                        0.0,
+                        0.0,
+                        0.0,
+                    )]
+                }
+                LoopClosureKind::Fixed => (0..6)
+                    .map(|i| Vector6::from_fn(|r, _| if r == i { 1.0 } else { 0.0 }))
+                    .collect(),
+            };
+
+            for weight in weights {
+                let mut row = Vec::new();
+                for (jacobian, sign) in [(&second, 1.0), (&first, -1.0)] {
+                    for (index, joint) in jacobian.joints().iter().enumerate() {
+                        if !joints.contains(joint) {
+                            joints.push(*joint);
+                        }
+
+                        let value = sign * weight.dot(&jacobian.matrix().column(index));
+                        row.push((*joint, value));
+                    }
+                }
+                rows.push(row);
+            }
+        }
+
+        let measured: HashMap<FrameID, f64> = joints
+            .iter()
+            .filter_map(|joint| {
+                joint_state(joint)
+                    .and_then(|s| *s.velocity())
+                    .map(|v| (*joint, v))
+            })
+            .collect();
+        let unmeasured: Vec<FrameID> = joints
+            .into_iter()
+            .filter(|joint| !measured.contains_key(joint))
+            .collect();
+        if unmeasured.is_empty() || rows.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        // Solve A_u * q_u = -A_m * q_m for the unmeasured joint velocities q_u
+        let columns: HashMap<FrameID, usize> = unmeasured
+            .iter()
+            .enumerate()
+            .map(|(column, joint)| (*joint, column))
+            .collect();
+        let mut a = DMatrix::<f64>::zeros(rows.len(), unmeasured.len());
+        let mut b = DVector::<f64>::zeros(rows.len());
+        for (row_index, row) in rows.iter().enumerate() {
+            for (joint, value) in row {
+                if let Some(velocity) = measured.get(joint) {
+                    b[row_index] -= value * velocity;
+                } else if let Some(column) = columns.get(joint) {
+                    a[(row_index, *column)] += value;
+                }
+            }
+        }
+
+        let solution = match a.svd(true, true).solve(&b, VELOCITY_SOLVER_TOLERANCE) {
+            Ok(v) => v,
+            Err(_) => return Ok(HashMap::new()),
+        };
+
+        Ok(unmeasured
+            .into_iter()
+            .zip(solution.iter().copied())
+            .collect())
+    }
+}
@@ -0,0 +1,190 @@
+use nalgebra::{Translation3, UnitQuaternion};
+
+use crate::{
+    change_notification_processing::HardwareChangeProcessor,
+    hardware::{
+        joint_state::{JointState, JointStateRange},
+        simulation::SimulatedSensor,
+    },
+    model_elements::{
+        frame_elements::{FrameDofType, FrameID, JointConstraint, JointSensor},
+        model::MotionModel,
+        validation::ValidationIssue,
+    },
+    number_space::NumberSpaceType,
+    test_fixtures::physical_properties,
+    Error,
+};
+
+use super::LoopClosureKind;
+
+// Builds a parallelogram linkage with an upper and a lower arm that rotate around the x-axis of
+// their pivots. Joints rotate around the origin of their parent, so each arm is attached to a
+// static pivot frame. Returns the model, the arms and the frames at the ends of the arms.
+fn create_linkage() -> (MotionModel, Vec<FrameID>, Vec<FrameID>) {
+    let mut model = MotionModel::new();
+    let body = model
+        .add_body(
+            "body".to_string(),
+            Translation3::identity(),
+            UnitQuaternion::identity(),
+            physical_properties(),
+        )
+        .unwrap();
+
+    let mut arms = Vec::new();
+    let mut ends = Vec::new();
+    for (name, height) in [("upper", 0.2), ("lower", 0.0)] {
+        let pivot = model
+            .add_static_chassis_element(
+                format!("{}-pivot", name),
+                body,
+                Translation3::new(0.0, 0.3, height),
+                UnitQuaternion::identity(),
+                physical_properties(),
+            )
+            .unwrap();
+        let arm = model
+            .add_suspension_element(
+                name.to_string(),
+                FrameDofType::RevoluteX,
+                pivot,
+                Translation3::identity(),
+                UnitQuaternion::identity(),
+                physical_properties(),
+                JointConstraint::new(),
+            )
+            .unwrap();
+        let end = model
+            .add_static_chassis_element(
+                format!("{}-end", name),
+                arm,
+                Translation3::new(0.0, 0.2, 0.0),
+                UnitQuaternion::identity(),
+                physical_properties(),
+            )
+            .unwrap();
+        arms.push(arm);
+        ends.push(end);
+    }
+
+    (model, arms, ends)
+}
+
+#[test]
+fn when_adding_a_loop_closure_it_should_validate_the_frames() {
+    let (mut model, arms, ends) = create_linkage();
+
+    let closure = model
+        .add_loop_closure(ends[0], ends[1], LoopClosureKind::Distance { length: 0.2 })
+        .unwrap();
+    assert_eq!(&ends[0], closure.first());
+    assert_eq!(&ends[1], closure.second());
+    assert_eq!(LoopClosureKind::Distance { length: 0.2 }, closure.kind());
+    assert_eq!(&[closure], model.loop_closures());
+
+    let missing = FrameID::new();
+    assert_eq!(
+        Err(Error::MissingFrameElement { id: missing }),
+        model.add_loop_closure(ends[0], missing, LoopClosureKind::Coincident)
+    );
+    assert_eq!(
+        Err(Error::InvalidLoopClosure),
+        model.add_loop_closure(ends[0], ends[0], LoopClosureKind::Fixed)
+    );
+    for length in [0.0, -1.0, f64::NAN] {
+        assert_eq!(
+            Err(Error::InvalidLoopClosure),
+            model.add_loop_closure(ends[0], ends[1], LoopClosureKind::Distance { length })
+        );
+    }
+
+    let other = model
+        .add_loop_closure(arms[0], arms[1], LoopClosureKind::Coincident)
+        .unwrap();
+    model.remove_loop_closure(&other).unwrap();
+    assert_eq!(
+        Err(Error::InvalidLoopClosure),
+        model.remove_loop_closure(&other)
+    );
+    assert_eq!(1, model.loop_closures().len());
+
+    // Removing one of the frames removes the constraint
+    model.remove_element(&ends[1]).unwrap();
+    assert!(model.loop_closures().is_empty());
+}
+
+#[test]
+fn when_a_loop_closure_is_not_satisfied_it_should_report_a_validation_issue() {
+    let (mut model, _, ends) = create_linkage();
+    let satisfied = model
+        .add_loop_closure(ends[0], ends[1], LoopClosureKind::Distance { length: 0.2 })
+        .unwrap();
+    assert!(model.loop_closure_error(&satisfied).unwrap() < 1e-12);
+
+    let violated = model
+        .add_loop_closure(ends[0], ends[1], LoopClosureKind::Coincident)
+        .unwrap();
+    let error = model.loop_closure_error(&violated).unwrap();
+    assert!((error - 0.2).abs() < 1e-12);
+
+    // The frames are parallel, so the error of a fixed constraint is only the distance
+    let fixed = model
+        .add_loop_closure(ends[0], ends[1], LoopClosureKind::Fixed)
+        .unwrap();
+    assert!((model.loop_closure_error(&fixed).unwrap() - 0.2).abs() < 1e-12);
+
+    let issues: Vec<ValidationIssue> = model
+        .validate()
+        .issues()
+        .iter()
+        .filter(|i| matches!(i, ValidationIssue::LoopClosureViolated { .. }))
+        .copied()
+        .collect();
+    assert_eq!(2, issues.len());
+    assert!(matches!(
+        issues[0],
+        ValidationIssue::LoopClosureViolated { frame, other, error }
+            if frame == ends[0] && other == ends[1] && (error - 0.2).abs() < 1e-12
+    ));
+}
+
+#[test]
+fn when_a_joint_of_a_loop_has_no_velocity_it_should_infer_the_velocity_from_the_loop() {
+    let processor = HardwareChangeProcessor::manual();
+    let (mut model, arms, ends) = create_linkage();
+
+    let mut hardware = SimulatedSensor::new(
+        NumberSpaceType::AngularLimited {
+            start_angle_in_radians: -std::f64::consts::PI,
+        },
+        JointStateRange::new(
+            JointState::new(-1.0, Some(-10.0), None, None),
+            JointState::new(1.0, Some(10.0), None, None),
+        ),
+        JointState::new(0.0, Some(1.5), None, None),
+    );
+    model
+        .add_joint_sensor(
+            &arms[0],
+            JointSensor::new(&mut hardware, &processor).unwrap(),
+        )
+        .unwrap();
+    processor.process_pending();
+
+    // Without a loop the lower arm is at rest
+    let twist = model.velocity_of_frame_in_body(&ends[1]).unwrap();
+    assert_eq!(0.0, twist.norm());
+
+    // The parallelogram forces the lower arm to follow the upper arm
+    model
+        .add_loop_closure(ends[0], ends[1], LoopClosureKind::Distance { length: 0.2 })
+        .unwrap();
+    let upper = model.velocity_of_frame_in_body(&ends[0]).unwrap();
+    let lower = model.velocity_of_frame_in_body(&ends[1]).unwrap();
+    assert!((upper[2] - 0.3).abs() < 1e-12);
+    assert!((upper - lower).norm() < 1e-12);
+
+    let arm = model.velocity_of_frame_in_body(&arms[1]).unwrap();
+    assert!((arm[3] - 1.5).abs() < 1e-12);
+}
@@ -0,0 +1,253 @@
+//! Provides the functions used to load models that were serialized by earlier versions of the
+//! crate.
+//!
+//! Each serialized [MotionModel] stores the version of the schema with which it was written in
+//! the `schema_version` field. Models that were written before the schema was versioned have no
+//! such field and are treated as version `0`. When the structures of the model change in a way
+//! that existing descriptions can no longer be deserialized, the [CURRENT_SCHEMA_VERSION] is
+//! incremented and a [ModelMigration] is added that upgrades a description from the previous
+//! version.
+//!
+//! A [MigrationRegistry] applies the migrations, one version at a time, to the JSON document of
+//! a description until it has the current version and then deserializes the model. Deserializing
+//! a [MotionModel] directly, e.g. with `serde_json::from_str` or
+//! `MotionModel::from_binary`, fails for descriptions that do not have the current schema
+//! version. Applications
+//! that store additional data in the description, e.g. in the frame metadata, can register their
+//! own migrations. The migrations are only available when the `serde` feature is enabled.
+
+use std::collections::BTreeMap;
+
+use serde_json::Value;
+
+use crate::Error;
+
+use super::model::MotionModel;
+
+#[cfg(test)]
+#[path = "migration_tests.rs"]
+mod migration_tests;
+
+/// The version of the schema of the serialized [MotionModel] that is written by this version of
+/// the crate.
+pub const CURRENT_SCHEMA_VERSION: u32 = 1;
+
+/// The name of the field that stores the schema version.
+const SCHEMA_VERSION_FIELD: &str = "schema_version";
+
+/// Defines an upgrade of a serialized [MotionModel] from one schema version to the next.
+pub trait ModelMigration: Send + Sync {
+    /// Returns the schema version of the descriptions that are upgraded by the migration. The
+    /// migrated description has the next schema version.
+    fn source_version(&self) -> u32;
+
+    /// Upgrades the given description from the source version to the next version. The
+    /// schema version of the description is updated by the [MigrationRegistry].
+    ///
+    /// ## Parameters
+    ///
+    /// * 'description' - The JSON document of the serialized model.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::ModelMigrationFailed] - Returned when the description cannot be upgraded.
+    fn migrate(&self, description: &mut Value) -> Result<(), Error>;
+}
+
+/// Upgrades the descriptions that were written before the schema was versioned. These
+/// descriptions only lack the fields that were added with a default value, so no changes are
+/// needed.
+struct UnversionedMigration;
+
+impl ModelMigration for UnversionedMigration {
+    fn source_version(&self) -> u32 {
+        0
+    }
+
+    fn migrate(&self, _description: &mut Value) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+/// Stores the [ModelMigration] instances and applies them to load serialized models.
+pub struct MigrationRegistry {
+    /// The migrations, stored by their source version.
+    migrations: BTreeMap<u32, Box<dyn ModelMigration>>,
+}
+
+impl MigrationRegistry {
+    /// Loads a [MotionModel] from the given JSON document, applying the migrations that are
+    /// needed to bring the document to the current schema version.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'description' - The JSON document of the serialized model.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::UnsupportedSchemaVersion] - Returned when the description is newer than the
+    ///   current schema version or when there is no migration for one of the versions.
+    /// * [Error::ModelMigrationFailed] - Returned when a migration fails or when the migrated
+    ///   description cannot be deserialized.
+    pub fn load(&self, mut description: Value) -> Result<MotionModel, Error> {
+        self.migrate(&mut description)?;
+        serde_json::from_value(description).map_err(|e| Error::ModelMigrationFailed {
+            reason: e.to_string(),
+        })
+    }
+
+    /// Loads a [MotionModel] from the given JSON text, applying the migrations that are needed to
+    /// bring the description to the current schema version.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'json' - The serialized model.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::UnsupportedSchemaVersion] - Returned when the description is newer than the
+    ///   current schema version or when there is no migration for one of the versions.
+    /// * [Error::ModelMigrationFailed] - Returned when the text is not valid JSON, when a
+    ///   migration fails or when the migrated description cannot be deserialized.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use nalgebra::{Matrix3, Translation3, UnitQuaternion, Vector3};
+    /// use swerve_vehicle_descriptors::model_elements::migration::MigrationRegistry;
+    /// use swerve_vehicle_descriptors::model_elements::model::{
+    ///     ChassisElementPhysicalProperties, MotionModel,
+    /// };
+    ///
+    /// let mut model = MotionModel::new();
+    /// model
+    ///     .add_body(
+    ///         "body".to_string(),
+    ///         Translation3::identity(),
+    ///         UnitQuaternion::identity(),
+    ///         ChassisElementPhysicalProperties::from_mass_properties(
+    ///             10.0,
+    ///             Vector3::zeros(),
+    ///             Matrix3::identity(),
+    ///         ),
+    ///     )
+    ///     .unwrap();
+    ///
+    /// let json = serde_json::to_string(&model).unwrap();
+    /// let loaded = MigrationRegistry::new().load_json(&json).unwrap();
+    /// assert_eq!(model.body().unwrap(), loaded.body().unwrap());
+    /// ```
+    pub fn load_json(&self, json: &str) -> Result<MotionModel, Error> {
+        let description: Value =
+            serde_json::from_str(json).map_err(|e| Error::ModelMigrationFailed {
+                reason: e.to_string(),
+            })?;
+        self.load(description)
+    }
+
+    /// Applies the migrations that are needed to bring the given description to the current
+    /// schema version and returns the schema version of the original description.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'description' - The JSON document of the serialized model.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::UnsupportedSchemaVersion] - Returned when the description is newer than the
+    ///   current schema version or when there is no migration for one of the versions.
+    /// * [Error::ModelMigrationFailed] - Returned when the description is not a JSON object or
+    ///   when a migration fails.
+    pub fn migrate(&self, description: &mut Value) -> Result<u32, Error> {
+        let object = description
+            .as_object()
+            .ok_or_else(|| Error::ModelMigrationFailed {
+                reason: "the description is not a JSON object".to_string(),
+            })?;
+
+        let original_version = match object.get(SCHEMA_VERSION_FIELD) {
+            None => 0,
+            Some(value) => value
+                .as_u64()
+                .and_then(|v| u32::try_from(v).ok())
+                .ok_or_else(|| Error::ModelMigrationFailed {
+                    reason: format!("the {} is not a valid version", SCHEMA_VERSION_FIELD),
+                })?,
+        };
+        if original_version > CURRENT_SCHEMA_VERSION {
+            return Err(Error::UnsupportedSchemaVersion {
+                version: original_version,
+            });
+        }
+
+        for version in original_version..CURRENT_SCHEMA_VERSION {
+            let migration = self
+                .migrations
+                .get(&version)
+                .ok_or(Error::UnsupportedSchemaVersion { version })?;
+            migration.migrate(description)?;
+
+            if let Some(object) = description.as_object_mut() {
+                object.insert(SCHEMA_VERSION_FIELD.to_string(), Value::from(version + 1));
+            }
+        }
+
+        Ok(original_version)
+    }
+
+    /// Creates a new [MigrationRegistry] instance that contains the migrations of the crate.
+    pub fn new() -> Self {
+        let mut registry = Self {
+            migrations: BTreeMap::new(),
+        };
+        registry.register(Box::new(UnversionedMigration));
+        registry
+    }
+
+    /// Registers a migration, replacing the migration for the same source version if there is
+    /// one. The replaced migration is returned.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'migration' - The migration.
+    pub fn register(
+        &mut self,
+        migration: Box<dyn ModelMigration>,
+    ) -> Option<Box<dyn ModelMigration>> {
+        self.migrations
+            .insert(migration.source_version(), migration)
+    }
+}
+
+impl Default for MigrationRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Reads the schema version of a serialized [MotionModel] and rejects the descriptions that do not
+/// have the current version. The older descriptions have to be loaded with a [MigrationRegistry],
+/// which upgrades them before they are deserialized.
+pub(crate) fn deserialize_schema_version<'de, D>(deserializer: D) -> Result<u32, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let version = <u32 as serde::Deserialize>::deserialize(deserializer)?;
+    if version != CURRENT_SCHEMA_VERSION {
+        return Err(serde::de::Error::custom(format!(
+            "the schema version {} is not supported, expected version {}. Load older descriptions with a MigrationRegistry",
+            version, CURRENT_SCHEMA_VERSION
+        )));
+    }
+
+    Ok(version)
+}
+
+/// Writes the current schema version, regardless of the version from which the model was
+/// loaded, because the model in memory always has the current structure.
+pub(crate) fn serialize_schema_version<S>(_version: &u32, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    serializer.serialize_u32(CURRENT_SCHEMA_VERSION)
+}
@@ -0,0 +1,165 @@
+use nalgebra::{Matrix3, Translation3, UnitQuaternion, Vector3};
+use serde_json::Value;
+
+use crate::{
+    model_elements::model::{ChassisElementPhysicalProperties, MotionModel},
+    Error,
+};
+
+use super::{MigrationRegistry, ModelMigration, CURRENT_SCHEMA_VERSION};
+
+// Changes the gravity of unversioned descriptions to the gravity on the moon.
+struct MoonGravityMigration;
+
+impl ModelMigration for MoonGravityMigration {
+    fn source_version(&self) -> u32 {
+        0
+    }
+
+    fn migrate(&self, description: &mut Value) -> Result<(), Error> {
+        description["gravity"] = serde_json::json!([0.0, 0.0, -1.62]);
+        Ok(())
+    }
+}
+
+// Fails for all descriptions.
+struct FailingMigration;
+
+impl ModelMigration for FailingMigration {
+    fn source_version(&self) -> u32 {
+        0
+    }
+
+    fn migrate(&self, _description: &mut Value) -> Result<(), Error> {
+        Err(Error::ModelMigrationFailed {
+            reason: "failed".to_string(),
+        })
+    }
+}
+
+fn build_model() -> MotionModel {
+    let mut model = MotionModel::new();
+    model
+        .add_body(
+            "body".to_string(),
+            Translation3::identity(),
+            UnitQuaternion::identity(),
+            ChassisElementPhysicalProperties::from_mass_properties(
+                1.0,
+                Vector3::zeros(),
+                Matrix3::identity(),
+            ),
+        )
+        .unwrap();
+    model
+}
+
+// Returns the description of the model as it was written before the schema was versioned.
+fn unversioned_description(model: &MotionModel) -> Value {
+    let mut description = serde_json::to_value(model).unwrap();
+    description
+        .as_object_mut()
+        .unwrap()
+        .remove("schema_version");
+    description
+}
+
+#[test]
+fn when_serializing_a_model_it_should_write_the_current_schema_version() {
+    let model = build_model();
+    let description = serde_json::to_value(&model).unwrap();
+    assert_eq!(
+        Some(CURRENT_SCHEMA_VERSION as u64),
+        description["schema_version"].as_u64()
+    );
+
+    // A model that was loaded from an older description is written with the current version
+    let loaded = MigrationRegistry::new()
+        .load(unversioned_description(&model))
+        .unwrap();
+    let description = serde_json::to_value(&loaded).unwrap();
+    assert_eq!(
+        Some(CURRENT_SCHEMA_VERSION as u64),
+        description["schema_version"].as_u64()
+    );
+}
+
+#[test]
+fn when_loading_an_older_description_it_should_migrate_it() {
+    let model = build_model();
+    let registry = MigrationRegistry::new();
+
+    let mut description = unversioned_description(&model);
+    assert_eq!(0, registry.migrate(&mut description).unwrap());
+    assert_eq!(
+        Some(CURRENT_SCHEMA_VERSION as u64),
+        description["schema_version"].as_u64()
+    );
+
+    let loaded = registry.load(unversioned_description(&model)).unwrap();
+    assert_eq!(model.body().unwrap(), loaded.body().unwrap());
+
+    let json = serde_json::to_string(&model).unwrap();
+    let loaded = registry.load_json(&json).unwrap();
+    assert_eq!(model.gravity(), loaded.gravity());
+}
+
+#[test]
+fn when_registering_a_migration_it_should_replace_the_migration_for_the_same_version() {
+    let model = build_model();
+    let mut registry = MigrationRegistry::default();
+    assert!(registry.register(Box::new(MoonGravityMigration)).is_some());
+
+    let loaded = registry.load(unversioned_description(&model)).unwrap();
+    assert_eq!(&Vector3::new(0.0, 0.0, -1.62), loaded.gravity());
+
+    // Descriptions with the current version are not migrated
+    let loaded = registry
+        .load(serde_json::to_value(&model).unwrap())
+        .unwrap();
+    assert_eq!(model.gravity(), loaded.gravity());
+
+    registry.register(Box::new(FailingMigration));
+    assert_eq!(
+        Err(Error::ModelMigrationFailed {
+            reason: "failed".to_string()
+        }),
+        registry.load(unversioned_description(&model)).map(|_| ())
+    );
+}
+
+#[test]
+fn when_loading_an_unsupported_description_it_should_error() {
+    let model = build_model();
+    let registry = MigrationRegistry::new();
+
+    let mut description = serde_json::to_value(&model).unwrap();
+    description["schema_version"] = Value::from(CURRENT_SCHEMA_VERSION + 1);
+    assert_eq!(
+        Err(Error::UnsupportedSchemaVersion {
+            version: CURRENT_SCHEMA_VERSION + 1
+        }),
+        registry.load(description).map(|_| ())
+    );
+
+    for invalid in ["[1, 2]", "{\"schema_version\": \"one\"}", "{"] {
+        assert!(matches!(
+            registry.load_json(invalid),
+            Err(Error::ModelMigrationFailed { .. })
+        ));
+    }
+}
+
+#[test]
+fn when_deserializing_a_description_without_the_registry_it_should_require_the_current_version() {
+    let model = build_model();
+    let description = serde_json::to_value(&model).unwrap();
+    assert!(serde_json::from_value::<MotionModel>(description.clone()).is_ok());
+
+    assert!(serde_json::from_value::<MotionModel>(unversioned_description(&model)).is_err());
+
+    let mut newer = description;
+    newer["schema_version"] = Value::from(CURRENT_SCHEMA_VERSION + 1);
+    let error = serde_json::from_value::<MotionModel>(newer).err().unwrap();
+    assert!(error.to_string().contains("MigrationRegistry"));
+}
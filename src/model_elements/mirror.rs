@@ -0,0 +1,373 @@
+//! Provides the functions used to create mirrored copies of parts of a [MotionModel].
+//!
+//! Most vehicles are symmetric, e.g. the modules on the left side of the vehicle are mirror
+//! images of the modules on the right side. Rather than describing each side separately, one side
+//! can be described and the other side created with [MotionModel::mirror_subtree].
+//!
+//! A reflection turns a right-handed reference frame into a left-handed one. To keep all the
+//! reference frames right-handed the mirrored copy of a frame is the reflection of the original
+//! frame followed by the reflection of its own axes. In practice this means that a point at
+//! `(x, y, z)` in the original frame is found at the reflection of that point in the mirrored
+//! frame, e.g. at `(x, -y, z)` when mirroring about the XZ plane. The degrees of freedom of the
+//! mirrored frames are the same as the degrees of freedom of the original frames, except for
+//! joints around or along an arbitrary axis, for which the axis is mirrored. A revolute joint
+//! around a principal axis that lies in the mirror plane, and a prismatic joint along the normal
+//! of the mirror plane, move in the opposite direction in the mirrored copy. The limits of these
+//! joints are reversed, so that the mirrored joint at position `-q` mirrors the original joint at
+//! position `q`.
+
+use std::collections::HashMap;
+
+use nalgebra::{
+    Isometry3, Matrix3, Matrix6, Rotation3, Translation3, Unit, UnitQuaternion, Vector3,
+};
+
+use crate::Error;
+
+use super::{
+    frame_elements::{FrameDofType, FrameID, FrameRole, JointConstraint, ReferenceFrame},
+    model::{ChassisElementPhysicalProperties, MotionModel, WheelProperties},
+};
+
+#[cfg(test)]
+#[path = "mirror_tests.rs"]
+mod mirror_tests;
+
+/// Defines the plane, in the reference frame of the parent of the mirrored subtree, about which
+/// a subtree is mirrored.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum MirrorPlane {
+    /// The plane spanned by the X-axis and the Z-axis, e.g. to create the left side of a vehicle
+    /// from the right side. The Y-coordinates are negated.
+    XZ,
+    /// The plane spanned by the Y-axis and the Z-axis, e.g. to create the rear of a vehicle from
+    /// the front. The X-coordinates are negated.
+    YZ,
+}
+
+impl MirrorPlane {
+    /// Returns the given isometry reflected about the plane.
+    pub fn mirror_isometry(&self, isometry: &Isometry3<f64>) -> Isometry3<f64> {
+        let reflection = self.reflection();
+        let rotation = reflection * isometry.rotation.to_rotation_matrix().matrix() * reflection;
+        Isometry3::from_parts(
+            Translation3::from(self.mirror_vector(&isometry.translation.vector)),
+            UnitQuaternion::from_rotation_matrix(&Rotation3::from_matrix_unchecked(rotation)),
+        )
+    }
+
+    /// Returns the given vector reflected about the plane.
+    pub fn mirror_vector(&self, vector: &Vector3<f64>) -> Vector3<f64> {
+        self.reflection() * vector
+    }
+
+    /// Returns a value indicating whether a joint with the given degree of freedom moves in the
+    /// opposite direction after it is reflected about the plane.
+    pub fn reverses(&self, degree_of_freedom: FrameDofType) -> bool {
+        matches!(
+            (self, degree_of_freedom),
+            (_, FrameDofType::RevoluteZ)
+                | (MirrorPlane::XZ, FrameDofType::RevoluteX)
+                | (MirrorPlane::XZ, FrameDofType::PrismaticY)
+                | (MirrorPlane::YZ, FrameDofType::RevoluteY)
+                | (MirrorPlane::YZ, FrameDofType::PrismaticX)
+        )
+    }
+
+    /// Returns the degree of freedom of the mirrored copy of a joint with the given degree of
+    /// freedom. The degrees of freedom around or along one of the principal axes are not
+    /// changed, see [MirrorPlane::reverses]. An arbitrary axis is reflected about the plane, and
+    /// the axis of a revolute joint is negated as well because an axis of rotation is a
+    /// pseudo-vector.
+    fn mirror_degree_of_freedom(&self, degree_of_freedom: FrameDofType) -> FrameDofType {
+        match degree_of_freedom {
+            FrameDofType::RevoluteAxis(axis) => {
+                FrameDofType::RevoluteAxis(Unit::new_normalize(-self.mirror_vector(&axis)))
+            }
+            FrameDofType::PrismaticAxis(axis) => {
+                FrameDofType::PrismaticAxis(Unit::new_normalize(self.mirror_vector(&axis)))
+            }
+            _ => degree_of_freedom,
+        }
+    }
+
+    /// Returns the given physical properties reflected about the plane.
+    fn mirror_physical_properties(
+        &self,
+        properties: &ChassisElementPhysicalProperties,
+    ) -> ChassisElementPhysicalProperties {
+        let reflection = self.reflection();
+
+        // The linear part of the spatial inertia transforms like a vector and the angular part
+        // like a pseudo-vector, which changes sign under a reflection.
+        let mut spatial_reflection = Matrix6::zeros();
+        spatial_reflection
+            .fixed_view_mut::<3, 3>(0, 0)
+            .copy_from(&reflection);
+        spatial_reflection
+            .fixed_view_mut::<3, 3>(3, 3)
+            .copy_from(&(-reflection));
+
+        ChassisElementPhysicalProperties::new(
+            properties.mass(),
+            self.mirror_vector(&properties.center_of_mass()),
+            reflection * properties.moment_of_inertia() * reflection,
+            spatial_reflection * properties.spatial_inertia() * spatial_reflection,
+        )
+    }
+
+    /// Returns the reflection matrix for the plane.
+    fn reflection(&self) -> Matrix3<f64> {
+        match self {
+            MirrorPlane::XZ => Matrix3::from_diagonal(&Vector3::new(1.0, -1.0, 1.0)),
+            MirrorPlane::YZ => Matrix3::from_diagonal(&Vector3::new(-1.0, 1.0, 1.0)),
+        }
+    }
+}
+
+impl MotionModel {
+    /// Adds a mirrored copy of the given element and all its descendants to the model. The copy
+    /// of the given element is attached to the parent of the given element. Returns the map from
+    /// the [FrameID] of each original element to the [FrameID] of its copy.
+    ///
+    /// The positions and orientations of the elements, the physical properties, the geometry
+    /// of the wheels, the joint constraints, the kinematics tables and the suspension droop
+    /// positions are mirrored, see the [mirror](self) module. The metadata of the elements is
+    /// copied as is. Drive modules of which all the
+    /// frames are copied are added as new drive modules, named with 'new_names'. The actuators
+    /// and sensors are connected to the hardware and are not copied. They should be attached to
+    /// the copies with [MotionModel::set_actuator] and [MotionModel::add_joint_sensor].
+    ///
+    /// ## Parameters
+    ///
+    /// * 'root' - The [FrameID] of the first element of the subtree that should be mirrored.
+    /// * 'plane' - The plane, in the reference frame of the parent of 'root', about which the
+    ///   subtree is mirrored.
+    /// * 'new_names' - The function that returns the name of a copy given the name of the
+    ///   original element or drive module.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when 'root' is not part of the model.
+    /// * [Error::InvalidFrameID] - Returned when 'root' is the body or when there is a steering
+    ///   frame between 'root' and the body, in which case the copy of a wheel would share the
+    ///   steering frame with the original wheel.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use nalgebra::{Matrix3, Translation3, UnitQuaternion, Vector3};
+    /// use swerve_vehicle_descriptors::model_elements::{
+    ///     mirror::MirrorPlane,
+    ///     model::{ChassisElementPhysicalProperties, MotionModel, WheelProperties},
+    ///     module_spec::{BodySpec, ModuleSpec},
+    /// };
+    ///
+    /// let properties = || {
+    ///     ChassisElementPhysicalProperties::from_mass_properties(
+    ///         1.0,
+    ///         Vector3::zeros(),
+    ///         Matrix3::identity(),
+    ///     )
+    /// };
+    /// let body = BodySpec::new(
+    ///     "body".to_string(),
+    ///     Translation3::identity(),
+    ///     UnitQuaternion::identity(),
+    ///     properties(),
+    /// );
+    /// let module = ModuleSpec::new(
+    ///     "front_left".to_string(),
+    ///     Translation3::new(1.0, 0.5, 0.0),
+    ///     UnitQuaternion::identity(),
+    ///     WheelProperties::new(0.1, 0.05, Vector3::zeros()),
+    ///     properties(),
+    ///     properties(),
+    /// );
+    /// let (mut model, frames) = MotionModel::with_modules(body, vec![module]).unwrap();
+    ///
+    /// let copies = model
+    ///     .mirror_subtree(frames[0].steering(), MirrorPlane::XZ, |name| {
+    ///         name.replace("left", "right")
+    ///     })
+    ///     .unwrap();
+    ///
+    /// let wheel = copies[frames[0].wheel()];
+    /// let wheel_to_body = model.homogeneous_transform_to_body(&wheel).unwrap();
+    /// assert_eq!(-0.5, wheel_to_body[(1, 3)]);
+    /// assert_eq!("front_right_wheel", model.reference_frame(&wheel).unwrap().name());
+    /// assert_eq!(2, model.drive_modules().count());
+    /// ```
+    pub fn mirror_subtree(
+        &mut self,
+        root: &FrameID,
+        plane: MirrorPlane,
+        new_names: impl Fn(&str) -> String,
+    ) -> Result<HashMap<FrameID, FrameID>, Error> {
+        let root_role = self.frame_role(root)?;
+        if root_role == FrameRole::Body {
+            return Err(Error::InvalidFrameID { id: *root });
+        }
+
+        let mut ancestor = *self.parent_of(root)?;
+        while !self.is_body(&ancestor) {
+            if self.frame_role(&ancestor)? == FrameRole::Steering {
+                return Err(Error::InvalidFrameID { id: *root });
+            }
+
+            ancestor = *self.parent_of(&ancestor)?;
+        }
+
+        // Collect the subtree with each element after its parent, so that the parent of each
+        // copy exists by the time the copy is added.
+        let mut originals = vec![*root];
+        let mut index = 0;
+        while index < originals.len() {
+            let children: Vec<FrameID> = self
+                .children_of(&originals[index])?
+                .into_iter()
+                .copied()
+                .collect();
+            originals.extend(children);
+            index += 1;
+        }
+
+        let mut copies = HashMap::with_capacity(originals.len());
+        for original in originals.iter() {
+            let parent = *self.parent_of(original)?;
+            let parent = copies.get(&parent).copied().unwrap_or(parent);
+            let copy = self.add_mirrored_element(original, parent, plane, &new_names)?;
+            copies.insert(*original, copy);
+        }
+
+        let modules: Vec<_> = self
+            .drive_modules
+            .iter()
+            .filter_map(|(name, frames)| Some((new_names(name), frames.mapped(&copies)?)))
+            .collect();
+        self.drive_modules.extend(modules);
+
+        Ok(copies)
+    }
+
+    /// Adds a mirrored copy of the given element, without its children, to the given parent.
+    /// Returns the [FrameID] of the copy.
+    fn add_mirrored_element(
+        &mut self,
+        original: &FrameID,
+        parent: FrameID,
+        plane: MirrorPlane,
+        new_names: &impl Fn(&str) -> String,
+    ) -> Result<FrameID, Error> {
+        let reference_frame = self.reference_frame(original)?;
+        let name = new_names(reference_frame.name());
+        let degree_of_freedom =
+            plane.mirror_degree_of_freedom(reference_frame.degree_of_freedom_kind());
+        let is_actuated = reference_frame.is_actuated();
+        let role = reference_frame.role();
+
+        let transform = plane.mirror_isometry(self.static_transform_to_parent(original)?);
+        let element = self.chassis_element(original)?;
+        let physical_properties =
+            plane.mirror_physical_properties(&ChassisElementPhysicalProperties::new(
+                element.mass_in_kg(),
+                *element.center_of_mass(),
+                *element.moment_of_inertia(),
+                *element.spatial_inertia(),
+            ));
+
+        let copy = match role {
+            FrameRole::Steering => self.add_steering_element_with_optional_actuator(
+                name,
+                degree_of_freedom,
+                parent,
+                transform.translation,
+                transform.rotation,
+                physical_properties,
+                None,
+            )?,
+            FrameRole::Wheel => {
+                let wheel_properties = self.wheel_properties(original).ok().map(|p| {
+                    let mut properties = WheelProperties::new(
+                        p.radius(),
+                        p.width(),
+                        plane.mirror_vector(p.contact_offset()),
+                    );
+                    properties.rolling_radius = p.rolling_radius;
+                    properties
+                });
+                self.add_wheel_element(
+                    name,
+                    parent,
+                    transform.translation,
+                    transform.rotation,
+                    physical_properties,
+                    None,
+                    wheel_properties,
+                )?
+            }
+            _ => self.add_element_unchecked(
+                ReferenceFrame::new_with_role(name.clone(), degree_of_freedom, is_actuated, role),
+                parent,
+                transform.translation,
+                transform.rotation,
+                name,
+                physical_properties,
+            )?,
+        };
+
+        // A kinematics table describes the motion of the joint directly, so the direction of the
+        // joint only changes for joints that move along their degree of freedom.
+        let table = self
+            .kinematics_tables
+            .get(original)
+            .map(|table| table.map_transforms(|t| plane.mirror_isometry(t)));
+        let reverses = table.is_none() && plane.reverses(degree_of_freedom);
+        if let Some(table) = table {
+            self.kinematics_tables.insert(copy, table);
+        }
+
+        if let Some(constraint) = self.joint_constraints.get(original).copied() {
+            let constraint = if reverses {
+                reversed_constraint(&constraint)
+            } else {
+                constraint
+            };
+            self.joint_constraints.insert(copy, constraint);
+        }
+
+        if let Some(metadata) = self.frame_metadata.get(original).cloned() {
+            self.frame_metadata.insert(copy, metadata);
+        }
+
+        if let Some(droop) = self.suspension_droop_positions.get(original).copied() {
+            let droop = if reverses { -droop } else { droop };
+            self.suspension_droop_positions.insert(copy, droop);
+        }
+
+        Ok(copy)
+    }
+}
+
+/// Returns the constraint for a joint that moves in the opposite direction, i.e. each limit
+/// `(minimum, maximum)` becomes `(-maximum, -minimum)`.
+fn reversed_constraint(constraint: &JointConstraint) -> JointConstraint {
+    let mut result = JointConstraint::new();
+    if let Some((minimum, maximum)) = constraint.position_limits() {
+        result = result.with_position_limits(-maximum, -minimum);
+    }
+
+    if let Some((minimum, maximum)) = constraint.velocity_limits() {
+        result = result.with_velocity_limits(-maximum, -minimum);
+    }
+
+    if let Some((minimum, maximum)) = constraint.acceleration_limits() {
+        result = result.with_acceleration_limits(-maximum, -minimum);
+    }
+
+    if let Some((minimum, maximum)) = constraint.effort_limits() {
+        result = result.with_effort_limits(-maximum, -minimum);
+    }
+
+    result
+}
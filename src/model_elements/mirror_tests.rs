@@ -0,0 +1,202 @@
+use nalgebra::{Isometry3, Matrix3, Matrix4, Translation3, Unit, UnitQuaternion, Vector3};
+
+use crate::{
+    model_elements::{
+        frame_elements::{FrameDofType, FrameID, JointConstraint},
+        model::{ChassisElementPhysicalProperties, MotionModel, WheelProperties},
+        module_spec::{BodySpec, ModuleSpec},
+    },
+    Error,
+};
+
+use super::MirrorPlane;
+
+fn physical_properties(mass: f64) -> ChassisElementPhysicalProperties {
+    ChassisElementPhysicalProperties::from_mass_properties(
+        mass,
+        Vector3::new(0.1, 0.2, 0.3),
+        Matrix3::new(1.0, 0.1, 0.2, 0.1, 2.0, 0.3, 0.2, 0.3, 3.0),
+    )
+}
+
+fn create_model() -> (MotionModel, FrameID) {
+    let body = BodySpec::new(
+        "body".to_string(),
+        Translation3::identity(),
+        UnitQuaternion::identity(),
+        physical_properties(10.0),
+    );
+    let module = ModuleSpec::new(
+        "front_left".to_string(),
+        Translation3::new(1.0, 0.5, 0.1),
+        UnitQuaternion::from_euler_angles(0.0, 0.0, 0.3),
+        WheelProperties::new(0.1, 0.05, Vector3::new(0.01, 0.02, 0.0)),
+        physical_properties(1.0),
+        physical_properties(2.0),
+    )
+    .with_wheel_offset(Translation3::new(0.05, 0.1, -0.2))
+    .with_suspension(
+        FrameDofType::RevoluteX,
+        physical_properties(3.0),
+        JointConstraint::new()
+            .with_position_limits(-0.1, 0.2)
+            .with_velocity_limits(-1.0, 2.0),
+    );
+    let (model, frames) = MotionModel::with_modules(body, vec![module]).unwrap();
+    let suspension = *frames[0].suspension().unwrap();
+    (model, suspension)
+}
+
+fn reflect(transform: &Matrix4<f64>) -> Matrix4<f64> {
+    let reflection = Matrix4::from_diagonal(&nalgebra::Vector4::new(1.0, -1.0, 1.0, 1.0));
+    reflection * transform * reflection
+}
+
+#[test]
+fn when_mirroring_an_isometry_it_should_reflect_the_translation_and_the_rotation() {
+    let isometry = Isometry3::from_parts(
+        Translation3::new(1.0, 2.0, 3.0),
+        UnitQuaternion::from_euler_angles(0.0, 0.0, 0.5),
+    );
+
+    let mirrored = MirrorPlane::XZ.mirror_isometry(&isometry);
+    assert_eq!(Vector3::new(1.0, -2.0, 3.0), mirrored.translation.vector);
+    assert!((-0.5 - mirrored.rotation.euler_angles().2).abs() < 1e-12);
+
+    let mirrored = MirrorPlane::YZ.mirror_isometry(&isometry);
+    assert_eq!(Vector3::new(-1.0, 2.0, 3.0), mirrored.translation.vector);
+    assert!((-0.5 - mirrored.rotation.euler_angles().2).abs() < 1e-12);
+
+    assert!(MirrorPlane::XZ.reverses(FrameDofType::RevoluteZ));
+    assert!(!MirrorPlane::XZ.reverses(FrameDofType::RevoluteY));
+    assert!(MirrorPlane::YZ.reverses(FrameDofType::PrismaticX));
+    assert!(!MirrorPlane::YZ.reverses(FrameDofType::PrismaticZ));
+}
+
+#[test]
+fn when_mirroring_an_arbitrary_axis_it_should_match_the_mirrored_principal_axes() {
+    let axis = Unit::new_normalize(Vector3::new(1.0, 2.0, 3.0));
+    let mirrored_axis = Unit::new_normalize(Vector3::new(1.0, -2.0, 3.0));
+
+    assert_eq!(
+        FrameDofType::PrismaticAxis(mirrored_axis),
+        MirrorPlane::XZ.mirror_degree_of_freedom(FrameDofType::PrismaticAxis(axis))
+    );
+    assert_eq!(
+        FrameDofType::RevoluteAxis(-mirrored_axis),
+        MirrorPlane::XZ.mirror_degree_of_freedom(FrameDofType::RevoluteAxis(axis))
+    );
+    assert!(!MirrorPlane::XZ.reverses(FrameDofType::RevoluteAxis(axis)));
+    assert_eq!(
+        FrameDofType::RevoluteZ,
+        MirrorPlane::XZ.mirror_degree_of_freedom(FrameDofType::RevoluteZ)
+    );
+
+    // A joint around the mirrored axis at position q moves like a reversed joint around the
+    // principal axis, i.e. it mirrors the original joint at position q
+    let model = MotionModel::new();
+    let z = FrameDofType::RevoluteAxis(Vector3::z_axis());
+    let original = model.transform_for_motion(0.3, z, &Isometry3::translation(1.0, 0.5, 0.0));
+    let mirrored = model.transform_for_motion(
+        0.3,
+        MirrorPlane::XZ.mirror_degree_of_freedom(z),
+        &MirrorPlane::XZ.mirror_isometry(&Isometry3::translation(1.0, 0.5, 0.0)),
+    );
+    let expected = MirrorPlane::XZ.mirror_isometry(&original);
+    assert!((expected.to_homogeneous() - mirrored.to_homogeneous()).norm() < 1e-12);
+}
+
+#[test]
+fn when_mirroring_a_subtree_it_should_add_mirrored_copies_of_the_elements() {
+    let (mut model, suspension) = create_model();
+    let originals: Vec<FrameID> = model
+        .chassis_elements()
+        .map(|e| *e.reference_frame())
+        .collect();
+
+    let copies = model
+        .mirror_subtree(&suspension, MirrorPlane::XZ, |name| {
+            name.replace("left", "right")
+        })
+        .unwrap();
+    assert_eq!(3, copies.len());
+    assert_eq!(2, model.number_of_wheels());
+
+    for (original, copy) in copies.iter() {
+        assert!(originals.contains(original));
+        assert_eq!(
+            model
+                .reference_frame(original)
+                .unwrap()
+                .name()
+                .replace("left", "right"),
+            model.reference_frame(copy).unwrap().name()
+        );
+        assert_eq!(model.frame_role(original), model.frame_role(copy));
+
+        let expected = reflect(&model.homogeneous_transform_to_body(original).unwrap());
+        let actual = model.homogeneous_transform_to_body(copy).unwrap();
+        assert!((expected - actual).abs().max() < 1e-12);
+
+        let original_element = model.chassis_element(original).unwrap();
+        let copy_element = model.chassis_element(copy).unwrap();
+        assert_eq!(original_element.mass_in_kg(), copy_element.mass_in_kg());
+        assert_eq!(Vector3::new(0.1, -0.2, 0.3), *copy_element.center_of_mass());
+
+        let expected = ChassisElementPhysicalProperties::from_mass_properties(
+            copy_element.mass_in_kg(),
+            *copy_element.center_of_mass(),
+            *copy_element.moment_of_inertia(),
+        );
+        assert!(
+            (expected.spatial_inertia() - copy_element.spatial_inertia())
+                .abs()
+                .max()
+                < 1e-12
+        );
+    }
+
+    // The suspension rotates around the x-axis, which lies in the mirror plane
+    let copy = copies[&suspension];
+    let constraint = model.joint_constraint(&copy).unwrap();
+    assert_eq!(Some((-0.2, 0.1)), constraint.position_limits());
+    assert_eq!(Some((-2.0, 1.0)), constraint.velocity_limits());
+
+    let modules: Vec<_> = model.drive_modules().map(|m| m.name()).collect();
+    assert_eq!(vec!["front_left", "front_right"], modules);
+    let module = model.drive_modules().nth(1).unwrap();
+    assert_eq!(Some(&copy), module.frames().suspension());
+    assert_eq!(
+        &Vector3::new(0.01, -0.02, 0.0),
+        module.wheel_properties().unwrap().contact_offset()
+    );
+    assert_eq!(
+        Ok(module.frames().steering()),
+        model.steering_frame_for_wheel(module.frames().wheel())
+    );
+}
+
+#[test]
+fn when_mirroring_an_invalid_subtree_it_should_error() {
+    let (mut model, suspension) = create_model();
+    let body = *model.body().unwrap();
+    let wheel = *model.wheels().unwrap()[0];
+
+    assert_eq!(
+        Err(Error::InvalidFrameID { id: body }),
+        model.mirror_subtree(&body, MirrorPlane::XZ, |name| name.to_string())
+    );
+    assert_eq!(
+        Err(Error::InvalidFrameID { id: wheel }),
+        model.mirror_subtree(&wheel, MirrorPlane::XZ, |name| name.to_string())
+    );
+
+    let missing = FrameID::new();
+    assert_eq!(
+        Err(Error::MissingFrameElement { id: missing }),
+        model.mirror_subtree(&missing, MirrorPlane::XZ, |name| name.to_string())
+    );
+
+    assert_eq!(1, model.number_of_wheels());
+    assert!(model.children_of(&suspension).is_ok());
+}
@@ -2,20 +2,72 @@
 
 extern crate nalgebra as na;
 
-use std::collections::{BTreeSet, HashMap};
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap, VecDeque},
+    time::{Duration, Instant},
+};
 
-use na::{Isometry3, Matrix3, Matrix4, Matrix6, Translation3, UnitQuaternion, Vector3};
+use crossbeam_channel::{Receiver, Sender};
+use na::{
+    Isometry3, Matrix3, Matrix4, Matrix6, Point3, Translation3, Unit, UnitQuaternion, Vector3,
+};
 
-use crate::Error;
+use crate::{
+    hardware::{
+        command_middleware::CommandMiddleware,
+        health::ActuatorHealth,
+        joint_state::JointState,
+        state_fusion::{PreferActuator, StateFusion},
+        state_middleware::StateMiddleware,
+    },
+    instrumentation::{event, span},
+    number_space::NumberSpaceType,
+    Error,
+};
 
-use super::frame_elements::{
-    Actuator, ChassisElement, FrameDofType, FrameID, JointConstraint, JointSensor, ReferenceFrame,
+use super::{
+    collision::CollisionGeometry,
+    frame_elements::{
+        Actuator, ChassisElement, ConstraintEnforcement, FrameDofType, FrameID, FrameRole,
+        JointCalibration, JointConstraint, JointSensor, JointTransmission, ReferenceFrame,
+    },
+    ground_contact::{GroundContactEvent, GroundContactThresholds},
+    kinematics_table::JointKinematicsTable,
+    loop_closure::LoopClosure,
+    model_change::{ModelChange, ModelChangeEvent},
+    model_event::{ModelEvent, ModelEventPublisher},
+    module_spec::ModuleFrames,
+    multi_dof::MultiDofJoint,
+    operating_mode::OperatingMode,
+    parameters::{ParameterBinding, ParameterSet},
+    sensor_mount::SensorKind,
+    stable_id::StableFrameID,
+    suspension::SuspensionSpring,
+    validation::{ValidationIssue, ValidationReport},
+    visual::VisualGeometry,
+    wrench::{Wrench, STANDARD_GRAVITY},
 };
 
 #[cfg(test)]
+#[allow(
+    dead_code,
+    clippy::assertions_on_constants,
+    clippy::needless_range_loop
+)]
 #[path = "model_tests.rs"]
 mod model_tests;
 
+/// The distance, in meters, between the center of a wheel and the origin of its steering frame
+/// below which the wheel is considered to be located at the origin of the steering frame.
+const MINIMUM_WHEEL_OFFSET: f64 = 1e-9;
+
+/// The error, in meters or radians, above which a loop closure constraint is considered to be
+/// violated.
+const LOOP_CLOSURE_TOLERANCE: f64 = 1e-6;
+
+/// The relative tolerance used to compare the inertia matrices of the chassis elements.
+const INERTIA_TOLERANCE: f64 = 1e-9;
+
 /// A delegating iterator for the KinematicTree so that we can return an iterator or an
 /// empty iterator.
 pub struct OptionIterator<I> {
@@ -60,6 +112,65 @@ impl<I> OptionIterator<I> {
     }
 }
 
+/// Iterates over the frames of a [MotionModel] in depth-first or breadth-first order, starting at
+/// the body, see [MotionModel::iter_dfs] and [MotionModel::iter_bfs].
+///
+/// Each item is the [FrameID] of a frame together with the depth of the frame, i.e. the number of
+/// links between the frame and the body. The body has a depth of zero. The children of a frame are
+/// visited in the order of their [FrameID].
+pub struct FrameTraversal<'a> {
+    /// The tree that is traversed.
+    tree: &'a KinematicTree,
+
+    /// The frames that have been found but not visited yet, together with their depth.
+    pending: VecDeque<(&'a FrameID, usize)>,
+
+    /// A value indicating whether the tree is traversed depth-first or breadth-first.
+    depth_first: bool,
+}
+
+impl<'a> FrameTraversal<'a> {
+    /// Creates a new [FrameTraversal] that starts at the body of the given tree.
+    fn new(tree: &'a KinematicTree, depth_first: bool) -> Self {
+        let pending = match tree.body_element() {
+            Ok(body) => VecDeque::from([(body.id(), 0)]),
+            Err(_) => VecDeque::new(),
+        };
+
+        Self {
+            tree,
+            pending,
+            depth_first,
+        }
+    }
+}
+
+impl<'a> Iterator for FrameTraversal<'a> {
+    type Item = (&'a FrameID, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (id, depth) = if self.depth_first {
+            self.pending.pop_back()?
+        } else {
+            self.pending.pop_front()?
+        };
+
+        if let Ok(children) = self.tree.children_of(id) {
+            let children = children.map(|child| (child.id(), depth + 1));
+            if self.depth_first {
+                // The pending frames are used as a stack, so the first child should be pushed
+                // last.
+                let children: Vec<_> = children.collect();
+                self.pending.extend(children.into_iter().rev());
+            } else {
+                self.pending.extend(children);
+            }
+        }
+
+        Some((id, depth))
+    }
+}
+
 /// Defines a kinematic tree that defines the kinematic model of a wheeled mobile robot. The root
 /// of the tree is the robot body with six degrees of freedom (3 translations, 3 rotations) with
 /// respect to the navigation / world reference frame.
@@ -70,6 +181,10 @@ impl<I> OptionIterator<I> {
 /// All branches of the kinematic tree end with the wheel frames, which, by convention, are attached
 /// to their parent frame by revolute joints around the y-axis.
 ///
+/// The tree of a [MotionModel] can be inspected with [MotionModel::kinematic_tree]. The model only
+/// hands out a shared reference to its tree, so the structure of the model can only be changed
+/// through the [MotionModel] itself.
+///
 /// ## References
 ///
 /// * [A vector algebra formulation of mobile robot velocity kinematics](https://scholar.google.co.nz/citations?view_op=view_citation&hl=en&user=H10kxZgAAAAJ&cstart=20&pagesize=80&sortby=pubdate&citation_for_view=H10kxZgAAAAJ:qjMakFHDy7sC)
@@ -77,16 +192,20 @@ impl<I> OptionIterator<I> {
 ///   Field and Service Robotics: Results of the 8th International Conference
 ///   2013/12/31
 ///
-struct KinematicTree {
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct KinematicTree {
     /// List of frame elements starting at the root.
+    #[cfg_attr(feature = "serde", serde(with = "super::frame_elements::frame_id_map"))]
     elements: HashMap<FrameID, ReferenceFrame>,
 
     /// The mapping from the parent elements to their direct children.
+    #[cfg_attr(feature = "serde", serde(with = "super::frame_elements::frame_id_map"))]
     children_of: HashMap<FrameID, BTreeSet<FrameID>>,
 
     /// The mapping from the child element to their parent. The child FrameID is
     /// used as the key. The value is a combination of the parent FrameID and the
     /// Homogeneous transform from the child to the parent when the joint displacement is zero.
+    #[cfg_attr(feature = "serde", serde(with = "super::frame_elements::frame_id_map"))]
     parent_of: HashMap<FrameID, (FrameID, Isometry3<f64>)>,
 
     /// The list of indices for the wheel frames.
@@ -100,8 +219,9 @@ impl KinematicTree {
     /// world (which has the 'FrameID::none()' id number). All other elements should have a parent
     /// element that is known to the tree.
     ///
-    /// Elements that have a revolute degree of freedom around the y-axis and have no children are
-    /// assumed to be the wheel elements.
+    /// Elements with the [FrameRole::Wheel] role are the wheel elements. Elements with the
+    /// [FrameRole::Generic] role, i.e. elements without a specific role, are assumed to be wheel
+    /// elements if they have a revolute degree of freedom around the y-axis and have no children.
     ///
     /// * 'element' - The element that should be stored.
     /// * 'parent_id' - The ID of the parent element. It is assumed that this element already exists
@@ -122,7 +242,7 @@ impl KinematicTree {
     ///   It is assumed that there is only 1 frame element with no parent. This element is assumed
     ///   to be the body element which by definition is attached to the world frame.
     ///
-    fn add_element(
+    pub fn add_element(
         &mut self,
         element: ReferenceFrame,
         parent_id: FrameID,
@@ -155,8 +275,14 @@ impl KinematicTree {
                 self.parent_of
                     .insert(*cloned_element_id, (parent_id_to_store, isometry));
 
-                // A parent node can never be a wheel
-                self.wheel_elements.remove(parent_id_ref);
+                // A parent node can never be a wheel, unless the parent has the wheel role
+                let parent_is_generic = self
+                    .elements
+                    .get(parent_id_ref)
+                    .map_or(false, |p| p.role() == FrameRole::Generic);
+                if parent_is_generic {
+                    self.wheel_elements.remove(parent_id_ref);
+                }
             }
 
             if !self.children_of.contains_key(parent_id_ref) {
@@ -180,11 +306,19 @@ impl KinematicTree {
             }
         }
 
-        // We assume the element is a wheel if:
+        // The role decides if the element is a wheel. For elements without a role we assume the
+        // element is a wheel if:
         // - It is a leaf node, i.e. it doesn't have any children
         // - it has a revolute motion around the Y-axis
-        let has_children = self.children_of.contains_key(element_id);
-        if !has_children && element_ref.degree_of_freedom_kind() == FrameDofType::RevoluteY {
+        let is_wheel = match element_ref.role() {
+            FrameRole::Wheel => true,
+            FrameRole::Generic => {
+                !self.children_of.contains_key(element_id)
+                    && element_ref.degree_of_freedom_kind() == FrameDofType::RevoluteY
+            }
+            _ => false,
+        };
+        if is_wheel {
             self.wheel_elements.insert(*element_id);
         }
 
@@ -206,7 +340,7 @@ impl KinematicTree {
     ///
     /// * [Error::MissingFrameElement] - Returned when there is no body element stored
     ///   in the tree
-    fn body_element(&self) -> Result<&ReferenceFrame, Error> {
+    pub fn body_element(&self) -> Result<&ReferenceFrame, Error> {
         if self.elements.is_empty() {
             return Err(Error::MissingFrameElement {
                 id: FrameID::none(),
@@ -233,19 +367,25 @@ impl KinematicTree {
     /// ## Errors
     ///
     /// * [Error::InvalidFrameID] - Returned when there is no reference frame with ID 'id'
-    fn children_of(&self, id: &FrameID) -> Result<impl Iterator<Item = &ReferenceFrame>, Error> {
+    /// * [Error::InternalInconsistency] - Returned when one of the children is not stored in
+    ///   the tree.
+    pub fn children_of(
+        &self,
+        id: &FrameID,
+    ) -> Result<impl Iterator<Item = &ReferenceFrame>, Error> {
         if !self.elements.contains_key(id) {
             return Err(Error::InvalidFrameID { id: *id });
         }
 
-        if !self.children_of.contains_key(id) {
-            return Ok(OptionIterator::new(None));
-        }
+        let children = match self.children_of.get(id) {
+            Some(c) => c
+                .iter()
+                .map(|child| self.referenced_element(child, "child", id))
+                .collect::<Result<Vec<_>, Error>>()?,
+            None => Vec::new(),
+        };
 
-        let children = &self.children_of[id];
-        Ok(OptionIterator::new(Some(
-            children.iter().map(|id| self.get_element_unchecked(id)),
-        )))
+        Ok(children.into_iter())
     }
 
     /// Returns the reference frame with the given ID
@@ -257,31 +397,17 @@ impl KinematicTree {
     /// ## Errors
     ///
     /// * [Error::InvalidFrameID] - Returned when there is no reference frame with ID 'id'
-    fn element(&self, id: &FrameID) -> Result<&ReferenceFrame, Error> {
-        if !self.elements.contains_key(id) {
-            return Err(Error::InvalidFrameID { id: *id });
+    pub fn element(&self, id: &FrameID) -> Result<&ReferenceFrame, Error> {
+        match self.elements.get(id) {
+            Some(e) => Ok(e),
+            None => Err(Error::InvalidFrameID { id: *id }),
         }
-
-        Ok(self.get_element_unchecked(id))
-    }
-
-    /// Returns the reference frame for the given ID without checking that this
-    /// reference frame actually exists.
-    ///
-    /// ## Parameters
-    ///
-    /// * 'id' - The ID of the reference frame that should be returned
-    ///
-    /// This function will panic if there is no [ReferenceFrame] with the given
-    /// ID.
-    fn get_element_unchecked(&self, id: &FrameID) -> &ReferenceFrame {
-        &(self.elements[id])
     }
 
     /// Returns an iterator that iterates over all the reference frames in the tree.
     ///
     /// The order of iteration is not guaranteed.
-    fn elements(&self) -> impl Iterator<Item = &ReferenceFrame> {
+    pub fn elements(&self) -> impl Iterator<Item = &ReferenceFrame> {
         self.elements.values()
     }
 
@@ -295,13 +421,16 @@ impl KinematicTree {
     /// ## Errors
     ///
     /// * [Error::InvalidFrameID] - Returned when there is no reference frame with ID 'id'
+    /// * [Error::MissingFrameElement] - Returned when the reference frame has no parent.
     pub fn homogeneous_transform_to_parent(&self, id: &FrameID) -> Result<&Isometry3<f64>, Error> {
         if !self.elements.contains_key(id) {
             return Err(Error::InvalidFrameID { id: *id });
         }
 
-        let (_, transform) = self.parent_of.get(id).unwrap();
-        Ok(transform)
+        match self.parent_of.get(id) {
+            Some((_, transform)) => Ok(transform),
+            None => Err(Error::MissingFrameElement { id: *id }),
+        }
     }
 
     /// Returns the parent reference frame for the given reference frame
@@ -314,17 +443,49 @@ impl KinematicTree {
     ///
     /// * [Error::InvalidFrameID] - Returned when there is no reference frame with ID 'id'
     /// * [Error::MissingFrameElement] - Returned when the reference frame has no parent.
-    fn parent_of(&self, child_id: &FrameID) -> Result<&ReferenceFrame, Error> {
+    /// * [Error::InternalInconsistency] - Returned when the parent is not stored in the tree.
+    pub fn parent_of(&self, child_id: &FrameID) -> Result<&ReferenceFrame, Error> {
         if !self.elements.contains_key(child_id) {
             return Err(Error::InvalidFrameID { id: *child_id });
         }
 
-        if !self.parent_of.contains_key(child_id) {
-            return Err(Error::MissingFrameElement { id: *child_id });
+        match self.parent_of.get(child_id) {
+            Some((parent_id, _)) => self.referenced_element(parent_id, "parent", child_id),
+            None => Err(Error::MissingFrameElement { id: *child_id }),
         }
+    }
 
-        let parent_id_ref = self.parent_of[child_id].0.as_ref();
-        Ok(self.get_element_unchecked(parent_id_ref))
+    /// Returns the reference frame that is referenced by a link between two reference frames
+    /// in the tree, e.g. the parent of a reference frame.
+    ///
+    /// The links are only created for reference frames that are stored in the tree, so a
+    /// missing reference frame means that the tree is no longer consistent.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'id' - The ID of the referenced reference frame
+    /// * 'relation' - The relation of the referenced reference frame to 'referenced_by', used
+    ///   to describe the inconsistency
+    /// * 'referenced_by' - The ID of the reference frame that holds the link
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::InternalInconsistency] - Returned when there is no reference frame with ID 'id'
+    fn referenced_element(
+        &self,
+        id: &FrameID,
+        relation: &str,
+        referenced_by: &FrameID,
+    ) -> Result<&ReferenceFrame, Error> {
+        match self.elements.get(id) {
+            Some(e) => Ok(e),
+            None => Err(Error::InternalInconsistency {
+                context: format!(
+                    "{} {} of frame {} is not stored in the kinematic tree",
+                    relation, id, referenced_by
+                ),
+            }),
+        }
     }
 
     /// Returns an iterator that returns all the wheel reference frames in the tree
@@ -332,17 +493,25 @@ impl KinematicTree {
     /// ## Errors
     ///
     /// * [Error::MissingFrameElement] - Returned when the tree is empty
-    fn wheels(&self) -> Result<impl Iterator<Item = &ReferenceFrame>, Error> {
+    pub fn wheels(&self) -> Result<impl Iterator<Item = &ReferenceFrame>, Error> {
         if self.elements.is_empty() {
             return Err(Error::MissingFrameElement {
                 id: FrameID::none(),
             });
         }
 
-        Ok(self
+        let wheels = self
             .wheel_elements
             .iter()
-            .map(|id| self.get_element_unchecked(id)))
+            .map(|id| match self.elements.get(id) {
+                Some(e) => Ok(e),
+                None => Err(Error::InternalInconsistency {
+                    context: format!("wheel {} is not stored in the kinematic tree", id),
+                }),
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        Ok(wheels.into_iter())
     }
 
     /// Returns a value indicating whether the kinematic tree contains a [ReferenceFrame]
@@ -351,7 +520,7 @@ impl KinematicTree {
     /// ## Parameters
     ///
     /// * 'id' - The ID of the reference frame
-    fn has_element(&self, id: &FrameID) -> bool {
+    pub fn has_element(&self, id: &FrameID) -> bool {
         self.elements.contains_key(id)
     }
 
@@ -365,7 +534,7 @@ impl KinematicTree {
     /// ## Errors
     ///
     /// * [Error::InvalidFrameID] - Returned when there is no reference frame with ID 'id'
-    fn is_body(&self, id: &FrameID) -> Result<bool, Error> {
+    pub fn is_body(&self, id: &FrameID) -> Result<bool, Error> {
         if !self.elements.contains_key(id) {
             return Err(Error::InvalidFrameID { id: *id });
         }
@@ -375,7 +544,7 @@ impl KinematicTree {
 
     /// Returns a value indicating whether there are any [ReferenceFrame] instances in
     /// the [KinematicTree]
-    fn is_empty(&self) -> bool {
+    pub fn is_empty(&self) -> bool {
         self.elements.is_empty()
     }
 
@@ -389,7 +558,7 @@ impl KinematicTree {
     /// ## Errors
     ///
     /// * [Error::InvalidFrameID] - Returned when there is no reference frame with ID 'id'
-    fn is_wheel(&self, id: &FrameID) -> Result<bool, Error> {
+    pub fn is_wheel(&self, id: &FrameID) -> Result<bool, Error> {
         if !self.elements.contains_key(id) {
             return Err(Error::InvalidFrameID { id: *id });
         }
@@ -397,8 +566,20 @@ impl KinematicTree {
         Ok(self.wheel_elements.contains(id))
     }
 
+    /// Returns an iterator that visits the reference frames in breadth-first order, starting at
+    /// the body, see [MotionModel::iter_bfs].
+    pub fn iter_bfs(&self) -> FrameTraversal<'_> {
+        FrameTraversal::new(self, false)
+    }
+
+    /// Returns an iterator that visits the reference frames in depth-first order, starting at
+    /// the body, see [MotionModel::iter_dfs].
+    pub fn iter_dfs(&self) -> FrameTraversal<'_> {
+        FrameTraversal::new(self, true)
+    }
+
     /// Creates a new [KinematicTree]
-    fn new() -> Self {
+    pub fn new() -> Self {
         Self {
             elements: HashMap::new(),
             parent_of: HashMap::new(),
@@ -411,9 +592,138 @@ impl KinematicTree {
     pub fn number_of_wheels(&self) -> usize {
         self.wheel_elements.len()
     }
+
+    /// Removes the given reference frame from the tree. The children of the reference frame
+    /// are attached to the parent of the removed reference frame, keeping their position and
+    /// orientation relative to the parent when the joint displacement is zero.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'id' - The ID of the reference frame that should be removed
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::InvalidFrameID] - Returned when there is no reference frame with ID 'id' or when
+    ///   the reference frame is the body.
+    fn remove_element_and_reparent_children(&mut self, id: &FrameID) -> Result<(), Error> {
+        let (parent_id, transform) = match self.parent_of.get(id) {
+            Some((p, t)) => (*p, *t),
+            None => return Err(Error::InvalidFrameID { id: *id }),
+        };
+
+        let children = self.children_of.remove(id).unwrap_or_default();
+        for child in children.iter() {
+            if let Some((p, t)) = self.parent_of.get_mut(child) {
+                *p = parent_id;
+                *t = transform * *t;
+            }
+        }
+
+        let siblings = self.children_of.entry(parent_id).or_default();
+        siblings.remove(id);
+        siblings.extend(children);
+
+        self.parent_of.remove(id);
+        self.wheel_elements.remove(id);
+        self.elements.remove(id);
+
+        Ok(())
+    }
+
+    /// Removes the given reference frame and all the reference frames that are attached to it,
+    /// directly or indirectly, from the tree. Returns the IDs of the removed reference frames,
+    /// starting with the given reference frame.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'id' - The ID of the reference frame at the root of the sub-tree
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::InvalidFrameID] - Returned when there is no reference frame with ID 'id' or when
+    ///   the reference frame is the body.
+    fn remove_subtree(&mut self, id: &FrameID) -> Result<Vec<FrameID>, Error> {
+        let parent_id = match self.parent_of.get(id) {
+            Some((p, _)) => *p,
+            None => return Err(Error::InvalidFrameID { id: *id }),
+        };
+
+        if let Some(siblings) = self.children_of.get_mut(&parent_id) {
+            siblings.remove(id);
+        }
+
+        let removed = self.subtree_of(id);
+        for frame in removed.iter() {
+            self.children_of.remove(frame);
+            self.parent_of.remove(frame);
+            self.wheel_elements.remove(frame);
+            self.elements.remove(frame);
+        }
+
+        Ok(removed)
+    }
+
+    /// Sets the homogeneous transform that turns coordinates in the child reference frame into
+    /// coordinates in the parent reference frame.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'id' - The ID of the child reference frame
+    /// * 'transform' - The transform from the child reference frame to the parent reference frame
+    ///   when the joint displacement is zero.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::InvalidFrameID] - Returned when there is no reference frame with ID 'id' or when
+    ///   the reference frame has no parent.
+    fn set_transform_to_parent(
+        &mut self,
+        id: &FrameID,
+        transform: Isometry3<f64>,
+    ) -> Result<(), Error> {
+        match self.parent_of.get_mut(id) {
+            Some((_, t)) => {
+                *t = transform;
+                Ok(())
+            }
+            None => Err(Error::InvalidFrameID { id: *id }),
+        }
+    }
+
+    /// Returns the ID of the given reference frame and of all the reference frames that are
+    /// attached to it, directly or indirectly. The given reference frame is the first element
+    /// in the list.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'id' - The ID of the reference frame at the root of the sub-tree
+    pub fn subtree_of(&self, id: &FrameID) -> Vec<FrameID> {
+        let mut result = Vec::new();
+        if !self.elements.contains_key(id) {
+            return result;
+        }
+
+        let mut stack = vec![*id];
+        while let Some(current) = stack.pop() {
+            result.push(current);
+            if let Some(children) = self.children_of.get(&current) {
+                stack.extend(children.iter().rev());
+            }
+        }
+
+        result
+    }
+}
+
+impl Default for KinematicTree {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 /// Stores the physical attributes for a [ChassisElement].
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ChassisElementPhysicalProperties {
     mass: f64,
     center_of_mass: Vector3<f64>,
@@ -427,6 +737,41 @@ impl ChassisElementPhysicalProperties {
         self.center_of_mass
     }
 
+    /// Creates a new instance of the [ChassisElementPhysicalProperties] struct with the spatial
+    /// inertia computed from the mass, the center of mass and the moment of inertia.
+    ///
+    /// The spatial inertia is given about the origin of the element reference frame, with the
+    /// linear part first, see the [mass_properties](crate::kinematics::mass_properties) module.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'mass' - The mass of the element in kilograms.
+    /// * 'center_of_mass' - The position of the center of mass in the element reference frame.
+    /// * 'moment_of_inertia' - The moment of inertia of the element around its center of mass,
+    ///   along the axes of the element reference frame.
+    pub fn from_mass_properties(
+        mass: f64,
+        center_of_mass: Vector3<f64>,
+        moment_of_inertia: Matrix3<f64>,
+    ) -> Self {
+        let skew = center_of_mass.cross_matrix();
+        let mut spatial_inertia = Matrix6::zeros();
+        spatial_inertia
+            .fixed_view_mut::<3, 3>(0, 0)
+            .copy_from(&(Matrix3::identity() * mass));
+        spatial_inertia
+            .fixed_view_mut::<3, 3>(0, 3)
+            .copy_from(&(-mass * skew));
+        spatial_inertia
+            .fixed_view_mut::<3, 3>(3, 0)
+            .copy_from(&(mass * skew));
+        spatial_inertia
+            .fixed_view_mut::<3, 3>(3, 3)
+            .copy_from(&(moment_of_inertia - mass * skew * skew));
+
+        Self::new(mass, center_of_mass, moment_of_inertia, spatial_inertia)
+    }
+
     /// Returns the mass for the element
     pub fn mass(&self) -> f64 {
         self.mass
@@ -458,14 +803,131 @@ impl ChassisElementPhysicalProperties {
     }
 }
 
+/// Stores the geometry of a wheel.
+///
+/// The geometry is described in the reference frame of the wheel with the wheel joint at its
+/// zero position. It is assumed that the wheel rolls along the x-axis and rotates around the
+/// y-axis of that reference frame, so that the point where the wheel touches the ground is
+/// located one radius below the center of the wheel, along the negative z-axis.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WheelProperties {
+    /// The radius of the wheel in meters.
+    radius: f64,
+
+    /// The width of the tire in meters.
+    width: f64,
+
+    /// The offset of the contact point from the point one radius below the center of the wheel.
+    contact_offset: Vector3<f64>,
+
+    /// The effective rolling radius of the wheel in meters, if it differs from the radius, e.g.
+    /// because of tire wear or load.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(super) rolling_radius: Option<f64>,
+}
+
+impl WheelProperties {
+    /// Returns the offset, in meters, of the contact point from the point that is located one
+    /// radius below the center of the wheel.
+    pub fn contact_offset(&self) -> &Vector3<f64> {
+        &self.contact_offset
+    }
+
+    /// Returns the position of the contact point in the reference frame of the wheel, with the
+    /// wheel joint at its zero position.
+    pub fn contact_point(&self) -> Vector3<f64> {
+        Vector3::new(0.0, 0.0, -self.radius) + self.contact_offset
+    }
+
+    /// Creates a new [WheelProperties] instance.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'radius' - The radius of the wheel in meters.
+    /// * 'width' - The width of the tire in meters.
+    /// * 'contact_offset' - The offset, in meters, of the contact point from the point that is
+    ///   located one radius below the center of the wheel, e.g. for a wheel with a cambered
+    ///   tire. Use [Vector3::zeros] when the contact point is directly below the center.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use nalgebra::Vector3;
+    /// use swerve_vehicle_descriptors::model_elements::model::WheelProperties;
+    ///
+    /// let properties = WheelProperties::new(0.1, 0.05, Vector3::new(0.0, 0.01, 0.0));
+    ///
+    /// assert_eq!(0.1, properties.radius());
+    /// assert_eq!(0.05, properties.width());
+    /// assert_eq!(Vector3::new(0.0, 0.01, -0.1), properties.contact_point());
+    /// ```
+    pub fn new(radius: f64, width: f64, contact_offset: Vector3<f64>) -> Self {
+        Self {
+            radius,
+            width,
+            contact_offset,
+            rolling_radius: None,
+        }
+    }
+
+    /// Returns the radius of the wheel in meters.
+    pub fn radius(&self) -> f64 {
+        self.radius
+    }
+
+    /// Returns the effective rolling radius of the wheel in meters, i.e. the distance the wheel
+    /// travels over the ground for each radian it rotates. This is the radius of the wheel unless
+    /// a calibrated rolling radius has been set.
+    pub fn rolling_radius(&self) -> f64 {
+        self.rolling_radius.unwrap_or(self.radius)
+    }
+
+    /// Returns the width of the tire in meters.
+    pub fn width(&self) -> f64 {
+        self.width
+    }
+
+    /// Returns the properties with the given effective rolling radius.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'rolling_radius' - The distance, in meters, the wheel travels over the ground for each
+    ///   radian it rotates.
+    pub fn with_rolling_radius(mut self, rolling_radius: f64) -> Self {
+        self.rolling_radius = Some(rolling_radius);
+        self
+    }
+}
+
 /// A motion model for a swerve robot.
 ///
 /// It is assumed that the robot will have N wheels, where N > 2. Each wheel has
 /// a single steering frame in the wheel-to-body chain of [ReferenceFrame] elements.
 /// Each steering frame should only link to exactly one wheel and each wheel should have
 /// exactly one steering frame.
+///
+/// When the `serde` feature is enabled the model can be serialized, e.g. to store the vehicle
+/// description in a file. The [Actuator] and [JointSensor] instances are connected to the
+/// hardware and are not serialized. After deserializing a model the actuators need to be
+/// attached again with [MotionModel::set_actuator]. The pose of the body in the world, the
+/// ground contact state, the external wrenches and the subscribers to the model events are not
+/// serialized either. Descriptions that were serialized by earlier versions of the crate are
+/// loaded with the `MigrationRegistry` of the `migration` module. Deserializing such a
+/// description directly fails.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MotionModel {
+    /// The version of the schema with which the model was serialized, see the
+    /// [migration](super::migration) module.
+    #[cfg(feature = "serde")]
+    #[serde(
+        serialize_with = "super::migration::serialize_schema_version",
+        deserialize_with = "super::migration::deserialize_schema_version"
+    )]
+    schema_version: u32,
+
     /// The [ChassisElement] instances that make up the model.
+    #[cfg_attr(feature = "serde", serde(with = "super::frame_elements::frame_id_map"))]
     chassis_elements: HashMap<FrameID, ChassisElement>,
 
     /// The collection of [ReferenceFrame] for all the [ChassisElement] in the model.
@@ -473,20 +935,193 @@ pub struct MotionModel {
 
     /// The collection of [FrameID] pointing to the steering frames and their
     /// associated wheels.
+    #[cfg_attr(feature = "serde", serde(with = "super::frame_elements::frame_id_map"))]
     steering_frame_to_wheel: HashMap<FrameID, FrameID>,
 
     /// The collection of [FrameID] pointing to the wheels and their associated
     /// steering frames.
+    #[cfg_attr(feature = "serde", serde(with = "super::frame_elements::frame_id_map"))]
     wheel_to_steering_frame: HashMap<FrameID, FrameID>,
 
     /// The collection of [Actuator] instances
+    #[cfg_attr(feature = "serde", serde(skip))]
     actuators: HashMap<FrameID, Actuator>,
 
     /// The collection of [JointSensor] instances
+    #[cfg_attr(feature = "serde", serde(skip))]
     sensors: HashMap<FrameID, JointSensor>,
 
     /// The collection of [JointConstraint] instances
-    joint_constraints: HashMap<FrameID, JointConstraint>,
+    #[cfg_attr(feature = "serde", serde(with = "super::frame_elements::frame_id_map"))]
+    pub(super) joint_constraints: HashMap<FrameID, JointConstraint>,
+
+    /// The calibration of the hardware of the joints.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, with = "super::frame_elements::frame_id_map")
+    )]
+    joint_calibrations: HashMap<FrameID, JointCalibration>,
+
+    /// The transmissions between the hardware and the joints.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, with = "super::frame_elements::frame_id_map")
+    )]
+    joint_transmissions: HashMap<FrameID, JointTransmission>,
+
+    /// The number of states that are stored in the history of the hardware of the joints.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, with = "super::frame_elements::frame_id_map")
+    )]
+    history_capacities: HashMap<FrameID, usize>,
+
+    /// The kind of sensor for each of the sensor mounts.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, with = "super::frame_elements::frame_id_map")
+    )]
+    pub(super) sensor_mounts: HashMap<FrameID, SensorKind>,
+
+    /// The collision geometry of the chassis elements.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, with = "super::frame_elements::frame_id_map")
+    )]
+    pub(super) collision_geometries: HashMap<FrameID, Vec<CollisionGeometry>>,
+
+    /// The pairs of chassis elements that are excluded from the self-collision check, ordered
+    /// by [FrameID].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(super) allowed_collisions: Vec<(FrameID, FrameID)>,
+
+    /// The visual geometry of the chassis elements.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, with = "super::frame_elements::frame_id_map")
+    )]
+    pub(super) visual_geometries: HashMap<FrameID, Vec<VisualGeometry>>,
+
+    /// The frame that is registered as the inertial measurement unit, if any.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(super) imu_frame: Option<FrameID>,
+
+    /// The last orientation of the inertial measurement unit in the world frame.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(super) imu_orientation: Option<UnitQuaternion<f64>>,
+
+    /// A value indicating whether the orientations and angles that enter the model are checked
+    /// against the conventions of the crate.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(super) audit_mode: bool,
+
+    /// The geometry of each of the wheels.
+    #[cfg_attr(feature = "serde", serde(with = "super::frame_elements::frame_id_map"))]
+    wheel_properties: HashMap<FrameID, WheelProperties>,
+
+    /// The collection of [JointKinematicsTable] instances for the frames that do not move
+    /// along a single axis.
+    #[cfg_attr(feature = "serde", serde(with = "super::frame_elements::frame_id_map"))]
+    pub(super) kinematics_tables: HashMap<FrameID, JointKinematicsTable>,
+
+    /// The joint position at full droop for each of the suspension frames.
+    #[cfg_attr(feature = "serde", serde(with = "super::frame_elements::frame_id_map"))]
+    pub(super) suspension_droop_positions: HashMap<FrameID, f64>,
+
+    /// The springs and dampers of the suspension frames.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, with = "super::frame_elements::frame_id_map")
+    )]
+    pub(super) suspension_springs: HashMap<FrameID, SuspensionSpring>,
+
+    /// The thresholds used to determine if a wheel is in contact with the ground.
+    ground_contact_thresholds: GroundContactThresholds,
+
+    /// The wheels that are currently not in contact with the ground.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    wheels_without_ground_contact: BTreeSet<FrameID>,
+
+    /// The senders used to notify subscribers of changes in the ground contact of the wheels.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    ground_contact_senders: Vec<Sender<GroundContactEvent>>,
+
+    /// The operating modes of the vehicle, stored by name.
+    operating_modes: HashMap<String, OperatingMode>,
+
+    /// The name of the active operating mode, if any.
+    active_operating_mode: Option<String>,
+
+    /// The revision of the model. The revision is incremented each time a structural change is
+    /// made to the model.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    revision: u64,
+
+    /// The pose of the body in the world frame.
+    #[cfg_attr(feature = "serde", serde(skip, default = "Isometry3::identity"))]
+    body_pose_in_world: Isometry3<f64>,
+
+    /// The senders used to notify subscribers of structural changes to the model.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    structural_change_senders: Vec<Sender<ModelChangeEvent>>,
+
+    /// The policy that combines the states of the joints that have both an actuator and a
+    /// sensor.
+    #[cfg_attr(feature = "serde", serde(skip, default = "default_state_fusion"))]
+    state_fusion: Box<dyn StateFusion>,
+
+    /// The publisher used to notify subscribers of the events of the model.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    event_publisher: ModelEventPublisher,
+
+    /// The outcome of the last validation of the model, or [None] if the validity is not
+    /// tracked because nobody has subscribed to the events of the model.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    last_validity: Option<bool>,
+
+    /// The user defined key-value pairs that are attached to the reference frames.
+    #[cfg_attr(feature = "serde", serde(with = "super::frame_elements::frame_id_map"))]
+    pub(super) frame_metadata: HashMap<FrameID, BTreeMap<String, String>>,
+
+    /// The alias names of the reference frames, e.g. the names used by other systems.
+    #[cfg_attr(feature = "serde", serde(default))]
+    frame_aliases: BTreeMap<String, FrameID>,
+
+    /// The identifiers of the reference frames that do not change between runs of the
+    /// application.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, with = "super::frame_elements::frame_id_map")
+    )]
+    pub(super) stable_ids: HashMap<FrameID, StableFrameID>,
+
+    /// The names and the frames of the wheel modules that were created from a
+    /// [ModuleSpec](super::module_spec::ModuleSpec), in the order in which they were added.
+    pub(super) drive_modules: Vec<(String, ModuleFrames)>,
+
+    /// The joints with more than one degree of freedom, in the order in which they were added.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(super) multi_dof_joints: Vec<MultiDofJoint>,
+
+    /// The constraints that close the kinematic loops of the model.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(super) loop_closures: Vec<LoopClosure>,
+
+    /// The bindings between the frames of the model and the parameters of the model.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(super) parameter_bindings: Vec<ParameterBinding>,
+
+    /// The parameters that were last applied to the model.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(super) parameters: ParameterSet,
+
+    /// The acceleration due to gravity, in meters per second squared, in the world frame.
+    #[cfg_attr(feature = "serde", serde(default = "default_gravity"))]
+    gravity: Vector3<f64>,
+
+    /// The external [Wrench] instances that act on the reference frames.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    external_wrenches: HashMap<FrameID, Wrench>,
 }
 
 impl MotionModel {
@@ -523,6 +1158,7 @@ impl MotionModel {
     ///
     /// * [Error::MissingFrameElement] - Returned when the parent [ReferenceFrame] is not part of the model.
     /// * [Error::InvalidFrameID] - Returned the parent [ReferenceFrame] is connected to a wheel.
+    #[allow(clippy::too_many_arguments)]
     pub fn add_actuated_chassis_element(
         &mut self,
         name: String,
@@ -541,9 +1177,14 @@ impl MotionModel {
             return Err(Error::InvalidFrameID { id: parent_id });
         }
 
-        let reference_frame = ReferenceFrame::new(name.clone(), degree_of_freedom, true);
+        let reference_frame = ReferenceFrame::new_with_role(
+            name.clone(),
+            degree_of_freedom,
+            true,
+            FrameRole::Actuated,
+        );
 
-        self.actuators.insert(*reference_frame.id(), actuator);
+        self.insert_actuator(*reference_frame.id(), actuator);
 
         self.add_element_unchecked(
             reference_frame,
@@ -597,7 +1238,12 @@ impl MotionModel {
             return Err(Error::InvalidFrameID { id: body_id });
         }
 
-        let reference_frame = ReferenceFrame::new(name.clone(), FrameDofType::Static, false);
+        let reference_frame = ReferenceFrame::new_with_role(
+            name.clone(),
+            FrameDofType::Static,
+            false,
+            FrameRole::Body,
+        );
 
         self.add_element_unchecked(
             reference_frame,
@@ -631,7 +1277,7 @@ impl MotionModel {
     /// ## Errors
     ///
     /// This method assumes everything has been checked. If something is wrong it will panic.
-    fn add_element_unchecked(
+    pub(super) fn add_element_unchecked(
         &mut self,
         reference_frame: ReferenceFrame,
         parent_id: FrameID,
@@ -640,12 +1286,14 @@ impl MotionModel {
         name: String,
         physical_properties: ChassisElementPhysicalProperties,
     ) -> Result<FrameID, Error> {
+        self.audit_new_frame(&reference_frame, &orientation_relative_to_parent)?;
         let id = self.reference_frames.add_element(
             reference_frame,
             parent_id,
             position_relative_to_parent,
             orientation_relative_to_parent,
         )?;
+        event!(DEBUG, frame = %id, name = name.as_str(), "Added a frame to the model");
 
         let element = ChassisElement::new(
             name,
@@ -655,9 +1303,194 @@ impl MotionModel {
             physical_properties.spatial_inertia,
             *id,
         );
-        self.chassis_elements.insert(*id, element);
+        let id = *id;
+        self.chassis_elements.insert(id, element);
+        self.invalidate(ModelChange::FrameAdded { frame: id });
+
+        Ok(id)
+    }
+
+    /// Adds a [CommandMiddleware] to the end of the chain of middleware that processes the
+    /// commands for the actuator of the given reference frame.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'frame_id' - The [FrameID] of the element.
+    /// * 'middleware' - The middleware that should be added.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the [ReferenceFrame] is not part of the model.
+    /// * [Error::InvalidFrameID] - Returned when the [ReferenceFrame] has no actuator.
+    pub fn add_command_middleware(
+        &mut self,
+        frame_id: &FrameID,
+        middleware: impl CommandMiddleware + 'static,
+    ) -> Result<(), Error> {
+        if !self.reference_frames.has_element(frame_id) {
+            return Err(Error::MissingFrameElement { id: *frame_id });
+        }
+
+        match self.actuators.get_mut(frame_id) {
+            Some(actuator) => {
+                actuator.add_command_middleware(middleware);
+                Ok(())
+            }
+            None => Err(Error::InvalidFrameID { id: *frame_id }),
+        }
+    }
+
+    /// Adds an alias name for the given reference frame, e.g. the name of the frame in another
+    /// system. A reference frame may have any number of aliases but each alias refers to a
+    /// single reference frame.
+    ///
+    /// The model does not interpret the aliases. Prefixing the alias with the name of the naming
+    /// scheme, e.g. `tf:front_left_wheel` or `can:0x21`, keeps the aliases of different systems
+    /// apart. The aliases of a frame are removed when the frame is removed from the model.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'frame_id' - The [FrameID] of the element.
+    /// * 'alias' - The alias name of the element.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the [ReferenceFrame] is not part of the model.
+    /// * [Error::DuplicateFrameAlias] - Returned when the alias already refers to another
+    ///   [ReferenceFrame].
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use nalgebra::{Matrix3, Translation3, UnitQuaternion, Vector3};
+    /// use swerve_vehicle_descriptors::model_elements::model::{
+    ///     ChassisElementPhysicalProperties, MotionModel,
+    /// };
+    ///
+    /// let mut model = MotionModel::new();
+    /// let body = model
+    ///     .add_body(
+    ///         "body".to_string(),
+    ///         Translation3::identity(),
+    ///         UnitQuaternion::identity(),
+    ///         ChassisElementPhysicalProperties::from_mass_properties(
+    ///             1.0,
+    ///             Vector3::zeros(),
+    ///             Matrix3::identity(),
+    ///         ),
+    ///     )
+    ///     .unwrap();
+    ///
+    /// model.add_frame_alias(&body, "tf:base_link".to_string()).unwrap();
+    /// assert_eq!(Some(&body), model.frame_by_alias("tf:base_link"));
+    /// assert_eq!(vec!["tf:base_link"], model.frame_aliases(&body).unwrap());
+    /// ```
+    pub fn add_frame_alias(&mut self, frame_id: &FrameID, alias: String) -> Result<(), Error> {
+        if !self.reference_frames.has_element(frame_id) {
+            return Err(Error::MissingFrameElement { id: *frame_id });
+        }
+
+        match self.frame_aliases.get(&alias) {
+            Some(existing) if existing != frame_id => Err(Error::DuplicateFrameAlias { alias }),
+            _ => {
+                self.frame_aliases.insert(alias, *frame_id);
+                Ok(())
+            }
+        }
+    }
+
+    /// Adds a [JointSensor] for the given reference frame. The sensor tracks the state of the
+    /// joint between the reference frame and its parent, e.g. for a joint that is not actuated
+    /// or for an external encoder on an actuated joint.
+    ///
+    /// The number space of the sensor should match the degree of freedom of the reference frame.
+    /// Revolute joints accept both angular and linear number spaces, the latter for sensors that
+    /// track the total rotation of the joint. Prismatic joints only accept linear number spaces.
+    /// A sensor with a limited linear number space should cover the full travel of the joint,
+    /// as given by the position limits of the [JointConstraint].
+    ///
+    /// ## Parameters
+    ///
+    /// * 'frame_id' - The [FrameID] of the element.
+    /// * 'sensor' - The sensor that tracks the state of the joint.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the [ReferenceFrame] is not part of the model.
+    /// * [Error::InvalidFrameID] - Returned when the [ReferenceFrame] can not move relative to
+    ///   its parent.
+    /// * [Error::IncompatibleNumberSpace] - Returned when the number space of the sensor does
+    ///   not match the degree of freedom of the [ReferenceFrame], or when the limits of a
+    ///   [NumberSpaceType::LinearLimited] number space do not cover the position limits of the
+    ///   [JointConstraint] of the [ReferenceFrame].
+    /// * [Error::FrameElementAlreadyExists] - Returned when the [ReferenceFrame] already has
+    ///   a sensor.
+    pub fn add_joint_sensor(
+        &mut self,
+        frame_id: &FrameID,
+        sensor: JointSensor,
+    ) -> Result<(), Error> {
+        self.verify_sensor_compatibility(frame_id, &sensor)?;
+        if self.sensors.contains_key(frame_id) {
+            return Err(Error::FrameElementAlreadyExists { id: *frame_id });
+        }
+
+        self.insert_sensor(*frame_id, sensor);
+        self.invalidate(ModelChange::SensorChanged { frame: *frame_id });
+        Ok(())
+    }
+
+    /// Adds an operating mode to the model. If the model already has an operating mode with
+    /// the same name it is replaced.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'mode' - The operating mode.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the operating mode has a suspension
+    ///   stiffness set-point for a [ReferenceFrame] that is not part of the model.
+    pub fn add_operating_mode(&mut self, mode: OperatingMode) -> Result<(), Error> {
+        if let Some(id) = mode
+            .suspension_frames()
+            .find(|id| !self.reference_frames.has_element(id))
+        {
+            return Err(Error::MissingFrameElement { id: *id });
+        }
+
+        self.operating_modes.insert(mode.name().to_string(), mode);
+        Ok(())
+    }
+
+    /// Adds a [StateMiddleware] to the end of the chain of middleware that processes the
+    /// states that the actuator of the given reference frame receives from the hardware.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'frame_id' - The [FrameID] of the element.
+    /// * 'middleware' - The middleware that should be added.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the [ReferenceFrame] is not part of the model.
+    /// * [Error::InvalidFrameID] - Returned when the [ReferenceFrame] has no actuator.
+    pub fn add_state_middleware(
+        &mut self,
+        frame_id: &FrameID,
+        middleware: impl StateMiddleware + 'static,
+    ) -> Result<(), Error> {
+        if !self.reference_frames.has_element(frame_id) {
+            return Err(Error::MissingFrameElement { id: *frame_id });
+        }
 
-        Ok(*id)
+        match self.actuators.get_mut(frame_id) {
+            Some(actuator) => {
+                actuator.add_state_middleware(middleware);
+                Ok(())
+            }
+            None => Err(Error::InvalidFrameID { id: *frame_id }),
+        }
     }
 
     /// Adds the chassis element that represents a static joint for the robot.
@@ -702,7 +1535,12 @@ impl MotionModel {
             return Err(Error::InvalidFrameID { id: parent_id });
         }
 
-        let reference_frame = ReferenceFrame::new(name.clone(), FrameDofType::Static, false);
+        let reference_frame = ReferenceFrame::new_with_role(
+            name.clone(),
+            FrameDofType::Static,
+            false,
+            FrameRole::Attachment,
+        );
 
         self.add_element_unchecked(
             reference_frame,
@@ -758,27 +1596,112 @@ impl MotionModel {
         physical_properties: ChassisElementPhysicalProperties,
         actuator: Actuator,
     ) -> Result<FrameID, Error> {
-        if !self.reference_frames.has_element(&parent_id) {
-            return Err(Error::MissingFrameElement { id: parent_id });
-        }
+        self.add_steering_element_with_optional_actuator(
+            name,
+            FrameDofType::RevoluteZ,
+            parent_id,
+            position_relative_to_parent,
+            orientation_relative_to_parent,
+            physical_properties,
+            Some(actuator),
+        )
+    }
 
-        if self.reference_frames.is_wheel(&parent_id)? {
-            return Err(Error::InvalidFrameID { id: parent_id });
+    /// Adds a steering element that rotates around the given axis of its parent frame, e.g. for
+    /// a module with a kingpin inclination where the frame of the parent is not aligned with
+    /// the steering axis. See [MotionModel::add_steering_element] for steering elements that
+    /// rotate around the z-axis.
+    ///
+    /// The element is tracked as the steering frame of the chain in the same way as an element
+    /// added with [MotionModel::add_steering_element].
+    ///
+    /// ## Parameters
+    ///
+    /// * 'name' - The name of the new chassis element
+    /// * 'degree_of_freedom' - The revolute degree of freedom of the steering joint
+    /// * 'parent_id' - The ID of the parent reference frame
+    /// * 'position_relative_to_parent' - The position of the element relative to the parent
+    ///   reference frame
+    /// * 'orientation_relative_to_parent' - The orientation of the element relative to the parent
+    ///   reference frame
+    /// * 'physical_properties' - The mass and inertia of the element
+    /// * 'actuator' - The actuator and its controller for the joint
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::InvalidSteeringDegreeOfFreedom] - Returned when the degree of freedom is not
+    ///   revolute.
+    /// * [Error::MissingFrameElement] - Returned when the parent [ReferenceFrame] is not part of the model.
+    /// * [Error::InvalidFrameID] - Returned the parent [ReferenceFrame] is connected to a wheel.
+    /// * [Error::MultipleSteeringFramesInChain] - Returned when there is already a steering frame
+    ///   in the chain of parent frames
+    #[allow(clippy::too_many_arguments)]
+    pub fn add_steering_element_with_degree_of_freedom(
+        &mut self,
+        name: String,
+        degree_of_freedom: FrameDofType,
+        parent_id: FrameID,
+        position_relative_to_parent: Translation3<f64>,
+        orientation_relative_to_parent: UnitQuaternion<f64>,
+        physical_properties: ChassisElementPhysicalProperties,
+        actuator: Actuator,
+    ) -> Result<FrameID, Error> {
+        self.add_steering_element_with_optional_actuator(
+            name,
+            degree_of_freedom,
+            parent_id,
+            position_relative_to_parent,
+            orientation_relative_to_parent,
+            physical_properties,
+            Some(actuator),
+        )
+    }
+
+    /// Adds a steering element to the model. The actuator for the element can be provided
+    /// later with [MotionModel::set_actuator].
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn add_steering_element_with_optional_actuator(
+        &mut self,
+        name: String,
+        degree_of_freedom: FrameDofType,
+        parent_id: FrameID,
+        position_relative_to_parent: Translation3<f64>,
+        orientation_relative_to_parent: UnitQuaternion<f64>,
+        physical_properties: ChassisElementPhysicalProperties,
+        actuator: Option<Actuator>,
+    ) -> Result<FrameID, Error> {
+        if !degree_of_freedom.is_revolute() {
+            return Err(Error::InvalidSteeringDegreeOfFreedom { degree_of_freedom });
+        }
+
+        if !self.reference_frames.has_element(&parent_id) {
+            return Err(Error::MissingFrameElement { id: parent_id });
+        }
+
+        if self.reference_frames.is_wheel(&parent_id)? {
+            return Err(Error::InvalidFrameID { id: parent_id });
         }
 
         // There should only be one steering element in the chain
         let mut element_in_chain = &parent_id;
         while !self.is_body(element_in_chain) {
-            if self.steering_frame_to_wheel.contains_key(element_in_chain) {
+            if self.is_steering_frame(element_in_chain) {
                 return Err(Error::MultipleSteeringFramesInChain { id: parent_id });
             }
 
             element_in_chain = self.parent_of(element_in_chain)?;
         }
 
-        let reference_frame = ReferenceFrame::new(name.clone(), FrameDofType::RevoluteZ, true);
+        let reference_frame = ReferenceFrame::new_with_role(
+            name.clone(),
+            degree_of_freedom,
+            true,
+            FrameRole::Steering,
+        );
 
-        self.actuators.insert(*reference_frame.id(), actuator);
+        if let Some(actuator) = actuator {
+            self.insert_actuator(*reference_frame.id(), actuator);
+        }
 
         self.steering_frame_to_wheel
             .insert(*reference_frame.id(), FrameID::none());
@@ -822,6 +1745,7 @@ impl MotionModel {
     ///
     /// * [Error::MissingFrameElement] - Returned when the parent [ReferenceFrame] is not part of the model.
     /// * [Error::InvalidFrameID] - Returned the parent [ReferenceFrame] is connected to a wheel.
+    #[allow(clippy::too_many_arguments)]
     pub fn add_suspension_element(
         &mut self,
         name: String,
@@ -840,7 +1764,12 @@ impl MotionModel {
             return Err(Error::InvalidFrameID { id: parent_id });
         }
 
-        let reference_frame = ReferenceFrame::new(name.clone(), degree_of_freedom, false);
+        let reference_frame = ReferenceFrame::new_with_role(
+            name.clone(),
+            degree_of_freedom,
+            false,
+            FrameRole::Suspension,
+        );
 
         self.joint_constraints
             .insert(*reference_frame.id(), joint_constraint);
@@ -897,6 +1826,74 @@ impl MotionModel {
         orientation_relative_to_parent: UnitQuaternion<f64>,
         physical_properties: ChassisElementPhysicalProperties,
         actuator: Actuator,
+    ) -> Result<FrameID, Error> {
+        self.add_wheel_element(
+            name,
+            parent_id,
+            position_relative_to_parent,
+            orientation_relative_to_parent,
+            physical_properties,
+            Some(actuator),
+            None,
+        )
+    }
+
+    /// Adds a new wheel element, with the given [WheelProperties], to the robot.
+    ///
+    /// See [MotionModel::add_wheel] for a description of the way the wheel is connected to its
+    /// parent.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'name' - The name of the new wheel element
+    /// * 'parent_id' - The ID of the parent reference frame
+    /// * 'position_relative_to_parent' - The position of the element relative to the parent
+    ///   reference frame
+    /// * 'orientation_relative_to_parent' - The orientation of the element relative to the parent
+    ///   reference frame
+    /// * 'physical_properties' - The mass and inertia of the element
+    /// * 'actuator' - A reference to the actuator and its controller for the joint
+    /// * 'wheel_properties' - The geometry of the wheel
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the parent [ReferenceFrame] is not part of the model.
+    /// * [Error::NoSteeringFramesInChain] - Returned when the parent [ReferenceFrame] is not part of the model.
+    /// * [Error::InvalidFrameID] - Returned the parent [ReferenceFrame] is connected to a wheel.
+    #[allow(clippy::too_many_arguments)]
+    pub fn add_wheel_with_properties(
+        &mut self,
+        name: String,
+        parent_id: FrameID,
+        position_relative_to_parent: Translation3<f64>,
+        orientation_relative_to_parent: UnitQuaternion<f64>,
+        physical_properties: ChassisElementPhysicalProperties,
+        actuator: Actuator,
+        wheel_properties: WheelProperties,
+    ) -> Result<FrameID, Error> {
+        self.add_wheel_element(
+            name,
+            parent_id,
+            position_relative_to_parent,
+            orientation_relative_to_parent,
+            physical_properties,
+            Some(actuator),
+            Some(wheel_properties),
+        )
+    }
+
+    /// Adds a new wheel element to the robot, with or without [WheelProperties]. The actuator
+    /// for the element can be provided later with [MotionModel::set_actuator].
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn add_wheel_element(
+        &mut self,
+        name: String,
+        parent_id: FrameID,
+        position_relative_to_parent: Translation3<f64>,
+        orientation_relative_to_parent: UnitQuaternion<f64>,
+        physical_properties: ChassisElementPhysicalProperties,
+        actuator: Option<Actuator>,
+        wheel_properties: Option<WheelProperties>,
     ) -> Result<FrameID, Error> {
         if !self.reference_frames.has_element(&parent_id) {
             return Err(Error::MissingFrameElement { id: parent_id });
@@ -910,7 +1907,7 @@ impl MotionModel {
         let mut element_in_chain = &parent_id;
         let mut steering_frame_id = FrameID::none();
         while !self.is_body(element_in_chain) {
-            if self.steering_frame_to_wheel.contains_key(element_in_chain) {
+            if self.is_steering_frame(element_in_chain) {
                 steering_frame_id = *element_in_chain;
                 break;
             }
@@ -922,9 +1919,16 @@ impl MotionModel {
             return Err(Error::NoSteeringFramesInChain { id: parent_id });
         }
 
-        let reference_frame = ReferenceFrame::new(name.clone(), FrameDofType::RevoluteY, true);
+        let reference_frame = ReferenceFrame::new_with_role(
+            name.clone(),
+            FrameDofType::RevoluteY,
+            true,
+            FrameRole::Wheel,
+        );
 
-        self.actuators.insert(*reference_frame.id(), actuator);
+        if let Some(actuator) = actuator {
+            self.insert_actuator(*reference_frame.id(), actuator);
+        }
 
         self.steering_frame_to_wheel
             .insert(steering_frame_id, *reference_frame.id());
@@ -932,6 +1936,11 @@ impl MotionModel {
         self.wheel_to_steering_frame
             .insert(*reference_frame.id(), steering_frame_id);
 
+        if let Some(properties) = wheel_properties {
+            self.wheel_properties
+                .insert(*reference_frame.id(), properties);
+        }
+
         self.add_element_unchecked(
             reference_frame,
             parent_id,
@@ -967,6 +1976,43 @@ impl MotionModel {
         }
     }
 
+    /// Returns the health of the actuators of all the actuated joints, ordered by [FrameID],
+    /// so that supervisory code can react to a failed actuator, e.g. by stopping the vehicle
+    /// when a steering motor is disconnected. See [Actuator::health].
+    pub fn actuator_health(&self) -> BTreeMap<FrameID, ActuatorHealth> {
+        self.actuators
+            .iter()
+            .map(|(id, actuator)| (*id, actuator.health()))
+            .collect()
+    }
+
+    /// Attaches an external [Wrench] to the given reference frame, replacing the wrench that
+    /// was attached before. Returns the wrench that was replaced, or [None] if the reference
+    /// frame had no wrench.
+    ///
+    /// The wrench acts on the origin of the reference frame and is expressed along the axes of
+    /// the reference frame, e.g. the weight of a payload on a mounting frame.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'frame_id' - The [FrameID] of the element.
+    /// * 'wrench' - The external force and torque.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the [ReferenceFrame] is not part of the model.
+    pub fn apply_wrench(
+        &mut self,
+        frame_id: &FrameID,
+        wrench: Wrench,
+    ) -> Result<Option<Wrench>, Error> {
+        if !self.reference_frames.has_element(frame_id) {
+            return Err(Error::MissingFrameElement { id: *frame_id });
+        }
+
+        Ok(self.external_wrenches.insert(*frame_id, wrench))
+    }
+
     /// Returns the [FrameID] of the body element.
     ///
     /// ## Errors
@@ -983,6 +2029,102 @@ impl MotionModel {
         Ok(frame.id())
     }
 
+    /// Returns the pose of the body in the world frame, i.e. the transform that turns coordinates
+    /// in the body frame into coordinates in the world frame.
+    ///
+    /// The pose is the identity until it is set with [MotionModel::set_body_pose_in_world].
+    pub fn body_pose_in_world(&self) -> &Isometry3<f64> {
+        &self.body_pose_in_world
+    }
+
+    /// Returns the [JointCalibration] of the hardware of the given joint. Joints without a
+    /// calibration use the identity calibration.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'frame_id' - The [FrameID] of the element.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the [ReferenceFrame] is not part of the model.
+    pub fn calibration(&self, frame_id: &FrameID) -> Result<JointCalibration, Error> {
+        if !self.reference_frames.has_element(frame_id) {
+            return Err(Error::MissingFrameElement { id: *frame_id });
+        }
+
+        Ok(self
+            .joint_calibrations
+            .get(frame_id)
+            .copied()
+            .unwrap_or_default())
+    }
+
+    /// Returns the chain of frames that connects the two given frames, starting at 'from' and
+    /// ending at 'to'. The chain runs from 'from' up to the closest common ancestor of the two
+    /// frames and then down to 'to'. Both frames are included in the chain.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'from' - The [FrameID] of the first frame of the chain.
+    /// * 'to' - The [FrameID] of the last frame of the chain.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when either [ReferenceFrame] is not part of the
+    ///   model.
+    /// * [Error::InvalidFrameID] - Returned when either [ReferenceFrame] is not connected to the
+    ///   body.
+    pub fn chain_between(&self, from: &FrameID, to: &FrameID) -> Result<Vec<&FrameID>, Error> {
+        let mut from_chain = self.chain_to_body(from)?;
+        let mut to_chain = self.chain_to_body(to)?;
+
+        // Both chains end at the body, so strip the shared ancestors from the end of the chains
+        // until only the closest common ancestor is left.
+        while from_chain.len() > 1
+            && to_chain.len() > 1
+            && from_chain[from_chain.len() - 2] == to_chain[to_chain.len() - 2]
+        {
+            from_chain.pop();
+            to_chain.pop();
+        }
+
+        to_chain.pop();
+        from_chain.extend(to_chain.into_iter().rev());
+        Ok(from_chain)
+    }
+
+    /// Returns the chain of frames from the given frame to the body, i.e. the given frame
+    /// followed by its parent, the parent of the parent and so on. The last frame of the chain
+    /// is the body.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'frame_id' - The [FrameID] of the first frame of the chain.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the [ReferenceFrame] is not part of the model.
+    /// * [Error::InvalidFrameID] - Returned when the [ReferenceFrame] is not connected to the body.
+    pub fn chain_to_body(&self, frame_id: &FrameID) -> Result<Vec<&FrameID>, Error> {
+        let mut current = self.reference_frame(frame_id)?.id();
+        let mut chain = vec![current];
+
+        // A chain that is longer than the number of elements contains a cycle
+        while !self.is_body(current) {
+            if chain.len() > self.reference_frames.elements.len() {
+                return Err(Error::InvalidFrameID { id: *frame_id });
+            }
+
+            current = match self.reference_frames.parent_of(current) {
+                Ok(parent) => parent.id(),
+                Err(_) => return Err(Error::InvalidFrameID { id: *frame_id }),
+            };
+            chain.push(current);
+        }
+
+        Ok(chain)
+    }
+
     /// Returns the [ChassisElement] for a given joint
     ///
     /// ## Parameters
@@ -999,6 +2141,13 @@ impl MotionModel {
         }
     }
 
+    /// Returns an iterator over all the [ChassisElement] instances in the model.
+    ///
+    /// The order of iteration is not guaranteed.
+    pub fn chassis_elements(&self) -> impl Iterator<Item = &ChassisElement> {
+        self.chassis_elements.values()
+    }
+
     /// Returns the collection containing all the [FrameID] of the child elements of the
     /// element with the given ID.
     ///
@@ -1022,6 +2171,26 @@ impl MotionModel {
         Ok(child_ids)
     }
 
+    /// Deactivates the active operating mode, if any.
+    pub fn clear_operating_mode(&mut self) {
+        self.active_operating_mode = None;
+    }
+
+    /// Returns the external [Wrench] that is attached to the given reference frame, or [None]
+    /// if the reference frame has no wrench.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'frame_id' - The [FrameID] of the element.
+    pub fn external_wrench(&self, frame_id: &FrameID) -> Option<&Wrench> {
+        self.external_wrenches.get(frame_id)
+    }
+
+    /// Returns the reference frames that have an external [Wrench] together with their wrench.
+    pub fn external_wrenches(&self) -> impl Iterator<Item = (&FrameID, &Wrench)> {
+        self.external_wrenches.iter()
+    }
+
     /// Returns the [FrameDofType] for the given frame
     ///
     /// ## Parameters
@@ -1040,91 +2209,2230 @@ impl MotionModel {
         Ok(frame.degree_of_freedom_kind())
     }
 
-    /// Returns the homogeneous transform matrix from the given reference frame to the
-    /// destination frame, taking into account the current position and orientation of the
-    /// frame relative to the destination frame.
+    /// Returns the aliases of the given reference frame, ordered by name.
     ///
     /// ## Parameters
     ///
-    /// * 'from' - The source element for which the transform is requested
-    /// * 'to' - The target element
+    /// * 'frame_id' - The [FrameID] of the element.
     ///
     /// ## Errors
     ///
-    pub fn homogeneous_transform_between_frames(
-        &self,
-        from: &FrameID,
-        to: &FrameID,
-    ) -> Result<Matrix4<f64>, Error> {
-        if !self.reference_frames.has_element(from) {
-            return Err(Error::MissingFrameElement { id: *from });
+    /// * [Error::MissingFrameElement] - Returned when the [ReferenceFrame] is not part of the model.
+    pub fn frame_aliases(&self, frame_id: &FrameID) -> Result<Vec<&str>, Error> {
+        if !self.reference_frames.has_element(frame_id) {
+            return Err(Error::MissingFrameElement { id: *frame_id });
         }
 
-        if !self.reference_frames.has_element(to) {
-            return Err(Error::MissingFrameElement { id: *to });
-        }
+        Ok(self
+            .frame_aliases
+            .iter()
+            .filter(|(_, id)| *id == frame_id)
+            .map(|(alias, _)| alias.as_str())
+            .collect())
+    }
 
-        if from == to {
-            return Ok(Matrix4::<f64>::identity());
-        }
+    /// Returns the reference frame that has the given alias, or [None] if no reference frame
+    /// has the alias. See [MotionModel::add_frame_alias].
+    ///
+    /// ## Parameters
+    ///
+    /// * 'alias' - The alias name of the element.
+    pub fn frame_by_alias(&self, alias: &str) -> Option<&FrameID> {
+        self.frame_aliases.get(alias)
+    }
 
-        // If 'to' is an ancestor then we can just calculate the stack
-        if self.is_ancestor(from, to) {
-            return self.homogeneous_transform_to_ancestor(from, to);
+    /// Returns the value that is stored for the given key in the metadata of the given reference
+    /// frame, or [None] if no value is stored for the key.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'frame_id' - The [FrameID] of the element.
+    /// * 'key' - The key of the metadata.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the [ReferenceFrame] is not part of the model.
+    pub fn frame_metadata(&self, frame_id: &FrameID, key: &str) -> Result<Option<&str>, Error> {
+        if !self.reference_frames.has_element(frame_id) {
+            return Err(Error::MissingFrameElement { id: *frame_id });
         }
 
-        // 'to' is a sibbling. Calculate both stacks and invert the sibbling stack
-        let from_transform_to_body = self.homogeneous_transform_to_body(from)?;
-        let mut to_transform_to_body = self.homogeneous_transform_to_body(to)?;
+        Ok(self
+            .frame_metadata
+            .get(frame_id)
+            .and_then(|metadata| metadata.get(key))
+            .map(|value| value.as_str()))
+    }
 
-        // Invert the to transform
-        let invert_result = to_transform_to_body.try_inverse_mut();
-        if !invert_result {
-            // This really shouldn't happen because homogeneous transforms should be invertible. So now we're in trouble ....
-            return Err(Error::FailedToComputeTransform {
-                from: *self.body()?,
-                to: *to,
-            });
+    /// Returns the key-value pairs in the metadata of the given reference frame, ordered by key.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'frame_id' - The [FrameID] of the element.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the [ReferenceFrame] is not part of the model.
+    pub fn frame_metadata_entries(&self, frame_id: &FrameID) -> Result<Vec<(&str, &str)>, Error> {
+        if !self.reference_frames.has_element(frame_id) {
+            return Err(Error::MissingFrameElement { id: *frame_id });
         }
 
-        Ok(to_transform_to_body * from_transform_to_body)
+        Ok(self
+            .frame_metadata
+            .get(frame_id)
+            .map(|metadata| {
+                metadata
+                    .iter()
+                    .map(|(key, value)| (key.as_str(), value.as_str()))
+                    .collect()
+            })
+            .unwrap_or_default())
     }
 
-    /// Returns the homogeneous transform matrix from the given reference frame to the
-    /// a parent element further up the chain, taking into account the current position and
-    /// orientation of the frame relative to the parent frame.
-    ///
-    /// It is assumed that the parent frame is in the chain from the 'from' element to the
-    /// body.
+    /// Returns the [FrameRole] for the given frame
     ///
     /// ## Parameters
     ///
-    /// * 'from' - The source element for which the transform is requested
-    /// * 'to' - The target parent element.
+    /// * 'frame_id' - The [FrameID] of the element from which the [FrameRole] should be returned.
     ///
     /// ## Errors
     ///
-    /// * [Error::MissingFrameElement] - Returned when the [ReferenceFrame] is not part of the model
-    pub fn homogeneous_transform_to_ancestor(
-        &self,
-        from: &FrameID,
-        to: &FrameID,
-    ) -> Result<Matrix4<f64>, Error> {
-        if !self.reference_frames.has_element(from) {
-            return Err(Error::MissingFrameElement { id: *from });
-        }
-
-        if !self.reference_frames.has_element(to) {
-            return Err(Error::MissingFrameElement { id: *to });
+    /// * [Error::MissingFrameElement] - Returned when the [ReferenceFrame] is not part of the model.
+    pub fn frame_role(&self, frame_id: &FrameID) -> Result<FrameRole, Error> {
+        if !self.reference_frames.has_element(frame_id) {
+            return Err(Error::MissingFrameElement { id: *frame_id });
         }
 
-        if from == to {
-            return Ok(Matrix4::<f64>::identity());
-        }
+        let frame = self.reference_frames.element(frame_id)?;
+        Ok(frame.role())
+    }
 
-        let mut transform = Matrix4::<f64>::identity();
-        let mut parent_element = self.reference_frames.parent_of(from)?;
-        let mut child_element = self.reference_frames.element(from)?;
+    /// Returns a list of [FrameID] of all the frames that have the given key in their metadata,
+    /// ordered by [FrameID]. When a value is given only the frames that store that value for the
+    /// key are returned.
+    ///
+    /// Frames can be tagged by storing an empty value for the tag, e.g.
+    ///
+    /// ```
+    /// use nalgebra::{Matrix3, Translation3, UnitQuaternion, Vector3};
+    /// use swerve_vehicle_descriptors::model_elements::model::{
+    ///     ChassisElementPhysicalProperties, MotionModel,
+    /// };
+    ///
+    /// let properties = ChassisElementPhysicalProperties::from_mass_properties(
+    ///     1.0,
+    ///     Vector3::zeros(),
+    ///     Matrix3::identity(),
+    /// );
+    ///
+    /// let mut model = MotionModel::new();
+    /// let body = model
+    ///     .add_body(
+    ///         "body".to_string(),
+    ///         Translation3::identity(),
+    ///         UnitQuaternion::identity(),
+    ///         properties,
+    ///     )
+    ///     .unwrap();
+    /// let bumper = model
+    ///     .add_static_chassis_element(
+    ///         "front_bumper".to_string(),
+    ///         body,
+    ///         Translation3::new(0.5, 0.0, 0.0),
+    ///         UnitQuaternion::identity(),
+    ///         properties,
+    ///     )
+    ///     .unwrap();
+    ///
+    /// model.set_frame_metadata(&bumper, "bumper".to_string(), String::new()).unwrap();
+    /// model.set_frame_metadata(&bumper, "side".to_string(), "front".to_string()).unwrap();
+    ///
+    /// assert_eq!(vec![&bumper], model.frames_with_metadata("bumper", None));
+    /// assert_eq!(vec![&bumper], model.frames_with_metadata("side", Some("front")));
+    /// assert!(model.frames_with_metadata("side", Some("rear")).is_empty());
+    /// ```
+    ///
+    /// ## Parameters
+    ///
+    /// * 'key' - The key of the metadata.
+    /// * 'value' - The value that should be stored for the key, or [None] to return the frames
+    ///   with any value for the key.
+    pub fn frames_with_metadata(&self, key: &str, value: Option<&str>) -> Vec<&FrameID> {
+        let mut list: Vec<&FrameID> = self
+            .frame_metadata
+            .iter()
+            .filter(|(_, metadata)| match (metadata.get(key), value) {
+                (Some(stored), Some(value)) => stored == value,
+                (Some(_), None) => true,
+                (None, _) => false,
+            })
+            .map(|(id, _)| id)
+            .collect();
+        list.sort();
+        list
+    }
+
+    /// Returns a list of [FrameID] of all the frames with the given [FrameRole], ordered by
+    /// [FrameID].
+    ///
+    /// ## Parameters
+    ///
+    /// * 'role' - The role of the frames that should be returned.
+    pub fn frames_with_role(&self, role: FrameRole) -> Vec<&FrameID> {
+        let mut list: Vec<&FrameID> = self
+            .reference_frames
+            .elements()
+            .filter(|f| f.role() == role)
+            .map(|f| f.id())
+            .collect();
+        list.sort();
+        list
+    }
+
+    /// Returns a [Receiver] that receives a [GroundContactEvent] each time a wheel loses or
+    /// regains contact with the ground.
+    ///
+    /// The ground contact state of the wheels is updated by calling
+    /// [MotionModel::update_ground_contact].
+    pub fn ground_contact_events(&mut self) -> Receiver<GroundContactEvent> {
+        let (sender, receiver) = crossbeam_channel::unbounded();
+        self.ground_contact_senders.push(sender);
+        receiver
+    }
+
+    /// Returns the acceleration due to gravity, in meters per second squared, in the world
+    /// frame. Defaults to the standard gravity along the negative z-axis.
+    pub fn gravity(&self) -> &Vector3<f64> {
+        &self.gravity
+    }
+
+    /// Returns the acceleration due to gravity, in meters per second squared, along the axes of
+    /// the body reference frame, based on the current orientation of the body in the world, see
+    /// [MotionModel::body_orientation_in_world].
+    pub fn gravity_in_body(&self) -> Vector3<f64> {
+        self.body_orientation_in_world().inverse() * self.gravity
+    }
+
+    /// Returns the thresholds used to determine if a wheel is in contact with the ground.
+    pub fn ground_contact_thresholds(&self) -> &GroundContactThresholds {
+        &self.ground_contact_thresholds
+    }
+
+    /// Returns the homogeneous transform matrix from the given reference frame to the
+    /// destination frame, taking into account the current position and orientation of the
+    /// frame relative to the destination frame.
+    ///
+    /// Use [MotionModel::isometry_between_frames] when the transform is only needed as a rigid-body
+    /// transform.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'from' - The source element for which the transform is requested
+    /// * 'to' - The target element
+    ///
+    /// ## Errors
+    ///
+    pub fn homogeneous_transform_between_frames(
+        &self,
+        from: &FrameID,
+        to: &FrameID,
+    ) -> Result<Matrix4<f64>, Error> {
+        self.isometry_between_frames(from, to)
+            .map(|transform| transform.to_homogeneous())
+    }
+
+    /// Returns the homogeneous transform matrix from the given reference frame to the
+    /// a parent element further up the chain, taking into account the current position and
+    /// orientation of the frame relative to the parent frame.
+    ///
+    /// It is assumed that the parent frame is in the chain from the 'from' element to the
+    /// body.
+    ///
+    /// Use [MotionModel::isometry_to_ancestor] when the transform is only needed as a rigid-body
+    /// transform.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'from' - The source element for which the transform is requested
+    /// * 'to' - The target parent element.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the [ReferenceFrame] is not part of the model
+    pub fn homogeneous_transform_to_ancestor(
+        &self,
+        from: &FrameID,
+        to: &FrameID,
+    ) -> Result<Matrix4<f64>, Error> {
+        self.homogeneous_transform_to_ancestor_with(from, to, |id| self.current_joint_position(id))
+    }
+
+    /// Returns the homogeneous transform matrix from the given reference frame to the
+    /// a parent element further up the chain, using the joint positions provided by the
+    /// 'joint_position' function.
+    ///
+    /// It is assumed that the parent frame is in the chain from the 'from' element to the
+    /// body.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'from' - The source element for which the transform is requested
+    /// * 'to' - The target parent element.
+    /// * 'joint_position' - Returns the position of the joint for a given [FrameID], or [None]
+    ///   if the joint should be assumed to be at its zero position.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the [ReferenceFrame] is not part of the model
+    pub(crate) fn homogeneous_transform_to_ancestor_with<F>(
+        &self,
+        from: &FrameID,
+        to: &FrameID,
+        joint_position: F,
+    ) -> Result<Matrix4<f64>, Error>
+    where
+        F: Fn(&FrameID) -> Option<f64>,
+    {
+        self.isometry_to_ancestor_with(from, to, joint_position)
+            .map(|transform| transform.to_homogeneous())
+    }
+
+    /// Returns the homogeneous transform matrix from the given reference frame to the
+    /// body frame, taking into account the current position and orientation of the
+    /// frame relative to the body frame.
+    ///
+    /// Use [MotionModel::isometry_to_body] when the transform is only needed as a rigid-body
+    /// transform.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'starting_element' - The source element for which the transform is requested
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the [ReferenceFrame] is not part of the model
+    pub fn homogeneous_transform_to_body(
+        &self,
+        starting_element: &FrameID,
+    ) -> Result<Matrix4<f64>, Error> {
+        self.isometry_to_body(starting_element)
+            .map(|transform| transform.to_homogeneous())
+    }
+
+    /// Returns the homogeneous transform matrix from the given reference frame to the body
+    /// frame at the given time, using the positions of the joints at that time as stored in
+    /// the history of the joints, see [MotionModel::joint_state_at].
+    ///
+    /// Use [MotionModel::isometry_to_body_at] when the transform is only needed as a rigid-body
+    /// transform.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'starting_element' - The source element for which the transform is requested
+    /// * 'time' - The time at which the transform is requested, e.g. the time at which a
+    ///   camera image was captured.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the [ReferenceFrame] is not part of the model
+    /// * [Error::MissingJointStateHistory] - Returned when the history of one of the joints
+    ///   between the frame and the body does not contain the given time.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use std::time::Instant;
+    /// use nalgebra::{Matrix3, Translation3, UnitQuaternion, Vector3};
+    /// use swerve_vehicle_descriptors::model_elements::model::{
+    ///     ChassisElementPhysicalProperties, MotionModel,
+    /// };
+    ///
+    /// let mut model = MotionModel::new();
+    /// let body = model
+    ///     .add_body(
+    ///         "body".to_string(),
+    ///         Translation3::identity(),
+    ///         UnitQuaternion::identity(),
+    ///         ChassisElementPhysicalProperties::from_mass_properties(
+    ///             1.0,
+    ///             Vector3::zeros(),
+    ///             Matrix3::identity(),
+    ///         ),
+    ///     )
+    ///     .unwrap();
+    /// let mount = model
+    ///     .add_static_chassis_element(
+    ///         "camera-mount".to_string(),
+    ///         body,
+    ///         Translation3::new(0.5, 0.0, 0.3),
+    ///         UnitQuaternion::identity(),
+    ///         ChassisElementPhysicalProperties::from_mass_properties(
+    ///             0.1,
+    ///             Vector3::zeros(),
+    ///             Matrix3::identity(),
+    ///         ),
+    ///     )
+    ///     .unwrap();
+    ///
+    /// // Frames without joints between them and the body do not need a history
+    /// let transform = model
+    ///     .homogeneous_transform_to_body_at(&mount, Instant::now())
+    ///     .unwrap();
+    /// assert_eq!(0.5, transform[(0, 3)]);
+    /// ```
+    pub fn homogeneous_transform_to_body_at(
+        &self,
+        starting_element: &FrameID,
+        time: Instant,
+    ) -> Result<Matrix4<f64>, Error> {
+        self.isometry_to_body_at(starting_element, time)
+            .map(|transform| transform.to_homogeneous())
+    }
+
+    /// Returns the homogeneous transform matrix from the given reference frame to the
+    /// parent frame, taking into account the current position and orientation of the
+    /// frame relative to the parent frame.
+    ///
+    /// Use [MotionModel::isometry_to_parent] when the transform is only needed as a rigid-body
+    /// transform.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'starting_element' - The source element for which the transform is requested
+    ///
+    /// ## Errors
+    ///
+    pub fn homogeneous_transform_to_parent(
+        &self,
+        starting_element: &FrameID,
+    ) -> Result<Matrix4<f64>, Error> {
+        self.isometry_to_parent(starting_element)
+            .map(|transform| transform.to_homogeneous())
+    }
+
+    /// Returns the homogeneous transform matrix from the given reference frame to the
+    /// world frame, taking into account the current position and orientation of the
+    /// frame relative to the body frame and the pose of the body in the world.
+    ///
+    /// Use [MotionModel::isometry_to_world] when the transform is only needed as a rigid-body
+    /// transform.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'starting_element' - The source element for which the transform is requested
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the [ReferenceFrame] is not part of the model
+    pub fn homogeneous_transform_to_world(
+        &self,
+        starting_element: &FrameID,
+    ) -> Result<Matrix4<f64>, Error> {
+        self.isometry_to_world(starting_element)
+            .map(|transform| transform.to_homogeneous())
+    }
+
+    /// Returns the [JointConstraint] for the given reference frame. Reference frames without a
+    /// constraint return a constraint that does not limit the joint.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'frame_id' - The [FrameID] of the element.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the [ReferenceFrame] is not part of the model.
+    pub fn joint_constraint(&self, frame_id: &FrameID) -> Result<JointConstraint, Error> {
+        if !self.reference_frames.has_element(frame_id) {
+            return Err(Error::MissingFrameElement { id: *frame_id });
+        }
+
+        Ok(self
+            .joint_constraints
+            .get(frame_id)
+            .copied()
+            .unwrap_or_default())
+    }
+
+    /// Returns the state of the joint for the given reference frame at the given time,
+    /// interpolated between the states in the history of the [Actuator] and the [JointSensor]
+    /// of the joint. This allows the joint states to be matched with the data of other sensors
+    /// that arrives with a delay, e.g. the images of a camera.
+    ///
+    /// The history of a joint is empty until a capacity is set with
+    /// [MotionModel::set_history_capacity]. When the joint has both an [Actuator] and a
+    /// [JointSensor] the two interpolated states are combined with the [StateFusion] of the
+    /// model. The newest state is returned for times after the newest state in the history.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'frame_id' - The [FrameID] of the element.
+    /// * 'time' - The time at which the state is required.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the joint has neither an actuator nor a
+    ///   sensor.
+    /// * [Error::MissingJointStateHistory] - Returned when the time is before the oldest state
+    ///   in the history of the joint, or when the history is empty.
+    pub fn joint_state_at(&self, frame_id: &FrameID, time: Instant) -> Result<JointState, Error> {
+        let missing = || Error::MissingJointStateHistory { id: *frame_id };
+        match (self.actuators.get(frame_id), self.sensors.get(frame_id)) {
+            (Some(actuator), Some(sensor)) => Ok(self.state_fusion.fuse(
+                actuator.state_at(time).ok_or_else(missing)?,
+                sensor.state_at(time).ok_or_else(missing)?,
+                actuator.numberspace(),
+            )),
+            (Some(actuator), None) => actuator.state_at(time).ok_or_else(missing),
+            (None, Some(sensor)) => sensor.state_at(time).ok_or_else(missing),
+            (None, None) => Err(Error::MissingFrameElement { id: *frame_id }),
+        }
+    }
+
+    /// Returns the current state of the joint for the given reference frame.
+    ///
+    /// When the joint has both an [Actuator] and a [JointSensor] the two states are combined
+    /// with the [StateFusion] of the model, see [MotionModel::set_state_fusion]. When the joint
+    /// only has one of the two the state of that one is returned.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'frame_id' - The [FrameID] of the element.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the joint has neither an actuator nor a
+    ///   sensor.
+    /// * [Error::FailedToReadActuatorJointState] - Returned when the state could not be read.
+    /// * [Error::NonFiniteJointState] - Returned, in audit mode, when a value of the state is not
+    ///   finite.
+    pub fn joint_state_for(&self, frame_id: &FrameID) -> Result<JointState, Error> {
+        let state = match (self.actuators.get(frame_id), self.sensors.get(frame_id)) {
+            (Some(actuator), Some(sensor)) => {
+                self.state_fusion
+                    .fuse(actuator.value()?, sensor.value()?, actuator.numberspace())
+            }
+            (Some(actuator), None) => actuator.value()?,
+            (None, Some(sensor)) => sensor.value()?,
+            (None, None) => return Err(Error::MissingFrameElement { id: *frame_id }),
+        };
+
+        self.audit_joint_state(frame_id, state)
+    }
+
+    /// Returns the [KinematicTree] that describes how the reference frames of the model are
+    /// connected.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use nalgebra::{Matrix3, Translation3, UnitQuaternion, Vector3};
+    /// use swerve_vehicle_descriptors::model_elements::model::{
+    ///     ChassisElementPhysicalProperties, MotionModel,
+    /// };
+    ///
+    /// let mut model = MotionModel::new();
+    /// let body = model
+    ///     .add_body(
+    ///         "body".to_string(),
+    ///         Translation3::identity(),
+    ///         UnitQuaternion::identity(),
+    ///         ChassisElementPhysicalProperties::from_mass_properties(
+    ///             1.0,
+    ///             Vector3::zeros(),
+    ///             Matrix3::identity(),
+    ///         ),
+    ///     )
+    ///     .unwrap();
+    ///
+    /// let tree = model.kinematic_tree();
+    /// assert_eq!(&body, tree.body_element().unwrap().id());
+    /// assert_eq!(0, tree.children_of(&body).unwrap().count());
+    /// ```
+    pub fn kinematic_tree(&self) -> &KinematicTree {
+        &self.reference_frames
+    }
+
+    /// Returns the [JointKinematicsTable] for the given reference frame, or [None] if the motion
+    /// of the frame is described by its degree of freedom.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'frame_id' - The [FrameID] of the element.
+    pub fn kinematics_table(&self, frame_id: &FrameID) -> Option<&JointKinematicsTable> {
+        self.kinematics_tables.get(frame_id)
+    }
+
+    /// Returns the time at which the state of the joint for the given reference frame was last
+    /// received from the hardware, or [None] if no state has been received yet.
+    ///
+    /// When the joint has both an [Actuator] and a [JointSensor] the time of the oldest of the
+    /// two states is returned, because the fused state is only as fresh as its oldest input.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'frame_id' - The [FrameID] of the element.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the joint has neither an actuator nor a
+    ///   sensor.
+    pub fn last_update_for(&self, frame_id: &FrameID) -> Result<Option<Instant>, Error> {
+        let updates: Vec<_> = self
+            .actuators
+            .get(frame_id)
+            .map(|actuator| actuator.last_update())
+            .into_iter()
+            .chain(
+                self.sensors
+                    .get(frame_id)
+                    .map(|sensor| sensor.last_update()),
+            )
+            .collect();
+        if updates.is_empty() {
+            return Err(Error::MissingFrameElement { id: *frame_id });
+        }
+
+        // When one of the sources has never been updated the joint has no known update time
+        Ok(updates
+            .into_iter()
+            .map(|update| update.map(|u| u.received_at()))
+            .collect::<Option<Vec<_>>>()
+            .and_then(|times| times.into_iter().min()))
+    }
+
+    /// Returns the active [OperatingMode], or [None] if no operating mode is active.
+    pub fn operating_mode(&self) -> Option<&OperatingMode> {
+        self.active_operating_mode
+            .as_ref()
+            .and_then(|name| self.operating_modes.get(name))
+    }
+
+    /// Returns the operating mode with the given name, or [None] if the model has no operating
+    /// mode with that name.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'name' - The name of the operating mode.
+    pub fn operating_mode_by_name(&self, name: &str) -> Option<&OperatingMode> {
+        self.operating_modes.get(name)
+    }
+
+    /// Returns the names of all the operating modes of the model.
+    pub fn operating_modes(&self) -> impl Iterator<Item = &str> {
+        self.operating_modes.keys().map(|name| name.as_str())
+    }
+
+    /// Returns the [FrameID] of the parent of the given element.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'frame_id' - The [FrameID] of the element from which the parent [FrameID] should be returned.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the [ReferenceFrame] is not part of the model
+    pub fn parent_of(&self, frame_id: &FrameID) -> Result<&FrameID, Error> {
+        if !self.reference_frames.has_element(frame_id) {
+            return Err(Error::MissingFrameElement { id: *frame_id });
+        }
+
+        let parent = self.reference_frames.parent_of(frame_id)?;
+        Ok(parent.id())
+    }
+
+    /// Returns the [ReferenceFrame] for a given joint
+    ///
+    /// ## Parameters
+    ///
+    /// * 'frame_id' - The [FrameID] of the [ReferenceFrame] that should be returned.
+    ///
+    /// ## Errors
+    ///
+    /// /// * [Error::MissingFrameElement] - Returned when the [ReferenceFrame] is not part of the model
+    pub fn reference_frame(&self, frame_id: &FrameID) -> Result<&ReferenceFrame, Error> {
+        if !self.reference_frames.has_element(frame_id) {
+            return Err(Error::MissingFrameElement { id: *frame_id });
+        }
+
+        self.reference_frames.element(frame_id)
+    }
+
+    /// Returns the [FrameID] of the joints for which states were received from the hardware that
+    /// were rejected because they contained a NaN or infinite value, together with the number of
+    /// rejected states. The joints are ordered by their [FrameID].
+    ///
+    /// A joint that keeps rejecting states is stuck at its last valid state, which generally
+    /// points to a problem with the hardware or the connection to it.
+    pub fn rejected_joint_states(&self) -> Vec<(FrameID, u64)> {
+        let actuators = self
+            .actuators
+            .iter()
+            .map(|(id, actuator)| (*id, actuator.rejected_state_count()));
+        let sensors = self
+            .sensors
+            .iter()
+            .map(|(id, sensor)| (*id, sensor.rejected_state_count()));
+
+        let mut result: BTreeMap<FrameID, u64> = BTreeMap::new();
+        for (id, count) in actuators.chain(sensors).filter(|(_, count)| *count > 0) {
+            *result.entry(id).or_default() += count;
+        }
+
+        result.into_iter().collect()
+    }
+
+    /// Removes the given frame element and all the frame elements that are attached to it,
+    /// directly or indirectly, from the model. Returns the [FrameID] of the removed frame
+    /// elements, starting with the given frame element.
+    ///
+    /// The actuators, constraints and other data that are stored for the removed frame elements
+    /// are removed as well. This can be used to reconfigure the model at runtime, e.g. when a
+    /// drive module is swapped out.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'frame_id' - The [FrameID] of the element that should be removed.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the [ReferenceFrame] is not part of the model.
+    /// * [Error::InvalidFrameID] - Returned when the [ReferenceFrame] is the body.
+    pub fn remove_element(&mut self, frame_id: &FrameID) -> Result<Vec<FrameID>, Error> {
+        if !self.reference_frames.has_element(frame_id) {
+            return Err(Error::MissingFrameElement { id: *frame_id });
+        }
+
+        if self.is_body(frame_id) {
+            return Err(Error::InvalidFrameID { id: *frame_id });
+        }
+
+        self.invalidate(ModelChange::FrameRemoved { frame: *frame_id });
+
+        let removed = self.reference_frames.remove_subtree(frame_id)?;
+        for frame in removed.iter() {
+            self.remove_element_data(frame);
+        }
+
+        // The structural change is published before the frame elements are removed, so the
+        // validity can only be determined now
+        self.publish_validity_change();
+
+        Ok(removed)
+    }
+
+    /// Removes the external [Wrench] from the given reference frame. Returns the wrench that was
+    /// removed, or [None] if the reference frame had no wrench.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'frame_id' - The [FrameID] of the element.
+    pub fn remove_external_wrench(&mut self, frame_id: &FrameID) -> Option<Wrench> {
+        self.external_wrenches.remove(frame_id)
+    }
+
+    /// Removes the given alias. Returns the reference frame that had the alias, or [None] if no
+    /// reference frame had the alias.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'alias' - The alias name that should be removed.
+    pub fn remove_frame_alias(&mut self, alias: &str) -> Option<FrameID> {
+        self.frame_aliases.remove(alias)
+    }
+
+    /// Removes the given key from the metadata of the given reference frame. Returns the value
+    /// that was stored for the key, or [None] if no value was stored.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'frame_id' - The [FrameID] of the element.
+    /// * 'key' - The key of the metadata.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the [ReferenceFrame] is not part of the model.
+    pub fn remove_frame_metadata(
+        &mut self,
+        frame_id: &FrameID,
+        key: &str,
+    ) -> Result<Option<String>, Error> {
+        if !self.reference_frames.has_element(frame_id) {
+            return Err(Error::MissingFrameElement { id: *frame_id });
+        }
+
+        let metadata = match self.frame_metadata.get_mut(frame_id) {
+            Some(metadata) => metadata,
+            None => return Ok(None),
+        };
+
+        let removed = metadata.remove(key);
+        if metadata.is_empty() {
+            self.frame_metadata.remove(frame_id);
+        }
+
+        Ok(removed)
+    }
+
+    /// Removes the [JointSensor] for the given reference frame. Returns the sensor that was
+    /// removed, or [None] if the reference frame had no sensor.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'frame_id' - The [FrameID] of the element.
+    pub fn remove_joint_sensor(&mut self, frame_id: &FrameID) -> Option<JointSensor> {
+        let removed = self.sensors.remove(frame_id);
+        if let Some(sensor) = removed.as_ref() {
+            sensor.set_event_target(None);
+            self.invalidate(ModelChange::SensorChanged { frame: *frame_id });
+        }
+
+        removed
+    }
+
+    /// Removes the given frame element from the model and attaches its children to the parent
+    /// of the removed frame element.
+    ///
+    /// The children keep the position and orientation relative to the parent that they had when
+    /// the joint of the removed frame element was at its zero position. The actuators,
+    /// constraints and other data that are stored for the removed frame element are removed
+    /// as well.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'frame_id' - The [FrameID] of the element that should be removed.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the [ReferenceFrame] is not part of the model.
+    /// * [Error::InvalidFrameID] - Returned when the [ReferenceFrame] is the body.
+    /// * [Error::NoSteeringFramesInChain] - Returned when the [ReferenceFrame] is the steering
+    ///   frame for a wheel, because the wheel would no longer have a steering frame.
+    pub fn remove_element_and_reparent_children(
+        &mut self,
+        frame_id: &FrameID,
+    ) -> Result<(), Error> {
+        if !self.reference_frames.has_element(frame_id) {
+            return Err(Error::MissingFrameElement { id: *frame_id });
+        }
+
+        if self.is_body(frame_id) {
+            return Err(Error::InvalidFrameID { id: *frame_id });
+        }
+
+        if let Some(wheel) = self.steering_frame_to_wheel.get(frame_id) {
+            if !wheel.is_none() {
+                return Err(Error::NoSteeringFramesInChain { id: *frame_id });
+            }
+        }
+
+        self.invalidate(ModelChange::FrameRemoved { frame: *frame_id });
+
+        self.reference_frames
+            .remove_element_and_reparent_children(frame_id)?;
+        self.remove_element_data(frame_id);
+        self.publish_validity_change();
+
+        Ok(())
+    }
+
+    /// Replaces the [Actuator] for the given reference frame. Returns the actuator that was
+    /// replaced, or [None] if the reference frame had no actuator. The [JointConstraint] of the
+    /// reference frame, if there is one, is applied to the new actuator.
+    ///
+    /// This can be used to reconfigure the model at runtime, e.g. when the hardware for a joint
+    /// is swapped out.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'frame_id' - The [FrameID] of the element.
+    /// * 'actuator' - The actuator that moves the element relative to its parent.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the [ReferenceFrame] is not part of the model.
+    /// * [Error::InvalidFrameID] - Returned when the [ReferenceFrame] is not actuated.
+    pub fn replace_actuator(
+        &mut self,
+        frame_id: &FrameID,
+        mut actuator: Actuator,
+    ) -> Result<Option<Actuator>, Error> {
+        if !self.reference_frames.has_element(frame_id) {
+            return Err(Error::MissingFrameElement { id: *frame_id });
+        }
+
+        if !self.reference_frames.element(frame_id)?.is_actuated() {
+            return Err(Error::InvalidFrameID { id: *frame_id });
+        }
+
+        if let Some(constraint) = self.joint_constraints.get(frame_id) {
+            actuator.set_constraint(*constraint);
+        }
+
+        let previous = self.insert_actuator(*frame_id, actuator);
+        self.invalidate(ModelChange::ActuatorChanged { frame: *frame_id });
+        Ok(previous)
+    }
+
+    /// Replaces the [JointSensor] for the given reference frame. Returns the sensor that was
+    /// replaced, or [None] if the reference frame had no sensor.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'frame_id' - The [FrameID] of the element.
+    /// * 'sensor' - The sensor that tracks the state of the joint.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the [ReferenceFrame] is not part of the model.
+    /// * [Error::InvalidFrameID] - Returned when the [ReferenceFrame] can not move relative to
+    ///   its parent.
+    /// * [Error::IncompatibleNumberSpace] - Returned when the number space of the sensor does
+    ///   not match the degree of freedom of the [ReferenceFrame], or when the limits of a
+    ///   [NumberSpaceType::LinearLimited] number space do not cover the position limits of the
+    ///   [JointConstraint] of the [ReferenceFrame].
+    pub fn replace_joint_sensor(
+        &mut self,
+        frame_id: &FrameID,
+        sensor: JointSensor,
+    ) -> Result<Option<JointSensor>, Error> {
+        self.verify_sensor_compatibility(frame_id, &sensor)?;
+
+        let previous = self.insert_sensor(*frame_id, sensor);
+        self.invalidate(ModelChange::SensorChanged { frame: *frame_id });
+        Ok(previous)
+    }
+
+    /// Returns the revision of the model.
+    ///
+    /// The revision is incremented each time a structural change is made to the model. Data
+    /// that was derived from the model is valid for as long as the revision does not change.
+    pub fn revision(&self) -> u64 {
+        self.revision
+    }
+
+    /// Returns the [JointSensor] for the given reference frame.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'frame_id' - The [FrameID] of the element.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the [ReferenceFrame] has no sensor.
+    pub fn sensor_for(&self, frame_id: &FrameID) -> Result<&JointSensor, Error> {
+        match self.sensors.get(frame_id) {
+            Some(s) => Ok(s),
+            None => Err(Error::MissingFrameElement { id: *frame_id }),
+        }
+    }
+
+    /// Sets the [Actuator] for the given reference frame, replacing the existing actuator if
+    /// there is one.
+    ///
+    /// This is used to connect the hardware to a model that was created without actuators,
+    /// e.g. a model that was deserialized.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'frame_id' - The [FrameID] of the element.
+    /// * 'actuator' - The actuator that moves the element relative to its parent.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the [ReferenceFrame] is not part of the model.
+    /// * [Error::InvalidFrameID] - Returned when the [ReferenceFrame] is not actuated.
+    pub fn set_actuator(&mut self, frame_id: &FrameID, actuator: Actuator) -> Result<(), Error> {
+        self.replace_actuator(frame_id, actuator)?;
+        Ok(())
+    }
+
+    /// Sets the longest time between two states received from the hardware before the
+    /// actuator of the given reference frame is considered stale, see
+    /// [Actuator::set_stale_timeout].
+    ///
+    /// ## Parameters
+    ///
+    /// * 'frame_id' - The [FrameID] of the element.
+    /// * 'timeout' - The stale timeout, or [None] to never consider the actuator stale.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the [ReferenceFrame] is not part of the model.
+    /// * [Error::InvalidFrameID] - Returned when the [ReferenceFrame] has no actuator.
+    pub fn set_actuator_stale_timeout(
+        &mut self,
+        frame_id: &FrameID,
+        timeout: Option<Duration>,
+    ) -> Result<(), Error> {
+        if !self.reference_frames.has_element(frame_id) {
+            return Err(Error::MissingFrameElement { id: *frame_id });
+        }
+
+        match self.actuators.get_mut(frame_id) {
+            Some(actuator) => {
+                actuator.set_stale_timeout(timeout);
+                Ok(())
+            }
+            None => Err(Error::InvalidFrameID { id: *frame_id }),
+        }
+    }
+
+    /// Sets the pose of the body in the world frame, e.g. from the odometry or from a
+    /// localization system.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'pose' - The transform that turns coordinates in the body frame into coordinates in
+    ///   the world frame.
+    pub fn set_body_pose_in_world(&mut self, pose: Isometry3<f64>) {
+        self.body_pose_in_world = pose;
+    }
+
+    /// Sets the acceleration due to gravity, e.g. for a vehicle that drives on a slope or for a
+    /// vehicle that is used on another planet.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'gravity' - The acceleration due to gravity, in meters per second squared, in the world
+    ///   frame.
+    pub fn set_gravity(&mut self, gravity: Vector3<f64>) {
+        self.gravity = gravity;
+    }
+
+    /// Sets the [JointCalibration] of the hardware of the given joint, replacing the existing
+    /// calibration if there is one. The calibration is applied to the [Actuator] and the
+    /// [JointSensor] of the joint, including the ones that are attached later, and is stored
+    /// with the model when it is serialized.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'frame_id' - The [FrameID] of the element.
+    /// * 'calibration' - The conversion between the raw values of the hardware and the state
+    ///   of the joint.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the [ReferenceFrame] is not part of the model.
+    /// * [Error::InvalidFrameID] - Returned when the [ReferenceFrame] does not move relative to
+    ///   its parent.
+    pub fn set_calibration(
+        &mut self,
+        frame_id: &FrameID,
+        calibration: JointCalibration,
+    ) -> Result<(), Error> {
+        if self.frame_degree_of_freedom(frame_id)? == FrameDofType::Static {
+            return Err(Error::InvalidFrameID { id: *frame_id });
+        }
+
+        if let Some(actuator) = self.actuators.get_mut(frame_id) {
+            actuator.set_calibration(calibration);
+        }
+        if let Some(sensor) = self.sensors.get_mut(frame_id) {
+            sensor.set_calibration(calibration);
+        }
+
+        self.joint_calibrations.insert(*frame_id, calibration);
+        Ok(())
+    }
+
+    /// Sets how the [Actuator] of the given reference frame handles the commands that are
+    /// outside of the [JointConstraint] of the reference frame.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'frame_id' - The [FrameID] of the element.
+    /// * 'enforcement' - The way in which the constraint is enforced.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the [ReferenceFrame] is not part of the model.
+    /// * [Error::InvalidFrameID] - Returned when the [ReferenceFrame] has no actuator.
+    pub fn set_constraint_enforcement(
+        &mut self,
+        frame_id: &FrameID,
+        enforcement: ConstraintEnforcement,
+    ) -> Result<(), Error> {
+        if !self.reference_frames.has_element(frame_id) {
+            return Err(Error::MissingFrameElement { id: *frame_id });
+        }
+
+        match self.actuators.get_mut(frame_id) {
+            Some(actuator) => {
+                actuator.set_constraint_enforcement(enforcement);
+                Ok(())
+            }
+            None => Err(Error::InvalidFrameID { id: *frame_id }),
+        }
+    }
+
+    /// Stores the given value for the given key in the metadata of the given reference frame.
+    /// Returns the value that was stored for the key before, if any.
+    ///
+    /// The metadata is not used by the model itself. It allows users to attach information to
+    /// the frames, e.g. to mark the frames that carry a sensor or a payload, and to find those
+    /// frames with [MotionModel::frames_with_metadata]. The metadata of a frame is removed when
+    /// the frame is removed from the model.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'frame_id' - The [FrameID] of the element.
+    /// * 'key' - The key of the metadata.
+    /// * 'value' - The value that should be stored for the key.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the [ReferenceFrame] is not part of the model.
+    pub fn set_frame_metadata(
+        &mut self,
+        frame_id: &FrameID,
+        key: String,
+        value: String,
+    ) -> Result<Option<String>, Error> {
+        if !self.reference_frames.has_element(frame_id) {
+            return Err(Error::MissingFrameElement { id: *frame_id });
+        }
+
+        Ok(self
+            .frame_metadata
+            .entry(*frame_id)
+            .or_default()
+            .insert(key, value))
+    }
+
+    /// Sets the thresholds used to determine if a wheel is in contact with the ground.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'thresholds' - The new thresholds.
+    pub fn set_ground_contact_thresholds(&mut self, thresholds: GroundContactThresholds) {
+        self.ground_contact_thresholds = thresholds;
+    }
+
+    /// Sets the maximum number of states that are stored in the history of the [Actuator] and
+    /// the [JointSensor] of the given joint, including the ones that are attached later. The
+    /// history is used by [MotionModel::joint_state_at]. A capacity of zero disables the
+    /// history.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'frame_id' - The [FrameID] of the element.
+    /// * 'capacity' - The maximum number of states in the history.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the [ReferenceFrame] is not part of the model.
+    /// * [Error::InvalidFrameID] - Returned when the [ReferenceFrame] does not move relative to
+    ///   its parent.
+    pub fn set_history_capacity(
+        &mut self,
+        frame_id: &FrameID,
+        capacity: usize,
+    ) -> Result<(), Error> {
+        if self.frame_degree_of_freedom(frame_id)? == FrameDofType::Static {
+            return Err(Error::InvalidFrameID { id: *frame_id });
+        }
+
+        if let Some(actuator) = self.actuators.get_mut(frame_id) {
+            actuator.set_history_capacity(capacity);
+        }
+        if let Some(sensor) = self.sensors.get_mut(frame_id) {
+            sensor.set_history_capacity(capacity);
+        }
+
+        self.history_capacities.insert(*frame_id, capacity);
+        Ok(())
+    }
+
+    /// Sets the [JointConstraint] for the given reference frame, replacing the existing
+    /// constraint if there is one. When the reference frame has an [Actuator] the constraint
+    /// is also applied to the commands for the actuator, depending on the
+    /// [ConstraintEnforcement] of the actuator.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'frame_id' - The [FrameID] of the element.
+    /// * 'constraint' - The limits on the motion of the element relative to its parent.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the [ReferenceFrame] is not part of the model.
+    /// * [Error::InvalidFrameID] - Returned when the [ReferenceFrame] does not move relative to
+    ///   its parent.
+    /// * [Error::IncompatibleNumberSpace] - Returned when the [JointSensor] of the
+    ///   [ReferenceFrame] has a [NumberSpaceType::LinearLimited] number space that does not
+    ///   cover the position limits of the constraint.
+    pub fn set_joint_constraint(
+        &mut self,
+        frame_id: &FrameID,
+        constraint: JointConstraint,
+    ) -> Result<(), Error> {
+        if self.frame_degree_of_freedom(frame_id)? == FrameDofType::Static {
+            return Err(Error::InvalidFrameID { id: *frame_id });
+        }
+
+        if let Some(sensor) = self.sensors.get(frame_id) {
+            if !covers_position_limits(sensor.number_space_type(), &constraint) {
+                return Err(Error::IncompatibleNumberSpace { id: *frame_id });
+            }
+        }
+
+        if let Some(actuator) = self.actuators.get_mut(frame_id) {
+            actuator.set_constraint(constraint);
+        }
+
+        self.joint_constraints.insert(*frame_id, constraint);
+        self.invalidate(ModelChange::JointConstraintChanged { frame: *frame_id });
+        Ok(())
+    }
+
+    /// Sets the [JointKinematicsTable] that describes the transform from the given reference
+    /// frame to its parent frame.
+    ///
+    /// Once a table is set, the transform queries evaluate the table at the current joint value
+    /// instead of applying the motion of the degree of freedom to the transform that was provided
+    /// when the frame was added. The joint value is read from the actuator of the frame. Frames
+    /// without an actuator are evaluated at a joint value of zero, unless the joint value is
+    /// provided by the caller.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'frame_id' - The [FrameID] of the element.
+    /// * 'table' - The table that describes the transform from the element to its parent.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the [ReferenceFrame] is not part of the model.
+    /// * [Error::InvalidFrameID] - Returned when the [ReferenceFrame] is the body.
+    pub fn set_kinematics_table(
+        &mut self,
+        frame_id: &FrameID,
+        table: JointKinematicsTable,
+    ) -> Result<(), Error> {
+        if !self.reference_frames.has_element(frame_id) {
+            return Err(Error::MissingFrameElement { id: *frame_id });
+        }
+
+        if self.is_body(frame_id) {
+            return Err(Error::InvalidFrameID { id: *frame_id });
+        }
+
+        self.kinematics_tables.insert(*frame_id, table);
+        self.invalidate(ModelChange::KinematicsTableChanged { frame: *frame_id });
+        Ok(())
+    }
+
+    /// Activates the operating mode with the given name.
+    ///
+    /// The speed limits and the twist scaling of the active operating mode are used by the
+    /// limit checking functions of the model.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'name' - The name of the operating mode.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingOperatingMode] - Returned when the model has no operating mode with
+    ///   the given name.
+    pub fn set_operating_mode(&mut self, name: &str) -> Result<(), Error> {
+        if !self.operating_modes.contains_key(name) {
+            return Err(Error::MissingOperatingMode {
+                name: name.to_string(),
+            });
+        }
+
+        self.active_operating_mode = Some(name.to_string());
+        Ok(())
+    }
+
+    /// Sets the policy that combines the states of the joints that have both an [Actuator] and
+    /// a [JointSensor]. By default the state of the actuator is used.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'fusion' - The policy that combines the state of the actuator with the state of the
+    ///   sensor.
+    pub fn set_state_fusion(&mut self, fusion: impl StateFusion + 'static) {
+        self.state_fusion = Box::new(fusion);
+    }
+
+    /// Sets the joint position of the given suspension frame when the suspension is at full
+    /// droop, i.e. when the suspension is fully extended.
+    ///
+    /// Wheels that do not have a suspension frame with a droop position between the wheel and
+    /// the body are always considered to be in contact with the ground.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'suspension_frame' - The [FrameID] of the suspension frame.
+    /// * 'droop_position' - The position of the suspension joint at full droop.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the [ReferenceFrame] is not part of the model.
+    /// * [Error::InvalidFrameID] - Returned when the [ReferenceFrame] is the body or a wheel.
+    pub fn set_suspension_droop_position(
+        &mut self,
+        suspension_frame: &FrameID,
+        droop_position: f64,
+    ) -> Result<(), Error> {
+        if !self.reference_frames.has_element(suspension_frame) {
+            return Err(Error::MissingFrameElement {
+                id: *suspension_frame,
+            });
+        }
+
+        if self.is_body(suspension_frame) || self.reference_frames.is_wheel(suspension_frame)? {
+            return Err(Error::InvalidFrameID {
+                id: *suspension_frame,
+            });
+        }
+
+        self.suspension_droop_positions
+            .insert(*suspension_frame, droop_position);
+        self.invalidate(ModelChange::SuspensionDroopPositionChanged {
+            frame: *suspension_frame,
+        });
+        Ok(())
+    }
+
+    /// Sets the [JointTransmission] between the hardware and the given joint, replacing the
+    /// existing transmission if there is one. The transmission is applied to the [Actuator] and
+    /// the [JointSensor] of the joint, including the ones that are attached later, and is
+    /// stored with the model when it is serialized.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'frame_id' - The [FrameID] of the element.
+    /// * 'transmission' - The direction and the gear ratio between the hardware and the joint.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the [ReferenceFrame] is not part of the model.
+    /// * [Error::InvalidFrameID] - Returned when the [ReferenceFrame] does not move relative to
+    ///   its parent.
+    pub fn set_transmission(
+        &mut self,
+        frame_id: &FrameID,
+        transmission: JointTransmission,
+    ) -> Result<(), Error> {
+        if self.frame_degree_of_freedom(frame_id)? == FrameDofType::Static {
+            return Err(Error::InvalidFrameID { id: *frame_id });
+        }
+
+        if let Some(actuator) = self.actuators.get_mut(frame_id) {
+            actuator.set_transmission(transmission);
+        }
+        if let Some(sensor) = self.sensors.get_mut(frame_id) {
+            sensor.set_transmission(transmission);
+        }
+
+        self.joint_transmissions.insert(*frame_id, transmission);
+        Ok(())
+    }
+
+    /// Sets the position and orientation of the given reference frame relative to its parent
+    /// frame when the joint displacement is zero.
+    ///
+    /// All the data that was derived from the previous transform is invalidated and the
+    /// subscribers of [MotionModel::structural_changes] are notified. The ground contact state
+    /// of the wheels attached to the frame is reset to being in contact until the next call
+    /// to [MotionModel::update_ground_contact].
+    ///
+    /// Frames that have a [JointKinematicsTable] keep using the table to compute the transform
+    /// to their parent.
+    ///
+    /// This is the method to use to write back the mounting pose of a module that was measured
+    /// by a calibration routine, without rebuilding the model.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'frame_id' - The [FrameID] of the element.
+    /// * 'position_relative_to_parent' - The position of the element relative to the parent
+    ///   reference frame
+    /// * 'orientation_relative_to_parent' - The orientation of the element relative to the parent
+    ///   reference frame
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the [ReferenceFrame] is not part of the model.
+    /// * [Error::InvalidFrameID] - Returned when the [ReferenceFrame] is the body.
+    /// * [Error::NonUnitOrientation] - Returned, in audit mode, when the orientation is not
+    ///   normalized.
+    #[doc(alias = "set_static_pose")]
+    pub fn set_transform_to_parent(
+        &mut self,
+        frame_id: &FrameID,
+        position_relative_to_parent: Translation3<f64>,
+        orientation_relative_to_parent: UnitQuaternion<f64>,
+    ) -> Result<(), Error> {
+        if !self.reference_frames.has_element(frame_id) {
+            return Err(Error::MissingFrameElement { id: *frame_id });
+        }
+
+        self.audit_orientation(&orientation_relative_to_parent)?;
+        self.reference_frames.set_transform_to_parent(
+            frame_id,
+            Isometry3::from_parts(position_relative_to_parent, orientation_relative_to_parent),
+        )?;
+        self.invalidate(ModelChange::TransformToParentChanged { frame: *frame_id });
+        Ok(())
+    }
+
+    /// Sets the [WheelProperties] of the given wheel, replacing the existing properties.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'wheel_frame' - The [FrameID] of the wheel.
+    /// * 'properties' - The geometry of the wheel.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the [ReferenceFrame] is not part of the model.
+    /// * [Error::InvalidFrameID] - Returned when the [ReferenceFrame] is not a wheel.
+    pub fn set_wheel_properties(
+        &mut self,
+        wheel_frame: &FrameID,
+        properties: WheelProperties,
+    ) -> Result<(), Error> {
+        if !self.reference_frames.has_element(wheel_frame) {
+            return Err(Error::MissingFrameElement { id: *wheel_frame });
+        }
+
+        if !self.reference_frames.is_wheel(wheel_frame)? {
+            return Err(Error::InvalidFrameID { id: *wheel_frame });
+        }
+
+        self.wheel_properties.insert(*wheel_frame, properties);
+        self.invalidate(ModelChange::WheelPropertiesChanged {
+            wheel: *wheel_frame,
+        });
+        Ok(())
+    }
+
+    /// Sets the radius of the given wheel.
+    ///
+    /// The wheel radius is used to convert the angular velocity of the wheel into the linear
+    /// velocity of the wheel contact point. When the wheel has no [WheelProperties] yet, the
+    /// wheel is assumed to have no width and the contact point is directly below the center of
+    /// the wheel.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'wheel_frame' - The [FrameID] of the wheel.
+    /// * 'radius' - The radius of the wheel in meters.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the [ReferenceFrame] is not part of the model.
+    /// * [Error::InvalidFrameID] - Returned when the [ReferenceFrame] is not a wheel.
+    pub fn set_wheel_radius(&mut self, wheel_frame: &FrameID, radius: f64) -> Result<(), Error> {
+        if !self.reference_frames.has_element(wheel_frame) {
+            return Err(Error::MissingFrameElement { id: *wheel_frame });
+        }
+
+        if !self.reference_frames.is_wheel(wheel_frame)? {
+            return Err(Error::InvalidFrameID { id: *wheel_frame });
+        }
+
+        self.wheel_properties
+            .entry(*wheel_frame)
+            .and_modify(|p| p.radius = radius)
+            .or_insert_with(|| WheelProperties::new(radius, 0.0, Vector3::zeros()));
+        self.invalidate(ModelChange::WheelRadiusChanged {
+            wheel: *wheel_frame,
+        });
+        Ok(())
+    }
+
+    /// Sets the effective rolling radius of the given wheel, e.g. after it was estimated with a
+    /// [RollingRadiusEstimator](crate::kinematics::rolling_radius::RollingRadiusEstimator). The
+    /// rolling radius is used to convert between the angular velocity of the wheel and the
+    /// linear velocity of the contact point. Setting the rolling radius to [None] reverts to
+    /// using the radius of the wheel.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'wheel_frame' - The [FrameID] of the wheel.
+    /// * 'rolling_radius' - The rolling radius of the wheel in meters, or [None] to use the
+    ///   radius of the wheel.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the [ReferenceFrame] is not part of the model.
+    /// * [Error::InvalidFrameID] - Returned when the [ReferenceFrame] is not a wheel.
+    /// * [Error::MissingWheelRadius] - Returned when the wheel has no [WheelProperties].
+    /// * [Error::InvalidRollingRadius] - Returned when the rolling radius is not a finite,
+    ///   positive number.
+    pub fn set_wheel_rolling_radius(
+        &mut self,
+        wheel_frame: &FrameID,
+        rolling_radius: Option<f64>,
+    ) -> Result<(), Error> {
+        if !self.reference_frames.has_element(wheel_frame) {
+            return Err(Error::MissingFrameElement { id: *wheel_frame });
+        }
+
+        if !self.reference_frames.is_wheel(wheel_frame)? {
+            return Err(Error::InvalidFrameID { id: *wheel_frame });
+        }
+
+        if let Some(radius) = rolling_radius {
+            if !radius.is_finite() || radius <= 0.0 {
+                return Err(Error::InvalidRollingRadius { radius });
+            }
+        }
+
+        let properties = self
+            .wheel_properties
+            .get_mut(wheel_frame)
+            .ok_or(Error::MissingWheelRadius { id: *wheel_frame })?;
+        properties.rolling_radius = rolling_radius;
+        self.invalidate(ModelChange::WheelRadiusChanged {
+            wheel: *wheel_frame,
+        });
+        Ok(())
+    }
+
+    /// Returns the homogeneous transform from the given reference frame to its parent frame
+    /// when the joint displacement is zero.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'frame_id' - The [FrameID] of the element for which the transform is requested
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the [ReferenceFrame] is not part of the model
+    ///   or when the [ReferenceFrame] is the body.
+    pub(crate) fn static_transform_to_parent(
+        &self,
+        frame_id: &FrameID,
+    ) -> Result<&Isometry3<f64>, Error> {
+        if !self.reference_frames.has_element(frame_id) || self.is_body(frame_id) {
+            return Err(Error::MissingFrameElement { id: *frame_id });
+        }
+
+        self.reference_frames
+            .homogeneous_transform_to_parent(frame_id)
+    }
+
+    /// Returns the joints for which no state has been received from the hardware within the
+    /// given amount of time, ordered by [FrameID].
+    ///
+    /// A joint is stale when its [Actuator] or its [JointSensor] has never received a state, or
+    /// when the last state was received longer than 'max_age' ago.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'max_age' - The maximum amount of time since the last update of a joint.
+    pub fn stale_joints(&self, max_age: Duration) -> Vec<FrameID> {
+        self.actuators
+            .keys()
+            .chain(self.sensors.keys())
+            .copied()
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .filter(|frame_id| match self.last_update_for(frame_id) {
+                Ok(Some(received_at)) => received_at.elapsed() > max_age,
+                _ => true,
+            })
+            .collect()
+    }
+
+    /// Returns the [FrameID] of the steering frame that is linked to the given wheel frame
+    ///
+    /// ## Parameters
+    ///
+    /// * 'wheel_frame' - The [FrameID] of the wheel for which the steering frame should be located.
+    ///
+    ///  ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the [ReferenceFrame] is not part of the model.
+    /// * [Error::NoSteeringFramesInChain] - Returned when there is no steering frame attached to the wheel.
+    pub fn steering_frame_for_wheel(&self, wheel_frame: &FrameID) -> Result<&FrameID, Error> {
+        if !self.reference_frames.has_element(wheel_frame) {
+            return Err(Error::MissingFrameElement { id: *wheel_frame });
+        }
+
+        let id_ref = match self.wheel_to_steering_frame.get(wheel_frame) {
+            Some(i) => i,
+            None => return Err(Error::NoSteeringFramesInChain { id: *wheel_frame }),
+        };
+
+        Ok(id_ref)
+    }
+
+    /// Returns a [Receiver] that receives a [ModelChangeEvent] each time a structural change
+    /// is made to the model, e.g. when a frame element is added or when the transform between
+    /// a frame element and its parent is changed.
+    pub fn structural_changes(&mut self) -> Receiver<ModelChangeEvent> {
+        let (sender, receiver) = crossbeam_channel::unbounded();
+        self.structural_change_senders.push(sender);
+        receiver
+    }
+
+    /// Returns a [Receiver] that receives a [ModelEvent] each time a joint state is received
+    /// from the hardware, a command is sent to an actuator, a structural change is made to the
+    /// model or the outcome of the validation of the model changes.
+    ///
+    /// The joint state events are sent from the thread that processes the hardware updates.
+    pub fn subscribe(&mut self) -> Receiver<ModelEvent> {
+        if self.last_validity.is_none() {
+            self.last_validity = Some(self.validate().is_valid());
+        }
+
+        self.event_publisher.subscribe()
+    }
+
+    /// Returns the [JointTransmission] between the hardware and the given joint. Joints
+    /// without a transmission use the identity transmission.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'frame_id' - The [FrameID] of the element.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the [ReferenceFrame] is not part of the model.
+    pub fn transmission(&self, frame_id: &FrameID) -> Result<JointTransmission, Error> {
+        if !self.reference_frames.has_element(frame_id) {
+            return Err(Error::MissingFrameElement { id: *frame_id });
+        }
+
+        Ok(self
+            .joint_transmissions
+            .get(frame_id)
+            .copied()
+            .unwrap_or_default())
+    }
+
+    /// Replaces the mass, the center of mass and the inertia of the given frame element, e.g.
+    /// after a battery swap or after a payload was mounted on the vehicle.
+    ///
+    /// The subscribers of [MotionModel::structural_changes] are notified of the change.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'frame_id' - The [FrameID] of the element.
+    /// * 'physical_properties' - The new mass and inertia of the element.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the element is not part of the model.
+    pub fn update_physical_properties(
+        &mut self,
+        frame_id: &FrameID,
+        physical_properties: ChassisElementPhysicalProperties,
+    ) -> Result<(), Error> {
+        let element = self
+            .chassis_elements
+            .get_mut(frame_id)
+            .ok_or(Error::MissingFrameElement { id: *frame_id })?;
+
+        *element = ChassisElement::new(
+            element.name().to_string(),
+            physical_properties.mass,
+            physical_properties.center_of_mass,
+            physical_properties.moment_of_inertia,
+            physical_properties.spatial_inertia,
+            *frame_id,
+        );
+        self.invalidate(ModelChange::PhysicalPropertiesChanged { frame: *frame_id });
+        Ok(())
+    }
+
+    /// Updates the ground contact state of the wheels and notifies the subscribers of any
+    /// changes.
+    ///
+    /// A wheel loses contact with the ground when all the suspension frames with a droop
+    /// position between the wheel and the body are at full droop and the load on the wheel
+    /// is close to zero. The contact is restored as soon as one of these conditions no longer
+    /// holds. Wheels without a load estimate keep their current state.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'joint_states' - The measured states of the joints, stored by the [FrameID] of the
+    ///   joint. Joints that are not in the collection use the current state of their actuator.
+    ///   Joints that have neither are assumed to be at their zero position.
+    /// * 'wheel_loads' - The estimated load, in Newton, on each wheel, stored by the [FrameID]
+    ///   of the wheel.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the model has no wheels.
+    pub fn update_ground_contact(
+        &mut self,
+        joint_states: &HashMap<FrameID, JointState>,
+        wheel_loads: &HashMap<FrameID, f64>,
+    ) -> Result<Vec<GroundContactEvent>, Error> {
+        let mut events = Vec::new();
+        for wheel in self.wheels()? {
+            let load = match wheel_loads.get(wheel) {
+                Some(l) => *l,
+                None => continue,
+            };
+
+            let in_contact = !(self.ground_contact_thresholds.is_unloaded(load)
+                && self.is_suspension_at_full_droop(wheel, joint_states)?);
+            let was_in_contact = !self.wheels_without_ground_contact.contains(wheel);
+            if in_contact == was_in_contact {
+                continue;
+            }
+
+            if in_contact {
+                events.push(GroundContactEvent::ContactRestored { wheel: *wheel });
+            } else {
+                events.push(GroundContactEvent::ContactLost { wheel: *wheel });
+            }
+        }
+
+        for event in events.iter() {
+            match event {
+                GroundContactEvent::ContactLost { wheel } => {
+                    self.wheels_without_ground_contact.insert(*wheel);
+                }
+                GroundContactEvent::ContactRestored { wheel } => {
+                    self.wheels_without_ground_contact.remove(wheel);
+                }
+            }
+
+            // Drop the subscribers that are no longer listening
+            self.ground_contact_senders
+                .retain(|sender| sender.send(*event).is_ok());
+        }
+
+        Ok(events)
+    }
+
+    /// Checks that the model describes a valid swerve vehicle and returns a report that
+    /// describes the issues that were found.
+    ///
+    /// It is expected that the model meets the following conditions:
+    /// - The model has a body and all the elements are connected to the body
+    /// - At least 2 wheels
+    /// - Each wheel rotates around its y-axis
+    /// - Each wheel has exactly 1 steering element
+    /// - Each steering element rotates around its z-axis and is connected to a wheel
+    /// - The center of each wheel is offset from the origin of its steering frame
+    /// - Each actuated element has an actuator
+    /// - Each element has a positive mass and a symmetric, positive definite moment of inertia
+    /// - The spatial inertia of each element matches its mass, center of mass and moment of
+    ///   inertia, see [ChassisElementPhysicalProperties::from_mass_properties]
+    /// - The current joint positions satisfy each loop closure constraint, see
+    ///   [MotionModel::loop_closure_error]
+    pub fn validate(&self) -> ValidationReport {
+        let mut issues = vec![];
+        if self.body().is_err() {
+            issues.push(ValidationIssue::MissingBody);
+            issues.push(ValidationIssue::TooFewWheels {
+                number_of_wheels: 0,
+            });
+            return ValidationReport::new(issues);
+        }
+
+        let number_of_wheels = self.number_of_wheels();
+        if number_of_wheels < 2 {
+            issues.push(ValidationIssue::TooFewWheels { number_of_wheels });
+        }
+
+        for frame in self.reference_frames.elements() {
+            if !self.is_connected_to_body(frame.id()) {
+                issues.push(ValidationIssue::UnreachableFrame { frame: *frame.id() });
+            }
+        }
+
+        // The same wheels as the ones used by the kinematics, which includes the frames without
+        // a role that are assumed to be wheels
+        for wheel in self.reference_frames.wheel_elements.iter() {
+            // Each wheel rotates in the xz-plane
+            if let Ok(dof) = self.frame_degree_of_freedom(wheel) {
+                if dof != FrameDofType::RevoluteY {
+                    issues.push(ValidationIssue::InvalidWheelDegreeOfFreedom {
+                        frame: *wheel,
+                        degree_of_freedom: dof,
+                    });
+                }
+            }
+
+            // Each wheel should have one, and exactly one steering joint
+            let steering_joint = match self.wheel_to_steering_frame.get(wheel) {
+                Some(steering_joint) => steering_joint,
+                None => {
+                    issues.push(ValidationIssue::MissingSteering { frame: *wheel });
+                    continue;
+                }
+            };
+
+            if self.number_of_steering_frames_in_chain(wheel) > 1 {
+                issues.push(ValidationIssue::MultipleSteering { frame: *wheel });
+            }
+
+            // A wheel at the origin of the steering frame is most likely a missing offset
+            if let Ok(wheel_to_steering) =
+                self.homogeneous_transform_to_ancestor_with(wheel, steering_joint, |_| None)
+            {
+                let offset: Vector3<f64> = wheel_to_steering.fixed_view::<3, 1>(0, 3).into();
+                if offset.norm() < MINIMUM_WHEEL_OFFSET {
+                    issues.push(ValidationIssue::ZeroWheelOffset { frame: *wheel });
+                }
+            }
+
+            // Each steering joint rotates the wheel
+            if let Ok(dof) = self.frame_degree_of_freedom(steering_joint) {
+                if !dof.is_revolute() {
+                    issues.push(ValidationIssue::InvalidSteeringDegreeOfFreedom {
+                        frame: *steering_joint,
+                        degree_of_freedom: dof,
+                    });
+                }
+            }
+        }
+
+        for steering_frame in self.frames_with_role(FrameRole::Steering) {
+            let has_wheel = self
+                .steering_frame_to_wheel
+                .get(steering_frame)
+                .map_or(false, |wheel| !wheel.is_none());
+            if !has_wheel {
+                issues.push(ValidationIssue::MissingWheel {
+                    frame: *steering_frame,
+                });
+            }
+        }
+
+        for frame in self.reference_frames.elements() {
+            if frame.is_actuated() && !self.actuators.contains_key(frame.id()) {
+                issues.push(ValidationIssue::MissingActuator { frame: *frame.id() });
+            }
+        }
+
+        for closure in &self.loop_closures {
+            if let Ok(error) = self.loop_closure_error(closure) {
+                if error > LOOP_CLOSURE_TOLERANCE {
+                    issues.push(ValidationIssue::LoopClosureViolated {
+                        frame: *closure.first(),
+                        other: *closure.second(),
+                        error,
+                    });
+                }
+            }
+        }
+
+        for element in self.chassis_elements.values() {
+            let frame = *element.reference_frame();
+            let mass = element.mass_in_kg();
+
+            // The links of a multi degree of freedom joint are massless by design
+            if mass == 0.0
+                && element.moment_of_inertia().iter().all(|v| *v == 0.0)
+                && self
+                    .multi_dof_joints
+                    .iter()
+                    .any(|joint| joint.is_massless_link(&frame))
+            {
+                continue;
+            }
+
+            if mass.is_nan() || mass <= 0.0 {
+                issues.push(ValidationIssue::NonPositiveMass { frame });
+            }
+
+            let inertia = element.moment_of_inertia();
+            if !is_approximately_equal(inertia, &inertia.transpose()) {
+                issues.push(ValidationIssue::AsymmetricInertia { frame });
+            }
+
+            // A symmetric matrix is positive definite if and only if it has a Cholesky
+            // decomposition.
+            if !inertia.iter().all(|v| v.is_finite()) || inertia.cholesky().is_none() {
+                issues.push(ValidationIssue::SingularInertia { frame });
+            }
+
+            let expected = ChassisElementPhysicalProperties::from_mass_properties(
+                mass,
+                *element.center_of_mass(),
+                *inertia,
+            );
+            if !is_approximately_equal(element.spatial_inertia(), &expected.spatial_inertia()) {
+                issues.push(ValidationIssue::InconsistentSpatialInertia { frame });
+            }
+        }
+
+        ValidationReport::new(issues)
+    }
+
+    /// Checks that the given state is within the [JointConstraint] of the reference frame.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'frame_id' - The [FrameID] of the element.
+    /// * 'state' - The state of the joint that should be checked.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the [ReferenceFrame] is not part of the model.
+    /// * [Error::JointLimitExceeded] - Returned when the state is outside of the limits of the
+    ///   joint.
+    pub fn validate_joint_state(
+        &self,
+        frame_id: &FrameID,
+        state: &JointState,
+    ) -> Result<(), Error> {
+        self.joint_constraint(frame_id)?.validate(state)
+    }
+
+    /// Returns the position, in body coordinates, of the point where the given wheel touches
+    /// the ground, based on the current position of the suspension and steering joints.
+    ///
+    /// The contact point is given by the [WheelProperties] of the wheel. The rotation of the
+    /// wheel around its axle does not move the contact point.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'wheel_frame' - The [FrameID] of the wheel.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the [ReferenceFrame] is not part of the model.
+    /// * [Error::InvalidFrameID] - Returned when the [ReferenceFrame] is not a wheel.
+    /// * [Error::MissingWheelRadius] - Returned when no radius has been set for the wheel.
+    pub fn wheel_contact_point_in_body(
+        &self,
+        wheel_frame: &FrameID,
+    ) -> Result<Vector3<f64>, Error> {
+        let properties = self.wheel_properties(wheel_frame)?;
+
+        let parent = self.parent_of(wheel_frame)?;
+        let parent_to_body = self.homogeneous_transform_to_body(parent)?;
+        let wheel_to_parent = self.static_transform_to_parent(wheel_frame)?;
+
+        let contact_point =
+            wheel_to_parent.transform_point(&Point3::from(properties.contact_point()));
+        Ok(parent_to_body.transform_point(&contact_point).coords)
+    }
+
+    /// Returns the position, in world coordinates, of the point where the given wheel touches
+    /// the ground.
+    ///
+    /// The ground is assumed to be flat and perpendicular to the z-axis of the world frame, so
+    /// the contact point is located one wheel radius below the center of the wheel.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'wheel_frame' - The [FrameID] of the wheel.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the [ReferenceFrame] is not part of the model.
+    /// * [Error::InvalidFrameID] - Returned when the [ReferenceFrame] is not a wheel.
+    /// * [Error::MissingWheelRadius] - Returned when no radius has been set for the wheel.
+    pub fn wheel_contact_point_in_world(
+        &self,
+        wheel_frame: &FrameID,
+    ) -> Result<Vector3<f64>, Error> {
+        let radius = self.wheel_radius(wheel_frame)?;
+        if !self.reference_frames.is_wheel(wheel_frame)? {
+            return Err(Error::InvalidFrameID { id: *wheel_frame });
+        }
+
+        let to_world = self.homogeneous_transform_to_world(wheel_frame)?;
+        let center = Vector3::new(to_world[(0, 3)], to_world[(1, 3)], to_world[(2, 3)]);
+        Ok(center - Vector3::new(0.0, 0.0, radius))
+    }
+
+    /// Returns the [WheelProperties] of the given wheel.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'wheel_frame' - The [FrameID] of the wheel.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the [ReferenceFrame] is not part of the model.
+    /// * [Error::InvalidFrameID] - Returned when the [ReferenceFrame] is not a wheel.
+    /// * [Error::MissingWheelRadius] - Returned when no properties have been set for the wheel.
+    pub fn wheel_properties(&self, wheel_frame: &FrameID) -> Result<&WheelProperties, Error> {
+        if !self.reference_frames.has_element(wheel_frame) {
+            return Err(Error::MissingFrameElement { id: *wheel_frame });
+        }
+
+        if !self.reference_frames.is_wheel(wheel_frame)? {
+            return Err(Error::InvalidFrameID { id: *wheel_frame });
+        }
+
+        match self.wheel_properties.get(wheel_frame) {
+            Some(p) => Ok(p),
+            None => Err(Error::MissingWheelRadius { id: *wheel_frame }),
+        }
+    }
+
+    /// Returns the radius, in meters, of the given wheel.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'wheel_frame' - The [FrameID] of the wheel.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the [ReferenceFrame] is not part of the model.
+    /// * [Error::MissingWheelRadius] - Returned when no radius has been set for the wheel.
+    pub fn wheel_radius(&self, wheel_frame: &FrameID) -> Result<f64, Error> {
+        if !self.reference_frames.has_element(wheel_frame) {
+            return Err(Error::MissingFrameElement { id: *wheel_frame });
+        }
+
+        match self.wheel_properties.get(wheel_frame) {
+            Some(p) => Ok(p.radius),
+            None => Err(Error::MissingWheelRadius { id: *wheel_frame }),
+        }
+    }
+
+    /// Returns the effective rolling radius, in meters, of the given wheel, see
+    /// [WheelProperties::rolling_radius].
+    ///
+    /// ## Parameters
+    ///
+    /// * 'wheel_frame' - The [FrameID] of the wheel.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the [ReferenceFrame] is not part of the model.
+    /// * [Error::MissingWheelRadius] - Returned when no radius has been set for the wheel.
+    pub fn wheel_rolling_radius(&self, wheel_frame: &FrameID) -> Result<f64, Error> {
+        if !self.reference_frames.has_element(wheel_frame) {
+            return Err(Error::MissingFrameElement { id: *wheel_frame });
+        }
+
+        match self.wheel_properties.get(wheel_frame) {
+            Some(p) => Ok(p.rolling_radius()),
+            None => Err(Error::MissingWheelRadius { id: *wheel_frame }),
+        }
+    }
+
+    /// Returns a list of [FrameID] of all the wheels that are in contact with the ground.
+    pub fn wheels_in_contact(&self) -> Result<Vec<&FrameID>, Error> {
+        let list = self
+            .wheels()?
+            .into_iter()
+            .filter(|w| self.is_wheel_in_contact(w))
+            .collect();
+        Ok(list)
+    }
+
+    /// Returns a list of [FrameID] of all the wheels
+    pub fn wheels(&self) -> Result<Vec<&FrameID>, Error> {
+        let list = self.reference_frames.wheels()?.map(|f| f.id()).collect();
+        Ok(list)
+    }
+
+    /// Indicates whether there are any actuated joints between the steering frames and the body frame
+    /// or the wheel frame and the steering frame.
+    pub fn has_active_suspension(&self) -> bool {
+        let number_of_actuators = self.actuators.len();
+        let number_of_wheels = self.reference_frames.number_of_wheels();
+
+        // Both the wheels and the steering frames are actuated, so if there are
+        // more actuators then there are wheels and steering frames then we have
+        // active suspension
+        number_of_actuators > 2 * number_of_wheels
+    }
+
+    /// Indicates whether the given joint has a sensor
+    ///
+    /// ## Parameters
+    ///
+    /// * 'frame_id' - The [FrameID] of the joint.
+    pub fn has_sensor(&self, frame_id: &FrameID) -> bool {
+        self.sensors.contains_key(frame_id)
+    }
+
+    /// Returns a value indicating if the joint with the given [FrameID] is an actuated joint
+    ///
+    /// ## Parameters
+    ///
+    /// * 'frame_id' - The [FrameID] of the joint.
+    pub fn is_actuated(&self, frame_id: &FrameID) -> bool {
+        self.actuators.contains_key(frame_id)
+    }
+
+    /// Returns a value indicating if the given 'to' frame is an ancestor of the 'from' frame.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'from' - The starting frame
+    /// * 'to' - The potential ancestor frame
+    pub fn is_ancestor(&self, from: &FrameID, to: &FrameID) -> bool {
+        if !self.reference_frames.has_element(from) {
+            return false;
+        }
+
+        if !self.reference_frames.has_element(to) {
+            return false;
+        }
+
+        if from == to {
+            return true;
+        }
+
+        let mut frame_id = from;
+        while !self.is_body(frame_id) {
+            let parent = match self.parent_of(frame_id) {
+                Ok(f) => f,
+                Err(_) => return false,
+            };
+
+            if parent == to {
+                return true;
+            }
+
+            frame_id = parent;
+        }
+
+        false
+    }
+
+    /// Returns a value indicating if the given [FrameID] points to the body frame.
+    ///
+    /// Note that providing a [FrameID] to a non-existing frame returns 'false'
+    ///
+    /// ## Parameters
+    ///
+    /// * 'frame_id' - The [FrameID] of the joint.
+    pub fn is_body(&self, frame_id: &FrameID) -> bool {
+        self.reference_frames.is_body(frame_id).unwrap_or(false)
+    }
+
+    /// Returns a tuple that describes if the model is valid and if the model is not valid what the issues are.
+    ///
+    /// The issues are the descriptions of the issues found by [MotionModel::validate]. Use
+    /// [MotionModel::validate] to get the issues in a form that can be processed by the caller.
+    pub fn is_valid(&self) -> (bool, Vec<String>) {
+        let report = self.validate();
+        (report.is_valid(), report.messages())
+    }
+
+    /// Returns a value indicating if the given wheel is in contact with the ground.
+    ///
+    /// Wheels are assumed to be in contact with the ground until [MotionModel::update_ground_contact]
+    /// determines otherwise.
+    pub fn is_wheel_in_contact(&self, wheel_frame: &FrameID) -> bool {
+        !self.wheels_without_ground_contact.contains(wheel_frame)
+    }
+
+    /// Returns a value indicating if the given [FrameID] points to the world frame
+    pub fn is_world(&self, frame_id: &FrameID) -> bool {
+        frame_id.is_none()
+    }
+
+    /// Returns the transform from the given reference frame to the destination frame, taking
+    /// into account the current position and orientation of the frame relative to the
+    /// destination frame.
+    ///
+    /// This is the rigid-body equivalent of [MotionModel::homogeneous_transform_between_frames].
+    ///
+    /// ## Parameters
+    ///
+    /// * 'from' - The source element for which the transform is requested
+    /// * 'to' - The target element
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when either [ReferenceFrame] is not part of the
+    ///   model
+    pub fn isometry_between_frames(
+        &self,
+        from: &FrameID,
+        to: &FrameID,
+    ) -> Result<Isometry3<f64>, Error> {
+        let _span = span!(TRACE, "isometry_between_frames", from = %from, to = %to);
+        if !self.reference_frames.has_element(from) {
+            return Err(Error::MissingFrameElement { id: *from });
+        }
+
+        if !self.reference_frames.has_element(to) {
+            return Err(Error::MissingFrameElement { id: *to });
+        }
+
+        if from == to {
+            return Ok(Isometry3::identity());
+        }
+
+        // If 'to' is an ancestor then we can just calculate the stack
+        if self.is_ancestor(from, to) {
+            return self.isometry_to_ancestor(from, to);
+        }
+
+        // 'to' is in another branch. Calculate both stacks and invert the stack of 'to'
+        let from_to_body = self.isometry_to_body(from)?;
+        let to_to_body = self.isometry_to_body(to)?;
+        Ok(to_to_body.inverse() * from_to_body)
+    }
+
+    /// Returns the transform from the given reference frame to a parent element further up the
+    /// chain, taking into account the current position and orientation of the frame relative to
+    /// the parent frame.
+    ///
+    /// This is the rigid-body equivalent of [MotionModel::homogeneous_transform_to_ancestor].
+    ///
+    /// ## Parameters
+    ///
+    /// * 'from' - The source element for which the transform is requested
+    /// * 'to' - The target parent element.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the [ReferenceFrame] is not part of the model
+    ///   or when 'to' is not in the chain from 'from' to the body.
+    pub fn isometry_to_ancestor(
+        &self,
+        from: &FrameID,
+        to: &FrameID,
+    ) -> Result<Isometry3<f64>, Error> {
+        self.isometry_to_ancestor_with(from, to, |id| self.current_joint_position(id))
+    }
+
+    /// Returns the transform from the given reference frame to a parent element further up the
+    /// chain, using the joint positions provided by the 'joint_position' function.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'from' - The source element for which the transform is requested
+    /// * 'to' - The target parent element.
+    /// * 'joint_position' - Returns the position of the joint for a given [FrameID], or [None]
+    ///   if the joint should be assumed to be at its zero position.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the [ReferenceFrame] is not part of the model
+    ///   or when 'to' is not in the chain from 'from' to the body.
+    pub(crate) fn isometry_to_ancestor_with<F>(
+        &self,
+        from: &FrameID,
+        to: &FrameID,
+        joint_position: F,
+    ) -> Result<Isometry3<f64>, Error>
+    where
+        F: Fn(&FrameID) -> Option<f64>,
+    {
+        let _span = span!(TRACE, "isometry_to_ancestor", from = %from, to = %to);
+        if !self.reference_frames.has_element(from) {
+            return Err(Error::MissingFrameElement { id: *from });
+        }
+
+        if !self.reference_frames.has_element(to) {
+            return Err(Error::MissingFrameElement { id: *to });
+        }
+
+        if from == to {
+            return Ok(Isometry3::identity());
+        }
+
+        let mut transform = Isometry3::identity();
+        let mut parent_element = self.reference_frames.parent_of(from)?;
+        let mut child_element = self.reference_frames.element(from)?;
         while child_element.id() != to {
             let dof = child_element.degree_of_freedom_kind();
 
@@ -1132,14 +4440,16 @@ impl MotionModel {
                 .reference_frames
                 .homogeneous_transform_to_parent(child_element.id())?;
 
-            let actuator_option = self.actuators.get(child_element.id());
-            let current_transform = if actuator_option.is_some() {
-                let local_transform =
-                    self.transform_for_motion(actuator_option.unwrap(), dof, transform_result);
-
-                local_transform.to_homogeneous()
-            } else {
-                transform_result.to_homogeneous()
+            let table_option = self.kinematics_tables.get(child_element.id());
+            let current_transform = match (table_option, joint_position(child_element.id())) {
+                (Some(table), position) => {
+                    // Joints without a known position are assumed to be at their zero position
+                    table.transform_at(position.unwrap_or(0.0))
+                }
+                (None, Some(position)) => {
+                    self.transform_for_motion(position, dof, transform_result)
+                }
+                (None, None) => *transform_result,
             };
 
             transform = current_transform * transform;
@@ -1165,9 +4475,10 @@ impl MotionModel {
         Ok(transform)
     }
 
-    /// Returns the homogeneous transform matrix from the given reference frame to the
-    /// body frame, taking into account the current position and orientation of the
-    /// frame relative to the body frame.
+    /// Returns the transform from the given reference frame to the body frame, taking into
+    /// account the current position and orientation of the frame relative to the body frame.
+    ///
+    /// This is the rigid-body equivalent of [MotionModel::homogeneous_transform_to_body].
     ///
     /// ## Parameters
     ///
@@ -1176,348 +4487,587 @@ impl MotionModel {
     /// ## Errors
     ///
     /// * [Error::MissingFrameElement] - Returned when the [ReferenceFrame] is not part of the model
-    pub fn homogeneous_transform_to_body(
-        &self,
-        starting_element: &FrameID,
-    ) -> Result<Matrix4<f64>, Error> {
+    pub fn isometry_to_body(&self, starting_element: &FrameID) -> Result<Isometry3<f64>, Error> {
         let body_frame = self.body()?;
-        self.homogeneous_transform_to_ancestor(starting_element, body_frame)
+        self.isometry_to_ancestor(starting_element, body_frame)
     }
 
-    /// Returns the homogeneous transform matrix from the given reference frame to the
-    /// parent frame, taking into account the current position and orientation of the
-    /// frame relative to the parent frame.
+    /// Returns the transform from the given reference frame to the body frame at the given
+    /// time, using the positions of the joints at that time as stored in the history of the
+    /// joints, see [MotionModel::joint_state_at].
+    ///
+    /// This is the rigid-body equivalent of [MotionModel::homogeneous_transform_to_body_at].
     ///
     /// ## Parameters
     ///
     /// * 'starting_element' - The source element for which the transform is requested
+    /// * 'time' - The time at which the transform is requested.
     ///
     /// ## Errors
     ///
-    pub fn homogeneous_transform_to_parent(
+    /// * [Error::MissingFrameElement] - Returned when the [ReferenceFrame] is not part of the model
+    /// * [Error::MissingJointStateHistory] - Returned when the history of one of the joints
+    ///   between the frame and the body does not contain the given time.
+    pub fn isometry_to_body_at(
         &self,
         starting_element: &FrameID,
-    ) -> Result<Matrix4<f64>, Error> {
+        time: Instant,
+    ) -> Result<Isometry3<f64>, Error> {
+        let body_frame = self.body()?;
+
+        // Look up all the joint positions first so that a missing history is reported
+        let mut joint_positions = HashMap::new();
+        for frame_id in self.chain_to_body(starting_element)? {
+            if self.actuators.contains_key(frame_id) || self.sensors.contains_key(frame_id) {
+                let state = self.joint_state_at(frame_id, time)?;
+                joint_positions.insert(*frame_id, state.position());
+            }
+        }
+
+        self.isometry_to_ancestor_with(starting_element, body_frame, |id| {
+            joint_positions.get(id).copied()
+        })
+    }
+
+    /// Returns the transform from the given reference frame to its parent frame, taking into
+    /// account the current position and orientation of the frame relative to the parent frame.
+    /// The transform of the body is the identity.
+    ///
+    /// This is the rigid-body equivalent of [MotionModel::homogeneous_transform_to_parent].
+    ///
+    /// ## Parameters
+    ///
+    /// * 'starting_element' - The source element for which the transform is requested
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the [ReferenceFrame] is not part of the model
+    pub fn isometry_to_parent(&self, starting_element: &FrameID) -> Result<Isometry3<f64>, Error> {
         if !self.reference_frames.has_element(starting_element) {
             return Err(Error::MissingFrameElement {
                 id: *starting_element,
             });
         }
 
-        let is_body = self.reference_frames.is_body(starting_element)?;
-        if is_body {
-            return Ok(Matrix4::<f64>::identity());
+        if self.reference_frames.is_body(starting_element)? {
+            return Ok(Isometry3::identity());
         }
 
         let parent = self.parent_of(starting_element)?;
-        self.homogeneous_transform_to_ancestor(starting_element, parent)
+        self.isometry_to_ancestor(starting_element, parent)
     }
 
-    /// Returns the [FrameID] of the parent of the given element.
+    /// Returns the transform from the given reference frame to the world frame, taking into
+    /// account the current position and orientation of the frame relative to the body frame and
+    /// the pose of the body in the world.
+    ///
+    /// This is the rigid-body equivalent of [MotionModel::homogeneous_transform_to_world].
     ///
     /// ## Parameters
     ///
-    /// * 'frame_id' - The [FrameID] of the element from which the parent [FrameID] should be returned.
+    /// * 'starting_element' - The source element for which the transform is requested
     ///
     /// ## Errors
     ///
     /// * [Error::MissingFrameElement] - Returned when the [ReferenceFrame] is not part of the model
-    pub fn parent_of(&self, frame_id: &FrameID) -> Result<&FrameID, Error> {
-        if !self.reference_frames.has_element(frame_id) {
-            return Err(Error::MissingFrameElement { id: *frame_id });
-        }
+    pub fn isometry_to_world(&self, starting_element: &FrameID) -> Result<Isometry3<f64>, Error> {
+        let to_body = self.isometry_to_body(starting_element)?;
+        Ok(self.body_pose_in_world * to_body)
+    }
 
-        let parent = self.reference_frames.parent_of(frame_id)?;
-        Ok(parent.id())
+    /// Returns an iterator that visits the frames of the model in breadth-first order, starting at
+    /// the body. All the frames at a given depth are visited before the frames at the next depth.
+    /// The iterator is empty when the model has no body.
+    pub fn iter_bfs(&self) -> FrameTraversal<'_> {
+        self.reference_frames.iter_bfs()
     }
 
-    /// Returns the [ReferenceFrame] for a given joint
-    ///
-    /// ## Parameters
-    ///
-    /// * 'frame_id' - The [FrameID] of the [ReferenceFrame] that should be returned.
+    /// Returns an iterator that visits the frames of the model in depth-first order, starting at
+    /// the body. Each frame is visited before its children and all the descendants of a frame are
+    /// visited before the next sibling of the frame. The iterator is empty when the model has no
+    /// body.
     ///
-    /// ## Errors
+    /// ## Examples
     ///
-    /// /// * [Error::MissingFrameElement] - Returned when the [ReferenceFrame] is not part of the model
-    pub fn reference_frame(&self, frame_id: &FrameID) -> Result<&ReferenceFrame, Error> {
-        if !self.reference_frames.has_element(frame_id) {
-            return Err(Error::MissingFrameElement { id: *frame_id });
+    /// ```
+    /// use nalgebra::{Matrix3, Translation3, UnitQuaternion, Vector3};
+    /// use swerve_vehicle_descriptors::model_elements::model::{
+    ///     ChassisElementPhysicalProperties, MotionModel,
+    /// };
+    ///
+    /// let properties = ChassisElementPhysicalProperties::from_mass_properties(
+    ///     1.0,
+    ///     Vector3::zeros(),
+    ///     Matrix3::identity(),
+    /// );
+    ///
+    /// let mut model = MotionModel::new();
+    /// let body = model
+    ///     .add_body(
+    ///         "body".to_string(),
+    ///         Translation3::identity(),
+    ///         UnitQuaternion::identity(),
+    ///         properties,
+    ///     )
+    ///     .unwrap();
+    /// let mount = model
+    ///     .add_static_chassis_element(
+    ///         "mount".to_string(),
+    ///         body,
+    ///         Translation3::new(0.5, 0.0, 0.0),
+    ///         UnitQuaternion::identity(),
+    ///         properties,
+    ///     )
+    ///     .unwrap();
+    ///
+    /// let frames: Vec<_> = model.iter_dfs().collect();
+    /// assert_eq!(vec![(&body, 0), (&mount, 1)], frames);
+    /// ```
+    pub fn iter_dfs(&self) -> FrameTraversal<'_> {
+        self.reference_frames.iter_dfs()
+    }
+
+    /// Returns a new [MotionModel] instance.
+    pub fn new() -> Self {
+        Self {
+            reference_frames: KinematicTree::new(),
+            #[cfg(feature = "serde")]
+            schema_version: super::migration::CURRENT_SCHEMA_VERSION,
+            chassis_elements: HashMap::new(),
+            steering_frame_to_wheel: HashMap::new(),
+            wheel_to_steering_frame: HashMap::new(),
+            actuators: HashMap::new(),
+            sensors: HashMap::new(),
+            joint_constraints: HashMap::new(),
+            joint_calibrations: HashMap::new(),
+            joint_transmissions: HashMap::new(),
+            history_capacities: HashMap::new(),
+            sensor_mounts: HashMap::new(),
+            collision_geometries: HashMap::new(),
+            allowed_collisions: Vec::new(),
+            visual_geometries: HashMap::new(),
+            imu_frame: None,
+            imu_orientation: None,
+            audit_mode: false,
+            wheel_properties: HashMap::new(),
+            kinematics_tables: HashMap::new(),
+            suspension_droop_positions: HashMap::new(),
+            suspension_springs: HashMap::new(),
+            ground_contact_thresholds: GroundContactThresholds::default(),
+            wheels_without_ground_contact: BTreeSet::new(),
+            ground_contact_senders: Vec::new(),
+            operating_modes: HashMap::new(),
+            active_operating_mode: None,
+            revision: 0,
+            body_pose_in_world: Isometry3::identity(),
+            structural_change_senders: Vec::new(),
+            state_fusion: default_state_fusion(),
+            event_publisher: ModelEventPublisher::default(),
+            last_validity: None,
+            frame_metadata: HashMap::new(),
+            frame_aliases: BTreeMap::new(),
+            stable_ids: HashMap::new(),
+            drive_modules: Vec::new(),
+            multi_dof_joints: Vec::new(),
+            loop_closures: Vec::new(),
+            parameter_bindings: Vec::new(),
+            parameters: ParameterSet::new(),
+            gravity: default_gravity(),
+            external_wrenches: HashMap::new(),
         }
+    }
 
-        self.reference_frames.element(frame_id)
+    /// Returns the number of elements with a joint constraint.
+    pub fn number_of_joint_constraints(&self) -> usize {
+        self.joint_constraints.len()
     }
 
-    /// Returns the [FrameID] of the steering frame that is linked to the given wheel frame
-    ///
-    /// ## Parameters
-    ///
-    /// * 'wheel_frame' - The [FrameID] of the wheel for which the steering frame should be located.
-    ///
-    ///  ## Errors
+    /// Returns the number of wheels the robot has.
+    pub fn number_of_wheels(&self) -> usize {
+        self.reference_frames.number_of_wheels()
+    }
+
+    /// Invalidates the data that depends on the part of the model that was changed and
+    /// notifies the subscribers of the change.
     ///
-    /// * [Error::MissingFrameElement] - Returned when the [ReferenceFrame] is not part of the model.
-    /// * [Error::NoSteeringFramesInChain] - Returned when there is no steering frame attached to the wheel.
-    pub fn steering_frame_for_wheel(&self, wheel_frame: &FrameID) -> Result<&FrameID, Error> {
-        if !self.reference_frames.has_element(wheel_frame) {
-            return Err(Error::MissingFrameElement { id: *wheel_frame });
-        }
+    /// All the derived data of the model should be invalidated here so that there is only one
+    /// place that needs to know which data depends on which part of the model.
+    pub(super) fn invalidate(&mut self, change: ModelChange) {
+        self.revision += 1;
 
-        let id_ref = match self.wheel_to_steering_frame.get(wheel_frame) {
-            Some(i) => i,
-            None => return Err(Error::NoSteeringFramesInChain { id: *wheel_frame }),
-        };
+        let mut invalidated_frames = Vec::new();
+        if let ModelChange::FrameAdded { frame } | ModelChange::FrameRemoved { frame } = change {
+            // The parent may have gained or lost children, e.g. it may have stopped being a wheel
+            if let Ok(parent) = self.reference_frames.parent_of(&frame) {
+                invalidated_frames.push(*parent.id());
+            }
+        }
+        invalidated_frames.extend(self.reference_frames.subtree_of(change.frame()));
 
-        Ok(id_ref)
-    }
+        // The ground contact state was determined with the old geometry, so assume that the
+        // affected wheels are in contact until the next update.
+        for frame in invalidated_frames.iter() {
+            self.wheels_without_ground_contact.remove(frame);
+        }
 
-    /// Returns a list of [FrameID] of all the wheels
-    pub fn wheels(&self) -> Result<Vec<&FrameID>, Error> {
-        let list = self.reference_frames.wheels()?.map(|f| f.id()).collect();
-        Ok(list)
-    }
+        let event = ModelChangeEvent::new(change, self.revision, invalidated_frames);
 
-    /// Indicates whether there are any actuated joints between the steering frames and the body frame
-    /// or the wheel frame and the steering frame.
-    pub fn has_active_suspension(&self) -> bool {
-        let number_of_actuators = self.actuators.len();
-        let number_of_wheels = self.reference_frames.number_of_wheels();
+        // Drop the subscribers that are no longer listening
+        self.structural_change_senders
+            .retain(|sender| sender.send(event.clone()).is_ok());
 
-        // Both the wheels and the steering frames are actuated, so if there are
-        // more actuators then there are wheels and steering frames then we have
-        // active suspension
-        number_of_actuators > 2 * number_of_wheels
+        self.event_publisher
+            .publish(ModelEvent::StructureChanged { change });
+        self.publish_validity_change();
     }
 
-    /// Indicates whether the given joint has a sensor
-    ///
-    /// ## Parameters
-    ///
-    /// * 'frame_id' - The [FrameID] of the joint.
-    pub fn has_sensor(&self, frame_id: &FrameID) -> bool {
-        self.sensors.contains_key(frame_id)
+    /// Validates the model and notifies the subscribers if the outcome differs from the outcome
+    /// of the last validation.
+    fn publish_validity_change(&mut self) {
+        // The validity is only tracked once there are subscribers, validating the model after
+        // each change is not free
+        if let Some(was_valid) = self.last_validity {
+            let is_valid = self.validate().is_valid();
+            if is_valid != was_valid {
+                self.last_validity = Some(is_valid);
+                self.event_publisher
+                    .publish(ModelEvent::ValidationChanged { is_valid });
+            }
+        }
     }
 
-    /// Returns a value indicating if the joint with the given [FrameID] is an actuated joint
-    ///
-    /// ## Parameters
-    ///
-    /// * 'frame_id' - The [FrameID] of the joint.
-    pub fn is_actuated(&self, frame_id: &FrameID) -> bool {
-        self.actuators.contains_key(frame_id)
+    /// Stores the actuator for the given frame element and links it to the subscribers of the
+    /// model. Returns the actuator that was replaced, if any.
+    fn insert_actuator(&mut self, frame_id: FrameID, mut actuator: Actuator) -> Option<Actuator> {
+        if let Some(calibration) = self.joint_calibrations.get(&frame_id) {
+            actuator.set_calibration(*calibration);
+        }
+        if let Some(transmission) = self.joint_transmissions.get(&frame_id) {
+            actuator.set_transmission(*transmission);
+        }
+        if let Some(capacity) = self.history_capacities.get(&frame_id) {
+            actuator.set_history_capacity(*capacity);
+        }
+
+        actuator.set_event_target(Some((frame_id, self.event_publisher.clone())));
+        let previous = self.actuators.insert(frame_id, actuator);
+        if let Some(previous) = previous.as_ref() {
+            previous.set_event_target(None);
+        }
+
+        previous
     }
 
-    /// Returns a value indicating if the given 'to' frame is an ancestor of the 'from' frame.
-    ///
-    /// ## Parameters
-    ///
-    /// * 'from' - The starting frame
-    /// * 'to' - The potential ancestor frame
-    pub fn is_ancestor(&self, from: &FrameID, to: &FrameID) -> bool {
-        if !self.reference_frames.has_element(from) {
-            return false;
+    /// Stores the sensor for the given frame element and links it to the subscribers of the
+    /// model. Returns the sensor that was replaced, if any.
+    fn insert_sensor(&mut self, frame_id: FrameID, mut sensor: JointSensor) -> Option<JointSensor> {
+        if let Some(calibration) = self.joint_calibrations.get(&frame_id) {
+            sensor.set_calibration(*calibration);
+        }
+        if let Some(transmission) = self.joint_transmissions.get(&frame_id) {
+            sensor.set_transmission(*transmission);
+        }
+        if let Some(capacity) = self.history_capacities.get(&frame_id) {
+            sensor.set_history_capacity(*capacity);
         }
 
-        if !self.reference_frames.has_element(to) {
-            return false;
+        sensor.set_event_target(Some((frame_id, self.event_publisher.clone())));
+        let previous = self.sensors.insert(frame_id, sensor);
+        if let Some(previous) = previous.as_ref() {
+            previous.set_event_target(None);
         }
 
-        if from == to {
-            return true;
+        previous
+    }
+
+    /// Removes the data that is stored for the given frame element, e.g. the actuator and the
+    /// joint constraint. The frame element should already have been removed from the
+    /// kinematic tree.
+    fn remove_element_data(&mut self, frame_id: &FrameID) {
+        self.chassis_elements.remove(frame_id);
+        if let Some(actuator) = self.actuators.remove(frame_id) {
+            actuator.set_event_target(None);
+        }
+        if let Some(sensor) = self.sensors.remove(frame_id) {
+            sensor.set_event_target(None);
+        }
+        self.joint_constraints.remove(frame_id);
+        self.joint_calibrations.remove(frame_id);
+        self.joint_transmissions.remove(frame_id);
+        self.history_capacities.remove(frame_id);
+        self.sensor_mounts.remove(frame_id);
+        self.collision_geometries.remove(frame_id);
+        self.allowed_collisions
+            .retain(|(first, second)| first != frame_id && second != frame_id);
+        self.visual_geometries.remove(frame_id);
+        if self.imu_frame.as_ref() == Some(frame_id) {
+            self.imu_frame = None;
+            self.imu_orientation = None;
+        }
+        self.wheel_properties.remove(frame_id);
+        self.kinematics_tables.remove(frame_id);
+        self.suspension_droop_positions.remove(frame_id);
+        self.suspension_springs.remove(frame_id);
+        self.frame_metadata.remove(frame_id);
+        self.frame_aliases.retain(|_, id| id != frame_id);
+        self.stable_ids.remove(frame_id);
+        self.external_wrenches.remove(frame_id);
+        self.wheels_without_ground_contact.remove(frame_id);
+        self.drive_modules
+            .retain(|(_, frames)| !frames.contains(frame_id));
+        self.multi_dof_joints
+            .retain(|joint| !joint.contains(frame_id));
+        self.loop_closures
+            .retain(|closure| !closure.contains(frame_id));
+        self.parameter_bindings
+            .retain(|binding| binding.frame() != frame_id);
+
+        for mode in self.operating_modes.values_mut() {
+            mode.remove_suspension_stiffness(frame_id);
         }
 
-        let mut frame_id = from;
-        while !self.is_body(frame_id) {
-            let parent = match self.parent_of(frame_id) {
-                Ok(f) => f,
-                Err(_) => return false,
-            };
+        self.steering_frame_to_wheel.remove(frame_id);
+        if let Some(steering_frame) = self.wheel_to_steering_frame.remove(frame_id) {
+            // The steering frame no longer has a wheel
+            if let Some(wheel) = self.steering_frame_to_wheel.get_mut(&steering_frame) {
+                *wheel = FrameID::none();
+            }
+        }
+    }
 
-            if parent == to {
+    /// Returns a value indicating if the chain of parents of the given element ends at the body.
+    fn is_connected_to_body(&self, frame_id: &FrameID) -> bool {
+        // A chain that is longer than the number of elements contains a cycle
+        let mut current = *frame_id;
+        for _ in 0..=self.reference_frames.elements.len() {
+            if self.is_body(&current) {
                 return true;
             }
 
-            frame_id = parent;
+            match self.reference_frames.parent_of(&current) {
+                Ok(parent) => current = *parent.id(),
+                Err(_) => return false,
+            }
         }
 
         false
     }
 
-    /// Returns a value indicating if the given [FrameID] points to the body frame.
-    ///
-    /// Note that providing a [FrameID] to a non-existing frame returns 'false'
-    ///
-    /// ## Parameters
-    ///
-    /// * 'frame_id' - The [FrameID] of the joint.
-    pub fn is_body(&self, frame_id: &FrameID) -> bool {
-        self.reference_frames.is_body(frame_id).unwrap_or(false)
+    /// Returns a value indicating if the given frame has the [FrameRole::Steering] role.
+    fn is_steering_frame(&self, frame_id: &FrameID) -> bool {
+        self.reference_frames
+            .element(frame_id)
+            .map_or(false, |frame| frame.role() == FrameRole::Steering)
     }
 
-    /// Returns a tuple that describes if the model is valid and if the model is not valid what the issues are.
-    ///
-    /// It is expected that the model meets the following conditions:
-    /// - At least 3 wheels
-    /// - Each wheel rotates around its y-axis
-    /// - Each wheel has exactly 1 steering element
-    /// - Each steering element rotates around its z-axis
-    pub fn is_valid(&self) -> (bool, Vec<String>) {
-        let mut result: Vec<String> = vec![];
+    /// Returns the number of steering frames in the chain between the given wheel and the body.
+    fn number_of_steering_frames_in_chain(&self, wheel_frame: &FrameID) -> usize {
+        let mut result = 0;
+        let mut current = *wheel_frame;
+        for _ in 0..=self.reference_frames.elements.len() {
+            if self.is_steering_frame(&current) {
+                result += 1;
+            }
 
-        // There should be at least two wheels
-        let wheels_result = self.wheels();
-        if wheels_result.is_err() {
-            result.push(String::from(
-                "Swerve model needs at least 2 wheel. Found 0 wheels.",
-            ));
-            return (false, result);
+            match self.reference_frames.parent_of(&current) {
+                Ok(parent) if !self.is_body(&current) => current = *parent.id(),
+                _ => break,
+            }
         }
 
-        let wheels = wheels_result.unwrap();
-        if wheels.len() < 2 {
-            result.push(format!(
-                "Swerve model needs at least 2 wheels. Found {} wheels.",
-                wheels.len()
-            ));
-        }
+        result
+    }
 
-        for w in wheels {
-            // Each wheel rotates in the xz-plane
-            let wheel_dof_result = self.frame_degree_of_freedom(w);
-            if wheel_dof_result.is_err() {
-                result.push(format!("Swerve model expects wheels to rotate around the y-axis. Wheel {} has no degrees of freedom.", w))
-            } else {
-                let dof = wheel_dof_result.unwrap();
-                if dof != FrameDofType::RevoluteY {
-                    result.push(format!("Swerve model expects wheels to rotate around the y-axis. Steering joint {} has degree of freedom: {:#?}.", w, dof));
+    /// Returns a value indicating if all the suspension frames with a droop position in the
+    /// chain between the given wheel and the body are at full droop. Returns false if there
+    /// are no suspension frames with a droop position in the chain.
+    fn is_suspension_at_full_droop(
+        &self,
+        wheel_frame: &FrameID,
+        joint_states: &HashMap<FrameID, JointState>,
+    ) -> Result<bool, Error> {
+        let mut has_suspension = false;
+        let mut frame = self.parent_of(wheel_frame)?;
+        while !self.is_body(frame) {
+            if let Some(droop_position) = self.suspension_droop_positions.get(frame) {
+                let position = joint_states
+                    .get(frame)
+                    .map(|s| s.position())
+                    .or_else(|| self.current_joint_position(frame))
+                    .unwrap_or(0.0);
+                if !self
+                    .ground_contact_thresholds
+                    .is_at_full_droop(position, *droop_position)
+                {
+                    return Ok(false);
                 }
-            }
 
-            // Each wheel should have one, and exactly one steering joint
-            let steering_joint_option = self.wheel_to_steering_frame.get(w);
-            if steering_joint_option.is_none() {
-                result.push(format!("Swerve model expects one steering frame for each wheel. Wheel {} does not have a steering frame.", w));
-                continue;
+                has_suspension = true;
             }
 
-            let steering_joint = steering_joint_option.unwrap();
-
-            // Each steering joint has a z-rotation
-            let steering_joint_dof_result = self.frame_degree_of_freedom(steering_joint);
-            if steering_joint_dof_result.is_err() {
-                result.push(format!("Swerve model expects steering joints to rotate around the z-axis. Steering joint {} has no degrees of freedom.", steering_joint));
-            } else {
-                let dof = steering_joint_dof_result.unwrap();
-                if dof != FrameDofType::RevoluteZ {
-                    result.push(format!("Swerve model expects steering joints to rotate around the z-axis. Steering joint {} has degree of freedom: {:#?}.", steering_joint, dof));
-                }
-            }
+            frame = self.parent_of(frame)?;
         }
 
-        for (key, value) in self.steering_frame_to_wheel.iter() {
-            if value.is_none() {
-                result.push(format!("Swerve model expects each steering joint to be connected to a wheel. Steering joint {} is not connected to a wheel.", key));
-            }
-        }
+        Ok(has_suspension)
+    }
 
-        (result.is_empty(), result)
+    /// Returns the number of times the states of the actuator and the sensor for the given
+    /// frame have been updated, or [None] if the joint has neither an actuator nor a sensor.
+    pub(crate) fn joint_state_version(&self, frame_id: &FrameID) -> Option<u64> {
+        let actuator = self
+            .actuators
+            .get(frame_id)
+            .map(|actuator| actuator.state_version());
+        let sensor = self
+            .sensors
+            .get(frame_id)
+            .map(|sensor| sensor.state_version());
+        match (actuator, sensor) {
+            (Some(a), Some(s)) => Some(a.wrapping_add(s)),
+            (a, s) => a.or(s),
+        }
     }
 
-    /// Returns a value indicating if the given [FrameID] points to the world frame
-    pub fn is_world(&self, frame_id: &FrameID) -> bool {
-        frame_id.is_none()
+    /// Returns the current position of the joint for the given frame without waiting for the
+    /// locks on the joint states. Returns [None] if the joint has neither an actuator nor a
+    /// sensor, or if the state of the joint is being updated by the hardware at this moment.
+    pub(crate) fn try_current_joint_position(&self, frame_id: &FrameID) -> Option<f64> {
+        let state = match (self.actuators.get(frame_id), self.sensors.get(frame_id)) {
+            (Some(actuator), Some(sensor)) => self.state_fusion.fuse(
+                actuator.try_value()?,
+                sensor.try_value()?,
+                actuator.numberspace(),
+            ),
+            (Some(actuator), None) => actuator.try_value()?,
+            (None, Some(sensor)) => sensor.try_value()?,
+            (None, None) => return None,
+        };
+
+        Some(state.position())
     }
 
-    /// Returns a new [MotionModel] instance.
-    pub fn new() -> Self {
-        Self {
-            reference_frames: KinematicTree::new(),
-            chassis_elements: HashMap::new(),
-            steering_frame_to_wheel: HashMap::new(),
-            wheel_to_steering_frame: HashMap::new(),
-            actuators: HashMap::new(),
-            sensors: HashMap::new(),
-            joint_constraints: HashMap::new(),
+    /// Verifies that the given sensor can be used for the joint of the given frame.
+    fn verify_sensor_compatibility(
+        &self,
+        frame_id: &FrameID,
+        sensor: &JointSensor,
+    ) -> Result<(), Error> {
+        if !self.reference_frames.has_element(frame_id) {
+            return Err(Error::MissingFrameElement { id: *frame_id });
         }
-    }
 
-    /// Returns the number of elements with a joint constraint.
-    pub fn number_of_joint_constraints(&self) -> usize {
-        self.joint_constraints.len()
+        let is_angular = matches!(
+            sensor.number_space_type(),
+            NumberSpaceType::AngularLimited { .. }
+        );
+        let dof = self
+            .reference_frames
+            .element(frame_id)?
+            .degree_of_freedom_kind();
+        if !dof.is_revolute() && !dof.is_prismatic() {
+            return Err(Error::InvalidFrameID { id: *frame_id });
+        }
+
+        let constraint = self.joint_constraint(frame_id)?;
+        if (dof.is_prismatic() && is_angular)
+            || !covers_position_limits(sensor.number_space_type(), &constraint)
+        {
+            return Err(Error::IncompatibleNumberSpace { id: *frame_id });
+        }
+
+        Ok(())
     }
 
-    /// Returns the number of wheels the robot has.
-    pub fn number_of_wheels(&self) -> usize {
-        self.reference_frames.number_of_wheels()
+    /// Returns the current position of the joint for the given frame, or [None] if the joint
+    /// has neither an actuator nor a sensor. See [MotionModel::joint_state_for].
+    pub(crate) fn current_joint_position(&self, frame_id: &FrameID) -> Option<f64> {
+        if !self.actuators.contains_key(frame_id) && !self.sensors.contains_key(frame_id) {
+            return None;
+        }
+
+        Some(match self.joint_state_for(frame_id) {
+            Ok(v) => v.position(),
+            Err(_) => 0.0,
+        })
     }
 
-    fn transform_for_motion(
+    /// Returns the transform to the parent of a joint of the given kind that has moved the given
+    /// distance from its zero position, where 'transform' is the transform at the zero position.
+    pub(crate) fn transform_for_motion(
         &self,
-        actuator: &Actuator,
+        position: f64,
         dof: FrameDofType,
         transform: &Isometry3<f64>,
     ) -> Isometry3<f64> {
         match dof {
-            FrameDofType::RevoluteX => self.transform_for_revolute_x_motion(actuator, transform),
-            FrameDofType::RevoluteY => self.transform_for_revolute_y_motion(actuator, transform),
-            FrameDofType::RevoluteZ => self.transform_for_revolute_z_motion(actuator, transform),
-            FrameDofType::PrismaticX => self.transform_for_prismatic_x_motion(actuator, transform),
-            FrameDofType::PrismaticY => self.transform_for_prismatic_y_motion(actuator, transform),
-            FrameDofType::PrismaticZ => self.transform_for_prismatic_z_motion(actuator, transform),
+            FrameDofType::RevoluteX => self.transform_for_revolute_x_motion(position, transform),
+            FrameDofType::RevoluteY => self.transform_for_revolute_y_motion(position, transform),
+            FrameDofType::RevoluteZ => self.transform_for_revolute_z_motion(position, transform),
+            FrameDofType::PrismaticX => self.transform_for_prismatic_x_motion(position, transform),
+            FrameDofType::PrismaticY => self.transform_for_prismatic_y_motion(position, transform),
+            FrameDofType::PrismaticZ => self.transform_for_prismatic_z_motion(position, transform),
+            FrameDofType::RevoluteAxis(axis) => {
+                self.transform_for_revolute_axis_motion(position, &axis, transform)
+            }
+            FrameDofType::PrismaticAxis(axis) => {
+                self.transform_for_prismatic_axis_motion(position, &axis, transform)
+            }
             _ => Isometry3::identity(),
         }
     }
 
+    fn transform_for_prismatic_axis_motion(
+        &self,
+        distance_moved: f64,
+        axis: &Unit<Vector3<f64>>,
+        transform: &Isometry3<f64>,
+    ) -> Isometry3<f64> {
+        let trans = Translation3::from(axis.into_inner() * distance_moved);
+        trans * transform
+    }
+
     fn transform_for_prismatic_x_motion(
         &self,
-        actuator: &Actuator,
+        distance_moved: f64,
         transform: &Isometry3<f64>,
     ) -> Isometry3<f64> {
-        let distance_moved = match actuator.value() {
-            Ok(v) => v.position(),
-            Err(_) => 0.0,
-        };
         let trans = Translation3::new(distance_moved, 0.0, 0.0);
         trans * transform
     }
 
     fn transform_for_prismatic_y_motion(
         &self,
-        actuator: &Actuator,
+        distance_moved: f64,
         transform: &Isometry3<f64>,
     ) -> Isometry3<f64> {
-        let distance_moved = match actuator.value() {
-            Ok(v) => v.position(),
-            Err(_) => 0.0,
-        };
         let trans = Translation3::new(0.0, distance_moved, 0.0);
         trans * transform
     }
 
     fn transform_for_prismatic_z_motion(
         &self,
-        actuator: &Actuator,
+        distance_moved: f64,
         transform: &Isometry3<f64>,
     ) -> Isometry3<f64> {
-        let distance_moved = match actuator.value() {
-            Ok(v) => v.position(),
-            Err(_) => 0.0,
-        };
-
         let trans = Translation3::new(0.0, 0.0, distance_moved);
         trans * transform
     }
 
-    fn transform_for_revolute_x_motion(
+    fn transform_for_revolute_axis_motion(
         &self,
-        actuator: &Actuator,
+        distance_rotated: f64,
+        axis: &Unit<Vector3<f64>>,
         transform: &Isometry3<f64>,
     ) -> Isometry3<f64> {
-        let distance_rotated = match actuator.value() {
-            Ok(v) => v.position(),
-            Err(_) => 0.0,
-        };
+        let rotation = UnitQuaternion::from_axis_angle(axis, distance_rotated);
+        rotation * transform
+    }
 
+    fn transform_for_revolute_x_motion(
+        &self,
+        distance_rotated: f64,
+        transform: &Isometry3<f64>,
+    ) -> Isometry3<f64> {
         // Rotation matrix for rotation around the x-axis is:
         //
         // [1    0           0      ]
@@ -1530,14 +5080,9 @@ impl MotionModel {
 
     fn transform_for_revolute_y_motion(
         &self,
-        actuator: &Actuator,
+        distance_rotated: f64,
         transform: &Isometry3<f64>,
     ) -> Isometry3<f64> {
-        let distance_rotated = match actuator.value() {
-            Ok(v) => v.position(),
-            Err(_) => 0.0,
-        };
-
         // Rotation matrix for rotation around the y-axis is:
         //
         // [ cos(θ)    0    sin(θ) ]
@@ -1550,14 +5095,9 @@ impl MotionModel {
 
     fn transform_for_revolute_z_motion(
         &self,
-        actuator: &Actuator,
+        distance_rotated: f64,
         transform: &Isometry3<f64>,
     ) -> Isometry3<f64> {
-        let distance_rotated = match actuator.value() {
-            Ok(v) => v.position(),
-            Err(_) => 0.0,
-        };
-
         // Rotation matrix for rotation around the z-axis is:
         //
         // [ cos(θ)   -sin(θ)   0 ]
@@ -1573,3 +5113,42 @@ impl Default for MotionModel {
         Self::new()
     }
 }
+
+/// Returns a value indicating whether a number space can represent all the positions that are
+/// allowed by the position limits of the constraint. Only a [NumberSpaceType::LinearLimited]
+/// space has bounds that can be narrower than the travel of the joint.
+fn covers_position_limits(
+    number_space_type: NumberSpaceType,
+    constraint: &JointConstraint,
+) -> bool {
+    match (number_space_type, constraint.position_limits()) {
+        (NumberSpaceType::LinearLimited { minimum, maximum }, Some((lower, upper))) => {
+            minimum <= lower && upper <= maximum
+        }
+        _ => true,
+    }
+}
+
+/// Returns the acceleration due to gravity that is used when no other value was set.
+fn default_gravity() -> Vector3<f64> {
+    Vector3::new(0.0, 0.0, -STANDARD_GRAVITY)
+}
+
+/// Returns the [StateFusion] that is used when no other policy was set.
+fn default_state_fusion() -> Box<dyn StateFusion> {
+    Box::new(PreferActuator)
+}
+
+/// Returns a value indicating whether the given matrices are equal within the relative
+/// [INERTIA_TOLERANCE]. Matrices that contain values that are not finite are never equal.
+fn is_approximately_equal<const R: usize, const C: usize>(
+    first: &na::SMatrix<f64, R, C>,
+    second: &na::SMatrix<f64, R, C>,
+) -> bool {
+    if !first.iter().chain(second.iter()).all(|v| v.is_finite()) {
+        return false;
+    }
+
+    let scale = first.amax().max(second.amax()).max(1.0);
+    (first - second).amax() <= INERTIA_TOLERANCE * scale
+}
@@ -0,0 +1,167 @@
+//! Defines the types used to notify consumers of structural changes to a
+//! [MotionModel](super::model::MotionModel).
+//!
+//! A structural change is any change to the geometry or the composition of the model, e.g.
+//! adding a frame element or changing the transform between a frame element and its parent.
+//! Structural changes make the data that was derived from the model stale. The model invalidates
+//! its own derived data, e.g. the ground contact state of the affected wheels, when the change
+//! is made. Consumers that keep their own derived data can subscribe to the changes with
+//! [MotionModel::structural_changes](super::model::MotionModel::structural_changes) or compare
+//! the [revision](super::model::MotionModel::revision) of the model with the revision at which
+//! their data was computed.
+
+use super::frame_elements::FrameID;
+
+/// Defines the structural changes that can be made to a [MotionModel](super::model::MotionModel).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ModelChange {
+    /// Indicates that the actuator of a frame element was replaced.
+    ActuatorChanged {
+        /// The ID of the frame element for which the actuator was replaced.
+        frame: FrameID,
+    },
+
+    /// Indicates that a frame element was added to the model.
+    FrameAdded {
+        /// The ID of the frame element that was added.
+        frame: FrameID,
+    },
+
+    /// Indicates that a frame element was removed from the model. The frame elements that were
+    /// attached to it were either removed as well or attached to its parent.
+    FrameRemoved {
+        /// The ID of the frame element that was removed.
+        frame: FrameID,
+    },
+
+    /// Indicates that the joint constraint of a frame element was changed.
+    JointConstraintChanged {
+        /// The ID of the frame element for which the joint constraint was changed.
+        frame: FrameID,
+    },
+
+    /// Indicates that the kinematics table of a frame element was changed.
+    KinematicsTableChanged {
+        /// The ID of the frame element for which the kinematics table was changed.
+        frame: FrameID,
+    },
+
+    /// Indicates that a loop closure constraint was added to, or removed from, the model.
+    LoopClosureChanged {
+        /// The ID of the first frame of the loop closure that was added or removed.
+        frame: FrameID,
+    },
+
+    /// Indicates that the mass, center of mass or inertia of a frame element was changed.
+    PhysicalPropertiesChanged {
+        /// The ID of the frame element for which the physical properties were changed.
+        frame: FrameID,
+    },
+
+    /// Indicates that the sensor of a frame element was added, replaced or removed.
+    SensorChanged {
+        /// The ID of the frame element for which the sensor was changed.
+        frame: FrameID,
+    },
+
+    /// Indicates that the full droop position of a suspension frame element was changed.
+    SuspensionDroopPositionChanged {
+        /// The ID of the suspension frame element.
+        frame: FrameID,
+    },
+
+    /// Indicates that the spring of a suspension frame element was set or removed.
+    SuspensionSpringChanged {
+        /// The ID of the suspension frame element.
+        frame: FrameID,
+    },
+
+    /// Indicates that the transform between a frame element and its parent was changed.
+    TransformToParentChanged {
+        /// The ID of the frame element for which the transform was changed.
+        frame: FrameID,
+    },
+
+    /// Indicates that the properties of a wheel, e.g. the width, were changed.
+    WheelPropertiesChanged {
+        /// The ID of the wheel frame element.
+        wheel: FrameID,
+    },
+
+    /// Indicates that the radius of a wheel was changed.
+    WheelRadiusChanged {
+        /// The ID of the wheel frame element.
+        wheel: FrameID,
+    },
+}
+
+impl ModelChange {
+    /// Returns the [FrameID] of the frame element that was changed.
+    pub fn frame(&self) -> &FrameID {
+        match self {
+            ModelChange::ActuatorChanged { frame } => frame,
+            ModelChange::FrameAdded { frame } => frame,
+            ModelChange::FrameRemoved { frame } => frame,
+            ModelChange::JointConstraintChanged { frame } => frame,
+            ModelChange::KinematicsTableChanged { frame } => frame,
+            ModelChange::LoopClosureChanged { frame } => frame,
+            ModelChange::PhysicalPropertiesChanged { frame } => frame,
+            ModelChange::SensorChanged { frame } => frame,
+            ModelChange::SuspensionDroopPositionChanged { frame } => frame,
+            ModelChange::SuspensionSpringChanged { frame } => frame,
+            ModelChange::TransformToParentChanged { frame } => frame,
+            ModelChange::WheelPropertiesChanged { wheel } => wheel,
+            ModelChange::WheelRadiusChanged { wheel } => wheel,
+        }
+    }
+}
+
+/// Describes a structural change to a [MotionModel](super::model::MotionModel) and the data
+/// that was invalidated by the change.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ModelChangeEvent {
+    /// The change that was made.
+    change: ModelChange,
+
+    /// The revision of the model after the change was made.
+    revision: u64,
+
+    /// The frame elements for which the derived data is no longer valid.
+    invalidated_frames: Vec<FrameID>,
+}
+
+impl ModelChangeEvent {
+    /// Returns the change that was made to the model.
+    pub fn change(&self) -> &ModelChange {
+        &self.change
+    }
+
+    /// Returns the [FrameID] of the frame elements for which the derived data, e.g. the transform
+    /// to the body or the contribution to the mass properties of the vehicle, is no longer valid.
+    ///
+    /// This is the changed frame element and all the frame elements that are attached to it,
+    /// directly or indirectly.
+    pub fn invalidated_frames(&self) -> &[FrameID] {
+        &self.invalidated_frames
+    }
+
+    /// Create a new [ModelChangeEvent] instance.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'change' - The change that was made to the model.
+    /// * 'revision' - The revision of the model after the change was made.
+    /// * 'invalidated_frames' - The frame elements for which the derived data is no longer valid.
+    pub fn new(change: ModelChange, revision: u64, invalidated_frames: Vec<FrameID>) -> Self {
+        Self {
+            change,
+            revision,
+            invalidated_frames,
+        }
+    }
+
+    /// Returns the revision of the model after the change was made.
+    pub fn revision(&self) -> u64 {
+        self.revision
+    }
+}
@@ -0,0 +1,117 @@
+//! Defines the events that a [MotionModel](super::model::MotionModel) sends to its subscribers.
+//!
+//! The structural changes, see the [model_change](super::model_change) module, only describe
+//! changes to the geometry and the composition of the model. The events also describe the
+//! changes that are made while the vehicle is moving, e.g. a new state that was received from
+//! the hardware of an actuator or a command that was sent to an actuator. This allows higher
+//! level code to react to the motion of the vehicle instead of having to poll the transforms.
+//!
+//! The joint state events are sent from the thread of the
+//! [HardwareChangeProcessor](crate::change_notification_processing::HardwareChangeProcessor)
+//! that processes the hardware updates, so consumers should not assume that the events arrive
+//! on the thread that owns the model.
+
+use std::sync::{Arc, Mutex};
+
+use crossbeam_channel::{Receiver, Sender};
+
+use super::{frame_elements::FrameID, model_change::ModelChange};
+
+#[cfg(test)]
+#[path = "model_event_tests.rs"]
+mod model_event_tests;
+
+/// Defines the events that are sent by a [MotionModel](super::model::MotionModel).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ModelEvent {
+    /// Indicates that a command was sent to the actuator of a frame element.
+    ActuatorCommandSent {
+        /// The ID of the frame element for which the command was sent.
+        frame: FrameID,
+    },
+
+    /// Indicates that a new state was received from the actuator or the sensor of a frame
+    /// element.
+    JointStateUpdated {
+        /// The ID of the frame element for which the state was received.
+        frame: FrameID,
+    },
+
+    /// Indicates that a structural change was made to the model.
+    StructureChanged {
+        /// The change that was made.
+        change: ModelChange,
+    },
+
+    /// Indicates that the outcome of the validation of the model changed, e.g. because an
+    /// actuator was added to the last frame element that did not have one.
+    ValidationChanged {
+        /// A value indicating whether the model is valid after the change.
+        is_valid: bool,
+    },
+}
+
+impl ModelEvent {
+    /// Returns the [FrameID] of the frame element that the event applies to, or [None] if the
+    /// event applies to the model as a whole.
+    pub fn frame(&self) -> Option<&FrameID> {
+        match self {
+            ModelEvent::ActuatorCommandSent { frame } => Some(frame),
+            ModelEvent::JointStateUpdated { frame } => Some(frame),
+            ModelEvent::StructureChanged { change } => Some(change.frame()),
+            ModelEvent::ValidationChanged { .. } => None,
+        }
+    }
+}
+
+/// Sends [ModelEvent] instances to the subscribers of a model.
+///
+/// The publisher is shared between the model and its actuators and sensors, so that the events
+/// for the hardware updates can be sent from the thread that processes the updates.
+#[derive(Clone, Default)]
+pub(crate) struct ModelEventPublisher {
+    /// The senders used to notify the subscribers.
+    senders: Arc<Mutex<Vec<Sender<ModelEvent>>>>,
+}
+
+impl ModelEventPublisher {
+    /// Sends the given event to all the subscribers.
+    pub(crate) fn publish(&self, event: ModelEvent) {
+        let mut senders = self.senders.lock().unwrap_or_else(|err| err.into_inner());
+
+        // Drop the subscribers that are no longer listening
+        senders.retain(|sender| sender.send(event).is_ok());
+    }
+
+    /// Returns a [Receiver] that receives all the events that are published from now on.
+    pub(crate) fn subscribe(&self) -> Receiver<ModelEvent> {
+        let (sender, receiver) = crossbeam_channel::unbounded();
+        self.senders
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .push(sender);
+        receiver
+    }
+}
+
+/// Links an actuator or a sensor to the frame element it is attached to and to the publisher
+/// of the model that contains the frame element. Empty until the actuator or sensor is added
+/// to a model.
+pub(crate) type ModelEventTarget = Arc<Mutex<Option<(FrameID, ModelEventPublisher)>>>;
+
+/// Returns the frame element of the given target, or [None] if the target has not been added to
+/// a model.
+#[cfg(feature = "tracing")]
+pub(crate) fn target_frame(target: &ModelEventTarget) -> Option<FrameID> {
+    let target = target.lock().unwrap_or_else(|err| err.into_inner());
+    target.as_ref().map(|(frame, _)| *frame)
+}
+
+/// Publishes the event for the frame element of the given target, if the target has been
+/// added to a model.
+pub(crate) fn publish_to_target(target: &ModelEventTarget, event: fn(FrameID) -> ModelEvent) {
+    let target = target.lock().unwrap_or_else(|err| err.into_inner());
+    if let Some((frame, publisher)) = target.as_ref() {
+        publisher.publish(event(*frame));
+    }
+}
@@ -0,0 +1,64 @@
+use std::sync::{Arc, Mutex};
+
+use crate::model_elements::{frame_elements::FrameID, model_change::ModelChange};
+
+use super::{publish_to_target, ModelEvent, ModelEventPublisher, ModelEventTarget};
+
+#[test]
+fn when_getting_the_frame_of_an_event_it_should_return_the_frame_of_the_change() {
+    let frame = FrameID::new();
+
+    assert_eq!(
+        Some(&frame),
+        ModelEvent::ActuatorCommandSent { frame }.frame()
+    );
+    assert_eq!(
+        Some(&frame),
+        ModelEvent::JointStateUpdated { frame }.frame()
+    );
+    assert_eq!(
+        Some(&frame),
+        ModelEvent::StructureChanged {
+            change: ModelChange::FrameAdded { frame }
+        }
+        .frame()
+    );
+    assert_eq!(
+        None,
+        ModelEvent::ValidationChanged { is_valid: true }.frame()
+    );
+}
+
+#[test]
+fn when_publishing_an_event_it_should_send_the_event_to_the_subscribers() {
+    let publisher = ModelEventPublisher::default();
+    let first = publisher.subscribe();
+    let second = publisher.subscribe();
+    drop(second);
+
+    let frame = FrameID::new();
+    publisher.publish(ModelEvent::JointStateUpdated { frame });
+    assert_eq!(
+        ModelEvent::JointStateUpdated { frame },
+        first.try_recv().unwrap()
+    );
+    assert_eq!(1, publisher.senders.lock().unwrap().len());
+}
+
+#[test]
+fn when_publishing_to_a_target_it_should_only_publish_once_attached() {
+    let target: ModelEventTarget = Arc::new(Mutex::new(None));
+    let publisher = ModelEventPublisher::default();
+    let receiver = publisher.subscribe();
+
+    publish_to_target(&target, |frame| ModelEvent::JointStateUpdated { frame });
+    assert!(receiver.try_recv().is_err());
+
+    let frame = FrameID::new();
+    *target.lock().unwrap() = Some((frame, publisher));
+    publish_to_target(&target, |frame| ModelEvent::ActuatorCommandSent { frame });
+    assert_eq!(
+        ModelEvent::ActuatorCommandSent { frame },
+        receiver.try_recv().unwrap()
+    );
+}
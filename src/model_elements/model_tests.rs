@@ -1,23 +1,44 @@
-use std::{f64::consts::PI, time::Duration};
+use std::{
+    collections::HashMap,
+    f64::consts::PI,
+    time::{Duration, Instant},
+};
 
 use crossbeam_channel::{Receiver, Sender};
 use float_cmp::{ApproxEq, F64Margin};
-use nalgebra::{Matrix3, Matrix4, Matrix6, RowVector4, Translation3, UnitQuaternion, Vector3};
+use nalgebra::{
+    Isometry3, Matrix3, Matrix4, Matrix6, RowVector4, Translation3, Unit, UnitQuaternion, Vector3,
+};
 
 use crate::{
-    change_notification_processing::{ChangeID, HardwareChangeProcessor},
+    change_notification_processing::{ChangeID, ChangeNotifier, HardwareChangeProcessor},
     hardware::{
         actuator_interface::{ActuatorAvailableRatesOfChange, HardwareActuator},
-        joint_state::JointState,
+        command_middleware::CommandOutcome,
+        health::{ActuatorFault, ActuatorHealth},
+        joint_state::{JointState, JointStateRange},
+        sensor_interface::HardwareSensor,
+        state_fusion::{PreferSensor, WeightedAverage},
+        state_middleware::{LinearConversion, StateOutcome},
     },
-    model_elements::frame_elements::{
-        Actuator, FrameDofType, FrameID, JointConstraint, ReferenceFrame,
+    model_elements::{
+        frame_elements::{
+            Actuator, ConstraintEnforcement, FrameDofType, FrameID, FrameRole, JointCalibration,
+            JointConstraint, JointSensor, JointTransmission, ReferenceFrame,
+        },
+        ground_contact::GroundContactEvent,
+        kinematics_table::{JointKinematicsTable, TableInterpolation},
+        model_change::ModelChange,
+        model_event::ModelEvent,
+        operating_mode::OperatingMode,
+        validation::ValidationIssue,
+        wrench::{Wrench, STANDARD_GRAVITY},
     },
     number_space::NumberSpaceType,
     Error,
 };
 
-use super::{ChassisElementPhysicalProperties, KinematicTree, MotionModel};
+use super::{ChassisElementPhysicalProperties, KinematicTree, MotionModel, WheelProperties};
 
 fn create_generic_non_actuated_element(name: String) -> ReferenceFrame {
     let degree_of_freedom_kind = FrameDofType::PrismaticX;
@@ -580,6 +601,55 @@ fn when_getting_the_body_with_no_frame_elements_it_should_error() {
     };
 }
 
+#[test]
+fn when_getting_elements_from_an_inconsistent_kinematic_tree_it_should_error() {
+    let mut tree = KinematicTree::new();
+
+    let body_element = create_generic_non_actuated_element("body".to_string());
+    let body_id = *body_element.id();
+
+    let wheel_element = create_wheel_element("wheel".to_string());
+    let wheel_id = *wheel_element.id();
+
+    tree.add_element(
+        body_element,
+        FrameID::none(),
+        Translation3::<f64>::identity(),
+        UnitQuaternion::identity(),
+    )
+    .unwrap();
+    tree.add_element(
+        wheel_element,
+        body_id,
+        Translation3::<f64>::identity(),
+        UnitQuaternion::identity(),
+    )
+    .unwrap();
+
+    // Break the invariants of the tree by removing the wheel from the stored elements while
+    // leaving the links to the wheel in place.
+    let wheel = tree.elements.remove(&wheel_id).unwrap();
+
+    match tree.children_of(&body_id) {
+        Ok(_) => panic!("Retrieved the children of an element with a missing child."),
+        Err(e) => assert!(matches!(e, Error::InternalInconsistency { .. })),
+    };
+
+    match tree.wheels() {
+        Ok(_) => panic!("Retrieved the wheels when a wheel was missing."),
+        Err(e) => assert!(matches!(e, Error::InternalInconsistency { .. })),
+    };
+
+    // Restore the wheel and remove the body instead.
+    tree.elements.insert(wheel_id, wheel);
+    tree.elements.remove(&body_id);
+
+    match tree.parent_of(&wheel_id) {
+        Ok(_) => panic!("Retrieved a parent that is not stored in the tree."),
+        Err(e) => assert!(matches!(e, Error::InternalInconsistency { .. })),
+    };
+}
+
 #[test]
 fn when_getting_the_children_it_should_return_all_the_directly_connected_elements() {
     let mut tree = KinematicTree::new();
@@ -1145,7 +1215,7 @@ struct MockHardwareActuator {
     receiver: Receiver<(JointState, ActuatorAvailableRatesOfChange)>,
     sender: Sender<(JointState, ActuatorAvailableRatesOfChange)>,
     command_sender: Sender<JointState>,
-    update_sender: Option<Sender<ChangeID>>,
+    update_sender: Option<ChangeNotifier>,
     id: Option<ChangeID>,
 }
 
@@ -1164,7 +1234,7 @@ impl HardwareActuator for MockHardwareActuator {
         Ok(self.command_sender.clone())
     }
 
-    fn on_change(&mut self, id: ChangeID, sender: Sender<ChangeID>) {
+    fn on_change(&mut self, id: ChangeID, sender: ChangeNotifier) {
         self.id = Some(id);
         self.update_sender = Some(sender);
     }
@@ -1174,6 +1244,58 @@ impl HardwareActuator for MockHardwareActuator {
     }
 }
 
+struct MockHardwareSensor {
+    receiver: Receiver<JointState>,
+    sender: Sender<JointState>,
+    motion_type: NumberSpaceType,
+    update_sender: Option<ChangeNotifier>,
+    id: Option<ChangeID>,
+}
+
+impl MockHardwareSensor {
+    fn new(motion_type: NumberSpaceType) -> Self {
+        let (sender, receiver) = crossbeam_channel::unbounded();
+        Self {
+            receiver,
+            sender,
+            motion_type,
+            update_sender: None,
+            id: None,
+        }
+    }
+
+    fn send(&self, state: JointState) {
+        self.sender.send(state).unwrap();
+        self.update_sender
+            .as_ref()
+            .unwrap()
+            .send(self.id.unwrap())
+            .unwrap();
+    }
+}
+
+impl HardwareSensor for MockHardwareSensor {
+    fn current_state_receiver(&self) -> Result<Receiver<JointState>, Error> {
+        Ok(self.receiver.clone())
+    }
+
+    fn joint_motion_type(&self) -> NumberSpaceType {
+        self.motion_type
+    }
+
+    fn joint_range(&self) -> JointStateRange {
+        JointStateRange::new(
+            JointState::new(-100.0, None, None, None),
+            JointState::new(100.0, None, None, None),
+        )
+    }
+
+    fn on_change(&mut self, id: ChangeID, sender: ChangeNotifier) {
+        self.id = Some(id);
+        self.update_sender = Some(sender);
+    }
+}
+
 fn add_actuated_joint_to_model(
     model: &mut MotionModel,
     parent_id: &FrameID,
@@ -1220,7 +1342,12 @@ fn add_body_to_model(model: &mut MotionModel) -> Result<FrameID, Error> {
     let mass = 1.0;
     let center_of_mass = Vector3::<f64>::identity();
     let moment_of_inertia = Matrix3::<f64>::identity();
-    let spatial_inertia = Matrix6::<f64>::identity();
+    let spatial_inertia = ChassisElementPhysicalProperties::from_mass_properties(
+        mass,
+        center_of_mass,
+        moment_of_inertia,
+    )
+    .spatial_inertia();
 
     let physical_properties = ChassisElementPhysicalProperties::new(
         mass,
@@ -1255,7 +1382,12 @@ fn add_steering_to_model(
     let mass = 1.0;
     let center_of_mass = Vector3::<f64>::identity();
     let moment_of_inertia = Matrix3::<f64>::identity();
-    let spatial_inertia = Matrix6::<f64>::identity();
+    let spatial_inertia = ChassisElementPhysicalProperties::from_mass_properties(
+        mass,
+        center_of_mass,
+        moment_of_inertia,
+    )
+    .spatial_inertia();
 
     let physical_properties = ChassisElementPhysicalProperties::new(
         mass,
@@ -1291,7 +1423,12 @@ fn add_suspension_to_model(
     let mass = 1.0;
     let center_of_mass = Vector3::<f64>::identity();
     let moment_of_inertia = Matrix3::<f64>::identity();
-    let spatial_inertia = Matrix6::<f64>::identity();
+    let spatial_inertia = ChassisElementPhysicalProperties::from_mass_properties(
+        mass,
+        center_of_mass,
+        moment_of_inertia,
+    )
+    .spatial_inertia();
 
     let physical_properties = ChassisElementPhysicalProperties::new(
         mass,
@@ -1326,7 +1463,12 @@ fn add_wheel_to_model(
     let mass = 1.0;
     let center_of_mass = Vector3::<f64>::identity();
     let moment_of_inertia = Matrix3::<f64>::identity();
-    let spatial_inertia = Matrix6::<f64>::identity();
+    let spatial_inertia = ChassisElementPhysicalProperties::from_mass_properties(
+        mass,
+        center_of_mass,
+        moment_of_inertia,
+    )
+    .spatial_inertia();
 
     let physical_properties = ChassisElementPhysicalProperties::new(
         mass,
@@ -1345,6 +1487,42 @@ fn add_wheel_to_model(
     )
 }
 
+fn add_module_with_suspension_to_model(
+    model: &mut MotionModel,
+    body_id: &FrameID,
+    change_processor: &HardwareChangeProcessor,
+) -> (FrameID, FrameID, Vec<MockHardwareActuator>) {
+    let suspension_id =
+        add_suspension_to_model(model, body_id, DriveModulePosition::LeftFront).unwrap();
+
+    let mut hardware = vec![];
+    for _ in 0..2 {
+        let (sender, receiver) = crossbeam_channel::unbounded();
+        let (cmd_sender, _) = crossbeam_channel::unbounded();
+        hardware.push(MockHardwareActuator {
+            receiver,
+            sender,
+            command_sender: cmd_sender,
+            update_sender: None,
+            id: None,
+        });
+    }
+
+    let steering_actuator = Actuator::new(&mut hardware[0], change_processor).unwrap();
+    let steering_id = add_steering_to_model(
+        model,
+        &suspension_id,
+        DriveModulePosition::LeftFront,
+        steering_actuator,
+    )
+    .unwrap();
+
+    let wheel_actuator = Actuator::new(&mut hardware[1], change_processor).unwrap();
+    let wheel_id = add_wheel_to_model(model, &steering_id, wheel_actuator).unwrap();
+
+    (suspension_id, wheel_id, hardware)
+}
+
 #[test]
 fn when_adding_actuated_chassis_element_it_should_store_the_element() {
     let mut model = MotionModel::new();
@@ -1909,6 +2087,84 @@ fn when_adding_steering_element_it_should_store_the_element() {
     assert_eq!(name, chassis.name());
 }
 
+#[test]
+fn when_adding_steering_element_with_degree_of_freedom_it_should_track_the_steering_frame() {
+    let mut model = MotionModel::new();
+    let body_id = add_body_to_model(&mut model).unwrap();
+    let change_processor = Box::new(HardwareChangeProcessor::new(10));
+
+    let new_actuator = || {
+        let (sender, receiver) = crossbeam_channel::unbounded();
+        let (cmd_sender, _cmd_receiver) = crossbeam_channel::unbounded();
+        let mut hardware_actuator = MockHardwareActuator {
+            receiver,
+            sender,
+            command_sender: cmd_sender,
+            update_sender: None,
+            id: None,
+        };
+        Actuator::new(&mut hardware_actuator, &change_processor).unwrap()
+    };
+
+    let physical_properties = ChassisElementPhysicalProperties::new(
+        1.0,
+        Vector3::<f64>::identity(),
+        Matrix3::<f64>::identity(),
+        Matrix6::<f64>::identity(),
+    );
+
+    assert_eq!(
+        Err(Error::InvalidSteeringDegreeOfFreedom {
+            degree_of_freedom: FrameDofType::PrismaticZ
+        }),
+        model.add_steering_element_with_degree_of_freedom(
+            "a".to_string(),
+            FrameDofType::PrismaticZ,
+            body_id,
+            Translation3::identity(),
+            UnitQuaternion::identity(),
+            physical_properties,
+            new_actuator(),
+        )
+    );
+    assert_eq!(1, model.chassis_elements().count());
+
+    let steering_id = model
+        .add_steering_element_with_degree_of_freedom(
+            "a".to_string(),
+            FrameDofType::RevoluteX,
+            body_id,
+            Translation3::identity(),
+            UnitQuaternion::identity(),
+            physical_properties,
+            new_actuator(),
+        )
+        .unwrap();
+    assert_eq!(
+        FrameDofType::RevoluteX,
+        model.frame_degree_of_freedom(&steering_id).unwrap()
+    );
+
+    let wheel_id = model
+        .add_wheel(
+            "b".to_string(),
+            steering_id,
+            Translation3::new(0.0, 0.0, -0.1),
+            UnitQuaternion::identity(),
+            physical_properties,
+            new_actuator(),
+        )
+        .unwrap();
+    assert_eq!(
+        &steering_id,
+        model.steering_frame_for_wheel(&wheel_id).unwrap()
+    );
+    assert!(!model.validate().issues().iter().any(|issue| matches!(
+        issue,
+        ValidationIssue::InvalidSteeringDegreeOfFreedom { .. }
+    )));
+}
+
 #[test]
 fn when_adding_steering_element_with_invalid_parent_it_should_error() {
     let mut model = MotionModel::new();
@@ -4663,6 +4919,50 @@ fn when_getting_homogeneous_transform_to_parent_with_primatic_z_motion_should_re
     assert_eq!(expected, actuator_to_body_matrix);
 }
 
+#[test]
+fn when_computing_the_motion_of_an_arbitrary_axis_joint_it_should_move_along_the_axis() {
+    let model = MotionModel::new();
+    let transform = Isometry3::from_parts(
+        Translation3::new(1.0, 0.5, 0.0),
+        UnitQuaternion::from_euler_angles(0.0, 0.0, 0.3),
+    );
+
+    // A principal axis gives the same motion as the matching principal degree of freedom
+    let expected = model.transform_for_motion(0.4, FrameDofType::RevoluteX, &transform);
+    let actual = model.transform_for_motion(
+        0.4,
+        FrameDofType::RevoluteAxis(Vector3::x_axis()),
+        &transform,
+    );
+    assert!((expected.to_homogeneous() - actual.to_homogeneous()).norm() < 1e-12);
+
+    let expected = model.transform_for_motion(0.4, FrameDofType::PrismaticZ, &transform);
+    let actual = model.transform_for_motion(
+        0.4,
+        FrameDofType::PrismaticAxis(Vector3::z_axis()),
+        &transform,
+    );
+    assert!((expected.to_homogeneous() - actual.to_homogeneous()).norm() < 1e-12);
+
+    let axis = Unit::new_normalize(Vector3::new(0.0, 1.0, 1.0));
+    let moved = model.transform_for_motion(0.2, FrameDofType::PrismaticAxis(axis), &transform);
+    assert!(
+        (moved.translation.vector - transform.translation.vector - axis.into_inner() * 0.2).norm()
+            < 1e-12
+    );
+    assert_eq!(transform.rotation, moved.rotation);
+
+    // The rotation is around the axis through the origin of the parent frame
+    let rotated =
+        model.transform_for_motion(0.5 * PI, FrameDofType::RevoluteAxis(axis), &transform);
+    let rotation = UnitQuaternion::from_axis_angle(&axis, 0.5 * PI);
+    assert!((rotated.to_homogeneous() - (rotation * transform).to_homogeneous()).norm() < 1e-12);
+    assert!(
+        (rotated.translation.vector.dot(&axis) - transform.translation.vector.dot(&axis)).abs()
+            < 1e-12
+    );
+}
+
 #[test]
 fn when_getting_homogeneous_transform_to_parent_with_revolute_x_motion_should_return_the_transform()
 {
@@ -5158,6 +5458,83 @@ fn when_getting_homogeneous_transform_to_parent_with_revolute_z_motion_should_re
     }
 }
 
+#[test]
+fn when_getting_homogeneous_transform_to_parent_with_kinematics_table_it_should_use_the_table() {
+    let mut model = MotionModel::new();
+    let body_id = add_body_to_model(&mut model).unwrap();
+
+    let (sender, receiver) = crossbeam_channel::unbounded();
+    let (cmd_sender, _) = crossbeam_channel::unbounded();
+    let mut hardware_actuator = MockHardwareActuator {
+        receiver,
+        sender: sender.clone(),
+        command_sender: cmd_sender,
+        update_sender: None,
+        id: None,
+    };
+    let change_processor = Box::new(HardwareChangeProcessor::new(1000));
+
+    let actuator = Actuator::new(&mut hardware_actuator, &change_processor).unwrap();
+    let id = add_actuated_joint_to_model(
+        &mut model,
+        &body_id,
+        DriveModulePosition::LeftFront,
+        FrameDofType::PrismaticZ,
+        actuator,
+    )
+    .unwrap();
+
+    // The wheel moves up and inwards while the joint moves
+    let table = JointKinematicsTable::new(
+        vec![
+            (
+                0.0,
+                Isometry3::from_parts(Translation3::new(1.0, 0.5, 0.0), UnitQuaternion::identity()),
+            ),
+            (
+                1.0,
+                Isometry3::from_parts(Translation3::new(1.0, 0.3, 1.0), UnitQuaternion::identity()),
+            ),
+        ],
+        TableInterpolation::Linear,
+    )
+    .unwrap();
+    model.set_kinematics_table(&id, table).unwrap();
+    assert!(model.kinematics_table(&id).is_some());
+
+    #[rustfmt::skip]
+    let expected_without_motion = Matrix4::new(
+        1.0, 0.0, 0.0, 1.0,
+        0.0, 1.0, 0.0, 0.5,
+        0.0, 0.0, 1.0, 0.0,
+        0.0, 0.0, 0.0, 1.0,
+    );
+    assert_eq!(
+        expected_without_motion,
+        model.homogeneous_transform_to_parent(&id).unwrap()
+    );
+
+    let msg = (
+        JointState::new(0.5, None, None, None),
+        ActuatorAvailableRatesOfChange::new(0.0, 0.0, 0.0, 0.0, 0.0, 0.0),
+    );
+    sender.send(msg).unwrap();
+    hardware_actuator
+        .update_sender
+        .as_ref()
+        .unwrap()
+        .send(hardware_actuator.id.unwrap())
+        .unwrap();
+
+    // Allow some time to ensure the task is not processed
+    std::thread::sleep(Duration::from_millis(20));
+
+    let transform = model.homogeneous_transform_to_parent(&id).unwrap();
+    assert!((transform[(0, 3)] - 1.0).abs() < 1e-12);
+    assert!((transform[(1, 3)] - 0.4).abs() < 1e-12);
+    assert!((transform[(2, 3)] - 0.5).abs() < 1e-12);
+}
+
 #[test]
 fn when_getting_active_suspension_with_actuators_matching_wheels_it_should_return_false() {
     let mut model = MotionModel::new();
@@ -5332,86 +5709,2186 @@ fn when_getting_steering_frame_for_wheel_with_invalid_frame_it_should_error() {
 }
 
 #[test]
-fn when_testing_if_a_frame_is_an_ancestor_it_should_return_false_if_it_is_not() {
+fn when_getting_wheel_radius_without_radius_it_should_error() {
     let mut model = MotionModel::new();
     let body_id = add_body_to_model(&mut model).unwrap();
 
-    // Leg 1
-    let (sender, receiver) = crossbeam_channel::unbounded();
-    let (cmd_sender, _) = crossbeam_channel::unbounded();
-    let mut hardware_actuator = MockHardwareActuator {
-        receiver,
-        sender: sender.clone(),
-        command_sender: cmd_sender,
+    let (steering_sender, steering_receiver) = crossbeam_channel::unbounded();
+    let (steering_cmd_sender, _) = crossbeam_channel::unbounded();
+    let mut steering_hardware_actuator = MockHardwareActuator {
+        receiver: steering_receiver,
+        sender: steering_sender,
+        command_sender: steering_cmd_sender,
         update_sender: None,
         id: None,
     };
-    let change_processor = Box::new(HardwareChangeProcessor::new(1000));
-
-    let actuator = Actuator::new(&mut hardware_actuator, &change_processor).unwrap();
-    let suspension_id_leg1 = add_actuated_joint_to_model(
+    let change_processor = Box::new(HardwareChangeProcessor::new(10));
+    let steering_actuator =
+        Actuator::new(&mut steering_hardware_actuator, &change_processor).unwrap();
+    let steering_id = add_steering_to_model(
         &mut model,
         &body_id,
         DriveModulePosition::LeftFront,
-        FrameDofType::RevoluteZ,
-        actuator,
+        steering_actuator,
     )
     .unwrap();
 
-    let (sender1, receiver1) = crossbeam_channel::unbounded();
-    let (cmd_sender1, _) = crossbeam_channel::unbounded();
-    let mut hardware_actuator1 = MockHardwareActuator {
-        receiver: receiver1,
-        sender: sender1.clone(),
-        command_sender: cmd_sender1,
+    let (wheel_sender, wheel_receiver) = crossbeam_channel::unbounded();
+    let (wheel_cmd_sender, _) = crossbeam_channel::unbounded();
+    let mut wheel_hardware_actuator = MockHardwareActuator {
+        receiver: wheel_receiver,
+        sender: wheel_sender,
+        command_sender: wheel_cmd_sender,
         update_sender: None,
         id: None,
     };
-    let change_processor = Box::new(HardwareChangeProcessor::new(10));
+    let wheel_actuator = Actuator::new(&mut wheel_hardware_actuator, &change_processor).unwrap();
+    let wheel_id = add_wheel_to_model(&mut model, &steering_id, wheel_actuator).unwrap();
 
-    let actuator1 = Actuator::new(&mut hardware_actuator1, &change_processor).unwrap();
+    let result = model.wheel_radius(&wheel_id);
+    assert_eq!(Err(Error::MissingWheelRadius { id: wheel_id }), result);
+}
 
-    let steering_id_leg1 = add_steering_to_model(
-        &mut model,
-        &suspension_id_leg1,
-        DriveModulePosition::LeftFront,
-        actuator1,
+#[test]
+fn when_setting_kinematics_table_with_invalid_frame_it_should_error() {
+    let mut model = MotionModel::new();
+    let body_id = add_body_to_model(&mut model).unwrap();
+
+    let table = JointKinematicsTable::new(
+        vec![(0.0, Isometry3::identity()), (1.0, Isometry3::identity())],
+        TableInterpolation::Linear,
     )
     .unwrap();
 
-    let (wheel_sender1, wheel_receiver1) = crossbeam_channel::unbounded();
-    let (wheel_cmd_sender1, _) = crossbeam_channel::unbounded();
-    let mut wheel_hardware_actuator1 = MockHardwareActuator {
-        receiver: wheel_receiver1,
-        sender: wheel_sender1,
-        command_sender: wheel_cmd_sender1,
-        update_sender: None,
-        id: None,
-    };
+    let body_result = model.set_kinematics_table(&body_id, table.clone());
+    assert_eq!(Err(Error::InvalidFrameID { id: body_id }), body_result);
 
-    let wheel_actuator1 = Actuator::new(&mut wheel_hardware_actuator1, &change_processor).unwrap();
+    let invalid_id = FrameID::new();
+    let missing_result = model.set_kinematics_table(&invalid_id, table);
+    assert_eq!(
+        Err(Error::MissingFrameElement { id: invalid_id }),
+        missing_result
+    );
+    assert!(model.kinematics_table(&invalid_id).is_none());
+}
 
-    let wheel_id_leg1 = add_wheel_to_model(&mut model, &steering_id_leg1, wheel_actuator1).unwrap();
+#[test]
+fn when_adding_operating_mode_with_invalid_frame_it_should_error() {
+    let mut model = MotionModel::new();
+    let body_id = add_body_to_model(&mut model).unwrap();
 
-    // Leg 2
-    let suspension_id_leg2 =
-        add_suspension_to_model(&mut model, &body_id, DriveModulePosition::RightFront).unwrap();
+    let mut valid_mode = OperatingMode::new("indoor".to_string(), 1.0, 1.0, 1.0);
+    valid_mode.set_suspension_stiffness(body_id, 1000.0);
+    assert!(model.add_operating_mode(valid_mode).is_ok());
 
-    let (sender2, receiver2) = crossbeam_channel::unbounded();
-    let (cmd_sender2, _) = crossbeam_channel::unbounded();
-    let mut hardware_actuator2 = MockHardwareActuator {
-        receiver: receiver2,
-        sender: sender2.clone(),
-        command_sender: cmd_sender2,
-        update_sender: None,
-        id: None,
-    };
+    let invalid_id = FrameID::new();
+    let mut invalid_mode = OperatingMode::new("outdoor".to_string(), 1.0, 1.0, 1.0);
+    invalid_mode.set_suspension_stiffness(invalid_id, 1000.0);
+    assert_eq!(
+        Err(Error::MissingFrameElement { id: invalid_id }),
+        model.add_operating_mode(invalid_mode)
+    );
 
-    let actuator2 = Actuator::new(&mut hardware_actuator2, &change_processor).unwrap();
+    let names: Vec<&str> = model.operating_modes().collect();
+    assert_eq!(vec!["indoor"], names);
+    assert!(model.operating_mode().is_none());
+}
 
-    let steering_id_leg2 = add_steering_to_model(
-        &mut model,
-        &suspension_id_leg2,
+#[test]
+fn when_setting_suspension_droop_position_with_invalid_frame_it_should_error() {
+    let mut model = MotionModel::new();
+    let body_id = add_body_to_model(&mut model).unwrap();
+    let change_processor = Box::new(HardwareChangeProcessor::new(10));
+    let (suspension_id, wheel_id, _hardware) =
+        add_module_with_suspension_to_model(&mut model, &body_id, &change_processor);
+
+    assert!(model
+        .set_suspension_droop_position(&suspension_id, -0.1)
+        .is_ok());
+    assert_eq!(
+        Err(Error::InvalidFrameID { id: body_id }),
+        model.set_suspension_droop_position(&body_id, -0.1)
+    );
+    assert_eq!(
+        Err(Error::InvalidFrameID { id: wheel_id }),
+        model.set_suspension_droop_position(&wheel_id, -0.1)
+    );
+
+    let invalid_id = FrameID::new();
+    assert_eq!(
+        Err(Error::MissingFrameElement { id: invalid_id }),
+        model.set_suspension_droop_position(&invalid_id, -0.1)
+    );
+}
+
+#[test]
+fn when_setting_wheel_radius_it_should_store_the_radius() {
+    let mut model = MotionModel::new();
+    let body_id = add_body_to_model(&mut model).unwrap();
+
+    let (steering_sender, steering_receiver) = crossbeam_channel::unbounded();
+    let (steering_cmd_sender, _) = crossbeam_channel::unbounded();
+    let mut steering_hardware_actuator = MockHardwareActuator {
+        receiver: steering_receiver,
+        sender: steering_sender,
+        command_sender: steering_cmd_sender,
+        update_sender: None,
+        id: None,
+    };
+    let change_processor = Box::new(HardwareChangeProcessor::new(10));
+    let steering_actuator =
+        Actuator::new(&mut steering_hardware_actuator, &change_processor).unwrap();
+    let steering_id = add_steering_to_model(
+        &mut model,
+        &body_id,
+        DriveModulePosition::LeftFront,
+        steering_actuator,
+    )
+    .unwrap();
+
+    let (wheel_sender, wheel_receiver) = crossbeam_channel::unbounded();
+    let (wheel_cmd_sender, _) = crossbeam_channel::unbounded();
+    let mut wheel_hardware_actuator = MockHardwareActuator {
+        receiver: wheel_receiver,
+        sender: wheel_sender,
+        command_sender: wheel_cmd_sender,
+        update_sender: None,
+        id: None,
+    };
+    let wheel_actuator = Actuator::new(&mut wheel_hardware_actuator, &change_processor).unwrap();
+    let wheel_id = add_wheel_to_model(&mut model, &steering_id, wheel_actuator).unwrap();
+
+    let result = model.set_wheel_radius(&wheel_id, 0.15);
+    assert!(result.is_ok());
+    assert_eq!(Ok(0.15), model.wheel_radius(&wheel_id));
+
+    let steering_result = model.set_wheel_radius(&steering_id, 0.15);
+    assert!(steering_result.is_err());
+
+    let missing_result = model.set_wheel_radius(&FrameID::new(), 0.15);
+    assert!(missing_result.is_err());
+}
+
+#[test]
+fn when_updating_ground_contact_with_wheel_at_full_droop_and_no_load_it_should_lose_contact() {
+    let mut model = MotionModel::new();
+    let body_id = add_body_to_model(&mut model).unwrap();
+    let change_processor = Box::new(HardwareChangeProcessor::new(10));
+    let (suspension_id, wheel_id, _hardware) =
+        add_module_with_suspension_to_model(&mut model, &body_id, &change_processor);
+
+    model
+        .set_suspension_droop_position(&suspension_id, -0.1)
+        .unwrap();
+    let receiver = model.ground_contact_events();
+
+    assert!(model.is_wheel_in_contact(&wheel_id));
+
+    // Full droop but loaded
+    let mut joint_states = HashMap::new();
+    joint_states.insert(suspension_id, JointState::new(-0.1, None, None, None));
+    let mut wheel_loads = HashMap::new();
+    wheel_loads.insert(wheel_id, 100.0);
+
+    let events = model
+        .update_ground_contact(&joint_states, &wheel_loads)
+        .unwrap();
+    assert!(events.is_empty());
+    assert_eq!(vec![&wheel_id], model.wheels_in_contact().unwrap());
+
+    // Full droop and unloaded
+    wheel_loads.insert(wheel_id, 0.0);
+    let events = model
+        .update_ground_contact(&joint_states, &wheel_loads)
+        .unwrap();
+    assert_eq!(
+        vec![GroundContactEvent::ContactLost { wheel: wheel_id }],
+        events
+    );
+    assert_eq!(
+        GroundContactEvent::ContactLost { wheel: wheel_id },
+        receiver.try_recv().unwrap()
+    );
+    assert!(!model.is_wheel_in_contact(&wheel_id));
+    assert!(model.wheels_in_contact().unwrap().is_empty());
+
+    // No change in state should not send any events
+    let events = model
+        .update_ground_contact(&joint_states, &wheel_loads)
+        .unwrap();
+    assert!(events.is_empty());
+    assert!(receiver.try_recv().is_err());
+
+    // Compressing the suspension restores the contact
+    joint_states.insert(suspension_id, JointState::new(0.0, None, None, None));
+    let events = model
+        .update_ground_contact(&joint_states, &wheel_loads)
+        .unwrap();
+    assert_eq!(
+        vec![GroundContactEvent::ContactRestored { wheel: wheel_id }],
+        events
+    );
+    assert_eq!(
+        GroundContactEvent::ContactRestored { wheel: wheel_id },
+        receiver.try_recv().unwrap()
+    );
+    assert!(model.is_wheel_in_contact(&wheel_id));
+}
+
+#[test]
+fn when_updating_ground_contact_without_droop_position_it_should_stay_in_contact() {
+    let mut model = MotionModel::new();
+    let body_id = add_body_to_model(&mut model).unwrap();
+    let change_processor = Box::new(HardwareChangeProcessor::new(10));
+    let (_, wheel_id, _hardware) =
+        add_module_with_suspension_to_model(&mut model, &body_id, &change_processor);
+
+    let mut wheel_loads = HashMap::new();
+    wheel_loads.insert(wheel_id, 0.0);
+
+    let events = model
+        .update_ground_contact(&HashMap::new(), &wheel_loads)
+        .unwrap();
+    assert!(events.is_empty());
+    assert!(model.is_wheel_in_contact(&wheel_id));
+}
+
+#[test]
+fn when_updating_physical_properties_it_should_replace_the_mass_and_inertia() {
+    let mut model = MotionModel::new();
+    let body_id = add_body_to_model(&mut model).unwrap();
+    let receiver = model.structural_changes();
+
+    let payload = ChassisElementPhysicalProperties::from_mass_properties(
+        25.0,
+        Vector3::new(0.1, 0.0, 0.2),
+        Matrix3::identity() * 2.0,
+    );
+    model.update_physical_properties(&body_id, payload).unwrap();
+
+    let element = model.chassis_element(&body_id).unwrap();
+    assert_eq!(25.0, element.mass_in_kg());
+    assert_eq!(&payload.center_of_mass(), element.center_of_mass());
+    assert_eq!(&(Matrix3::identity() * 2.0), element.moment_of_inertia());
+    assert_eq!(&payload.spatial_inertia, element.spatial_inertia());
+    assert_eq!("body", element.name());
+
+    let event = receiver.try_recv().unwrap();
+    assert_eq!(
+        &ModelChange::PhysicalPropertiesChanged { frame: body_id },
+        event.change()
+    );
+
+    let revision = model.revision();
+    let invalid_id = FrameID::new();
+    assert_eq!(
+        Err(Error::MissingFrameElement { id: invalid_id }),
+        model.update_physical_properties(&invalid_id, payload)
+    );
+    assert_eq!(revision, model.revision());
+}
+
+#[test]
+fn when_setting_transform_to_parent_it_should_update_the_transform() {
+    let mut model = MotionModel::new();
+    let body_id = add_body_to_model(&mut model).unwrap();
+    let change_processor = Box::new(HardwareChangeProcessor::new(10));
+    let (suspension_id, wheel_id, _hardware) =
+        add_module_with_suspension_to_model(&mut model, &body_id, &change_processor);
+
+    let position = Translation3::new(2.0, 1.0, 0.5);
+    let orientation = UnitQuaternion::from_euler_angles(0.0, 0.0, 0.5 * PI);
+    model
+        .set_transform_to_parent(&suspension_id, position, orientation)
+        .unwrap();
+
+    let transform = model
+        .homogeneous_transform_to_parent(&suspension_id)
+        .unwrap();
+    let expected = Isometry3::from_parts(position, orientation).to_homogeneous();
+    assert!((transform - expected).norm() < 1e-12);
+
+    // The wheel moves with the suspension frame
+    let steering_id = *model.steering_frame_for_wheel(&wheel_id).unwrap();
+    let wheel_to_body = model.homogeneous_transform_to_body(&wheel_id).unwrap();
+    let expected_wheel_to_body = expected
+        * model.homogeneous_transform_to_parent(&steering_id).unwrap()
+        * model.homogeneous_transform_to_parent(&wheel_id).unwrap();
+    assert!((wheel_to_body - expected_wheel_to_body).norm() < 1e-12);
+}
+
+#[test]
+fn when_a_calibrated_pose_is_written_back_it_should_update_the_transform_to_the_body() {
+    let mut model = MotionModel::new();
+    let body_id = add_body_to_model(&mut model).unwrap();
+    let change_processor = Box::new(HardwareChangeProcessor::new(10));
+    let (suspension_id, wheel_id, _hardware) =
+        add_module_with_suspension_to_model(&mut model, &body_id, &change_processor);
+
+    // Read the transforms first so that the write-back has to replace the cached values
+    let wheel_to_body = model.homogeneous_transform_to_body(&wheel_id).unwrap();
+    let suspension_to_body = model.homogeneous_transform_to_body(&suspension_id).unwrap();
+    let revision = model.revision();
+
+    let position = Translation3::new(1.01, 0.49, 0.0);
+    let orientation = UnitQuaternion::from_euler_angles(0.0, 0.0, 0.01);
+    model
+        .set_transform_to_parent(&suspension_id, position, orientation)
+        .unwrap();
+    assert!(model.revision() > revision);
+
+    let calibrated_position = model.isometry_to_body(&suspension_id).unwrap().translation;
+    assert!((calibrated_position.vector - Vector3::new(1.01, 0.49, 0.0)).norm() < 1e-12);
+
+    // The wheel keeps its pose relative to the suspension frame
+    let expected = Isometry3::from_parts(position, orientation).to_homogeneous()
+        * suspension_to_body.try_inverse().unwrap()
+        * wheel_to_body;
+    let actual = model.homogeneous_transform_to_body(&wheel_id).unwrap();
+    assert!((expected - actual).norm() < 1e-12);
+}
+
+#[test]
+fn when_setting_transform_to_parent_with_invalid_frame_it_should_error() {
+    let mut model = MotionModel::new();
+    let body_id = add_body_to_model(&mut model).unwrap();
+    let revision = model.revision();
+
+    assert_eq!(
+        Err(Error::InvalidFrameID { id: body_id }),
+        model.set_transform_to_parent(
+            &body_id,
+            Translation3::identity(),
+            UnitQuaternion::identity()
+        )
+    );
+
+    let invalid_id = FrameID::new();
+    assert_eq!(
+        Err(Error::MissingFrameElement { id: invalid_id }),
+        model.set_transform_to_parent(
+            &invalid_id,
+            Translation3::identity(),
+            UnitQuaternion::identity()
+        )
+    );
+
+    assert_eq!(revision, model.revision());
+}
+
+#[test]
+fn when_changing_the_structure_of_the_model_it_should_notify_subscribers() {
+    let mut model = MotionModel::new();
+    let receiver = model.structural_changes();
+
+    let body_id = add_body_to_model(&mut model).unwrap();
+    let event = receiver.try_recv().unwrap();
+    assert_eq!(&ModelChange::FrameAdded { frame: body_id }, event.change());
+    assert_eq!(vec![body_id], event.invalidated_frames());
+    assert_eq!(1, event.revision());
+
+    let change_processor = Box::new(HardwareChangeProcessor::new(10));
+    let (suspension_id, wheel_id, _hardware) =
+        add_module_with_suspension_to_model(&mut model, &body_id, &change_processor);
+    let steering_id = *model.steering_frame_for_wheel(&wheel_id).unwrap();
+
+    let added: Vec<ModelChange> = receiver.try_iter().map(|e| *e.change()).collect();
+    assert_eq!(
+        vec![
+            ModelChange::FrameAdded {
+                frame: suspension_id
+            },
+            ModelChange::FrameAdded { frame: steering_id },
+            ModelChange::FrameAdded { frame: wheel_id },
+        ],
+        added
+    );
+    assert_eq!(4, model.revision());
+
+    model.set_wheel_radius(&wheel_id, 0.1).unwrap();
+    let event = receiver.try_recv().unwrap();
+    assert_eq!(
+        &ModelChange::WheelRadiusChanged { wheel: wheel_id },
+        event.change()
+    );
+    assert_eq!(vec![wheel_id], event.invalidated_frames());
+
+    model
+        .set_transform_to_parent(
+            &suspension_id,
+            Translation3::new(1.0, 1.0, 0.0),
+            UnitQuaternion::identity(),
+        )
+        .unwrap();
+    let event = receiver.try_recv().unwrap();
+    assert_eq!(
+        &ModelChange::TransformToParentChanged {
+            frame: suspension_id
+        },
+        event.change()
+    );
+    assert_eq!(
+        vec![suspension_id, steering_id, wheel_id],
+        event.invalidated_frames()
+    );
+    assert_eq!(6, event.revision());
+    assert_eq!(6, model.revision());
+    assert!(receiver.try_recv().is_err());
+}
+
+#[test]
+fn when_changing_the_structure_of_the_model_it_should_reset_the_ground_contact() {
+    let mut model = MotionModel::new();
+    let body_id = add_body_to_model(&mut model).unwrap();
+    let change_processor = Box::new(HardwareChangeProcessor::new(10));
+    let (suspension_id, wheel_id, _hardware) =
+        add_module_with_suspension_to_model(&mut model, &body_id, &change_processor);
+
+    model
+        .set_suspension_droop_position(&suspension_id, -0.1)
+        .unwrap();
+
+    let mut joint_states = HashMap::new();
+    joint_states.insert(suspension_id, JointState::new(-0.1, None, None, None));
+    let mut wheel_loads = HashMap::new();
+    wheel_loads.insert(wheel_id, 0.0);
+    model
+        .update_ground_contact(&joint_states, &wheel_loads)
+        .unwrap();
+    assert!(!model.is_wheel_in_contact(&wheel_id));
+
+    model
+        .set_transform_to_parent(
+            &suspension_id,
+            Translation3::new(1.0, 0.5, 0.1),
+            UnitQuaternion::identity(),
+        )
+        .unwrap();
+    assert!(model.is_wheel_in_contact(&wheel_id));
+
+    // The next update determines the contact state with the new geometry
+    let events = model
+        .update_ground_contact(&joint_states, &wheel_loads)
+        .unwrap();
+    assert_eq!(
+        vec![GroundContactEvent::ContactLost { wheel: wheel_id }],
+        events
+    );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn when_serializing_a_model_it_should_round_trip() {
+    let mut model = MotionModel::new();
+    let body_id = add_body_to_model(&mut model).unwrap();
+    let change_processor = Box::new(HardwareChangeProcessor::new(10));
+    let (suspension_id, wheel_id, mut hardware) =
+        add_module_with_suspension_to_model(&mut model, &body_id, &change_processor);
+    let steering_id = *model.steering_frame_for_wheel(&wheel_id).unwrap();
+
+    model.set_wheel_radius(&wheel_id, 0.1).unwrap();
+    model
+        .set_suspension_droop_position(&suspension_id, -0.1)
+        .unwrap();
+    model
+        .set_kinematics_table(
+            &suspension_id,
+            JointKinematicsTable::new(
+                vec![
+                    (-0.1, Isometry3::translation(1.0, 0.5, -0.1)),
+                    (0.1, Isometry3::translation(1.1, 0.5, 0.1)),
+                ],
+                TableInterpolation::Linear,
+            )
+            .unwrap(),
+        )
+        .unwrap();
+
+    let mut mode = OperatingMode::new("indoor".to_string(), 1.0, 1.0, 0.5);
+    mode.set_suspension_stiffness(suspension_id, 1000.0);
+    model.add_operating_mode(mode.clone()).unwrap();
+    model.set_operating_mode("indoor").unwrap();
+    let calibration = JointCalibration::new(0.25, 1.0).unwrap();
+    model.set_calibration(&steering_id, calibration).unwrap();
+    let transmission = JointTransmission::new(true, 3.0).unwrap();
+    model.set_transmission(&wheel_id, transmission).unwrap();
+
+    let json = serde_json::to_string(&model).unwrap();
+    let mut loaded: MotionModel = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(&body_id, loaded.body().unwrap());
+    assert_eq!(vec![&wheel_id], loaded.wheels().unwrap());
+    assert_eq!(&suspension_id, loaded.parent_of(&steering_id).unwrap());
+    assert_eq!(
+        &steering_id,
+        loaded.steering_frame_for_wheel(&wheel_id).unwrap()
+    );
+    assert_eq!(
+        model.reference_frame(&wheel_id).unwrap().name(),
+        loaded.reference_frame(&wheel_id).unwrap().name()
+    );
+    assert!(
+        (model.homogeneous_transform_to_parent(&steering_id).unwrap()
+            - loaded
+                .homogeneous_transform_to_parent(&steering_id)
+                .unwrap())
+        .norm()
+            < 1e-12
+    );
+    assert_eq!(
+        model.chassis_element(&wheel_id).unwrap().mass_in_kg(),
+        loaded.chassis_element(&wheel_id).unwrap().mass_in_kg()
+    );
+    assert_eq!(0.1, loaded.wheel_radius(&wheel_id).unwrap());
+    assert_eq!(
+        model.kinematics_table(&suspension_id),
+        loaded.kinematics_table(&suspension_id)
+    );
+    assert_eq!(Some(&mode), loaded.operating_mode());
+
+    // The actuators are not serialized
+    assert!(loaded.actuator_for(&wheel_id).is_err());
+    let (is_valid, errors) = loaded.is_valid();
+    assert!(!is_valid);
+    assert_eq!(
+        2,
+        errors
+            .iter()
+            .filter(|e| e.contains("does not have an actuator"))
+            .count()
+    );
+
+    let wheel_actuator = Actuator::new(&mut hardware[1], &change_processor).unwrap();
+    loaded.set_actuator(&wheel_id, wheel_actuator).unwrap();
+    let steering_actuator = Actuator::new(&mut hardware[0], &change_processor).unwrap();
+    loaded
+        .set_actuator(&steering_id, steering_actuator)
+        .unwrap();
+    assert!(loaded.actuator_for(&wheel_id).is_ok());
+    assert_eq!(
+        calibration,
+        loaded.actuator_for(&steering_id).unwrap().calibration()
+    );
+    assert_eq!(
+        transmission,
+        loaded.actuator_for(&wheel_id).unwrap().transmission()
+    );
+    assert!(
+        (model.homogeneous_transform_to_body(&wheel_id).unwrap()
+            - loaded.homogeneous_transform_to_body(&wheel_id).unwrap())
+        .norm()
+            < 1e-12
+    );
+}
+
+#[test]
+fn when_setting_actuator_with_invalid_frame_it_should_error() {
+    let mut model = MotionModel::new();
+    let body_id = add_body_to_model(&mut model).unwrap();
+    let change_processor = Box::new(HardwareChangeProcessor::new(10));
+    let (suspension_id, _, mut hardware) =
+        add_module_with_suspension_to_model(&mut model, &body_id, &change_processor);
+
+    let actuator = Actuator::new(&mut hardware[0], &change_processor).unwrap();
+    assert_eq!(
+        Err(Error::InvalidFrameID { id: suspension_id }),
+        model.set_actuator(&suspension_id, actuator)
+    );
+
+    let invalid_id = FrameID::new();
+    let actuator = Actuator::new(&mut hardware[1], &change_processor).unwrap();
+    assert_eq!(
+        Err(Error::MissingFrameElement { id: invalid_id }),
+        model.set_actuator(&invalid_id, actuator)
+    );
+}
+
+#[test]
+fn when_replacing_actuator_it_should_return_the_previous_actuator() {
+    let mut model = MotionModel::new();
+    let receiver = model.structural_changes();
+    let body_id = add_body_to_model(&mut model).unwrap();
+    let change_processor = Box::new(HardwareChangeProcessor::new(10));
+    let (_, wheel_id, mut hardware) =
+        add_module_with_suspension_to_model(&mut model, &body_id, &change_processor);
+    let revision = model.revision();
+    let _ = receiver.try_iter().count();
+
+    let actuator = Actuator::new(&mut hardware[1], &change_processor).unwrap();
+    let previous = model.replace_actuator(&wheel_id, actuator).unwrap();
+    assert!(previous.is_some());
+    assert!(model.actuator_for(&wheel_id).is_ok());
+    assert_eq!(revision + 1, model.revision());
+
+    let event = receiver.try_recv().unwrap();
+    assert_eq!(
+        &ModelChange::ActuatorChanged { frame: wheel_id },
+        event.change()
+    );
+}
+
+#[test]
+fn when_replacing_actuator_with_invalid_frame_it_should_error() {
+    let mut model = MotionModel::new();
+    let body_id = add_body_to_model(&mut model).unwrap();
+    let change_processor = Box::new(HardwareChangeProcessor::new(10));
+    let (suspension_id, _, mut hardware) =
+        add_module_with_suspension_to_model(&mut model, &body_id, &change_processor);
+
+    let actuator = Actuator::new(&mut hardware[0], &change_processor).unwrap();
+    assert!(matches!(
+        model.replace_actuator(&suspension_id, actuator),
+        Err(Error::InvalidFrameID { id }) if id == suspension_id
+    ));
+
+    let invalid_id = FrameID::new();
+    let actuator = Actuator::new(&mut hardware[1], &change_processor).unwrap();
+    assert!(matches!(
+        model.replace_actuator(&invalid_id, actuator),
+        Err(Error::MissingFrameElement { id }) if id == invalid_id
+    ));
+}
+
+// Polls the condition until it is true, for at most two seconds, so that the tests do not depend
+// on how quickly the background thread of the change processor handles the updates.
+fn wait_until(condition: impl Fn() -> bool) -> bool {
+    let deadline = std::time::Instant::now() + Duration::from_secs(2);
+    while std::time::Instant::now() < deadline {
+        if condition() {
+            return true;
+        }
+
+        std::thread::sleep(Duration::from_millis(1));
+    }
+
+    condition()
+}
+
+#[test]
+fn when_adding_joint_sensor_it_should_track_the_joint_position() {
+    let mut model = MotionModel::new();
+    let receiver = model.structural_changes();
+    let body_id = add_body_to_model(&mut model).unwrap();
+    let change_processor = Box::new(HardwareChangeProcessor::new(10));
+    let (suspension_id, wheel_id, _hardware) =
+        add_module_with_suspension_to_model(&mut model, &body_id, &change_processor);
+    let revision = model.revision();
+    let _ = receiver.try_iter().count();
+    let original = model.homogeneous_transform_to_body(&wheel_id).unwrap();
+
+    let mut hardware = MockHardwareSensor::new(NumberSpaceType::LinearUnlimited);
+    let sensor = JointSensor::new(&mut hardware, &change_processor).unwrap();
+    model.add_joint_sensor(&suspension_id, sensor).unwrap();
+    assert!(model.has_sensor(&suspension_id));
+    assert_eq!(
+        NumberSpaceType::LinearUnlimited,
+        model
+            .sensor_for(&suspension_id)
+            .unwrap()
+            .number_space_type()
+    );
+    assert_eq!(revision + 1, model.revision());
+    assert_eq!(
+        &ModelChange::SensorChanged {
+            frame: suspension_id
+        },
+        receiver.try_recv().unwrap().change()
+    );
+
+    // The suspension is not actuated so the sensor determines the position of the joint
+    hardware.send(JointState::new(0.05, None, None, None));
+    assert!(wait_until(|| {
+        let moved = model.homogeneous_transform_to_body(&wheel_id).unwrap();
+        (original[(2, 3)] + 0.05 - moved[(2, 3)]).abs() < 1e-12
+    }));
+
+    let mut hardware = MockHardwareSensor::new(NumberSpaceType::LinearUnlimited);
+    let sensor = JointSensor::new(&mut hardware, &change_processor).unwrap();
+    assert!(matches!(
+        model.add_joint_sensor(&suspension_id, sensor),
+        Err(Error::FrameElementAlreadyExists { id }) if id == suspension_id
+    ));
+}
+
+#[test]
+fn when_a_limited_sensor_does_not_cover_the_joint_travel_it_should_error() {
+    let mut model = MotionModel::new();
+    let body_id = add_body_to_model(&mut model).unwrap();
+    let change_processor = Box::new(HardwareChangeProcessor::new(10));
+    let (suspension_id, _, _hardware) =
+        add_module_with_suspension_to_model(&mut model, &body_id, &change_processor);
+    model
+        .set_joint_constraint(
+            &suspension_id,
+            JointConstraint::new().with_position_limits(-0.1, 0.1),
+        )
+        .unwrap();
+
+    let mut hardware = MockHardwareSensor::new(NumberSpaceType::LinearLimited {
+        minimum: -0.05,
+        maximum: 0.1,
+    });
+    let sensor = JointSensor::new(&mut hardware, &change_processor).unwrap();
+    assert!(matches!(
+        model.add_joint_sensor(&suspension_id, sensor),
+        Err(Error::IncompatibleNumberSpace { id }) if id == suspension_id
+    ));
+
+    let mut hardware = MockHardwareSensor::new(NumberSpaceType::LinearLimited {
+        minimum: -0.1,
+        maximum: 0.1,
+    });
+    let sensor = JointSensor::new(&mut hardware, &change_processor).unwrap();
+    assert!(model.add_joint_sensor(&suspension_id, sensor).is_ok());
+
+    // The travel of the joint can't be extended beyond the range of the sensor
+    assert_eq!(
+        Err(Error::IncompatibleNumberSpace { id: suspension_id }),
+        model.set_joint_constraint(
+            &suspension_id,
+            JointConstraint::new().with_position_limits(-0.2, 0.1),
+        )
+    );
+    assert_eq!(
+        Some((-0.1, 0.1)),
+        model
+            .joint_constraint(&suspension_id)
+            .unwrap()
+            .position_limits()
+    );
+    assert!(model
+        .set_joint_constraint(
+            &suspension_id,
+            JointConstraint::new().with_position_limits(-0.05, 0.05),
+        )
+        .is_ok());
+}
+
+#[test]
+fn when_adding_joint_sensor_with_incompatible_number_space_it_should_error() {
+    let mut model = MotionModel::new();
+    let body_id = add_body_to_model(&mut model).unwrap();
+    let change_processor = Box::new(HardwareChangeProcessor::new(10));
+    let (suspension_id, wheel_id, _hardware) =
+        add_module_with_suspension_to_model(&mut model, &body_id, &change_processor);
+
+    let angular = NumberSpaceType::AngularLimited {
+        start_angle_in_radians: 0.0,
+    };
+    let mut hardware = MockHardwareSensor::new(angular);
+    let sensor = JointSensor::new(&mut hardware, &change_processor).unwrap();
+    assert!(matches!(
+        model.add_joint_sensor(&suspension_id, sensor),
+        Err(Error::IncompatibleNumberSpace { id }) if id == suspension_id
+    ));
+
+    let sensor = JointSensor::new(&mut hardware, &change_processor).unwrap();
+    assert!(matches!(
+        model.add_joint_sensor(&body_id, sensor),
+        Err(Error::InvalidFrameID { id }) if id == body_id
+    ));
+
+    let invalid_id = FrameID::new();
+    let sensor = JointSensor::new(&mut hardware, &change_processor).unwrap();
+    assert!(matches!(
+        model.add_joint_sensor(&invalid_id, sensor),
+        Err(Error::MissingFrameElement { id }) if id == invalid_id
+    ));
+
+    // Revolute joints accept both angular and linear sensors
+    let sensor = JointSensor::new(&mut hardware, &change_processor).unwrap();
+    assert!(model.add_joint_sensor(&wheel_id, sensor).is_ok());
+
+    let mut hardware = MockHardwareSensor::new(NumberSpaceType::LinearUnlimited);
+    let sensor = JointSensor::new(&mut hardware, &change_processor).unwrap();
+    assert!(model
+        .replace_joint_sensor(&wheel_id, sensor)
+        .unwrap()
+        .is_some());
+    assert!(model.sensor_for(&suspension_id).is_err());
+}
+
+#[test]
+fn when_getting_joint_state_with_actuator_and_sensor_it_should_fuse_the_states() {
+    let mut model = MotionModel::new();
+    let body_id = add_body_to_model(&mut model).unwrap();
+    let change_processor = Box::new(HardwareChangeProcessor::new(10));
+    let (suspension_id, wheel_id, hardware) =
+        add_module_with_suspension_to_model(&mut model, &body_id, &change_processor);
+    assert!(matches!(
+        model.joint_state_for(&suspension_id),
+        Err(Error::MissingFrameElement { id }) if id == suspension_id
+    ));
+
+    let mut sensor_hardware = MockHardwareSensor::new(NumberSpaceType::LinearUnlimited);
+    let sensor = JointSensor::new(&mut sensor_hardware, &change_processor).unwrap();
+    model.add_joint_sensor(&wheel_id, sensor).unwrap();
+
+    hardware[1]
+        .sender
+        .send((
+            JointState::new(1.0, Some(2.0), None, None),
+            ActuatorAvailableRatesOfChange::new(0.0, 0.0, 0.0, 0.0, 0.0, 0.0),
+        ))
+        .unwrap();
+    hardware[1]
+        .update_sender
+        .as_ref()
+        .unwrap()
+        .send(hardware[1].id.unwrap())
+        .unwrap();
+    sensor_hardware.send(JointState::new(2.0, Some(4.0), None, None));
+    assert!(wait_until(|| {
+        model
+            .actuator_for(&wheel_id)
+            .unwrap()
+            .value()
+            .unwrap()
+            .position()
+            == 1.0
+            && model
+                .sensor_for(&wheel_id)
+                .unwrap()
+                .value()
+                .unwrap()
+                .position()
+                == 2.0
+    }));
+
+    // By default the actuator state is used
+    assert_eq!(1.0, model.joint_state_for(&wheel_id).unwrap().position());
+
+    model.set_state_fusion(PreferSensor);
+    assert_eq!(2.0, model.joint_state_for(&wheel_id).unwrap().position());
+
+    model.set_state_fusion(WeightedAverage::new(0.5));
+    let state = model.joint_state_for(&wheel_id).unwrap();
+    assert_eq!(1.5, state.position());
+    assert_eq!(&Some(3.0), state.velocity());
+
+    model.set_state_fusion(|_: JointState, _: JointState| JointState::new(-1.0, None, None, None));
+    assert_eq!(-1.0, model.joint_state_for(&wheel_id).unwrap().position());
+}
+
+#[test]
+fn when_getting_last_update_it_should_track_the_oldest_source() {
+    let mut model = MotionModel::new();
+    let body_id = add_body_to_model(&mut model).unwrap();
+    let change_processor = Box::new(HardwareChangeProcessor::new(1000));
+    let (suspension_id, wheel_id, hardware) =
+        add_module_with_suspension_to_model(&mut model, &body_id, &change_processor);
+    let steering_id = *model.parent_of(&wheel_id).unwrap();
+    assert!(matches!(
+        model.last_update_for(&suspension_id),
+        Err(Error::MissingFrameElement { id }) if id == suspension_id
+    ));
+    assert_eq!(None, model.last_update_for(&wheel_id).unwrap());
+
+    let mut sensor_hardware = MockHardwareSensor::new(NumberSpaceType::LinearUnlimited);
+    let sensor = JointSensor::new(&mut sensor_hardware, &change_processor).unwrap();
+    model.add_joint_sensor(&wheel_id, sensor).unwrap();
+
+    let mut stale = vec![steering_id, wheel_id];
+    stale.sort();
+    assert_eq!(stale, model.stale_joints(Duration::from_secs(60)));
+
+    let before = Instant::now();
+    hardware[1]
+        .sender
+        .send((
+            JointState::new(1.0, None, None, None),
+            ActuatorAvailableRatesOfChange::new(0.0, 0.0, 0.0, 0.0, 0.0, 0.0),
+        ))
+        .unwrap();
+    hardware[1]
+        .update_sender
+        .as_ref()
+        .unwrap()
+        .send(hardware[1].id.unwrap())
+        .unwrap();
+    std::thread::sleep(Duration::from_millis(20));
+
+    // The sensor has not been updated yet so the joint has no known update time
+    let actuator_update = model
+        .actuator_for(&wheel_id)
+        .unwrap()
+        .last_update()
+        .unwrap();
+    assert_eq!(1.0, actuator_update.state().position());
+    assert!(actuator_update.received_at() >= before);
+    assert_eq!(None, model.last_update_for(&wheel_id).unwrap());
+
+    sensor_hardware.send(JointState::new(2.0, None, None, None));
+    std::thread::sleep(Duration::from_millis(20));
+
+    let sensor_update = model.sensor_for(&wheel_id).unwrap().last_update().unwrap();
+    assert_eq!(2.0, sensor_update.state().position());
+    assert_eq!(
+        Some(actuator_update.received_at()),
+        model.last_update_for(&wheel_id).unwrap()
+    );
+
+    assert_eq!(
+        vec![steering_id],
+        model.stale_joints(Duration::from_secs(60))
+    );
+    assert_eq!(stale, model.stale_joints(Duration::ZERO));
+}
+
+#[test]
+fn when_setting_a_calibration_it_should_apply_it_to_the_hardware_of_the_joint() {
+    let mut model = MotionModel::new();
+    let body_id = add_body_to_model(&mut model).unwrap();
+    let change_processor = HardwareChangeProcessor::manual();
+    let (suspension_id, wheel_id, hardware) =
+        add_module_with_suspension_to_model(&mut model, &body_id, &change_processor);
+    assert!(model.calibration(&wheel_id).unwrap().is_identity());
+
+    let calibration = JointCalibration::new(0.5, -1.0).unwrap();
+    model.set_calibration(&wheel_id, calibration).unwrap();
+    assert_eq!(calibration, model.calibration(&wheel_id).unwrap());
+    assert_eq!(
+        calibration,
+        model.actuator_for(&wheel_id).unwrap().calibration()
+    );
+    assert!(matches!(
+        model.set_calibration(&body_id, calibration),
+        Err(Error::InvalidFrameID { id }) if id == body_id
+    ));
+    assert!(matches!(
+        model.calibration(&FrameID::new()),
+        Err(Error::MissingFrameElement { .. })
+    ));
+
+    // Hardware that is attached later uses the calibration of the joint
+    let mut sensor_hardware = MockHardwareSensor::new(NumberSpaceType::LinearUnlimited);
+    let sensor = JointSensor::new(&mut sensor_hardware, &change_processor).unwrap();
+    model.add_joint_sensor(&wheel_id, sensor).unwrap();
+    assert_eq!(
+        calibration,
+        model.sensor_for(&wheel_id).unwrap().calibration()
+    );
+
+    hardware[1]
+        .sender
+        .send((
+            JointState::new(2.0, None, None, None),
+            ActuatorAvailableRatesOfChange::new(0.0, 0.0, 0.0, 0.0, 0.0, 0.0),
+        ))
+        .unwrap();
+    hardware[1]
+        .update_sender
+        .as_ref()
+        .unwrap()
+        .send(hardware[1].id.unwrap())
+        .unwrap();
+    change_processor.process_pending();
+    assert_eq!(
+        -1.5,
+        model
+            .actuator_for(&wheel_id)
+            .unwrap()
+            .value()
+            .unwrap()
+            .position()
+    );
+
+    // The calibration is removed together with the joint
+    model.remove_element(&suspension_id).unwrap();
+    assert!(model.calibration(&wheel_id).is_err());
+}
+
+#[test]
+fn when_setting_a_transmission_it_should_apply_it_to_the_hardware_of_the_joint() {
+    let mut model = MotionModel::new();
+    let body_id = add_body_to_model(&mut model).unwrap();
+    let change_processor = HardwareChangeProcessor::manual();
+    let (_, wheel_id, mut hardware) =
+        add_module_with_suspension_to_model(&mut model, &body_id, &change_processor);
+    assert!(model.transmission(&wheel_id).unwrap().is_identity());
+
+    let transmission = JointTransmission::new(true, 2.0).unwrap();
+    model.set_transmission(&wheel_id, transmission).unwrap();
+    assert_eq!(transmission, model.transmission(&wheel_id).unwrap());
+    assert!(matches!(
+        model.set_transmission(&body_id, transmission),
+        Err(Error::InvalidFrameID { id }) if id == body_id
+    ));
+
+    // Swapping the motor keeps the transmission of the joint
+    let actuator = Actuator::new(&mut hardware[1], &change_processor).unwrap();
+    model.replace_actuator(&wheel_id, actuator).unwrap();
+    assert_eq!(
+        transmission,
+        model.actuator_for(&wheel_id).unwrap().transmission()
+    );
+
+    hardware[1]
+        .sender
+        .send((
+            JointState::new(3.0, None, None, None),
+            ActuatorAvailableRatesOfChange::new(0.0, 0.0, 0.0, 0.0, 0.0, 0.0),
+        ))
+        .unwrap();
+    hardware[1]
+        .update_sender
+        .as_ref()
+        .unwrap()
+        .send(hardware[1].id.unwrap())
+        .unwrap();
+    change_processor.process_pending();
+    assert_eq!(
+        -1.5,
+        model
+            .actuator_for(&wheel_id)
+            .unwrap()
+            .value()
+            .unwrap()
+            .position()
+    );
+}
+
+#[test]
+fn when_getting_a_past_joint_state_it_should_use_the_history_of_the_joint() {
+    let mut model = MotionModel::new();
+    let body_id = add_body_to_model(&mut model).unwrap();
+    let change_processor = HardwareChangeProcessor::manual();
+    let (suspension_id, wheel_id, hardware) =
+        add_module_with_suspension_to_model(&mut model, &body_id, &change_processor);
+    let send = |position: f64| {
+        hardware[1]
+            .sender
+            .send((
+                JointState::new(position, None, None, None),
+                ActuatorAvailableRatesOfChange::new(0.0, 0.0, 0.0, 0.0, 0.0, 0.0),
+            ))
+            .unwrap();
+        hardware[1]
+            .update_sender
+            .as_ref()
+            .unwrap()
+            .send(hardware[1].id.unwrap())
+            .unwrap();
+        change_processor.process_pending();
+    };
+
+    send(1.0);
+    assert!(matches!(
+        model.joint_state_at(&wheel_id, Instant::now()),
+        Err(Error::MissingJointStateHistory { id }) if id == wheel_id
+    ));
+    assert!(matches!(
+        model.joint_state_at(&suspension_id, Instant::now()),
+        Err(Error::MissingFrameElement { .. })
+    ));
+    assert!(matches!(
+        model.set_history_capacity(&body_id, 10),
+        Err(Error::InvalidFrameID { .. })
+    ));
+
+    model.set_history_capacity(&wheel_id, 10).unwrap();
+    let before = Instant::now();
+    send(2.0);
+    std::thread::sleep(Duration::from_millis(1));
+    send(4.0);
+
+    let history = model.actuator_for(&wheel_id).unwrap().history();
+    assert_eq!(
+        10,
+        model.actuator_for(&wheel_id).unwrap().history_capacity()
+    );
+    assert_eq!(2, history.len());
+    let start = history[0].received_at();
+    let middle = start + (history[1].received_at() - start) / 2;
+    let state = model.joint_state_at(&wheel_id, middle).unwrap();
+    assert!((state.position() - 3.0).abs() < 1e-6);
+    assert!(model.joint_state_at(&wheel_id, before).is_err());
+    assert_eq!(
+        4.0,
+        model
+            .joint_state_at(&wheel_id, Instant::now())
+            .unwrap()
+            .position()
+    );
+}
+
+#[test]
+fn when_getting_a_past_transform_it_should_use_the_joint_states_at_that_time() {
+    let mut model = MotionModel::new();
+    let body_id = add_body_to_model(&mut model).unwrap();
+    let change_processor = HardwareChangeProcessor::manual();
+    let (_, wheel_id, hardware) =
+        add_module_with_suspension_to_model(&mut model, &body_id, &change_processor);
+    let steering_id = *model.parent_of(&wheel_id).unwrap();
+    let send = |steering: f64, wheel: f64| {
+        for (hardware, position) in hardware.iter().zip([steering, wheel]) {
+            hardware
+                .sender
+                .send((
+                    JointState::new(position, None, None, None),
+                    ActuatorAvailableRatesOfChange::new(0.0, 0.0, 0.0, 0.0, 0.0, 0.0),
+                ))
+                .unwrap();
+            hardware
+                .update_sender
+                .as_ref()
+                .unwrap()
+                .send(hardware.id.unwrap())
+                .unwrap();
+        }
+        change_processor.process_pending();
+    };
+
+    let before = Instant::now();
+    model.set_history_capacity(&steering_id, 10).unwrap();
+    model.set_history_capacity(&wheel_id, 10).unwrap();
+    send(0.5, 1.0);
+    let expected = model.homogeneous_transform_to_body(&wheel_id).unwrap();
+    let past = Instant::now();
+
+    // The joints hold their positions for a while before they move
+    std::thread::sleep(Duration::from_millis(1));
+    send(0.5, 1.0);
+    std::thread::sleep(Duration::from_millis(1));
+    send(1.0, 2.0);
+
+    let transform = model
+        .homogeneous_transform_to_body_at(&wheel_id, past)
+        .unwrap();
+    assert!((expected - transform).norm() < 1e-12);
+    assert!((expected - model.homogeneous_transform_to_body(&wheel_id).unwrap()).norm() > 1e-3);
+
+    let isometry = model
+        .isometry_to_body_at(&wheel_id, Instant::now())
+        .unwrap();
+    assert!(
+        (model.homogeneous_transform_to_body(&wheel_id).unwrap() - isometry.to_homogeneous())
+            .norm()
+            < 1e-12
+    );
+
+    assert!(matches!(
+        model.homogeneous_transform_to_body_at(&wheel_id, before),
+        Err(Error::MissingJointStateHistory { .. })
+    ));
+    assert!(matches!(
+        model.isometry_to_body_at(&FrameID::new(), past),
+        Err(Error::MissingFrameElement { .. })
+    ));
+}
+
+#[test]
+fn when_getting_the_actuator_health_it_should_report_all_the_actuated_joints() {
+    let mut model = MotionModel::new();
+    let body_id = add_body_to_model(&mut model).unwrap();
+    let change_processor = Box::new(HardwareChangeProcessor::new(1000));
+    let (suspension_id, wheel_id, _hardware) =
+        add_module_with_suspension_to_model(&mut model, &body_id, &change_processor);
+    let steering_id = *model.parent_of(&wheel_id).unwrap();
+
+    let health = model.actuator_health();
+    let mut ids = vec![steering_id, wheel_id];
+    ids.sort();
+    assert_eq!(ids, health.keys().copied().collect::<Vec<_>>());
+    assert!(health.values().all(ActuatorHealth::is_ok));
+
+    model
+        .set_actuator_stale_timeout(&wheel_id, Some(Duration::from_secs(60)))
+        .unwrap();
+    assert!(matches!(
+        model.set_actuator_stale_timeout(&suspension_id, None),
+        Err(Error::InvalidFrameID { id }) if id == suspension_id
+    ));
+    assert!(matches!(
+        model.set_actuator_stale_timeout(&FrameID::new(), None),
+        Err(Error::MissingFrameElement { .. })
+    ));
+
+    // The command channels of the mock hardware are closed
+    assert!(model
+        .actuator_for(&steering_id)
+        .unwrap()
+        .update_state(JointState::new(0.5, None, None, None))
+        .is_err());
+
+    let health = model.actuator_health();
+    assert_eq!(
+        Some(&ActuatorHealth::Faulted(ActuatorFault::Disconnected)),
+        health.get(&steering_id)
+    );
+    assert_eq!(Some(&ActuatorHealth::Stale), health.get(&wheel_id));
+}
+
+#[test]
+fn when_subscribing_to_model_events_it_should_publish_the_hardware_events() {
+    let mut model = MotionModel::new();
+    let body_id = add_body_to_model(&mut model).unwrap();
+    let receiver = model.subscribe();
+    let change_processor = Box::new(HardwareChangeProcessor::new(1000));
+    let (suspension_id, wheel_id, _hardware) =
+        add_module_with_suspension_to_model(&mut model, &body_id, &change_processor);
+    assert!(receiver.try_iter().any(|event| event
+        == ModelEvent::StructureChanged {
+            change: ModelChange::FrameAdded { frame: wheel_id }
+        }));
+
+    // Keep the command channel open so that the commands can be sent
+    let (sender, state_receiver) = crossbeam_channel::unbounded();
+    let (command_sender, _command_receiver) = crossbeam_channel::unbounded();
+    let mut hardware = MockHardwareActuator {
+        receiver: state_receiver,
+        sender,
+        command_sender,
+        update_sender: None,
+        id: None,
+    };
+    let actuator = Actuator::new(&mut hardware, &change_processor).unwrap();
+    model.replace_actuator(&wheel_id, actuator).unwrap();
+
+    let mut sensor_hardware = MockHardwareSensor::new(NumberSpaceType::LinearUnlimited);
+    let sensor = JointSensor::new(&mut sensor_hardware, &change_processor).unwrap();
+    model.add_joint_sensor(&suspension_id, sensor).unwrap();
+    let _ = receiver.try_iter().count();
+
+    hardware
+        .sender
+        .send((
+            JointState::new(1.0, None, None, None),
+            ActuatorAvailableRatesOfChange::new(0.0, 0.0, 0.0, 0.0, 0.0, 0.0),
+        ))
+        .unwrap();
+    hardware
+        .update_sender
+        .as_ref()
+        .unwrap()
+        .send(hardware.id.unwrap())
+        .unwrap();
+    std::thread::sleep(Duration::from_millis(20));
+    assert_eq!(
+        ModelEvent::JointStateUpdated { frame: wheel_id },
+        receiver.try_recv().unwrap()
+    );
+
+    sensor_hardware.send(JointState::new(0.05, None, None, None));
+    std::thread::sleep(Duration::from_millis(20));
+    assert_eq!(
+        ModelEvent::JointStateUpdated {
+            frame: suspension_id
+        },
+        receiver.try_recv().unwrap()
+    );
+
+    model
+        .actuator_for(&wheel_id)
+        .unwrap()
+        .update_state(JointState::new(2.0, None, None, None))
+        .unwrap();
+    assert_eq!(
+        ModelEvent::ActuatorCommandSent { frame: wheel_id },
+        receiver.try_recv().unwrap()
+    );
+
+    // Sensors that are removed from the model no longer publish events
+    let sensor = model.remove_joint_sensor(&suspension_id).unwrap();
+    let _ = receiver.try_iter().count();
+    sensor_hardware.send(JointState::new(0.1, None, None, None));
+    std::thread::sleep(Duration::from_millis(20));
+    assert_eq!(0.1, sensor.value().unwrap().position());
+    assert!(receiver.try_recv().is_err());
+}
+
+#[test]
+fn when_removing_joint_sensor_it_should_return_the_sensor() {
+    let mut model = MotionModel::new();
+    let body_id = add_body_to_model(&mut model).unwrap();
+    let change_processor = Box::new(HardwareChangeProcessor::new(10));
+    let (suspension_id, _, _hardware) =
+        add_module_with_suspension_to_model(&mut model, &body_id, &change_processor);
+
+    let mut hardware = MockHardwareSensor::new(NumberSpaceType::LinearUnlimited);
+    let sensor = JointSensor::new(&mut hardware, &change_processor).unwrap();
+    assert!(model
+        .replace_joint_sensor(&suspension_id, sensor)
+        .unwrap()
+        .is_none());
+
+    let revision = model.revision();
+    assert!(model.remove_joint_sensor(&suspension_id).is_some());
+    assert!(!model.has_sensor(&suspension_id));
+    assert_eq!(revision + 1, model.revision());
+
+    assert!(model.remove_joint_sensor(&suspension_id).is_none());
+    assert_eq!(revision + 1, model.revision());
+}
+
+#[test]
+fn when_adding_command_middleware_it_should_process_the_actuator_commands() {
+    let mut model = MotionModel::new();
+    let body_id = add_body_to_model(&mut model).unwrap();
+    let change_processor = Box::new(HardwareChangeProcessor::new(10));
+    let (suspension_id, wheel_id, _hardware) =
+        add_module_with_suspension_to_model(&mut model, &body_id, &change_processor);
+
+    model
+        .add_command_middleware(&wheel_id, |_: JointState| CommandOutcome::Veto {
+            reason: "wheel is locked".to_string(),
+        })
+        .unwrap();
+    assert_eq!(
+        1,
+        model
+            .actuator_for(&wheel_id)
+            .unwrap()
+            .number_of_command_middleware()
+    );
+
+    let result = model
+        .actuator_for(&wheel_id)
+        .unwrap()
+        .update_state(JointState::new(1.0, None, None, None));
+    assert_eq!(
+        Err(Error::CommandVetoed {
+            reason: "wheel is locked".to_string()
+        }),
+        result
+    );
+
+    assert_eq!(
+        Err(Error::InvalidFrameID { id: suspension_id }),
+        model.add_command_middleware(&suspension_id, CommandOutcome::Forward)
+    );
+
+    let invalid_id = FrameID::new();
+    assert_eq!(
+        Err(Error::MissingFrameElement { id: invalid_id }),
+        model.add_command_middleware(&invalid_id, CommandOutcome::Forward)
+    );
+}
+
+#[test]
+fn when_setting_a_joint_constraint_it_should_validate_the_joint_states() {
+    let mut model = MotionModel::new();
+    let body_id = add_body_to_model(&mut model).unwrap();
+    let change_processor = Box::new(HardwareChangeProcessor::new(10));
+    let (suspension_id, wheel_id, _hardware) =
+        add_module_with_suspension_to_model(&mut model, &body_id, &change_processor);
+    let receiver = model.structural_changes();
+
+    // Elements without a constraint are not limited
+    assert_eq!(
+        Ok(JointConstraint::new()),
+        model.joint_constraint(&wheel_id)
+    );
+    assert!(model
+        .validate_joint_state(&wheel_id, &JointState::new(1e6, None, None, None))
+        .is_ok());
+
+    let constraint = JointConstraint::new().with_position_limits(-0.05, 0.1);
+    model
+        .set_joint_constraint(&suspension_id, constraint)
+        .unwrap();
+    assert_eq!(Ok(constraint), model.joint_constraint(&suspension_id));
+
+    let event = receiver.try_recv().unwrap();
+    assert_eq!(
+        &ModelChange::JointConstraintChanged {
+            frame: suspension_id
+        },
+        event.change()
+    );
+
+    assert!(model
+        .validate_joint_state(&suspension_id, &JointState::new(0.1, None, None, None))
+        .is_ok());
+    assert_eq!(
+        Err(Error::JointLimitExceeded {
+            quantity: "position".to_string(),
+            value: -0.1,
+            minimum: -0.05,
+            maximum: 0.1
+        }),
+        model.validate_joint_state(&suspension_id, &JointState::new(-0.1, None, None, None))
+    );
+
+    assert_eq!(
+        Err(Error::InvalidFrameID { id: body_id }),
+        model.set_joint_constraint(&body_id, constraint)
+    );
+
+    let invalid_id = FrameID::new();
+    assert_eq!(
+        Err(Error::MissingFrameElement { id: invalid_id }),
+        model.set_joint_constraint(&invalid_id, constraint)
+    );
+    assert_eq!(
+        Err(Error::MissingFrameElement { id: invalid_id }),
+        model.validate_joint_state(&invalid_id, &JointState::new(0.0, None, None, None))
+    );
+}
+
+#[test]
+fn when_enforcing_a_joint_constraint_it_should_limit_the_actuator_commands() {
+    let mut model = MotionModel::new();
+    let body_id = add_body_to_model(&mut model).unwrap();
+    let change_processor = Box::new(HardwareChangeProcessor::new(10));
+    let (suspension_id, wheel_id, _hardware) =
+        add_module_with_suspension_to_model(&mut model, &body_id, &change_processor);
+    let steering_id = *model.steering_frame_for_wheel(&wheel_id).unwrap();
+
+    let constraint = JointConstraint::new().with_position_limits(-1.0, 1.0);
+    model
+        .set_joint_constraint(&steering_id, constraint)
+        .unwrap();
+    model
+        .set_constraint_enforcement(&steering_id, ConstraintEnforcement::Reject)
+        .unwrap();
+    assert_eq!(
+        &constraint,
+        model.actuator_for(&steering_id).unwrap().constraint()
+    );
+
+    let result = model
+        .actuator_for(&steering_id)
+        .unwrap()
+        .update_state(JointState::new(1.5, None, None, None));
+    assert_eq!(
+        Err(Error::JointLimitExceeded {
+            quantity: "position".to_string(),
+            value: 1.5,
+            minimum: -1.0,
+            maximum: 1.0
+        }),
+        result
+    );
+
+    // A replacement actuator should be limited by the same constraint
+    let (sender, receiver) = crossbeam_channel::unbounded();
+    let (cmd_sender, _cmd_receiver) = crossbeam_channel::unbounded();
+    let mut hardware = MockHardwareActuator {
+        receiver,
+        sender,
+        command_sender: cmd_sender,
+        update_sender: None,
+        id: None,
+    };
+    let actuator = Actuator::new(&mut hardware, &change_processor).unwrap();
+    model.replace_actuator(&steering_id, actuator).unwrap();
+    assert_eq!(
+        &constraint,
+        model.actuator_for(&steering_id).unwrap().constraint()
+    );
+
+    assert_eq!(
+        Err(Error::InvalidFrameID { id: suspension_id }),
+        model.set_constraint_enforcement(&suspension_id, ConstraintEnforcement::Clamp)
+    );
+
+    let invalid_id = FrameID::new();
+    assert_eq!(
+        Err(Error::MissingFrameElement { id: invalid_id }),
+        model.set_constraint_enforcement(&invalid_id, ConstraintEnforcement::Clamp)
+    );
+}
+
+#[test]
+fn when_adding_state_middleware_it_should_process_the_received_states() {
+    let mut model = MotionModel::new();
+    let body_id = add_body_to_model(&mut model).unwrap();
+    let change_processor = Box::new(HardwareChangeProcessor::new(1000));
+    let (suspension_id, wheel_id, hardware) =
+        add_module_with_suspension_to_model(&mut model, &body_id, &change_processor);
+
+    model
+        .add_state_middleware(&wheel_id, LinearConversion::new(2.0, 0.0))
+        .unwrap();
+
+    hardware[1]
+        .sender
+        .send((
+            JointState::new(1.5, None, None, None),
+            ActuatorAvailableRatesOfChange::new(0.0, 0.0, 0.0, 0.0, 0.0, 0.0),
+        ))
+        .unwrap();
+    hardware[1]
+        .update_sender
+        .as_ref()
+        .unwrap()
+        .send(hardware[1].id.unwrap())
+        .unwrap();
+    std::thread::sleep(Duration::from_millis(50));
+
+    assert_eq!(
+        3.0,
+        model
+            .actuator_for(&wheel_id)
+            .unwrap()
+            .value()
+            .unwrap()
+            .position()
+    );
+
+    assert_eq!(
+        Err(Error::InvalidFrameID { id: suspension_id }),
+        model.add_state_middleware(&suspension_id, StateOutcome::Accept)
+    );
+
+    let invalid_id = FrameID::new();
+    assert_eq!(
+        Err(Error::MissingFrameElement { id: invalid_id }),
+        model.add_state_middleware(&invalid_id, StateOutcome::Accept)
+    );
+}
+
+#[test]
+fn when_receiving_non_finite_joint_states_it_should_keep_the_last_valid_state() {
+    let mut model = MotionModel::new();
+    let body_id = add_body_to_model(&mut model).unwrap();
+    let change_processor = Box::new(HardwareChangeProcessor::new(1000));
+    let (_, wheel_id, hardware) =
+        add_module_with_suspension_to_model(&mut model, &body_id, &change_processor);
+    let steering_id = *model.steering_frame_for_wheel(&wheel_id).unwrap();
+
+    let send = |state: JointState, rates: ActuatorAvailableRatesOfChange| {
+        hardware[0].sender.send((state, rates)).unwrap();
+        hardware[0]
+            .update_sender
+            .as_ref()
+            .unwrap()
+            .send(hardware[0].id.unwrap())
+            .unwrap();
+        std::thread::sleep(Duration::from_millis(50));
+    };
+    let rates = ActuatorAvailableRatesOfChange::new(0.0, 0.0, 0.0, 0.0, 0.0, 0.0);
+
+    send(JointState::new(0.5 * PI, Some(1.0), None, None), rates);
+    let expected = model.homogeneous_transform_to_body(&wheel_id).unwrap();
+    assert!(model.rejected_joint_states().is_empty());
+
+    send(JointState::new(f64::NAN, None, None, None), rates);
+    send(
+        JointState::new(0.25 * PI, Some(f64::INFINITY), None, None),
+        rates,
+    );
+    send(
+        JointState::new(0.25 * PI, None, None, None),
+        ActuatorAvailableRatesOfChange::new(f64::NAN, 0.0, 0.0, 0.0, 0.0, 0.0),
+    );
+
+    let actuator = model.actuator_for(&steering_id).unwrap();
+    assert_eq!(3, actuator.rejected_state_count());
+    assert_eq!(0.5 * PI, actuator.value().unwrap().position());
+    assert_eq!(Some(1.0), *actuator.value().unwrap().velocity());
+
+    let transform = model.homogeneous_transform_to_body(&wheel_id).unwrap();
+    assert!(transform.iter().all(|v| v.is_finite()));
+    assert!((expected - transform).norm() < 1e-12);
+
+    assert_eq!(vec![(steering_id, 3)], model.rejected_joint_states());
+}
+
+#[test]
+fn when_removing_element_it_should_remove_the_subtree() {
+    let mut model = MotionModel::new();
+    let body_id = add_body_to_model(&mut model).unwrap();
+    let change_processor = Box::new(HardwareChangeProcessor::new(10));
+    let (suspension_id, wheel_id, _hardware) =
+        add_module_with_suspension_to_model(&mut model, &body_id, &change_processor);
+    let (other_suspension_id, other_wheel_id, _other_hardware) =
+        add_module_with_suspension_to_model(&mut model, &body_id, &change_processor);
+    let steering_id = *model.steering_frame_for_wheel(&wheel_id).unwrap();
+
+    model.set_wheel_radius(&wheel_id, 0.1).unwrap();
+    model
+        .set_suspension_droop_position(&suspension_id, -0.1)
+        .unwrap();
+    let mut mode = OperatingMode::new("indoor".to_string(), 1.0, 1.0, 0.5);
+    mode.set_suspension_stiffness(suspension_id, 1000.0);
+    mode.set_suspension_stiffness(other_suspension_id, 1000.0);
+    model.add_operating_mode(mode).unwrap();
+    model.set_operating_mode("indoor").unwrap();
+
+    let receiver = model.structural_changes();
+    let removed = model.remove_element(&suspension_id).unwrap();
+    assert_eq!(vec![suspension_id, steering_id, wheel_id], removed);
+
+    let event = receiver.try_recv().unwrap();
+    assert_eq!(
+        &ModelChange::FrameRemoved {
+            frame: suspension_id
+        },
+        event.change()
+    );
+    assert_eq!(
+        vec![body_id, suspension_id, steering_id, wheel_id],
+        event.invalidated_frames()
+    );
+
+    for id in removed.iter() {
+        assert!(model.reference_frame(id).is_err());
+        assert!(model.chassis_element(id).is_err());
+    }
+
+    assert!(model.actuator_for(&steering_id).is_err());
+    assert!(model.wheel_radius(&wheel_id).is_err());
+    assert_eq!(vec![&other_wheel_id], model.wheels().unwrap());
+    assert_eq!(
+        vec![&other_suspension_id],
+        model.children_of(&body_id).unwrap()
+    );
+
+    let mode = model.operating_mode().unwrap();
+    assert_eq!(None, mode.suspension_stiffness(&suspension_id));
+    assert_eq!(
+        Some(1000.0),
+        mode.suspension_stiffness(&other_suspension_id)
+    );
+
+    let (is_valid, errors) = model.is_valid();
+    assert!(!is_valid);
+    assert!(errors
+        .iter()
+        .all(|e| !e.contains("does not have an actuator")));
+}
+
+#[test]
+fn when_removing_element_with_invalid_frame_it_should_error() {
+    let mut model = MotionModel::new();
+    let body_id = add_body_to_model(&mut model).unwrap();
+    let revision = model.revision();
+
+    assert_eq!(
+        Err(Error::InvalidFrameID { id: body_id }),
+        model.remove_element(&body_id)
+    );
+    assert_eq!(
+        Err(Error::InvalidFrameID { id: body_id }),
+        model.remove_element_and_reparent_children(&body_id)
+    );
+
+    let invalid_id = FrameID::new();
+    assert_eq!(
+        Err(Error::MissingFrameElement { id: invalid_id }),
+        model.remove_element(&invalid_id)
+    );
+    assert_eq!(
+        Err(Error::MissingFrameElement { id: invalid_id }),
+        model.remove_element_and_reparent_children(&invalid_id)
+    );
+
+    assert_eq!(revision, model.revision());
+}
+
+#[test]
+fn when_removing_element_and_reparenting_children_it_should_keep_the_children_in_place() {
+    let mut model = MotionModel::new();
+    let body_id = add_body_to_model(&mut model).unwrap();
+    let change_processor = Box::new(HardwareChangeProcessor::new(10));
+    let (suspension_id, wheel_id, _hardware) =
+        add_module_with_suspension_to_model(&mut model, &body_id, &change_processor);
+    let steering_id = *model.steering_frame_for_wheel(&wheel_id).unwrap();
+
+    let steering_to_body = model.homogeneous_transform_to_body(&steering_id).unwrap();
+    let wheel_to_body = model.homogeneous_transform_to_body(&wheel_id).unwrap();
+
+    model
+        .remove_element_and_reparent_children(&suspension_id)
+        .unwrap();
+
+    assert!(model.reference_frame(&suspension_id).is_err());
+    assert_eq!(&body_id, model.parent_of(&steering_id).unwrap());
+    assert_eq!(vec![&steering_id], model.children_of(&body_id).unwrap());
+    assert_eq!(
+        &steering_id,
+        model.steering_frame_for_wheel(&wheel_id).unwrap()
+    );
+
+    assert!(
+        (steering_to_body - model.homogeneous_transform_to_body(&steering_id).unwrap()).norm()
+            < 1e-12
+    );
+    assert!(
+        (wheel_to_body - model.homogeneous_transform_to_body(&wheel_id).unwrap()).norm() < 1e-12
+    );
+}
+
+#[test]
+fn when_removing_steering_element_and_reparenting_children_it_should_error() {
+    let mut model = MotionModel::new();
+    let body_id = add_body_to_model(&mut model).unwrap();
+    let change_processor = Box::new(HardwareChangeProcessor::new(10));
+    let (_, wheel_id, _hardware) =
+        add_module_with_suspension_to_model(&mut model, &body_id, &change_processor);
+    let steering_id = *model.steering_frame_for_wheel(&wheel_id).unwrap();
+
+    assert_eq!(
+        Err(Error::NoSteeringFramesInChain { id: steering_id }),
+        model.remove_element_and_reparent_children(&steering_id)
+    );
+
+    // Once the wheel is removed the steering frame can be removed
+    assert_eq!(vec![wheel_id], model.remove_element(&wheel_id).unwrap());
+    model
+        .remove_element_and_reparent_children(&steering_id)
+        .unwrap();
+    assert!(model.reference_frame(&steering_id).is_err());
+}
+
+#[test]
+fn when_getting_the_transform_to_world_it_should_include_the_body_pose() {
+    let mut model = MotionModel::new();
+    let body_id = add_body_to_model(&mut model).unwrap();
+    let change_processor = Box::new(HardwareChangeProcessor::new(10));
+    let (_, wheel_id, _hardware) =
+        add_module_with_suspension_to_model(&mut model, &body_id, &change_processor);
+
+    // Without a pose the world frame matches the body frame
+    assert_eq!(&Isometry3::identity(), model.body_pose_in_world());
+    assert_eq!(
+        model.homogeneous_transform_to_body(&wheel_id).unwrap(),
+        model.homogeneous_transform_to_world(&wheel_id).unwrap()
+    );
+
+    let pose = Isometry3::from_parts(
+        Translation3::new(10.0, -5.0, 0.2),
+        UnitQuaternion::from_euler_angles(0.0, 0.0, 0.5 * PI),
+    );
+    model.set_body_pose_in_world(pose);
+    assert_eq!(&pose, model.body_pose_in_world());
+
+    let expected = pose.to_homogeneous() * model.homogeneous_transform_to_body(&wheel_id).unwrap();
+    let to_world = model.homogeneous_transform_to_world(&wheel_id).unwrap();
+    assert!((expected - to_world).norm() < 1e-12);
+
+    let body_to_world = model.homogeneous_transform_to_world(&body_id).unwrap();
+    assert!((pose.to_homogeneous() - body_to_world).norm() < 1e-12);
+}
+
+#[test]
+fn when_getting_the_wheel_contact_point_in_world_it_should_be_below_the_wheel() {
+    let mut model = MotionModel::new();
+    let body_id = add_body_to_model(&mut model).unwrap();
+    let change_processor = Box::new(HardwareChangeProcessor::new(10));
+    let (suspension_id, wheel_id, _hardware) =
+        add_module_with_suspension_to_model(&mut model, &body_id, &change_processor);
+
+    assert_eq!(
+        Err(Error::MissingWheelRadius { id: wheel_id }),
+        model.wheel_contact_point_in_world(&wheel_id)
+    );
+    assert_eq!(
+        Err(Error::MissingWheelRadius { id: suspension_id }),
+        model.wheel_contact_point_in_world(&suspension_id)
+    );
+
+    model.set_wheel_radius(&wheel_id, 0.1).unwrap();
+    model.set_body_pose_in_world(Isometry3::translation(10.0, -5.0, 0.5));
+
+    let to_world = model.homogeneous_transform_to_world(&wheel_id).unwrap();
+    let contact_point = model.wheel_contact_point_in_world(&wheel_id).unwrap();
+    assert!((to_world[(0, 3)] - contact_point.x).abs() < 1e-12);
+    assert!((to_world[(1, 3)] - contact_point.y).abs() < 1e-12);
+    assert!((to_world[(2, 3)] - 0.1 - contact_point.z).abs() < 1e-12);
+}
+
+#[test]
+fn when_adding_a_wheel_with_properties_it_should_store_the_properties() {
+    let mut model = MotionModel::new();
+    let body_id = add_body_to_model(&mut model).unwrap();
+    let change_processor = Box::new(HardwareChangeProcessor::new(10));
+
+    let mut hardware = vec![];
+    for _ in 0..2 {
+        let (sender, receiver) = crossbeam_channel::unbounded();
+        let (cmd_sender, _) = crossbeam_channel::unbounded();
+        hardware.push(MockHardwareActuator {
+            receiver,
+            sender,
+            command_sender: cmd_sender,
+            update_sender: None,
+            id: None,
+        });
+    }
+
+    let steering_actuator = Actuator::new(&mut hardware[0], &change_processor).unwrap();
+    let steering_id = add_steering_to_model(
+        &mut model,
+        &body_id,
+        DriveModulePosition::LeftFront,
+        steering_actuator,
+    )
+    .unwrap();
+
+    let properties = WheelProperties::new(0.15, 0.05, Vector3::new(0.0, 0.01, 0.0));
+    let wheel_actuator = Actuator::new(&mut hardware[1], &change_processor).unwrap();
+    let wheel_id = model
+        .add_wheel_with_properties(
+            "wheel".to_string(),
+            steering_id,
+            Translation3::new(0.0, 0.0, -0.1),
+            UnitQuaternion::identity(),
+            ChassisElementPhysicalProperties::new(
+                1.0,
+                Vector3::identity(),
+                Matrix3::identity(),
+                Matrix6::identity(),
+            ),
+            wheel_actuator,
+            properties,
+        )
+        .unwrap();
+
+    assert_eq!(Ok(&properties), model.wheel_properties(&wheel_id));
+    assert_eq!(Ok(0.15), model.wheel_radius(&wheel_id));
+
+    // Changing the radius should keep the other properties
+    model.set_wheel_radius(&wheel_id, 0.2).unwrap();
+    let updated = model.wheel_properties(&wheel_id).unwrap();
+    assert_eq!(0.2, updated.radius());
+    assert_eq!(0.05, updated.width());
+    assert_eq!(&Vector3::new(0.0, 0.01, 0.0), updated.contact_offset());
+
+    assert_eq!(
+        Err(Error::InvalidFrameID { id: steering_id }),
+        model.wheel_properties(&steering_id)
+    );
+}
+
+#[test]
+fn when_setting_wheel_properties_it_should_notify_the_change() {
+    let mut model = MotionModel::new();
+    let body_id = add_body_to_model(&mut model).unwrap();
+    let change_processor = Box::new(HardwareChangeProcessor::new(10));
+    let (suspension_id, wheel_id, _hardware) =
+        add_module_with_suspension_to_model(&mut model, &body_id, &change_processor);
+    let receiver = model.structural_changes();
+
+    assert_eq!(
+        Err(Error::MissingWheelRadius { id: wheel_id }),
+        model.wheel_properties(&wheel_id)
+    );
+
+    let properties = WheelProperties::new(0.1, 0.04, Vector3::zeros());
+    model.set_wheel_properties(&wheel_id, properties).unwrap();
+    assert_eq!(Ok(&properties), model.wheel_properties(&wheel_id));
+
+    let event = receiver.try_recv().unwrap();
+    assert_eq!(
+        &ModelChange::WheelPropertiesChanged { wheel: wheel_id },
+        event.change()
+    );
+
+    assert_eq!(
+        Err(Error::InvalidFrameID { id: suspension_id }),
+        model.set_wheel_properties(&suspension_id, properties)
+    );
+
+    let invalid_id = FrameID::new();
+    assert_eq!(
+        Err(Error::MissingFrameElement { id: invalid_id }),
+        model.set_wheel_properties(&invalid_id, properties)
+    );
+}
+
+#[test]
+fn when_getting_the_wheel_contact_point_in_body_it_should_follow_the_steering_angle() {
+    let mut model = MotionModel::new();
+    let body_id = add_body_to_model(&mut model).unwrap();
+    let change_processor = Box::new(HardwareChangeProcessor::new(1000));
+    let (suspension_id, wheel_id, hardware) =
+        add_module_with_suspension_to_model(&mut model, &body_id, &change_processor);
+    let steering_id = *model.steering_frame_for_wheel(&wheel_id).unwrap();
+
+    assert_eq!(
+        Err(Error::MissingWheelRadius { id: wheel_id }),
+        model.wheel_contact_point_in_body(&wheel_id)
+    );
+    assert_eq!(
+        Err(Error::InvalidFrameID { id: suspension_id }),
+        model.wheel_contact_point_in_body(&suspension_id)
+    );
+
+    model
+        .set_wheel_properties(
+            &wheel_id,
+            WheelProperties::new(0.1, 0.05, Vector3::new(0.0, 0.02, 0.0)),
+        )
+        .unwrap();
+
+    // The wheel is 0.1 below the steering frame, so the contact point is 0.2 below it
+    let expected_in_steering = nalgebra::Point3::new(0.0, 0.02, -0.2);
+
+    let rates = ActuatorAvailableRatesOfChange::new(0.0, 0.0, 0.0, 0.0, 0.0, 0.0);
+    for (steering_angle, wheel_angle) in [(0.0, 0.0), (0.5 * PI, 1.0), (-0.25 * PI, 3.0)] {
+        for (actuator, position) in hardware.iter().zip([steering_angle, wheel_angle]) {
+            actuator
+                .sender
+                .send((JointState::new(position, None, None, None), rates))
+                .unwrap();
+            actuator
+                .update_sender
+                .as_ref()
+                .unwrap()
+                .send(actuator.id.unwrap())
+                .unwrap();
+        }
+        std::thread::sleep(Duration::from_millis(50));
+
+        // The rotation of the wheel around its axle should not move the contact point
+        let steering_to_body = model.homogeneous_transform_to_body(&steering_id).unwrap();
+        let expected = steering_to_body
+            .transform_point(&expected_in_steering)
+            .coords;
+        let contact_point = model.wheel_contact_point_in_body(&wheel_id).unwrap();
+        assert!((expected - contact_point).norm() < 1e-12);
+    }
+}
+
+#[test]
+fn when_adding_elements_it_should_store_the_frame_role() {
+    let mut model = MotionModel::new();
+    let body_id = add_body_to_model(&mut model).unwrap();
+    let change_processor = Box::new(HardwareChangeProcessor::new(10));
+    let (suspension_id, wheel_id, _hardware) =
+        add_module_with_suspension_to_model(&mut model, &body_id, &change_processor);
+    let steering_id = *model.steering_frame_for_wheel(&wheel_id).unwrap();
+
+    let attachment_id = model
+        .add_static_chassis_element(
+            "bumper".to_string(),
+            body_id,
+            Translation3::new(1.5, 0.0, 0.0),
+            UnitQuaternion::identity(),
+            ChassisElementPhysicalProperties::new(
+                1.0,
+                Vector3::<f64>::identity(),
+                Matrix3::<f64>::identity(),
+                Matrix6::<f64>::identity(),
+            ),
+        )
+        .unwrap();
+
+    assert_eq!(FrameRole::Body, model.frame_role(&body_id).unwrap());
+    assert_eq!(
+        FrameRole::Suspension,
+        model.frame_role(&suspension_id).unwrap()
+    );
+    assert_eq!(FrameRole::Steering, model.frame_role(&steering_id).unwrap());
+    assert_eq!(FrameRole::Wheel, model.frame_role(&wheel_id).unwrap());
+    assert_eq!(
+        FrameRole::Attachment,
+        model.frame_role(&attachment_id).unwrap()
+    );
+    assert_eq!(
+        FrameRole::Wheel,
+        model.reference_frame(&wheel_id).unwrap().role()
+    );
+
+    assert_eq!(vec![&wheel_id], model.frames_with_role(FrameRole::Wheel));
+    assert_eq!(vec![&body_id], model.frames_with_role(FrameRole::Body));
+    assert!(model.frames_with_role(FrameRole::Generic).is_empty());
+
+    let invalid_id = FrameID::new();
+    assert_eq!(
+        Err(Error::MissingFrameElement { id: invalid_id }),
+        model.frame_role(&invalid_id)
+    );
+}
+
+#[test]
+fn when_adding_an_actuated_element_that_rotates_around_the_y_axis_it_should_not_be_a_wheel() {
+    let mut model = MotionModel::new();
+    let body_id = add_body_to_model(&mut model).unwrap();
+    let (sender, receiver) = crossbeam_channel::unbounded();
+    let (cmd_sender, _) = crossbeam_channel::unbounded();
+    let mut hardware = MockHardwareActuator {
+        receiver,
+        sender,
+        command_sender: cmd_sender,
+        update_sender: None,
+        id: None,
+    };
+    let change_processor = Box::new(HardwareChangeProcessor::new(10));
+
+    let frame_id = add_actuated_joint_to_model(
+        &mut model,
+        &body_id,
+        DriveModulePosition::LeftFront,
+        FrameDofType::RevoluteY,
+        Actuator::new(&mut hardware, &change_processor).unwrap(),
+    )
+    .unwrap();
+
+    assert_eq!(FrameRole::Actuated, model.frame_role(&frame_id).unwrap());
+    assert!(model.wheels().unwrap().is_empty());
+    assert_eq!(0, model.number_of_wheels());
+}
+
+#[test]
+fn when_validating_a_model_it_should_check_the_wheels_without_a_role() {
+    let mut model = MotionModel::new();
+    let body_id = add_body_to_model(&mut model).unwrap();
+
+    // A frame without a role that rotates around the y-axis is assumed to be a wheel
+    let wheel_id = model
+        .add_element_unchecked(
+            create_wheel_element("wheel".to_string()),
+            body_id,
+            Translation3::new(1.0, 0.5, 0.0),
+            UnitQuaternion::identity(),
+            "wheel".to_string(),
+            ChassisElementPhysicalProperties::new(
+                1.0,
+                Vector3::<f64>::identity(),
+                Matrix3::<f64>::identity(),
+                Matrix6::<f64>::identity(),
+            ),
+        )
+        .unwrap();
+    assert_eq!(vec![&wheel_id], model.wheels().unwrap());
+
+    let report = model.validate();
+    assert!(report
+        .issues()
+        .contains(&ValidationIssue::MissingSteering { frame: wheel_id }));
+}
+
+#[test]
+fn when_adding_a_child_to_an_element_with_the_wheel_role_it_should_remain_a_wheel() {
+    let mut tree = KinematicTree::new();
+
+    let body = create_generic_non_actuated_element("body".to_string());
+    let body_id = *body.id();
+    let wheel = ReferenceFrame::new_with_role(
+        "wheel".to_string(),
+        FrameDofType::RevoluteY,
+        true,
+        FrameRole::Wheel,
+    );
+    let wheel_id = *wheel.id();
+    let suspension = ReferenceFrame::new_with_role(
+        "suspension".to_string(),
+        FrameDofType::RevoluteY,
+        true,
+        FrameRole::Suspension,
+    );
+    let suspension_id = *suspension.id();
+    let sensor = create_generic_non_actuated_element("sensor".to_string());
+
+    tree.add_element(
+        body,
+        FrameID::none(),
+        Translation3::identity(),
+        UnitQuaternion::identity(),
+    )
+    .unwrap();
+    tree.add_element(
+        wheel,
+        body_id,
+        Translation3::identity(),
+        UnitQuaternion::identity(),
+    )
+    .unwrap();
+    tree.add_element(
+        suspension,
+        body_id,
+        Translation3::identity(),
+        UnitQuaternion::identity(),
+    )
+    .unwrap();
+    tree.add_element(
+        sensor,
+        wheel_id,
+        Translation3::identity(),
+        UnitQuaternion::identity(),
+    )
+    .unwrap();
+
+    assert!(tree.is_wheel(&wheel_id).unwrap());
+    assert!(!tree.is_wheel(&suspension_id).unwrap());
+    assert_eq!(1, tree.number_of_wheels());
+}
+
+#[test]
+fn when_testing_if_a_frame_is_an_ancestor_it_should_return_false_if_it_is_not() {
+    let mut model = MotionModel::new();
+    let body_id = add_body_to_model(&mut model).unwrap();
+
+    // Leg 1
+    let (sender, receiver) = crossbeam_channel::unbounded();
+    let (cmd_sender, _) = crossbeam_channel::unbounded();
+    let mut hardware_actuator = MockHardwareActuator {
+        receiver,
+        sender: sender.clone(),
+        command_sender: cmd_sender,
+        update_sender: None,
+        id: None,
+    };
+    let change_processor = Box::new(HardwareChangeProcessor::new(1000));
+
+    let actuator = Actuator::new(&mut hardware_actuator, &change_processor).unwrap();
+    let suspension_id_leg1 = add_actuated_joint_to_model(
+        &mut model,
+        &body_id,
+        DriveModulePosition::LeftFront,
+        FrameDofType::RevoluteZ,
+        actuator,
+    )
+    .unwrap();
+
+    let (sender1, receiver1) = crossbeam_channel::unbounded();
+    let (cmd_sender1, _) = crossbeam_channel::unbounded();
+    let mut hardware_actuator1 = MockHardwareActuator {
+        receiver: receiver1,
+        sender: sender1.clone(),
+        command_sender: cmd_sender1,
+        update_sender: None,
+        id: None,
+    };
+    let change_processor = Box::new(HardwareChangeProcessor::new(10));
+
+    let actuator1 = Actuator::new(&mut hardware_actuator1, &change_processor).unwrap();
+
+    let steering_id_leg1 = add_steering_to_model(
+        &mut model,
+        &suspension_id_leg1,
+        DriveModulePosition::LeftFront,
+        actuator1,
+    )
+    .unwrap();
+
+    let (wheel_sender1, wheel_receiver1) = crossbeam_channel::unbounded();
+    let (wheel_cmd_sender1, _) = crossbeam_channel::unbounded();
+    let mut wheel_hardware_actuator1 = MockHardwareActuator {
+        receiver: wheel_receiver1,
+        sender: wheel_sender1,
+        command_sender: wheel_cmd_sender1,
+        update_sender: None,
+        id: None,
+    };
+
+    let wheel_actuator1 = Actuator::new(&mut wheel_hardware_actuator1, &change_processor).unwrap();
+
+    let wheel_id_leg1 = add_wheel_to_model(&mut model, &steering_id_leg1, wheel_actuator1).unwrap();
+
+    // Leg 2
+    let suspension_id_leg2 =
+        add_suspension_to_model(&mut model, &body_id, DriveModulePosition::RightFront).unwrap();
+
+    let (sender2, receiver2) = crossbeam_channel::unbounded();
+    let (cmd_sender2, _) = crossbeam_channel::unbounded();
+    let mut hardware_actuator2 = MockHardwareActuator {
+        receiver: receiver2,
+        sender: sender2.clone(),
+        command_sender: cmd_sender2,
+        update_sender: None,
+        id: None,
+    };
+
+    let actuator2 = Actuator::new(&mut hardware_actuator2, &change_processor).unwrap();
+
+    let steering_id_leg2 = add_steering_to_model(
+        &mut model,
+        &suspension_id_leg2,
         DriveModulePosition::RightFront,
         actuator2,
     )
@@ -5568,3 +8045,426 @@ fn when_testing_if_a_frame_is_the_world_frame_it_should_return_true_if_it_is() {
 
     assert!(model.is_world(&FrameID::none()));
 }
+
+#[test]
+fn when_creating_physical_properties_from_mass_properties_it_should_compute_the_spatial_inertia() {
+    let properties = ChassisElementPhysicalProperties::from_mass_properties(
+        2.0,
+        Vector3::new(1.0, 0.0, 0.0),
+        Matrix3::identity(),
+    );
+
+    assert_eq!(2.0, properties.mass());
+    let spatial_inertia = properties.spatial_inertia();
+    assert_eq!(2.0, spatial_inertia[(0, 0)]);
+
+    // The center of mass is offset along the x-axis, so linear motion along the y-axis
+    // couples to rotation around the z-axis
+    assert_eq!(2.0, spatial_inertia[(5, 1)]);
+    assert_eq!(2.0, spatial_inertia[(1, 5)]);
+
+    // Parallel axis theorem
+    assert_eq!(1.0, spatial_inertia[(3, 3)]);
+    assert_eq!(3.0, spatial_inertia[(4, 4)]);
+    assert_eq!(3.0, spatial_inertia[(5, 5)]);
+    assert_eq!(spatial_inertia, spatial_inertia.transpose());
+}
+
+#[test]
+fn when_setting_frame_metadata_it_should_find_the_frames_by_key_and_value() {
+    let mut model = MotionModel::new();
+    let body_id = add_body_to_model(&mut model).unwrap();
+    let properties = ChassisElementPhysicalProperties::new(
+        1.0,
+        Vector3::<f64>::identity(),
+        Matrix3::<f64>::identity(),
+        Matrix6::<f64>::identity(),
+    );
+    let mut add_attachment = |name: &str| {
+        model
+            .add_static_chassis_element(
+                name.to_string(),
+                body_id,
+                Translation3::new(1.0, 0.0, 0.0),
+                UnitQuaternion::identity(),
+                properties,
+            )
+            .unwrap()
+    };
+    let front = add_attachment("front_bumper");
+    let rear = add_attachment("rear_bumper");
+
+    assert_eq!(
+        Ok(None),
+        model.set_frame_metadata(&front, "bumper".to_string(), String::new())
+    );
+    model
+        .set_frame_metadata(&rear, "bumper".to_string(), String::new())
+        .unwrap();
+    model
+        .set_frame_metadata(&rear, "side".to_string(), "front".to_string())
+        .unwrap();
+    assert_eq!(
+        Ok(Some("front".to_string())),
+        model.set_frame_metadata(&rear, "side".to_string(), "rear".to_string())
+    );
+
+    assert_eq!(Ok(Some("rear")), model.frame_metadata(&rear, "side"));
+    assert_eq!(Ok(None), model.frame_metadata(&front, "side"));
+    assert_eq!(Ok(vec![]), model.frame_metadata_entries(&body_id));
+    assert_eq!(
+        Ok(vec![("bumper", ""), ("side", "rear")]),
+        model.frame_metadata_entries(&rear)
+    );
+
+    let mut bumpers = vec![&front, &rear];
+    bumpers.sort();
+    assert_eq!(bumpers, model.frames_with_metadata("bumper", None));
+    assert_eq!(
+        vec![&rear],
+        model.frames_with_metadata("side", Some("rear"))
+    );
+    assert!(model.frames_with_metadata("side", Some("front")).is_empty());
+    assert!(model.frames_with_metadata("payload", None).is_empty());
+
+    assert_eq!(
+        Ok(Some(String::new())),
+        model.remove_frame_metadata(&front, "bumper")
+    );
+    assert_eq!(Ok(None), model.remove_frame_metadata(&front, "bumper"));
+    assert_eq!(vec![&rear], model.frames_with_metadata("bumper", None));
+
+    model.remove_element(&rear).unwrap();
+    assert!(model.frames_with_metadata("bumper", None).is_empty());
+
+    let invalid_id = FrameID::new();
+    assert_eq!(
+        Err(Error::MissingFrameElement { id: invalid_id }),
+        model.set_frame_metadata(&invalid_id, "bumper".to_string(), String::new())
+    );
+    assert_eq!(
+        Err(Error::MissingFrameElement { id: invalid_id }),
+        model.frame_metadata(&invalid_id, "bumper")
+    );
+}
+
+#[test]
+fn when_adding_frame_aliases_it_should_resolve_the_frames_by_alias() {
+    let mut model = MotionModel::new();
+    let body_id = add_body_to_model(&mut model).unwrap();
+    let lidar = model
+        .add_static_chassis_element(
+            "lidar".to_string(),
+            body_id,
+            Translation3::new(1.0, 0.0, 0.5),
+            UnitQuaternion::identity(),
+            ChassisElementPhysicalProperties::new(
+                1.0,
+                Vector3::<f64>::identity(),
+                Matrix3::<f64>::identity(),
+                Matrix6::<f64>::identity(),
+            ),
+        )
+        .unwrap();
+
+    model
+        .add_frame_alias(&lidar, "tf:lidar_link".to_string())
+        .unwrap();
+    model
+        .add_frame_alias(&lidar, "can:0x21".to_string())
+        .unwrap();
+    model
+        .add_frame_alias(&body_id, "tf:base_link".to_string())
+        .unwrap();
+
+    // Adding an alias twice to the same frame is allowed
+    assert_eq!(
+        Ok(()),
+        model.add_frame_alias(&lidar, "tf:lidar_link".to_string())
+    );
+    assert_eq!(
+        Err(Error::DuplicateFrameAlias {
+            alias: "tf:lidar_link".to_string()
+        }),
+        model.add_frame_alias(&body_id, "tf:lidar_link".to_string())
+    );
+
+    assert_eq!(Some(&lidar), model.frame_by_alias("tf:lidar_link"));
+    assert_eq!(Some(&lidar), model.frame_by_alias("can:0x21"));
+    assert_eq!(None, model.frame_by_alias("lidar"));
+    assert_eq!(
+        Ok(vec!["can:0x21", "tf:lidar_link"]),
+        model.frame_aliases(&lidar)
+    );
+
+    assert_eq!(Some(lidar), model.remove_frame_alias("can:0x21"));
+    assert_eq!(None, model.remove_frame_alias("can:0x21"));
+    assert_eq!(Ok(vec!["tf:lidar_link"]), model.frame_aliases(&lidar));
+
+    model.remove_element(&lidar).unwrap();
+    assert_eq!(None, model.frame_by_alias("tf:lidar_link"));
+    assert_eq!(Some(&body_id), model.frame_by_alias("tf:base_link"));
+
+    let invalid_id = FrameID::new();
+    assert_eq!(
+        Err(Error::MissingFrameElement { id: invalid_id }),
+        model.add_frame_alias(&invalid_id, "tf:other".to_string())
+    );
+    assert_eq!(
+        Err(Error::MissingFrameElement { id: invalid_id }),
+        model.frame_aliases(&invalid_id)
+    );
+}
+
+#[test]
+fn when_getting_the_chain_to_the_body_it_should_return_all_the_ancestors_in_order() {
+    let mut model = MotionModel::new();
+    let body_id = add_body_to_model(&mut model).unwrap();
+    let change_processor = Box::new(HardwareChangeProcessor::new(10));
+    let (suspension_id, wheel_id, _hardware) =
+        add_module_with_suspension_to_model(&mut model, &body_id, &change_processor);
+    let steering_id = *model.steering_frame_for_wheel(&wheel_id).unwrap();
+
+    assert_eq!(
+        vec![&wheel_id, &steering_id, &suspension_id, &body_id],
+        model.chain_to_body(&wheel_id).unwrap()
+    );
+    assert_eq!(vec![&body_id], model.chain_to_body(&body_id).unwrap());
+
+    let invalid_id = FrameID::new();
+    assert_eq!(
+        Err(Error::MissingFrameElement { id: invalid_id }),
+        model.chain_to_body(&invalid_id)
+    );
+}
+
+#[test]
+fn when_getting_the_chain_between_frames_it_should_pass_through_the_common_ancestor() {
+    let mut model = MotionModel::new();
+    let body_id = add_body_to_model(&mut model).unwrap();
+    let change_processor = Box::new(HardwareChangeProcessor::new(10));
+    let (left_suspension, left_wheel, _left_hardware) =
+        add_module_with_suspension_to_model(&mut model, &body_id, &change_processor);
+    let (right_suspension, right_wheel, _right_hardware) =
+        add_module_with_suspension_to_model(&mut model, &body_id, &change_processor);
+    let left_steering = *model.steering_frame_for_wheel(&left_wheel).unwrap();
+    let right_steering = *model.steering_frame_for_wheel(&right_wheel).unwrap();
+
+    assert_eq!(
+        vec![
+            &left_wheel,
+            &left_steering,
+            &left_suspension,
+            &body_id,
+            &right_suspension,
+            &right_steering,
+            &right_wheel
+        ],
+        model.chain_between(&left_wheel, &right_wheel).unwrap()
+    );
+    assert_eq!(
+        vec![&left_wheel, &left_steering, &left_suspension],
+        model.chain_between(&left_wheel, &left_suspension).unwrap()
+    );
+    assert_eq!(
+        vec![&left_suspension, &left_steering, &left_wheel],
+        model.chain_between(&left_suspension, &left_wheel).unwrap()
+    );
+    assert_eq!(
+        vec![&left_wheel],
+        model.chain_between(&left_wheel, &left_wheel).unwrap()
+    );
+
+    let invalid_id = FrameID::new();
+    assert_eq!(
+        Err(Error::MissingFrameElement { id: invalid_id }),
+        model.chain_between(&left_wheel, &invalid_id)
+    );
+}
+
+#[test]
+fn when_traversing_the_model_it_should_visit_the_frames_in_order() {
+    let mut model = MotionModel::new();
+    assert_eq!(0, model.iter_dfs().count());
+    assert_eq!(0, model.iter_bfs().count());
+
+    let body_id = add_body_to_model(&mut model).unwrap();
+    let change_processor = Box::new(HardwareChangeProcessor::new(10));
+    let (left_suspension, left_wheel, _left_hardware) =
+        add_module_with_suspension_to_model(&mut model, &body_id, &change_processor);
+    let (right_suspension, right_wheel, _right_hardware) =
+        add_module_with_suspension_to_model(&mut model, &body_id, &change_processor);
+    let left_steering = *model.steering_frame_for_wheel(&left_wheel).unwrap();
+    let right_steering = *model.steering_frame_for_wheel(&right_wheel).unwrap();
+
+    assert_eq!(
+        vec![
+            (&body_id, 0),
+            (&left_suspension, 1),
+            (&left_steering, 2),
+            (&left_wheel, 3),
+            (&right_suspension, 1),
+            (&right_steering, 2),
+            (&right_wheel, 3),
+        ],
+        model.iter_dfs().collect::<Vec<_>>()
+    );
+    assert_eq!(
+        vec![
+            (&body_id, 0),
+            (&left_suspension, 1),
+            (&right_suspension, 1),
+            (&left_steering, 2),
+            (&right_steering, 2),
+            (&left_wheel, 3),
+            (&right_wheel, 3),
+        ],
+        model.iter_bfs().collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn when_getting_the_kinematic_tree_it_should_describe_the_structure_of_the_model() {
+    let mut model = MotionModel::new();
+    let body_id = add_body_to_model(&mut model).unwrap();
+    let change_processor = Box::new(HardwareChangeProcessor::new(10));
+    let (suspension_id, wheel_id, _hardware) =
+        add_module_with_suspension_to_model(&mut model, &body_id, &change_processor);
+    let steering_id = *model.steering_frame_for_wheel(&wheel_id).unwrap();
+
+    let tree = model.kinematic_tree();
+    assert!(!tree.is_empty());
+    assert_eq!(&body_id, tree.body_element().unwrap().id());
+    assert_eq!(Ok(true), tree.is_body(&body_id));
+    assert_eq!(Ok(true), tree.is_wheel(&wheel_id));
+    assert_eq!(1, tree.number_of_wheels());
+    assert_eq!(
+        vec![&wheel_id],
+        tree.wheels().unwrap().map(|f| f.id()).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        vec![&suspension_id],
+        tree.children_of(&body_id)
+            .unwrap()
+            .map(|f| f.id())
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(&steering_id, tree.parent_of(&wheel_id).unwrap().id());
+    assert_eq!(
+        vec![suspension_id, steering_id, wheel_id],
+        tree.subtree_of(&suspension_id)
+    );
+    assert_eq!(4, tree.elements().count());
+    assert_eq!(
+        model.iter_dfs().collect::<Vec<_>>(),
+        tree.iter_dfs().collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn when_getting_isometries_it_should_match_the_homogeneous_transforms() {
+    let mut model = MotionModel::new();
+    let body_id = add_body_to_model(&mut model).unwrap();
+    let change_processor = Box::new(HardwareChangeProcessor::new(10));
+    let (left_suspension, left_wheel, _left_hardware) =
+        add_module_with_suspension_to_model(&mut model, &body_id, &change_processor);
+    let (_right_suspension, right_wheel, _right_hardware) =
+        add_module_with_suspension_to_model(&mut model, &body_id, &change_processor);
+    model.set_body_pose_in_world(Isometry3::new(
+        Vector3::new(1.0, 2.0, 3.0),
+        Vector3::new(0.0, 0.0, 0.5),
+    ));
+
+    let assert_same = |isometry: Isometry3<f64>, matrix: Matrix4<f64>| {
+        assert!((isometry.to_homogeneous() - matrix).abs().max() < 1e-12);
+    };
+    for id in [body_id, left_suspension, left_wheel, right_wheel] {
+        assert_same(
+            model.isometry_to_body(&id).unwrap(),
+            model.homogeneous_transform_to_body(&id).unwrap(),
+        );
+        assert_same(
+            model.isometry_to_parent(&id).unwrap(),
+            model.homogeneous_transform_to_parent(&id).unwrap(),
+        );
+        assert_same(
+            model.isometry_to_world(&id).unwrap(),
+            model.homogeneous_transform_to_world(&id).unwrap(),
+        );
+    }
+
+    assert_same(
+        model
+            .isometry_to_ancestor(&left_wheel, &left_suspension)
+            .unwrap(),
+        model
+            .homogeneous_transform_to_ancestor(&left_wheel, &left_suspension)
+            .unwrap(),
+    );
+    assert_same(
+        model
+            .isometry_between_frames(&left_wheel, &right_wheel)
+            .unwrap(),
+        model
+            .homogeneous_transform_between_frames(&left_wheel, &right_wheel)
+            .unwrap(),
+    );
+    assert_eq!(
+        Isometry3::identity(),
+        model.isometry_to_parent(&body_id).unwrap()
+    );
+
+    let missing = FrameID::new();
+    assert_eq!(
+        Err(Error::MissingFrameElement { id: missing }),
+        model.isometry_to_body(&missing)
+    );
+    assert_eq!(
+        Err(Error::MissingFrameElement { id: right_wheel }),
+        model.isometry_to_ancestor(&left_wheel, &right_wheel)
+    );
+}
+
+#[test]
+fn when_applying_wrenches_it_should_store_them_per_frame() {
+    let mut model = MotionModel::new();
+    assert_eq!(&Vector3::new(0.0, 0.0, -STANDARD_GRAVITY), model.gravity());
+
+    let body_id = add_body_to_model(&mut model).unwrap();
+    let change_processor = Box::new(HardwareChangeProcessor::new(10));
+    let (suspension_id, _wheel_id, _hardware) =
+        add_module_with_suspension_to_model(&mut model, &body_id, &change_processor);
+
+    let wrench = Wrench::new(Vector3::new(0.0, 0.0, -10.0), Vector3::zeros());
+    assert_eq!(Ok(None), model.apply_wrench(&suspension_id, wrench));
+    assert_eq!(Some(&wrench), model.external_wrench(&suspension_id));
+    assert_eq!(
+        Ok(Some(wrench)),
+        model.apply_wrench(&suspension_id, Wrench::zero())
+    );
+    assert_eq!(1, model.external_wrenches().count());
+    assert_eq!(None, model.external_wrench(&body_id));
+
+    let missing = FrameID::new();
+    assert_eq!(
+        Err(Error::MissingFrameElement { id: missing }),
+        model.apply_wrench(&missing, wrench)
+    );
+
+    assert_eq!(
+        Some(Wrench::zero()),
+        model.remove_external_wrench(&suspension_id)
+    );
+    assert_eq!(None, model.remove_external_wrench(&suspension_id));
+
+    model.apply_wrench(&suspension_id, wrench).unwrap();
+    model.remove_element(&suspension_id).unwrap();
+    assert_eq!(0, model.external_wrenches().count());
+
+    model.set_gravity(Vector3::new(0.0, 0.0, -1.62));
+    model.set_body_pose_in_world(Isometry3::new(
+        Vector3::zeros(),
+        Vector3::new(0.0, 0.5 * std::f64::consts::PI, 0.0),
+    ));
+    assert!((Vector3::new(1.62, 0.0, 0.0) - model.gravity_in_body()).norm() < 1e-12);
+}
@@ -0,0 +1,654 @@
+//! Provides the specifications that are used to create a complete [MotionModel] in a single call.
+//!
+//! Most vehicles consist of a body and a number of similar wheel modules. Each module has an
+//! optional suspension frame, a steering frame and a wheel frame. Instead of adding each frame
+//! separately a [ModuleSpec] describes a complete module and [MotionModel::with_modules] creates
+//! the body and all the modules at once.
+//!
+//! The frames of a module are connected as follows:
+//!
+//! ```text
+//! body -> [suspension] -> steering -> wheel
+//! ```
+//!
+//! The first frame of the module is placed at the position and orientation of the module relative
+//! to the body. When the module has a suspension frame the steering frame is placed at the origin
+//! of the suspension frame. The wheel is placed at the wheel offset relative to the steering frame.
+//!
+//! The [MotionModelBuilder] collects the body and the modules with a fluent interface and reports
+//! all the problems with the specifications at once when the model is built.
+//!
+//! Modules can also be added to a model that already has a body with
+//! [MotionModel::add_drive_module]. The model keeps track of the modules that were created from a
+//! [ModuleSpec], so that [MotionModel::drive_modules] can return each module as a [DriveModule]
+//! that groups the frames and the actuators of the module.
+
+use std::collections::{HashMap, HashSet};
+
+use nalgebra::{Translation3, UnitQuaternion};
+
+use crate::{
+    instrumentation::{event, span},
+    Error,
+};
+
+use super::{
+    frame_elements::{Actuator, FrameDofType, FrameID, JointConstraint},
+    model::{ChassisElementPhysicalProperties, MotionModel, WheelProperties},
+};
+
+#[cfg(test)]
+#[path = "module_spec_tests.rs"]
+mod module_spec_tests;
+
+/// Describes the body of the vehicle.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BodySpec {
+    /// The name of the body.
+    name: String,
+
+    /// The position of the body relative to the world.
+    position: Translation3<f64>,
+
+    /// The orientation of the body relative to the world.
+    orientation: UnitQuaternion<f64>,
+
+    /// The physical properties of the body.
+    physical_properties: ChassisElementPhysicalProperties,
+}
+
+impl BodySpec {
+    /// Creates a new [BodySpec] instance.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'name' - The name of the body.
+    /// * 'position' - The position of the body relative to the world.
+    /// * 'orientation' - The orientation of the body relative to the world.
+    /// * 'physical_properties' - The mass and inertia of the body.
+    pub fn new(
+        name: String,
+        position: Translation3<f64>,
+        orientation: UnitQuaternion<f64>,
+        physical_properties: ChassisElementPhysicalProperties,
+    ) -> Self {
+        Self {
+            name,
+            position,
+            orientation,
+            physical_properties,
+        }
+    }
+}
+
+/// Describes the suspension frame of a wheel module.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct SuspensionSpec {
+    /// The degree of freedom of the suspension.
+    degree_of_freedom: FrameDofType,
+
+    /// The physical properties of the suspension.
+    physical_properties: ChassisElementPhysicalProperties,
+
+    /// The limits on the motion of the suspension.
+    constraint: JointConstraint,
+}
+
+/// Describes a wheel module, i.e. an optional suspension frame, a steering frame and a wheel.
+///
+/// The actuators of the module are optional. Modules without actuators can be connected to the
+/// hardware later with [MotionModel::set_actuator].
+pub struct ModuleSpec {
+    /// The name of the module. The frames of the module are named after the module.
+    name: String,
+
+    /// The position of the module relative to the body.
+    position: Translation3<f64>,
+
+    /// The orientation of the module relative to the body.
+    orientation: UnitQuaternion<f64>,
+
+    /// The position of the center of the wheel relative to the steering frame.
+    wheel_offset: Translation3<f64>,
+
+    /// The geometry of the wheel.
+    wheel_properties: WheelProperties,
+
+    /// The physical properties of the steering frame.
+    steering_physical_properties: ChassisElementPhysicalProperties,
+
+    /// The physical properties of the wheel.
+    wheel_physical_properties: ChassisElementPhysicalProperties,
+
+    /// The suspension of the module, if the module has one.
+    suspension: Option<SuspensionSpec>,
+
+    /// The actuator that steers the module.
+    steering_actuator: Option<Actuator>,
+
+    /// The actuator that drives the wheel.
+    wheel_actuator: Option<Actuator>,
+}
+
+impl ModuleSpec {
+    /// Creates a new [ModuleSpec] instance for a module without a suspension, in which the
+    /// center of the wheel is at the origin of the steering frame.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'name' - The name of the module.
+    /// * 'position' - The position of the module relative to the body.
+    /// * 'orientation' - The orientation of the module relative to the body.
+    /// * 'wheel_properties' - The geometry of the wheel.
+    /// * 'steering_physical_properties' - The mass and inertia of the steering frame.
+    /// * 'wheel_physical_properties' - The mass and inertia of the wheel.
+    pub fn new(
+        name: String,
+        position: Translation3<f64>,
+        orientation: UnitQuaternion<f64>,
+        wheel_properties: WheelProperties,
+        steering_physical_properties: ChassisElementPhysicalProperties,
+        wheel_physical_properties: ChassisElementPhysicalProperties,
+    ) -> Self {
+        Self {
+            name,
+            position,
+            orientation,
+            wheel_offset: Translation3::identity(),
+            wheel_properties,
+            steering_physical_properties,
+            wheel_physical_properties,
+            suspension: None,
+            steering_actuator: None,
+            wheel_actuator: None,
+        }
+    }
+
+    /// Returns the name of the module.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Checks that the geometry of the module is valid.
+    fn validate(&self) -> Result<(), Error> {
+        let invalid = |reason: &str| Error::InvalidModuleSpecification {
+            name: self.name.clone(),
+            reason: reason.to_string(),
+        };
+
+        if !self.position.vector.iter().all(|v| v.is_finite())
+            || !self.wheel_offset.vector.iter().all(|v| v.is_finite())
+        {
+            return Err(invalid("the position of the module is not finite"));
+        }
+
+        let radius = self.wheel_properties.radius();
+        if !radius.is_finite() || radius <= 0.0 {
+            return Err(invalid("the wheel radius should be a positive number"));
+        }
+
+        if let Some(suspension) = &self.suspension {
+            if suspension.degree_of_freedom == FrameDofType::Static {
+                return Err(invalid("the suspension should be able to move"));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the specification with the given actuator for the steering frame.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'actuator' - The actuator that steers the module.
+    pub fn with_steering_actuator(mut self, actuator: Actuator) -> Self {
+        self.steering_actuator = Some(actuator);
+        self
+    }
+
+    /// Returns the specification with a suspension frame between the body and the steering
+    /// frame.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'degree_of_freedom' - The degree of freedom of the suspension.
+    /// * 'physical_properties' - The mass and inertia of the suspension.
+    /// * 'constraint' - The limits on the motion of the suspension.
+    pub fn with_suspension(
+        mut self,
+        degree_of_freedom: FrameDofType,
+        physical_properties: ChassisElementPhysicalProperties,
+        constraint: JointConstraint,
+    ) -> Self {
+        self.suspension = Some(SuspensionSpec {
+            degree_of_freedom,
+            physical_properties,
+            constraint,
+        });
+        self
+    }
+
+    /// Returns the specification with the given actuator for the wheel.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'actuator' - The actuator that drives the wheel.
+    pub fn with_wheel_actuator(mut self, actuator: Actuator) -> Self {
+        self.wheel_actuator = Some(actuator);
+        self
+    }
+
+    /// Returns the specification with the center of the wheel at the given position relative to
+    /// the steering frame.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'offset' - The position of the center of the wheel relative to the steering frame.
+    pub fn with_wheel_offset(mut self, offset: Translation3<f64>) -> Self {
+        self.wheel_offset = offset;
+        self
+    }
+}
+
+/// Stores the [FrameID] of each of the frames of a module that was created from a [ModuleSpec].
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ModuleFrames {
+    /// The ID of the suspension frame, if the module has one.
+    suspension: Option<FrameID>,
+
+    /// The ID of the steering frame.
+    steering: FrameID,
+
+    /// The ID of the wheel frame.
+    wheel: FrameID,
+}
+
+impl ModuleFrames {
+    /// Returns a value indicating whether the given frame is one of the frames of the module.
+    pub fn contains(&self, frame_id: &FrameID) -> bool {
+        self.steering == *frame_id
+            || self.wheel == *frame_id
+            || self.suspension.as_ref() == Some(frame_id)
+    }
+
+    /// Returns the frames that the frames of the module are mapped to, or [None] if one of the
+    /// frames is not in the map.
+    pub(super) fn mapped(&self, map: &HashMap<FrameID, FrameID>) -> Option<ModuleFrames> {
+        let suspension = match &self.suspension {
+            Some(id) => Some(*map.get(id)?),
+            None => None,
+        };
+
+        Some(ModuleFrames {
+            suspension,
+            steering: *map.get(&self.steering)?,
+            wheel: *map.get(&self.wheel)?,
+        })
+    }
+
+    /// Returns the [FrameID] of the steering frame.
+    pub fn steering(&self) -> &FrameID {
+        &self.steering
+    }
+
+    /// Returns the [FrameID] of the suspension frame, or [None] if the module has no
+    /// suspension.
+    pub fn suspension(&self) -> Option<&FrameID> {
+        self.suspension.as_ref()
+    }
+
+    /// Returns the [FrameID] of the wheel frame.
+    pub fn wheel(&self) -> &FrameID {
+        &self.wheel
+    }
+}
+
+/// Collects the specifications of the body and the wheel modules of a [MotionModel] and creates
+/// the model in a single step.
+///
+/// The specifications are not checked until [MotionModelBuilder::build] is called, at which
+/// point all the problems are reported together.
+///
+/// ## Examples
+///
+/// ```
+/// use nalgebra::{Matrix3, Translation3, UnitQuaternion, Vector3};
+/// use swerve_vehicle_descriptors::model_elements::{
+///     model::{ChassisElementPhysicalProperties, MotionModel, WheelProperties},
+///     module_spec::{BodySpec, ModuleSpec},
+/// };
+///
+/// let properties = || {
+///     ChassisElementPhysicalProperties::from_mass_properties(
+///         1.0,
+///         Vector3::zeros(),
+///         Matrix3::identity(),
+///     )
+/// };
+/// let module = |name: &str, x: f64, y: f64| {
+///     ModuleSpec::new(
+///         name.to_string(),
+///         Translation3::new(x, y, 0.0),
+///         UnitQuaternion::identity(),
+///         WheelProperties::new(0.1, 0.05, Vector3::zeros()),
+///         properties(),
+///         properties(),
+///     )
+///     .with_wheel_offset(Translation3::new(0.0, 0.0, -0.1))
+/// };
+///
+/// let model = MotionModel::builder()
+///     .body(BodySpec::new(
+///         "body".to_string(),
+///         Translation3::identity(),
+///         UnitQuaternion::identity(),
+///         properties(),
+///     ))
+///     .drive_module(module("front_left", 1.0, 0.5))
+///     .drive_module(module("front_right", 1.0, -0.5))
+///     .drive_module(module("rear_left", -1.0, 0.5))
+///     .drive_module(module("rear_right", -1.0, -0.5))
+///     .build()
+///     .unwrap();
+///
+/// assert_eq!(4, model.number_of_wheels());
+/// ```
+#[derive(Default)]
+pub struct MotionModelBuilder {
+    /// The specification of the body.
+    body: Option<BodySpec>,
+
+    /// The specifications of the wheel modules, in the order in which they were added.
+    modules: Vec<ModuleSpec>,
+}
+
+impl MotionModelBuilder {
+    /// Returns the builder with the given specification for the body, replacing the
+    /// specification that was provided before.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'body' - The specification of the body.
+    pub fn body(mut self, body: BodySpec) -> Self {
+        self.body = Some(body);
+        self
+    }
+
+    /// Creates the model from the specifications.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::ModelBuildFailed] - Returned when the model could not be built. The error
+    ///   contains an [Error::MissingFrameElement] when no body was specified and an
+    ///   [Error::InvalidModuleSpecification] for each module that is not valid or that has the
+    ///   same name as a module that was added before it.
+    pub fn build(self) -> Result<MotionModel, Error> {
+        let _span = span!(DEBUG, "build_model", modules = self.modules.len());
+        let mut errors = Vec::new();
+        if self.body.is_none() {
+            errors.push(Error::MissingFrameElement {
+                id: FrameID::none(),
+            });
+        }
+
+        let mut names = HashSet::new();
+        for module in self.modules.iter() {
+            if let Err(error) = module.validate() {
+                event!(WARN, module = module.name(), error = %error, "The module specification is not valid");
+                errors.push(error);
+            }
+
+            if !names.insert(module.name()) {
+                errors.push(Error::InvalidModuleSpecification {
+                    name: module.name().to_string(),
+                    reason: "a module with the same name was added before".to_string(),
+                });
+            }
+        }
+
+        match self.body {
+            Some(body) if errors.is_empty() => MotionModel::with_modules(body, self.modules)
+                .map(|(model, _)| model)
+                .map_err(|error| Error::ModelBuildFailed {
+                    errors: vec![error],
+                }),
+            _ => {
+                event!(WARN, errors = errors.len(), "The model could not be built");
+                Err(Error::ModelBuildFailed { errors })
+            }
+        }
+    }
+
+    /// Returns the builder with the given wheel module added after the modules that were
+    /// added before.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'module' - The specification of the wheel module.
+    pub fn drive_module(mut self, module: ModuleSpec) -> Self {
+        self.modules.push(module);
+        self
+    }
+}
+
+/// A wheel module of a [MotionModel], i.e. the suspension, steering and wheel frames of the
+/// module together with their actuators.
+///
+/// The module borrows the model it belongs to, see [MotionModel::drive_modules].
+#[derive(Clone, Copy)]
+pub struct DriveModule<'a> {
+    /// The name of the module.
+    name: &'a str,
+
+    /// The frames of the module.
+    frames: &'a ModuleFrames,
+
+    /// The model that contains the module.
+    model: &'a MotionModel,
+}
+
+impl<'a> DriveModule<'a> {
+    /// Returns the frames of the module.
+    pub fn frames(&self) -> &'a ModuleFrames {
+        self.frames
+    }
+
+    /// Returns the name of the module.
+    pub fn name(&self) -> &'a str {
+        self.name
+    }
+
+    /// Returns the actuator that steers the module, or [None] if the steering frame has no
+    /// actuator.
+    pub fn steering_actuator(&self) -> Option<&'a Actuator> {
+        self.model.actuator_for(&self.frames.steering).ok()
+    }
+
+    /// Returns the actuator that drives the wheel, or [None] if the wheel has no actuator.
+    pub fn wheel_actuator(&self) -> Option<&'a Actuator> {
+        self.model.actuator_for(&self.frames.wheel).ok()
+    }
+
+    /// Returns the geometry of the wheel of the module.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the wheel has no wheel properties.
+    pub fn wheel_properties(&self) -> Result<&'a WheelProperties, Error> {
+        self.model.wheel_properties(&self.frames.wheel)
+    }
+}
+
+impl MotionModel {
+    /// Adds a wheel module to the model. The first frame of the module is attached to the body.
+    /// Returns the frames of the module.
+    ///
+    /// The module is validated before any frame is created.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'module' - The specification of the wheel module.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::InvalidModuleSpecification] - Returned when the module has a position that is
+    ///   not finite, a wheel radius that is not positive or a suspension that can not move.
+    /// * [Error::MissingFrameElement] - Returned when the model has no body.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use nalgebra::{Matrix3, Translation3, UnitQuaternion, Vector3};
+    /// use swerve_vehicle_descriptors::model_elements::{
+    ///     model::{ChassisElementPhysicalProperties, MotionModel, WheelProperties},
+    ///     module_spec::ModuleSpec,
+    /// };
+    ///
+    /// let properties = || {
+    ///     ChassisElementPhysicalProperties::from_mass_properties(
+    ///         1.0,
+    ///         Vector3::zeros(),
+    ///         Matrix3::identity(),
+    ///     )
+    /// };
+    ///
+    /// let mut model = MotionModel::new();
+    /// model
+    ///     .add_body(
+    ///         "body".to_string(),
+    ///         Translation3::identity(),
+    ///         UnitQuaternion::identity(),
+    ///         properties(),
+    ///     )
+    ///     .unwrap();
+    ///
+    /// let frames = model
+    ///     .add_drive_module(
+    ///         ModuleSpec::new(
+    ///             "front_left".to_string(),
+    ///             Translation3::new(1.0, 0.5, 0.0),
+    ///             UnitQuaternion::identity(),
+    ///             WheelProperties::new(0.1, 0.05, Vector3::zeros()),
+    ///             properties(),
+    ///             properties(),
+    ///         )
+    ///         .with_wheel_offset(Translation3::new(0.0, 0.0, -0.1)),
+    ///     )
+    ///     .unwrap();
+    ///
+    /// let module = model.drive_modules().next().unwrap();
+    /// assert_eq!("front_left", module.name());
+    /// assert_eq!(&frames, module.frames());
+    /// ```
+    pub fn add_drive_module(&mut self, module: ModuleSpec) -> Result<ModuleFrames, Error> {
+        module.validate()?;
+        let body_id = *self.body()?;
+
+        let (suspension, steering_parent, steering_position, steering_orientation) =
+            match module.suspension {
+                Some(suspension) => {
+                    let id = self.add_suspension_element(
+                        format!("{}_suspension", module.name),
+                        suspension.degree_of_freedom,
+                        body_id,
+                        module.position,
+                        module.orientation,
+                        suspension.physical_properties,
+                        suspension.constraint,
+                    )?;
+                    (
+                        Some(id),
+                        id,
+                        Translation3::identity(),
+                        UnitQuaternion::identity(),
+                    )
+                }
+                None => (None, body_id, module.position, module.orientation),
+            };
+
+        let steering = self.add_steering_element_with_optional_actuator(
+            format!("{}_steering", module.name),
+            FrameDofType::RevoluteZ,
+            steering_parent,
+            steering_position,
+            steering_orientation,
+            module.steering_physical_properties,
+            module.steering_actuator,
+        )?;
+
+        let wheel = self.add_wheel_element(
+            format!("{}_wheel", module.name),
+            steering,
+            module.wheel_offset,
+            UnitQuaternion::identity(),
+            module.wheel_physical_properties,
+            module.wheel_actuator,
+            Some(module.wheel_properties),
+        )?;
+
+        let frames = ModuleFrames {
+            suspension,
+            steering,
+            wheel,
+        };
+        self.drive_modules.push((module.name, frames));
+        Ok(frames)
+    }
+
+    /// Returns a [MotionModelBuilder] that creates a new model.
+    pub fn builder() -> MotionModelBuilder {
+        MotionModelBuilder::default()
+    }
+
+    /// Returns the wheel modules of the model, in the order in which they were added. Only the
+    /// modules that were created from a [ModuleSpec] are returned. A module is no longer
+    /// returned once one of its frames is removed from the model.
+    pub fn drive_modules(&self) -> impl Iterator<Item = DriveModule<'_>> {
+        self.drive_modules
+            .iter()
+            .map(move |(name, frames)| DriveModule {
+                name,
+                frames,
+                model: self,
+            })
+    }
+
+    /// Creates a new model that consists of the given body and wheel modules. Returns the model
+    /// together with the frames of each module, in the same order as the modules.
+    ///
+    /// All the modules are validated before any frame is created.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'body' - The specification of the body.
+    /// * 'modules' - The specifications of the wheel modules.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::InvalidModuleSpecification] - Returned when a module has a position that is not
+    ///   finite, a wheel radius that is not positive or a suspension that can not move.
+    pub fn with_modules(
+        body: BodySpec,
+        modules: Vec<ModuleSpec>,
+    ) -> Result<(Self, Vec<ModuleFrames>), Error> {
+        for module in modules.iter() {
+            module.validate()?;
+        }
+
+        let mut model = Self::new();
+        model.add_body(
+            body.name,
+            body.position,
+            body.orientation,
+            body.physical_properties,
+        )?;
+
+        let frames = modules
+            .into_iter()
+            .map(|module| model.add_drive_module(module))
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        Ok((model, frames))
+    }
+}
@@ -0,0 +1,265 @@
+use nalgebra::{Translation3, UnitQuaternion, Vector3};
+
+use crate::{
+    change_notification_processing::HardwareChangeProcessor,
+    model_elements::{
+        frame_elements::{Actuator, FrameDofType, FrameRole, JointConstraint},
+        model::{MotionModel, WheelProperties},
+    },
+    number_space::NumberSpaceType,
+    test_fixtures::{physical_properties, physical_properties_with_mass},
+    test_support::MockActuator,
+    Error,
+};
+
+use super::{BodySpec, ModuleSpec};
+
+fn body_spec() -> BodySpec {
+    BodySpec::new(
+        "body".to_string(),
+        Translation3::identity(),
+        UnitQuaternion::identity(),
+        physical_properties_with_mass(10.0, Vector3::zeros()),
+    )
+}
+
+fn module_spec(name: &str, x: f64, y: f64) -> ModuleSpec {
+    ModuleSpec::new(
+        name.to_string(),
+        Translation3::new(x, y, 0.0),
+        UnitQuaternion::identity(),
+        WheelProperties::new(0.1, 0.05, Vector3::zeros()),
+        physical_properties(),
+        physical_properties_with_mass(2.0, Vector3::zeros()),
+    )
+    .with_wheel_offset(Translation3::new(0.0, 0.0, -0.2))
+}
+
+fn actuator(change_processor: &HardwareChangeProcessor) -> Actuator {
+    let mut hardware = MockActuator::new(NumberSpaceType::LinearUnlimited);
+    Actuator::new(&mut hardware, change_processor).unwrap()
+}
+
+#[test]
+fn when_creating_a_model_with_modules_it_should_add_the_frames_of_each_module() {
+    let modules = vec![
+        module_spec("left_front", 1.0, 0.5),
+        module_spec("left_rear", -1.0, 0.5),
+        module_spec("right_rear", -1.0, -0.5),
+        module_spec("right_front", 1.0, -0.5),
+    ];
+
+    let (mut model, frames) = MotionModel::with_modules(body_spec(), modules).unwrap();
+    assert_eq!(4, frames.len());
+    assert_eq!(4, model.number_of_wheels());
+
+    let body = *model.body().unwrap();
+    for (frame, (x, y)) in frames
+        .iter()
+        .zip([(1.0, 0.5), (-1.0, 0.5), (-1.0, -0.5), (1.0, -0.5)])
+    {
+        assert_eq!(None, frame.suspension());
+        assert_eq!(&body, model.parent_of(frame.steering()).unwrap());
+        assert_eq!(frame.steering(), model.parent_of(frame.wheel()).unwrap());
+        assert_eq!(
+            Ok(frame.steering()),
+            model.steering_frame_for_wheel(frame.wheel())
+        );
+        assert_eq!(Ok(FrameRole::Wheel), model.frame_role(frame.wheel()));
+        assert_eq!(Ok(0.1), model.wheel_radius(frame.wheel()));
+        assert_eq!(
+            2.0,
+            model.chassis_element(frame.wheel()).unwrap().mass_in_kg()
+        );
+
+        let wheel_to_body = model.homogeneous_transform_to_body(frame.wheel()).unwrap();
+        assert!((x - wheel_to_body[(0, 3)]).abs() < 1e-12);
+        assert!((y - wheel_to_body[(1, 3)]).abs() < 1e-12);
+        assert!((-0.2 - wheel_to_body[(2, 3)]).abs() < 1e-12);
+    }
+
+    assert_eq!(
+        "left_front_wheel",
+        model.reference_frame(frames[0].wheel()).unwrap().name()
+    );
+
+    // The modules were created without actuators, so the model is not complete yet
+    let (is_valid, _) = model.is_valid();
+    assert!(!is_valid);
+
+    let change_processor = HardwareChangeProcessor::new(10);
+    for frame in frames.iter() {
+        model
+            .set_actuator(frame.steering(), actuator(&change_processor))
+            .unwrap();
+        model
+            .set_actuator(frame.wheel(), actuator(&change_processor))
+            .unwrap();
+    }
+
+    let (is_valid, errors) = model.is_valid();
+    assert!(is_valid, "{:?}", errors);
+}
+
+#[test]
+fn when_creating_a_model_with_suspended_modules_it_should_add_the_suspension_and_actuators() {
+    let change_processor = HardwareChangeProcessor::new(10);
+    let constraint = JointConstraint::new().with_position_limits(-0.05, 0.05);
+    let modules = (0..2)
+        .map(|i| {
+            module_spec(&format!("module_{}", i), 0.0, 1.0 - 2.0 * i as f64)
+                .with_suspension(
+                    FrameDofType::PrismaticZ,
+                    physical_properties_with_mass(0.5, Vector3::zeros()),
+                    constraint,
+                )
+                .with_steering_actuator(actuator(&change_processor))
+                .with_wheel_actuator(actuator(&change_processor))
+        })
+        .collect();
+
+    let (model, frames) = MotionModel::with_modules(body_spec(), modules).unwrap();
+    let (is_valid, errors) = model.is_valid();
+    assert!(is_valid, "{:?}", errors);
+    assert_eq!(2, model.number_of_joint_constraints());
+
+    for frame in frames.iter() {
+        let suspension = frame.suspension().unwrap();
+        assert_eq!(Ok(FrameRole::Suspension), model.frame_role(suspension));
+        assert_eq!(suspension, model.parent_of(frame.steering()).unwrap());
+        assert_eq!(Ok(constraint), model.joint_constraint(suspension));
+
+        assert!(model.actuator_for(frame.steering()).is_ok());
+        assert!(model.actuator_for(frame.wheel()).is_ok());
+    }
+}
+
+#[test]
+fn when_creating_a_model_with_an_invalid_module_it_should_error() {
+    let modules = vec![
+        module_spec("valid", 1.0, 0.5),
+        ModuleSpec::new(
+            "invalid".to_string(),
+            Translation3::new(1.0, -0.5, 0.0),
+            UnitQuaternion::identity(),
+            WheelProperties::new(0.0, 0.05, Vector3::zeros()),
+            physical_properties(),
+            physical_properties(),
+        ),
+    ];
+
+    let result = MotionModel::with_modules(body_spec(), modules);
+    assert!(matches!(
+        result,
+        Err(Error::InvalidModuleSpecification { name, .. }) if name == "invalid"
+    ));
+
+    let modules = vec![module_spec("static", 1.0, 0.5).with_suspension(
+        FrameDofType::Static,
+        physical_properties(),
+        JointConstraint::new(),
+    )];
+    assert!(matches!(
+        MotionModel::with_modules(body_spec(), modules),
+        Err(Error::InvalidModuleSpecification { .. })
+    ));
+
+    let modules = vec![module_spec("unbounded", f64::NAN, 0.5)];
+    assert!(matches!(
+        MotionModel::with_modules(body_spec(), modules),
+        Err(Error::InvalidModuleSpecification { .. })
+    ));
+}
+
+#[test]
+fn when_adding_a_drive_module_it_should_group_the_frames_and_actuators() {
+    let change_processor = HardwareChangeProcessor::new(10);
+    let (mut model, frames) =
+        MotionModel::with_modules(body_spec(), vec![module_spec("left_front", 1.0, 0.5)]).unwrap();
+
+    let added = model
+        .add_drive_module(
+            module_spec("right_front", 1.0, -0.5)
+                .with_steering_actuator(actuator(&change_processor))
+                .with_wheel_actuator(actuator(&change_processor)),
+        )
+        .unwrap();
+    assert_eq!(2, model.number_of_wheels());
+    assert_eq!(
+        model.body().unwrap(),
+        model.parent_of(added.steering()).unwrap()
+    );
+
+    let modules: Vec<_> = model.drive_modules().collect();
+    assert_eq!(2, modules.len());
+    assert_eq!("left_front", modules[0].name());
+    assert_eq!(&frames[0], modules[0].frames());
+    assert!(modules[0].steering_actuator().is_none());
+    assert!(modules[0].wheel_actuator().is_none());
+
+    assert_eq!("right_front", modules[1].name());
+    assert_eq!(&added, modules[1].frames());
+    assert!(modules[1].steering_actuator().is_some());
+    assert!(modules[1].wheel_actuator().is_some());
+    assert_eq!(Ok(0.1), modules[1].wheel_properties().map(|w| w.radius()));
+
+    // Removing a frame of a module removes the module
+    model.remove_element(frames[0].wheel()).unwrap();
+    let names: Vec<_> = model.drive_modules().map(|m| m.name()).collect();
+    assert_eq!(vec!["right_front"], names);
+}
+
+#[test]
+fn when_adding_a_drive_module_without_a_body_it_should_error() {
+    let mut model = MotionModel::new();
+
+    let result = model.add_drive_module(module_spec("module", 1.0, 0.5));
+    assert!(matches!(result, Err(Error::MissingFrameElement { .. })));
+    assert_eq!(0, model.drive_modules().count());
+}
+
+#[test]
+fn when_building_a_model_it_should_add_the_body_and_the_modules() {
+    let model = MotionModel::builder()
+        .body(body_spec())
+        .drive_module(module_spec("left", 1.0, 0.5))
+        .drive_module(module_spec("right", 1.0, -0.5))
+        .build()
+        .unwrap();
+
+    assert!(model.body().is_ok());
+    assert_eq!(2, model.number_of_wheels());
+    let names: Vec<_> = model.drive_modules().map(|m| m.name()).collect();
+    assert_eq!(vec!["left", "right"], names);
+}
+
+#[test]
+fn when_building_an_invalid_model_it_should_report_all_the_errors() {
+    let result = MotionModel::builder()
+        .drive_module(module_spec("left", 1.0, 0.5))
+        .drive_module(module_spec("left", 1.0, -0.5))
+        .drive_module(ModuleSpec::new(
+            "invalid".to_string(),
+            Translation3::new(1.0, f64::NAN, 0.0),
+            UnitQuaternion::identity(),
+            WheelProperties::new(0.1, 0.05, Vector3::zeros()),
+            physical_properties(),
+            physical_properties(),
+        ))
+        .build();
+
+    let errors = match result {
+        Err(Error::ModelBuildFailed { errors }) => errors,
+        _ => panic!("Expected the build to fail"),
+    };
+    assert_eq!(3, errors.len());
+    assert!(matches!(errors[0], Error::MissingFrameElement { .. }));
+    assert!(matches!(
+        &errors[1],
+        Error::InvalidModuleSpecification { name, .. } if name == "left"
+    ));
+    assert!(matches!(
+        &errors[2],
+        Error::InvalidModuleSpecification { name, .. } if name == "invalid"
+    ));
+}
@@ -0,0 +1,303 @@
+//! Provides joints with more than one degree of freedom, e.g. the universal joints and ball
+//! joints of a suspension linkage.
+//!
+//! A [ReferenceFrame](super::frame_elements::ReferenceFrame) moves along, or around, a single
+//! axis of its parent frame. A multi degree of freedom joint is therefore added to a
+//! [MotionModel] as a composite frame element, i.e. a chain of frames that each have one of the
+//! degrees of freedom of the joint, see [MultiDofJointKind::degrees_of_freedom]. The chain starts
+//! with a static mount frame at the center of the joint, so that the joint rotates around its
+//! center rather than around the origin of its parent. All the frames of the chain have their
+//! origin at the center of the joint. Only the last frame of the chain, the output frame, has the
+//! mass and inertia of the element. The other frames are massless so that the physical
+//! properties of the linkage are not distorted.
+//!
+//! The position of each degree of freedom is the position of the matching frame of the chain.
+//! An actuator or a sensor can be attached to each of these frames, and the combined state of
+//! the joint is provided by [MotionModel::multi_dof_joint_state].
+//!
+//! # Examples
+//!
+//! ```
+//! use nalgebra::{Matrix3, Translation3, UnitQuaternion, Vector3};
+//! use swerve_vehicle_descriptors::model_elements::model::{
+//!     ChassisElementPhysicalProperties, MotionModel,
+//! };
+//! use swerve_vehicle_descriptors::model_elements::multi_dof::MultiDofJointKind;
+//!
+//! let properties =
+//!     ChassisElementPhysicalProperties::from_mass_properties(1.0, Vector3::zeros(), Matrix3::identity());
+//! let mut model = MotionModel::new();
+//! let body = model
+//!     .add_body(
+//!         "body".to_string(),
+//!         Translation3::identity(),
+//!         UnitQuaternion::identity(),
+//!         properties,
+//!     )
+//!     .unwrap();
+//!
+//! let ball_joint = model
+//!     .add_multi_dof_joint(
+//!         "upper-arm".to_string(),
+//!         MultiDofJointKind::Spherical,
+//!         body,
+//!         Translation3::new(0.5, 0.3, 0.1),
+//!         UnitQuaternion::identity(),
+//!         properties,
+//!     )
+//!     .unwrap();
+//! assert_eq!(3, ball_joint.frames().len());
+//!
+//! // Joints without hardware are at their zero position
+//! let state = model.multi_dof_joint_state(&ball_joint).unwrap();
+//! assert_eq!(vec![0.0, 0.0, 0.0], state.positions());
+//! ```
+
+use nalgebra::{Matrix3, Translation3, UnitQuaternion, Vector3};
+
+use crate::{hardware::joint_state::JointState, Error};
+
+use super::{
+    frame_elements::{FrameDofType, FrameID, JointConstraint},
+    model::{ChassisElementPhysicalProperties, MotionModel},
+};
+
+#[cfg(test)]
+#[path = "multi_dof_tests.rs"]
+mod multi_dof_tests;
+
+/// Defines the kinds of joints that have more than one degree of freedom.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MultiDofJointKind {
+    /// A planar joint, which translates along the x-axis and the y-axis of the parent frame and
+    /// rotates around the z-axis.
+    Planar,
+
+    /// A spherical joint, or ball joint, which rotates around the z-axis, the y-axis and the
+    /// x-axis, in that order, i.e. the positions are the yaw, pitch and roll angles.
+    Spherical,
+
+    /// A universal joint, which rotates around the x-axis and then around the y-axis.
+    Universal,
+}
+
+impl MultiDofJointKind {
+    /// Returns the degrees of freedom of the joint, in the order of the frames in the chain
+    /// from the parent to the output frame.
+    pub fn degrees_of_freedom(&self) -> &'static [FrameDofType] {
+        match self {
+            MultiDofJointKind::Planar => &[
+                FrameDofType::PrismaticX,
+                FrameDofType::PrismaticY,
+                FrameDofType::RevoluteZ,
+            ],
+            MultiDofJointKind::Spherical => &[
+                FrameDofType::RevoluteZ,
+                FrameDofType::RevoluteY,
+                FrameDofType::RevoluteX,
+            ],
+            MultiDofJointKind::Universal => &[FrameDofType::RevoluteX, FrameDofType::RevoluteY],
+        }
+    }
+}
+
+/// Stores the frames of a joint with more than one degree of freedom.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MultiDofJoint {
+    /// The kind of joint.
+    kind: MultiDofJointKind,
+
+    /// The static frame at the center of the joint, to which the first frame of the joint is
+    /// attached.
+    mount: FrameID,
+
+    /// The frames of the joint, one for each degree of freedom, from the parent to the output
+    /// frame.
+    frames: Vec<FrameID>,
+}
+
+impl MultiDofJoint {
+    /// Returns a value indicating whether the given frame is one of the frames of the joint,
+    /// including the mount frame.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'frame_id' - The [FrameID] of the frame.
+    pub fn contains(&self, frame_id: &FrameID) -> bool {
+        self.mount == *frame_id || self.frames.contains(frame_id)
+    }
+
+    /// Returns the frames of the joint, one for each degree of freedom, ordered from the parent
+    /// to the output frame.
+    pub fn frames(&self) -> &[FrameID] {
+        &self.frames
+    }
+
+    /// Returns a value indicating whether the given frame is one of the massless frames of the
+    /// joint, i.e. the mount frame or one of the frames between the mount and the output frame.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'frame_id' - The [FrameID] of the frame.
+    pub fn is_massless_link(&self, frame_id: &FrameID) -> bool {
+        self.contains(frame_id) && self.output_frame() != frame_id
+    }
+
+    /// Returns the kind of joint.
+    pub fn kind(&self) -> MultiDofJointKind {
+        self.kind
+    }
+
+    /// Returns the [FrameID] of the static frame at the center of the joint, to which the first
+    /// frame of the joint is attached.
+    pub fn mount_frame(&self) -> &FrameID {
+        &self.mount
+    }
+
+    /// Returns the [FrameID] of the output frame, i.e. the frame that has the physical
+    /// properties of the element and to which child elements are attached.
+    pub fn output_frame(&self) -> &FrameID {
+        // A joint always has at least two degrees of freedom
+        &self.frames[self.frames.len() - 1]
+    }
+}
+
+/// Stores the state of each of the degrees of freedom of a [MultiDofJoint].
+#[derive(Clone, Debug, PartialEq)]
+pub struct MultiDofJointState {
+    /// The state of each degree of freedom, in the order of the frames of the joint.
+    states: Vec<JointState>,
+}
+
+impl MultiDofJointState {
+    /// Returns the position of each degree of freedom, in the order of the frames of the joint.
+    pub fn positions(&self) -> Vec<f64> {
+        self.states.iter().map(|s| s.position()).collect()
+    }
+
+    /// Returns the state of each degree of freedom, in the order of the frames of the joint.
+    pub fn states(&self) -> &[JointState] {
+        &self.states
+    }
+}
+
+impl MotionModel {
+    /// Adds a joint with more than one degree of freedom as a static mount frame followed by a
+    /// chain of frames, one frame for each degree of freedom. The frames are passive, i.e. they
+    /// have no actuator, and have no joint constraint. A joint constraint or a sensor can be
+    /// added to the individual frames later.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'name' - The name of the element. The output frame has this name, the mount frame has
+    ///   the name followed by `_mount` and the other frames of the chain have the name followed
+    ///   by the index of their degree of freedom.
+    /// * 'kind' - The kind of joint.
+    /// * 'parent_id' - The ID of the parent reference frame.
+    /// * 'position_relative_to_parent' - The position of the center of the joint relative to the
+    ///   parent reference frame.
+    /// * 'orientation_relative_to_parent' - The orientation of the joint relative to the parent
+    ///   reference frame. The axes of the degrees of freedom are the axes of this orientation.
+    /// * 'physical_properties' - The mass and inertia of the element, which are assigned to the
+    ///   output frame.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the parent [ReferenceFrame](super::frame_elements::ReferenceFrame)
+    ///   is not part of the model.
+    /// * [Error::InvalidFrameID] - Returned when the parent is connected to a wheel.
+    pub fn add_multi_dof_joint(
+        &mut self,
+        name: String,
+        kind: MultiDofJointKind,
+        parent_id: FrameID,
+        position_relative_to_parent: Translation3<f64>,
+        orientation_relative_to_parent: UnitQuaternion<f64>,
+        physical_properties: ChassisElementPhysicalProperties,
+    ) -> Result<MultiDofJoint, Error> {
+        let massless = ChassisElementPhysicalProperties::from_mass_properties(
+            0.0,
+            Vector3::zeros(),
+            Matrix3::zeros(),
+        );
+
+        let mount = self.add_static_chassis_element(
+            format!("{}_mount", name),
+            parent_id,
+            position_relative_to_parent,
+            orientation_relative_to_parent,
+            massless,
+        )?;
+
+        let degrees_of_freedom = kind.degrees_of_freedom();
+        let mut frames = Vec::with_capacity(degrees_of_freedom.len());
+        let mut parent = mount;
+        for (index, degree_of_freedom) in degrees_of_freedom.iter().enumerate() {
+            let (frame_name, properties) = if index == degrees_of_freedom.len() - 1 {
+                (name.clone(), physical_properties)
+            } else {
+                (format!("{}_{}", name, index), massless)
+            };
+
+            parent = self.add_suspension_element(
+                frame_name,
+                *degree_of_freedom,
+                parent,
+                Translation3::identity(),
+                UnitQuaternion::identity(),
+                properties,
+                JointConstraint::new(),
+            )?;
+            frames.push(parent);
+        }
+
+        let joint = MultiDofJoint {
+            kind,
+            mount,
+            frames,
+        };
+        self.multi_dof_joints.push(joint.clone());
+        Ok(joint)
+    }
+
+    /// Returns the joints with more than one degree of freedom, in the order in which they were
+    /// added. A joint is removed when one of its frames is removed from the model.
+    pub fn multi_dof_joints(&self) -> &[MultiDofJoint] {
+        &self.multi_dof_joints
+    }
+
+    /// Returns the state of each of the degrees of freedom of the given joint. A degree of
+    /// freedom that has neither an actuator nor a sensor is at its zero position.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'joint' - The joint for which the state is requested.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when one of the frames of the joint is not part
+    ///   of the model.
+    /// * [Error::FailedToReadActuatorJointState] - Returned when the state of an actuator could
+    ///   not be read.
+    pub fn multi_dof_joint_state(
+        &self,
+        joint: &MultiDofJoint,
+    ) -> Result<MultiDofJointState, Error> {
+        let states = joint
+            .frames
+            .iter()
+            .map(|frame_id| {
+                self.reference_frame(frame_id)?;
+                if self.is_actuated(frame_id) || self.has_sensor(frame_id) {
+                    self.joint_state_for(frame_id)
+                } else {
+                    Ok(JointState::new(0.0, None, None, None))
+                }
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        Ok(MultiDofJointState { states })
+    }
+}
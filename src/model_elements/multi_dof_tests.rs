@@ -0,0 +1,279 @@
+use nalgebra::{Isometry3, Matrix3, Translation3, UnitQuaternion, Vector3};
+
+use crate::{
+    change_notification_processing::HardwareChangeProcessor,
+    hardware::{
+        joint_state::{JointState, JointStateRange},
+        simulation::SimulatedSensor,
+    },
+    model_elements::{
+        frame_elements::{FrameDofType, FrameID, JointSensor},
+        model::{ChassisElementPhysicalProperties, MotionModel},
+    },
+    number_space::NumberSpaceType,
+    Error,
+};
+
+use super::MultiDofJointKind;
+
+fn physical_properties(mass: f64) -> ChassisElementPhysicalProperties {
+    ChassisElementPhysicalProperties::from_mass_properties(
+        mass,
+        Vector3::new(0.0, 0.0, -0.1),
+        Matrix3::identity(),
+    )
+}
+
+fn create_model() -> (MotionModel, FrameID) {
+    let mut model = MotionModel::new();
+    let body = model
+        .add_body(
+            "body".to_string(),
+            Translation3::identity(),
+            UnitQuaternion::identity(),
+            physical_properties(10.0),
+        )
+        .unwrap();
+    (model, body)
+}
+
+fn add_sensor(
+    model: &mut MotionModel,
+    frame: &FrameID,
+    motion_type: NumberSpaceType,
+    position: f64,
+    processor: &HardwareChangeProcessor,
+) {
+    let mut hardware = SimulatedSensor::new(
+        motion_type,
+        JointStateRange::new(
+            JointState::new(-10.0, None, None, None),
+            JointState::new(10.0, None, None, None),
+        ),
+        JointState::new(position, None, None, None),
+    );
+    model
+        .add_joint_sensor(frame, JointSensor::new(&mut hardware, processor).unwrap())
+        .unwrap();
+}
+
+#[test]
+fn when_adding_a_multi_dof_joint_it_should_add_a_chain_of_frames() {
+    let (mut model, body) = create_model();
+
+    for (kind, expected) in [
+        (
+            MultiDofJointKind::Universal,
+            vec![FrameDofType::RevoluteX, FrameDofType::RevoluteY],
+        ),
+        (
+            MultiDofJointKind::Planar,
+            vec![
+                FrameDofType::PrismaticX,
+                FrameDofType::PrismaticY,
+                FrameDofType::RevoluteZ,
+            ],
+        ),
+    ] {
+        let joint = model
+            .add_multi_dof_joint(
+                format!("{:?}", kind),
+                kind,
+                body,
+                Translation3::new(1.0, 0.0, 0.0),
+                UnitQuaternion::identity(),
+                physical_properties(2.0),
+            )
+            .unwrap();
+        assert_eq!(kind, joint.kind());
+
+        let degrees_of_freedom: Vec<FrameDofType> = joint
+            .frames()
+            .iter()
+            .map(|f| model.frame_degree_of_freedom(f).unwrap())
+            .collect();
+        assert_eq!(expected, degrees_of_freedom);
+
+        // Only the output frame has mass, and all frames are at the center of the joint
+        for frame in joint.frames() {
+            let element = model.chassis_element(frame).unwrap();
+            let expected_mass = if frame == joint.output_frame() {
+                2.0
+            } else {
+                0.0
+            };
+            assert_eq!(expected_mass, element.mass_in_kg());
+
+            let position = model.isometry_to_body(frame).unwrap().translation.vector;
+            assert_eq!(Vector3::new(1.0, 0.0, 0.0), position);
+        }
+
+        assert_eq!(
+            format!("{:?}", kind),
+            model.reference_frame(joint.output_frame()).unwrap().name()
+        );
+        assert_eq!(
+            FrameDofType::Static,
+            model.frame_degree_of_freedom(joint.mount_frame()).unwrap()
+        );
+        assert_eq!(Ok(joint.mount_frame()), model.parent_of(&joint.frames()[0]));
+        assert!(!model
+            .kinematic_tree()
+            .is_wheel(joint.output_frame())
+            .unwrap());
+    }
+
+    let id = FrameID::new();
+    assert_eq!(
+        Err(Error::MissingFrameElement { id }),
+        model.add_multi_dof_joint(
+            "invalid".to_string(),
+            MultiDofJointKind::Spherical,
+            id,
+            Translation3::identity(),
+            UnitQuaternion::identity(),
+            physical_properties(1.0),
+        )
+    );
+}
+
+#[test]
+fn when_a_spherical_joint_moves_it_should_rotate_by_the_yaw_pitch_and_roll() {
+    let processor = HardwareChangeProcessor::manual();
+    let (mut model, body) = create_model();
+    let joint = model
+        .add_multi_dof_joint(
+            "ball".to_string(),
+            MultiDofJointKind::Spherical,
+            body,
+            Translation3::new(0.5, 0.3, 0.1),
+            UnitQuaternion::identity(),
+            physical_properties(1.0),
+        )
+        .unwrap();
+
+    let state = model.multi_dof_joint_state(&joint).unwrap();
+    assert_eq!(vec![0.0, 0.0, 0.0], state.positions());
+
+    let (roll, pitch, yaw) = (0.1, -0.2, 0.3);
+    for (frame, position) in joint.frames().iter().zip([yaw, pitch, roll]) {
+        add_sensor(
+            &mut model,
+            frame,
+            NumberSpaceType::AngularLimited {
+                start_angle_in_radians: -std::f64::consts::PI,
+            },
+            position,
+            &processor,
+        );
+    }
+    processor.process_pending();
+
+    let state = model.multi_dof_joint_state(&joint).unwrap();
+    assert_eq!(3, state.states().len());
+    for (actual, expected) in state.positions().iter().zip([yaw, pitch, roll]) {
+        assert!((actual - expected).abs() < 1e-12);
+    }
+
+    let expected = Isometry3::from_parts(
+        Translation3::new(0.5, 0.3, 0.1),
+        UnitQuaternion::from_euler_angles(roll, pitch, yaw),
+    );
+    let actual = model.isometry_to_body(joint.output_frame()).unwrap();
+    assert!((expected.to_homogeneous() - actual.to_homogeneous()).norm() < 1e-12);
+}
+
+#[test]
+fn when_a_planar_joint_moves_it_should_translate_and_rotate_in_the_plane() {
+    let processor = HardwareChangeProcessor::manual();
+    let (mut model, body) = create_model();
+    let joint = model
+        .add_multi_dof_joint(
+            "slider".to_string(),
+            MultiDofJointKind::Planar,
+            body,
+            Translation3::new(0.0, 0.0, 0.2),
+            UnitQuaternion::identity(),
+            physical_properties(1.0),
+        )
+        .unwrap();
+
+    let frames = joint.frames();
+    add_sensor(
+        &mut model,
+        &frames[0],
+        NumberSpaceType::LinearUnlimited,
+        0.4,
+        &processor,
+    );
+    add_sensor(
+        &mut model,
+        &frames[2],
+        NumberSpaceType::AngularLimited {
+            start_angle_in_radians: -std::f64::consts::PI,
+        },
+        0.5,
+        &processor,
+    );
+    processor.process_pending();
+
+    // The frame without a sensor stays at its zero position
+    let positions = model.multi_dof_joint_state(&joint).unwrap().positions();
+    assert!((positions[0] - 0.4).abs() < 1e-12);
+    assert_eq!(0.0, positions[1]);
+    assert!((positions[2] - 0.5).abs() < 1e-12);
+
+    let expected = Isometry3::from_parts(
+        Translation3::new(0.4, 0.0, 0.2),
+        UnitQuaternion::from_euler_angles(0.0, 0.0, 0.5),
+    );
+    let actual = model.isometry_to_body(joint.output_frame()).unwrap();
+    assert!((expected.to_homogeneous() - actual.to_homogeneous()).norm() < 1e-12);
+}
+
+#[test]
+fn when_validating_a_multi_dof_joint_it_should_accept_the_massless_frames() {
+    let (mut model, body) = create_model();
+    let joint = model
+        .add_multi_dof_joint(
+            "ball".to_string(),
+            MultiDofJointKind::Spherical,
+            body,
+            Translation3::new(0.5, 0.3, 0.1),
+            UnitQuaternion::identity(),
+            physical_properties(1.0),
+        )
+        .unwrap();
+    assert_eq!(1, model.multi_dof_joints().len());
+    assert_eq!(&joint, &model.multi_dof_joints()[0]);
+    assert!(joint.is_massless_link(joint.mount_frame()));
+    assert!(!joint.is_massless_link(joint.output_frame()));
+
+    let report = model.validate();
+    for frame in joint.frames().iter().chain([joint.mount_frame()]) {
+        assert!(report.issues().iter().all(|i| i.frame() != Some(frame)));
+    }
+
+    // A massless frame that is not part of a joint is still reported
+    let other = model
+        .add_static_chassis_element(
+            "other".to_string(),
+            body,
+            Translation3::identity(),
+            UnitQuaternion::identity(),
+            ChassisElementPhysicalProperties::from_mass_properties(
+                0.0,
+                Vector3::zeros(),
+                Matrix3::zeros(),
+            ),
+        )
+        .unwrap();
+    assert!(model
+        .validate()
+        .issues()
+        .iter()
+        .any(|i| i.frame() == Some(&other)));
+
+    model.remove_element(&joint.frames()[1]).unwrap();
+    assert!(model.multi_dof_joints().is_empty());
+}
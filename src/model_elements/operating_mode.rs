@@ -0,0 +1,142 @@
+//! Defines the operating modes of a vehicle.
+//!
+//! An operating mode groups the settings that depend on the environment the vehicle operates
+//! in, for instance the maximum speeds for indoor and outdoor operation. A
+//! [MotionModel](super::model::MotionModel) can store multiple operating modes, one of which
+//! can be active at any given time.
+
+use std::collections::HashMap;
+
+use super::frame_elements::FrameID;
+
+#[cfg(test)]
+#[path = "operating_mode_tests.rs"]
+mod operating_mode_tests;
+
+/// Defines the settings for a single operating mode of the vehicle.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OperatingMode {
+    /// The name of the operating mode.
+    name: String,
+
+    /// The maximum linear speed of the body in meters per second.
+    maximum_linear_speed: f64,
+
+    /// The maximum angular speed of the body in radians per second.
+    maximum_angular_speed: f64,
+
+    /// The scaling factor applied to the maximum speeds.
+    twist_scale: f64,
+
+    /// The stiffness set-point for the suspension frames, stored by the [FrameID] of the
+    /// suspension frame.
+    #[cfg_attr(feature = "serde", serde(with = "super::frame_elements::frame_id_map"))]
+    suspension_stiffness: HashMap<FrameID, f64>,
+}
+
+impl OperatingMode {
+    /// Returns the maximum angular speed of the body, in radians per second, after the twist
+    /// scaling has been applied.
+    pub fn effective_maximum_angular_speed(&self) -> f64 {
+        self.maximum_angular_speed * self.twist_scale
+    }
+
+    /// Returns the maximum linear speed of the body, in meters per second, after the twist
+    /// scaling has been applied.
+    pub fn effective_maximum_linear_speed(&self) -> f64 {
+        self.maximum_linear_speed * self.twist_scale
+    }
+
+    /// Returns the maximum angular speed of the body in radians per second.
+    pub fn maximum_angular_speed(&self) -> f64 {
+        self.maximum_angular_speed
+    }
+
+    /// Returns the maximum linear speed of the body in meters per second.
+    pub fn maximum_linear_speed(&self) -> f64 {
+        self.maximum_linear_speed
+    }
+
+    /// Returns the name of the operating mode.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Creates a new [OperatingMode] instance.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'name' - The name of the operating mode.
+    /// * 'maximum_linear_speed' - The maximum linear speed of the body in meters per second.
+    /// * 'maximum_angular_speed' - The maximum angular speed of the body in radians per second.
+    /// * 'twist_scale' - The scaling factor applied to the maximum speeds, e.g. 0.5 to only
+    ///   allow half the maximum speeds.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use swerve_vehicle_descriptors::model_elements::operating_mode::OperatingMode;
+    ///
+    /// let indoor = OperatingMode::new("indoor".to_string(), 1.0, 1.5, 0.5);
+    ///
+    /// assert_eq!(indoor.effective_maximum_linear_speed(), 0.5);
+    /// assert_eq!(indoor.effective_maximum_angular_speed(), 0.75);
+    /// ```
+    pub fn new(
+        name: String,
+        maximum_linear_speed: f64,
+        maximum_angular_speed: f64,
+        twist_scale: f64,
+    ) -> Self {
+        Self {
+            name,
+            maximum_linear_speed,
+            maximum_angular_speed,
+            twist_scale,
+            suspension_stiffness: HashMap::new(),
+        }
+    }
+
+    /// Removes the stiffness set-point for the given suspension frame. Returns the stiffness
+    /// that was removed, or [None] if the operating mode did not define a stiffness for the frame.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'suspension_frame' - The [FrameID] of the suspension frame.
+    pub fn remove_suspension_stiffness(&mut self, suspension_frame: &FrameID) -> Option<f64> {
+        self.suspension_stiffness.remove(suspension_frame)
+    }
+
+    /// Sets the stiffness set-point for the given suspension frame.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'suspension_frame' - The [FrameID] of the suspension frame.
+    /// * 'stiffness' - The stiffness set-point, in Newton per meter for prismatic suspension
+    ///   joints or Newton meter per radian for revolute suspension joints.
+    pub fn set_suspension_stiffness(&mut self, suspension_frame: FrameID, stiffness: f64) {
+        self.suspension_stiffness
+            .insert(suspension_frame, stiffness);
+    }
+
+    /// Returns the stiffness set-point for the given suspension frame, or [None] if the
+    /// operating mode does not define a stiffness for the frame.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'suspension_frame' - The [FrameID] of the suspension frame.
+    pub fn suspension_stiffness(&self, suspension_frame: &FrameID) -> Option<f64> {
+        self.suspension_stiffness.get(suspension_frame).copied()
+    }
+
+    /// Returns the [FrameID] of the suspension frames that have a stiffness set-point.
+    pub fn suspension_frames(&self) -> impl Iterator<Item = &FrameID> {
+        self.suspension_stiffness.keys()
+    }
+
+    /// Returns the scaling factor applied to the maximum speeds.
+    pub fn twist_scale(&self) -> f64 {
+        self.twist_scale
+    }
+}
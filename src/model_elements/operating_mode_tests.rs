@@ -0,0 +1,43 @@
+use crate::model_elements::frame_elements::FrameID;
+
+use super::OperatingMode;
+
+#[test]
+fn when_creating_operating_mode_it_should_store_the_values() {
+    let mode = OperatingMode::new("outdoor".to_string(), 2.0, 3.0, 0.5);
+
+    assert_eq!("outdoor", mode.name());
+    assert_eq!(2.0, mode.maximum_linear_speed());
+    assert_eq!(3.0, mode.maximum_angular_speed());
+    assert_eq!(0.5, mode.twist_scale());
+    assert_eq!(1.0, mode.effective_maximum_linear_speed());
+    assert_eq!(1.5, mode.effective_maximum_angular_speed());
+    assert_eq!(0, mode.suspension_frames().count());
+}
+
+#[test]
+fn when_removing_suspension_stiffness_it_should_remove_the_value() {
+    let mut mode = OperatingMode::new("outdoor".to_string(), 2.0, 3.0, 1.0);
+    let suspension = FrameID::new();
+    mode.set_suspension_stiffness(suspension, 1500.0);
+
+    assert_eq!(Some(1500.0), mode.remove_suspension_stiffness(&suspension));
+    assert_eq!(None, mode.suspension_stiffness(&suspension));
+    assert_eq!(None, mode.remove_suspension_stiffness(&suspension));
+    assert_eq!(0, mode.suspension_frames().count());
+}
+
+#[test]
+fn when_setting_suspension_stiffness_it_should_store_the_value() {
+    let mut mode = OperatingMode::new("outdoor".to_string(), 2.0, 3.0, 1.0);
+    let suspension = FrameID::new();
+
+    mode.set_suspension_stiffness(suspension, 1500.0);
+
+    assert_eq!(Some(1500.0), mode.suspension_stiffness(&suspension));
+    assert_eq!(None, mode.suspension_stiffness(&FrameID::new()));
+    assert_eq!(
+        vec![&suspension],
+        mode.suspension_frames().collect::<Vec<_>>()
+    );
+}
@@ -0,0 +1,487 @@
+//! Provides the named parameters, e.g. the wheelbase or the wheel radius, from which the
+//! dimensions of a vehicle are derived.
+//!
+//! A family of vehicles often shares a single layout that only differs in a few dimensions. The
+//! dimensions are stored by name in a [ParameterSet]. The position of a frame relative to its
+//! parent, and the radius of a wheel, can be bound to a [ParameterExpression], i.e. a linear
+//! combination of the parameters. When the parameters change, e.g. because a calibration
+//! routine measured the actual track width of the vehicle,
+//! [MotionModel::rebuild_with] re-evaluates the bindings and updates the affected static
+//! transforms and wheel radii, without rebuilding the model.
+//!
+//! [MotionModel::rectangular_swerve_from_parameters] creates the model of a symmetric swerve
+//! vehicle from the [WHEELBASE], [TRACK_WIDTH] and [WHEEL_RADIUS] parameters, with the
+//! positions of the modules and the radii of the wheels bound to those parameters.
+//!
+//! # Examples
+//!
+//! ```
+//! use nalgebra::{Matrix3, Vector3};
+//! use swerve_vehicle_descriptors::model_elements::{
+//!     model::{ChassisElementPhysicalProperties, MotionModel, WheelProperties},
+//!     parameters::{ParameterSet, TRACK_WIDTH, WHEELBASE, WHEEL_RADIUS},
+//!     templates::ModuleTemplate,
+//! };
+//!
+//! let properties =
+//!     ChassisElementPhysicalProperties::from_mass_properties(1.0, Vector3::zeros(), Matrix3::identity());
+//! let module = ModuleTemplate::new(
+//!     WheelProperties::new(0.1, 0.05, Vector3::zeros()),
+//!     properties,
+//!     properties,
+//! );
+//! let parameters = ParameterSet::new()
+//!     .with_parameter(WHEELBASE, 2.0)
+//!     .with_parameter(TRACK_WIDTH, 1.0)
+//!     .with_parameter(WHEEL_RADIUS, 0.1);
+//!
+//! let mut model =
+//!     MotionModel::rectangular_swerve_from_parameters(4, &parameters, properties, &module)
+//!         .unwrap();
+//!
+//! // A calibration routine measured a wider track
+//! let calibrated = parameters.clone().with_parameter(TRACK_WIDTH, 1.1);
+//! let changed = model.rebuild_with(&calibrated).unwrap();
+//! assert_eq!(4, changed.len());
+//! assert_eq!(&calibrated, model.parameters());
+//! ```
+
+use std::collections::BTreeMap;
+
+use nalgebra::Translation3;
+
+use crate::Error;
+
+use super::{
+    frame_elements::FrameID,
+    model::{ChassisElementPhysicalProperties, MotionModel},
+    module_spec::MotionModelBuilder,
+    templates::ModuleTemplate,
+};
+
+#[cfg(test)]
+#[path = "parameters_tests.rs"]
+mod parameters_tests;
+
+/// The name of the parameter that stores the distance, in meters, between the front and the
+/// rear row of modules.
+pub const WHEELBASE: &str = "wheelbase";
+
+/// The name of the parameter that stores the distance, in meters, between the left and the
+/// right module of a row.
+pub const TRACK_WIDTH: &str = "track_width";
+
+/// The name of the parameter that stores the radius of the wheels, in meters.
+pub const WHEEL_RADIUS: &str = "wheel_radius";
+
+/// Stores the values of a set of named parameters.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ParameterSet {
+    /// The values of the parameters, ordered by name.
+    values: BTreeMap<String, f64>,
+}
+
+impl ParameterSet {
+    /// Returns the value of the parameter with the given name, or [None] if the set does not
+    /// contain the parameter.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'name' - The name of the parameter.
+    pub fn get(&self, name: &str) -> Option<f64> {
+        self.values.get(name).copied()
+    }
+
+    /// Returns a value indicating whether the set contains no parameters.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Returns an iterator over the names and values of the parameters, ordered by name.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, f64)> {
+        self.values
+            .iter()
+            .map(|(name, value)| (name.as_str(), *value))
+    }
+
+    /// Returns the number of parameters in the set.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Creates a new, empty, [ParameterSet] instance.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the value of the parameter with the given name, replacing the existing value.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'name' - The name of the parameter.
+    /// * 'value' - The value of the parameter.
+    pub fn set(&mut self, name: &str, value: f64) {
+        self.values.insert(name.to_string(), value);
+    }
+
+    /// Returns the value of the parameter with the given name.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'name' - The name of the parameter.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingParameter] - Returned when the set does not contain the parameter.
+    pub fn value(&self, name: &str) -> Result<f64, Error> {
+        self.get(name).ok_or_else(|| Error::MissingParameter {
+            name: name.to_string(),
+        })
+    }
+
+    /// Returns the set with the value of the parameter with the given name set to the given
+    /// value.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'name' - The name of the parameter.
+    /// * 'value' - The value of the parameter.
+    pub fn with_parameter(mut self, name: &str, value: f64) -> Self {
+        self.set(name, value);
+        self
+    }
+}
+
+/// Describes a value that is a linear combination of named parameters, i.e.
+/// `constant + Σ coefficient * parameter`.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ParameterExpression {
+    /// The constant part of the expression.
+    constant: f64,
+
+    /// The names of the parameters and their coefficients.
+    terms: Vec<(String, f64)>,
+}
+
+impl ParameterExpression {
+    /// Creates a new [ParameterExpression] instance that always has the given value.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'value' - The value of the expression.
+    pub fn constant(value: f64) -> Self {
+        Self {
+            constant: value,
+            terms: Vec::new(),
+        }
+    }
+
+    /// Returns the value of the expression for the given parameters.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'parameters' - The values of the parameters.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingParameter] - Returned when one of the parameters of the expression is
+    ///   not part of the set.
+    pub fn evaluate(&self, parameters: &ParameterSet) -> Result<f64, Error> {
+        self.terms
+            .iter()
+            .try_fold(self.constant, |sum, (name, coefficient)| {
+                Ok(sum + coefficient * parameters.value(name)?)
+            })
+    }
+
+    /// Creates a new [ParameterExpression] instance that is equal to the parameter with the
+    /// given name.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'name' - The name of the parameter.
+    pub fn parameter(name: &str) -> Self {
+        Self::constant(0.0).with_term(name, 1.0)
+    }
+
+    /// Returns the names of the parameters that are used by the expression.
+    pub fn parameters(&self) -> impl Iterator<Item = &str> {
+        self.terms.iter().map(|(name, _)| name.as_str())
+    }
+
+    /// Returns the expression with the given parameter, multiplied by the given coefficient,
+    /// added to it.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'name' - The name of the parameter.
+    /// * 'coefficient' - The factor by which the parameter is multiplied.
+    pub fn with_term(mut self, name: &str, coefficient: f64) -> Self {
+        self.terms.push((name.to_string(), coefficient));
+        self
+    }
+}
+
+/// Describes a property of a frame element that is derived from the parameters of the model.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ParameterBinding {
+    /// The position of a frame relative to its parent frame. The orientation of the frame is
+    /// not changed.
+    Position {
+        /// The ID of the frame.
+        frame: FrameID,
+
+        /// The x, y and z coordinates of the position, in meters.
+        position: [ParameterExpression; 3],
+    },
+
+    /// The radius of a wheel.
+    WheelRadius {
+        /// The ID of the wheel frame.
+        wheel: FrameID,
+
+        /// The radius of the wheel, in meters.
+        radius: ParameterExpression,
+    },
+}
+
+impl ParameterBinding {
+    /// Returns the [FrameID] of the frame to which the binding applies.
+    pub fn frame(&self) -> &FrameID {
+        match self {
+            ParameterBinding::Position { frame, .. } => frame,
+            ParameterBinding::WheelRadius { wheel, .. } => wheel,
+        }
+    }
+}
+
+/// Stores the evaluated value of a [ParameterBinding].
+enum BoundValue {
+    /// The position of a frame relative to its parent frame.
+    Position(Translation3<f64>),
+
+    /// The radius of a wheel.
+    WheelRadius(f64),
+}
+
+impl MotionModel {
+    /// Binds the position of the given frame, relative to its parent frame, to the given
+    /// expressions. The binding replaces an existing position binding of the frame. The
+    /// position is not changed until the next call to [MotionModel::rebuild_with].
+    ///
+    /// ## Parameters
+    ///
+    /// * 'frame_id' - The [FrameID] of the frame.
+    /// * 'position' - The expressions for the x, y and z coordinates of the position.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the frame is not part of the model.
+    /// * [Error::InvalidFrameID] - Returned when the frame is the body.
+    pub fn bind_position_to_parameters(
+        &mut self,
+        frame_id: &FrameID,
+        position: [ParameterExpression; 3],
+    ) -> Result<(), Error> {
+        if !self.kinematic_tree().has_element(frame_id) {
+            return Err(Error::MissingFrameElement { id: *frame_id });
+        }
+
+        if self.is_body(frame_id) {
+            return Err(Error::InvalidFrameID { id: *frame_id });
+        }
+
+        self.parameter_bindings.retain(
+            |b| !matches!(b, ParameterBinding::Position { frame, .. } if frame == frame_id),
+        );
+        self.parameter_bindings.push(ParameterBinding::Position {
+            frame: *frame_id,
+            position,
+        });
+        Ok(())
+    }
+
+    /// Binds the radius of the given wheel to the given expression. The binding replaces an
+    /// existing radius binding of the wheel. The radius is not changed until the next call to
+    /// [MotionModel::rebuild_with].
+    ///
+    /// ## Parameters
+    ///
+    /// * 'wheel_frame' - The [FrameID] of the wheel.
+    /// * 'radius' - The expression for the radius of the wheel.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the frame is not part of the model.
+    /// * [Error::InvalidFrameID] - Returned when the frame is not a wheel.
+    pub fn bind_wheel_radius_to_parameters(
+        &mut self,
+        wheel_frame: &FrameID,
+        radius: ParameterExpression,
+    ) -> Result<(), Error> {
+        if !self.kinematic_tree().has_element(wheel_frame) {
+            return Err(Error::MissingFrameElement { id: *wheel_frame });
+        }
+
+        if !self.kinematic_tree().is_wheel(wheel_frame)? {
+            return Err(Error::InvalidFrameID { id: *wheel_frame });
+        }
+
+        self.parameter_bindings.retain(
+            |b| !matches!(b, ParameterBinding::WheelRadius { wheel, .. } if wheel == wheel_frame),
+        );
+        self.parameter_bindings.push(ParameterBinding::WheelRadius {
+            wheel: *wheel_frame,
+            radius,
+        });
+        Ok(())
+    }
+
+    /// Returns the bindings between the frames of the model and the parameters, in the order
+    /// in which they were added. A binding is removed when its frame is removed from the model.
+    pub fn parameter_bindings(&self) -> &[ParameterBinding] {
+        &self.parameter_bindings
+    }
+
+    /// Returns the parameters that were last applied to the model with
+    /// [MotionModel::rebuild_with].
+    pub fn parameters(&self) -> &ParameterSet {
+        &self.parameters
+    }
+
+    /// Creates a model of a vehicle with the wheel modules placed symmetrically on a rectangle,
+    /// in the same way as [MotionModelBuilder::rectangular_swerve], with the dimensions taken
+    /// from the [WHEELBASE], [TRACK_WIDTH] and [WHEEL_RADIUS] parameters.
+    ///
+    /// The positions of the modules are bound to the wheelbase and the track width and the
+    /// radii of the wheels are bound to the wheel radius, so that the model follows changes to
+    /// the parameters that are applied with [MotionModel::rebuild_with].
+    ///
+    /// ## Parameters
+    ///
+    /// * 'number_of_modules' - The number of wheel modules. Must be an even number of at least
+    ///   four.
+    /// * 'parameters' - The parameters of the vehicle. Other parameters than the ones listed
+    ///   above are stored with the model.
+    /// * 'body_physical_properties' - The mass and inertia of the body.
+    /// * 'module' - The properties that are shared by all the wheel modules.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingParameter] - Returned when one of the parameters is missing.
+    /// * [Error::InvalidTemplate] - Returned when the number of modules is odd or smaller than
+    ///   four, or when the wheelbase or the track width is not a finite, positive number.
+    pub fn rectangular_swerve_from_parameters(
+        number_of_modules: usize,
+        parameters: &ParameterSet,
+        body_physical_properties: ChassisElementPhysicalProperties,
+        module: &ModuleTemplate,
+    ) -> Result<MotionModel, Error> {
+        let wheelbase = parameters.value(WHEELBASE)?;
+        let track_width = parameters.value(TRACK_WIDTH)?;
+        parameters.value(WHEEL_RADIUS)?;
+
+        let mut model = MotionModelBuilder::rectangular_swerve(
+            number_of_modules,
+            wheelbase,
+            track_width,
+            body_physical_properties,
+            module,
+        )?
+        .build()?;
+
+        // The modules are added from front to rear, with the left module of each row first
+        let rows = number_of_modules / 2;
+        let frames: Vec<_> = model.drive_modules().map(|m| *m.frames()).collect();
+        for (index, module_frames) in frames.iter().enumerate() {
+            let row = index / 2;
+            let side = if index % 2 == 0 { 0.5 } else { -0.5 };
+            let mount = module_frames
+                .suspension()
+                .copied()
+                .unwrap_or(*module_frames.steering());
+
+            model.bind_position_to_parameters(
+                &mount,
+                [
+                    ParameterExpression::constant(0.0)
+                        .with_term(WHEELBASE, 0.5 - row as f64 / (rows - 1) as f64),
+                    ParameterExpression::constant(0.0).with_term(TRACK_WIDTH, side),
+                    ParameterExpression::constant(0.0),
+                ],
+            )?;
+            model.bind_wheel_radius_to_parameters(
+                module_frames.wheel(),
+                ParameterExpression::parameter(WHEEL_RADIUS),
+            )?;
+        }
+
+        model.rebuild_with(parameters)?;
+        Ok(model)
+    }
+
+    /// Applies the given parameters to the model, i.e. re-evaluates all the
+    /// [ParameterBinding] instances and updates the static transforms and the wheel radii that
+    /// changed. The parameters are stored with the model, see [MotionModel::parameters].
+    ///
+    /// The model is only changed when all the bindings can be evaluated. Returns the frames
+    /// that were changed, ordered by [FrameID].
+    ///
+    /// ## Parameters
+    ///
+    /// * 'parameters' - The new values of the parameters.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingParameter] - Returned when a binding uses a parameter that is not part
+    ///   of the set.
+    pub fn rebuild_with(&mut self, parameters: &ParameterSet) -> Result<Vec<FrameID>, Error> {
+        let values = self
+            .parameter_bindings
+            .iter()
+            .map(|binding| {
+                let value = match binding {
+                    ParameterBinding::Position { position, .. } => {
+                        BoundValue::Position(Translation3::new(
+                            position[0].evaluate(parameters)?,
+                            position[1].evaluate(parameters)?,
+                            position[2].evaluate(parameters)?,
+                        ))
+                    }
+                    ParameterBinding::WheelRadius { radius, .. } => {
+                        BoundValue::WheelRadius(radius.evaluate(parameters)?)
+                    }
+                };
+                Ok((*binding.frame(), value))
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        let mut changed = Vec::new();
+        for (frame_id, value) in values {
+            match value {
+                BoundValue::Position(position) => {
+                    let transform = *self.static_transform_to_parent(&frame_id)?;
+                    if transform.translation != position {
+                        self.set_transform_to_parent(&frame_id, position, transform.rotation)?;
+                        changed.push(frame_id);
+                    }
+                }
+                BoundValue::WheelRadius(radius) => {
+                    let current = self.wheel_properties(&frame_id).map(|p| p.radius()).ok();
+                    if current != Some(radius) {
+                        self.set_wheel_radius(&frame_id, radius)?;
+                        changed.push(frame_id);
+                    }
+                }
+            }
+        }
+
+        self.parameters = parameters.clone();
+        changed.sort();
+        changed.dedup();
+        Ok(changed)
+    }
+}
@@ -0,0 +1,224 @@
+use nalgebra::{Translation3, UnitQuaternion, Vector3};
+
+use crate::{
+    model_elements::{
+        frame_elements::FrameID,
+        model::{MotionModel, WheelProperties},
+        templates::ModuleTemplate,
+    },
+    test_fixtures::physical_properties,
+    Error,
+};
+
+use super::{
+    ParameterBinding, ParameterExpression, ParameterSet, TRACK_WIDTH, WHEELBASE, WHEEL_RADIUS,
+};
+
+fn parameters() -> ParameterSet {
+    ParameterSet::new()
+        .with_parameter(WHEELBASE, 2.0)
+        .with_parameter(TRACK_WIDTH, 1.0)
+        .with_parameter(WHEEL_RADIUS, 0.1)
+}
+
+fn create_model(number_of_modules: usize) -> MotionModel {
+    let module = ModuleTemplate::new(
+        WheelProperties::new(0.05, 0.05, Vector3::zeros()),
+        physical_properties(),
+        physical_properties(),
+    )
+    .with_wheel_offset(Translation3::new(0.0, 0.0, -0.1));
+
+    MotionModel::rectangular_swerve_from_parameters(
+        number_of_modules,
+        &parameters(),
+        physical_properties(),
+        &module,
+    )
+    .unwrap()
+}
+
+#[test]
+fn when_evaluating_an_expression_it_should_combine_the_parameters() {
+    let parameters = parameters();
+    assert_eq!(3, parameters.len());
+    assert!(!parameters.is_empty());
+    assert_eq!(Some(2.0), parameters.get(WHEELBASE));
+    assert_eq!(
+        vec![(TRACK_WIDTH, 1.0), (WHEEL_RADIUS, 0.1), (WHEELBASE, 2.0)],
+        parameters.iter().collect::<Vec<_>>()
+    );
+
+    let expression = ParameterExpression::constant(0.25)
+        .with_term(WHEELBASE, 0.5)
+        .with_term(TRACK_WIDTH, -2.0);
+    assert_eq!(Ok(0.25 + 1.0 - 2.0), expression.evaluate(&parameters));
+    assert_eq!(
+        vec![WHEELBASE, TRACK_WIDTH],
+        expression.parameters().collect::<Vec<_>>()
+    );
+    assert_eq!(
+        Ok(0.1),
+        ParameterExpression::parameter(WHEEL_RADIUS).evaluate(&parameters)
+    );
+
+    assert_eq!(
+        Err(Error::MissingParameter {
+            name: "height".to_string()
+        }),
+        ParameterExpression::parameter("height").evaluate(&parameters)
+    );
+    assert!(ParameterSet::new().is_empty());
+}
+
+#[test]
+fn when_building_from_parameters_it_should_place_the_modules() {
+    let model = create_model(6);
+    assert_eq!(&parameters(), model.parameters());
+    assert_eq!(12, model.parameter_bindings().len());
+
+    let expected = [
+        ("front_left", 1.0, 0.5),
+        ("front_right", 1.0, -0.5),
+        ("middle_1_left", 0.0, 0.5),
+        ("middle_1_right", 0.0, -0.5),
+        ("rear_left", -1.0, 0.5),
+        ("rear_right", -1.0, -0.5),
+    ];
+    for (module, (name, x, y)) in model.drive_modules().zip(expected) {
+        assert_eq!(name, module.name());
+        let position = model
+            .isometry_to_body(module.frames().steering())
+            .unwrap()
+            .translation
+            .vector;
+        assert!((position - Vector3::new(x, y, 0.0)).norm() < 1e-12);
+
+        // The radius of the template is replaced by the parameter
+        assert_eq!(0.1, module.wheel_properties().unwrap().radius());
+    }
+
+    let missing = ParameterSet::new().with_parameter(WHEELBASE, 2.0);
+    assert_eq!(
+        Err(Error::MissingParameter {
+            name: TRACK_WIDTH.to_string()
+        }),
+        MotionModel::rectangular_swerve_from_parameters(
+            4,
+            &missing,
+            physical_properties(),
+            &ModuleTemplate::new(
+                WheelProperties::new(0.1, 0.05, Vector3::zeros()),
+                physical_properties(),
+                physical_properties(),
+            ),
+        )
+        .map(|_| ())
+    );
+}
+
+#[test]
+fn when_rebuilding_with_new_parameters_it_should_update_the_bound_frames() {
+    let mut model = create_model(4);
+    let revision = model.revision();
+
+    // Nothing changes when the parameters are the same
+    assert_eq!(Ok(vec![]), model.rebuild_with(&parameters()));
+    assert_eq!(revision, model.revision());
+
+    let wheels: Vec<FrameID> = model.drive_modules().map(|m| *m.frames().wheel()).collect();
+    let steering: Vec<FrameID> = model
+        .drive_modules()
+        .map(|m| *m.frames().steering())
+        .collect();
+
+    let wider = parameters()
+        .with_parameter(WHEELBASE, 3.0)
+        .with_parameter(WHEEL_RADIUS, 0.12);
+    let mut expected: Vec<FrameID> = wheels.iter().chain(steering.iter()).copied().collect();
+    expected.sort();
+    assert_eq!(Ok(expected), model.rebuild_with(&wider));
+    assert!(model.revision() > revision);
+    assert_eq!(&wider, model.parameters());
+
+    let front_left = model.isometry_to_body(&steering[0]).unwrap();
+    assert!((front_left.translation.vector - Vector3::new(1.5, 0.5, 0.0)).norm() < 1e-12);
+    assert_eq!(UnitQuaternion::identity(), front_left.rotation);
+    assert_eq!(0.12, model.wheel_properties(&wheels[3]).unwrap().radius());
+
+    // A missing parameter leaves the model untouched
+    let revision = model.revision();
+    let mut incomplete = ParameterSet::new().with_parameter(WHEELBASE, 4.0);
+    incomplete.set(TRACK_WIDTH, 2.0);
+    assert_eq!(
+        Err(Error::MissingParameter {
+            name: WHEEL_RADIUS.to_string()
+        }),
+        model.rebuild_with(&incomplete)
+    );
+    assert_eq!(revision, model.revision());
+    assert_eq!(&wider, model.parameters());
+}
+
+#[test]
+fn when_binding_frames_it_should_check_the_frames() {
+    let mut model = create_model(4);
+    let body = *model.body().unwrap();
+    let module = model.drive_modules().next().unwrap();
+    let (steering, wheel) = (*module.frames().steering(), *module.frames().wheel());
+
+    let position = || {
+        [
+            ParameterExpression::parameter(WHEELBASE),
+            ParameterExpression::constant(0.0),
+            ParameterExpression::constant(0.0),
+        ]
+    };
+    assert_eq!(
+        Err(Error::InvalidFrameID { id: body }),
+        model.bind_position_to_parameters(&body, position())
+    );
+    let missing = FrameID::new();
+    assert_eq!(
+        Err(Error::MissingFrameElement { id: missing }),
+        model.bind_position_to_parameters(&missing, position())
+    );
+    assert_eq!(
+        Err(Error::InvalidFrameID { id: steering }),
+        model.bind_wheel_radius_to_parameters(&steering, ParameterExpression::constant(0.2))
+    );
+
+    // Binding a frame again replaces the previous binding
+    model
+        .bind_position_to_parameters(&steering, position())
+        .unwrap();
+    model.rebuild_with(&parameters()).unwrap();
+    assert_eq!(4 + 4, model.parameter_bindings().len());
+    assert_eq!(
+        2.0,
+        model.isometry_to_body(&steering).unwrap().translation.x
+    );
+
+    model.remove_element(&wheel).unwrap();
+    assert!(model
+        .parameter_bindings()
+        .iter()
+        .all(|b| !matches!(b, ParameterBinding::WheelRadius { wheel: w, .. } if *w == wheel)));
+    assert_eq!(7, model.parameter_bindings().len());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn when_serializing_it_should_store_the_parameters_and_bindings() {
+    let model = create_model(4);
+
+    let json = serde_json::to_string(&model).unwrap();
+    let mut loaded: MotionModel = serde_json::from_str(&json).unwrap();
+    assert_eq!(model.parameters(), loaded.parameters());
+    assert_eq!(model.parameter_bindings(), loaded.parameter_bindings());
+
+    let changed = loaded
+        .rebuild_with(&parameters().with_parameter(TRACK_WIDTH, 1.2))
+        .unwrap();
+    assert_eq!(4, changed.len());
+}
@@ -0,0 +1,468 @@
+//! Detects collisions between the chassis elements of a vehicle, e.g. a steering knuckle that
+//! hits the chassis somewhere in the travel of the suspension.
+//!
+//! [MotionModel::check_self_collisions] tests the [collision geometry](super::collision) of
+//! every pair of chassis elements against each other. The joints that move the two elements
+//! relative to each other are swept through their range of motion, as given by the position
+//! limits of their [JointConstraint](super::frame_elements::JointConstraint), so that
+//! collisions are found at design time rather than when the vehicle is driving. Revolute joints
+//! without position limits are swept over a full rotation.
+//!
+//! Pairs of elements that are expected to touch, e.g. two elements that share a joint, are
+//! excluded from the check with [MotionModel::allow_collision]. Shapes that belong to the same
+//! element are never tested against each other and mesh shapes are not tested because meshes are
+//! not loaded by this crate.
+//!
+//! The shapes are tested with the Gilbert-Johnson-Keerthi algorithm. Shapes that only touch
+//! are not reported as colliding.
+
+use std::collections::{HashMap, HashSet};
+
+use nalgebra::{Isometry3, Vector3};
+
+use crate::{
+    kinematics::workspace::{number_of_configurations, set_configuration},
+    Error,
+};
+
+use super::{collision::CollisionShape, frame_elements::FrameID, model::MotionModel};
+
+#[cfg(test)]
+#[path = "self_collision_tests.rs"]
+mod self_collision_tests;
+
+/// The maximum number of iterations of the intersection test for a pair of shapes.
+const MAX_INTERSECTION_ITERATIONS: usize = 64;
+
+/// The squared length of the search direction below which the origin is assumed to lie on the
+/// simplex of the intersection test.
+const INTERSECTION_TOLERANCE: f64 = 1e-20;
+
+/// Describes a pair of chassis elements whose collision geometry overlaps.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SelfCollision {
+    /// The element with the smallest [FrameID].
+    first: FrameID,
+
+    /// The element with the largest [FrameID].
+    second: FrameID,
+
+    /// The positions of the joints that move the elements relative to each other at which the
+    /// collision was found, ordered by [FrameID].
+    joint_positions: Vec<(FrameID, f64)>,
+}
+
+impl SelfCollision {
+    /// Returns the element with the smallest [FrameID].
+    pub fn first(&self) -> &FrameID {
+        &self.first
+    }
+
+    /// Returns the positions of the joints that move the elements relative to each other at
+    /// which the collision was found, ordered by [FrameID]. The positions are empty when the
+    /// current configuration of the vehicle was checked.
+    pub fn joint_positions(&self) -> &[(FrameID, f64)] {
+        &self.joint_positions
+    }
+
+    /// Returns the element with the largest [FrameID].
+    pub fn second(&self) -> &FrameID {
+        &self.second
+    }
+}
+
+/// A collision shape together with its pose in the body frame.
+struct PosedShape<'a> {
+    /// The shape.
+    shape: &'a CollisionShape,
+
+    /// The transform from the frame of the shape to the body frame.
+    pose: Isometry3<f64>,
+}
+
+impl PosedShape<'_> {
+    /// Returns the point of the shape, in the body frame, that lies furthest in the given
+    /// direction.
+    fn support_point(&self, direction: &Vector3<f64>) -> Vector3<f64> {
+        let local_direction = self.pose.rotation.inverse_transform_vector(direction);
+        let local_point = self
+            .shape
+            .support_point(&local_direction)
+            .unwrap_or_else(Vector3::zeros);
+        self.pose.rotation.transform_vector(&local_point) + self.pose.translation.vector
+    }
+}
+
+impl MotionModel {
+    /// Excludes the given pair of chassis elements from the self-collision check, e.g. because
+    /// the elements share a joint and their geometry is expected to touch.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'first' - The [FrameID] of the first element.
+    /// * 'second' - The [FrameID] of the second element.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when either element is not part of the model.
+    /// * [Error::InvalidFrameID] - Returned when both elements are the same.
+    pub fn allow_collision(&mut self, first: &FrameID, second: &FrameID) -> Result<(), Error> {
+        for id in [first, second] {
+            if !self.kinematic_tree().has_element(id) {
+                return Err(Error::MissingFrameElement { id: *id });
+            }
+        }
+
+        if first == second {
+            return Err(Error::InvalidFrameID { id: *first });
+        }
+
+        let pair = ordered_pair(first, second);
+        if !self.allowed_collisions.contains(&pair) {
+            self.allowed_collisions.push(pair);
+            self.allowed_collisions.sort();
+        }
+
+        Ok(())
+    }
+
+    /// Returns the pairs of chassis elements that are excluded from the self-collision check,
+    /// ordered by [FrameID]. The first element of each pair has the smallest [FrameID].
+    pub fn allowed_collisions(&self) -> &[(FrameID, FrameID)] {
+        &self.allowed_collisions
+    }
+
+    /// Returns the pairs of chassis elements whose collision geometry overlaps, ordered by
+    /// [FrameID].
+    ///
+    /// For each pair of elements the joints that move the elements relative to each other are
+    /// sampled at 'resolution' evenly spaced positions over their range of motion, and every
+    /// combination of the samples is tested. The first colliding combination is reported. The
+    /// number of combinations grows exponentially with the number of joints between the
+    /// elements. A 'resolution' of zero checks the current configuration of the vehicle only.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'resolution' - The number of positions at which each joint is sampled, or zero to
+    ///   check the current configuration. At least two positions are sampled when sweeping.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the model has no body.
+    /// * [Error::MissingJointLimits] - Returned when a prismatic joint that has to be swept has
+    ///   no position limits.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use nalgebra::{Isometry3, Matrix3, Translation3, UnitQuaternion, Vector3};
+    /// use swerve_vehicle_descriptors::model_elements::collision::{
+    ///     CollisionGeometry, CollisionShape,
+    /// };
+    /// use swerve_vehicle_descriptors::model_elements::frame_elements::{
+    ///     FrameDofType, JointConstraint,
+    /// };
+    /// use swerve_vehicle_descriptors::model_elements::model::{
+    ///     ChassisElementPhysicalProperties, MotionModel,
+    /// };
+    ///
+    /// let properties = ChassisElementPhysicalProperties::from_mass_properties(
+    ///     1.0,
+    ///     Vector3::zeros(),
+    ///     Matrix3::identity(),
+    /// );
+    /// let mut model = MotionModel::new();
+    /// let body = model
+    ///     .add_body(
+    ///         "body".to_string(),
+    ///         Translation3::identity(),
+    ///         UnitQuaternion::identity(),
+    ///         properties,
+    ///     )
+    ///     .unwrap();
+    /// let arm = model
+    ///     .add_suspension_element(
+    ///         "arm".to_string(),
+    ///         FrameDofType::PrismaticX,
+    ///         body,
+    ///         Translation3::new(1.0, 0.0, 0.0),
+    ///         UnitQuaternion::identity(),
+    ///         properties,
+    ///         JointConstraint::new().with_position_limits(-0.8, 0.0),
+    ///     )
+    ///     .unwrap();
+    ///
+    /// let sphere = |radius| {
+    ///     CollisionGeometry::new(CollisionShape::Sphere { radius }, Isometry3::identity()).unwrap()
+    /// };
+    /// model.add_collision_geometry(&body, sphere(0.25)).unwrap();
+    /// model.add_collision_geometry(&arm, sphere(0.25)).unwrap();
+    ///
+    /// // The arm only reaches the body when it is retracted
+    /// assert!(model.check_self_collisions(0).unwrap().is_empty());
+    /// assert_eq!(1, model.check_self_collisions(5).unwrap().len());
+    /// ```
+    pub fn check_self_collisions(&self, resolution: usize) -> Result<Vec<SelfCollision>, Error> {
+        let body = *self.body()?;
+
+        let mut frames: Vec<&FrameID> = self
+            .collision_geometries
+            .iter()
+            .filter(|(_, geometries)| {
+                geometries
+                    .iter()
+                    .any(|g| !matches!(g.shape(), CollisionShape::Mesh { .. }))
+            })
+            .map(|(id, _)| id)
+            .collect();
+        frames.sort();
+
+        let mut collisions = Vec::new();
+        for (index, first) in frames.iter().enumerate() {
+            for second in &frames[index + 1..] {
+                if self.is_collision_allowed(first, second) {
+                    continue;
+                }
+
+                if resolution == 0 {
+                    let first_to_body = self.isometry_to_body(first)?;
+                    let second_to_body = self.isometry_to_body(second)?;
+                    if self.geometries_intersect(first, &first_to_body, second, &second_to_body) {
+                        collisions.push(SelfCollision {
+                            first: **first,
+                            second: **second,
+                            joint_positions: Vec::new(),
+                        });
+                    }
+
+                    continue;
+                }
+
+                // Only the joints that are not shared by the two elements move the elements
+                // relative to each other
+                let first_chain: HashSet<&FrameID> =
+                    self.chain_to_body(first)?.into_iter().collect();
+                let second_chain: HashSet<&FrameID> =
+                    self.chain_to_body(second)?.into_iter().collect();
+                let mut relative_joints: Vec<&FrameID> = first_chain
+                    .symmetric_difference(&second_chain)
+                    .copied()
+                    .collect();
+                relative_joints.sort();
+
+                let mut joints = Vec::new();
+                for joint in relative_joints {
+                    if let Some(positions) = self.sampled_joint_positions(joint, resolution)? {
+                        joints.push((*joint, positions));
+                    }
+                }
+
+                let mut configuration: HashMap<FrameID, f64> = HashMap::with_capacity(joints.len());
+                for sample in 0..number_of_configurations(&joints) {
+                    set_configuration(&joints, sample, &mut configuration);
+
+                    let position = |id: &FrameID| configuration.get(id).copied();
+                    let first_to_body = self.isometry_to_ancestor_with(first, &body, position)?;
+                    let second_to_body = self.isometry_to_ancestor_with(second, &body, position)?;
+                    if self.geometries_intersect(first, &first_to_body, second, &second_to_body) {
+                        collisions.push(SelfCollision {
+                            first: **first,
+                            second: **second,
+                            joint_positions: joints
+                                .iter()
+                                .map(|(joint, _)| (*joint, configuration[joint]))
+                                .collect(),
+                        });
+                        break;
+                    }
+                }
+            }
+        }
+
+        Ok(collisions)
+    }
+
+    /// Returns a value indicating whether the given pair of chassis elements is excluded from
+    /// the self-collision check.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'first' - The [FrameID] of the first element.
+    /// * 'second' - The [FrameID] of the second element.
+    pub fn is_collision_allowed(&self, first: &FrameID, second: &FrameID) -> bool {
+        self.allowed_collisions
+            .binary_search(&ordered_pair(first, second))
+            .is_ok()
+    }
+
+    /// Includes the given pair of chassis elements in the self-collision check again. Returns
+    /// a value indicating whether the pair was excluded.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'first' - The [FrameID] of the first element.
+    /// * 'second' - The [FrameID] of the second element.
+    pub fn remove_allowed_collision(&mut self, first: &FrameID, second: &FrameID) -> bool {
+        let pair = ordered_pair(first, second);
+        let count = self.allowed_collisions.len();
+        self.allowed_collisions.retain(|p| *p != pair);
+        self.allowed_collisions.len() != count
+    }
+
+    /// Returns a value indicating whether any of the collision shapes of the first element
+    /// overlaps with any of the collision shapes of the second element.
+    fn geometries_intersect(
+        &self,
+        first: &FrameID,
+        first_to_body: &Isometry3<f64>,
+        second: &FrameID,
+        second_to_body: &Isometry3<f64>,
+    ) -> bool {
+        let posed = |frame: &FrameID, frame_to_body: &Isometry3<f64>| -> Vec<PosedShape<'_>> {
+            self.collision_geometries(frame)
+                .iter()
+                .filter(|g| !matches!(g.shape(), CollisionShape::Mesh { .. }))
+                .map(|g| PosedShape {
+                    shape: g.shape(),
+                    pose: frame_to_body * g.origin(),
+                })
+                .collect()
+        };
+
+        let first_shapes = posed(first, first_to_body);
+        let second_shapes = posed(second, second_to_body);
+        first_shapes
+            .iter()
+            .any(|a| second_shapes.iter().any(|b| shapes_intersect(a, b)))
+    }
+}
+
+/// Returns the given pair of [FrameID] values with the smallest value first.
+fn ordered_pair(first: &FrameID, second: &FrameID) -> (FrameID, FrameID) {
+    if first <= second {
+        (*first, *second)
+    } else {
+        (*second, *first)
+    }
+}
+
+/// Returns a value indicating whether the given convex shapes overlap, using the
+/// Gilbert-Johnson-Keerthi algorithm on the Minkowski difference of the shapes.
+fn shapes_intersect(a: &PosedShape<'_>, b: &PosedShape<'_>) -> bool {
+    let support =
+        |direction: &Vector3<f64>| a.support_point(direction) - b.support_point(&-direction);
+
+    let mut direction = b.pose.translation.vector - a.pose.translation.vector;
+    if direction.norm_squared() < INTERSECTION_TOLERANCE {
+        direction = Vector3::x();
+    }
+
+    // The newest point of the simplex is stored first
+    let mut simplex = vec![support(&direction)];
+    direction = -simplex[0];
+    for _ in 0..MAX_INTERSECTION_ITERATIONS {
+        if direction.norm_squared() < INTERSECTION_TOLERANCE {
+            return true;
+        }
+
+        let point = support(&direction);
+        if point.dot(&direction) <= 0.0 {
+            // The Minkowski difference does not extend past the origin
+            return false;
+        }
+
+        simplex.insert(0, point);
+        if reduce_simplex(&mut simplex, &mut direction) {
+            return true;
+        }
+    }
+
+    false
+}
+
+/// Reduces the simplex to the feature that is closest to the origin and updates the search
+/// direction towards the origin. Returns a value indicating whether the simplex contains the
+/// origin.
+fn reduce_simplex(simplex: &mut Vec<Vector3<f64>>, direction: &mut Vector3<f64>) -> bool {
+    match simplex.len() {
+        2 => reduce_line(simplex, direction),
+        3 => reduce_triangle(simplex, direction),
+        _ => reduce_tetrahedron(simplex, direction),
+    }
+}
+
+fn reduce_line(simplex: &mut Vec<Vector3<f64>>, direction: &mut Vector3<f64>) -> bool {
+    let (a, b) = (simplex[0], simplex[1]);
+    let ab = b - a;
+    let ao = -a;
+    if ab.dot(&ao) > 0.0 {
+        *direction = ab.cross(&ao).cross(&ab);
+    } else {
+        simplex.truncate(1);
+        *direction = ao;
+    }
+
+    direction.norm_squared() < INTERSECTION_TOLERANCE
+}
+
+fn reduce_triangle(simplex: &mut Vec<Vector3<f64>>, direction: &mut Vector3<f64>) -> bool {
+    let (a, b, c) = (simplex[0], simplex[1], simplex[2]);
+    let ab = b - a;
+    let ac = c - a;
+    let ao = -a;
+    let abc = ab.cross(&ac);
+
+    if abc.cross(&ac).dot(&ao) > 0.0 {
+        if ac.dot(&ao) > 0.0 {
+            *simplex = vec![a, c];
+            *direction = ac.cross(&ao).cross(&ac);
+            return direction.norm_squared() < INTERSECTION_TOLERANCE;
+        }
+
+        *simplex = vec![a, b];
+        return reduce_line(simplex, direction);
+    }
+
+    if ab.cross(&abc).dot(&ao) > 0.0 {
+        *simplex = vec![a, b];
+        return reduce_line(simplex, direction);
+    }
+
+    let side = abc.dot(&ao);
+    if side > 0.0 {
+        *direction = abc;
+    } else if side < 0.0 {
+        *simplex = vec![a, c, b];
+        *direction = -abc;
+    } else {
+        // The origin lies inside the triangle
+        return true;
+    }
+
+    false
+}
+
+fn reduce_tetrahedron(simplex: &mut Vec<Vector3<f64>>, direction: &mut Vector3<f64>) -> bool {
+    let (a, b, c, d) = (simplex[0], simplex[1], simplex[2], simplex[3]);
+    let ab = b - a;
+    let ac = c - a;
+    let ad = d - a;
+    let ao = -a;
+
+    if ab.cross(&ac).dot(&ao) > 0.0 {
+        *simplex = vec![a, b, c];
+        return reduce_triangle(simplex, direction);
+    }
+
+    if ac.cross(&ad).dot(&ao) > 0.0 {
+        *simplex = vec![a, c, d];
+        return reduce_triangle(simplex, direction);
+    }
+
+    if ad.cross(&ab).dot(&ao) > 0.0 {
+        *simplex = vec![a, d, b];
+        return reduce_triangle(simplex, direction);
+    }
+
+    true
+}
@@ -0,0 +1,260 @@
+use std::f64::consts::PI;
+
+use nalgebra::{Isometry3, Translation3, UnitQuaternion, Vector3};
+
+use crate::{
+    model_elements::{
+        collision::{CollisionGeometry, CollisionShape},
+        frame_elements::{FrameDofType, FrameID, JointConstraint},
+        model::MotionModel,
+    },
+    test_fixtures::physical_properties,
+    Error,
+};
+
+fn geometry(shape: CollisionShape, origin: Isometry3<f64>) -> CollisionGeometry {
+    CollisionGeometry::new(shape, origin).unwrap()
+}
+
+fn cube(size: f64) -> CollisionShape {
+    CollisionShape::Box {
+        size: Vector3::repeat(size),
+    }
+}
+
+fn add_static_element(
+    model: &mut MotionModel,
+    parent: FrameID,
+    name: &str,
+    position: Translation3<f64>,
+    orientation: UnitQuaternion<f64>,
+    shape: CollisionShape,
+) -> FrameID {
+    let id = model
+        .add_static_chassis_element(
+            name.to_string(),
+            parent,
+            position,
+            orientation,
+            physical_properties(),
+        )
+        .unwrap();
+    model
+        .add_collision_geometry(&id, geometry(shape, Isometry3::identity()))
+        .unwrap();
+    id
+}
+
+fn create_body(model: &mut MotionModel) -> FrameID {
+    let body = model
+        .add_body(
+            "body".to_string(),
+            Translation3::identity(),
+            UnitQuaternion::identity(),
+            physical_properties(),
+        )
+        .unwrap();
+    model
+        .add_collision_geometry(&body, geometry(cube(1.0), Isometry3::identity()))
+        .unwrap();
+    body
+}
+
+fn pair(first: &FrameID, second: &FrameID) -> (FrameID, FrameID) {
+    if first < second {
+        (*first, *second)
+    } else {
+        (*second, *first)
+    }
+}
+
+#[test]
+fn when_checking_the_current_configuration_it_should_report_overlapping_shapes() {
+    let mut model = MotionModel::new();
+    let body = create_body(&mut model);
+
+    // The rotated cube reaches into the body while the aligned cube stays clear
+    let rotated = add_static_element(
+        &mut model,
+        body,
+        "rotated",
+        Translation3::new(0.75, 0.3, 0.0),
+        UnitQuaternion::from_euler_angles(0.0, 0.0, 0.25 * PI),
+        cube(0.4),
+    );
+    add_static_element(
+        &mut model,
+        body,
+        "aligned",
+        Translation3::new(0.75, -0.3, 0.0),
+        UnitQuaternion::identity(),
+        cube(0.4),
+    );
+    let cylinder = add_static_element(
+        &mut model,
+        body,
+        "cylinder",
+        Translation3::new(0.0, 0.0, 0.7),
+        UnitQuaternion::identity(),
+        CollisionShape::Cylinder {
+            radius: 0.1,
+            length: 0.5,
+        },
+    );
+    add_static_element(
+        &mut model,
+        body,
+        "sphere",
+        Translation3::new(0.0, 0.8, 1.0),
+        UnitQuaternion::identity(),
+        CollisionShape::Sphere { radius: 0.45 },
+    );
+
+    // Meshes are not loaded and never collide
+    add_static_element(
+        &mut model,
+        body,
+        "mesh",
+        Translation3::identity(),
+        UnitQuaternion::identity(),
+        CollisionShape::Mesh {
+            uri: "package://vehicle/meshes/body.stl".to_string(),
+            scale: Vector3::repeat(1.0),
+        },
+    );
+
+    let collisions = model.check_self_collisions(0).unwrap();
+    let mut pairs: Vec<(FrameID, FrameID)> = collisions
+        .iter()
+        .map(|c| (*c.first(), *c.second()))
+        .collect();
+    pairs.sort();
+    let mut expected = vec![pair(&body, &rotated), pair(&body, &cylinder)];
+    expected.sort();
+    assert_eq!(expected, pairs);
+    assert!(collisions.iter().all(|c| c.joint_positions().is_empty()));
+    assert!(collisions.iter().all(|c| c.first() < c.second()));
+
+    // Allowed pairs are not checked
+    model.allow_collision(&cylinder, &body).unwrap();
+    assert!(model.is_collision_allowed(&body, &cylinder));
+    assert_eq!(&[pair(&body, &cylinder)], model.allowed_collisions());
+    assert_eq!(1, model.check_self_collisions(0).unwrap().len());
+}
+
+#[test]
+fn when_sweeping_the_joints_it_should_report_the_colliding_configuration() {
+    let mut model = MotionModel::new();
+    let body = create_body(&mut model);
+
+    // A knuckle with an arm that points along its x-axis and turns without limits. Joints rotate
+    // around the origin of their parent, so the knuckle is attached to a mount.
+    let mount = model
+        .add_static_chassis_element(
+            "mount".to_string(),
+            body,
+            Translation3::new(1.5, 0.0, 0.0),
+            UnitQuaternion::identity(),
+            physical_properties(),
+        )
+        .unwrap();
+    let knuckle = model
+        .add_suspension_element(
+            "knuckle".to_string(),
+            FrameDofType::RevoluteZ,
+            mount,
+            Translation3::identity(),
+            UnitQuaternion::identity(),
+            physical_properties(),
+            JointConstraint::new(),
+        )
+        .unwrap();
+    model
+        .add_collision_geometry(
+            &knuckle,
+            geometry(
+                CollisionShape::Box {
+                    size: Vector3::new(1.2, 0.1, 0.1),
+                },
+                Isometry3::translation(0.6, 0.0, 0.0),
+            ),
+        )
+        .unwrap();
+
+    // A post on the chassis next to the knuckle
+    let post = add_static_element(
+        &mut model,
+        body,
+        "post",
+        Translation3::new(1.5, 0.5, 0.0),
+        UnitQuaternion::identity(),
+        CollisionShape::Sphere { radius: 0.1 },
+    );
+
+    assert!(model.check_self_collisions(0).unwrap().is_empty());
+
+    // The arm hits the post when it points along the y-axis and the body when it points back
+    let collisions = model.check_self_collisions(4).unwrap();
+    assert_eq!(2, collisions.len());
+    for collision in &collisions {
+        assert_eq!(1, collision.joint_positions().len());
+        let (joint, position) = collision.joint_positions()[0];
+        assert_eq!(knuckle, joint);
+
+        if pair(&knuckle, &post) == (*collision.first(), *collision.second()) {
+            assert!((position - 0.5 * PI).abs() < 1e-12);
+        } else {
+            assert_eq!(
+                pair(&knuckle, &body),
+                (*collision.first(), *collision.second())
+            );
+            assert!((position + PI).abs() < 1e-12);
+        }
+    }
+
+    // Limiting the knuckle keeps it clear of both
+    model
+        .set_joint_constraint(
+            &knuckle,
+            JointConstraint::new().with_position_limits(-0.25 * PI, 0.25 * PI),
+        )
+        .unwrap();
+    assert!(model.check_self_collisions(8).unwrap().is_empty());
+}
+
+#[test]
+fn when_allowing_a_collision_it_should_check_the_frames() {
+    let mut model = MotionModel::new();
+    let body = create_body(&mut model);
+    let arm = add_static_element(
+        &mut model,
+        body,
+        "arm",
+        Translation3::new(0.5, 0.0, 0.0),
+        UnitQuaternion::identity(),
+        cube(0.2),
+    );
+
+    let missing = FrameID::new();
+    assert_eq!(
+        Err(Error::MissingFrameElement { id: missing }),
+        model.allow_collision(&body, &missing)
+    );
+    assert_eq!(
+        Err(Error::InvalidFrameID { id: arm }),
+        model.allow_collision(&arm, &arm)
+    );
+
+    model.allow_collision(&arm, &body).unwrap();
+    model.allow_collision(&body, &arm).unwrap();
+    assert_eq!(1, model.allowed_collisions().len());
+    assert!(model.check_self_collisions(0).unwrap().is_empty());
+
+    assert!(model.remove_allowed_collision(&body, &arm));
+    assert!(!model.remove_allowed_collision(&body, &arm));
+    assert_eq!(1, model.check_self_collisions(0).unwrap().len());
+
+    model.allow_collision(&arm, &body).unwrap();
+    model.remove_element(&arm).unwrap();
+    assert!(model.allowed_collisions().is_empty());
+}
@@ -0,0 +1,246 @@
+//! Describes the frames on which the perception sensors of a vehicle are mounted.
+//!
+//! Cameras, IMUs, lidars and similar sensors do not change the kinematics of a vehicle, but they
+//! are part of the description of the vehicle. A sensor mount is a static frame element that is
+//! added with [MotionModel::add_sensor_mount] and that records the [SensorKind] of the sensor.
+//! The extrinsics of the sensors, i.e. the transforms from the sensor frames to the body or to
+//! each other, are provided by [MotionModel::sensor_mounts] and
+//! [MotionModel::sensor_extrinsics_between].
+//!
+//! # Examples
+//!
+//! ```
+//! use nalgebra::{Isometry3, Matrix3, Translation3, UnitQuaternion, Vector3};
+//! use swerve_vehicle_descriptors::model_elements::model::{
+//!     ChassisElementPhysicalProperties, MotionModel,
+//! };
+//! use swerve_vehicle_descriptors::model_elements::sensor_mount::SensorKind;
+//!
+//! let properties =
+//!     ChassisElementPhysicalProperties::from_mass_properties(0.1, Vector3::zeros(), Matrix3::identity());
+//! let mut model = MotionModel::new();
+//! let body = model
+//!     .add_body(
+//!         "body".to_string(),
+//!         Translation3::identity(),
+//!         UnitQuaternion::identity(),
+//!         properties.clone(),
+//!     )
+//!     .unwrap();
+//!
+//! let camera = model
+//!     .add_sensor_mount(
+//!         "front-camera".to_string(),
+//!         body,
+//!         Isometry3::translation(0.5, 0.0, 0.3),
+//!         SensorKind::Camera,
+//!         properties.clone(),
+//!     )
+//!     .unwrap();
+//! let imu = model
+//!     .add_sensor_mount(
+//!         "imu".to_string(),
+//!         body,
+//!         Isometry3::translation(0.0, 0.0, 0.1),
+//!         SensorKind::Imu,
+//!         properties,
+//!     )
+//!     .unwrap();
+//!
+//! let mounts = model.sensor_mounts().unwrap();
+//! assert_eq!(2, mounts.len());
+//! assert_eq!(Some(&SensorKind::Camera), model.sensor_kind(&camera));
+//!
+//! // The position of the camera as seen from the IMU
+//! let extrinsics = model.sensor_extrinsics_between(&camera, &imu).unwrap();
+//! assert!((extrinsics.translation.vector - Vector3::new(0.5, 0.0, 0.2)).norm() < 1e-12);
+//! ```
+
+use std::fmt::Display;
+
+use nalgebra::Isometry3;
+
+use crate::Error;
+
+use super::{
+    frame_elements::FrameID,
+    model::{ChassisElementPhysicalProperties, MotionModel},
+};
+
+#[cfg(test)]
+#[path = "sensor_mount_tests.rs"]
+mod sensor_mount_tests;
+
+/// Defines the kinds of sensors that can be mounted on a vehicle.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SensorKind {
+    /// A camera, including depth and stereo cameras.
+    Camera,
+    /// A global navigation satellite system receiver, e.g. a GPS antenna.
+    Gnss,
+    /// An inertial measurement unit.
+    Imu,
+    /// A lidar.
+    Lidar,
+    /// A radar.
+    Radar,
+    /// An ultrasonic range sensor.
+    Ultrasonic,
+    /// A sensor of a kind that is not known to the crate, described by its name.
+    Other(String),
+}
+
+impl Display for SensorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SensorKind::Camera => write!(f, "camera"),
+            SensorKind::Gnss => write!(f, "gnss"),
+            SensorKind::Imu => write!(f, "imu"),
+            SensorKind::Lidar => write!(f, "lidar"),
+            SensorKind::Radar => write!(f, "radar"),
+            SensorKind::Ultrasonic => write!(f, "ultrasonic"),
+            SensorKind::Other(name) => write!(f, "{}", name),
+        }
+    }
+}
+
+/// Describes a sensor that is mounted on a vehicle, together with its extrinsics.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SensorMount {
+    /// The frame of the sensor mount.
+    frame: FrameID,
+
+    /// The name of the sensor mount.
+    name: String,
+
+    /// The kind of sensor that is mounted.
+    kind: SensorKind,
+
+    /// The transform from the frame of the sensor mount to the body frame.
+    extrinsics: Isometry3<f64>,
+}
+
+impl SensorMount {
+    /// Returns the transform from the frame of the sensor mount to the body frame, for the
+    /// joint positions at the time the mount was retrieved.
+    pub fn extrinsics(&self) -> &Isometry3<f64> {
+        &self.extrinsics
+    }
+
+    /// Returns the [FrameID] of the sensor mount.
+    pub fn frame(&self) -> FrameID {
+        self.frame
+    }
+
+    /// Returns the kind of sensor that is mounted.
+    pub fn kind(&self) -> &SensorKind {
+        &self.kind
+    }
+
+    /// Returns the name of the sensor mount.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl MotionModel {
+    /// Adds a frame element on which a sensor is mounted. The sensor mount is rigidly attached
+    /// to its parent.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'name' - The name of the sensor mount, e.g. `front-camera`.
+    /// * 'parent_id' - The [FrameID] of the frame to which the sensor is attached.
+    /// * 'pose' - The position and orientation of the sensor mount relative to the parent frame.
+    /// * 'kind' - The kind of sensor that is mounted.
+    /// * 'physical_properties' - The mass properties of the sensor.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the parent frame is not part of the model.
+    /// * [Error::InvalidFrameID] - Returned when the parent frame is a wheel.
+    pub fn add_sensor_mount(
+        &mut self,
+        name: String,
+        parent_id: FrameID,
+        pose: Isometry3<f64>,
+        kind: SensorKind,
+        physical_properties: ChassisElementPhysicalProperties,
+    ) -> Result<FrameID, Error> {
+        let frame_id = self.add_static_chassis_element(
+            name,
+            parent_id,
+            pose.translation,
+            pose.rotation,
+            physical_properties,
+        )?;
+
+        self.sensor_mounts.insert(frame_id, kind);
+        Ok(frame_id)
+    }
+
+    /// Returns the transform from the frame of the 'from' sensor mount to the frame of the 'to'
+    /// sensor mount, for the current positions of the joints, e.g. to project the points of a
+    /// lidar into the image of a camera.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'from' - The [FrameID] of the sensor mount in which the data is expressed.
+    /// * 'to' - The [FrameID] of the sensor mount to which the data is transformed.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when either frame is not part of the model.
+    /// * [Error::InvalidFrameID] - Returned when either frame is not a sensor mount.
+    pub fn sensor_extrinsics_between(
+        &self,
+        from: &FrameID,
+        to: &FrameID,
+    ) -> Result<Isometry3<f64>, Error> {
+        for frame_id in [from, to] {
+            if !self.kinematic_tree().has_element(frame_id) {
+                return Err(Error::MissingFrameElement { id: *frame_id });
+            }
+
+            if !self.sensor_mounts.contains_key(frame_id) {
+                return Err(Error::InvalidFrameID { id: *frame_id });
+            }
+        }
+
+        self.isometry_between_frames(from, to)
+    }
+
+    /// Returns the kind of sensor that is mounted on the given frame, or [None] if the frame is
+    /// not a sensor mount.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'frame_id' - The [FrameID] of the frame.
+    pub fn sensor_kind(&self, frame_id: &FrameID) -> Option<&SensorKind> {
+        self.sensor_mounts.get(frame_id)
+    }
+
+    /// Returns all the sensor mounts of the model, ordered by [FrameID], with the transforms
+    /// from the sensor frames to the body frame for the current positions of the joints.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the model has no body.
+    pub fn sensor_mounts(&self) -> Result<Vec<SensorMount>, Error> {
+        let mut frames: Vec<&FrameID> = self.sensor_mounts.keys().collect();
+        frames.sort();
+
+        frames
+            .into_iter()
+            .map(|frame_id| {
+                Ok(SensorMount {
+                    frame: *frame_id,
+                    name: self.reference_frame(frame_id)?.name().to_string(),
+                    kind: self.sensor_mounts[frame_id].clone(),
+                    extrinsics: self.isometry_to_body(frame_id)?,
+                })
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,150 @@
+use std::f64::consts::PI;
+
+use nalgebra::{Isometry3, Translation3, UnitQuaternion, Vector3};
+
+use crate::{
+    model_elements::{
+        frame_elements::FrameID,
+        model::{ChassisElementPhysicalProperties, MotionModel},
+    },
+    test_fixtures::physical_properties_with_mass,
+    Error,
+};
+
+use super::SensorKind;
+
+fn physical_properties() -> ChassisElementPhysicalProperties {
+    physical_properties_with_mass(0.1, Vector3::zeros())
+}
+
+// Builds a body with a mast, a camera on the mast that is rotated 90 degrees around the z-axis
+// and a lidar directly on the body.
+fn build_model() -> (MotionModel, FrameID, FrameID, FrameID) {
+    let mut model = MotionModel::new();
+    let body = model
+        .add_body(
+            "body".to_string(),
+            Translation3::identity(),
+            UnitQuaternion::identity(),
+            physical_properties(),
+        )
+        .unwrap();
+
+    let mast = model
+        .add_static_chassis_element(
+            "mast".to_string(),
+            body,
+            Translation3::new(0.0, 0.0, 1.0),
+            UnitQuaternion::identity(),
+            physical_properties(),
+        )
+        .unwrap();
+
+    let camera = model
+        .add_sensor_mount(
+            "camera".to_string(),
+            mast,
+            Isometry3::new(
+                Vector3::new(0.5, 0.0, 0.0),
+                Vector3::new(0.0, 0.0, 0.5 * PI),
+            ),
+            SensorKind::Camera,
+            physical_properties(),
+        )
+        .unwrap();
+
+    let lidar = model
+        .add_sensor_mount(
+            "lidar".to_string(),
+            body,
+            Isometry3::translation(-0.5, 0.0, 0.2),
+            SensorKind::Other("solid-state-lidar".to_string()),
+            physical_properties(),
+        )
+        .unwrap();
+
+    (model, mast, camera, lidar)
+}
+
+#[test]
+fn when_adding_a_sensor_mount_it_should_record_the_kind() {
+    let (mut model, mast, camera, lidar) = build_model();
+    assert_eq!(Some(&SensorKind::Camera), model.sensor_kind(&camera));
+    assert_eq!(
+        Some(&SensorKind::Other("solid-state-lidar".to_string())),
+        model.sensor_kind(&lidar)
+    );
+    assert_eq!(None, model.sensor_kind(&mast));
+    assert_eq!(
+        "solid-state-lidar",
+        model.sensor_kind(&lidar).unwrap().to_string()
+    );
+
+    let result = model.add_sensor_mount(
+        "imu".to_string(),
+        FrameID::new(),
+        Isometry3::identity(),
+        SensorKind::Imu,
+        physical_properties(),
+    );
+    assert!(matches!(result, Err(Error::MissingFrameElement { .. })));
+
+    model.remove_element(&camera).unwrap();
+    assert_eq!(None, model.sensor_kind(&camera));
+    assert_eq!(1, model.sensor_mounts().unwrap().len());
+}
+
+#[test]
+fn when_listing_the_sensor_mounts_it_should_provide_the_extrinsics_to_the_body() {
+    let (model, _, camera, lidar) = build_model();
+
+    let mounts = model.sensor_mounts().unwrap();
+    assert_eq!(2, mounts.len());
+
+    let camera_mount = mounts.iter().find(|m| m.frame() == camera).unwrap();
+    assert_eq!("camera", camera_mount.name());
+    assert_eq!(&SensorKind::Camera, camera_mount.kind());
+    assert!(
+        (camera_mount.extrinsics().translation.vector - Vector3::new(0.5, 0.0, 1.0)).norm() < 1e-12
+    );
+    let x_axis = camera_mount.extrinsics() * Vector3::x();
+    assert!((x_axis - Vector3::y()).norm() < 1e-12);
+
+    let lidar_mount = mounts.iter().find(|m| m.frame() == lidar).unwrap();
+    assert_eq!(
+        &model.isometry_to_body(&lidar).unwrap(),
+        lidar_mount.extrinsics()
+    );
+}
+
+#[test]
+fn when_getting_the_extrinsics_between_sensors_it_should_only_accept_sensor_mounts() {
+    let (model, mast, camera, lidar) = build_model();
+
+    let extrinsics = model.sensor_extrinsics_between(&lidar, &camera).unwrap();
+    let expected = model.isometry_to_body(&camera).unwrap().inverse()
+        * model.isometry_to_body(&lidar).unwrap();
+    assert!((expected.to_homogeneous() - extrinsics.to_homogeneous()).norm() < 1e-12);
+
+    assert_eq!(
+        Err(Error::InvalidFrameID { id: mast }),
+        model.sensor_extrinsics_between(&mast, &camera)
+    );
+
+    let missing = FrameID::new();
+    assert_eq!(
+        Err(Error::MissingFrameElement { id: missing }),
+        model.sensor_extrinsics_between(&camera, &missing)
+    );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn when_serializing_it_should_store_the_sensor_kinds() {
+    let (model, _, camera, lidar) = build_model();
+
+    let json = serde_json::to_string(&model).unwrap();
+    let loaded: MotionModel = serde_json::from_str(&json).unwrap();
+    assert_eq!(Some(&SensorKind::Camera), loaded.sensor_kind(&camera));
+    assert_eq!(model.sensor_kind(&lidar), loaded.sensor_kind(&lidar));
+}
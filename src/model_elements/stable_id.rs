@@ -0,0 +1,308 @@
+//! Provides the identifiers that refer to the frame elements of a [MotionModel] across process
+//! restarts.
+//!
+//! A [FrameID] is created from a counter each time a frame element is added to a model, so the
+//! same frame element may have a different [FrameID] each time the application builds its model.
+//! Configuration that is stored between runs should therefore not refer to frame elements by
+//! their [FrameID]. Instead a [StableFrameID] is assigned to the frame elements, either from a
+//! UUID or derived from a name, with [MotionModel::assign_stable_id]. The stable IDs are
+//! stored with the model when it is serialized.
+//!
+//! A [FrameIdMapping] records which [FrameID] was assigned to each [StableFrameID] in a given
+//! run. Stored with data that refers to frame elements by their [FrameID] it allows that data
+//! to be translated to the [FrameID] values of a later run, see
+//! [FrameIdMapping::translation_to].
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Display,
+    str::FromStr,
+};
+
+use crate::Error;
+
+use super::{frame_elements::FrameID, model::MotionModel};
+
+#[cfg(test)]
+#[path = "stable_id_tests.rs"]
+mod stable_id_tests;
+
+/// The offset basis of the 128-bit FNV-1a hash.
+const FNV_OFFSET_BASIS: u128 = 0x6c62272e07bb014262b821756295c58d;
+
+/// The prime of the 128-bit FNV-1a hash.
+const FNV_PRIME: u128 = 0x0000000001000000000000000000013b;
+
+/// An identifier for a frame element that does not change between runs of the application.
+///
+/// The identifier is a 128-bit value, which is formatted in the same way as a UUID, e.g.
+/// `67e55044-10b1-426f-9247-bb680e5fe0c8`.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct StableFrameID {
+    /// The value of the identifier.
+    value: u128,
+}
+
+impl StableFrameID {
+    /// Returns the 16 bytes of the identifier, in the same order as the bytes of a UUID.
+    pub fn as_bytes(&self) -> [u8; 16] {
+        self.value.to_be_bytes()
+    }
+
+    /// Creates a [StableFrameID] from the 16 bytes of a UUID.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'bytes' - The bytes of the UUID.
+    pub fn from_bytes(bytes: [u8; 16]) -> Self {
+        Self {
+            value: u128::from_be_bytes(bytes),
+        }
+    }
+
+    /// Creates a [StableFrameID] that is derived from the given name. The same name always
+    /// results in the same identifier.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'name' - The name from which the identifier is derived, e.g. `left-front/steering`.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use swerve_vehicle_descriptors::model_elements::stable_id::StableFrameID;
+    ///
+    /// assert_eq!(
+    ///     StableFrameID::from_name("left-front/steering"),
+    ///     StableFrameID::from_name("left-front/steering")
+    /// );
+    /// assert_ne!(
+    ///     StableFrameID::from_name("left-front/steering"),
+    ///     StableFrameID::from_name("right-front/steering")
+    /// );
+    /// ```
+    pub fn from_name(name: &str) -> Self {
+        let value = name.bytes().fold(FNV_OFFSET_BASIS, |hash, byte| {
+            (hash ^ byte as u128).wrapping_mul(FNV_PRIME)
+        });
+
+        Self { value }
+    }
+}
+
+impl Display for StableFrameID {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let hex = format!("{:032x}", self.value);
+        write!(
+            f,
+            "{}-{}-{}-{}-{}",
+            &hex[0..8],
+            &hex[8..12],
+            &hex[12..16],
+            &hex[16..20],
+            &hex[20..32]
+        )
+    }
+}
+
+impl FromStr for StableFrameID {
+    type Err = Error;
+
+    /// Parses a [StableFrameID] from 32 hexadecimal digits, which may be separated by hyphens
+    /// as in the textual form of a UUID.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let digits: String = s.chars().filter(|c| *c != '-').collect();
+        if digits.len() != 32 || !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(Error::InvalidStableFrameID {
+                value: s.to_string(),
+            });
+        }
+
+        u128::from_str_radix(&digits, 16)
+            .map(|value| Self { value })
+            .map_err(|_| Error::InvalidStableFrameID {
+                value: s.to_string(),
+            })
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for StableFrameID {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for StableFrameID {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let text = <String as serde::Deserialize>::deserialize(deserializer)?;
+        text.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// Records the [FrameID] that was assigned to each [StableFrameID] of a model in a given run of
+/// the application.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FrameIdMapping {
+    /// The [FrameID] for each stable ID.
+    frames: BTreeMap<StableFrameID, FrameID>,
+}
+
+impl FrameIdMapping {
+    /// Returns the [FrameID] that was assigned to the given stable ID, or [None] if the stable ID
+    /// is not part of the mapping.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'stable_id' - The stable ID of the frame element.
+    pub fn frame_for(&self, stable_id: &StableFrameID) -> Option<FrameID> {
+        self.frames.get(stable_id).copied()
+    }
+
+    /// Returns a value indicating whether the mapping is empty.
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Returns an iterator over the stable IDs and the [FrameID] values, ordered by stable ID.
+    pub fn iter(&self) -> impl Iterator<Item = (&StableFrameID, &FrameID)> {
+        self.frames.iter()
+    }
+
+    /// Returns the number of stable IDs in the mapping.
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Returns the stable ID that was assigned to the given frame element, or [None] if the
+    /// frame element has no stable ID.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'frame_id' - The [FrameID] of the frame element.
+    pub fn stable_id_for(&self, frame_id: &FrameID) -> Option<StableFrameID> {
+        self.frames
+            .iter()
+            .find(|(_, id)| *id == frame_id)
+            .map(|(stable_id, _)| *stable_id)
+    }
+
+    /// Returns the map from the [FrameID] values of this mapping to the [FrameID] values of the
+    /// 'current' mapping, for all the stable IDs that are part of both mappings.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'current' - The mapping of the current run, see [MotionModel::frame_id_mapping].
+    pub fn translation_to(&self, current: &FrameIdMapping) -> HashMap<FrameID, FrameID> {
+        self.frames
+            .iter()
+            .filter_map(|(stable_id, old)| current.frame_for(stable_id).map(|new| (*old, new)))
+            .collect()
+    }
+}
+
+impl MotionModel {
+    /// Assigns a stable ID to the given frame element. A stable ID that was previously assigned
+    /// to the frame element is replaced.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'frame_id' - The [FrameID] of the frame element.
+    /// * 'stable_id' - The stable ID of the frame element.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the frame element is not part of the model.
+    /// * [Error::DuplicateStableFrameID] - Returned when the stable ID is already assigned to
+    ///   another frame element.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use nalgebra::{Matrix3, Translation3, UnitQuaternion, Vector3};
+    /// use swerve_vehicle_descriptors::model_elements::model::{
+    ///     ChassisElementPhysicalProperties, MotionModel,
+    /// };
+    /// use swerve_vehicle_descriptors::model_elements::stable_id::StableFrameID;
+    ///
+    /// let mut model = MotionModel::new();
+    /// let body = model
+    ///     .add_body(
+    ///         "body".to_string(),
+    ///         Translation3::identity(),
+    ///         UnitQuaternion::identity(),
+    ///         ChassisElementPhysicalProperties::from_mass_properties(
+    ///             1.0,
+    ///             Vector3::zeros(),
+    ///             Matrix3::identity(),
+    ///         ),
+    ///     )
+    ///     .unwrap();
+    ///
+    /// let stable_id: StableFrameID = "67e55044-10b1-426f-9247-bb680e5fe0c8".parse().unwrap();
+    /// model.assign_stable_id(&body, stable_id).unwrap();
+    /// assert_eq!(Some(body), model.frame_for_stable_id(&stable_id));
+    /// assert_eq!(Some(body), model.frame_id_mapping().frame_for(&stable_id));
+    /// ```
+    pub fn assign_stable_id(
+        &mut self,
+        frame_id: &FrameID,
+        stable_id: StableFrameID,
+    ) -> Result<(), Error> {
+        if !self.kinematic_tree().has_element(frame_id) {
+            return Err(Error::MissingFrameElement { id: *frame_id });
+        }
+
+        if let Some(other) = self.frame_for_stable_id(&stable_id) {
+            if other != *frame_id {
+                return Err(Error::DuplicateStableFrameID { id: stable_id });
+            }
+        }
+
+        self.stable_ids.insert(*frame_id, stable_id);
+        Ok(())
+    }
+
+    /// Returns the frame element that has the given stable ID, or [None] if no frame element
+    /// has the stable ID.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'stable_id' - The stable ID of the frame element.
+    pub fn frame_for_stable_id(&self, stable_id: &StableFrameID) -> Option<FrameID> {
+        self.stable_ids
+            .iter()
+            .find(|(_, id)| *id == stable_id)
+            .map(|(frame_id, _)| *frame_id)
+    }
+
+    /// Returns the [FrameIdMapping] that records the [FrameID] of each frame element that has a
+    /// stable ID.
+    pub fn frame_id_mapping(&self) -> FrameIdMapping {
+        FrameIdMapping {
+            frames: self
+                .stable_ids
+                .iter()
+                .map(|(frame_id, stable_id)| (*stable_id, *frame_id))
+                .collect(),
+        }
+    }
+
+    /// Returns the stable ID of the given frame element, or [None] if no stable ID was assigned
+    /// to the frame element.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'frame_id' - The [FrameID] of the frame element.
+    pub fn stable_id_of(&self, frame_id: &FrameID) -> Option<StableFrameID> {
+        self.stable_ids.get(frame_id).copied()
+    }
+}
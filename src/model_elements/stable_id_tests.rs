@@ -0,0 +1,148 @@
+use nalgebra::{Translation3, UnitQuaternion};
+
+use crate::{
+    model_elements::{frame_elements::FrameID, model::MotionModel},
+    test_fixtures::physical_properties,
+    Error,
+};
+
+use super::{FrameIdMapping, StableFrameID};
+
+// Builds a body with two static elements and assigns name derived stable IDs to all of them.
+// Each call creates new FrameIDs, in the same way as a restart of the application does.
+fn build_model() -> (MotionModel, Vec<FrameID>) {
+    let mut model = MotionModel::new();
+    let body = model
+        .add_body(
+            "body".to_string(),
+            Translation3::identity(),
+            UnitQuaternion::identity(),
+            physical_properties(),
+        )
+        .unwrap();
+
+    let mut frames = vec![body];
+    for name in ["bumper", "lidar"] {
+        frames.push(
+            model
+                .add_static_chassis_element(
+                    name.to_string(),
+                    body,
+                    Translation3::new(1.0, 0.0, 0.0),
+                    UnitQuaternion::identity(),
+                    physical_properties(),
+                )
+                .unwrap(),
+        );
+    }
+
+    for (frame, name) in frames.iter().zip(["body", "body/bumper", "body/lidar"]) {
+        model
+            .assign_stable_id(frame, StableFrameID::from_name(name))
+            .unwrap();
+    }
+
+    (model, frames)
+}
+
+#[test]
+fn when_formatting_a_stable_id_it_should_round_trip_through_the_uuid_text() {
+    let text = "67e55044-10b1-426f-9247-bb680e5fe0c8";
+    let id: StableFrameID = text.parse().unwrap();
+    assert_eq!(text, id.to_string());
+    assert_eq!(0x67, id.as_bytes()[0]);
+    assert_eq!(0xc8, id.as_bytes()[15]);
+    assert_eq!(id, StableFrameID::from_bytes(id.as_bytes()));
+    assert_eq!(Ok(id), "67E5504410B1426F9247BB680E5FE0C8".parse());
+
+    for invalid in ["", "67e55044", "67e55044-10b1-426f-9247-bb680e5fe0cg"] {
+        assert_eq!(
+            Err(Error::InvalidStableFrameID {
+                value: invalid.to_string()
+            }),
+            invalid.parse::<StableFrameID>()
+        );
+    }
+
+    // The derived IDs must never change, otherwise stored configuration breaks
+    assert_eq!(
+        "6c62272e-07bb-0142-62b8-21756295c58d",
+        StableFrameID::from_name("").to_string()
+    );
+    assert_ne!(
+        StableFrameID::from_name("body/bumper"),
+        StableFrameID::from_name("body/lidar")
+    );
+}
+
+#[test]
+fn when_assigning_stable_ids_it_should_reject_duplicates_and_unknown_frames() {
+    let (mut model, frames) = build_model();
+    let bumper = StableFrameID::from_name("body/bumper");
+    assert_eq!(Some(bumper), model.stable_id_of(&frames[1]));
+    assert_eq!(Some(frames[1]), model.frame_for_stable_id(&bumper));
+
+    assert_eq!(
+        Err(Error::DuplicateStableFrameID { id: bumper }),
+        model.assign_stable_id(&frames[2], bumper)
+    );
+    assert!(matches!(
+        model.assign_stable_id(&FrameID::new(), StableFrameID::from_name("other")),
+        Err(Error::MissingFrameElement { .. })
+    ));
+
+    // Re-assigning frees the previous ID
+    let front = StableFrameID::from_name("body/front-bumper");
+    model.assign_stable_id(&frames[1], front).unwrap();
+    assert_eq!(None, model.frame_for_stable_id(&bumper));
+    assert_eq!(Some(front), model.stable_id_of(&frames[1]));
+
+    model.remove_element(&frames[1]).unwrap();
+    assert_eq!(None, model.frame_for_stable_id(&front));
+    assert_eq!(2, model.frame_id_mapping().len());
+}
+
+#[test]
+fn when_translating_a_saved_mapping_it_should_map_to_the_frames_of_the_current_run() {
+    let (first_model, first_frames) = build_model();
+    let saved = first_model.frame_id_mapping();
+    assert_eq!(3, saved.len());
+    assert_eq!(
+        Some(StableFrameID::from_name("body/lidar")),
+        saved.stable_id_for(&first_frames[2])
+    );
+
+    let (mut second_model, second_frames) = build_model();
+    assert_ne!(first_frames, second_frames);
+
+    second_model.remove_element(&second_frames[2]).unwrap();
+    let translation = saved.translation_to(&second_model.frame_id_mapping());
+    assert_eq!(2, translation.len());
+    assert_eq!(Some(&second_frames[0]), translation.get(&first_frames[0]));
+    assert_eq!(Some(&second_frames[1]), translation.get(&first_frames[1]));
+    assert_eq!(None, translation.get(&first_frames[2]));
+
+    assert!(FrameIdMapping::default().is_empty());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn when_serializing_it_should_store_the_stable_ids() {
+    let (model, frames) = build_model();
+
+    let json = serde_json::to_string(&model).unwrap();
+    let loaded: MotionModel = serde_json::from_str(&json).unwrap();
+    assert_eq!(model.frame_id_mapping(), loaded.frame_id_mapping());
+    assert_eq!(
+        Some(frames[2]),
+        loaded.frame_for_stable_id(&StableFrameID::from_name("body/lidar"))
+    );
+
+    let mapping = model.frame_id_mapping();
+    let json = serde_json::to_string(&mapping).unwrap();
+    assert!(json.contains(&StableFrameID::from_name("body").to_string()));
+    assert_eq!(
+        mapping,
+        serde_json::from_str::<FrameIdMapping>(&json).unwrap()
+    );
+}
@@ -0,0 +1,361 @@
+//! Describes the springs and dampers of the suspension of a vehicle.
+//!
+//! A suspension frame, i.e. a joint between a wheel and the body that is not a steering or a
+//! wheel joint, can have a [SuspensionSpring] that describes the stiffness, the damping and the
+//! preload of the spring and damper that act on the joint. The units follow the degree of freedom
+//! of the joint, i.e. meters and Newtons for a prismatic joint and radians and Newton meters for
+//! a revolute joint.
+//!
+//! The geometric description of a vehicle places all the joints at their zero position. Under
+//! its own weight the vehicle sits lower. [MotionModel::static_suspension_deflection] computes
+//! the positions of the sprung joints at which the springs balance the weight of the vehicle
+//! when it stands on level ground. The weight is distributed over the wheels with the
+//! minimum-norm distribution that balances the forces and the moments around the center of
+//! mass, which is exact for vehicles with three wheels and for vehicles that are symmetric
+//! around their center of mass. The load on a wheel is converted into a generalized force on each
+//! sprung joint between the wheel and the body with the Jacobian of the contact point of the
+//! wheel. A spring only deflects once the load exceeds its preload, and the deflection is
+//! clamped to the position limits of the [JointConstraint](super::frame_elements::JointConstraint)
+//! of the joint. The change of the geometry due to the deflection is not taken into account.
+//!
+//! # Examples
+//!
+//! ```
+//! use nalgebra::{Matrix3, Translation3, UnitQuaternion, Vector3};
+//! use swerve_vehicle_descriptors::model_elements::{
+//!     frame_elements::{FrameDofType, JointConstraint},
+//!     model::{ChassisElementPhysicalProperties, MotionModel, WheelProperties},
+//!     module_spec::{BodySpec, ModuleSpec},
+//!     suspension::SuspensionSpring,
+//! };
+//!
+//! let properties = |mass| {
+//!     ChassisElementPhysicalProperties::from_mass_properties(mass, Vector3::zeros(), Matrix3::identity())
+//! };
+//!
+//! // A body of 36 kg with four modules of 3 kg each, which have a vertical suspension
+//! let mut builder = MotionModel::builder().body(BodySpec::new(
+//!     "body".to_string(),
+//!     Translation3::identity(),
+//!     UnitQuaternion::identity(),
+//!     properties(36.0),
+//! ));
+//! for (name, x, y) in [("fl", 1.0, 0.5), ("fr", 1.0, -0.5), ("rl", -1.0, 0.5), ("rr", -1.0, -0.5)] {
+//!     builder = builder.drive_module(
+//!         ModuleSpec::new(
+//!             name.to_string(),
+//!             Translation3::new(x, y, 0.0),
+//!             UnitQuaternion::identity(),
+//!             WheelProperties::new(0.1, 0.05, Vector3::zeros()),
+//!             properties(1.0),
+//!             properties(1.0),
+//!         )
+//!         .with_suspension(FrameDofType::PrismaticZ, properties(1.0), JointConstraint::new())
+//!         .with_wheel_offset(Translation3::new(0.0, 0.0, -0.1)),
+//!     );
+//! }
+//! let mut model = builder.build().unwrap();
+//!
+//! let suspensions: Vec<_> = model
+//!     .drive_modules()
+//!     .map(|m| *m.frames().suspension().unwrap())
+//!     .collect();
+//! for suspension in &suspensions {
+//!     model
+//!         .set_suspension_spring(suspension, SuspensionSpring::new(10_000.0, 500.0, 0.0))
+//!         .unwrap();
+//! }
+//!
+//! // Each wheel carries a quarter of the weight of 48 kg
+//! let deflection = model.static_suspension_deflection().unwrap();
+//! let expected = 12.0 * model.gravity().norm() / 10_000.0;
+//! assert!((deflection[&suspensions[0]] - expected).abs() < 1e-12);
+//! ```
+
+use std::collections::HashMap;
+
+use nalgebra::{DMatrix, DVector, Point3, Vector3};
+
+use crate::{
+    kinematics::mass_properties::{combined_center_of_mass, element_masses_in_body_with},
+    Error,
+};
+
+use super::{
+    frame_elements::{clamp_to, FrameID},
+    model::MotionModel,
+    model_change::ModelChange,
+};
+
+#[cfg(test)]
+#[path = "suspension_tests.rs"]
+mod suspension_tests;
+
+/// The tolerance used when distributing the weight of the vehicle over the wheels.
+const LOAD_SOLVER_TOLERANCE: f64 = 1e-12;
+
+/// Describes the spring and the damper that act on a suspension joint.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SuspensionSpring {
+    /// The stiffness of the spring, in Newton per meter or Newton meter per radian.
+    stiffness: f64,
+
+    /// The damping coefficient of the damper, in Newton second per meter or Newton meter second
+    /// per radian.
+    damping: f64,
+
+    /// The force, or torque, that the spring exerts when the joint is at its zero position.
+    preload: f64,
+}
+
+impl SuspensionSpring {
+    /// Returns the damping coefficient of the damper, in Newton second per meter or Newton
+    /// meter second per radian.
+    pub fn damping(&self) -> f64 {
+        self.damping
+    }
+
+    /// Returns the force, in Newton, or the torque, in Newton meter, that is needed to deflect
+    /// the spring from the zero position of the joint.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'deflection' - The deflection of the spring from the zero position of the joint, in
+    ///   meters or radians. A positive deflection compresses the spring.
+    pub fn force_at(&self, deflection: f64) -> f64 {
+        self.preload + self.stiffness * deflection
+    }
+
+    /// Creates a new [SuspensionSpring] instance.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'stiffness' - The stiffness of the spring, in Newton per meter for prismatic joints and
+    ///   Newton meter per radian for revolute joints.
+    /// * 'damping' - The damping coefficient of the damper, in Newton second per meter for
+    ///   prismatic joints and Newton meter second per radian for revolute joints.
+    /// * 'preload' - The force, or torque, that the spring exerts when the joint is at its zero
+    ///   position, i.e. the load that is needed before the suspension starts to compress.
+    pub fn new(stiffness: f64, damping: f64, preload: f64) -> Self {
+        Self {
+            stiffness,
+            damping,
+            preload,
+        }
+    }
+
+    /// Returns the force, in Newton, or the torque, in Newton meter, that the spring exerts
+    /// when the joint is at its zero position.
+    pub fn preload(&self) -> f64 {
+        self.preload
+    }
+
+    /// Returns the stiffness of the spring, in Newton per meter or Newton meter per radian.
+    pub fn stiffness(&self) -> f64 {
+        self.stiffness
+    }
+
+    /// Returns a value indicating whether the parameters of the spring are valid, i.e. the
+    /// stiffness is a finite, positive number and the damping and the preload are finite,
+    /// non-negative numbers.
+    fn is_valid(&self) -> bool {
+        self.stiffness.is_finite()
+            && self.stiffness > 0.0
+            && self.damping.is_finite()
+            && self.damping >= 0.0
+            && self.preload.is_finite()
+            && self.preload >= 0.0
+    }
+}
+
+impl MotionModel {
+    /// Removes the [SuspensionSpring] of the given suspension frame. Returns the spring that was
+    /// removed, or [None] if the frame did not have a spring.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'suspension_frame' - The [FrameID] of the suspension frame.
+    pub fn remove_suspension_spring(
+        &mut self,
+        suspension_frame: &FrameID,
+    ) -> Option<SuspensionSpring> {
+        let spring = self.suspension_springs.remove(suspension_frame);
+        if spring.is_some() {
+            self.invalidate(ModelChange::SuspensionSpringChanged {
+                frame: *suspension_frame,
+            });
+        }
+
+        spring
+    }
+
+    /// Sets the [SuspensionSpring] of the given suspension frame, replacing the existing
+    /// spring.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'suspension_frame' - The [FrameID] of the suspension frame.
+    /// * 'spring' - The spring and damper that act on the joint of the frame.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the frame is not part of the model.
+    /// * [Error::InvalidFrameID] - Returned when the frame is the body or a wheel, or when the
+    ///   frame does not move relative to its parent.
+    /// * [Error::InvalidSuspensionSpring] - Returned when the stiffness is not a finite,
+    ///   positive number or when the damping or the preload is negative or not finite.
+    pub fn set_suspension_spring(
+        &mut self,
+        suspension_frame: &FrameID,
+        spring: SuspensionSpring,
+    ) -> Result<(), Error> {
+        if !self.kinematic_tree().has_element(suspension_frame) {
+            return Err(Error::MissingFrameElement {
+                id: *suspension_frame,
+            });
+        }
+
+        if self.is_body(suspension_frame)
+            || self.kinematic_tree().is_wheel(suspension_frame)?
+            || self
+                .frame_degree_of_freedom(suspension_frame)?
+                .axis()
+                .is_none()
+        {
+            return Err(Error::InvalidFrameID {
+                id: *suspension_frame,
+            });
+        }
+
+        if !spring.is_valid() {
+            return Err(Error::InvalidSuspensionSpring {
+                stiffness: spring.stiffness,
+                damping: spring.damping,
+                preload: spring.preload,
+            });
+        }
+
+        self.suspension_springs.insert(*suspension_frame, spring);
+        self.invalidate(ModelChange::SuspensionSpringChanged {
+            frame: *suspension_frame,
+        });
+        Ok(())
+    }
+
+    /// Returns the positions of the suspension joints that have a [SuspensionSpring] when the
+    /// vehicle stands still on level ground, i.e. the positions at which the springs balance the
+    /// weight of the vehicle. See the [module documentation](self) for the assumptions that are
+    /// made.
+    ///
+    /// The weight is computed from the masses of all the chassis elements with all the joints at
+    /// their zero position, using the magnitude of the gravity of the model. Joints that do not
+    /// carry any load of a wheel stay at their zero position.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the model has no body or no wheels.
+    /// * [Error::MissingWheelRadius] - Returned when the radius of a wheel is not known.
+    pub fn static_suspension_deflection(&self) -> Result<HashMap<FrameID, f64>, Error> {
+        let zero_position = |_: &FrameID| None;
+        let body = *self.body()?;
+        let wheels: Vec<FrameID> = self.wheels()?.into_iter().copied().collect();
+
+        // The contact points of the wheels with all the joints at their zero position
+        let mut contact_points = Vec::with_capacity(wheels.len());
+        for wheel in &wheels {
+            let wheel_to_body = self.isometry_to_ancestor_with(wheel, &body, zero_position)?;
+            let contact_point = self.wheel_properties(wheel)?.contact_point();
+            contact_points.push(wheel_to_body * Point3::from(contact_point));
+        }
+
+        let loads = self.static_wheel_loads(&contact_points)?;
+
+        let mut result: HashMap<FrameID, f64> = self
+            .suspension_springs
+            .keys()
+            .map(|frame| (*frame, 0.0))
+            .collect();
+        for ((wheel, contact_point), load) in wheels.iter().zip(&contact_points).zip(loads.iter()) {
+            let jacobian = self.jacobian_for_with(wheel, zero_position)?;
+            let wheel_origin = self
+                .isometry_to_ancestor_with(wheel, &body, zero_position)?
+                .translation
+                .vector;
+            let lever = contact_point.coords - wheel_origin;
+
+            for (index, joint) in jacobian.joints().iter().enumerate() {
+                let spring = match self.suspension_springs.get(joint) {
+                    Some(s) => s,
+                    None => continue,
+                };
+
+                // The rate at which the contact point moves up, relative to the body, when the
+                // joint moves in its positive direction.
+                let column = jacobian.matrix().column(index);
+                let linear = Vector3::new(column[0], column[1], column[2]);
+                let angular = Vector3::new(column[3], column[4], column[5]);
+                let vertical_rate = (linear + angular.cross(&lever)).z;
+                if vertical_rate == 0.0 {
+                    continue;
+                }
+
+                // The load pushes the contact point up, which compresses the spring
+                let generalized_load = load * vertical_rate.abs();
+                let compression = if generalized_load > spring.preload {
+                    (generalized_load - spring.preload) / spring.stiffness
+                } else {
+                    0.0
+                };
+
+                let position = clamp_to(
+                    compression * vertical_rate.signum(),
+                    self.joint_constraint(joint)?.position_limits(),
+                );
+                result.insert(*joint, position);
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Returns the [SuspensionSpring] of the given suspension frame, or [None] if the frame
+    /// does not have a spring.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'suspension_frame' - The [FrameID] of the suspension frame.
+    pub fn suspension_spring(&self, suspension_frame: &FrameID) -> Option<&SuspensionSpring> {
+        self.suspension_springs.get(suspension_frame)
+    }
+
+    /// Returns the vertical load, in Newton, on each of the given contact points when the
+    /// vehicle stands still on level ground. The loads are the minimum-norm solution of the
+    /// balance of the vertical forces and of the moments around the x-axis and the y-axis.
+    fn static_wheel_loads(&self, contact_points: &[Point3<f64>]) -> Result<Vec<f64>, Error> {
+        let elements = element_masses_in_body_with(self, |_| None)?;
+        let (mass, center_of_mass) = combined_center_of_mass(&elements);
+        let weight = mass * self.gravity().norm();
+
+        let mut a = DMatrix::<f64>::zeros(3, contact_points.len());
+        for (index, point) in contact_points.iter().enumerate() {
+            a[(0, index)] = 1.0;
+            a[(1, index)] = point.x;
+            a[(2, index)] = point.y;
+        }
+        let b = DVector::from_vec(vec![
+            weight,
+            weight * center_of_mass.x,
+            weight * center_of_mass.y,
+        ]);
+
+        let loads = a
+            .svd(true, true)
+            .solve(&b, LOAD_SOLVER_TOLERANCE)
+            .map_err(|_| Error::InsufficientWheelMeasurements {
+                required: 1,
+                available: contact_points.len(),
+            })?;
+        Ok(loads.iter().copied().collect())
+    }
+}
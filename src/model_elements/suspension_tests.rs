@@ -0,0 +1,195 @@
+use nalgebra::{Translation3, UnitQuaternion, Vector3};
+
+use crate::{
+    model_elements::{
+        frame_elements::{FrameDofType, FrameID, JointConstraint},
+        model::{MotionModel, WheelProperties},
+        model_change::ModelChange,
+        module_spec::{BodySpec, ModuleSpec},
+    },
+    test_fixtures::physical_properties_with_mass,
+    Error,
+};
+
+use super::SuspensionSpring;
+
+// Builds a vehicle with a body of 36 kg and four modules of 3 kg each, ordered front left,
+// front right, rear left and rear right. Returns the model and the suspension frames.
+fn create_model(
+    degree_of_freedom: FrameDofType,
+    body_center_of_mass: Vector3<f64>,
+    constraint: JointConstraint,
+) -> (MotionModel, Vec<FrameID>) {
+    let mut builder = MotionModel::builder().body(BodySpec::new(
+        "body".to_string(),
+        Translation3::identity(),
+        UnitQuaternion::identity(),
+        physical_properties_with_mass(36.0, body_center_of_mass),
+    ));
+    for (name, x, y) in [
+        ("front_left", 1.0, 0.5),
+        ("front_right", 1.0, -0.5),
+        ("rear_left", -1.0, 0.5),
+        ("rear_right", -1.0, -0.5),
+    ] {
+        builder = builder.drive_module(
+            ModuleSpec::new(
+                name.to_string(),
+                Translation3::new(x, y, 0.0),
+                UnitQuaternion::identity(),
+                WheelProperties::new(0.1, 0.05, Vector3::zeros()),
+                physical_properties_with_mass(1.0, Vector3::zeros()),
+                physical_properties_with_mass(1.0, Vector3::zeros()),
+            )
+            .with_suspension(
+                degree_of_freedom,
+                physical_properties_with_mass(1.0, Vector3::zeros()),
+                constraint,
+            )
+            .with_wheel_offset(Translation3::new(0.0, 0.0, -0.1)),
+        );
+    }
+
+    let model = builder.build().unwrap();
+    let suspensions = model
+        .drive_modules()
+        .map(|m| *m.frames().suspension().unwrap())
+        .collect();
+    (model, suspensions)
+}
+
+#[test]
+fn when_setting_a_suspension_spring_it_should_validate_the_frame_and_the_spring() {
+    let (mut model, suspensions) = create_model(
+        FrameDofType::PrismaticZ,
+        Vector3::zeros(),
+        JointConstraint::new(),
+    );
+    let changes = model.structural_changes();
+    let spring = SuspensionSpring::new(10_000.0, 500.0, 20.0);
+    assert_eq!(20.0 + 10_000.0 * 0.01, spring.force_at(0.01));
+
+    model
+        .set_suspension_spring(&suspensions[0], spring)
+        .unwrap();
+    assert_eq!(Some(&spring), model.suspension_spring(&suspensions[0]));
+    assert_eq!(None, model.suspension_spring(&suspensions[1]));
+    assert_eq!(
+        &ModelChange::SuspensionSpringChanged {
+            frame: suspensions[0]
+        },
+        changes.try_recv().unwrap().change()
+    );
+
+    let body = *model.body().unwrap();
+    let wheel = *model.drive_modules().next().unwrap().frames().wheel();
+    for frame in [body, wheel] {
+        assert_eq!(
+            Err(Error::InvalidFrameID { id: frame }),
+            model.set_suspension_spring(&frame, spring)
+        );
+    }
+    let missing = FrameID::new();
+    assert_eq!(
+        Err(Error::MissingFrameElement { id: missing }),
+        model.set_suspension_spring(&missing, spring)
+    );
+
+    for (stiffness, damping, preload) in [(0.0, 1.0, 0.0), (1.0, -1.0, 0.0), (1.0, 0.0, f64::NAN)] {
+        assert!(matches!(
+            model.set_suspension_spring(
+                &suspensions[1],
+                SuspensionSpring::new(stiffness, damping, preload)
+            ),
+            Err(Error::InvalidSuspensionSpring { .. })
+        ));
+    }
+
+    assert_eq!(
+        Some(spring),
+        model.remove_suspension_spring(&suspensions[0])
+    );
+    assert_eq!(None, model.remove_suspension_spring(&suspensions[0]));
+
+    model
+        .set_suspension_spring(&suspensions[1], spring)
+        .unwrap();
+    model.remove_element(&suspensions[1]).unwrap();
+    assert_eq!(None, model.suspension_spring(&suspensions[1]));
+}
+
+#[test]
+fn when_computing_the_static_deflection_it_should_distribute_the_weight() {
+    // The center of mass of the vehicle is 0.375 m in front of the body origin, which puts
+    // 0.34375 of the weight on each of the front wheels and 0.15625 on each of the rear wheels
+    let (mut model, suspensions) = create_model(
+        FrameDofType::PrismaticZ,
+        Vector3::new(0.5, 0.0, 0.0),
+        JointConstraint::new().with_position_limits(-0.1, 0.02),
+    );
+    let weight = 48.0 * model.gravity().norm();
+    let (front_load, rear_load) = (0.34375 * weight, 0.15625 * weight);
+
+    let springs = [
+        SuspensionSpring::new(10_000.0, 0.0, 0.0),
+        SuspensionSpring::new(10_000.0, 0.0, 50.0),
+        SuspensionSpring::new(1_000.0, 0.0, 0.0),
+        SuspensionSpring::new(10_000.0, 0.0, 2.0 * rear_load),
+    ];
+    for (suspension, spring) in suspensions.iter().zip(springs) {
+        model.set_suspension_spring(suspension, spring).unwrap();
+    }
+
+    let deflection = model.static_suspension_deflection().unwrap();
+    assert_eq!(4, deflection.len());
+    assert!((deflection[&suspensions[0]] - front_load / 10_000.0).abs() < 1e-12);
+
+    // The preload is subtracted from the load
+    assert!((deflection[&suspensions[1]] - (front_load - 50.0) / 10_000.0).abs() < 1e-12);
+
+    // The soft spring is clamped to its limit and the preload exceeds the load
+    assert_eq!(0.02, deflection[&suspensions[2]]);
+    assert_eq!(0.0, deflection[&suspensions[3]]);
+}
+
+#[test]
+fn when_a_revolute_suspension_is_loaded_it_should_rotate_in_the_direction_that_lifts_the_wheel() {
+    // The suspensions rotate around the y-axis through the body origin, so a positive rotation
+    // lowers the front wheels and lifts the rear wheels
+    let (mut model, suspensions) = create_model(
+        FrameDofType::RevoluteY,
+        Vector3::zeros(),
+        JointConstraint::new(),
+    );
+    for suspension in &suspensions {
+        model
+            .set_suspension_spring(suspension, SuspensionSpring::new(5_000.0, 0.0, 0.0))
+            .unwrap();
+    }
+
+    let deflection = model.static_suspension_deflection().unwrap();
+    let load = 12.0 * model.gravity().norm();
+
+    // The contact points are 1 m in front of, or behind, the axis of rotation
+    let expected = load / 5_000.0;
+    assert!((deflection[&suspensions[0]] + expected).abs() < 1e-12);
+    assert!((deflection[&suspensions[3]] - expected).abs() < 1e-12);
+}
+
+#[test]
+fn when_the_position_limits_are_not_ordered_it_should_not_panic() {
+    let (mut model, suspensions) = create_model(
+        FrameDofType::PrismaticZ,
+        Vector3::zeros(),
+        JointConstraint::new().with_position_limits(0.05, -0.05),
+    );
+    for suspension in &suspensions {
+        model
+            .set_suspension_spring(suspension, SuspensionSpring::new(1_000.0, 0.0, 0.0))
+            .unwrap();
+    }
+
+    let deflection = model.static_suspension_deflection().unwrap();
+    assert_eq!(4, deflection.len());
+    assert!(deflection.values().all(|position| position.is_finite()));
+}
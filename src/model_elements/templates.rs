@@ -0,0 +1,203 @@
+//! Provides templates for common vehicle layouts.
+//!
+//! Most swerve vehicles are symmetric, with the wheel modules placed in pairs on the left and
+//! right side of the body. Instead of describing each module separately,
+//! [MotionModelBuilder::rectangular_swerve] creates a [MotionModelBuilder] that contains the body
+//! and all the modules of such a vehicle, based on a single [ModuleTemplate].
+//!
+//! The modules of a template are named after their position on the vehicle. The first row of
+//! modules is the `front` row, the last row is the `rear` row and the rows in between are the
+//! `middle_1`, `middle_2`, ... rows, counting from the front. Each row has a `left` and a `right`
+//! module, e.g. `front_left` and `rear_right`. The frames of the modules are named after the
+//! modules, see [MotionModel::add_drive_module](super::model::MotionModel::add_drive_module).
+
+use nalgebra::{Translation3, UnitQuaternion};
+
+use crate::Error;
+
+use super::{
+    model::{ChassisElementPhysicalProperties, WheelProperties},
+    module_spec::{BodySpec, ModuleSpec, MotionModelBuilder},
+};
+
+#[cfg(test)]
+#[path = "templates_tests.rs"]
+mod templates_tests;
+
+/// Describes the properties that are shared by all the wheel modules of a template.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ModuleTemplate {
+    /// The geometry of the wheels.
+    wheel_properties: WheelProperties,
+
+    /// The physical properties of the steering frames.
+    steering_physical_properties: ChassisElementPhysicalProperties,
+
+    /// The physical properties of the wheels.
+    wheel_physical_properties: ChassisElementPhysicalProperties,
+
+    /// The position of the center of the wheel relative to the steering frame.
+    wheel_offset: Translation3<f64>,
+}
+
+impl ModuleTemplate {
+    /// Creates a new [ModuleTemplate] instance for modules in which the center of the wheel is
+    /// at the origin of the steering frame.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'wheel_properties' - The geometry of the wheels.
+    /// * 'steering_physical_properties' - The mass and inertia of each steering frame.
+    /// * 'wheel_physical_properties' - The mass and inertia of each wheel.
+    pub fn new(
+        wheel_properties: WheelProperties,
+        steering_physical_properties: ChassisElementPhysicalProperties,
+        wheel_physical_properties: ChassisElementPhysicalProperties,
+    ) -> Self {
+        Self {
+            wheel_properties,
+            steering_physical_properties,
+            wheel_physical_properties,
+            wheel_offset: Translation3::identity(),
+        }
+    }
+
+    /// Returns the template with the center of the wheel at the given position relative to the
+    /// steering frame.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'offset' - The position of the center of the wheel relative to the steering frame.
+    pub fn with_wheel_offset(mut self, offset: Translation3<f64>) -> Self {
+        self.wheel_offset = offset;
+        self
+    }
+
+    /// Returns the [ModuleSpec] for a module with the given name at the given position.
+    fn module_spec(&self, name: String, position: Translation3<f64>) -> ModuleSpec {
+        ModuleSpec::new(
+            name,
+            position,
+            UnitQuaternion::identity(),
+            self.wheel_properties,
+            self.steering_physical_properties,
+            self.wheel_physical_properties,
+        )
+        .with_wheel_offset(self.wheel_offset)
+    }
+}
+
+impl MotionModelBuilder {
+    /// Creates a builder for a vehicle with the wheel modules placed symmetrically on a
+    /// rectangle that is centered on the origin of the body.
+    ///
+    /// The modules are placed in rows of two, one on the left side (positive y) and one on the
+    /// right side (negative y) of the body. The front row is placed at half the wheelbase in
+    /// front of the body (positive x), the rear row at half the wheelbase behind the body and
+    /// any other rows are spaced evenly in between. Within each row the left module is added
+    /// before the right module and the rows are added from front to rear. The body is named
+    /// `body` and is placed at the origin of the world.
+    ///
+    /// The actuators of the modules can be connected once the model is built, e.g. with
+    /// [MotionModel::set_actuator](super::model::MotionModel::set_actuator).
+    ///
+    /// ## Parameters
+    ///
+    /// * 'number_of_modules' - The number of wheel modules. Must be an even number of at least
+    ///   four.
+    /// * 'wheelbase' - The distance between the front and the rear row of modules.
+    /// * 'track_width' - The distance between the left and the right module of a row.
+    /// * 'body_physical_properties' - The mass and inertia of the body.
+    /// * 'module' - The properties that are shared by all the wheel modules.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::InvalidTemplate] - Returned when the number of modules is odd or smaller than
+    ///   four, or when the wheelbase or the track width is not a finite, positive number.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use nalgebra::{Matrix3, Vector3};
+    /// use swerve_vehicle_descriptors::model_elements::{
+    ///     model::{ChassisElementPhysicalProperties, WheelProperties},
+    ///     module_spec::MotionModelBuilder,
+    ///     templates::ModuleTemplate,
+    /// };
+    ///
+    /// let properties = || {
+    ///     ChassisElementPhysicalProperties::from_mass_properties(
+    ///         1.0,
+    ///         Vector3::zeros(),
+    ///         Matrix3::identity(),
+    ///     )
+    /// };
+    /// let module = ModuleTemplate::new(
+    ///     WheelProperties::new(0.1, 0.05, Vector3::zeros()),
+    ///     properties(),
+    ///     properties(),
+    /// );
+    ///
+    /// let model = MotionModelBuilder::rectangular_swerve(4, 2.0, 1.0, properties(), &module)
+    ///     .unwrap()
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// let names: Vec<_> = model.drive_modules().map(|m| m.name()).collect();
+    /// assert_eq!(
+    ///     vec!["front_left", "front_right", "rear_left", "rear_right"],
+    ///     names
+    /// );
+    /// ```
+    pub fn rectangular_swerve(
+        number_of_modules: usize,
+        wheelbase: f64,
+        track_width: f64,
+        body_physical_properties: ChassisElementPhysicalProperties,
+        module: &ModuleTemplate,
+    ) -> Result<Self, Error> {
+        let invalid = |reason: &str| Error::InvalidTemplate {
+            reason: reason.to_string(),
+        };
+
+        if number_of_modules < 4 || number_of_modules % 2 != 0 {
+            return Err(invalid(
+                "the number of modules should be an even number of at least four",
+            ));
+        }
+
+        if !wheelbase.is_finite() || wheelbase <= 0.0 {
+            return Err(invalid("the wheelbase should be a positive number"));
+        }
+
+        if !track_width.is_finite() || track_width <= 0.0 {
+            return Err(invalid("the track width should be a positive number"));
+        }
+
+        let mut builder = Self::default().body(BodySpec::new(
+            "body".to_string(),
+            Translation3::identity(),
+            UnitQuaternion::identity(),
+            body_physical_properties,
+        ));
+
+        let rows = number_of_modules / 2;
+        let row_spacing = wheelbase / (rows - 1) as f64;
+        for row in 0..rows {
+            let row_name = match row {
+                0 => "front".to_string(),
+                r if r == rows - 1 => "rear".to_string(),
+                r => format!("middle_{}", r),
+            };
+            let x = 0.5 * wheelbase - row as f64 * row_spacing;
+            for (side, y) in [("left", 0.5 * track_width), ("right", -0.5 * track_width)] {
+                builder = builder.drive_module(module.module_spec(
+                    format!("{}_{}", row_name, side),
+                    Translation3::new(x, y, 0.0),
+                ));
+            }
+        }
+
+        Ok(builder)
+    }
+}
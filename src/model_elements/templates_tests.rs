@@ -0,0 +1,125 @@
+use nalgebra::{Translation3, Vector3};
+
+use crate::{
+    model_elements::{model::WheelProperties, module_spec::MotionModelBuilder},
+    test_fixtures::physical_properties,
+    Error,
+};
+
+use super::ModuleTemplate;
+
+fn module_template() -> ModuleTemplate {
+    ModuleTemplate::new(
+        WheelProperties::new(0.1, 0.05, Vector3::zeros()),
+        physical_properties(),
+        physical_properties(),
+    )
+    .with_wheel_offset(Translation3::new(0.0, 0.0, -0.1))
+}
+
+#[test]
+fn when_creating_a_rectangular_swerve_it_should_place_the_modules_symmetrically() {
+    let model = MotionModelBuilder::rectangular_swerve(
+        4,
+        2.0,
+        1.0,
+        physical_properties(),
+        &module_template(),
+    )
+    .unwrap()
+    .build()
+    .unwrap();
+    assert_eq!(4, model.number_of_wheels());
+
+    let expected = [
+        ("front_left", 1.0, 0.5),
+        ("front_right", 1.0, -0.5),
+        ("rear_left", -1.0, 0.5),
+        ("rear_right", -1.0, -0.5),
+    ];
+    for (module, (name, x, y)) in model.drive_modules().zip(expected) {
+        assert_eq!(name, module.name());
+        assert_eq!(Ok(0.1), module.wheel_properties().map(|p| p.radius()));
+        assert_eq!(
+            format!("{}_wheel", name),
+            model
+                .reference_frame(module.frames().wheel())
+                .unwrap()
+                .name()
+        );
+
+        let wheel_to_body = model
+            .homogeneous_transform_to_body(module.frames().wheel())
+            .unwrap();
+        assert!((x - wheel_to_body[(0, 3)]).abs() < 1e-12);
+        assert!((y - wheel_to_body[(1, 3)]).abs() < 1e-12);
+        assert!((-0.1 - wheel_to_body[(2, 3)]).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn when_creating_a_rectangular_swerve_with_more_rows_it_should_space_the_rows_evenly() {
+    let model = MotionModelBuilder::rectangular_swerve(
+        8,
+        3.0,
+        1.0,
+        physical_properties(),
+        &module_template(),
+    )
+    .unwrap()
+    .build()
+    .unwrap();
+
+    let modules: Vec<_> = model
+        .drive_modules()
+        .map(|m| {
+            let transform = model
+                .homogeneous_transform_to_body(m.frames().steering())
+                .unwrap();
+            (m.name().to_string(), transform[(0, 3)])
+        })
+        .collect();
+    let names: Vec<_> = modules.iter().map(|(name, _)| name.as_str()).collect();
+    assert_eq!(
+        vec![
+            "front_left",
+            "front_right",
+            "middle_1_left",
+            "middle_1_right",
+            "middle_2_left",
+            "middle_2_right",
+            "rear_left",
+            "rear_right"
+        ],
+        names
+    );
+
+    for ((_, x), expected) in modules
+        .iter()
+        .zip([1.5, 1.5, 0.5, 0.5, -0.5, -0.5, -1.5, -1.5])
+    {
+        assert!((expected - x).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn when_creating_a_rectangular_swerve_with_invalid_dimensions_it_should_error() {
+    let template = module_template();
+    for (count, wheelbase, track_width) in [
+        (2, 2.0, 1.0),
+        (5, 2.0, 1.0),
+        (4, 0.0, 1.0),
+        (4, 2.0, f64::NAN),
+    ] {
+        assert!(matches!(
+            MotionModelBuilder::rectangular_swerve(
+                count,
+                wheelbase,
+                track_width,
+                physical_properties(),
+                &template
+            ),
+            Err(Error::InvalidTemplate { .. })
+        ));
+    }
+}
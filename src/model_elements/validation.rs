@@ -0,0 +1,222 @@
+//! Defines the types used to describe the issues that prevent a
+//! [MotionModel](super::model::MotionModel) from describing a valid swerve vehicle.
+//!
+//! The model is checked with [MotionModel::validate](super::model::MotionModel::validate), which
+//! returns a [ValidationReport] that contains a [ValidationIssue] for each problem that was
+//! found. Each issue carries the [FrameID] of the offending frame element, if there is one, so
+//! that callers can react to specific issues without having to parse the description of the
+//! issue.
+
+use std::fmt;
+
+use super::frame_elements::{FrameDofType, FrameID};
+
+#[cfg(test)]
+#[path = "validation_tests.rs"]
+mod validation_tests;
+
+/// Defines the issues that prevent a [MotionModel](super::model::MotionModel) from describing a
+/// valid swerve vehicle.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ValidationIssue {
+    /// Indicates that the moment of inertia of a frame element is not symmetric.
+    AsymmetricInertia {
+        /// The ID of the frame element.
+        frame: FrameID,
+    },
+
+    /// Indicates that the spatial inertia of a frame element does not match the mass, the
+    /// center of mass and the moment of inertia of the element.
+    InconsistentSpatialInertia {
+        /// The ID of the frame element.
+        frame: FrameID,
+    },
+
+    /// Indicates that a steering frame does not have a revolute degree of freedom.
+    InvalidSteeringDegreeOfFreedom {
+        /// The ID of the steering frame.
+        frame: FrameID,
+
+        /// The degree of freedom of the steering frame.
+        degree_of_freedom: FrameDofType,
+    },
+
+    /// Indicates that a wheel does not rotate around its y-axis.
+    InvalidWheelDegreeOfFreedom {
+        /// The ID of the wheel frame.
+        frame: FrameID,
+
+        /// The degree of freedom of the wheel frame.
+        degree_of_freedom: FrameDofType,
+    },
+
+    /// Indicates that the current joint positions do not satisfy a loop closure constraint.
+    LoopClosureViolated {
+        /// The ID of the first frame of the constraint.
+        frame: FrameID,
+
+        /// The ID of the second frame of the constraint.
+        other: FrameID,
+
+        /// The amount by which the constraint is violated, see
+        /// [MotionModel::loop_closure_error](super::model::MotionModel::loop_closure_error).
+        error: f64,
+    },
+
+    /// Indicates that an actuated frame element does not have an actuator.
+    MissingActuator {
+        /// The ID of the frame element without an actuator.
+        frame: FrameID,
+    },
+
+    /// Indicates that the model does not have a body.
+    MissingBody,
+
+    /// Indicates that a wheel does not have a steering frame.
+    MissingSteering {
+        /// The ID of the wheel frame.
+        frame: FrameID,
+    },
+
+    /// Indicates that a steering frame is not connected to a wheel.
+    MissingWheel {
+        /// The ID of the steering frame.
+        frame: FrameID,
+    },
+
+    /// Indicates that there is more than one steering frame in the chain from a wheel to the
+    /// body.
+    MultipleSteering {
+        /// The ID of the wheel frame.
+        frame: FrameID,
+    },
+
+    /// Indicates that the mass of a frame element is zero, negative or not a number.
+    NonPositiveMass {
+        /// The ID of the frame element.
+        frame: FrameID,
+    },
+
+    /// Indicates that the moment of inertia of a frame element is not positive definite.
+    SingularInertia {
+        /// The ID of the frame element.
+        frame: FrameID,
+    },
+
+    /// Indicates that the model does not have enough wheels.
+    TooFewWheels {
+        /// The number of wheels in the model.
+        number_of_wheels: usize,
+    },
+
+    /// Indicates that a frame element is not connected to the body, either directly or
+    /// through its ancestors.
+    UnreachableFrame {
+        /// The ID of the frame element.
+        frame: FrameID,
+    },
+
+    /// Indicates that the center of a wheel is at the origin of its steering frame.
+    ZeroWheelOffset {
+        /// The ID of the wheel frame.
+        frame: FrameID,
+    },
+}
+
+impl ValidationIssue {
+    /// Returns the [FrameID] of the frame element that causes the issue, or [None] if the issue
+    /// applies to the model as a whole.
+    pub fn frame(&self) -> Option<&FrameID> {
+        match self {
+            ValidationIssue::AsymmetricInertia { frame } => Some(frame),
+            ValidationIssue::InconsistentSpatialInertia { frame } => Some(frame),
+            ValidationIssue::InvalidSteeringDegreeOfFreedom { frame, .. } => Some(frame),
+            ValidationIssue::InvalidWheelDegreeOfFreedom { frame, .. } => Some(frame),
+            ValidationIssue::LoopClosureViolated { frame, .. } => Some(frame),
+            ValidationIssue::MissingActuator { frame } => Some(frame),
+            ValidationIssue::MissingBody => None,
+            ValidationIssue::MissingSteering { frame } => Some(frame),
+            ValidationIssue::MissingWheel { frame } => Some(frame),
+            ValidationIssue::MultipleSteering { frame } => Some(frame),
+            ValidationIssue::NonPositiveMass { frame } => Some(frame),
+            ValidationIssue::SingularInertia { frame } => Some(frame),
+            ValidationIssue::TooFewWheels { .. } => None,
+            ValidationIssue::UnreachableFrame { frame } => Some(frame),
+            ValidationIssue::ZeroWheelOffset { frame } => Some(frame),
+        }
+    }
+}
+
+impl fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValidationIssue::AsymmetricInertia { frame } => write!(f, "Swerve model expects each element to have a symmetric moment of inertia. Element {} has an asymmetric moment of inertia.", frame),
+            ValidationIssue::InconsistentSpatialInertia { frame } => write!(f, "Swerve model expects the spatial inertia of each element to match its mass, center of mass and moment of inertia. Element {} has an inconsistent spatial inertia.", frame),
+            ValidationIssue::InvalidSteeringDegreeOfFreedom { frame, degree_of_freedom } => write!(f, "Swerve model expects steering joints to have a revolute degree of freedom. Steering joint {} has degree of freedom: {:#?}.", frame, degree_of_freedom),
+            ValidationIssue::InvalidWheelDegreeOfFreedom { frame, degree_of_freedom } => write!(f, "Swerve model expects wheels to rotate around the y-axis. Wheel {} has degree of freedom: {:#?}.", frame, degree_of_freedom),
+            ValidationIssue::LoopClosureViolated { frame, other, error } => write!(f, "Swerve model expects the loop closure constraints to be satisfied. The constraint between elements {} and {} is violated by {}.", frame, other, error),
+            ValidationIssue::MissingActuator { frame } => write!(f, "Swerve model expects each actuated joint to have an actuator. Joint {} does not have an actuator.", frame),
+            ValidationIssue::MissingBody => write!(f, "Swerve model expects a body frame. Found no body frame."),
+            ValidationIssue::MissingSteering { frame } => write!(f, "Swerve model expects one steering frame for each wheel. Wheel {} does not have a steering frame.", frame),
+            ValidationIssue::MissingWheel { frame } => write!(f, "Swerve model expects each steering joint to be connected to a wheel. Steering joint {} is not connected to a wheel.", frame),
+            ValidationIssue::MultipleSteering { frame } => write!(f, "Swerve model expects one steering frame for each wheel. Wheel {} has more than one steering frame.", frame),
+            ValidationIssue::NonPositiveMass { frame } => write!(f, "Swerve model expects each element to have a positive mass. Element {} does not have a positive mass.", frame),
+            ValidationIssue::SingularInertia { frame } => write!(f, "Swerve model expects each element to have a positive definite moment of inertia. Element {} has a singular moment of inertia.", frame),
+            ValidationIssue::TooFewWheels { number_of_wheels } => write!(f, "Swerve model needs at least 2 wheels. Found {} wheels.", number_of_wheels),
+            ValidationIssue::UnreachableFrame { frame } => write!(f, "Swerve model expects each element to be connected to the body. Element {} is not connected to the body.", frame),
+            ValidationIssue::ZeroWheelOffset { frame } => write!(f, "Swerve model expects each wheel to be offset from its steering frame. Wheel {} is located at the origin of its steering frame.", frame),
+        }
+    }
+}
+
+/// Describes the result of validating a [MotionModel](super::model::MotionModel).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ValidationReport {
+    /// The issues that were found, in the order in which they were found.
+    issues: Vec<ValidationIssue>,
+}
+
+impl ValidationReport {
+    /// Returns a value indicating whether the model is valid, i.e. whether no issues were found.
+    pub fn is_valid(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// Returns the issues that were found.
+    pub fn issues(&self) -> &[ValidationIssue] {
+        &self.issues
+    }
+
+    /// Returns the issues that are caused by the given frame element.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'frame_id' - The ID of the frame element.
+    pub fn issues_for_frame<'a>(
+        &'a self,
+        frame_id: &'a FrameID,
+    ) -> impl Iterator<Item = &'a ValidationIssue> + 'a {
+        self.issues
+            .iter()
+            .filter(move |issue| issue.frame() == Some(frame_id))
+    }
+
+    /// Returns the descriptions of the issues that were found.
+    pub fn messages(&self) -> Vec<String> {
+        self.issues.iter().map(|issue| issue.to_string()).collect()
+    }
+
+    /// Creates a new [ValidationReport] instance.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'issues' - The issues that were found.
+    pub fn new(issues: Vec<ValidationIssue>) -> Self {
+        #[cfg(feature = "tracing")]
+        for issue in &issues {
+            tracing::warn!(frame = ?issue.frame(), issue = %issue, "The model is not valid");
+        }
+
+        Self { issues }
+    }
+}
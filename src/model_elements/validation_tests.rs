@@ -0,0 +1,309 @@
+use nalgebra::{Matrix3, Matrix6, Translation3, UnitQuaternion, Vector3};
+
+use crate::{
+    change_notification_processing::HardwareChangeProcessor,
+    model_elements::{
+        frame_elements::{Actuator, FrameDofType},
+        model::{ChassisElementPhysicalProperties, MotionModel, WheelProperties},
+        model_event::ModelEvent,
+        module_spec::{BodySpec, ModuleFrames, ModuleSpec},
+    },
+    number_space::NumberSpaceType,
+    test_support::MockActuator,
+};
+
+use super::{ValidationIssue, ValidationReport};
+
+fn physical_properties(
+    mass: f64,
+    moment_of_inertia: Matrix3<f64>,
+) -> ChassisElementPhysicalProperties {
+    ChassisElementPhysicalProperties::from_mass_properties(
+        mass,
+        Vector3::<f64>::zeros(),
+        moment_of_inertia,
+    )
+}
+
+// Creates a model with a body and the given number of modules. The modules have actuators if
+// a change processor is provided.
+fn create_model(
+    body_properties: ChassisElementPhysicalProperties,
+    number_of_modules: usize,
+    change_processor: Option<&HardwareChangeProcessor>,
+) -> (MotionModel, Vec<ModuleFrames>) {
+    let body = BodySpec::new(
+        "body".to_string(),
+        Translation3::identity(),
+        UnitQuaternion::identity(),
+        body_properties,
+    );
+
+    let modules = (0..number_of_modules)
+        .map(|i| {
+            let module = ModuleSpec::new(
+                format!("module_{}", i),
+                Translation3::new(1.0, i as f64, 0.0),
+                UnitQuaternion::identity(),
+                WheelProperties::new(0.1, 0.05, Vector3::zeros()),
+                physical_properties(1.0, Matrix3::identity()),
+                physical_properties(1.0, Matrix3::identity()),
+            )
+            .with_wheel_offset(Translation3::new(0.0, 0.0, -0.2));
+            match change_processor {
+                Some(processor) => module
+                    .with_steering_actuator(actuator(processor))
+                    .with_wheel_actuator(actuator(processor)),
+                None => module,
+            }
+        })
+        .collect();
+
+    MotionModel::with_modules(body, modules).unwrap()
+}
+
+fn actuator(change_processor: &HardwareChangeProcessor) -> Actuator {
+    let mut hardware = MockActuator::new(NumberSpaceType::LinearUnlimited);
+    Actuator::new(&mut hardware, change_processor).unwrap()
+}
+
+#[test]
+fn when_validating_a_valid_model_it_should_report_no_issues() {
+    let change_processor = HardwareChangeProcessor::new(10);
+    let (model, _) = create_model(
+        physical_properties(10.0, Matrix3::identity()),
+        2,
+        Some(&change_processor),
+    );
+
+    let report = model.validate();
+    assert!(report.is_valid(), "{:?}", report.messages());
+    assert!(report.issues().is_empty());
+    assert!(report.messages().is_empty());
+}
+
+#[test]
+fn when_validating_an_empty_model_it_should_report_the_missing_body() {
+    let model = MotionModel::new();
+
+    let report = model.validate();
+    assert!(!report.is_valid());
+    assert_eq!(
+        &[
+            ValidationIssue::MissingBody,
+            ValidationIssue::TooFewWheels {
+                number_of_wheels: 0
+            }
+        ],
+        report.issues()
+    );
+    assert!(report.issues().iter().all(|issue| issue.frame().is_none()));
+}
+
+#[test]
+fn when_validating_a_model_without_actuators_it_should_report_each_frame() {
+    let (model, frames) = create_model(physical_properties(10.0, Matrix3::identity()), 1, None);
+
+    let report = model.validate();
+    assert!(!report.is_valid());
+    assert_eq!(3, report.issues().len());
+    assert!(report.issues().contains(&ValidationIssue::TooFewWheels {
+        number_of_wheels: 1
+    }));
+
+    let steering_issues: Vec<_> = report.issues_for_frame(frames[0].steering()).collect();
+    assert_eq!(
+        vec![&ValidationIssue::MissingActuator {
+            frame: *frames[0].steering()
+        }],
+        steering_issues
+    );
+
+    let wheel_issues: Vec<_> = report.issues_for_frame(frames[0].wheel()).collect();
+    assert_eq!(
+        vec![&ValidationIssue::MissingActuator {
+            frame: *frames[0].wheel()
+        }],
+        wheel_issues
+    );
+}
+
+#[test]
+fn when_validating_a_model_with_invalid_physical_properties_it_should_report_the_element() {
+    let change_processor = HardwareChangeProcessor::new(10);
+    let (model, _) = create_model(
+        physical_properties(0.0, Matrix3::zeros()),
+        2,
+        Some(&change_processor),
+    );
+    let body = *model.body().unwrap();
+
+    let report = model.validate();
+    assert_eq!(
+        &[
+            ValidationIssue::NonPositiveMass { frame: body },
+            ValidationIssue::SingularInertia { frame: body }
+        ],
+        report.issues()
+    );
+
+    let (model, _) = create_model(
+        physical_properties(f64::NAN, Matrix3::from_diagonal_element(-1.0)),
+        2,
+        Some(&change_processor),
+    );
+    let body = *model.body().unwrap();
+    assert_eq!(
+        &[
+            ValidationIssue::NonPositiveMass { frame: body },
+            ValidationIssue::SingularInertia { frame: body },
+            ValidationIssue::InconsistentSpatialInertia { frame: body }
+        ],
+        model.validate().issues()
+    );
+}
+
+#[test]
+fn when_validating_a_model_with_inconsistent_inertia_it_should_report_the_element() {
+    let change_processor = HardwareChangeProcessor::new(10);
+    let mut moment_of_inertia = Matrix3::<f64>::identity();
+    moment_of_inertia[(0, 1)] = 0.5;
+    let (model, _) = create_model(
+        physical_properties(10.0, moment_of_inertia),
+        2,
+        Some(&change_processor),
+    );
+    let body = *model.body().unwrap();
+    assert_eq!(
+        &[ValidationIssue::AsymmetricInertia { frame: body }],
+        model.validate().issues()
+    );
+
+    // The spatial inertia of a point mass at the origin does not depend on the moment of inertia
+    let (model, _) = create_model(
+        ChassisElementPhysicalProperties::new(
+            10.0,
+            Vector3::new(0.1, 0.0, 0.0),
+            Matrix3::identity(),
+            Matrix6::identity() * 10.0,
+        ),
+        2,
+        Some(&change_processor),
+    );
+    let body = *model.body().unwrap();
+    assert_eq!(
+        &[ValidationIssue::InconsistentSpatialInertia { frame: body }],
+        model.validate().issues()
+    );
+}
+
+#[test]
+fn when_validating_a_model_with_a_wheel_at_the_steering_origin_it_should_report_the_wheel() {
+    let change_processor = HardwareChangeProcessor::new(10);
+    let body = BodySpec::new(
+        "body".to_string(),
+        Translation3::identity(),
+        UnitQuaternion::identity(),
+        physical_properties(10.0, Matrix3::identity()),
+    );
+    let modules = [0.5, -0.5]
+        .iter()
+        .map(|y| {
+            ModuleSpec::new(
+                "module".to_string(),
+                Translation3::new(1.0, *y, 0.0),
+                UnitQuaternion::identity(),
+                WheelProperties::new(0.1, 0.05, Vector3::zeros()),
+                physical_properties(1.0, Matrix3::identity()),
+                physical_properties(1.0, Matrix3::identity()),
+            )
+            .with_steering_actuator(actuator(&change_processor))
+            .with_wheel_actuator(actuator(&change_processor))
+        })
+        .collect();
+    let (model, frames) = MotionModel::with_modules(body, modules).unwrap();
+
+    let report = model.validate();
+    assert_eq!(2, report.issues().len());
+    for frame in frames.iter() {
+        assert_eq!(
+            vec![&ValidationIssue::ZeroWheelOffset {
+                frame: *frame.wheel()
+            }],
+            report.issues_for_frame(frame.wheel()).collect::<Vec<_>>()
+        );
+    }
+}
+
+#[test]
+fn when_validating_a_model_with_a_steering_frame_without_wheel_it_should_report_the_frame() {
+    let change_processor = HardwareChangeProcessor::new(10);
+    let (mut model, _) = create_model(
+        physical_properties(10.0, Matrix3::identity()),
+        2,
+        Some(&change_processor),
+    );
+    let body = *model.body().unwrap();
+    let steering = model
+        .add_steering_element(
+            "steering".to_string(),
+            body,
+            Translation3::new(-1.0, 0.0, 0.0),
+            UnitQuaternion::identity(),
+            physical_properties(1.0, Matrix3::identity()),
+            actuator(&change_processor),
+        )
+        .unwrap();
+
+    let report = model.validate();
+    assert_eq!(
+        &[ValidationIssue::MissingWheel { frame: steering }],
+        report.issues()
+    );
+    assert_eq!(Some(&steering), report.issues()[0].frame());
+}
+
+#[test]
+fn when_the_validity_of_a_model_changes_it_should_notify_the_subscribers() {
+    let change_processor = HardwareChangeProcessor::new(10);
+    let (mut model, frames) = create_model(
+        physical_properties(10.0, Matrix3::identity()),
+        2,
+        Some(&change_processor),
+    );
+    let receiver = model.subscribe();
+
+    model.remove_element(frames[0].wheel()).unwrap();
+    assert!(receiver
+        .try_iter()
+        .any(|event| event == ModelEvent::ValidationChanged { is_valid: false }));
+
+    // Changes that keep the model invalid do not change the validity
+    model.remove_element(frames[1].wheel()).unwrap();
+    assert!(!receiver
+        .try_iter()
+        .any(|event| matches!(event, ModelEvent::ValidationChanged { .. })));
+}
+
+#[test]
+fn when_displaying_an_issue_it_should_describe_the_issue() {
+    let (model, frames) = create_model(physical_properties(10.0, Matrix3::identity()), 1, None);
+    let wheel = *frames[0].wheel();
+
+    let issue = ValidationIssue::InvalidWheelDegreeOfFreedom {
+        frame: wheel,
+        degree_of_freedom: FrameDofType::RevoluteZ,
+    };
+    assert_eq!(
+        format!(
+            "Swerve model expects wheels to rotate around the y-axis. Wheel {} has degree of freedom: RevoluteZ.",
+            wheel
+        ),
+        issue.to_string()
+    );
+
+    let report = ValidationReport::new(vec![issue]);
+    assert_eq!(vec![issue.to_string()], report.messages());
+    assert_eq!(1, report.issues_for_frame(&wheel).count());
+    assert_eq!(0, report.issues_for_frame(model.body().unwrap()).count());
+}
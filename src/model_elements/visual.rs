@@ -0,0 +1,269 @@
+//! Describes the visual geometry of the chassis elements of a vehicle.
+//!
+//! The visual geometry is used to render a vehicle and may be more detailed than the
+//! [collision geometry](super::collision). Each chassis element can have any number of
+//! [VisualGeometry] instances, which are added with [MotionModel::add_visual_geometry]. A visual
+//! geometry uses the same [CollisionShape] primitives and mesh references as the collision
+//! geometry, together with an optional [Material]. The visuals of all the elements, posed for the
+//! current positions of the joints, are provided by [MotionModel::visuals_in_body]. The visual
+//! and collision geometry is also written by [MotionModel::to_urdf].
+
+use nalgebra::Isometry3;
+
+use crate::Error;
+
+use super::{collision::CollisionShape, frame_elements::FrameID, model::MotionModel};
+
+#[cfg(test)]
+#[path = "visual_tests.rs"]
+mod visual_tests;
+
+/// Describes the appearance of a visual geometry.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Material {
+    /// The name of the material, e.g. `aluminium`.
+    name: String,
+
+    /// The red, green, blue and alpha components of the color, each between 0.0 and 1.0.
+    rgba: [f64; 4],
+}
+
+impl Material {
+    /// Returns the name of the material.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Creates a new [Material] instance.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'name' - The name of the material.
+    /// * 'rgba' - The red, green, blue and alpha components of the color, each between 0.0 and
+    ///   1.0.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::InvalidMaterial] - Returned when a component of the color is not between 0.0
+    ///   and 1.0.
+    pub fn new(name: String, rgba: [f64; 4]) -> Result<Self, Error> {
+        if !rgba.iter().all(|c| (0.0..=1.0).contains(c)) {
+            return Err(Error::InvalidMaterial { name });
+        }
+
+        Ok(Self { name, rgba })
+    }
+
+    /// Returns the red, green, blue and alpha components of the color.
+    pub fn rgba(&self) -> &[f64; 4] {
+        &self.rgba
+    }
+}
+
+/// Describes a shape, its [Material] and the pose of the shape relative to the reference frame
+/// of the chassis element to which it belongs.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VisualGeometry {
+    /// The shape.
+    shape: CollisionShape,
+
+    /// The transform from the frame of the shape to the reference frame of the element.
+    origin: Isometry3<f64>,
+
+    /// The material of the shape, if any.
+    material: Option<Material>,
+}
+
+impl VisualGeometry {
+    /// Returns the material of the shape, or [None] if the shape uses the default material of
+    /// the renderer, e.g. the material that is part of a mesh.
+    pub fn material(&self) -> Option<&Material> {
+        self.material.as_ref()
+    }
+
+    /// Creates a new [VisualGeometry] instance.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'shape' - The shape.
+    /// * 'origin' - The position and orientation of the shape relative to the reference frame of
+    ///   the element.
+    /// * 'material' - The material of the shape, if any.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::InvalidCollisionShape] - Returned when a dimension of the shape is not a
+    ///   finite, positive number, or when a mesh has no location.
+    pub fn new(
+        shape: CollisionShape,
+        origin: Isometry3<f64>,
+        material: Option<Material>,
+    ) -> Result<Self, Error> {
+        if !shape.has_valid_dimensions() {
+            return Err(Error::InvalidCollisionShape);
+        }
+
+        Ok(Self {
+            shape,
+            origin,
+            material,
+        })
+    }
+
+    /// Returns the transform from the frame of the shape to the reference frame of the element.
+    pub fn origin(&self) -> &Isometry3<f64> {
+        &self.origin
+    }
+
+    /// Returns the shape.
+    pub fn shape(&self) -> &CollisionShape {
+        &self.shape
+    }
+}
+
+/// Describes a [VisualGeometry] of a chassis element, posed for the positions of the joints at
+/// the time it was retrieved.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PosedVisual {
+    /// The frame of the element to which the visual belongs.
+    frame: FrameID,
+
+    /// The visual geometry, relative to the reference frame of the element.
+    geometry: VisualGeometry,
+
+    /// The transform from the frame of the shape to the body frame.
+    pose: Isometry3<f64>,
+}
+
+impl PosedVisual {
+    /// Returns the [FrameID] of the element to which the visual belongs.
+    pub fn frame(&self) -> FrameID {
+        self.frame
+    }
+
+    /// Returns the visual geometry, relative to the reference frame of the element.
+    pub fn geometry(&self) -> &VisualGeometry {
+        &self.geometry
+    }
+
+    /// Returns the transform from the frame of the shape to the body frame.
+    pub fn pose(&self) -> &Isometry3<f64> {
+        &self.pose
+    }
+}
+
+impl MotionModel {
+    /// Adds a [VisualGeometry] to the given chassis element.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'frame_id' - The [FrameID] of the element.
+    /// * 'geometry' - The visual geometry, relative to the reference frame of the element.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the element is not part of the model.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use nalgebra::{Isometry3, Matrix3, Translation3, UnitQuaternion, Vector3};
+    /// use swerve_vehicle_descriptors::model_elements::collision::CollisionShape;
+    /// use swerve_vehicle_descriptors::model_elements::model::{
+    ///     ChassisElementPhysicalProperties, MotionModel,
+    /// };
+    /// use swerve_vehicle_descriptors::model_elements::visual::{Material, VisualGeometry};
+    ///
+    /// let mut model = MotionModel::new();
+    /// let body = model
+    ///     .add_body(
+    ///         "body".to_string(),
+    ///         Translation3::identity(),
+    ///         UnitQuaternion::identity(),
+    ///         ChassisElementPhysicalProperties::from_mass_properties(
+    ///             10.0,
+    ///             Vector3::zeros(),
+    ///             Matrix3::identity(),
+    ///         ),
+    ///     )
+    ///     .unwrap();
+    ///
+    /// let visual = VisualGeometry::new(
+    ///     CollisionShape::Mesh {
+    ///         uri: "package://vehicle/meshes/body.dae".to_string(),
+    ///         scale: Vector3::repeat(1.0),
+    ///     },
+    ///     Isometry3::identity(),
+    ///     Some(Material::new("orange".to_string(), [1.0, 0.5, 0.0, 1.0]).unwrap()),
+    /// )
+    /// .unwrap();
+    /// model.add_visual_geometry(&body, visual).unwrap();
+    ///
+    /// let visuals = model.visuals_in_body().unwrap();
+    /// assert_eq!("orange", visuals[0].geometry().material().unwrap().name());
+    /// ```
+    pub fn add_visual_geometry(
+        &mut self,
+        frame_id: &FrameID,
+        geometry: VisualGeometry,
+    ) -> Result<(), Error> {
+        if !self.kinematic_tree().has_element(frame_id) {
+            return Err(Error::MissingFrameElement { id: *frame_id });
+        }
+
+        self.visual_geometries
+            .entry(*frame_id)
+            .or_default()
+            .push(geometry);
+        Ok(())
+    }
+
+    /// Removes all the visual geometry of the given chassis element and returns it.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'frame_id' - The [FrameID] of the element.
+    pub fn remove_visual_geometries(&mut self, frame_id: &FrameID) -> Vec<VisualGeometry> {
+        self.visual_geometries.remove(frame_id).unwrap_or_default()
+    }
+
+    /// Returns the visual geometry of the given chassis element, in the order in which it was
+    /// added. Elements without visual geometry return an empty slice.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'frame_id' - The [FrameID] of the element.
+    pub fn visual_geometries(&self, frame_id: &FrameID) -> &[VisualGeometry] {
+        self.visual_geometries
+            .get(frame_id)
+            .map(|geometries| geometries.as_slice())
+            .unwrap_or(&[])
+    }
+
+    /// Returns the visuals of all the chassis elements, ordered by [FrameID], with the pose of
+    /// each visual relative to the body frame for the current positions of the joints.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the model has no body.
+    pub fn visuals_in_body(&self) -> Result<Vec<PosedVisual>, Error> {
+        let mut frames: Vec<&FrameID> = self.visual_geometries.keys().collect();
+        frames.sort();
+
+        let mut visuals = Vec::new();
+        for frame_id in frames {
+            let frame_to_body = self.isometry_to_body(frame_id)?;
+            for geometry in &self.visual_geometries[frame_id] {
+                visuals.push(PosedVisual {
+                    frame: *frame_id,
+                    geometry: geometry.clone(),
+                    pose: frame_to_body * geometry.origin,
+                });
+            }
+        }
+
+        Ok(visuals)
+    }
+}
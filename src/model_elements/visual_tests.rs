@@ -0,0 +1,117 @@
+use std::f64::consts::PI;
+
+use nalgebra::{Isometry3, Translation3, UnitQuaternion, Vector3};
+
+use crate::{
+    model_elements::{collision::CollisionShape, frame_elements::FrameID, model::MotionModel},
+    test_fixtures::physical_properties,
+    Error,
+};
+
+use super::{Material, VisualGeometry};
+
+fn mesh() -> CollisionShape {
+    CollisionShape::Mesh {
+        uri: "package://vehicle/meshes/arm.dae".to_string(),
+        scale: Vector3::repeat(1.0),
+    }
+}
+
+#[test]
+fn when_creating_a_material_it_should_reject_invalid_colors() {
+    let material = Material::new("red".to_string(), [1.0, 0.0, 0.0, 0.5]).unwrap();
+    assert_eq!("red", material.name());
+    assert_eq!(&[1.0, 0.0, 0.0, 0.5], material.rgba());
+
+    for rgba in [
+        [1.5, 0.0, 0.0, 1.0],
+        [0.0, -0.1, 0.0, 1.0],
+        [0.0, 0.0, f64::NAN, 1.0],
+    ] {
+        assert_eq!(
+            Err(Error::InvalidMaterial {
+                name: "invalid".to_string()
+            }),
+            Material::new("invalid".to_string(), rgba)
+        );
+    }
+
+    assert_eq!(
+        Err(Error::InvalidCollisionShape),
+        VisualGeometry::new(
+            CollisionShape::Sphere { radius: -1.0 },
+            Isometry3::identity(),
+            None
+        )
+    );
+}
+
+#[test]
+fn when_getting_the_visuals_it_should_pose_them_in_the_body_frame() {
+    let mut model = MotionModel::new();
+    let body = model
+        .add_body(
+            "body".to_string(),
+            Translation3::identity(),
+            UnitQuaternion::identity(),
+            physical_properties(),
+        )
+        .unwrap();
+    let arm = model
+        .add_static_chassis_element(
+            "arm".to_string(),
+            body,
+            Translation3::new(1.0, 0.0, 0.0),
+            UnitQuaternion::from_euler_angles(0.0, 0.0, 0.5 * PI),
+            physical_properties(),
+        )
+        .unwrap();
+
+    let visual = VisualGeometry::new(mesh(), Isometry3::translation(0.5, 0.0, 0.0), None).unwrap();
+    model.add_visual_geometry(&arm, visual.clone()).unwrap();
+    assert_eq!(vec![visual.clone()], model.visual_geometries(&arm).to_vec());
+    assert!(model.visual_geometries(&body).is_empty());
+
+    let missing = FrameID::new();
+    assert_eq!(
+        Err(Error::MissingFrameElement { id: missing }),
+        model.add_visual_geometry(&missing, visual.clone())
+    );
+
+    let visuals = model.visuals_in_body().unwrap();
+    assert_eq!(1, visuals.len());
+    assert_eq!(arm, visuals[0].frame());
+    assert_eq!(&visual, visuals[0].geometry());
+    assert!((visuals[0].pose().translation.vector - Vector3::new(1.0, 0.5, 0.0)).norm() < 1e-12);
+
+    assert_eq!(vec![visual], model.remove_visual_geometries(&arm));
+    assert!(model.visuals_in_body().unwrap().is_empty());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn when_serializing_it_should_store_the_visual_geometry() {
+    let mut model = MotionModel::new();
+    let body = model
+        .add_body(
+            "body".to_string(),
+            Translation3::identity(),
+            UnitQuaternion::identity(),
+            physical_properties(),
+        )
+        .unwrap();
+    let material = Material::new("grey".to_string(), [0.5, 0.5, 0.5, 1.0]).unwrap();
+    model
+        .add_visual_geometry(
+            &body,
+            VisualGeometry::new(mesh(), Isometry3::identity(), Some(material)).unwrap(),
+        )
+        .unwrap();
+
+    let json = serde_json::to_string(&model).unwrap();
+    let loaded: MotionModel = serde_json::from_str(&json).unwrap();
+    assert_eq!(
+        model.visual_geometries(&body),
+        loaded.visual_geometries(&body)
+    );
+}
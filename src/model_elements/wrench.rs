@@ -0,0 +1,118 @@
+//! Defines the forces and torques that act on the vehicle from the outside.
+//!
+//! External forces, e.g. the weight of a payload or the pull of a tow bar, are attached to the
+//! reference frames of the model with [MotionModel::apply_wrench](super::model::MotionModel::apply_wrench).
+//! Gravity is not stored as a [Wrench] because it acts on all the chassis elements. It is
+//! stored as a vector in the world frame, see
+//! [MotionModel::set_gravity](super::model::MotionModel::set_gravity).
+//!
+//! Wrenches use the same ordering as the twists, i.e. the force first followed by the torque,
+//! so that the power delivered by a wrench `W` to a frame with twist `V` is `V^T W`.
+
+use std::ops::Add;
+
+use nalgebra::{Isometry3, Vector3, Vector6};
+
+#[cfg(test)]
+#[path = "wrench_tests.rs"]
+mod wrench_tests;
+
+/// The standard acceleration due to gravity, in meters per second squared.
+pub const STANDARD_GRAVITY: f64 = 9.80665;
+
+/// Describes a force and a torque that act on the origin of a reference frame. The force and
+/// the torque are expressed along the axes of the reference frame.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Wrench {
+    /// The force in Newton.
+    force: Vector3<f64>,
+
+    /// The torque in Newton meter.
+    torque: Vector3<f64>,
+}
+
+impl Wrench {
+    /// Returns the force in Newton.
+    pub fn force(&self) -> &Vector3<f64> {
+        &self.force
+    }
+
+    /// Returns the wrench of the given force when it acts on the given point instead of on the
+    /// origin of the reference frame.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'force' - The force in Newton.
+    /// * 'point' - The point at which the force acts.
+    pub fn from_force_at(force: Vector3<f64>, point: &Vector3<f64>) -> Self {
+        Self {
+            force,
+            torque: point.cross(&force),
+        }
+    }
+
+    /// Creates a new [Wrench] instance.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'force' - The force in Newton.
+    /// * 'torque' - The torque in Newton meter.
+    pub fn new(force: Vector3<f64>, torque: Vector3<f64>) -> Self {
+        Self { force, torque }
+    }
+
+    /// Returns the wrench as a 6 element vector, i.e. `[f_x, f_y, f_z, τ_x, τ_y, τ_z]`.
+    pub fn to_vector(&self) -> Vector6<f64> {
+        Vector6::new(
+            self.force.x,
+            self.force.y,
+            self.force.z,
+            self.torque.x,
+            self.torque.y,
+            self.torque.z,
+        )
+    }
+
+    /// Returns the torque in Newton meter.
+    pub fn torque(&self) -> &Vector3<f64> {
+        &self.torque
+    }
+
+    /// Returns the equivalent wrench that acts on the origin of another reference frame.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'transform' - The transform from the reference frame of the wrench to the other
+    ///   reference frame.
+    pub fn transformed(&self, transform: &Isometry3<f64>) -> Self {
+        let force = transform.rotation * self.force;
+        let torque = transform.rotation * self.torque + transform.translation.vector.cross(&force);
+        Self { force, torque }
+    }
+
+    /// Returns a wrench without force or torque.
+    pub fn zero() -> Self {
+        Self {
+            force: Vector3::zeros(),
+            torque: Vector3::zeros(),
+        }
+    }
+}
+
+impl Add for Wrench {
+    type Output = Wrench;
+
+    fn add(self, other: Wrench) -> Wrench {
+        Wrench {
+            force: self.force + other.force,
+            torque: self.torque + other.torque,
+        }
+    }
+}
+
+impl Default for Wrench {
+    fn default() -> Self {
+        Self::zero()
+    }
+}
@@ -0,0 +1,47 @@
+use std::f64::consts::PI;
+
+use nalgebra::{Isometry3, Translation3, UnitQuaternion, Vector3, Vector6};
+
+use super::Wrench;
+
+#[test]
+fn when_creating_a_wrench_from_a_force_at_a_point_it_should_include_the_moment() {
+    let wrench = Wrench::from_force_at(Vector3::new(0.0, 0.0, -10.0), &Vector3::new(1.0, 0.0, 0.0));
+    assert_eq!(&Vector3::new(0.0, 0.0, -10.0), wrench.force());
+    assert_eq!(&Vector3::new(0.0, 10.0, 0.0), wrench.torque());
+    assert_eq!(
+        Vector6::new(0.0, 0.0, -10.0, 0.0, 10.0, 0.0),
+        wrench.to_vector()
+    );
+}
+
+#[test]
+fn when_transforming_a_wrench_it_should_rotate_and_move_the_force() {
+    let wrench = Wrench::new(Vector3::new(1.0, 0.0, 0.0), Vector3::new(0.0, 0.0, 2.0));
+    let transform = Isometry3::from_parts(
+        Translation3::new(0.0, 1.0, 0.0),
+        UnitQuaternion::from_euler_angles(0.0, 0.0, 0.5 * PI),
+    );
+
+    let transformed = wrench.transformed(&transform);
+    assert!((Vector3::new(0.0, 1.0, 0.0) - transformed.force()).norm() < 1e-12);
+    assert!((Vector3::new(0.0, 0.0, 2.0) - transformed.torque()).norm() < 1e-12);
+
+    // The transformed wrench does the same work for a matching motion of the frame
+    let point = Vector3::new(0.3, -0.2, 0.1);
+    let moved = Wrench::from_force_at(*wrench.force(), &point);
+    let expected = moved.transformed(&transform);
+    let direct = Wrench::from_force_at(
+        transform.rotation * wrench.force(),
+        &(transform * nalgebra::Point3::from(point)).coords,
+    );
+    assert!((expected.to_vector() - direct.to_vector()).norm() < 1e-12);
+}
+
+#[test]
+fn when_adding_wrenches_it_should_add_the_forces_and_torques() {
+    let sum = Wrench::new(Vector3::x(), Vector3::y()) + Wrench::new(Vector3::z(), Vector3::x());
+    assert_eq!(&Vector3::new(1.0, 0.0, 1.0), sum.force());
+    assert_eq!(&Vector3::new(1.0, 1.0, 0.0), sum.torque());
+    assert_eq!(Wrench::zero(), Wrench::default());
+}
@@ -10,12 +10,24 @@
 //! go backwards from lower boundary and end up directly at the upper boundary. An example of this
 //! kind of space is a space that describes the position on a circle.
 //!
-//! Currently implemented are a linear unbounded space and an angular bounded space. The
-//! [to_number_space()] function is used to create either of these number spaces. For the angular
+//! Currently implemented are a linear unbounded space, a linear bounded space and an angular
+//! bounded space. The [to_number_space()] function is used to create any of these number spaces.
+//! A linear bounded space, created with [NumberSpaceType::LinearLimited], does not wrap around.
+//! Values outside the limits are clamped to the nearest limit, e.g. for the travel of a
+//! suspension joint that is stopped by the bump stops. A
+//! [MotionModel](crate::model_elements::model::MotionModel) only accepts a sensor with a linear
+//! bounded space if the space covers the position limits of the joint. For the angular
 //! space you can specify the starting angle in radians by creating the [NumberSpaceType::AngularLimited]
 //! value with the given starting angle. The [to_number_space()] function assumes that the
 //! angular number space is 2 * [Pi](core::f64::consts::PI) in size.
 //!
+//! For code that works with angles directly, e.g. to decide whether a wheel should steer
+//! -170 degrees or +190 degrees, the module provides the [wrap_angle()],
+//! [shortest_angular_distance()] and [interpolate_angle()] functions. These functions use the
+//! (-π, π] range, independent of the start angle of a number space. The
+//! [RealNumberValueSpace::interpolate_values] method interpolates along the shortest path in
+//! the range of a specific number space.
+//!
 
 use std::f64::consts::PI;
 
@@ -24,11 +36,22 @@ use std::f64::consts::PI;
 mod number_space_tests;
 
 /// Defines the different kinds of number spaces available.
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum NumberSpaceType {
     /// Indicates that a number space is a linear number space where numbers sequentially
     /// increase from -infinity to +infinity.
     LinearUnlimited,
 
+    /// Indicates that a number space is a linear number space where numbers sequentially
+    /// increase from the minimum to the maximum. Values outside the limits are clamped to the
+    /// nearest limit.
+    LinearLimited {
+        /// The smallest value in the number space.
+        minimum: f64,
+        /// The largest value in the number space.
+        maximum: f64,
+    },
+
     /// Indicates that a number space is an angular number space where numbers sequentially
     /// increase from the start angle to the start angle + 2 PI.
     AngularLimited {
@@ -70,6 +93,40 @@ pub trait RealNumberValueSpace {
     /// ```
     fn distance_between_values(&self, start: f64, end: f64) -> Vec<f64>;
 
+    /// Returns the value that lies the given fraction along the shortest path from the start
+    /// value to the end value, normalized to the number space.
+    ///
+    /// For a periodic number space the shortest path may cross the boundary of the space, in
+    /// which case the interpolated value wraps around.
+    ///
+    /// ## Parameters
+    ///
+    /// * `start` - The starting value.
+    /// * `end` - The ending value.
+    /// * `fraction` - The fraction of the path, where 0.0 returns the start value and 1.0 returns
+    ///   the end value.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use core::f64::consts::PI;
+    /// use swerve_vehicle_descriptors::number_space::{ NumberSpaceType, to_number_space };
+    ///
+    /// // Create a linear space
+    /// let space = to_number_space(NumberSpaceType::LinearUnlimited);
+    /// let value = space.interpolate_values(1.0, 2.0, 0.5);
+    /// assert_eq!(1.5, value);
+    ///
+    /// // Create a periodic space that starts at 0.0 and runs to 2 * PI
+    /// let space = to_number_space(NumberSpaceType::AngularLimited { start_angle_in_radians: 0.0 });
+    /// let value = space.interpolate_values(1.5 * PI, 0.5 * PI, 0.5);
+    /// assert_eq!(0.0, value);
+    /// ```
+    fn interpolate_values(&self, start: f64, end: f64, fraction: f64) -> f64 {
+        let start = self.normalize_value(start);
+        self.normalize_value(start + self.smallest_distance_between_values(start, end) * fraction)
+    }
+
     /// Returns the value in the space that is closest to the target value
     ///
     /// Normalizing the value is useful in periodic or limited number spaces.
@@ -152,6 +209,41 @@ impl RealNumberValueSpace for LinearUnboundedSpace {
     }
 }
 
+/// Defines a linear number space that is bounded by a minimum and a maximum value.
+///
+/// The linear bounded number space does not wrap around. Values outside the range are clamped
+/// to the nearest boundary, so the distance between two values is the distance between the
+/// clamped values.
+pub(crate) struct LinearBoundedSpace {
+    minimum: f64,
+    maximum: f64,
+}
+
+impl LinearBoundedSpace {
+    /// Creates a new [LinearBoundedSpace] instance. The limits are swapped if the minimum is
+    /// larger than the maximum.
+    pub fn new(minimum: f64, maximum: f64) -> LinearBoundedSpace {
+        LinearBoundedSpace {
+            minimum: minimum.min(maximum),
+            maximum: maximum.max(minimum),
+        }
+    }
+}
+
+impl RealNumberValueSpace for LinearBoundedSpace {
+    fn distance_between_values(&self, start: f64, end: f64) -> Vec<f64> {
+        vec![self.smallest_distance_between_values(start, end)]
+    }
+
+    fn normalize_value(&self, value: f64) -> f64 {
+        value.max(self.minimum).min(self.maximum)
+    }
+
+    fn smallest_distance_between_values(&self, start: f64, end: f64) -> f64 {
+        self.normalize_value(end) - self.normalize_value(start)
+    }
+}
+
 /// Defines a periodic number space that wraps around at the period.
 ///
 /// The periodic number space is used for calculations of numbers in circular
@@ -239,6 +331,48 @@ impl RealNumberValueSpace for PeriodicBoundedCircularSpace {
     }
 }
 
+/// Returns the value of the given angle along the shortest path from the start angle to the
+/// end angle, mapped onto the range (-π, π].
+///
+/// ## Parameters
+///
+/// * `start` - The starting angle in radians.
+/// * `end` - The ending angle in radians.
+/// * `fraction` - The fraction of the path, where 0.0 returns the start angle and 1.0 returns
+///   the end angle.
+///
+/// ```
+/// use core::f64::consts::PI;
+/// use swerve_vehicle_descriptors::number_space::interpolate_angle;
+///
+/// // The shortest path from 170 degrees to -170 degrees crosses 180 degrees
+/// let angle = interpolate_angle(170_f64.to_radians(), -170_f64.to_radians(), 0.5);
+/// assert!((PI - angle).abs() < 1e-9);
+/// ```
+pub fn interpolate_angle(start: f64, end: f64, fraction: f64) -> f64 {
+    wrap_angle(start + shortest_angular_distance(start, end) * fraction)
+}
+
+/// Returns the signed angle, in radians, of the shortest rotation from the start angle to the
+/// end angle. The result is in the range (-π, π], so a rotation of exactly half a turn is
+/// positive.
+///
+/// ## Parameters
+///
+/// * `start` - The starting angle in radians.
+/// * `end` - The ending angle in radians.
+///
+/// ```
+/// use swerve_vehicle_descriptors::number_space::shortest_angular_distance;
+///
+/// // Rotating +20 degrees is shorter than rotating -340 degrees
+/// let distance = shortest_angular_distance(170_f64.to_radians(), -170_f64.to_radians());
+/// assert!((20_f64.to_radians() - distance).abs() < 1e-9);
+/// ```
+pub fn shortest_angular_distance(start: f64, end: f64) -> f64 {
+    wrap_angle(end - start)
+}
+
 /// Returns a [RealNumberValueSpace] instance for the given number space type.
 ///
 /// ```
@@ -251,6 +385,11 @@ impl RealNumberValueSpace for PeriodicBoundedCircularSpace {
 /// assert!(linear_distances.len() == 1);
 /// assert_eq!(1.0, linear_distances[0]);
 ///
+/// // Create a linear space that is limited to [-0.1, 0.1]
+/// let space = to_number_space(NumberSpaceType::LinearLimited { minimum: -0.1, maximum: 0.1 });
+/// assert_eq!(0.1, space.normalize_value(0.5));
+/// assert_eq!(vec![0.2], space.distance_between_values(-0.5, 0.5));
+///
 /// // Create a periodic space that starts at 0.0 and runs to 2 * PI
 /// let space = to_number_space(NumberSpaceType::AngularLimited { start_angle_in_radians: 0.0 });
 /// let angular_distances = space.distance_between_values(0.0, PI);
@@ -261,6 +400,9 @@ impl RealNumberValueSpace for PeriodicBoundedCircularSpace {
 pub fn to_number_space(number_space_type: NumberSpaceType) -> Box<dyn RealNumberValueSpace> {
     match number_space_type {
         NumberSpaceType::LinearUnlimited => Box::new(LinearUnboundedSpace::new()),
+        NumberSpaceType::LinearLimited { minimum, maximum } => {
+            Box::new(LinearBoundedSpace::new(minimum, maximum))
+        }
         NumberSpaceType::AngularLimited {
             start_angle_in_radians,
         } => Box::new(PeriodicBoundedCircularSpace::new_with_two_pi_range(
@@ -268,3 +410,25 @@ pub fn to_number_space(number_space_type: NumberSpaceType) -> Box<dyn RealNumber
         )),
     }
 }
+
+/// Returns the given angle, in radians, mapped onto the range (-π, π].
+///
+/// ## Parameters
+///
+/// * `angle` - The angle in radians.
+///
+/// ```
+/// use core::f64::consts::PI;
+/// use swerve_vehicle_descriptors::number_space::wrap_angle;
+///
+/// assert_eq!(PI, wrap_angle(-PI));
+/// assert_eq!(-0.5 * PI, wrap_angle(1.5 * PI));
+/// ```
+pub fn wrap_angle(angle: f64) -> f64 {
+    let wrapped = angle.rem_euclid(2.0 * PI);
+    if wrapped > PI {
+        wrapped - 2.0 * PI
+    } else {
+        wrapped
+    }
+}
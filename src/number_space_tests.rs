@@ -149,6 +149,55 @@ fn test_linear_unbounded_space_smallest_distance_between_values() {
     )));
 }
 
+// Linear bounded space
+
+#[test]
+fn test_linear_bounded_space_distance_between_values() {
+    let space = LinearBoundedSpace::new(-1.0, 2.0);
+
+    assert_eq!(space.distance_between_values(0.0, 0.0), vec![0.0]);
+    assert_eq!(space.distance_between_values(0.0, 1.0), vec![1.0]);
+    assert_eq!(space.distance_between_values(1.0, -1.0), vec![-2.0]);
+
+    // Values outside the limits are clamped before the distance is computed
+    assert_eq!(space.distance_between_values(-5.0, 5.0), vec![3.0]);
+    assert_eq!(
+        space.distance_between_values(f64::INFINITY, f64::NEG_INFINITY),
+        vec![-3.0]
+    );
+    assert_eq!(space.distance_between_values(3.0, 4.0), vec![0.0]);
+}
+
+#[test]
+fn test_linear_bounded_space_normalize_value() {
+    let space = LinearBoundedSpace::new(-1.0, 2.0);
+
+    assert_eq!(space.normalize_value(0.0), 0.0);
+    assert_eq!(space.normalize_value(-1.0), -1.0);
+    assert_eq!(space.normalize_value(2.0), 2.0);
+    assert_eq!(space.normalize_value(-1.5), -1.0);
+    assert_eq!(space.normalize_value(f64::INFINITY), 2.0);
+    assert_eq!(space.normalize_value(f64::NEG_INFINITY), -1.0);
+
+    // Limits that are given in the wrong order are swapped
+    let space = LinearBoundedSpace::new(2.0, -1.0);
+    assert_eq!(space.normalize_value(3.0), 2.0);
+    assert_eq!(space.normalize_value(-3.0), -1.0);
+}
+
+#[test]
+fn test_linear_bounded_space_smallest_distance_between_values() {
+    let space = to_number_space(NumberSpaceType::LinearLimited {
+        minimum: -1.0,
+        maximum: 2.0,
+    });
+
+    assert_eq!(space.smallest_distance_between_values(0.0, 1.5), 1.5);
+    assert_eq!(space.smallest_distance_between_values(1.5, 0.0), -1.5);
+    assert_eq!(space.smallest_distance_between_values(0.0, 10.0), 2.0);
+    assert_eq!(space.smallest_distance_between_values(10.0, -10.0), -3.0);
+}
+
 // Periodic circular space
 
 #[test]
@@ -712,3 +761,84 @@ fn test_periodic_bounded_circular_space_smallest_distance_between_values_zero_to
             }
         ));
 }
+
+// Angle helpers
+
+#[test]
+fn test_wrap_angle() {
+    let margin = F64Margin {
+        ulps: 2,
+        epsilon: 1e-9,
+    };
+
+    assert!(wrap_angle(0.0).approx_eq(0.0, margin));
+    assert!(wrap_angle(PI).approx_eq(PI, margin));
+    assert!(wrap_angle(-PI).approx_eq(PI, margin));
+    assert!(wrap_angle(1.5 * PI).approx_eq(-0.5 * PI, margin));
+    assert!(wrap_angle(-3.5 * PI).approx_eq(0.5 * PI, margin));
+}
+
+#[test]
+fn test_shortest_angular_distance() {
+    let margin = F64Margin {
+        ulps: 2,
+        epsilon: 1e-9,
+    };
+
+    assert!(shortest_angular_distance(0.0, 0.5 * PI).approx_eq(0.5 * PI, margin));
+    assert!(shortest_angular_distance(0.5 * PI, 0.0).approx_eq(-0.5 * PI, margin));
+
+    // Across the boundary of the (-π, π] range
+    assert!(shortest_angular_distance(0.9 * PI, -0.9 * PI).approx_eq(0.2 * PI, margin));
+    assert!(shortest_angular_distance(-0.9 * PI, 0.9 * PI).approx_eq(-0.2 * PI, margin));
+
+    // Half a turn is always positive
+    assert!(shortest_angular_distance(0.0, PI).approx_eq(PI, margin));
+    assert!(shortest_angular_distance(PI, 0.0).approx_eq(PI, margin));
+}
+
+#[test]
+fn test_interpolate_angle() {
+    let margin = F64Margin {
+        ulps: 2,
+        epsilon: 1e-9,
+    };
+
+    assert!(interpolate_angle(0.0, 0.5 * PI, 0.5).approx_eq(0.25 * PI, margin));
+    assert!(interpolate_angle(0.9 * PI, -0.9 * PI, 0.0).approx_eq(0.9 * PI, margin));
+    assert!(interpolate_angle(0.9 * PI, -0.9 * PI, 0.5).approx_eq(PI, margin));
+    assert!(interpolate_angle(0.9 * PI, -0.9 * PI, 0.75).approx_eq(-0.95 * PI, margin));
+    assert!(interpolate_angle(0.9 * PI, -0.9 * PI, 1.0).approx_eq(-0.9 * PI, margin));
+}
+
+#[test]
+fn test_interpolate_values() {
+    let margin = F64Margin {
+        ulps: 2,
+        epsilon: 1e-9,
+    };
+
+    let space = LinearUnboundedSpace::new();
+    assert!(space
+        .interpolate_values(-1.0, 3.0, 0.25)
+        .approx_eq(0.0, margin));
+
+    let space = LinearBoundedSpace::new(-1.0, 1.0);
+    assert!(space
+        .interpolate_values(-5.0, 5.0, 0.75)
+        .approx_eq(0.5, margin));
+
+    let space = PeriodicBoundedCircularSpace::new_with_two_pi_range(0.0);
+    assert!(space
+        .interpolate_values(1.75 * PI, 0.25 * PI, 0.25)
+        .approx_eq(1.875 * PI, margin));
+    assert!(space
+        .interpolate_values(1.75 * PI, 0.25 * PI, 0.75)
+        .approx_eq(0.125 * PI, margin));
+
+    // The shortest path from 0.75 PI to -0.5 PI crosses the boundary at PI
+    let space = PeriodicBoundedCircularSpace::new_with_two_pi_range(-PI);
+    assert!(space
+        .interpolate_values(0.75 * PI, -0.5 * PI, 2.0 / 3.0)
+        .approx_eq(-0.75 * PI, margin));
+}
@@ -0,0 +1,366 @@
+//! Provides the adapters that connect a [MotionModel] to a ROS 2 system.
+//!
+//! The adapters convert the state of the model into the content of the
+//! [sensor_msgs/JointState](https://docs.ros2.org/latest/api/sensor_msgs/msg/JointState.html)
+//! and [geometry_msgs/TransformStamped](https://docs.ros2.org/latest/api/geometry_msgs/msg/TransformStamped.html)
+//! messages, and convert received joint commands into commands for the actuators of the model.
+//! The adapters do not depend on a ROS 2 client library. The application copies the content of
+//! a [JointStateMessage] or a [TransformStampedMessage] into the message type of the client
+//! library that it uses, e.g. `r2r` or `rclrs`, and publishes it. Received command messages are
+//! copied into a [JointStateMessage] and passed to [RosBridge::apply_command].
+//!
+//! The names of the joints and links match the names used by [MotionModel::to_urdf], so that
+//! the published messages can be combined with the URDF description of the model. Each joint is
+//! named after the link that it connects to its parent, followed by `_joint`.
+//!
+//! The adapters are only available when the `ros2` feature is enabled.
+
+use std::{collections::HashMap, time::Duration};
+
+use nalgebra::Isometry3;
+
+use crate::{
+    hardware::joint_state::JointState,
+    model_elements::{frame_elements::FrameID, model::MotionModel},
+    urdf::JOINT_SUFFIX,
+    Error,
+};
+
+#[cfg(test)]
+#[path = "ros2_tests.rs"]
+mod ros2_tests;
+
+/// A ROS 2 time stamp, i.e. the content of a `builtin_interfaces/Time` message.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RosTime {
+    /// The number of whole seconds.
+    pub sec: i32,
+
+    /// The number of nanoseconds in addition to the whole seconds.
+    pub nanosec: u32,
+}
+
+impl From<Duration> for RosTime {
+    /// Creates a [RosTime] from the time since the start of the clock, e.g. the time since the
+    /// UNIX epoch. Durations that do not fit in the seconds field are saturated.
+    fn from(duration: Duration) -> Self {
+        Self {
+            sec: i32::try_from(duration.as_secs()).unwrap_or(i32::MAX),
+            nanosec: duration.subsec_nanos(),
+        }
+    }
+}
+
+/// The content of a `sensor_msgs/JointState` message.
+///
+/// The position, velocity and effort arrays are either empty or have the same length as the
+/// names, as required by ROS 2.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct JointStateMessage {
+    /// The time at which the joint states were measured.
+    pub stamp: RosTime,
+
+    /// The names of the joints.
+    pub name: Vec<String>,
+
+    /// The position of each joint, in radians or meters.
+    pub position: Vec<f64>,
+
+    /// The velocity of each joint, in radians per second or meters per second.
+    pub velocity: Vec<f64>,
+
+    /// The effort of each joint. The model does not track the efforts of the joints, so the
+    /// published messages never contain efforts.
+    pub effort: Vec<f64>,
+}
+
+/// The content of a `geometry_msgs/TransformStamped` message, i.e. a single TF transform.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TransformStampedMessage {
+    /// The time at which the transform is valid.
+    pub stamp: RosTime,
+
+    /// The name of the parent link.
+    pub frame_id: String,
+
+    /// The name of the child link.
+    pub child_frame_id: String,
+
+    /// The pose of the child link in the parent link.
+    pub transform: Isometry3<f64>,
+}
+
+impl TransformStampedMessage {
+    /// Returns the rotation of the transform as a quaternion in the `[x, y, z, w]` order used by
+    /// ROS 2.
+    pub fn rotation(&self) -> [f64; 4] {
+        let rotation = self.transform.rotation;
+        [rotation.i, rotation.j, rotation.k, rotation.w]
+    }
+
+    /// Returns the translation of the transform as `[x, y, z]`.
+    pub fn translation(&self) -> [f64; 3] {
+        let translation = self.transform.translation;
+        [translation.x, translation.y, translation.z]
+    }
+}
+
+/// Converts between the state of a [MotionModel] and the content of ROS 2 messages.
+///
+/// The bridge stores the ROS 2 names of the frames of the model when it is created. Frames that
+/// are added to the model later are not known to the bridge.
+///
+/// ## Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use nalgebra::{Matrix3, Matrix6, Translation3, UnitQuaternion, Vector3};
+/// use swerve_vehicle_descriptors::model_elements::model::{ChassisElementPhysicalProperties, MotionModel};
+/// use swerve_vehicle_descriptors::ros2::RosBridge;
+///
+/// let mut model = MotionModel::new();
+/// model.add_body(
+///     "body".to_string(),
+///     Translation3::identity(),
+///     UnitQuaternion::identity(),
+///     ChassisElementPhysicalProperties::new(
+///         10.0,
+///         Vector3::zeros(),
+///         Matrix3::identity(),
+///         Matrix6::identity(),
+///     ),
+/// ).unwrap();
+///
+/// let bridge = RosBridge::new(&model).unwrap();
+/// let message = bridge.joint_state_message(&model, Duration::from_secs(5).into()).unwrap();
+/// assert!(message.name.is_empty());
+/// assert_eq!(5, message.stamp.sec);
+/// ```
+pub struct RosBridge {
+    /// The frames and the names of the joints that connect them to their parents, in the order
+    /// of the URDF description.
+    joints: Vec<(FrameID, String)>,
+
+    /// The frame for each joint name.
+    frames_by_joint: HashMap<String, FrameID>,
+
+    /// The name of the URDF link for each frame.
+    links: HashMap<FrameID, String>,
+}
+
+impl RosBridge {
+    /// Sends the joint commands in the given message to the actuators of the model.
+    ///
+    /// The positions in the message are the commanded positions. The velocities are sent with
+    /// the positions if the message contains them. The message is checked completely before any
+    /// command is sent, so either all the commands are sent or none are.
+    ///
+    /// Returns the frames of the actuators to which a command was sent, in the order of the
+    /// message.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'model' - The model that contains the actuators.
+    /// * 'message' - The received command message.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::InvalidRosMessage] - Returned when the position or velocity arrays do not match
+    ///   the names, when a name is not the name of a joint of the model or when a joint has no
+    ///   actuator.
+    /// * Any error returned by [Actuator::update_state](crate::model_elements::frame_elements::Actuator::update_state).
+    pub fn apply_command(
+        &self,
+        model: &MotionModel,
+        message: &JointStateMessage,
+    ) -> Result<Vec<FrameID>, Error> {
+        if message.position.len() != message.name.len() {
+            return Err(Error::InvalidRosMessage {
+                reason: format!(
+                    "The message has {} names but {} positions.",
+                    message.name.len(),
+                    message.position.len()
+                ),
+            });
+        }
+
+        if !message.velocity.is_empty() && message.velocity.len() != message.name.len() {
+            return Err(Error::InvalidRosMessage {
+                reason: format!(
+                    "The message has {} names but {} velocities.",
+                    message.name.len(),
+                    message.velocity.len()
+                ),
+            });
+        }
+
+        let mut frames = Vec::with_capacity(message.name.len());
+        for name in message.name.iter() {
+            let frame = self
+                .frame_for_joint(name)
+                .ok_or_else(|| Error::InvalidRosMessage {
+                    reason: format!("The model has no joint named '{}'.", name),
+                })?;
+            if !model.is_actuated(&frame) {
+                return Err(Error::InvalidRosMessage {
+                    reason: format!("The joint '{}' has no actuator.", name),
+                });
+            }
+
+            frames.push(frame);
+        }
+
+        for (index, frame) in frames.iter().enumerate() {
+            let velocity = message.velocity.get(index).copied();
+            model.actuator_for(frame)?.update_state(JointState::new(
+                message.position[index],
+                velocity,
+                None,
+                None,
+            ))?;
+        }
+
+        Ok(frames)
+    }
+
+    /// Returns the frame of the joint with the given name, or [None] if the model has no joint
+    /// with that name.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'joint_name' - The ROS 2 name of the joint.
+    pub fn frame_for_joint(&self, joint_name: &str) -> Option<FrameID> {
+        self.frames_by_joint.get(joint_name).copied()
+    }
+
+    /// Returns the name of the joint that connects the given frame to its parent, or [None] if
+    /// the frame is not known to the bridge or if it is the body.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'frame_id' - The [FrameID] of the frame.
+    pub fn joint_name(&self, frame_id: &FrameID) -> Option<&str> {
+        self.joints
+            .iter()
+            .find(|(id, _)| id == frame_id)
+            .map(|(_, name)| name.as_str())
+    }
+
+    /// Returns the joint states of all the joints that have an actuator or a sensor.
+    ///
+    /// The joint states are read from a single [snapshot](MotionModel::snapshot) of the model.
+    /// The velocities are only included if all the joints report a velocity.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'model' - The model from which the bridge was created.
+    /// * 'stamp' - The time stamp of the message.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::FailedToReadActuatorJointState] - Returned when the state of a joint could not
+    ///   be read.
+    pub fn joint_state_message(
+        &self,
+        model: &MotionModel,
+        stamp: RosTime,
+    ) -> Result<JointStateMessage, Error> {
+        let snapshot = model.snapshot()?;
+
+        let mut message = JointStateMessage {
+            stamp,
+            ..Default::default()
+        };
+        let mut velocities = Vec::new();
+        for (frame, name) in self.joints.iter() {
+            if let Some(state) = snapshot.joint_state(frame) {
+                message.name.push(name.clone());
+                message.position.push(state.position());
+                velocities.push(*state.velocity());
+            }
+        }
+
+        if let Some(velocity) = velocities.into_iter().collect::<Option<Vec<f64>>>() {
+            message.velocity = velocity;
+        }
+
+        Ok(message)
+    }
+
+    /// Returns the name of the URDF link of the given frame, or [None] if the frame is not known
+    /// to the bridge.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'frame_id' - The [FrameID] of the frame.
+    pub fn link_name(&self, frame_id: &FrameID) -> Option<&str> {
+        self.links.get(frame_id).map(|name| name.as_str())
+    }
+
+    /// Creates a new [RosBridge] for the given model.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'model' - The model for which the messages are created.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the model has no body.
+    pub fn new(model: &MotionModel) -> Result<Self, Error> {
+        let body = *model.body()?;
+        let names = model.urdf_link_names()?;
+
+        let joints: Vec<(FrameID, String)> = names
+            .iter()
+            .filter(|(id, _)| *id != body)
+            .map(|(id, name)| (*id, format!("{}{}", name, JOINT_SUFFIX)))
+            .collect();
+        let frames_by_joint = joints
+            .iter()
+            .map(|(id, name)| (name.clone(), *id))
+            .collect();
+
+        Ok(Self {
+            joints,
+            frames_by_joint,
+            links: names.into_iter().collect(),
+        })
+    }
+
+    /// Returns the TF transforms from each link to its child links.
+    ///
+    /// The transforms are computed from a single [snapshot](MotionModel::snapshot) of the model.
+    /// Each transform connects a link directly to its parent link. Unlike the URDF description
+    /// no additional links are used for revolute joints with an offset.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'model' - The model from which the bridge was created.
+    /// * 'stamp' - The time stamp of the messages.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::FailedToReadActuatorJointState] - Returned when the state of a joint could not
+    ///   be read.
+    /// * [Error::MissingFrameElement] - Returned when a frame is no longer part of the model.
+    pub fn transform_messages(
+        &self,
+        model: &MotionModel,
+        stamp: RosTime,
+    ) -> Result<Vec<TransformStampedMessage>, Error> {
+        let snapshot = model.snapshot()?;
+
+        let mut messages = Vec::with_capacity(self.joints.len());
+        for (frame, _) in self.joints.iter() {
+            let parent = model.parent_of(frame)?;
+            messages.push(TransformStampedMessage {
+                stamp,
+                frame_id: self.link_name(parent).unwrap_or_default().to_string(),
+                child_frame_id: self.link_name(frame).unwrap_or_default().to_string(),
+                transform: snapshot.isometry_to_ancestor(frame, parent)?,
+            });
+        }
+
+        Ok(messages)
+    }
+}
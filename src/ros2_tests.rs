@@ -0,0 +1,207 @@
+use std::{f64::consts::PI, time::Duration};
+
+use nalgebra::{Translation3, UnitQuaternion};
+
+use crate::{
+    change_notification_processing::HardwareChangeProcessor,
+    hardware::{
+        joint_state::{JointState, JointStateRange},
+        simulation::{SimulatedActuator, SimulatedDynamics},
+    },
+    model_elements::{
+        frame_elements::{Actuator, FrameID},
+        model::MotionModel,
+    },
+    number_space::NumberSpaceType,
+    test_fixtures::physical_properties,
+    Error,
+};
+
+use super::{JointStateMessage, RosBridge, RosTime};
+
+fn simulated_actuator(motion_type: NumberSpaceType) -> SimulatedActuator {
+    SimulatedActuator::new(
+        motion_type,
+        JointStateRange::new(
+            JointState::new(-100.0, None, None, None),
+            JointState::new(100.0, None, None, None),
+        ),
+        SimulatedDynamics::new(),
+    )
+}
+
+// A body with a single module, consisting of a steering frame at (1.0, 0.5, 0.0) and a wheel
+// 0.1 below the steering frame.
+struct TestModule {
+    model: MotionModel,
+    steering: FrameID,
+    wheel: FrameID,
+    steering_hardware: SimulatedActuator,
+    wheel_hardware: SimulatedActuator,
+    processor: HardwareChangeProcessor,
+}
+
+impl TestModule {
+    fn new() -> Self {
+        let processor = HardwareChangeProcessor::manual();
+        let mut model = MotionModel::new();
+        let body = model
+            .add_body(
+                "body".to_string(),
+                Translation3::identity(),
+                UnitQuaternion::identity(),
+                physical_properties(),
+            )
+            .unwrap();
+
+        let mut steering_hardware = simulated_actuator(NumberSpaceType::AngularLimited {
+            start_angle_in_radians: 0.0,
+        });
+        let steering = model
+            .add_steering_element(
+                "steering".to_string(),
+                body,
+                Translation3::new(1.0, 0.5, 0.0),
+                UnitQuaternion::identity(),
+                physical_properties(),
+                Actuator::new(&mut steering_hardware, &processor).unwrap(),
+            )
+            .unwrap();
+
+        let mut wheel_hardware = simulated_actuator(NumberSpaceType::LinearUnlimited);
+        let wheel = model
+            .add_wheel(
+                "wheel".to_string(),
+                steering,
+                Translation3::new(0.0, 0.0, -0.1),
+                UnitQuaternion::identity(),
+                physical_properties(),
+                Actuator::new(&mut wheel_hardware, &processor).unwrap(),
+            )
+            .unwrap();
+        processor.process_pending();
+
+        Self {
+            model,
+            steering,
+            wheel,
+            steering_hardware,
+            wheel_hardware,
+            processor,
+        }
+    }
+
+    fn step(&self) {
+        self.steering_hardware.step(Duration::from_millis(100));
+        self.wheel_hardware.step(Duration::from_millis(100));
+        self.processor.process_pending();
+    }
+}
+
+#[test]
+fn when_converting_a_duration_it_should_split_seconds_and_nanoseconds() {
+    assert_eq!(
+        RosTime {
+            sec: 12,
+            nanosec: 345_000_000
+        },
+        RosTime::from(Duration::from_millis(12_345))
+    );
+    assert_eq!(i32::MAX, RosTime::from(Duration::from_secs(u64::MAX)).sec);
+}
+
+#[test]
+fn when_creating_messages_it_should_use_the_urdf_names_and_the_model_state() {
+    let module = TestModule::new();
+    let bridge = RosBridge::new(&module.model).unwrap();
+    assert_eq!(Some("steering_joint"), bridge.joint_name(&module.steering));
+    assert_eq!(Some(module.wheel), bridge.frame_for_joint("wheel_joint"));
+    assert_eq!(None, bridge.frame_for_joint("wheel"));
+    assert_eq!(None, bridge.joint_name(module.model.body().unwrap()));
+    assert_eq!(Some("body"), bridge.link_name(module.model.body().unwrap()));
+
+    module
+        .steering_hardware
+        .set_state(JointState::new(0.5 * PI, Some(0.0), None, None));
+    module
+        .wheel_hardware
+        .set_state(JointState::new(2.0, Some(1.5), None, None));
+    module.step();
+
+    let stamp = RosTime { sec: 3, nanosec: 4 };
+    let message = bridge.joint_state_message(&module.model, stamp).unwrap();
+    assert_eq!(stamp, message.stamp);
+    assert_eq!(vec!["steering_joint", "wheel_joint"], message.name);
+    assert!((0.5 * PI - message.position[0]).abs() < 1e-12);
+    assert!((2.0 - message.position[1]).abs() < 1e-12);
+    assert_eq!(2, message.velocity.len());
+    assert!(message.effort.is_empty());
+
+    let transforms = bridge.transform_messages(&module.model, stamp).unwrap();
+    assert_eq!(2, transforms.len());
+    assert_eq!("body", transforms[0].frame_id);
+    assert_eq!("steering", transforms[0].child_frame_id);
+    // The steering motion rotates the steering frame around the origin of the body
+    let [x, y, z] = transforms[0].translation();
+    assert!((-0.5 - x).abs() < 1e-12 && (1.0 - y).abs() < 1e-12 && z.abs() < 1e-12);
+    let [x, y, z, w] = transforms[0].rotation();
+    let half_angle = 0.25 * PI;
+    assert!(x.abs() < 1e-12 && y.abs() < 1e-12);
+    assert!((half_angle.sin() - z).abs() < 1e-12 && (half_angle.cos() - w).abs() < 1e-12);
+
+    assert_eq!("steering", transforms[1].frame_id);
+    assert_eq!("wheel", transforms[1].child_frame_id);
+    let expected = module
+        .model
+        .isometry_between_frames(&module.wheel, &module.steering)
+        .unwrap();
+    assert!((expected.to_homogeneous() - transforms[1].transform.to_homogeneous()).norm() < 1e-12);
+}
+
+#[test]
+fn when_applying_a_command_it_should_drive_the_actuators() {
+    let module = TestModule::new();
+    let bridge = RosBridge::new(&module.model).unwrap();
+
+    let command = JointStateMessage {
+        name: vec!["wheel_joint".to_string(), "steering_joint".to_string()],
+        position: vec![1.0, 0.25],
+        velocity: vec![0.0, 0.0],
+        ..Default::default()
+    };
+    assert_eq!(
+        Ok(vec![module.wheel, module.steering]),
+        bridge.apply_command(&module.model, &command)
+    );
+    module.step();
+    assert!((1.0 - module.wheel_hardware.state().position()).abs() < 1e-12);
+    assert!((0.25 - module.steering_hardware.state().position()).abs() < 1e-12);
+
+    // Invalid messages are rejected without sending any command
+    let invalid_messages = [
+        JointStateMessage {
+            name: vec!["wheel_joint".to_string()],
+            ..Default::default()
+        },
+        JointStateMessage {
+            name: vec!["wheel_joint".to_string()],
+            position: vec![3.0],
+            velocity: vec![1.0, 2.0],
+            ..Default::default()
+        },
+        JointStateMessage {
+            name: vec!["wheel_joint".to_string(), "unknown_joint".to_string()],
+            position: vec![3.0, 3.0],
+            ..Default::default()
+        },
+    ];
+    for message in invalid_messages.iter() {
+        assert!(matches!(
+            bridge.apply_command(&module.model, message),
+            Err(Error::InvalidRosMessage { .. })
+        ));
+    }
+
+    module.step();
+    assert!((1.0 - module.wheel_hardware.state().position()).abs() < 1e-12);
+}
@@ -0,0 +1,400 @@
+//! Provides the functions used to export a [MotionModel] as an
+//! [SDF](http://sdformat.org/spec) model, e.g. to simulate the vehicle in Gazebo.
+//!
+//! Each frame element is exported as an SDF link with the mass, center of mass and moment of
+//! inertia of the element and with its [visual](crate::model_elements::visual) and
+//! [collision](crate::model_elements::collision) geometry. Each connection between a frame
+//! element and its parent is exported as an SDF joint. The body frame element is the canonical
+//! link of the SDF model.
+//!
+//! The [MotionModel] applies the motion of a joint in the reference frame of the parent element,
+//! i.e. a revolute joint rotates the child element around an axis that passes through the origin
+//! of the parent frame. SDF allows the pose of a joint to be specified relative to any frame,
+//! so, unlike the [URDF](crate::urdf) export, each joint is exported as a single SDF joint that
+//! is located at the origin of the parent link with its axis expressed in the parent link.
+//!
+//! The limits of the [JointConstraint](crate::model_elements::frame_elements::JointConstraint) of
+//! a joint are exported as the limits of the SDF joint. Wheels that have [WheelProperties](
+//! crate::model_elements::model::WheelProperties) but no collision geometry are given a cylinder
+//! with the radius and the width of the wheel as collision geometry. The collision geometry of
+//! the wheels uses the friction coefficients of the [WheelFriction].
+
+use std::{collections::HashMap, f64::consts::PI, fmt::Write};
+
+use nalgebra::{Isometry3, Vector3};
+
+use crate::{
+    model_elements::{collision::CollisionShape, frame_elements::FrameID, model::MotionModel},
+    urdf::{axis_for, escape, number, JOINT_SUFFIX},
+    Error,
+};
+
+#[cfg(test)]
+#[path = "sdf_tests.rs"]
+mod sdf_tests;
+
+/// The version of the SDF specification that is written.
+const SDF_VERSION: &str = "1.7";
+
+/// Describes the friction between the wheels and the ground, as used by the ODE physics engine
+/// of Gazebo.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct WheelFriction {
+    /// The friction coefficient along the first friction direction.
+    mu: f64,
+
+    /// The friction coefficient along the second friction direction.
+    mu2: f64,
+}
+
+impl WheelFriction {
+    /// Returns the friction coefficient along the first friction direction.
+    pub fn mu(&self) -> f64 {
+        self.mu
+    }
+
+    /// Returns the friction coefficient along the second friction direction.
+    pub fn mu2(&self) -> f64 {
+        self.mu2
+    }
+
+    /// Creates a new [WheelFriction] instance.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'mu' - The friction coefficient along the first friction direction.
+    /// * 'mu2' - The friction coefficient along the second friction direction.
+    pub fn new(mu: f64, mu2: f64) -> Self {
+        Self { mu, mu2 }
+    }
+}
+
+impl Default for WheelFriction {
+    fn default() -> Self {
+        Self::new(1.0, 1.0)
+    }
+}
+
+impl MotionModel {
+    /// Returns the SDF description of the model.
+    ///
+    /// The name of the model and the canonical link is the name of the body. Because SDF requires
+    /// unique link names, elements that share a name are given a numerical suffix, in the same
+    /// way as for [MotionModel::to_urdf].
+    ///
+    /// The joints are exported at their zero position. For elements with a
+    /// [JointKinematicsTable](crate::model_elements::kinematics_table::JointKinematicsTable)
+    /// the transform of the table at the zero position is used.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'wheel_friction' - The friction between the wheels and the ground.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the model has no body.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use nalgebra::{Matrix3, Translation3, UnitQuaternion, Vector3};
+    /// use swerve_vehicle_descriptors::model_elements::model::{
+    ///     ChassisElementPhysicalProperties, MotionModel,
+    /// };
+    /// use swerve_vehicle_descriptors::sdf::WheelFriction;
+    ///
+    /// let mut model = MotionModel::new();
+    /// model
+    ///     .add_body(
+    ///         "rover".to_string(),
+    ///         Translation3::identity(),
+    ///         UnitQuaternion::identity(),
+    ///         ChassisElementPhysicalProperties::from_mass_properties(
+    ///             10.0,
+    ///             Vector3::zeros(),
+    ///             Matrix3::identity(),
+    ///         ),
+    ///     )
+    ///     .unwrap();
+    ///
+    /// let sdf = model.to_sdf(&WheelFriction::default()).unwrap();
+    /// assert!(sdf.contains("<model name=\"rover\" canonical_link=\"rover\">"));
+    /// ```
+    pub fn to_sdf(&self, wheel_friction: &WheelFriction) -> Result<String, Error> {
+        let body = self.body()?;
+        let links = self.urdf_link_names()?;
+        let names: HashMap<FrameID, String> = links.iter().cloned().collect();
+        let robot_name = names.get(body).cloned().unwrap_or_default();
+
+        let mut sdf = String::new();
+        // Writing to a String never fails so the results of 'writeln!' can be ignored.
+        let _ = writeln!(sdf, "<?xml version=\"1.0\"?>");
+        let _ = writeln!(sdf, "<sdf version=\"{}\">", SDF_VERSION);
+        let _ = writeln!(
+            sdf,
+            "  <model name=\"{}\" canonical_link=\"{}\">",
+            escape(&robot_name),
+            escape(&robot_name)
+        );
+
+        for (frame_id, name) in &links {
+            let parent_name = if frame_id == body {
+                None
+            } else {
+                let parent = self.parent_of(frame_id)?;
+                names.get(parent).map(|n| n.as_str())
+            };
+
+            self.write_sdf_link(&mut sdf, frame_id, name, parent_name, wheel_friction)?;
+            if let Some(parent_name) = parent_name {
+                self.write_sdf_joint(&mut sdf, frame_id, name, parent_name)?;
+            }
+        }
+
+        let _ = writeln!(sdf, "  </model>");
+        let _ = writeln!(sdf, "</sdf>");
+        Ok(sdf)
+    }
+
+    /// Writes the SDF joint that connects the given element to its parent.
+    fn write_sdf_joint(
+        &self,
+        sdf: &mut String,
+        frame_id: &FrameID,
+        child_name: &str,
+        parent_name: &str,
+    ) -> Result<(), Error> {
+        let dof = self.frame_degree_of_freedom(frame_id)?;
+        let joint_type = if dof.is_revolute() {
+            "revolute"
+        } else if dof.is_prismatic() {
+            "prismatic"
+        } else {
+            "fixed"
+        };
+
+        let _ = writeln!(
+            sdf,
+            "    <joint name=\"{}{}\" type=\"{}\">",
+            escape(child_name),
+            JOINT_SUFFIX,
+            joint_type
+        );
+        let _ = writeln!(sdf, "      <parent>{}</parent>", escape(parent_name));
+        let _ = writeln!(sdf, "      <child>{}</child>", escape(child_name));
+
+        // The motion of the joint is applied around the origin of the parent frame
+        write_pose_element(sdf, "      ", Some(parent_name), &Isometry3::identity());
+
+        if let Some(axis) = axis_for(dof) {
+            let constraint = self.joint_constraint(frame_id)?;
+            let _ = writeln!(sdf, "      <axis>");
+            let _ = writeln!(
+                sdf,
+                "        <xyz expressed_in=\"{}\">{} {} {}</xyz>",
+                escape(parent_name),
+                axis.x,
+                axis.y,
+                axis.z
+            );
+            if constraint.position_limits().is_some()
+                || constraint.velocity_limits().is_some()
+                || constraint.effort_limits().is_some()
+            {
+                let _ = writeln!(sdf, "        <limit>");
+                if let Some((lower, upper)) = constraint.position_limits() {
+                    let _ = writeln!(sdf, "          <lower>{}</lower>", lower);
+                    let _ = writeln!(sdf, "          <upper>{}</upper>", upper);
+                }
+                if let Some((min, max)) = constraint.effort_limits() {
+                    let _ = writeln!(
+                        sdf,
+                        "          <effort>{}</effort>",
+                        min.abs().max(max.abs())
+                    );
+                }
+                if let Some((min, max)) = constraint.velocity_limits() {
+                    let _ = writeln!(
+                        sdf,
+                        "          <velocity>{}</velocity>",
+                        min.abs().max(max.abs())
+                    );
+                }
+                let _ = writeln!(sdf, "        </limit>");
+            }
+            let _ = writeln!(sdf, "      </axis>");
+        }
+
+        let _ = writeln!(sdf, "    </joint>");
+        Ok(())
+    }
+
+    /// Writes the SDF link for the given element.
+    fn write_sdf_link(
+        &self,
+        sdf: &mut String,
+        frame_id: &FrameID,
+        name: &str,
+        parent_name: Option<&str>,
+        wheel_friction: &WheelFriction,
+    ) -> Result<(), Error> {
+        let element = self.chassis_element(frame_id)?;
+        let com = element.center_of_mass();
+        let inertia = element.moment_of_inertia();
+
+        let _ = writeln!(sdf, "    <link name=\"{}\">", escape(name));
+        if parent_name.is_some() {
+            let transform = match self.kinematics_table(frame_id) {
+                Some(table) => table.transform_at(0.0),
+                None => *self.static_transform_to_parent(frame_id)?,
+            };
+            write_pose_element(sdf, "      ", parent_name, &transform);
+        }
+
+        let _ = writeln!(sdf, "      <inertial>");
+        let _ = writeln!(
+            sdf,
+            "        <pose>{} {} {} 0 0 0</pose>",
+            com.x, com.y, com.z
+        );
+        let _ = writeln!(sdf, "        <mass>{}</mass>", element.mass_in_kg());
+        let _ = writeln!(sdf, "        <inertia>");
+        for (tag, (row, column)) in [
+            ("ixx", (0, 0)),
+            ("ixy", (0, 1)),
+            ("ixz", (0, 2)),
+            ("iyy", (1, 1)),
+            ("iyz", (1, 2)),
+            ("izz", (2, 2)),
+        ] {
+            let _ = writeln!(
+                sdf,
+                "          <{}>{}</{}>",
+                tag,
+                inertia[(row, column)],
+                tag
+            );
+        }
+        let _ = writeln!(sdf, "        </inertia>");
+        let _ = writeln!(sdf, "      </inertial>");
+
+        for (index, visual) in self.visual_geometries(frame_id).iter().enumerate() {
+            let _ = writeln!(sdf, "      <visual name=\"visual_{}\">", index);
+            write_pose_element(sdf, "        ", None, visual.origin());
+            write_geometry_element(sdf, visual.shape());
+            if let Some(material) = visual.material() {
+                let [r, g, b, a] = material.rgba();
+                let _ = writeln!(sdf, "        <material>");
+                let _ = writeln!(sdf, "          <ambient>{} {} {} {}</ambient>", r, g, b, a);
+                let _ = writeln!(sdf, "          <diffuse>{} {} {} {}</diffuse>", r, g, b, a);
+                let _ = writeln!(sdf, "        </material>");
+            }
+            let _ = writeln!(sdf, "      </visual>");
+        }
+
+        let is_wheel = self.kinematic_tree().is_wheel(frame_id)?;
+        let mut collisions: Vec<(Isometry3<f64>, CollisionShape)> = self
+            .collision_geometries(frame_id)
+            .iter()
+            .map(|c| (*c.origin(), c.shape().clone()))
+            .collect();
+        if is_wheel && collisions.is_empty() {
+            if let Ok(properties) = self.wheel_properties(frame_id) {
+                // The wheel rotates around its y-axis, while the axis of an SDF cylinder is the
+                // z-axis.
+                collisions.push((
+                    Isometry3::rotation(Vector3::new(0.5 * PI, 0.0, 0.0)),
+                    CollisionShape::Cylinder {
+                        radius: properties.radius(),
+                        length: properties.width(),
+                    },
+                ));
+            }
+        }
+
+        for (index, (origin, shape)) in collisions.iter().enumerate() {
+            let _ = writeln!(sdf, "      <collision name=\"collision_{}\">", index);
+            write_pose_element(sdf, "        ", None, origin);
+            write_geometry_element(sdf, shape);
+            if is_wheel {
+                let _ = writeln!(sdf, "        <surface>");
+                let _ = writeln!(sdf, "          <friction>");
+                let _ = writeln!(sdf, "            <ode>");
+                let _ = writeln!(sdf, "              <mu>{}</mu>", wheel_friction.mu);
+                let _ = writeln!(sdf, "              <mu2>{}</mu2>", wheel_friction.mu2);
+                let _ = writeln!(sdf, "            </ode>");
+                let _ = writeln!(sdf, "          </friction>");
+                let _ = writeln!(sdf, "        </surface>");
+            }
+            let _ = writeln!(sdf, "      </collision>");
+        }
+
+        let _ = writeln!(sdf, "    </link>");
+        Ok(())
+    }
+}
+
+/// Writes an SDF geometry element for the given shape.
+fn write_geometry_element(sdf: &mut String, shape: &CollisionShape) {
+    let _ = writeln!(sdf, "        <geometry>");
+    match shape {
+        CollisionShape::Box { size } => {
+            let _ = writeln!(
+                sdf,
+                "          <box><size>{} {} {}</size></box>",
+                size.x, size.y, size.z
+            );
+        }
+        CollisionShape::Cylinder { radius, length } => {
+            let _ = writeln!(
+                sdf,
+                "          <cylinder><radius>{}</radius><length>{}</length></cylinder>",
+                radius, length
+            );
+        }
+        CollisionShape::Mesh { uri, scale } => {
+            let _ = writeln!(
+                sdf,
+                "          <mesh><uri>{}</uri><scale>{} {} {}</scale></mesh>",
+                escape(uri),
+                scale.x,
+                scale.y,
+                scale.z
+            );
+        }
+        CollisionShape::Sphere { radius } => {
+            let _ = writeln!(
+                sdf,
+                "          <sphere><radius>{}</radius></sphere>",
+                radius
+            );
+        }
+    }
+    let _ = writeln!(sdf, "        </geometry>");
+}
+
+/// Writes an SDF pose element, optionally relative to the given frame.
+fn write_pose_element(
+    sdf: &mut String,
+    indent: &str,
+    relative_to: Option<&str>,
+    transform: &Isometry3<f64>,
+) {
+    let translation = transform.translation.vector;
+    let (roll, pitch, yaw) = transform.rotation.euler_angles();
+    let relative_to = relative_to
+        .map(|name| format!(" relative_to=\"{}\"", escape(name)))
+        .unwrap_or_default();
+    let _ = writeln!(
+        sdf,
+        "{}<pose{}>{} {} {} {} {} {}</pose>",
+        indent,
+        relative_to,
+        number(translation.x),
+        number(translation.y),
+        number(translation.z),
+        number(roll),
+        number(pitch),
+        number(yaw)
+    );
+}
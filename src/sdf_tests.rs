@@ -0,0 +1,182 @@
+use nalgebra::{Isometry3, Matrix3, Matrix6, Translation3, UnitQuaternion, Vector3};
+
+use crate::{
+    change_notification_processing::HardwareChangeProcessor,
+    model_elements::{
+        collision::{CollisionGeometry, CollisionShape},
+        frame_elements::{Actuator, FrameDofType, JointConstraint},
+        model::{ChassisElementPhysicalProperties, MotionModel, WheelProperties},
+        visual::{Material, VisualGeometry},
+    },
+    number_space::NumberSpaceType,
+    test_support::MockActuator,
+};
+
+use super::WheelFriction;
+
+fn physical_properties(mass: f64) -> ChassisElementPhysicalProperties {
+    #[rustfmt::skip]
+    let moment_of_inertia = Matrix3::new(
+        1.0, 0.1, 0.2,
+        0.1, 2.0, 0.3,
+        0.2, 0.3, 3.0);
+
+    ChassisElementPhysicalProperties::new(
+        mass,
+        Vector3::new(0.0, 0.0, 0.5),
+        moment_of_inertia,
+        Matrix6::<f64>::identity(),
+    )
+}
+
+// Creates a model with a body and a single module with a limited suspension, a steering element
+// and a wheel 0.1 below the steering element.
+fn create_model(change_processor: &HardwareChangeProcessor) -> MotionModel {
+    let mut model = MotionModel::new();
+    let body = model
+        .add_body(
+            "body".to_string(),
+            Translation3::identity(),
+            UnitQuaternion::identity(),
+            physical_properties(10.0),
+        )
+        .unwrap();
+
+    let suspension = model
+        .add_suspension_element(
+            "suspension".to_string(),
+            FrameDofType::PrismaticZ,
+            body,
+            Translation3::new(1.0, 0.5, 0.0),
+            UnitQuaternion::identity(),
+            physical_properties(1.0),
+            JointConstraint::new()
+                .with_position_limits(-0.05, 0.1)
+                .with_velocity_limits(-2.0, 1.0)
+                .with_effort_limits(-50.0, 50.0),
+        )
+        .unwrap();
+
+    let mut steering_hardware = MockActuator::new(NumberSpaceType::LinearUnlimited);
+    let steering = model
+        .add_steering_element(
+            "steering".to_string(),
+            suspension,
+            Translation3::identity(),
+            UnitQuaternion::identity(),
+            physical_properties(1.0),
+            Actuator::new(&mut steering_hardware, change_processor).unwrap(),
+        )
+        .unwrap();
+
+    let mut wheel_hardware = MockActuator::new(NumberSpaceType::LinearUnlimited);
+    let wheel = model
+        .add_wheel(
+            "wheel".to_string(),
+            steering,
+            Translation3::new(0.0, 0.0, -0.1),
+            UnitQuaternion::identity(),
+            physical_properties(1.0),
+            Actuator::new(&mut wheel_hardware, change_processor).unwrap(),
+        )
+        .unwrap();
+    model
+        .set_wheel_properties(&wheel, WheelProperties::new(0.1, 0.05, Vector3::zeros()))
+        .unwrap();
+
+    model
+}
+
+#[test]
+fn when_exporting_an_empty_model_it_should_error() {
+    let model = MotionModel::new();
+    assert!(model.to_sdf(&WheelFriction::default()).is_err());
+}
+
+#[test]
+fn when_exporting_a_model_it_should_write_the_links_and_the_inertial_properties() {
+    let change_processor = HardwareChangeProcessor::new(10);
+    let model = create_model(&change_processor);
+
+    let sdf = model.to_sdf(&WheelFriction::default()).unwrap();
+
+    assert!(sdf.starts_with("<?xml version=\"1.0\"?>\n<sdf version=\"1.7\">\n"));
+    assert!(sdf.contains("  <model name=\"body\" canonical_link=\"body\">\n"));
+    assert!(sdf.ends_with("  </model>\n</sdf>\n"));
+    assert_eq!(4, sdf.matches("<link ").count());
+
+    assert!(sdf.contains("        <pose>0 0 0.5 0 0 0</pose>\n        <mass>10</mass>\n"));
+    assert!(sdf.contains("          <ixx>1</ixx>\n          <ixy>0.1</ixy>\n          <ixz>0.2</ixz>\n          <iyy>2</iyy>\n          <iyz>0.3</iyz>\n          <izz>3</izz>\n"));
+
+    // The links are posed relative to their parents
+    assert!(sdf.contains(
+        "    <link name=\"suspension\">\n      <pose relative_to=\"body\">1 0.5 0 0 0 0</pose>\n"
+    ));
+    assert!(sdf.contains(
+        "    <link name=\"wheel\">\n      <pose relative_to=\"steering\">0 0 -0.1 0 0 0</pose>\n"
+    ));
+}
+
+#[test]
+fn when_exporting_a_model_it_should_write_the_joints_with_their_limits() {
+    let change_processor = HardwareChangeProcessor::new(10);
+    let model = create_model(&change_processor);
+
+    let sdf = model.to_sdf(&WheelFriction::default()).unwrap();
+
+    assert!(sdf.contains(
+        "    <joint name=\"suspension_joint\" type=\"prismatic\">\n      <parent>body</parent>\n      <child>suspension</child>\n      <pose relative_to=\"body\">0 0 0 0 0 0</pose>\n      <axis>\n        <xyz expressed_in=\"body\">0 0 1</xyz>\n        <limit>\n          <lower>-0.05</lower>\n          <upper>0.1</upper>\n          <effort>50</effort>\n          <velocity>2</velocity>\n        </limit>\n      </axis>\n    </joint>\n"
+    ));
+
+    // Joints without a constraint are not limited and revolute joints with an offset are
+    // located at the origin of the parent link
+    assert!(sdf.contains(
+        "    <joint name=\"wheel_joint\" type=\"revolute\">\n      <parent>steering</parent>\n      <child>wheel</child>\n      <pose relative_to=\"steering\">0 0 0 0 0 0</pose>\n      <axis>\n        <xyz expressed_in=\"steering\">0 1 0</xyz>\n      </axis>\n    </joint>\n"
+    ));
+    assert_eq!(3, sdf.matches("<joint ").count());
+}
+
+#[test]
+fn when_exporting_a_model_it_should_write_the_geometry_and_the_wheel_friction() {
+    let change_processor = HardwareChangeProcessor::new(10);
+    let mut model = create_model(&change_processor);
+    let body = *model.body().unwrap();
+    model
+        .add_visual_geometry(
+            &body,
+            VisualGeometry::new(
+                CollisionShape::Sphere { radius: 0.5 },
+                Isometry3::translation(0.0, 0.0, 0.25),
+                Some(Material::new("red".to_string(), [1.0, 0.0, 0.0, 1.0]).unwrap()),
+            )
+            .unwrap(),
+        )
+        .unwrap();
+    model
+        .add_collision_geometry(
+            &body,
+            CollisionGeometry::new(
+                CollisionShape::Box {
+                    size: Vector3::new(1.0, 0.5, 0.25),
+                },
+                Isometry3::identity(),
+            )
+            .unwrap(),
+        )
+        .unwrap();
+
+    let sdf = model.to_sdf(&WheelFriction::new(0.9, 0.7)).unwrap();
+
+    assert!(sdf.contains(
+        "      <visual name=\"visual_0\">\n        <pose>0 0 0.25 0 0 0</pose>\n        <geometry>\n          <sphere><radius>0.5</radius></sphere>\n        </geometry>\n        <material>\n          <ambient>1 0 0 1</ambient>\n          <diffuse>1 0 0 1</diffuse>\n        </material>\n      </visual>\n"
+    ));
+    assert!(sdf.contains(
+        "      <collision name=\"collision_0\">\n        <pose>0 0 0 0 0 0</pose>\n        <geometry>\n          <box><size>1 0.5 0.25</size></box>\n        </geometry>\n      </collision>\n"
+    ));
+
+    // The wheel gets a cylinder from its properties, with the friction of the wheels
+    assert!(sdf.contains(
+        "          <cylinder><radius>0.1</radius><length>0.05</length></cylinder>\n        </geometry>\n        <surface>\n          <friction>\n            <ode>\n              <mu>0.9</mu>\n              <mu2>0.7</mu2>\n"
+    ));
+    assert_eq!(1, sdf.matches("<surface>").count());
+}
@@ -0,0 +1,396 @@
+//! Provides a soak test that checks that a [MotionModel] keeps working while the actuators are
+//! updated at a high rate.
+//!
+//! The actuators of a model receive their state from the hardware on a separate thread, see the
+//! [change_notification_processing](crate::change_notification_processing) module, while the
+//! transforms are computed on the thread that owns the model. A [SoakTest] runs a number of
+//! producers, each on its own thread, that send states to the hardware of the actuators as fast
+//! as possible, or at a fixed interval, while the calling thread continuously computes the
+//! transform from each frame element to the body. When the test is done the [SoakReport]
+//! describes whether the following invariants held:
+//!
+//! - No producer and no query panicked.
+//! - No query failed, e.g. because a lock was poisoned.
+//! - No query took longer than the maximum latency.
+//! - The state of each actuator was updated during the test, i.e. no actuator was left with a
+//!   stale state.
+//!
+//! The soak test is only available when the `soak` feature is enabled. It is intended to be used
+//! by the continuous integration of applications that use this crate, to validate the integration
+//! of their hardware under load.
+
+use std::{
+    any::Any,
+    panic::{self, AssertUnwindSafe},
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    model_elements::{frame_elements::FrameID, model::MotionModel},
+    Error,
+};
+
+#[cfg(test)]
+#[path = "soak_tests.rs"]
+mod soak_tests;
+
+/// Stores the settings of a [SoakTest].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SoakConfiguration {
+    /// The amount of time the test runs for.
+    duration: Duration,
+
+    /// The maximum amount of time a single transform query is allowed to take.
+    maximum_query_latency: Duration,
+
+    /// The amount of time each producer waits between two calls.
+    producer_interval: Duration,
+}
+
+impl SoakConfiguration {
+    /// Returns the amount of time the test runs for.
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    /// Returns the maximum amount of time a single transform query is allowed to take.
+    pub fn maximum_query_latency(&self) -> Duration {
+        self.maximum_query_latency
+    }
+
+    /// Creates a new [SoakConfiguration] instance in which the producers run without waiting
+    /// between calls.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'duration' - The amount of time the test runs for.
+    /// * 'maximum_query_latency' - The maximum amount of time a single transform query is
+    ///   allowed to take.
+    pub fn new(duration: Duration, maximum_query_latency: Duration) -> Self {
+        Self {
+            duration,
+            maximum_query_latency,
+            producer_interval: Duration::ZERO,
+        }
+    }
+
+    /// Returns the amount of time each producer waits between two calls.
+    pub fn producer_interval(&self) -> Duration {
+        self.producer_interval
+    }
+
+    /// Returns the configuration with the given amount of time between two calls of each
+    /// producer.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'interval' - The amount of time each producer waits between two calls.
+    pub fn with_producer_interval(mut self, interval: Duration) -> Self {
+        self.producer_interval = interval;
+        self
+    }
+}
+
+/// Describes the outcome of a [SoakTest].
+#[derive(Clone, Debug, PartialEq)]
+pub struct SoakReport {
+    /// The amount of time the test ran for.
+    duration: Duration,
+
+    /// The number of transform queries that were made.
+    number_of_queries: u64,
+
+    /// The number of transform queries that returned an error.
+    number_of_failed_queries: u64,
+
+    /// The number of transform queries that took longer than the maximum latency.
+    number_of_slow_queries: u64,
+
+    /// The description of the first transform query that failed or panicked.
+    first_query_failure: Option<String>,
+
+    /// The number of transform queries that panicked.
+    number_of_query_panics: u64,
+
+    /// The longest time a transform query took.
+    maximum_query_latency: Duration,
+
+    /// The average time a transform query took.
+    mean_query_latency: Duration,
+
+    /// The number of times each producer was called, in the order in which the producers were
+    /// added.
+    producer_iterations: Vec<u64>,
+
+    /// The descriptions of the panics of the producers.
+    producer_panics: Vec<String>,
+
+    /// The actuated joints for which the state was not updated during the test.
+    stale_joints: Vec<FrameID>,
+}
+
+impl SoakReport {
+    /// Returns the amount of time the test ran for.
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    /// Returns the description of the first transform query that failed or panicked, or [None]
+    /// if all queries succeeded.
+    pub fn first_query_failure(&self) -> Option<&str> {
+        self.first_query_failure.as_deref()
+    }
+
+    /// Returns a value indicating whether all the invariants held during the test.
+    pub fn is_successful(&self) -> bool {
+        self.number_of_failed_queries == 0
+            && self.number_of_query_panics == 0
+            && self.number_of_slow_queries == 0
+            && self.producer_panics.is_empty()
+            && self.stale_joints.is_empty()
+    }
+
+    /// Returns the longest time a transform query took.
+    pub fn maximum_query_latency(&self) -> Duration {
+        self.maximum_query_latency
+    }
+
+    /// Returns the average time a transform query took.
+    pub fn mean_query_latency(&self) -> Duration {
+        self.mean_query_latency
+    }
+
+    /// Returns the number of transform queries that returned an error.
+    pub fn number_of_failed_queries(&self) -> u64 {
+        self.number_of_failed_queries
+    }
+
+    /// Returns the number of transform queries that were made.
+    pub fn number_of_queries(&self) -> u64 {
+        self.number_of_queries
+    }
+
+    /// Returns the number of transform queries that panicked.
+    pub fn number_of_query_panics(&self) -> u64 {
+        self.number_of_query_panics
+    }
+
+    /// Returns the number of transform queries that took longer than the maximum latency.
+    pub fn number_of_slow_queries(&self) -> u64 {
+        self.number_of_slow_queries
+    }
+
+    /// Returns the number of times each producer was called, in the order in which the
+    /// producers were added.
+    pub fn producer_iterations(&self) -> &[u64] {
+        &self.producer_iterations
+    }
+
+    /// Returns the descriptions of the panics of the producers.
+    pub fn producer_panics(&self) -> &[String] {
+        &self.producer_panics
+    }
+
+    /// Returns the actuated joints for which the state was not updated during the test.
+    pub fn stale_joints(&self) -> &[FrameID] {
+        &self.stale_joints
+    }
+}
+
+/// Runs producers that update the actuators of a [MotionModel] while the transforms of the
+/// model are queried.
+pub struct SoakTest {
+    /// The settings of the test.
+    configuration: SoakConfiguration,
+
+    /// The functions that send states to the hardware of the actuators.
+    producers: Vec<Box<dyn FnMut() + Send>>,
+}
+
+impl SoakTest {
+    /// Creates a new [SoakTest] instance without any producers.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'configuration' - The settings of the test.
+    pub fn new(configuration: SoakConfiguration) -> Self {
+        Self {
+            configuration,
+            producers: Vec::new(),
+        }
+    }
+
+    /// Runs the test and returns the outcome.
+    ///
+    /// The producers are started on their own threads and the transforms are queried on the
+    /// calling thread until the duration of the test has passed. A query that never returns,
+    /// e.g. because of a deadlock, stops the test from completing.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'model' - The model for which the transforms are queried. The producers should update
+    ///   the hardware of the actuators of this model.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the model has no body.
+    pub fn run(self, model: &MotionModel) -> Result<SoakReport, Error> {
+        model.body()?;
+        let frames: Vec<FrameID> = model
+            .chassis_elements()
+            .map(|element| *element.reference_frame())
+            .collect();
+        let actuated_joints: Vec<(FrameID, u64)> = frames
+            .iter()
+            .filter_map(|frame_id| {
+                model
+                    .actuator_for(frame_id)
+                    .ok()
+                    .map(|actuator| (*frame_id, actuator.state_version()))
+            })
+            .collect();
+
+        let stop = Arc::new(AtomicBool::new(false));
+        let interval = self.configuration.producer_interval;
+        let handles: Vec<_> = self
+            .producers
+            .into_iter()
+            .map(|mut producer| {
+                let stop = stop.clone();
+                let iterations = Arc::new(AtomicU64::new(0));
+                let counter = iterations.clone();
+                let handle = thread::spawn(move || {
+                    while !stop.load(Ordering::Relaxed) {
+                        producer();
+                        counter.fetch_add(1, Ordering::Relaxed);
+                        if !interval.is_zero() {
+                            thread::sleep(interval);
+                        }
+                    }
+                });
+                (handle, iterations)
+            })
+            .collect();
+
+        let mut number_of_queries: u64 = 0;
+        let mut number_of_failed_queries = 0;
+        let mut number_of_slow_queries = 0;
+        let mut number_of_query_panics = 0;
+        let mut first_query_failure = None;
+        let mut maximum_query_latency = Duration::ZERO;
+        let mut total_query_latency = Duration::ZERO;
+
+        let start = Instant::now();
+        while start.elapsed() < self.configuration.duration {
+            for frame_id in frames.iter() {
+                let query_start = Instant::now();
+                let result = panic::catch_unwind(AssertUnwindSafe(|| {
+                    model.homogeneous_transform_to_body(frame_id)
+                }));
+                let latency = query_start.elapsed();
+
+                number_of_queries += 1;
+                total_query_latency += latency;
+                maximum_query_latency = maximum_query_latency.max(latency);
+                if latency > self.configuration.maximum_query_latency {
+                    number_of_slow_queries += 1;
+                }
+
+                let failure = match result {
+                    Ok(Ok(_)) => None,
+                    Ok(Err(e)) => {
+                        number_of_failed_queries += 1;
+                        Some(format!("{}: {}", frame_id, e))
+                    }
+                    Err(payload) => {
+                        number_of_query_panics += 1;
+                        Some(format!("{}: {}", frame_id, panic_message(payload)))
+                    }
+                };
+                if first_query_failure.is_none() {
+                    first_query_failure = failure;
+                }
+            }
+        }
+
+        stop.store(true, Ordering::Relaxed);
+        let mut producer_iterations = Vec::with_capacity(handles.len());
+        let mut producer_panics = Vec::new();
+        for (handle, iterations) in handles {
+            if let Err(payload) = handle.join() {
+                producer_panics.push(panic_message(payload));
+            }
+
+            producer_iterations.push(iterations.load(Ordering::Relaxed));
+        }
+
+        let duration = start.elapsed();
+
+        // The states are processed on a separate thread, so give that thread the chance to
+        // process the last updates before checking for stale states.
+        thread::sleep(self.configuration.maximum_query_latency);
+        let stale_joints = actuated_joints
+            .into_iter()
+            .filter(|(frame_id, version)| {
+                model
+                    .actuator_for(frame_id)
+                    .map_or(false, |actuator| actuator.state_version() == *version)
+            })
+            .map(|(frame_id, _)| frame_id)
+            .collect();
+
+        let mean_query_latency = if number_of_queries > 0 {
+            Duration::from_nanos(
+                (total_query_latency.as_nanos() / number_of_queries as u128) as u64,
+            )
+        } else {
+            Duration::ZERO
+        };
+
+        Ok(SoakReport {
+            duration,
+            number_of_queries,
+            number_of_failed_queries,
+            number_of_slow_queries,
+            first_query_failure,
+            number_of_query_panics,
+            maximum_query_latency,
+            mean_query_latency,
+            producer_iterations,
+            producer_panics,
+            stale_joints,
+        })
+    }
+
+    /// Returns the test with the given producer.
+    ///
+    /// The producer is called repeatedly on its own thread until the test ends. Each call
+    /// should send a state to the hardware of one or more actuators of the model.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'producer' - The function that sends states to the hardware of the actuators.
+    pub fn with_producer<F>(mut self, producer: F) -> Self
+    where
+        F: FnMut() + Send + 'static,
+    {
+        self.producers.push(Box::new(producer));
+        self
+    }
+}
+
+/// Returns the description of the given panic.
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
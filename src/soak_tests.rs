@@ -0,0 +1,135 @@
+use std::time::Duration;
+
+use nalgebra::{Translation3, UnitQuaternion, Vector3};
+
+use crate::{
+    change_notification_processing::HardwareChangeProcessor,
+    model_elements::{
+        frame_elements::Actuator,
+        model::{MotionModel, WheelProperties},
+        module_spec::{BodySpec, ModuleSpec},
+    },
+    number_space::NumberSpaceType,
+    test_fixtures::physical_properties,
+    test_support::MockActuator,
+};
+
+use super::{SoakConfiguration, SoakTest};
+
+// Returns a producer that sends an increasing position to the actuator.
+fn producer(hardware: &MockActuator) -> impl FnMut() + Send + 'static {
+    let hardware = hardware.clone();
+    let mut position = 0.0;
+    move || {
+        position += 0.001;
+        hardware.send_position(position);
+    }
+}
+
+// A vehicle with two modules, each with an actuated steering frame and wheel.
+struct TestVehicle {
+    model: MotionModel,
+    hardware: Vec<MockActuator>,
+    _change_processor: HardwareChangeProcessor,
+}
+
+impl TestVehicle {
+    fn new() -> Self {
+        let change_processor = HardwareChangeProcessor::new(1000);
+        let mut hardware = vec![];
+        let mut actuator = || {
+            let mut mock = MockActuator::new(NumberSpaceType::LinearUnlimited);
+            let actuator = Actuator::new(&mut mock, &change_processor).unwrap();
+            hardware.push(mock);
+            actuator
+        };
+
+        let modules = [0.5, -0.5]
+            .iter()
+            .map(|y| {
+                ModuleSpec::new(
+                    "module".to_string(),
+                    Translation3::new(1.0, *y, 0.0),
+                    UnitQuaternion::identity(),
+                    WheelProperties::new(0.1, 0.05, Vector3::zeros()),
+                    physical_properties(),
+                    physical_properties(),
+                )
+                .with_steering_actuator(actuator())
+                .with_wheel_actuator(actuator())
+            })
+            .collect();
+
+        let body = BodySpec::new(
+            "body".to_string(),
+            Translation3::identity(),
+            UnitQuaternion::identity(),
+            physical_properties(),
+        );
+        let (model, _) = MotionModel::with_modules(body, modules).unwrap();
+
+        Self {
+            model,
+            hardware,
+            _change_processor: change_processor,
+        }
+    }
+}
+
+fn configuration() -> SoakConfiguration {
+    SoakConfiguration::new(Duration::from_millis(100), Duration::from_millis(50))
+}
+
+#[test]
+fn when_running_a_soak_test_it_should_query_while_the_producers_update_the_actuators() {
+    let vehicle = TestVehicle::new();
+    let mut test = SoakTest::new(configuration());
+    for hardware in vehicle.hardware.iter() {
+        test = test.with_producer(producer(hardware));
+    }
+
+    let report = test.run(&vehicle.model).unwrap();
+    assert!(report.is_successful(), "{:?}", report);
+    assert!(report.duration() >= Duration::from_millis(100));
+    assert!(report.number_of_queries() > 0);
+    assert_eq!(0, report.number_of_failed_queries());
+    assert_eq!(None, report.first_query_failure());
+    assert!(report.mean_query_latency() <= report.maximum_query_latency());
+    assert_eq!(4, report.producer_iterations().len());
+    assert!(report.producer_iterations().iter().all(|i| *i > 0));
+    assert!(report.stale_joints().is_empty());
+}
+
+#[test]
+fn when_running_a_soak_test_with_missing_producers_it_should_report_stale_joints() {
+    let vehicle = TestVehicle::new();
+
+    let report = SoakTest::new(configuration())
+        .with_producer(producer(&vehicle.hardware[0]))
+        .run(&vehicle.model)
+        .unwrap();
+    assert!(!report.is_successful());
+    assert_eq!(3, report.stale_joints().len());
+}
+
+#[test]
+fn when_a_producer_panics_it_should_report_the_panic() {
+    let vehicle = TestVehicle::new();
+    let mut test = SoakTest::new(configuration().with_producer_interval(Duration::from_millis(1)))
+        .with_producer(|| panic!("producer failed"));
+    for hardware in vehicle.hardware.iter() {
+        test = test.with_producer(producer(hardware));
+    }
+
+    let report = test.run(&vehicle.model).unwrap();
+    assert!(!report.is_successful());
+    assert_eq!(&["producer failed".to_string()], report.producer_panics());
+    assert_eq!(0, report.producer_iterations()[0]);
+}
+
+#[test]
+fn when_running_a_soak_test_on_an_empty_model_it_should_error() {
+    let model = MotionModel::new();
+
+    assert!(SoakTest::new(configuration()).run(&model).is_err());
+}
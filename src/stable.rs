@@ -0,0 +1,16 @@
+//! The `stable` module contains the versioned facades of the crate, i.e. small subsets of the
+//! public API that are covered by a semver guarantee.
+//!
+//! The rest of the crate is still evolving and may change in any minor release. Applications
+//! that need to pin against a surface that only changes with a major release, e.g. the firmware
+//! of an embedded controller with a long support cycle, should only use the items in one of the
+//! versioned facades. Each facade covers the construction of a model, the transform queries and
+//! the forward and inverse kinematics of the vehicle.
+//!
+//! A facade is never changed in a breaking way. New functionality is added to a new facade,
+//! e.g. `v2`, while the existing facades keep working for as long as the major version of the
+//! crate does not change.
+//!
+//! The [v1] module contains the first version of the facade.
+
+pub mod v1;
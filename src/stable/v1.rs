@@ -0,0 +1,210 @@
+//! The first version of the stable facade. See the [stable](crate::stable) module for the
+//! guarantees that apply to this facade.
+//!
+//! A [Vehicle] is created from the specification of the body and the specifications of the wheel
+//! modules. The types needed to describe the vehicle and to connect the hardware are re-exported
+//! from this module, so that applications do not have to refer to the internal module layout of
+//! the crate.
+//!
+//! The twists used by the facade are stored as a 6 element vector with the linear velocity first
+//! followed by the angular velocity, i.e. `[v_x, v_y, v_z, ω_x, ω_y, ω_z]`, expressed in the body
+//! reference frame.
+
+use std::collections::HashMap;
+
+use nalgebra::{Matrix4, Vector6};
+
+use crate::model_elements::model::MotionModel;
+
+pub use crate::{
+    change_notification_processing::{ChangeID, HardwareChangeProcessor},
+    hardware::{
+        actuator_interface::{ActuatorAvailableRatesOfChange, HardwareActuator},
+        joint_state::{JointState, JointStateRange},
+    },
+    model_elements::{
+        frame_elements::{Actuator, FrameDofType, FrameID, JointConstraint},
+        model::{ChassisElementPhysicalProperties, WheelProperties},
+        module_spec::{BodySpec, ModuleFrames, ModuleSpec},
+    },
+    number_space::NumberSpaceType,
+    Error,
+};
+
+#[cfg(test)]
+#[path = "v1_tests.rs"]
+mod v1_tests;
+
+/// Stores the joint set-points for a single wheel module that realize a body twist.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ModuleCommand {
+    /// The [FrameID] of the steering frame of the module.
+    steering_frame: FrameID,
+
+    /// The steering angle, in radians, relative to the body x-axis.
+    steering_angle: f64,
+
+    /// The position, in radians, of the steering joint.
+    steering_joint_position: f64,
+
+    /// The angular velocity, in radians per second, of the wheel.
+    wheel_angular_velocity: f64,
+}
+
+impl ModuleCommand {
+    /// Returns the steering angle, in radians, relative to the body x-axis.
+    pub fn steering_angle(&self) -> f64 {
+        self.steering_angle
+    }
+
+    /// Returns the [FrameID] of the steering frame of the module.
+    pub fn steering_frame(&self) -> &FrameID {
+        &self.steering_frame
+    }
+
+    /// Returns the position, in radians, of the steering joint that results in the steering
+    /// angle.
+    pub fn steering_joint_position(&self) -> f64 {
+        self.steering_joint_position
+    }
+
+    /// Returns the angular velocity, in radians per second, of the wheel.
+    pub fn wheel_angular_velocity(&self) -> f64 {
+        self.wheel_angular_velocity
+    }
+}
+
+/// Describes the geometry and the current state of a swerve vehicle.
+pub struct Vehicle {
+    /// The model that stores the geometry and the actuators of the vehicle.
+    model: MotionModel,
+}
+
+impl Vehicle {
+    /// Returns the [FrameID] of the body.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the vehicle has no body.
+    pub fn body(&self) -> Result<FrameID, Error> {
+        self.model.body().copied()
+    }
+
+    /// Returns the estimated twist of the body, based on the current steering angles and wheel
+    /// velocities as reported by the actuators.
+    ///
+    /// Only the `v_x`, `v_y` and `ω_z` components of the twist will be non-zero.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the vehicle has no wheels or when a wheel
+    ///   has no actuator.
+    /// * [Error::MissingWheelRadius] - Returned when the radius of a wheel is not known.
+    /// * [Error::InsufficientWheelMeasurements] - Returned when fewer than two wheels report
+    ///   a velocity.
+    pub fn body_twist(&self) -> Result<Vector6<f64>, Error> {
+        self.model.body_twist_from_joint_states()
+    }
+
+    /// Returns the joint set-points that each wheel module needs in order to realize the given
+    /// body twist, stored by the [FrameID] of the wheel.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'twist' - The desired twist of the body. Only the planar components, `v_x`, `v_y` and
+    ///   `ω_z`, are used.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the vehicle has no wheels.
+    /// * [Error::NoSteeringFramesInChain] - Returned when a wheel has no steering frame.
+    /// * [Error::MissingWheelRadius] - Returned when the radius of a wheel is not known.
+    pub fn module_commands(
+        &self,
+        twist: Vector6<f64>,
+    ) -> Result<HashMap<FrameID, ModuleCommand>, Error> {
+        let setpoints = self.model.steering_and_wheel_rates_for_body_twist(twist)?;
+        Ok(setpoints
+            .into_iter()
+            .map(|(wheel, setpoint)| {
+                (
+                    wheel,
+                    ModuleCommand {
+                        steering_frame: *setpoint.steering_frame(),
+                        steering_angle: setpoint.steering_angle(),
+                        steering_joint_position: setpoint.steering_joint_position(),
+                        wheel_angular_velocity: setpoint.wheel_angular_velocity(),
+                    },
+                )
+            })
+            .collect())
+    }
+
+    /// Returns the underlying [MotionModel].
+    ///
+    /// The [MotionModel] is not part of the stable facade. It can be used to access the
+    /// functionality that is not covered by the facade, with the understanding that this
+    /// functionality may change in any minor release.
+    pub fn motion_model(&self) -> &MotionModel {
+        &self.model
+    }
+
+    /// Creates a new [Vehicle] instance that consists of the given body and wheel modules.
+    /// Returns the vehicle together with the frames of each module, in the same order as the
+    /// modules.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'body' - The specification of the body.
+    /// * 'modules' - The specifications of the wheel modules.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::InvalidModuleSpecification] - Returned when a module is not valid.
+    pub fn new(
+        body: BodySpec,
+        modules: Vec<ModuleSpec>,
+    ) -> Result<(Self, Vec<ModuleFrames>), Error> {
+        let (model, frames) = MotionModel::with_modules(body, modules)?;
+        Ok((Self { model }, frames))
+    }
+
+    /// Returns the homogeneous transform from the given frame to the destination frame, based
+    /// on the current positions of the joints.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'from' - The [FrameID] of the frame for which the transform is requested.
+    /// * 'to' - The [FrameID] of the destination frame.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when either frame is not part of the vehicle.
+    /// * [Error::FailedToComputeTransform] - Returned when the transform could not be computed.
+    pub fn transform_between(&self, from: &FrameID, to: &FrameID) -> Result<Matrix4<f64>, Error> {
+        self.model.homogeneous_transform_between_frames(from, to)
+    }
+
+    /// Returns the homogeneous transform from the given frame to the body, based on the
+    /// current positions of the joints.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'frame_id' - The [FrameID] of the frame for which the transform is requested.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the frame is not part of the vehicle.
+    pub fn transform_to_body(&self, frame_id: &FrameID) -> Result<Matrix4<f64>, Error> {
+        self.model.homogeneous_transform_to_body(frame_id)
+    }
+
+    /// Returns the [FrameID] of all the wheels of the vehicle.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the vehicle has no body.
+    pub fn wheels(&self) -> Result<Vec<FrameID>, Error> {
+        Ok(self.model.wheels()?.into_iter().copied().collect())
+    }
+}
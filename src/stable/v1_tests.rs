@@ -0,0 +1,108 @@
+use nalgebra::{Translation3, UnitQuaternion, Vector3, Vector6};
+
+use super::{
+    Actuator, BodySpec, Error, FrameID, HardwareChangeProcessor, ModuleSpec, NumberSpaceType,
+    Vehicle, WheelProperties,
+};
+use crate::test_fixtures::physical_properties;
+use crate::test_support::MockActuator;
+
+fn actuator(change_processor: &HardwareChangeProcessor) -> Actuator {
+    let mut hardware = MockActuator::new(NumberSpaceType::LinearUnlimited);
+    Actuator::new(&mut hardware, change_processor).unwrap()
+}
+
+// Creates a vehicle with four modules at (±1, ±0.5) using only the items from the facade.
+fn create_vehicle(change_processor: &HardwareChangeProcessor) -> (Vehicle, Vec<FrameID>) {
+    let body = BodySpec::new(
+        "body".to_string(),
+        Translation3::identity(),
+        UnitQuaternion::identity(),
+        physical_properties(),
+    );
+    let modules = [(1.0, 0.5), (-1.0, 0.5), (-1.0, -0.5), (1.0, -0.5)]
+        .iter()
+        .map(|(x, y)| {
+            ModuleSpec::new(
+                "module".to_string(),
+                Translation3::new(*x, *y, 0.0),
+                UnitQuaternion::identity(),
+                WheelProperties::new(0.1, 0.05, Vector3::zeros()),
+                physical_properties(),
+                physical_properties(),
+            )
+            .with_wheel_offset(Translation3::new(0.0, 0.0, -0.2))
+            .with_steering_actuator(actuator(change_processor))
+            .with_wheel_actuator(actuator(change_processor))
+        })
+        .collect();
+
+    let (vehicle, frames) = Vehicle::new(body, modules).unwrap();
+    let wheels = frames.iter().map(|f| *f.wheel()).collect();
+    (vehicle, wheels)
+}
+
+#[test]
+fn when_creating_a_vehicle_it_should_provide_the_transforms() {
+    let change_processor = HardwareChangeProcessor::new(10);
+    let (vehicle, wheels) = create_vehicle(&change_processor);
+
+    let body = vehicle.body().unwrap();
+    assert_eq!(vehicle.motion_model().body().unwrap(), &body);
+
+    let mut vehicle_wheels = vehicle.wheels().unwrap();
+    vehicle_wheels.sort();
+    let mut expected = wheels.clone();
+    expected.sort();
+    assert_eq!(expected, vehicle_wheels);
+
+    let transform = vehicle.transform_to_body(&wheels[0]).unwrap();
+    assert_eq!(1.0, transform[(0, 3)]);
+    assert_eq!(0.5, transform[(1, 3)]);
+    assert_eq!(-0.2, transform[(2, 3)]);
+
+    let between = vehicle.transform_between(&wheels[0], &wheels[1]).unwrap();
+    assert!((2.0 - between[(0, 3)]).abs() < 1e-12);
+    assert!(between[(1, 3)].abs() < 1e-12);
+
+    assert!(matches!(
+        vehicle.transform_to_body(&FrameID::new()),
+        Err(Error::MissingFrameElement { .. })
+    ));
+}
+
+#[test]
+fn when_requesting_module_commands_it_should_return_a_command_per_wheel() {
+    let change_processor = HardwareChangeProcessor::new(10);
+    let (vehicle, wheels) = create_vehicle(&change_processor);
+
+    let twist = Vector6::new(1.0, 0.0, 0.0, 0.0, 0.0, 0.0);
+    let commands = vehicle.module_commands(twist).unwrap();
+    assert_eq!(4, commands.len());
+
+    let expected = vehicle
+        .motion_model()
+        .steering_and_wheel_rates_for_body_twist(twist)
+        .unwrap();
+    for wheel in wheels.iter() {
+        let command = &commands[wheel];
+        let setpoint = &expected[wheel];
+        assert_eq!(setpoint.steering_frame(), command.steering_frame());
+        assert_eq!(setpoint.steering_angle(), command.steering_angle());
+        assert_eq!(
+            setpoint.steering_joint_position(),
+            command.steering_joint_position()
+        );
+        assert_eq!(10.0, command.wheel_angular_velocity());
+    }
+}
+
+#[test]
+fn when_requesting_the_body_twist_it_should_use_the_actuator_states() {
+    let change_processor = HardwareChangeProcessor::new(10);
+    let (vehicle, _) = create_vehicle(&change_processor);
+
+    // The actuators report a zero velocity until the hardware sends a state
+    let twist = vehicle.body_twist().unwrap();
+    assert!(twist.norm() < 1e-12);
+}
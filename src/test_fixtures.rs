@@ -0,0 +1,160 @@
+//! Provides the fixtures that are shared by the unit tests of the crate. The mock hardware is
+//! provided by the [test_support](crate::test_support) module.
+
+use std::f64::consts::PI;
+
+use nalgebra::{Matrix3, Translation3, UnitQuaternion, Vector3};
+
+use crate::{
+    change_notification_processing::HardwareChangeProcessor,
+    model_elements::{
+        frame_elements::{Actuator, FrameDofType, FrameID, JointConstraint},
+        model::{ChassisElementPhysicalProperties, MotionModel, WheelProperties},
+        module_spec::{BodySpec, ModuleFrames, ModuleSpec},
+    },
+    number_space::NumberSpaceType,
+    test_support::MockActuator,
+};
+
+/// Returns the physical properties of an element with a mass of 1 kg, with the center of mass
+/// at the origin of the element.
+pub(crate) fn physical_properties() -> ChassisElementPhysicalProperties {
+    ChassisElementPhysicalProperties::from_mass_properties(
+        1.0,
+        Vector3::zeros(),
+        Matrix3::identity(),
+    )
+}
+
+/// Returns the physical properties of an element with the given mass and center of mass.
+///
+/// ## Parameters
+///
+/// * 'mass' - The mass of the element in kg.
+/// * 'center_of_mass' - The center of mass relative to the origin of the element.
+pub(crate) fn physical_properties_with_mass(
+    mass: f64,
+    center_of_mass: Vector3<f64>,
+) -> ChassisElementPhysicalProperties {
+    ChassisElementPhysicalProperties::from_mass_properties(
+        mass,
+        center_of_mass,
+        Matrix3::identity(),
+    )
+}
+
+/// A single module attached to the body at (1.0, 0.5, 0.0), rotated 30 degrees around the
+/// z-axis. The module consists of a suspension frame with a prismatic z joint, a steering frame
+/// 0.2 in front of and 0.1 below the suspension frame, and a wheel frame 0.1 below the steering
+/// frame. The hardware updates are processed on a background thread.
+pub(crate) struct TestModule {
+    pub(crate) model: MotionModel,
+    pub(crate) body: FrameID,
+    pub(crate) suspension: FrameID,
+    pub(crate) steering: FrameID,
+    pub(crate) wheel: FrameID,
+    pub(crate) steering_actuator: MockActuator,
+    _wheel_actuator: MockActuator,
+    _change_processor: Box<HardwareChangeProcessor>,
+}
+
+impl TestModule {
+    pub(crate) fn new() -> Self {
+        let change_processor = Box::new(HardwareChangeProcessor::new(1000));
+        let mut model = MotionModel::new();
+        let body = model
+            .add_body(
+                "body".to_string(),
+                Translation3::identity(),
+                UnitQuaternion::identity(),
+                physical_properties(),
+            )
+            .unwrap();
+
+        let suspension = model
+            .add_suspension_element(
+                "suspension".to_string(),
+                FrameDofType::PrismaticZ,
+                body,
+                Translation3::new(1.0, 0.5, 0.0),
+                UnitQuaternion::from_euler_angles(0.0, 0.0, PI / 6.0),
+                physical_properties(),
+                JointConstraint::new(),
+            )
+            .unwrap();
+
+        let mut steering_actuator = MockActuator::new(NumberSpaceType::LinearUnlimited);
+        let steering = model
+            .add_steering_element(
+                "steering".to_string(),
+                suspension,
+                Translation3::new(0.2, 0.0, -0.1),
+                UnitQuaternion::identity(),
+                physical_properties(),
+                Actuator::new(&mut steering_actuator, &change_processor).unwrap(),
+            )
+            .unwrap();
+
+        let mut wheel_actuator = MockActuator::new(NumberSpaceType::LinearUnlimited);
+        let wheel = model
+            .add_wheel(
+                "wheel".to_string(),
+                steering,
+                Translation3::new(0.0, 0.0, -0.1),
+                UnitQuaternion::identity(),
+                physical_properties(),
+                Actuator::new(&mut wheel_actuator, &change_processor).unwrap(),
+            )
+            .unwrap();
+
+        Self {
+            model,
+            body,
+            suspension,
+            steering,
+            wheel,
+            steering_actuator,
+            _wheel_actuator: wheel_actuator,
+            _change_processor: change_processor,
+        }
+    }
+}
+
+/// Returns a model with a body and two modules without actuators, 'left' at (1.0, 0.5, 0.0) and
+/// 'right' at (1.0, -0.5, 0.0). Only the left module has a suspension.
+pub(crate) fn two_module_model() -> (MotionModel, Vec<ModuleFrames>) {
+    let body = BodySpec::new(
+        "body".to_string(),
+        Translation3::identity(),
+        UnitQuaternion::identity(),
+        physical_properties(),
+    );
+
+    let modules = vec![
+        ModuleSpec::new(
+            "left".to_string(),
+            Translation3::new(1.0, 0.5, 0.0),
+            UnitQuaternion::identity(),
+            WheelProperties::new(0.1, 0.05, Vector3::zeros()),
+            physical_properties(),
+            physical_properties(),
+        )
+        .with_wheel_offset(Translation3::new(0.0, 0.0, -0.25))
+        .with_suspension(
+            FrameDofType::PrismaticZ,
+            physical_properties(),
+            JointConstraint::new(),
+        ),
+        ModuleSpec::new(
+            "right".to_string(),
+            Translation3::new(1.0, -0.5, 0.0),
+            UnitQuaternion::identity(),
+            WheelProperties::new(0.1, 0.05, Vector3::zeros()),
+            physical_properties(),
+            physical_properties(),
+        )
+        .with_wheel_offset(Translation3::new(0.0, 0.0, -0.25)),
+    ];
+
+    MotionModel::with_modules(body, modules).unwrap()
+}
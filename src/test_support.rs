@@ -0,0 +1,472 @@
+//! Provides mock hardware and ready-made vehicles for the tests of applications that use this
+//! crate.
+//!
+//! A [MockActuator] implements the [HardwareActuator] trait and a [MockSensor] implements the
+//! [HardwareSensor] trait. Both report exactly the states that the test sends to them and the
+//! [MockActuator] records the commands that it receives. A [TestVehicle] contains a
+//! [MotionModel] of a rectangular swerve vehicle of which each steering frame and each wheel is
+//! driven by a [MockActuator].
+//!
+//! The [TestVehicle] processes the hardware notifications with a
+//! [HardwareChangeProcessor::manual] processor, so the states that are sent to the mock hardware
+//! are only stored in the model when [TestVehicle::process_pending] is called. This keeps the
+//! tests deterministic and avoids waiting for a background thread.
+//!
+//! The test support is only available when the `test_support` feature is enabled. Enable it for
+//! the dev-dependencies of a crate, e.g.
+//!
+//! ```toml
+//! [dev-dependencies]
+//! swerve_vehicle_descriptors = { version = "0.1", features = ["test_support"] }
+//! ```
+//!
+//! ## Examples
+//!
+//! ```
+//! use std::f64::consts::PI;
+//! use swerve_vehicle_descriptors::test_support::TestVehicle;
+//!
+//! let mut vehicle = TestVehicle::rectangular_swerve(4, 1.0, 0.5).unwrap();
+//! vehicle.steering_actuator(0).send_position(0.5 * PI);
+//! vehicle.process_pending();
+//!
+//! let steering = vehicle.steering_frames()[0];
+//! assert!((0.5 * PI - vehicle.model().joint_state_for(&steering).unwrap().position()).abs() < 1e-12);
+//! ```
+
+use std::{
+    f64::consts::PI,
+    sync::{Arc, Mutex},
+};
+
+use crossbeam_channel::{Receiver, Sender};
+use nalgebra::{Matrix3, Translation3, Vector3};
+
+use crate::{
+    change_notification_processing::{ChangeID, ChangeNotifier, HardwareChangeProcessor},
+    hardware::{
+        actuator_interface::{ActuatorAvailableRatesOfChange, HardwareActuator},
+        joint_state::{JointState, JointStateRange},
+        sensor_interface::HardwareSensor,
+    },
+    model_elements::{
+        frame_elements::{Actuator, FrameID},
+        model::{ChassisElementPhysicalProperties, MotionModel, WheelProperties},
+        module_spec::MotionModelBuilder,
+        templates::ModuleTemplate,
+    },
+    number_space::NumberSpaceType,
+    Error,
+};
+
+#[cfg(test)]
+#[path = "test_support_tests.rs"]
+mod test_support_tests;
+
+/// The radius of the wheels of a [TestVehicle].
+pub const TEST_WHEEL_RADIUS: f64 = 0.1;
+
+/// The notifier and the [ChangeID] that are provided by the software side of the hardware.
+type Notifier = Arc<Mutex<Option<(ChangeID, ChangeNotifier)>>>;
+
+/// Notifies the software side of the hardware, if it is connected, that a new state is waiting.
+fn notify(notifier: &Notifier) {
+    let guard = notifier.lock().unwrap_or_else(|err| err.into_inner());
+    if let Some((id, sender)) = guard.as_ref() {
+        // A failure means that the software side is gone, in which case nobody is listening
+        let _ = sender.send(*id);
+    }
+}
+
+/// A mock actuator that reports the states that are sent to it and records the commands that it
+/// receives.
+///
+/// The copies of a [MockActuator] share the same channels, so that a copy can be kept by the
+/// test while the original is used to create an [Actuator].
+#[derive(Clone)]
+pub struct MockActuator {
+    /// The motion type of the actuator.
+    motion_type: NumberSpaceType,
+
+    /// The range of the actuator.
+    range: JointStateRange,
+
+    /// The rates of change that are reported with each state.
+    rates_of_change: ActuatorAvailableRatesOfChange,
+
+    /// The sender for the states that are reported to the software actuator.
+    state_sender: Sender<(JointState, ActuatorAvailableRatesOfChange)>,
+
+    /// The receiver for the states that are reported to the software actuator.
+    state_receiver: Receiver<(JointState, ActuatorAvailableRatesOfChange)>,
+
+    /// The sender for the commands from the software actuator.
+    command_sender: Sender<JointState>,
+
+    /// The receiver for the commands from the software actuator.
+    command_receiver: Receiver<JointState>,
+
+    /// The notifier for the software actuator.
+    notifier: Notifier,
+}
+
+impl MockActuator {
+    /// Returns the commands that were received since the last call, in the order in which they
+    /// were received.
+    pub fn commands(&self) -> Vec<JointState> {
+        self.command_receiver.try_iter().collect()
+    }
+
+    /// Creates a new [MockActuator] instance with the given motion type, a range of
+    /// [-100, 100] and unlimited rates of change.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'motion_type' - The [NumberSpaceType] that describes the motion of the actuator.
+    pub fn new(motion_type: NumberSpaceType) -> Self {
+        let (state_sender, state_receiver) = crossbeam_channel::unbounded();
+        let (command_sender, command_receiver) = crossbeam_channel::unbounded();
+        Self {
+            motion_type,
+            range: JointStateRange::new(
+                JointState::new(-100.0, None, None, None),
+                JointState::new(100.0, None, None, None),
+            ),
+            rates_of_change: ActuatorAvailableRatesOfChange::new(
+                -f64::MAX,
+                f64::MAX,
+                -f64::MAX,
+                f64::MAX,
+                -f64::MAX,
+                f64::MAX,
+            ),
+            state_sender,
+            state_receiver,
+            command_sender,
+            command_receiver,
+            notifier: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Reports the given position, without velocity or acceleration, to the software actuator.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'position' - The position of the actuator.
+    pub fn send_position(&self, position: f64) {
+        self.send_state(JointState::new(position, None, None, None));
+    }
+
+    /// Reports the given state to the software actuator. The software actuator must be created
+    /// before the state is sent, otherwise it is not notified of the state.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'state' - The state of the actuator.
+    pub fn send_state(&self, state: JointState) {
+        // The receiver is owned by this actuator, so sending the state can not fail
+        let _ = self.state_sender.send((state, self.rates_of_change));
+        notify(&self.notifier);
+    }
+
+    /// Returns the actuator with the given range.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'range' - The minimum and maximum states of the actuator.
+    pub fn with_range(mut self, range: JointStateRange) -> Self {
+        self.range = range;
+        self
+    }
+
+    /// Returns the actuator that reports the given rates of change with each state.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'rates_of_change' - The rates of change that are reported with each state.
+    pub fn with_rates_of_change(mut self, rates_of_change: ActuatorAvailableRatesOfChange) -> Self {
+        self.rates_of_change = rates_of_change;
+        self
+    }
+}
+
+impl HardwareActuator for MockActuator {
+    fn actuator_motion_type(&self) -> NumberSpaceType {
+        self.motion_type
+    }
+
+    fn actuator_range(&self) -> JointStateRange {
+        self.range
+    }
+
+    fn command_sender(&self) -> Result<Sender<JointState>, Error> {
+        Ok(self.command_sender.clone())
+    }
+
+    fn current_state_receiver(
+        &self,
+    ) -> Result<Receiver<(JointState, ActuatorAvailableRatesOfChange)>, Error> {
+        Ok(self.state_receiver.clone())
+    }
+
+    fn on_change(&mut self, id: ChangeID, notifier: ChangeNotifier) {
+        *self.notifier.lock().unwrap_or_else(|err| err.into_inner()) = Some((id, notifier));
+    }
+}
+
+/// A mock sensor that reports the states that are sent to it.
+///
+/// The copies of a [MockSensor] share the same channels, so that a copy can be kept by the test
+/// while the original is used to create a
+/// [JointSensor](crate::model_elements::frame_elements::JointSensor).
+#[derive(Clone)]
+pub struct MockSensor {
+    /// The motion type of the joint.
+    motion_type: NumberSpaceType,
+
+    /// The range of the joint.
+    range: JointStateRange,
+
+    /// The sender for the states that are reported to the software sensor.
+    state_sender: Sender<JointState>,
+
+    /// The receiver for the states that are reported to the software sensor.
+    state_receiver: Receiver<JointState>,
+
+    /// The notifier for the software sensor.
+    notifier: Notifier,
+}
+
+impl MockSensor {
+    /// Creates a new [MockSensor] instance with the given motion type and a range of
+    /// [-100, 100].
+    ///
+    /// ## Parameters
+    ///
+    /// * 'motion_type' - The [NumberSpaceType] that describes the motion of the joint.
+    pub fn new(motion_type: NumberSpaceType) -> Self {
+        let (state_sender, state_receiver) = crossbeam_channel::unbounded();
+        Self {
+            motion_type,
+            range: JointStateRange::new(
+                JointState::new(-100.0, None, None, None),
+                JointState::new(100.0, None, None, None),
+            ),
+            state_sender,
+            state_receiver,
+            notifier: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Reports the given position, without velocity or acceleration, to the software sensor.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'position' - The position of the joint.
+    pub fn send_position(&self, position: f64) {
+        self.send_state(JointState::new(position, None, None, None));
+    }
+
+    /// Reports the given state to the software sensor. The software sensor must be created
+    /// before the state is sent, otherwise it is not notified of the state.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'state' - The state of the joint.
+    pub fn send_state(&self, state: JointState) {
+        // The receiver is owned by this sensor, so sending the state can not fail
+        let _ = self.state_sender.send(state);
+        notify(&self.notifier);
+    }
+
+    /// Returns the sensor with the given range.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'range' - The minimum and maximum states of the joint.
+    pub fn with_range(mut self, range: JointStateRange) -> Self {
+        self.range = range;
+        self
+    }
+}
+
+impl HardwareSensor for MockSensor {
+    fn current_state_receiver(&self) -> Result<Receiver<JointState>, Error> {
+        Ok(self.state_receiver.clone())
+    }
+
+    fn joint_motion_type(&self) -> NumberSpaceType {
+        self.motion_type
+    }
+
+    fn joint_range(&self) -> JointStateRange {
+        self.range
+    }
+
+    fn on_change(&mut self, id: ChangeID, notifier: ChangeNotifier) {
+        *self.notifier.lock().unwrap_or_else(|err| err.into_inner()) = Some((id, notifier));
+    }
+}
+
+/// A [MotionModel] of a swerve vehicle with mock actuators for all the steering frames and
+/// wheels.
+///
+/// The modules are laid out as described by [MotionModelBuilder::rectangular_swerve], i.e. the
+/// modules are ordered from front to rear and within each row from left to right. The steering
+/// actuators report angles in the range [-π, π) and the wheel actuators report linear,
+/// unlimited, positions. All the elements have a mass of 1 kg, the wheels have a radius of
+/// [TEST_WHEEL_RADIUS] and the center of each wheel is [TEST_WHEEL_RADIUS] below its steering
+/// frame.
+pub struct TestVehicle {
+    /// The model of the vehicle.
+    model: MotionModel,
+
+    /// The steering frames, in the order of the modules.
+    steering_frames: Vec<FrameID>,
+
+    /// The wheels, in the order of the modules.
+    wheels: Vec<FrameID>,
+
+    /// The hardware of the steering actuators, in the order of the modules.
+    steering_actuators: Vec<MockActuator>,
+
+    /// The hardware of the wheel actuators, in the order of the modules.
+    wheel_actuators: Vec<MockActuator>,
+
+    /// The processor for the notifications of the mock hardware.
+    change_processor: Box<HardwareChangeProcessor>,
+}
+
+impl TestVehicle {
+    /// Returns the processor for the notifications of the mock hardware.
+    pub fn change_processor(&self) -> &HardwareChangeProcessor {
+        &self.change_processor
+    }
+
+    /// Returns the model of the vehicle.
+    pub fn model(&self) -> &MotionModel {
+        &self.model
+    }
+
+    /// Returns the model of the vehicle for modification.
+    pub fn model_mut(&mut self) -> &mut MotionModel {
+        &mut self.model
+    }
+
+    /// Stores the states that were sent to the mock hardware in the model. Returns the number of
+    /// states that were stored.
+    pub fn process_pending(&mut self) -> usize {
+        self.change_processor.process_pending()
+    }
+
+    /// Creates a new [TestVehicle] with the modules placed symmetrically on a rectangle that is
+    /// centered on the origin of the body.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'number_of_modules' - The number of wheel modules. Must be an even number of at least
+    ///   four.
+    /// * 'wheelbase' - The distance between the front and the rear row of modules.
+    /// * 'track_width' - The distance between the left and the right module of a row.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::InvalidTemplate] - Returned when the number of modules or the dimensions are
+    ///   not valid, see [MotionModelBuilder::rectangular_swerve].
+    pub fn rectangular_swerve(
+        number_of_modules: usize,
+        wheelbase: f64,
+        track_width: f64,
+    ) -> Result<Self, Error> {
+        let properties = ChassisElementPhysicalProperties::from_mass_properties(
+            1.0,
+            Vector3::zeros(),
+            Matrix3::identity(),
+        );
+        let template = ModuleTemplate::new(
+            WheelProperties::new(TEST_WHEEL_RADIUS, 0.05, Vector3::zeros()),
+            properties,
+            properties,
+        )
+        .with_wheel_offset(Translation3::new(0.0, 0.0, -TEST_WHEEL_RADIUS));
+        let mut model = MotionModelBuilder::rectangular_swerve(
+            number_of_modules,
+            wheelbase,
+            track_width,
+            properties,
+            &template,
+        )?
+        .build()?;
+
+        let frames: Vec<(FrameID, FrameID)> = model
+            .drive_modules()
+            .map(|m| (*m.frames().steering(), *m.frames().wheel()))
+            .collect();
+
+        let change_processor = Box::new(HardwareChangeProcessor::manual());
+        let mut steering_actuators = Vec::with_capacity(frames.len());
+        let mut wheel_actuators = Vec::with_capacity(frames.len());
+        for (steering, wheel) in frames.iter() {
+            let mut steering_hardware = MockActuator::new(NumberSpaceType::AngularLimited {
+                start_angle_in_radians: -PI,
+            });
+            model.set_actuator(
+                steering,
+                Actuator::new(&mut steering_hardware, &change_processor)?,
+            )?;
+            steering_actuators.push(steering_hardware);
+
+            let mut wheel_hardware = MockActuator::new(NumberSpaceType::LinearUnlimited);
+            model.set_actuator(
+                wheel,
+                Actuator::new(&mut wheel_hardware, &change_processor)?,
+            )?;
+            wheel_actuators.push(wheel_hardware);
+        }
+
+        Ok(Self {
+            model,
+            steering_frames: frames.iter().map(|(steering, _)| *steering).collect(),
+            wheels: frames.iter().map(|(_, wheel)| *wheel).collect(),
+            steering_actuators,
+            wheel_actuators,
+            change_processor,
+        })
+    }
+
+    /// Returns the hardware of the steering actuator of the given module.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'module' - The index of the module.
+    ///
+    /// ## Panics
+    ///
+    /// Panics when the vehicle has no module with the given index.
+    pub fn steering_actuator(&self, module: usize) -> &MockActuator {
+        &self.steering_actuators[module]
+    }
+
+    /// Returns the steering frames, in the order of the modules.
+    pub fn steering_frames(&self) -> &[FrameID] {
+        &self.steering_frames
+    }
+
+    /// Returns the hardware of the wheel actuator of the given module.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'module' - The index of the module.
+    ///
+    /// ## Panics
+    ///
+    /// Panics when the vehicle has no module with the given index.
+    pub fn wheel_actuator(&self, module: usize) -> &MockActuator {
+        &self.wheel_actuators[module]
+    }
+
+    /// Returns the wheels, in the order of the modules.
+    pub fn wheels(&self) -> &[FrameID] {
+        &self.wheels
+    }
+}
@@ -0,0 +1,106 @@
+use std::f64::consts::PI;
+
+use crate::{
+    change_notification_processing::HardwareChangeProcessor,
+    hardware::joint_state::JointState,
+    model_elements::frame_elements::{Actuator, JointSensor},
+    number_space::NumberSpaceType,
+    Error,
+};
+
+use super::{MockActuator, MockSensor, TestVehicle, TEST_WHEEL_RADIUS};
+
+#[test]
+fn when_creating_a_test_vehicle_it_should_connect_mock_actuators_to_all_joints() {
+    let mut vehicle = TestVehicle::rectangular_swerve(4, 2.0, 1.0).unwrap();
+    assert_eq!(4, vehicle.steering_frames().len());
+    assert_eq!(4, vehicle.wheels().len());
+    assert_eq!(4, vehicle.model().number_of_wheels());
+
+    for (index, wheel) in vehicle.wheels().iter().enumerate() {
+        let model = vehicle.model();
+        assert!(model.is_actuated(wheel));
+        assert!(model.is_actuated(&vehicle.steering_frames()[index]));
+        assert_eq!(Ok(TEST_WHEEL_RADIUS), model.wheel_radius(wheel));
+        assert_eq!(
+            Ok(&vehicle.steering_frames()[index]),
+            model.steering_frame_for_wheel(wheel)
+        );
+    }
+
+    vehicle.steering_actuator(1).send_position(0.5 * PI);
+    vehicle
+        .wheel_actuator(1)
+        .send_state(JointState::new(1.0, Some(2.0), None, None));
+    assert_eq!(
+        0.0,
+        vehicle
+            .model()
+            .joint_state_for(&vehicle.wheels()[1])
+            .unwrap()
+            .position()
+    );
+
+    assert_eq!(2, vehicle.process_pending());
+    let steering = vehicle.steering_frames()[1];
+    let wheel = vehicle.wheels()[1];
+    assert_eq!(
+        0.5 * PI,
+        vehicle
+            .model()
+            .joint_state_for(&steering)
+            .unwrap()
+            .position()
+    );
+    assert_eq!(
+        JointState::new(1.0, Some(2.0), None, None),
+        vehicle.model().joint_state_for(&wheel).unwrap()
+    );
+
+    assert!(matches!(
+        TestVehicle::rectangular_swerve(3, 2.0, 1.0),
+        Err(Error::InvalidTemplate { .. })
+    ));
+}
+
+#[test]
+fn when_sending_commands_to_a_mock_actuator_it_should_record_them() {
+    let processor = HardwareChangeProcessor::manual();
+    let mut hardware = MockActuator::new(NumberSpaceType::LinearUnlimited);
+    let actuator = Actuator::new(&mut hardware, &processor).unwrap();
+    assert!(hardware.commands().is_empty());
+
+    actuator
+        .update_state(JointState::new(1.0, None, None, None))
+        .unwrap();
+    actuator
+        .update_state(JointState::new(2.0, Some(0.5), None, None))
+        .unwrap();
+    assert_eq!(
+        vec![
+            JointState::new(1.0, None, None, None),
+            JointState::new(2.0, Some(0.5), None, None)
+        ],
+        hardware.commands()
+    );
+    assert!(hardware.commands().is_empty());
+
+    hardware.send_position(3.0);
+    assert_eq!(1, processor.process_pending());
+    assert_eq!(3.0, actuator.value().unwrap().position());
+}
+
+#[test]
+fn when_sending_states_to_a_mock_sensor_it_should_report_them() {
+    let processor = HardwareChangeProcessor::manual();
+    let mut hardware = MockSensor::new(NumberSpaceType::LinearUnlimited);
+    let sensor = JointSensor::new(&mut hardware, &processor).unwrap();
+
+    hardware.send_position(-1.5);
+    hardware.send_state(JointState::new(2.5, Some(1.0), None, None));
+    assert_eq!(2, processor.process_pending());
+    assert_eq!(
+        JointState::new(2.5, Some(1.0), None, None),
+        sensor.value().unwrap()
+    );
+}
@@ -0,0 +1,318 @@
+//! Provides the functions used to export test vectors for the kinematics of a [MotionModel].
+//!
+//! A test vector consists of the positions of all the joints of the model and the transforms from
+//! each frame element to the body that follow from those joint positions. Implementations of the
+//! same kinematics, e.g. in the firmware of an embedded controller, can be validated by loading
+//! the test vectors, computing the transforms from the joint positions and comparing the results
+//! with the expected transforms.
+//!
+//! The test vectors are exported as JSON with the following layout:
+//!
+//! ```text
+//! {
+//!   "frames": [
+//!     { "name": "body", "parent": null, "degree_of_freedom": "Static" },
+//!     { "name": "steering", "parent": 0, "degree_of_freedom": "RevoluteZ" },
+//!     ...
+//!   ],
+//!   "test_vectors": [
+//!     {
+//!       "joint_positions": [0, 0.7853981633974483, ...],
+//!       "transforms_to_body": [[1, 0, 0, 1, 0, 1, 0, 0.5, 0, 0, 1, 0, 0, 0, 0, 1], ...]
+//!     },
+//!     ...
+//!   ]
+//! }
+//! ```
+//!
+//! The frame elements are listed depth-first, starting at the body, and the parent of a frame
+//! element is the index of the parent in the list. The joint positions and the transforms are
+//! listed in the same order as the frame elements. The joint positions of static frame elements
+//! are always zero. The transforms are 4x4 homogeneous matrices stored in row-major order. The
+//! numbers are written with enough digits to recover the exact value.
+
+use std::{collections::HashMap, f64::consts::FRAC_PI_4, fmt::Write};
+
+use nalgebra::Matrix4;
+
+use crate::{
+    dot::escape,
+    model_elements::{
+        frame_elements::{FrameDofType, FrameID},
+        model::MotionModel,
+    },
+    Error,
+};
+
+#[cfg(test)]
+#[path = "test_vectors_tests.rs"]
+mod test_vectors_tests;
+
+/// The position, in radians, used for revolute joints in the canonical test vectors.
+const CANONICAL_REVOLUTE_POSITION: f64 = FRAC_PI_4;
+
+/// The position, in meters, used for prismatic joints in the canonical test vectors.
+const CANONICAL_PRISMATIC_POSITION: f64 = 0.05;
+
+/// Stores the joint positions of a model and the transforms from each frame element to the body
+/// that follow from those joint positions.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TestVector {
+    /// The positions of the joints. Joints that are not included are at their zero position.
+    joint_positions: HashMap<FrameID, f64>,
+
+    /// The transforms from each frame element to the body.
+    transforms_to_body: HashMap<FrameID, Matrix4<f64>>,
+}
+
+impl TestVector {
+    /// Returns the position of the given joint, or zero if the test vector does not include a
+    /// position for the joint.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'frame_id' - The [FrameID] of the joint.
+    pub fn joint_position(&self, frame_id: &FrameID) -> f64 {
+        self.joint_positions.get(frame_id).copied().unwrap_or(0.0)
+    }
+
+    /// Returns the positions of the joints, stored by the [FrameID] of the joint.
+    pub fn joint_positions(&self) -> &HashMap<FrameID, f64> {
+        &self.joint_positions
+    }
+
+    /// Returns the transform from the given frame element to the body, or [None] if the frame
+    /// element is not part of the model for which the test vector was created.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'frame_id' - The [FrameID] of the frame element.
+    pub fn transform_to_body(&self, frame_id: &FrameID) -> Option<&Matrix4<f64>> {
+        self.transforms_to_body.get(frame_id)
+    }
+
+    /// Returns the transforms from each frame element to the body, stored by the [FrameID] of
+    /// the frame element.
+    pub fn transforms_to_body(&self) -> &HashMap<FrameID, Matrix4<f64>> {
+        &self.transforms_to_body
+    }
+}
+
+impl MotionModel {
+    /// Returns a canonical set of test vectors for the model.
+    ///
+    /// The set contains a test vector with all the joints at their zero position, a test vector
+    /// for each positive and negative position of each moving joint, with all the other joints at
+    /// their zero position, and a test vector in which all the moving joints are at a different
+    /// position. Revolute joints are moved by up to π/4 radians and prismatic joints by up to
+    /// 0.05 meters.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the model has no body.
+    pub fn canonical_test_vectors(&self) -> Result<Vec<TestVector>, Error> {
+        let joints: Vec<(FrameID, f64)> = self
+            .frames_depth_first()?
+            .into_iter()
+            .filter_map(|frame_id| {
+                let position = match self.frame_degree_of_freedom(&frame_id) {
+                    Ok(dof) if dof.is_revolute() => CANONICAL_REVOLUTE_POSITION,
+                    Ok(dof) if dof.is_prismatic() => CANONICAL_PRISMATIC_POSITION,
+                    _ => return None,
+                };
+                Some((frame_id, position))
+            })
+            .collect();
+
+        let mut vectors = vec![self.test_vector(&HashMap::new())?];
+        for (frame_id, position) in joints.iter() {
+            for sign in [1.0, -1.0] {
+                let joint_positions = HashMap::from([(*frame_id, sign * position)]);
+                vectors.push(self.test_vector(&joint_positions)?);
+            }
+        }
+
+        // Give each joint a different position so that swapped joints are detected
+        let number_of_joints = joints.len() as f64;
+        let joint_positions = joints
+            .iter()
+            .enumerate()
+            .map(|(index, (frame_id, position))| {
+                let sign = if index % 2 == 0 { 1.0 } else { -1.0 };
+                let fraction = (index + 1) as f64 / number_of_joints;
+                (*frame_id, sign * fraction * position)
+            })
+            .collect();
+        vectors.push(self.test_vector(&joint_positions)?);
+
+        Ok(vectors)
+    }
+
+    /// Returns the test vector for the given joint positions.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'joint_positions' - The positions of the joints, stored by the [FrameID] of the joint.
+    ///   Joints that are not included are assumed to be at their zero position.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the model has no body.
+    /// * [Error::InvalidFrameID] - Returned when a joint position is provided for a frame element
+    ///   that is not part of the model or that can not move relative to its parent.
+    pub fn test_vector(
+        &self,
+        joint_positions: &HashMap<FrameID, f64>,
+    ) -> Result<TestVector, Error> {
+        let body = *self.body()?;
+        for frame_id in joint_positions.keys() {
+            if !self.is_moving_joint(frame_id) {
+                return Err(Error::InvalidFrameID { id: *frame_id });
+            }
+        }
+
+        let mut transforms_to_body = HashMap::new();
+        for frame_id in self.frames_depth_first()? {
+            let transform =
+                self.homogeneous_transform_to_ancestor_with(&frame_id, &body, |id| {
+                    joint_positions.get(id).copied()
+                })?;
+            transforms_to_body.insert(frame_id, transform);
+        }
+
+        Ok(TestVector {
+            joint_positions: joint_positions.clone(),
+            transforms_to_body,
+        })
+    }
+
+    /// Returns the JSON description of the given test vectors.
+    ///
+    /// Numbers that are not finite are written as `null`.
+    ///
+    /// ## Parameters
+    ///
+    /// * 'vectors' - The test vectors that should be exported. The test vectors should have been
+    ///   created for this model.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the model has no body.
+    /// * [Error::InvalidFrameID] - Returned when a test vector was not created for this model.
+    pub fn test_vectors_to_json(&self, vectors: &[TestVector]) -> Result<String, Error> {
+        let frames = self.frames_depth_first()?;
+        let indices: HashMap<FrameID, usize> = frames
+            .iter()
+            .enumerate()
+            .map(|(index, frame_id)| (*frame_id, index))
+            .collect();
+
+        let mut json = String::new();
+        // Writing to a String never fails so the results of 'write!' can be ignored.
+        let _ = writeln!(json, "{{");
+        let _ = writeln!(json, "  \"frames\": [");
+        for (index, frame_id) in frames.iter().enumerate() {
+            let frame = self.reference_frame(frame_id)?;
+            let parent = if self.is_body(frame_id) {
+                "null".to_string()
+            } else {
+                let parent_id = self.parent_of(frame_id)?;
+                match indices.get(parent_id) {
+                    Some(parent_index) => parent_index.to_string(),
+                    None => return Err(Error::InvalidFrameID { id: *parent_id }),
+                }
+            };
+
+            let _ = writeln!(
+                json,
+                "    {{ \"name\": \"{}\", \"parent\": {}, \"degree_of_freedom\": \"{:?}\" }}{}",
+                escape(frame.name()),
+                parent,
+                frame.degree_of_freedom_kind(),
+                separator(index, frames.len())
+            );
+        }
+        let _ = writeln!(json, "  ],");
+
+        let _ = writeln!(json, "  \"test_vectors\": [");
+        for (vector_index, vector) in vectors.iter().enumerate() {
+            let mut joint_positions = Vec::with_capacity(frames.len());
+            let mut transforms = Vec::with_capacity(frames.len());
+            for frame_id in frames.iter() {
+                let transform = vector
+                    .transform_to_body(frame_id)
+                    .ok_or(Error::InvalidFrameID { id: *frame_id })?;
+
+                joint_positions.push(number(vector.joint_position(frame_id)));
+
+                // nalgebra stores matrices in column-major order
+                let elements: Vec<String> =
+                    transform.transpose().iter().map(|v| number(*v)).collect();
+                transforms.push(format!("[{}]", elements.join(", ")));
+            }
+
+            let _ = writeln!(json, "    {{");
+            let _ = writeln!(
+                json,
+                "      \"joint_positions\": [{}],",
+                joint_positions.join(", ")
+            );
+            let _ = writeln!(
+                json,
+                "      \"transforms_to_body\": [{}]",
+                transforms.join(", ")
+            );
+            let _ = writeln!(json, "    }}{}", separator(vector_index, vectors.len()));
+        }
+        let _ = writeln!(json, "  ]");
+        let _ = writeln!(json, "}}");
+
+        Ok(json)
+    }
+
+    /// Returns the [FrameID] of all the frame elements, depth-first starting at the body.
+    fn frames_depth_first(&self) -> Result<Vec<FrameID>, Error> {
+        let mut result = vec![];
+        let mut stack = vec![*self.body()?];
+        while let Some(frame_id) = stack.pop() {
+            // Push in reverse order so that the children are visited in order
+            for child in self.children_of(&frame_id)?.iter().rev() {
+                stack.push(**child);
+            }
+
+            result.push(frame_id);
+        }
+
+        Ok(result)
+    }
+
+    /// Returns a value indicating whether the given frame element can move relative to its
+    /// parent.
+    fn is_moving_joint(&self, frame_id: &FrameID) -> bool {
+        self.kinematics_table(frame_id).is_some()
+            || matches!(
+                self.frame_degree_of_freedom(frame_id),
+                Ok(dof) if dof != FrameDofType::Static
+            )
+    }
+}
+
+/// Returns the JSON representation of the given number. Negative zero is written as '0' and
+/// numbers that are not finite are written as 'null'.
+fn number(value: f64) -> String {
+    if value.is_finite() {
+        // The Display implementation writes the shortest representation that round trips
+        format!("{}", value + 0.0)
+    } else {
+        "null".to_string()
+    }
+}
+
+/// Returns the separator that follows the element at the given index in a JSON list.
+fn separator(index: usize, length: usize) -> &'static str {
+    if index + 1 < length {
+        ","
+    } else {
+        ""
+    }
+}
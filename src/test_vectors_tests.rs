@@ -0,0 +1,128 @@
+use std::{collections::HashMap, f64::consts::FRAC_PI_2};
+
+use crate::{model_elements::model::MotionModel, test_fixtures::two_module_model, Error};
+
+use super::{escape, number};
+
+#[test]
+fn when_creating_a_test_vector_it_should_compute_the_transforms_at_the_joint_positions() {
+    let (model, frames) = two_module_model();
+    let steering = *frames[1].steering();
+    let wheel = *frames[1].wheel();
+
+    let vector = model
+        .test_vector(&HashMap::from([(steering, FRAC_PI_2)]))
+        .unwrap();
+    assert_eq!(FRAC_PI_2, vector.joint_position(&steering));
+    assert_eq!(0.0, vector.joint_position(&wheel));
+    assert_eq!(6, vector.transforms_to_body().len());
+
+    // The steering joint rotates around the z-axis of its parent, i.e. the body
+    let transform = vector.transform_to_body(&wheel).unwrap();
+    assert!((0.5 - transform[(0, 3)]).abs() < 1e-12);
+    assert!((1.0 - transform[(1, 3)]).abs() < 1e-12);
+    assert!((-0.25 - transform[(2, 3)]).abs() < 1e-12);
+
+    let current = model
+        .homogeneous_transform_to_body(frames[0].wheel())
+        .unwrap();
+    assert_eq!(
+        &current,
+        vector.transform_to_body(frames[0].wheel()).unwrap()
+    );
+}
+
+#[test]
+fn when_creating_a_test_vector_for_a_static_frame_it_should_error() {
+    let (model, _) = two_module_model();
+    let body = *model.body().unwrap();
+
+    let result = model.test_vector(&HashMap::from([(body, 1.0)]));
+    assert_eq!(Err(Error::InvalidFrameID { id: body }), result);
+
+    assert!(MotionModel::new().test_vector(&HashMap::new()).is_err());
+}
+
+#[test]
+fn when_creating_the_canonical_test_vectors_it_should_move_each_joint() {
+    let (model, frames) = two_module_model();
+
+    // One suspension, two steering and two wheel joints
+    let vectors = model.canonical_test_vectors().unwrap();
+    assert_eq!(1 + 2 * 5 + 1, vectors.len());
+
+    assert!(vectors[0].joint_positions().is_empty());
+
+    let suspension = frames[0].suspension().unwrap();
+    assert_eq!(0.05, vectors[1].joint_position(suspension));
+    assert_eq!(-0.05, vectors[2].joint_position(suspension));
+    assert_eq!(1, vectors[1].joint_positions().len());
+
+    let combined = vectors.last().unwrap();
+    assert_eq!(5, combined.joint_positions().len());
+    assert!(combined.joint_positions().values().all(|p| *p != 0.0));
+}
+
+#[test]
+fn when_exporting_test_vectors_it_should_write_valid_json() {
+    let (model, frames) = two_module_model();
+    let vectors = model.canonical_test_vectors().unwrap();
+
+    let json = model.test_vectors_to_json(&vectors).unwrap();
+    let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+    let exported_frames = value["frames"].as_array().unwrap();
+    assert_eq!(6, exported_frames.len());
+    assert_eq!("body", exported_frames[0]["name"]);
+    assert!(exported_frames[0]["parent"].is_null());
+    assert_eq!("Static", exported_frames[0]["degree_of_freedom"]);
+    assert_eq!("left_suspension", exported_frames[1]["name"]);
+    assert_eq!(0, exported_frames[1]["parent"]);
+    assert_eq!("left_steering", exported_frames[2]["name"]);
+    assert_eq!(1, exported_frames[2]["parent"]);
+    assert_eq!("RevoluteZ", exported_frames[2]["degree_of_freedom"]);
+
+    let exported_vectors = value["test_vectors"].as_array().unwrap();
+    assert_eq!(vectors.len(), exported_vectors.len());
+
+    // The transforms are stored in row-major order, in the order of the frames
+    let combined = vectors.last().unwrap();
+    let exported = exported_vectors.last().unwrap();
+    let steering = frames[0].steering();
+    assert_eq!(
+        combined.joint_position(steering),
+        exported["joint_positions"][2].as_f64().unwrap()
+    );
+
+    let transform = combined.transform_to_body(steering).unwrap();
+    let exported_transform = exported["transforms_to_body"][2].as_array().unwrap();
+    assert_eq!(16, exported_transform.len());
+    for row in 0..4 {
+        for column in 0..4 {
+            assert_eq!(
+                transform[(row, column)],
+                exported_transform[4 * row + column].as_f64().unwrap()
+            );
+        }
+    }
+}
+
+#[test]
+fn when_exporting_a_test_vector_from_another_model_it_should_error() {
+    let (model, _) = two_module_model();
+    let (other, _) = two_module_model();
+    let vectors = other.canonical_test_vectors().unwrap();
+
+    assert!(model.test_vectors_to_json(&vectors).is_err());
+}
+
+#[test]
+fn when_formatting_json_values_it_should_escape_and_round_trip() {
+    assert_eq!("a \\\"quoted\\\" name", escape("a \"quoted\" name"));
+    assert_eq!("tab\\tand\\u0001", escape("tab\tand\u{1}"));
+
+    assert_eq!("0", number(-0.0));
+    assert_eq!("null", number(f64::NAN));
+    assert_eq!("null", number(f64::INFINITY));
+    assert_eq!(0.1 + 0.2, number(0.1 + 0.2).parse::<f64>().unwrap());
+}
@@ -0,0 +1,168 @@
+//! Provides typed wrappers for the positions and velocities of joints.
+//!
+//! The [JointState](crate::hardware::joint_state::JointState) stores its values as plain numbers
+//! in SI units, i.e. radians for revolute joints and meters for prismatic joints. Plain numbers
+//! make it easy to pass a value in degrees where radians are expected. The types in this module
+//! carry the unit of a value, so that the compiler catches these mistakes. Values in degrees
+//! are converted to radians with the [From] implementations, e.g.
+//!
+//! ```
+//! use std::f64::consts::PI;
+//! use swerve_vehicle_descriptors::hardware::joint_state::JointState;
+//! use swerve_vehicle_descriptors::units::{Degrees, Radians};
+//!
+//! let state = JointState::new_angular(Degrees::new(90.0), None);
+//! assert!((0.5 * PI - state.angular_position().value()).abs() < 1e-9);
+//!
+//! let radians: Radians = Degrees::new(180.0).into();
+//! assert!((PI - radians.value()).abs() < 1e-9);
+//! ```
+//!
+//! The wrappers support the arithmetic that preserves the unit, i.e. adding and subtracting
+//! values with the same unit and scaling a value by a plain number.
+
+use std::{
+    fmt::Display,
+    ops::{Add, Div, Mul, Neg, Sub},
+};
+
+#[cfg(test)]
+#[path = "units_tests.rs"]
+mod units_tests;
+
+/// Defines a wrapper type around an [f64] for a value with the given unit.
+macro_rules! unit {
+    ($(#[$attr:meta])* $name:ident, $symbol:literal) => {
+        $(#[$attr])*
+        #[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+        #[cfg_attr(
+            feature = "serde",
+            derive(serde::Serialize, serde::Deserialize),
+            serde(transparent)
+        )]
+        pub struct $name(f64);
+
+        impl $name {
+            /// Creates a new instance with the given value.
+            ///
+            /// ## Parameters
+            ///
+            /// * 'value' - The value in the unit of the type.
+            pub const fn new(value: f64) -> Self {
+                Self(value)
+            }
+
+            /// Returns the value in the unit of the type.
+            pub const fn value(&self) -> f64 {
+                self.0
+            }
+        }
+
+        impl Add for $name {
+            type Output = Self;
+
+            fn add(self, other: Self) -> Self {
+                Self(self.0 + other.0)
+            }
+        }
+
+        impl Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "{} {}", self.0, $symbol)
+            }
+        }
+
+        impl Div<f64> for $name {
+            type Output = Self;
+
+            fn div(self, divisor: f64) -> Self {
+                Self(self.0 / divisor)
+            }
+        }
+
+        impl Mul<f64> for $name {
+            type Output = Self;
+
+            fn mul(self, factor: f64) -> Self {
+                Self(self.0 * factor)
+            }
+        }
+
+        impl Neg for $name {
+            type Output = Self;
+
+            fn neg(self) -> Self {
+                Self(-self.0)
+            }
+        }
+
+        impl Sub for $name {
+            type Output = Self;
+
+            fn sub(self, other: Self) -> Self {
+                Self(self.0 - other.0)
+            }
+        }
+    };
+}
+
+unit!(
+    /// An angle in degrees. Convert to [Radians] before using the angle in a joint state.
+    Degrees,
+    "deg"
+);
+
+unit!(
+    /// An angular velocity in degrees per second. Convert to [RadiansPerSecond] before using
+    /// the velocity in a joint state.
+    DegreesPerSecond,
+    "deg/s"
+);
+
+unit!(
+    /// A distance in meters, e.g. the position of a prismatic joint.
+    Meters,
+    "m"
+);
+
+unit!(
+    /// A linear velocity in meters per second, e.g. the velocity of a prismatic joint.
+    MetersPerSecond,
+    "m/s"
+);
+
+unit!(
+    /// An angle in radians, e.g. the position of a revolute joint.
+    Radians,
+    "rad"
+);
+
+unit!(
+    /// An angular velocity in radians per second, e.g. the velocity of a revolute joint.
+    RadiansPerSecond,
+    "rad/s"
+);
+
+impl From<Degrees> for Radians {
+    fn from(angle: Degrees) -> Self {
+        Self(angle.0.to_radians())
+    }
+}
+
+impl From<Radians> for Degrees {
+    fn from(angle: Radians) -> Self {
+        Self(angle.0.to_degrees())
+    }
+}
+
+impl From<DegreesPerSecond> for RadiansPerSecond {
+    fn from(velocity: DegreesPerSecond) -> Self {
+        Self(velocity.0.to_radians())
+    }
+}
+
+impl From<RadiansPerSecond> for DegreesPerSecond {
+    fn from(velocity: RadiansPerSecond) -> Self {
+        Self(velocity.0.to_degrees())
+    }
+}
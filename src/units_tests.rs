@@ -0,0 +1,39 @@
+use std::f64::consts::PI;
+
+use super::{Degrees, DegreesPerSecond, Meters, MetersPerSecond, Radians, RadiansPerSecond};
+
+#[test]
+fn when_converting_degrees_it_should_return_the_angle_in_radians() {
+    let radians: Radians = Degrees::new(90.0).into();
+    assert!((0.5 * PI - radians.value()).abs() < 1e-12);
+
+    let degrees: Degrees = Radians::new(PI).into();
+    assert!((180.0 - degrees.value()).abs() < 1e-12);
+
+    let velocity: RadiansPerSecond = DegreesPerSecond::new(-360.0).into();
+    assert!((-2.0 * PI - velocity.value()).abs() < 1e-12);
+
+    let velocity: DegreesPerSecond = RadiansPerSecond::new(0.5 * PI).into();
+    assert!((90.0 - velocity.value()).abs() < 1e-12);
+}
+
+#[test]
+fn when_combining_values_it_should_keep_the_unit() {
+    let distance = Meters::new(1.5) + Meters::new(0.5) - Meters::new(1.0);
+    assert_eq!(Meters::new(1.0), distance);
+    assert_eq!(Meters::new(-2.0), -distance * 2.0);
+    assert_eq!(Meters::new(0.25), distance / 4.0);
+
+    assert!(MetersPerSecond::new(1.0) < MetersPerSecond::new(2.0));
+    assert_eq!(Radians::new(0.0), Radians::default());
+}
+
+#[test]
+fn when_displaying_a_value_it_should_include_the_unit() {
+    assert_eq!("1.5 m", Meters::new(1.5).to_string());
+    assert_eq!("2 m/s", MetersPerSecond::new(2.0).to_string());
+    assert_eq!("0.5 rad", Radians::new(0.5).to_string());
+    assert_eq!("-1 rad/s", RadiansPerSecond::new(-1.0).to_string());
+    assert_eq!("90 deg", Degrees::new(90.0).to_string());
+    assert_eq!("45 deg/s", DegreesPerSecond::new(45.0).to_string());
+}
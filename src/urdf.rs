@@ -0,0 +1,433 @@
+//! Provides the functions used to export a [MotionModel] as a
+//! [URDF](http://wiki.ros.org/urdf/XML) description.
+//!
+//! Each frame element is exported as a URDF link with the mass, center of mass and moment of
+//! inertia of the element. Each connection between a frame element and its parent is exported
+//! as a URDF joint. The body frame element is the root link of the URDF model. The
+//! [visual](crate::model_elements::visual) and [collision](crate::model_elements::collision)
+//! geometry of the elements is exported as the visual and collision elements of the links.
+//!
+//! The [MotionModel] applies the motion of a joint in the reference frame of the parent element,
+//! i.e. a revolute joint rotates the child element around an axis that passes through the origin
+//! of the parent frame. URDF applies the motion of a joint in the reference frame of the joint
+//! origin. Prismatic joints and revolute joints without an offset from the parent origin can be
+//! described by a single URDF joint. Revolute joints with an offset from the parent origin are
+//! exported as a moving joint, located at the origin of the parent frame, connected to an
+//! additional link which is connected to the child link by a fixed joint.
+//!
+//! The model does not store the limits of the joints. Revolute joints are exported as
+//! `continuous` joints and prismatic joints are given a placeholder limit.
+
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Write,
+};
+
+use nalgebra::{Isometry3, Vector3};
+
+use crate::{
+    model_elements::{
+        collision::CollisionShape,
+        frame_elements::{FrameDofType, FrameID},
+        model::MotionModel,
+    },
+    Error,
+};
+
+#[cfg(test)]
+#[path = "urdf_tests.rs"]
+mod urdf_tests;
+
+/// The placeholder range, in meters, used for the limits of prismatic joints.
+const PRISMATIC_JOINT_PLACEHOLDER_LIMIT: f64 = 1000.0;
+
+/// The suffix added to the name of a link to create the name of the joint that connects the
+/// link to its parent.
+pub(crate) const JOINT_SUFFIX: &str = "_joint";
+
+/// The suffix added to the name of a link to create the name of the additional link that is
+/// used for revolute joints with an offset.
+const MOTION_LINK_SUFFIX: &str = "_motion";
+
+/// The suffix added to the name of a link to create the name of the fixed joint that connects
+/// the additional link to the link for revolute joints with an offset.
+const OFFSET_JOINT_SUFFIX: &str = "_offset";
+
+/// The smallest translation, in meters, that is considered to be an offset from the
+/// parent origin.
+const OFFSET_TOLERANCE: f64 = 1e-12;
+
+impl MotionModel {
+    /// Returns the URDF description of the model.
+    ///
+    /// The name of the robot and the root link is the name of the body. Because URDF requires
+    /// unique link names, elements that share a name are given a numerical suffix.
+    ///
+    /// The joints are exported at their zero position. For elements with a
+    /// [JointKinematicsTable](crate::model_elements::kinematics_table::JointKinematicsTable)
+    /// the transform of the table at the zero position is used.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the model has no body.
+    pub fn to_urdf(&self) -> Result<String, Error> {
+        let body = self.body()?;
+        let names: HashMap<FrameID, String> = self.urdf_link_names()?.into_iter().collect();
+        let name_of = |id: &FrameID| names.get(id).cloned().unwrap_or_default();
+        let robot_name = name_of(body);
+
+        let mut urdf = String::new();
+        // Writing to a String never fails so the results of 'writeln!' can be ignored.
+        let _ = writeln!(urdf, "<?xml version=\"1.0\"?>");
+        let _ = writeln!(urdf, "<robot name=\"{}\">", escape(&robot_name));
+        self.write_link(&mut urdf, body, &robot_name)?;
+
+        let mut stack = vec![*body];
+        while let Some(parent) = stack.pop() {
+            let parent_name = name_of(&parent);
+            let children = self.children_of(&parent)?;
+
+            // Push in reverse order so that the children are written in order
+            for child in children.iter().rev() {
+                stack.push(**child);
+            }
+
+            for child in children {
+                let child_name = name_of(child);
+                self.write_link(&mut urdf, child, &child_name)?;
+                self.write_joint(&mut urdf, child, &parent_name, &child_name)?;
+            }
+        }
+
+        let _ = writeln!(urdf, "</robot>");
+        Ok(urdf)
+    }
+
+    /// Returns the unique URDF link name of each frame element, in the order in which the links
+    /// are written by [MotionModel::to_urdf]. The name of the joint that connects a link to its
+    /// parent is the name of the link followed by `_joint`.
+    ///
+    /// ## Errors
+    ///
+    /// * [Error::MissingFrameElement] - Returned when the model has no body.
+    pub(crate) fn urdf_link_names(&self) -> Result<Vec<(FrameID, String)>, Error> {
+        let body = self.body()?;
+        let mut names = LinkNames::new();
+        let mut result = vec![(*body, names.name_for(self.reference_frame(body)?.name()))];
+
+        let mut stack = vec![*body];
+        while let Some(parent) = stack.pop() {
+            let children = self.children_of(&parent)?;
+            for child in children.iter().rev() {
+                stack.push(**child);
+            }
+
+            for child in children {
+                let name = names.name_for(self.reference_frame(child)?.name());
+                result.push((*child, name));
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Writes the URDF joint, or joints, that connect the given element to its parent.
+    fn write_joint(
+        &self,
+        urdf: &mut String,
+        frame_id: &FrameID,
+        parent_name: &str,
+        child_name: &str,
+    ) -> Result<(), Error> {
+        let dof = self.frame_degree_of_freedom(frame_id)?;
+        let transform = match self.kinematics_table(frame_id) {
+            Some(table) => table.transform_at(0.0),
+            None => *self.static_transform_to_parent(frame_id)?,
+        };
+        let joint_name = format!("{}{}", child_name, JOINT_SUFFIX);
+
+        let axis = match axis_for(dof) {
+            Some(a) => a,
+            None => {
+                write_joint_element(
+                    urdf,
+                    &joint_name,
+                    "fixed",
+                    parent_name,
+                    child_name,
+                    &transform,
+                    None,
+                );
+                return Ok(());
+            }
+        };
+
+        let is_revolute = dof.is_revolute();
+        if is_revolute && transform.translation.vector.norm() > OFFSET_TOLERANCE {
+            // The joint rotates around the parent origin so it cannot be combined with the
+            // offset of the child element.
+            let motion_link_name = format!("{}{}", child_name, MOTION_LINK_SUFFIX);
+            let _ = writeln!(urdf, "  <link name=\"{}\"/>", escape(&motion_link_name));
+            write_joint_element(
+                urdf,
+                &joint_name,
+                "continuous",
+                parent_name,
+                &motion_link_name,
+                &Isometry3::identity(),
+                Some(&axis),
+            );
+            write_joint_element(
+                urdf,
+                &format!("{}{}", child_name, OFFSET_JOINT_SUFFIX),
+                "fixed",
+                &motion_link_name,
+                child_name,
+                &transform,
+                None,
+            );
+            return Ok(());
+        }
+
+        // The motion is applied in the parent frame, which, for joints without an offset,
+        // is equal to applying the motion around the axis rotated into the child frame.
+        let child_axis = transform.rotation.inverse_transform_vector(&axis);
+        let joint_type = if is_revolute {
+            "continuous"
+        } else {
+            "prismatic"
+        };
+        write_joint_element(
+            urdf,
+            &joint_name,
+            joint_type,
+            parent_name,
+            child_name,
+            &transform,
+            Some(&child_axis),
+        );
+
+        Ok(())
+    }
+
+    /// Writes the URDF link for the given element.
+    fn write_link(&self, urdf: &mut String, frame_id: &FrameID, name: &str) -> Result<(), Error> {
+        let element = self.chassis_element(frame_id)?;
+        let com = element.center_of_mass();
+        let inertia = element.moment_of_inertia();
+
+        let _ = writeln!(urdf, "  <link name=\"{}\">", escape(name));
+        let _ = writeln!(urdf, "    <inertial>");
+        let _ = writeln!(
+            urdf,
+            "      <origin xyz=\"{} {} {}\" rpy=\"0 0 0\"/>",
+            com.x, com.y, com.z
+        );
+        let _ = writeln!(urdf, "      <mass value=\"{}\"/>", element.mass_in_kg());
+        let _ = writeln!(
+            urdf,
+            "      <inertia ixx=\"{}\" ixy=\"{}\" ixz=\"{}\" iyy=\"{}\" iyz=\"{}\" izz=\"{}\"/>",
+            inertia[(0, 0)],
+            inertia[(0, 1)],
+            inertia[(0, 2)],
+            inertia[(1, 1)],
+            inertia[(1, 2)],
+            inertia[(2, 2)]
+        );
+        let _ = writeln!(urdf, "    </inertial>");
+
+        for visual in self.visual_geometries(frame_id) {
+            let _ = writeln!(urdf, "    <visual>");
+            write_origin_element(urdf, visual.origin());
+            write_geometry_element(urdf, visual.shape());
+            if let Some(material) = visual.material() {
+                let [r, g, b, a] = material.rgba();
+                let _ = writeln!(
+                    urdf,
+                    "      <material name=\"{}\">",
+                    escape(material.name())
+                );
+                let _ = writeln!(urdf, "        <color rgba=\"{} {} {} {}\"/>", r, g, b, a);
+                let _ = writeln!(urdf, "      </material>");
+            }
+            let _ = writeln!(urdf, "    </visual>");
+        }
+
+        for collision in self.collision_geometries(frame_id) {
+            let _ = writeln!(urdf, "    <collision>");
+            write_origin_element(urdf, collision.origin());
+            write_geometry_element(urdf, collision.shape());
+            let _ = writeln!(urdf, "    </collision>");
+        }
+
+        let _ = writeln!(urdf, "  </link>");
+
+        Ok(())
+    }
+}
+
+/// Tracks the URDF names of the links to ensure that they are unique.
+struct LinkNames {
+    /// The next suffix to try for each of the requested names.
+    counts: HashMap<String, usize>,
+
+    /// The names that have been handed out so far.
+    used: HashSet<String>,
+}
+
+impl LinkNames {
+    /// Returns a unique URDF name for a frame element with the given name.
+    fn name_for(&mut self, name: &str) -> String {
+        let base = if name.is_empty() { "link" } else { name };
+        let count = self.counts.entry(base.to_string()).or_insert(0);
+
+        // A generated name may match the name of another element, e.g. 'wheel_1', so keep
+        // going until the name is not in use.
+        let mut unique = base.to_string();
+        while self.used.contains(&unique) {
+            *count += 1;
+            unique = format!("{}_{}", base, count);
+        }
+
+        self.used.insert(unique.clone());
+        unique
+    }
+
+    /// Creates a new [LinkNames] instance.
+    fn new() -> Self {
+        Self {
+            counts: HashMap::new(),
+            used: HashSet::new(),
+        }
+    }
+}
+
+/// Returns the axis of motion for the given degree of freedom, or [None] if the element
+/// is static.
+pub(crate) fn axis_for(dof: FrameDofType) -> Option<Vector3<f64>> {
+    dof.axis()
+}
+
+/// Returns the given text with the XML special characters escaped.
+pub(crate) fn escape(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => result.push_str("&amp;"),
+            '<' => result.push_str("&lt;"),
+            '>' => result.push_str("&gt;"),
+            '"' => result.push_str("&quot;"),
+            '\'' => result.push_str("&apos;"),
+            _ => result.push(c),
+        }
+    }
+
+    result
+}
+
+/// Returns the given number without the sign if the number is zero, so that negative zero
+/// is written as '0'.
+pub(crate) fn number(value: f64) -> f64 {
+    value + 0.0
+}
+
+/// Writes a URDF geometry element for the given shape.
+fn write_geometry_element(urdf: &mut String, shape: &CollisionShape) {
+    let _ = writeln!(urdf, "      <geometry>");
+    match shape {
+        CollisionShape::Box { size } => {
+            let _ = writeln!(
+                urdf,
+                "        <box size=\"{} {} {}\"/>",
+                size.x, size.y, size.z
+            );
+        }
+        CollisionShape::Cylinder { radius, length } => {
+            let _ = writeln!(
+                urdf,
+                "        <cylinder radius=\"{}\" length=\"{}\"/>",
+                radius, length
+            );
+        }
+        CollisionShape::Mesh { uri, scale } => {
+            let _ = writeln!(
+                urdf,
+                "        <mesh filename=\"{}\" scale=\"{} {} {}\"/>",
+                escape(uri),
+                scale.x,
+                scale.y,
+                scale.z
+            );
+        }
+        CollisionShape::Sphere { radius } => {
+            let _ = writeln!(urdf, "        <sphere radius=\"{}\"/>", radius);
+        }
+    }
+    let _ = writeln!(urdf, "      </geometry>");
+}
+
+/// Writes a URDF joint element.
+fn write_joint_element(
+    urdf: &mut String,
+    name: &str,
+    joint_type: &str,
+    parent: &str,
+    child: &str,
+    transform: &Isometry3<f64>,
+    axis: Option<&Vector3<f64>>,
+) {
+    let translation = transform.translation.vector;
+    let (roll, pitch, yaw) = transform.rotation.euler_angles();
+
+    let _ = writeln!(
+        urdf,
+        "  <joint name=\"{}\" type=\"{}\">",
+        escape(name),
+        joint_type
+    );
+    let _ = writeln!(urdf, "    <parent link=\"{}\"/>", escape(parent));
+    let _ = writeln!(urdf, "    <child link=\"{}\"/>", escape(child));
+    let _ = writeln!(
+        urdf,
+        "    <origin xyz=\"{} {} {}\" rpy=\"{} {} {}\"/>",
+        number(translation.x),
+        number(translation.y),
+        number(translation.z),
+        number(roll),
+        number(pitch),
+        number(yaw)
+    );
+    if let Some(a) = axis {
+        let _ = writeln!(
+            urdf,
+            "    <axis xyz=\"{} {} {}\"/>",
+            number(a.x),
+            number(a.y),
+            number(a.z)
+        );
+    }
+    if joint_type == "prismatic" {
+        let _ = writeln!(
+            urdf,
+            "    <limit lower=\"{}\" upper=\"{}\" effort=\"0\" velocity=\"0\"/>",
+            -PRISMATIC_JOINT_PLACEHOLDER_LIMIT, PRISMATIC_JOINT_PLACEHOLDER_LIMIT
+        );
+    }
+    let _ = writeln!(urdf, "  </joint>");
+}
+
+/// Writes the URDF origin element of a visual or collision element.
+fn write_origin_element(urdf: &mut String, transform: &Isometry3<f64>) {
+    let translation = transform.translation.vector;
+    let (roll, pitch, yaw) = transform.rotation.euler_angles();
+    let _ = writeln!(
+        urdf,
+        "      <origin xyz=\"{} {} {}\" rpy=\"{} {} {}\"/>",
+        number(translation.x),
+        number(translation.y),
+        number(translation.z),
+        number(roll),
+        number(pitch),
+        number(yaw)
+    );
+}
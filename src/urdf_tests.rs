@@ -0,0 +1,253 @@
+use std::f64::consts::PI;
+
+use nalgebra::{Isometry3, Matrix3, Matrix6, Translation3, UnitQuaternion, Vector3};
+
+use crate::{
+    change_notification_processing::HardwareChangeProcessor,
+    model_elements::{
+        collision::{CollisionGeometry, CollisionShape},
+        frame_elements::{Actuator, FrameDofType, JointConstraint},
+        model::{ChassisElementPhysicalProperties, MotionModel},
+        visual::{Material, VisualGeometry},
+    },
+    number_space::NumberSpaceType,
+    test_support::MockActuator,
+};
+
+use super::{escape, LinkNames};
+
+fn physical_properties(mass: f64) -> ChassisElementPhysicalProperties {
+    #[rustfmt::skip]
+    let moment_of_inertia = Matrix3::new(
+        1.0, 0.1, 0.2,
+        0.1, 2.0, 0.3,
+        0.2, 0.3, 3.0);
+
+    ChassisElementPhysicalProperties::new(
+        mass,
+        Vector3::new(0.0, 0.0, 0.5),
+        moment_of_inertia,
+        Matrix6::<f64>::identity(),
+    )
+}
+
+// Creates a model with a body and two modules. Each module has a suspension, a steering element
+// and a wheel.
+fn create_model(change_processor: &HardwareChangeProcessor) -> MotionModel {
+    let mut model = MotionModel::new();
+    let body = model
+        .add_body(
+            "body".to_string(),
+            Translation3::identity(),
+            UnitQuaternion::identity(),
+            physical_properties(10.0),
+        )
+        .unwrap();
+
+    for y in [0.5, -0.5] {
+        let suspension = model
+            .add_suspension_element(
+                "suspension".to_string(),
+                FrameDofType::PrismaticX,
+                body,
+                Translation3::new(1.0, y, 0.0),
+                UnitQuaternion::from_euler_angles(0.0, 0.0, PI / 6.0),
+                physical_properties(1.0),
+                JointConstraint::new(),
+            )
+            .unwrap();
+
+        let mut steering_hardware = MockActuator::new(NumberSpaceType::LinearUnlimited);
+        let steering = model
+            .add_steering_element(
+                "steering".to_string(),
+                suspension,
+                Translation3::identity(),
+                UnitQuaternion::identity(),
+                physical_properties(1.0),
+                Actuator::new(&mut steering_hardware, change_processor).unwrap(),
+            )
+            .unwrap();
+
+        let mut wheel_hardware = MockActuator::new(NumberSpaceType::LinearUnlimited);
+        model
+            .add_wheel(
+                "wheel".to_string(),
+                steering,
+                Translation3::new(0.0, 0.0, -0.1),
+                UnitQuaternion::identity(),
+                physical_properties(1.0),
+                Actuator::new(&mut wheel_hardware, change_processor).unwrap(),
+            )
+            .unwrap();
+    }
+
+    model
+}
+
+#[test]
+fn when_exporting_an_empty_model_it_should_error() {
+    let model = MotionModel::new();
+
+    assert!(model.to_urdf().is_err());
+}
+
+#[test]
+fn when_exporting_a_model_it_should_write_a_robot_element() {
+    let change_processor = HardwareChangeProcessor::new(10);
+    let model = create_model(&change_processor);
+
+    let urdf = model.to_urdf().unwrap();
+
+    assert!(urdf.starts_with("<?xml version=\"1.0\"?>\n<robot name=\"body\">\n"));
+    assert!(urdf.ends_with("</robot>\n"));
+}
+
+#[test]
+fn when_a_generated_link_name_is_in_use_it_should_generate_another_name() {
+    let mut names = LinkNames::new();
+
+    assert_eq!("wheel", names.name_for("wheel"));
+    assert_eq!("wheel_1", names.name_for("wheel"));
+    assert_eq!("wheel_1_1", names.name_for("wheel_1"));
+
+    let mut names = LinkNames::new();
+
+    assert_eq!("wheel_1", names.name_for("wheel_1"));
+    assert_eq!("wheel", names.name_for("wheel"));
+    assert_eq!("wheel_2", names.name_for("wheel"));
+    assert_eq!("wheel_3", names.name_for("wheel"));
+    assert_eq!("link", names.name_for(""));
+}
+
+#[test]
+fn when_exporting_a_model_it_should_write_unique_links() {
+    let change_processor = HardwareChangeProcessor::new(10);
+    let model = create_model(&change_processor);
+
+    let urdf = model.to_urdf().unwrap();
+
+    for name in [
+        "body",
+        "suspension",
+        "suspension_1",
+        "steering",
+        "steering_1",
+        "wheel",
+        "wheel_1",
+        "wheel_motion",
+        "wheel_1_motion",
+    ] {
+        let link = format!("<link name=\"{}\"", name);
+        assert_eq!(1, urdf.matches(&link).count(), "Missing link {}", name);
+    }
+    assert_eq!(9, urdf.matches("<link ").count());
+}
+
+#[test]
+fn when_exporting_a_model_it_should_write_the_inertial_properties() {
+    let change_processor = HardwareChangeProcessor::new(10);
+    let model = create_model(&change_processor);
+
+    let urdf = model.to_urdf().unwrap();
+
+    assert!(urdf.contains("<mass value=\"10\"/>"));
+    assert!(urdf.contains("<origin xyz=\"0 0 0.5\" rpy=\"0 0 0\"/>"));
+    assert!(urdf
+        .contains("<inertia ixx=\"1\" ixy=\"0.1\" ixz=\"0.2\" iyy=\"2\" iyz=\"0.3\" izz=\"3\"/>"));
+}
+
+#[test]
+fn when_exporting_a_model_it_should_write_the_joints() {
+    let change_processor = HardwareChangeProcessor::new(10);
+    let model = create_model(&change_processor);
+
+    let urdf = model.to_urdf().unwrap();
+
+    // Suspension: prismatic joint along the x-axis of the body, expressed in the rotated
+    // suspension frame.
+    let angle = PI / 6.0;
+    let suspension_joint = format!(
+        "  <joint name=\"suspension_joint\" type=\"prismatic\">\n    <parent link=\"body\"/>\n    <child link=\"suspension\"/>\n    <origin xyz=\"1 0.5 0\" rpy=\"0 0 {}\"/>\n    <axis xyz=\"",
+        angle
+    );
+    let start = urdf.find(&suspension_joint).unwrap() + suspension_joint.len();
+    let axis: Vec<f64> = urdf[start..urdf[start..].find('"').unwrap() + start]
+        .split(' ')
+        .map(|v| v.parse().unwrap())
+        .collect();
+    assert!((axis[0] - angle.cos()).abs() < 1e-12);
+    assert!((axis[1] + angle.sin()).abs() < 1e-12);
+    assert!(axis[2].abs() < 1e-12);
+
+    assert_eq!(2, urdf.matches("type=\"prismatic\"").count());
+    assert_eq!(
+        2,
+        urdf.matches("<limit lower=\"-1000\" upper=\"1000\" effort=\"0\" velocity=\"0\"/>")
+            .count()
+    );
+
+    // Steering: revolute joint without an offset
+    assert!(urdf.contains(
+        "  <joint name=\"steering_joint\" type=\"continuous\">\n    <parent link=\"suspension\"/>\n    <child link=\"steering\"/>\n    <origin xyz=\"0 0 0\" rpy=\"0 0 0\"/>\n    <axis xyz=\"0 0 1\"/>\n"
+    ));
+
+    // Wheel: revolute joint with an offset
+    assert!(urdf.contains(
+        "  <joint name=\"wheel_joint\" type=\"continuous\">\n    <parent link=\"steering\"/>\n    <child link=\"wheel_motion\"/>\n    <origin xyz=\"0 0 0\" rpy=\"0 0 0\"/>\n    <axis xyz=\"0 1 0\"/>\n"
+    ));
+    assert!(urdf.contains(
+        "  <joint name=\"wheel_offset\" type=\"fixed\">\n    <parent link=\"wheel_motion\"/>\n    <child link=\"wheel\"/>\n    <origin xyz=\"0 0 -0.1\" rpy=\"0 0 0\"/>\n"
+    ));
+    assert_eq!(8, urdf.matches("<joint ").count());
+}
+
+#[test]
+fn when_exporting_a_model_it_should_write_the_visual_and_collision_geometry() {
+    let change_processor = HardwareChangeProcessor::new(10);
+    let mut model = create_model(&change_processor);
+    let body = *model.body().unwrap();
+
+    model
+        .add_visual_geometry(
+            &body,
+            VisualGeometry::new(
+                CollisionShape::Mesh {
+                    uri: "package://vehicle/body.dae".to_string(),
+                    scale: Vector3::new(1.0, 1.0, 2.0),
+                },
+                Isometry3::translation(0.0, 0.0, 0.25),
+                Some(Material::new("red & white".to_string(), [1.0, 0.0, 0.0, 1.0]).unwrap()),
+            )
+            .unwrap(),
+        )
+        .unwrap();
+    model
+        .add_collision_geometry(
+            &body,
+            CollisionGeometry::new(
+                CollisionShape::Box {
+                    size: Vector3::new(1.0, 0.5, 0.25),
+                },
+                Isometry3::identity(),
+            )
+            .unwrap(),
+        )
+        .unwrap();
+
+    let urdf = model.to_urdf().unwrap();
+
+    assert!(urdf.contains(
+        "    <visual>\n      <origin xyz=\"0 0 0.25\" rpy=\"0 0 0\"/>\n      <geometry>\n        <mesh filename=\"package://vehicle/body.dae\" scale=\"1 1 2\"/>\n      </geometry>\n      <material name=\"red &amp; white\">\n        <color rgba=\"1 0 0 1\"/>\n      </material>\n    </visual>\n"
+    ));
+    assert!(urdf.contains(
+        "    <collision>\n      <origin xyz=\"0 0 0\" rpy=\"0 0 0\"/>\n      <geometry>\n        <box size=\"1 0.5 0.25\"/>\n      </geometry>\n    </collision>\n  </link>\n"
+    ));
+    assert_eq!(1, urdf.matches("<visual>").count());
+    assert_eq!(1, urdf.matches("<collision>").count());
+}
+
+#[test]
+fn when_escaping_text_it_should_replace_special_characters() {
+    assert_eq!("a&amp;b&lt;c&gt;d&quot;e&apos;f", escape("a&b<c>d\"e'f"));
+}
@@ -0,0 +1,168 @@
+//! Verifies that the real-time transform queries do not allocate memory.
+//!
+//! The test binary uses an allocator that counts the allocations made by each thread, so the
+//! allocations made by the test harness on other threads do not affect the result.
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+    f64::consts::PI,
+    time::Duration,
+};
+
+use nalgebra::{Isometry3, Matrix3, Matrix4, Matrix6, Translation3, UnitQuaternion, Vector3};
+use swerve_vehicle_descriptors::{
+    change_notification_processing::HardwareChangeProcessor,
+    hardware::{
+        joint_state::{JointState, JointStateRange},
+        simulation::{SimulatedActuator, SimulatedDynamics},
+    },
+    kinematics::realtime::RealtimeTransforms,
+    model_elements::{
+        frame_elements::Actuator,
+        model::{ChassisElementPhysicalProperties, MotionModel},
+    },
+    number_space::NumberSpaceType,
+};
+
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|count| count.set(count.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.with(|count| count.set(count.get() + 1));
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn allocations() -> usize {
+    ALLOCATIONS.with(|count| count.get())
+}
+
+fn physical_properties() -> ChassisElementPhysicalProperties {
+    ChassisElementPhysicalProperties::new(
+        1.0,
+        Vector3::<f64>::zeros(),
+        Matrix3::<f64>::identity(),
+        Matrix6::<f64>::identity(),
+    )
+}
+
+fn simulated_actuator(motion_type: NumberSpaceType) -> SimulatedActuator {
+    SimulatedActuator::new(
+        motion_type,
+        JointStateRange::new(
+            JointState::new(-100.0, None, None, None),
+            JointState::new(100.0, None, None, None),
+        ),
+        SimulatedDynamics::new(),
+    )
+}
+
+#[test]
+fn when_querying_transforms_in_a_control_loop_it_should_not_allocate() {
+    let processor = HardwareChangeProcessor::manual();
+    let mut model = MotionModel::new();
+    let body = model
+        .add_body(
+            "body".to_string(),
+            Translation3::identity(),
+            UnitQuaternion::identity(),
+            physical_properties(),
+        )
+        .unwrap();
+
+    let mut hardware = Vec::new();
+    let mut wheels = Vec::new();
+    for (x, y) in [(1.0, 0.5), (-1.0, 0.5), (-1.0, -0.5), (1.0, -0.5)] {
+        let mut steering_hardware = simulated_actuator(NumberSpaceType::AngularLimited {
+            start_angle_in_radians: 0.0,
+        });
+        let steering = model
+            .add_steering_element(
+                "steering".to_string(),
+                body,
+                Translation3::new(x, y, 0.0),
+                UnitQuaternion::identity(),
+                physical_properties(),
+                Actuator::new(&mut steering_hardware, &processor).unwrap(),
+            )
+            .unwrap();
+
+        let mut wheel_hardware = simulated_actuator(NumberSpaceType::LinearUnlimited);
+        let wheel = model
+            .add_wheel(
+                "wheel".to_string(),
+                steering,
+                Translation3::new(0.0, 0.0, -0.1),
+                UnitQuaternion::identity(),
+                physical_properties(),
+                Actuator::new(&mut wheel_hardware, &processor).unwrap(),
+            )
+            .unwrap();
+
+        steering_hardware.set_state(JointState::new(0.25 * PI, Some(0.0), None, None));
+        hardware.push(steering_hardware);
+        hardware.push(wheel_hardware);
+        wheels.push(wheel);
+    }
+
+    for actuator in hardware.iter() {
+        actuator.step(Duration::from_millis(10));
+    }
+    processor.process_pending();
+
+    let mut transforms = RealtimeTransforms::new(&model).unwrap();
+    let mut matrix = Matrix4::zeros();
+    let mut isometry = Isometry3::identity();
+
+    let before = allocations();
+    for _ in 0..1000 {
+        assert_eq!(0, transforms.update(&model).unwrap());
+        for wheel in wheels.iter() {
+            transforms
+                .homogeneous_transform_to_body_into(wheel, &mut matrix)
+                .unwrap();
+            transforms
+                .homogeneous_transform_to_world_into(wheel, &mut matrix)
+                .unwrap();
+            transforms
+                .homogeneous_transform_between_frames_into(wheel, &wheels[0], &mut matrix)
+                .unwrap();
+            transforms
+                .isometry_to_body_into(wheel, &mut isometry)
+                .unwrap();
+            transforms
+                .isometry_to_world_into(wheel, &mut isometry)
+                .unwrap();
+            transforms
+                .isometry_between_frames_into(wheel, &wheels[0], &mut isometry)
+                .unwrap();
+            assert!(transforms.joint_position(wheel).is_some());
+        }
+    }
+    let after = allocations();
+
+    assert_eq!(0, after - before);
+
+    let expected = model.homogeneous_transform_to_body(&wheels[2]).unwrap();
+    transforms
+        .homogeneous_transform_to_body_into(&wheels[2], &mut matrix)
+        .unwrap();
+    assert!((expected - matrix).norm() < 1e-12);
+}